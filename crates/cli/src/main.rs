@@ -0,0 +1,393 @@
+//! `platerator`: generate actuator plates without running the web server.
+//!
+//! Parameters come from the defaults, then an optional TOML file (`--config`), then
+//! individual flags, each overriding the last. The TOML keys are the same as the
+//! JSON API's:
+//!
+//! ```toml
+//! bolt_spacing = 80
+//! bolt_size = "M8"
+//! material = "stainless_steel"
+//! plate_thickness = "0.375in"
+//! ```
+//!
+//! Lengths are millimeters, or strings with a unit (`"2.5in"`, `"63.5mm"`, `"1500um"`).
+//!
+//! ```text
+//! platerator --config plate.toml --pin-count 4 --out build/ --name bracket-a
+//! ```
+//!
+//! `--name` may use placeholders filled in from the plate, such as `{hash}`,
+//! `{bolt_spacing}`, and `{date}`; see `parametric::output` for the full list:
+//!
+//! ```text
+//! platerator --bolt-spacing 80 --name 'bracket-{bolt_spacing}-{date}'
+//! ```
+//!
+//! `--sweep FIELD=START..=END:STEP` (repeatable) generates every combination of the
+//! given ranges around those parameters instead, skipping invalid ones, and writes
+//! `plate-NNNN.*` files plus a `manifest.json` describing them:
+//!
+//! ```text
+//! platerator --sweep bolt_spacing=40..=80:10 --sweep pin_count=2..=6:2 --out sweep/
+//! ```
+//!
+//! Exit status: 0 on success, 1 for invalid parameters, 2 for usage errors, 3 when
+//! the engine fails.
+
+use clap::Parser;
+use domain::ActuatorPlate;
+use parametric::output::OutputConfig;
+use parametric::sweep::{Sweep, SweepRange};
+use parametric::{AllErrors, GeometryBackend, ZooCli};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser, Debug, Default)]
+#[command(name = "platerator", version, about = "Generate actuator plate models headlessly")]
+struct Args {
+    /// TOML file with plate parameters; flags override its values
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Directory to write model and drawing files into (created if missing)
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    out: PathBuf,
+
+    /// Base name for the output files, e.g. plate-{hash} or {date}-{bolt_spacing}
+    #[arg(long, default_value = "plate")]
+    name: String,
+
+    /// Only validate the parameters; don't generate anything
+    #[arg(long)]
+    validate_only: bool,
+
+    /// Vary a field over a range, e.g. bolt_spacing=40..=80:10 (mm); repeat to
+    /// generate every combination
+    #[arg(long, value_name = "FIELD=START..=END:STEP")]
+    sweep: Vec<SweepRange>,
+
+    /// Distance between mounting bolt centers, e.g. 60 (mm) or 2.5in
+    #[arg(long, value_name = "LENGTH")]
+    bolt_spacing: Option<String>,
+    /// ISO metric bolt size, e.g. M8
+    #[arg(long)]
+    bolt_size: Option<String>,
+    /// Bracket height, e.g. 60 (mm) or 2.5in
+    #[arg(long, value_name = "LENGTH")]
+    bracket_height: Option<String>,
+    /// Bracket width, e.g. 60 (mm) or 2.5in
+    #[arg(long, value_name = "LENGTH")]
+    bracket_width: Option<String>,
+    /// aluminum, stainless_steel, carbon_steel, or brass
+    #[arg(long)]
+    material: Option<String>,
+    /// Actuator pin diameter, e.g. 60 (mm) or 2.5in
+    #[arg(long, value_name = "LENGTH")]
+    pin_diameter: Option<String>,
+    /// Number of actuator pins (1–12)
+    #[arg(long)]
+    pin_count: Option<u16>,
+    /// Plate thickness, e.g. 60 (mm) or 2.5in
+    #[arg(long, value_name = "LENGTH")]
+    plate_thickness: Option<String>,
+    /// Expected force per pin (N)
+    #[arg(long)]
+    expected_force_per_pin: Option<u32>,
+}
+
+/// Why a run failed, and the exit status it maps to.
+#[derive(Debug)]
+enum CliError {
+    Usage(String),
+    Invalid(Vec<validation::PlateValidationError>),
+    Engine(String),
+}
+
+impl CliError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Invalid(_) => 1,
+            CliError::Usage(_) => 2,
+            CliError::Engine(_) => 3,
+        }
+    }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Usage(msg) => write!(f, "error: {}", msg),
+            CliError::Invalid(errors) => {
+                write!(f, "invalid plate parameters:")?;
+                for e in errors {
+                    write!(f, "\n  - {} ({})", e, e.related_fields().join(", "))?;
+                }
+                Ok(())
+            }
+            CliError::Engine(msg) => write!(f, "generation failed: {}", msg),
+        }
+    }
+}
+
+/// Layer the config file and flags over the default plate.
+fn build_plate(args: &Args) -> Result<ActuatorPlate, CliError> {
+    let mut table = toml::Table::try_from(ActuatorPlate::default())
+        .map_err(|e| CliError::Usage(e.to_string()))?;
+
+    if let Some(path) = &args.config {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| CliError::Usage(format!("can't read {}: {}", path.display(), e)))?;
+        let file: toml::Table = contents
+            .parse()
+            .map_err(|e| CliError::Usage(format!("{}: {}", path.display(), e)))?;
+        for (key, value) in file {
+            if !table.contains_key(&key) {
+                return Err(CliError::Usage(format!("{}: unknown parameter `{}`", path.display(), key)));
+            }
+            table.insert(key, value);
+        }
+    }
+
+    let mut set = |key: &str, value: Option<toml::Value>| {
+        if let Some(value) = value {
+            table.insert(key.to_string(), value);
+        }
+    };
+    let int = |v: Option<u16>| v.map(|v| toml::Value::Integer(v.into()));
+    let length = |v: &Option<String>| v.as_ref().map(|s| s.clone().into());
+    set("bolt_spacing", length(&args.bolt_spacing));
+    set("bolt_size", args.bolt_size.as_ref().map(|s| s.to_uppercase().into()));
+    set("bracket_height", length(&args.bracket_height));
+    set("bracket_width", length(&args.bracket_width));
+    set("material", args.material.as_ref().map(|s| s.to_lowercase().into()));
+    set("pin_diameter", length(&args.pin_diameter));
+    set("pin_count", int(args.pin_count));
+    set("plate_thickness", length(&args.plate_thickness));
+    set(
+        "expected_force_per_pin",
+        args.expected_force_per_pin.map(|v| toml::Value::Integer(v.into())),
+    );
+
+    table.try_into().map_err(|e: toml::de::Error| CliError::Usage(e.message().to_string()))
+}
+
+/// Validate, generate, and copy the model and drawing files into `args.out`.
+fn run(args: &Args, engine: &dyn GeometryBackend) -> Result<Vec<PathBuf>, CliError> {
+    let plate = build_plate(args)?;
+    if !args.sweep.is_empty() {
+        return run_sweep(args, plate, engine);
+    }
+    validation::validate(&plate).map_err(CliError::Invalid)?;
+    if args.validate_only {
+        return Ok(Vec::new());
+    }
+    let output = OutputConfig {
+        directory: args.out.clone(),
+        name: args.name.clone(),
+    };
+    output
+        .validate()
+        .map_err(|e| CliError::Usage(format!("--name: {}", e)))?;
+
+    let result = parametric::generate_model_with(&plate, engine).map_err(|e| match e {
+        AllErrors::ValidationErrors(errors) => CliError::Invalid(errors),
+        AllErrors::GeneratorError(msg) => CliError::Engine(msg),
+    })?;
+
+    output.save(&plate, &result).map_err(CliError::Engine)
+}
+
+/// Generate every valid combination of `args.sweep` into `args.out`, returning
+/// the manifest's path.
+fn run_sweep(args: &Args, base: ActuatorPlate, engine: &dyn GeometryBackend) -> Result<Vec<PathBuf>, CliError> {
+    let sweep = Sweep {
+        base,
+        ranges: args.sweep.clone(),
+    };
+    let limits = validation::ValidationLimits::DEFAULT;
+    let points = sweep.points(&limits).map_err(|e| CliError::Usage(e.to_string()))?;
+    if points.iter().all(|point| !point.errors.is_empty()) {
+        let first = points.into_iter().next().map(|point| point.errors).unwrap_or_default();
+        return Err(CliError::Invalid(first));
+    }
+    if args.validate_only {
+        return Ok(Vec::new());
+    }
+
+    let manifest = parametric::sweep::run(&sweep, engine, &limits, &args.out).map_err(|e| CliError::Engine(e.to_string()))?;
+    eprintln!(
+        "{} of {} combinations valid, {} failed to generate",
+        manifest.plates.len(),
+        manifest.total,
+        manifest.failed()
+    );
+    if let Some(failed) = manifest.plates.iter().find(|entry| entry.error.is_some()) {
+        return Err(CliError::Engine(format!(
+            "plate-{:04}: {}",
+            failed.index,
+            failed.error.as_deref().unwrap_or_default()
+        )));
+    }
+    Ok(vec![args.out.join("manifest.json")])
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args, &ZooCli::from_env()) {
+        Ok(written) if written.is_empty() => {
+            println!("Plate parameters are valid");
+            ExitCode::SUCCESS
+        }
+        Ok(written) => {
+            for path in written {
+                println!("{}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::{BoltSize, Length, Material};
+    use std::path::Path;
+
+    fn write_config(dir: &Path, contents: &str) -> PathBuf {
+        let path = dir.join("plate.toml");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_flags_override_config_file_over_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = Args {
+            config: Some(write_config(
+                dir.path(),
+                "bolt_spacing = 80\nbolt_size = \"M8\"\nmaterial = \"brass\"\n",
+            )),
+            material: Some("Stainless_Steel".to_string()),
+            pin_count: Some(4),
+            plate_thickness: Some("0.375in".to_string()),
+            ..Default::default()
+        };
+
+        let plate = build_plate(&args).unwrap();
+        assert_eq!(plate.bolt_spacing, Length::from_mm(80));
+        assert_eq!(plate.plate_thickness, Length::from_micrometers(9_525));
+        assert_eq!(plate.bolt_size, BoltSize::M8);
+        assert_eq!(plate.material, Material::StainlessSteel);
+        assert_eq!(plate.pin_count.get(), 4);
+        assert_eq!(plate.bracket_width, ActuatorPlate::default().bracket_width);
+    }
+
+    #[test]
+    fn test_unknown_config_key_is_a_usage_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = Args {
+            config: Some(write_config(dir.path(), "bolt_spacin = 80\n")),
+            ..Default::default()
+        };
+
+        let err = build_plate(&args).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("unknown parameter `bolt_spacin`"));
+    }
+
+    #[test]
+    fn test_invalid_plate_is_reported_with_fields() {
+        let args = Args {
+            pin_count: Some(13),
+            validate_only: true,
+            ..Default::default()
+        };
+
+        let err = run(&args, &ZooCli::default()).unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(err.to_string().contains("pinCount"));
+
+        // Zero pins can't be read into a plate at all
+        let args = Args { pin_count: Some(0), ..args };
+        let err = run(&args, &ZooCli::default()).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("at least 1"));
+    }
+
+    #[test]
+    fn test_sweep_writes_manifest_for_valid_combinations() {
+        let engine = parametric::mock::MockBackend::new();
+        let out = tempfile::tempdir().unwrap();
+        let args = Args {
+            out: out.path().to_path_buf(),
+            sweep: vec![
+                "bolt_spacing=40..=60:10".parse().unwrap(),
+                "pin_count=12..=13:1".parse().unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        let written = run(&args, &engine).unwrap();
+        assert_eq!(written, [out.path().join("manifest.json")]);
+        // 13 pins is over the limit, so half of the six combinations are skipped
+        assert_eq!(engine.generated(), 3);
+        assert!(out.path().join("plate-0004.step").exists());
+        assert!(!out.path().join("plate-0005.step").exists());
+
+        let args = Args {
+            sweep: vec!["pin_count=13..=14:1".parse().unwrap()],
+            ..args
+        };
+        assert_eq!(run(&args, &engine).unwrap_err().exit_code(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_generates_files_into_output_dir() {
+        use parametric::mock::{MockBehavior, MockEngine};
+
+        let engine = MockEngine::new(MockBehavior::Succeed).unwrap();
+        let out = tempfile::tempdir().unwrap();
+        let args = Args {
+            out: out.path().join("models"),
+            name: "bracket-a".to_string(),
+            ..Default::default()
+        };
+
+        let written = run(&args, &engine.cli()).unwrap();
+        assert_eq!(written.len(), 9);
+        assert!(out.path().join("models/bracket-a.pdf").exists());
+        let step = std::fs::read_to_string(out.path().join("models/bracket-a.step")).unwrap();
+        assert!(step.contains("FILE_NAME('mock.step','"), "{}", step);
+    }
+
+    #[test]
+    fn test_name_placeholders_come_from_the_plate() {
+        let out = tempfile::tempdir().unwrap();
+        let args = Args {
+            out: out.path().to_path_buf(),
+            name: "bracket-{bolt_spacing}-{bolt_size}".to_string(),
+            bolt_spacing: Some("80".to_string()),
+            ..Default::default()
+        };
+
+        let written = run(&args, &parametric::mock::MockBackend::new()).unwrap();
+        assert_eq!(written[0], out.path().join("bracket-80-M10.step"));
+
+        // A bad template is a usage error before anything is generated
+        let engine = parametric::mock::MockBackend::new();
+        let args = Args {
+            name: "bracket-{spacing}".to_string(),
+            ..args
+        };
+        let err = run(&args, &engine).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("unknown placeholder {spacing}"), "{}", err);
+        assert_eq!(engine.generated(), 0);
+    }
+}
@@ -0,0 +1,225 @@
+//! ISO metric bolt sizes and the holes machined for them.
+//!
+//! Hole sizes come from here rather than from the user: the clearance hole the
+//! plate is drilled to, and the counterbore or countersink a plate gets when it
+//! asks for one without giving sizes.
+
+use serde::{Deserialize, Serialize};
+
+/// How much room a clearance hole leaves around the bolt, as the fine, medium,
+/// and coarse series of ISO 273.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ClearanceFit {
+    /// Fine series: locates the part, but the bolt pattern must be accurate
+    Close,
+    /// Medium series, the usual choice
+    #[default]
+    Normal,
+    /// Coarse series: forgiving of a bolt pattern that is off
+    Loose,
+}
+
+/// Standard ISO metric bolt sizes with their hole and head dimensions.
+///
+/// Each variant represents a standard metric bolt size (e.g., M3 = 3mm nominal diameter).
+/// The clearance hole diameter is sized to allow the bolt to pass through freely.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "UPPERCASE")]
+pub enum BoltSize {
+    /// M3 bolt (3mm nominal, 3.4mm clearance hole)
+    M3,
+    /// M4 bolt (4mm nominal, 4.5mm clearance hole)
+    M4,
+    /// M5 bolt (5mm nominal, 5.5mm clearance hole)
+    M5,
+    /// M6 bolt (6mm nominal, 6.6mm clearance hole)
+    M6,
+    /// M8 bolt (8mm nominal, 9.0mm clearance hole)
+    M8,
+    /// M10 bolt (10mm nominal, 11.0mm clearance hole)
+    M10,
+    /// M12 bolt (12mm nominal, 13.5mm clearance hole)
+    M12,
+}
+
+impl BoltSize {
+    pub const ALL: [BoltSize; 7] = [
+        BoltSize::M3,
+        BoltSize::M4,
+        BoltSize::M5,
+        BoltSize::M6,
+        BoltSize::M8,
+        BoltSize::M10,
+        BoltSize::M12,
+    ];
+
+    /// The name used in JSON, e.g. `M10`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            BoltSize::M3 => "M3",
+            BoltSize::M4 => "M4",
+            BoltSize::M5 => "M5",
+            BoltSize::M6 => "M6",
+            BoltSize::M8 => "M8",
+            BoltSize::M10 => "M10",
+            BoltSize::M12 => "M12",
+        }
+    }
+
+    /// The size named `name`, ignoring case and surrounding space, e.g. `m10`.
+    pub fn from_name(name: &str) -> Option<BoltSize> {
+        let name = name.trim();
+        BoltSize::ALL.into_iter().find(|size| size.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the nominal diameter of the bolt in millimeters.
+    pub const fn nominal_diameter_mm(self) -> u16 {
+        match self {
+            BoltSize::M3 => 3,
+            BoltSize::M4 => 4,
+            BoltSize::M5 => 5,
+            BoltSize::M6 => 6,
+            BoltSize::M8 => 8,
+            BoltSize::M10 => 10,
+            BoltSize::M12 => 12,
+        }
+    }
+
+    /// Returns the clearance hole diameter in millimeters, at the
+    /// [`ClearanceFit::Normal`] fit plates are drilled to.
+    ///
+    /// Clearance holes are sized to allow the bolt to pass through freely
+    /// without binding.
+    pub const fn clearance_hole_diameter_mm(self) -> f32 {
+        self.clearance_diameter_mm(ClearanceFit::Normal)
+    }
+
+    /// Clearance hole diameter in millimeters for a fit (ISO 273).
+    pub const fn clearance_diameter_mm(self, fit: ClearanceFit) -> f32 {
+        match (self, fit) {
+            (BoltSize::M3, ClearanceFit::Close) => 3.2,
+            (BoltSize::M3, ClearanceFit::Normal) => 3.4,
+            (BoltSize::M3, ClearanceFit::Loose) => 3.6,
+            (BoltSize::M4, ClearanceFit::Close) => 4.3,
+            (BoltSize::M4, ClearanceFit::Normal) => 4.5,
+            (BoltSize::M4, ClearanceFit::Loose) => 4.8,
+            (BoltSize::M5, ClearanceFit::Close) => 5.3,
+            (BoltSize::M5, ClearanceFit::Normal) => 5.5,
+            (BoltSize::M5, ClearanceFit::Loose) => 5.8,
+            (BoltSize::M6, ClearanceFit::Close) => 6.4,
+            (BoltSize::M6, ClearanceFit::Normal) => 6.6,
+            (BoltSize::M6, ClearanceFit::Loose) => 7.0,
+            (BoltSize::M8, ClearanceFit::Close) => 8.4,
+            (BoltSize::M8, ClearanceFit::Normal) => 9.0,
+            (BoltSize::M8, ClearanceFit::Loose) => 10.0,
+            (BoltSize::M10, ClearanceFit::Close) => 10.5,
+            (BoltSize::M10, ClearanceFit::Normal) => 11.0,
+            (BoltSize::M10, ClearanceFit::Loose) => 12.0,
+            (BoltSize::M12, ClearanceFit::Close) => 13.0,
+            (BoltSize::M12, ClearanceFit::Normal) => 13.5,
+            (BoltSize::M12, ClearanceFit::Loose) => 14.5,
+        }
+    }
+
+    /// Tensile stress area of the coarse thread in mm² (ISO 898-1), the section that
+    /// carries load where the threads cross a shear plane.
+    pub const fn stress_area_mm2(self) -> f32 {
+        match self {
+            BoltSize::M3 => 5.03,
+            BoltSize::M4 => 8.78,
+            BoltSize::M5 => 14.2,
+            BoltSize::M6 => 20.1,
+            BoltSize::M8 => 36.6,
+            BoltSize::M10 => 58.0,
+            BoltSize::M12 => 84.3,
+        }
+    }
+
+    /// ISO 261 thread pitches for this size in micrometers, coarse first.
+    pub const fn standard_pitches_um(self) -> &'static [u16] {
+        match self {
+            BoltSize::M3 => &[500, 350],
+            BoltSize::M4 => &[700, 500],
+            BoltSize::M5 => &[800, 500],
+            BoltSize::M6 => &[1000, 750],
+            BoltSize::M8 => &[1250, 1000, 750],
+            BoltSize::M10 => &[1500, 1250, 1000, 750],
+            BoltSize::M12 => &[1750, 1500, 1250, 1000],
+        }
+    }
+
+    /// Head diameter of an ISO 4762 socket head cap screw, in millimeters; the
+    /// smallest counterbore the head fits in.
+    pub const fn socket_head_diameter_mm(self) -> f32 {
+        match self {
+            BoltSize::M3 => 5.5,
+            BoltSize::M4 => 7.0,
+            BoltSize::M5 => 8.5,
+            BoltSize::M6 => 10.0,
+            BoltSize::M8 => 13.0,
+            BoltSize::M10 => 16.0,
+            BoltSize::M12 => 18.0,
+        }
+    }
+
+    /// Head height of an ISO 4762 socket head cap screw in millimeters: one
+    /// nominal diameter.
+    pub const fn socket_head_height_mm(self) -> f32 {
+        self.nominal_diameter_mm() as f32
+    }
+
+    /// Counterbore diameter for an ISO 4762 socket head, in millimeters (DIN 974-1).
+    pub const fn counterbore_diameter_mm(self) -> f32 {
+        match self {
+            BoltSize::M3 => 6.5,
+            BoltSize::M4 => 8.0,
+            BoltSize::M5 => 10.0,
+            BoltSize::M6 => 11.0,
+            BoltSize::M8 => 15.0,
+            BoltSize::M10 => 18.0,
+            BoltSize::M12 => 20.0,
+        }
+    }
+
+    /// Counterbore depth in millimeters that sinks an ISO 4762 head just below the
+    /// face (DIN 974-1).
+    pub const fn counterbore_depth_mm(self) -> f32 {
+        match self {
+            BoltSize::M3 => 3.4,
+            BoltSize::M4 => 4.4,
+            BoltSize::M5 => 5.4,
+            BoltSize::M6 => 6.4,
+            BoltSize::M8 => 8.6,
+            BoltSize::M10 => 10.6,
+            BoltSize::M12 => 12.6,
+        }
+    }
+
+    /// Head diameter of an ISO 10642 countersunk screw, in millimeters; the
+    /// smallest countersink the head sits flush in.
+    pub const fn countersunk_head_diameter_mm(self) -> f32 {
+        match self {
+            BoltSize::M3 => 6.72,
+            BoltSize::M4 => 8.96,
+            BoltSize::M5 => 11.2,
+            BoltSize::M6 => 13.44,
+            BoltSize::M8 => 17.92,
+            BoltSize::M10 => 22.4,
+            BoltSize::M12 => 26.88,
+        }
+    }
+    /// Countersink diameter in millimeters that seats an ISO 10642 head flush,
+    /// a little wider than the head.
+    pub const fn countersink_diameter_mm(self) -> f32 {
+        match self {
+            BoltSize::M3 => 7.0,
+            BoltSize::M4 => 9.2,
+            BoltSize::M5 => 11.5,
+            BoltSize::M6 => 13.7,
+            BoltSize::M8 => 18.2,
+            BoltSize::M10 => 22.7,
+            BoltSize::M12 => 27.2,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Counts of things: pins on a plate, plates in an order.
+
+use core::fmt;
+use core::num::NonZeroU32;
+use serde::{Deserialize, Serialize};
+
+/// A count of at least one.
+///
+/// Serializes as a plain integer. Zero, negative, and fractional numbers, and
+/// anything above `u32::MAX`, fail to deserialize, so code holding a `Count`
+/// never has to check for them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Count(NonZeroU32);
+
+/// Errors making a [`Count`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CountError {
+    /// Zero or negative.
+    Zero,
+    /// More than `u32::MAX`.
+    Overflow,
+}
+
+impl fmt::Display for CountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CountError::Zero => write!(f, "count must be at least 1"),
+            CountError::Overflow => write!(f, "count must not exceed {}", u32::MAX),
+        }
+    }
+}
+
+impl Count {
+    pub const ONE: Count = Count(NonZeroU32::MIN);
+
+    pub const fn new(n: u32) -> Result<Self, CountError> {
+        match NonZeroU32::new(n) {
+            Some(n) => Ok(Count(n)),
+            None => Err(CountError::Zero),
+        }
+    }
+
+    /// For constants, e.g. presets. Panics on zero, which fails the build in a
+    /// const context.
+    pub const fn of(n: u32) -> Self {
+        match Count::new(n) {
+            Ok(count) => count,
+            Err(_) => panic!("count must be at least 1"),
+        }
+    }
+
+    pub const fn get(self) -> u32 {
+        self.0.get()
+    }
+
+    /// `None` if the sum exceeds `u32::MAX`.
+    pub const fn checked_add(self, other: Count) -> Option<Count> {
+        match self.0.checked_add(other.get()) {
+            Some(n) => Some(Count(n)),
+            None => None,
+        }
+    }
+
+    /// `None` if the product exceeds `u32::MAX`.
+    pub const fn checked_mul(self, other: Count) -> Option<Count> {
+        match self.0.checked_mul(other.0) {
+            Some(n) => Some(Count(n)),
+            None => None,
+        }
+    }
+
+    /// `None` unless the difference is at least one.
+    pub const fn checked_sub(self, other: Count) -> Option<Count> {
+        match self.get().checked_sub(other.get()) {
+            Some(n) => match Count::new(n) {
+                Ok(count) => Some(count),
+                Err(_) => None,
+            },
+            None => None,
+        }
+    }
+
+    pub const fn saturating_add(self, other: Count) -> Count {
+        Count(self.0.saturating_add(other.get()))
+    }
+
+    pub const fn saturating_mul(self, other: Count) -> Count {
+        Count(self.0.saturating_mul(other.0))
+    }
+
+    /// `self` times a per-item amount, e.g. force per pin times the pin count.
+    /// Widened to `u64`, so it can't overflow for `u32` amounts.
+    pub const fn times(self, each: u32) -> u64 {
+        self.get() as u64 * each as u64
+    }
+}
+
+impl TryFrom<u32> for Count {
+    type Error = CountError;
+
+    fn try_from(n: u32) -> Result<Self, Self::Error> {
+        Count::new(n)
+    }
+}
+
+impl TryFrom<u64> for Count {
+    type Error = CountError;
+
+    fn try_from(n: u64) -> Result<Self, Self::Error> {
+        Count::new(u32::try_from(n).map_err(|_| CountError::Overflow)?)
+    }
+}
+
+impl From<Count> for u32 {
+    fn from(count: Count) -> u32 {
+        count.get()
+    }
+}
+
+impl From<Count> for u64 {
+    fn from(count: Count) -> u64 {
+        count.get() as u64
+    }
+}
+
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for Count {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.get())
+    }
+}
+
+impl<'de> Deserialize<'de> for Count {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CountVisitor;
+
+        impl serde::de::Visitor<'_> for CountVisitor {
+            type Value = Count;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a whole number of at least 1")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, n: u64) -> Result<Count, E> {
+                Count::try_from(n).map_err(E::custom)
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, n: i64) -> Result<Count, E> {
+                let n = u64::try_from(n).map_err(|_| E::custom(CountError::Zero))?;
+                self.visit_u64(n)
+            }
+        }
+
+        deserializer.deserialize_u64(CountVisitor)
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for Count {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::{KnownFormat, ObjectBuilder, SchemaFormat, Type};
+        ObjectBuilder::new()
+            .schema_type(Type::Integer)
+            .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int64)))
+            .minimum(Some(1))
+            .description(Some("A whole number of at least 1"))
+            .into()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::ToSchema for Count {}
@@ -0,0 +1,141 @@
+//! Field-by-field comparison of two plates.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::Serialize;
+
+use crate::{ActuatorPlate, Dimension, HoleSpec, Length, PinFit, PlateFamily, Tolerance};
+
+/// The fields that differ between two plates, in [`ActuatorPlate`] field order.
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PlateDiff {
+    pub changes: Vec<FieldChange>,
+}
+
+/// One field that differs between two plates.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FieldChange {
+    /// Plate field, e.g. `bolt_spacing` or `tolerances.pin_diameter`
+    #[cfg_attr(feature = "openapi", schema(example = "bolt_spacing"))]
+    pub field: String,
+    /// Value on the first plate, as it would be written in a request
+    #[cfg_attr(feature = "openapi", schema(example = "60"))]
+    pub before: String,
+    /// Value on the second plate
+    #[cfg_attr(feature = "openapi", schema(example = "72.5"))]
+    pub after: String,
+    /// `after - before` in millimeters, for lengths only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = 12.5))]
+    pub delta_mm: Option<f64>,
+}
+
+impl PlateDiff {
+    /// True when the plates are identical.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The change to `field`, if it differs.
+    pub fn get(&self, field: &str) -> Option<&FieldChange> {
+        self.changes.iter().find(|change| change.field == field)
+    }
+
+    fn push(&mut self, field: &str, before: String, after: String, delta_mm: Option<f64>) {
+        if before != after {
+            self.changes.push(FieldChange {
+                field: field.to_string(),
+                before,
+                after,
+                delta_mm,
+            });
+        }
+    }
+
+    fn push_length(&mut self, field: &str, before: Length, after: Length) {
+        let delta_um = after.micrometers() as i64 - before.micrometers() as i64;
+        self.push(
+            field,
+            before.to_string(),
+            after.to_string(),
+            Some(delta_um as f64 / 1000.0),
+        );
+    }
+}
+
+impl ActuatorPlate {
+    /// What changes going from `self` to `other`.
+    pub fn diff(&self, other: &ActuatorPlate) -> PlateDiff {
+        let mut diff = PlateDiff::default();
+        diff.push_length("bolt_spacing", self.bolt_spacing, other.bolt_spacing);
+        diff.push(
+            "bolt_size",
+            format!("M{}", self.bolt_size.nominal_diameter_mm()),
+            format!("M{}", other.bolt_size.nominal_diameter_mm()),
+            None,
+        );
+        diff.push_length("bracket_height", self.bracket_height, other.bracket_height);
+        diff.push_length("bracket_width", self.bracket_width, other.bracket_width);
+        diff.push(
+            "material",
+            self.material.name().to_string(),
+            other.material.name().to_string(),
+            None,
+        );
+        diff.push_length("pin_diameter", self.pin_diameter, other.pin_diameter);
+        diff.push("pin_count", self.pin_count.to_string(), other.pin_count.to_string(), None);
+        diff.push_length("plate_thickness", self.plate_thickness, other.plate_thickness);
+        diff.push(
+            "expected_force_per_pin",
+            self.expected_force_per_pin.0.to_string(),
+            other.expected_force_per_pin.0.to_string(),
+            None,
+        );
+        diff.push("bolt_holes", describe_holes(&self.bolt_holes), describe_holes(&other.bolt_holes), None);
+        for dimension in Dimension::ALL {
+            diff.push(
+                &format!("tolerances.{}", dimension.field_name()),
+                describe_tolerance(self.tolerances.get(dimension)),
+                describe_tolerance(other.tolerances.get(dimension)),
+                None,
+            );
+        }
+        diff.push("family", describe_family(&self.family), describe_family(&other.family), None);
+        diff.push("pin_fit", describe_pin_fit(self.pin_fit), describe_pin_fit(other.pin_fit), None);
+        diff
+    }
+}
+
+fn describe_holes(holes: &HoleSpec) -> String {
+    match holes {
+        HoleSpec::Through => "through".to_string(),
+        HoleSpec::Counterbore { diameter, depth } => format!("counterbore \u{2300}{} \u{D7} {}", diameter, depth),
+        HoleSpec::Countersink { diameter } => format!("countersink \u{2300}{}", diameter),
+        HoleSpec::Tapped { thread } => format!("tapped {}", thread),
+    }
+}
+
+fn describe_family(family: &PlateFamily) -> String {
+    match family {
+        PlateFamily::Standard => "standard".to_string(),
+        PlateFamily::Slotted { slot_length } => format!("slotted {}", slot_length),
+        PlateFamily::DualPin { pin_spacing } => format!("dual pin {} apart", pin_spacing),
+    }
+}
+
+fn describe_tolerance(tolerance: Option<Tolerance>) -> String {
+    match tolerance {
+        Some(tolerance) => tolerance.to_string(),
+        None => "general".to_string(),
+    }
+}
+
+fn describe_pin_fit(fit: Option<PinFit>) -> String {
+    match fit {
+        Some(fit) => fit.name().to_string(),
+        None => "none".to_string(),
+    }
+}
@@ -0,0 +1,41 @@
+//! Plate families: variations on the standard plate's hole layout.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Length;
+
+/// Which plate layout to build. Each family has its own KCL template.
+///
+/// Serializes with a `type` tag, e.g. `{"type": "standard"}` or
+/// `{"type": "slotted", "slot_length": 12}`. Plates that don't say are standard.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlateFamily {
+    /// Round bolt holes in the corners and pins on a circle in the center.
+    #[default]
+    Standard,
+    /// Bolt holes elongated across the width, so the plate can be adjusted
+    /// sideways before the bolts are tightened.
+    Slotted {
+        /// Travel along each slot: the distance between the centers of its two
+        /// rounded ends
+        slot_length: Length,
+    },
+    /// Exactly two pins on the horizontal centerline, for a clevis or yoke.
+    DualPin {
+        /// Distance between the two pin centers
+        pin_spacing: Length,
+    },
+}
+
+impl PlateFamily {
+    /// Stable snake_case name, as in the `type` tag.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlateFamily::Standard => "standard",
+            PlateFamily::Slotted { .. } => "slotted",
+            PlateFamily::DualPin { .. } => "dual_pin",
+        }
+    }
+}
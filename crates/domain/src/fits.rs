@@ -0,0 +1,131 @@
+//! Pin bores sized for how the pin should sit in the plate.
+//!
+//! Pins are taken to be ground to `h6`, so the fit is set by the bore alone
+//! (ISO 286 shaft basis): `P7` presses the pin in, `K7` locates it with little or
+//! no play, and `G7` lets it slide. The limits come from the ISO 286-2 tables,
+//! which cover sizes up to 500 mm.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{FitClass, Length, Tolerance};
+
+/// Tolerance class of the pins every fit assumes.
+pub const PIN_CLASS: FitClass = FitClass { deviation: "h", grade: 6 };
+
+/// Largest pin diameter a [`PinFit`] can size a bore for.
+pub const MAX_FIT_DIAMETER: Length = Length::from_mm(500);
+
+/// Upper end of each ISO 286 nominal size range, in millimeters. A size belongs to
+/// the first range it doesn't exceed.
+const SIZE_RANGES_MM: [u32; 13] = [3, 6, 10, 18, 30, 50, 80, 120, 180, 250, 315, 400, 500];
+
+/// IT7 tolerance band per size range, in micrometers.
+const IT7_UM: [i32; 13] = [10, 12, 15, 18, 21, 25, 30, 35, 40, 46, 52, 57, 63];
+
+/// Lower deviation of `G` holes per size range, in micrometers.
+const G_LOWER_UM: [i32; 13] = [2, 4, 5, 6, 7, 9, 10, 12, 14, 15, 17, 18, 20];
+
+/// Upper deviation of `K7` holes per size range, in micrometers.
+const K7_UPPER_UM: [i32; 13] = [0, 3, 5, 6, 6, 7, 9, 10, 12, 13, 16, 17, 18];
+
+/// Upper deviation of `P7` holes per size range, in micrometers.
+const P7_UPPER_UM: [i32; 13] = [-6, -8, -9, -11, -14, -17, -21, -24, -28, -33, -36, -41, -45];
+
+/// How a pin sits in its bore.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PinFit {
+    /// Interference fit (`P7/h6`): the pin is pressed in and stays put
+    Press,
+    /// Transition fit (`K7/h6`): located accurately, tapped in and out by hand
+    Transition,
+    /// Clearance fit (`G7/h6`): the pin slides and turns freely
+    Slip,
+}
+
+impl PinFit {
+    pub const ALL: [PinFit; 3] = [PinFit::Press, PinFit::Transition, PinFit::Slip];
+
+    /// The name used in JSON, e.g. `press`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            PinFit::Press => "press",
+            PinFit::Transition => "transition",
+            PinFit::Slip => "slip",
+        }
+    }
+
+    /// ISO 286 class of the bore.
+    pub const fn hole_class(self) -> FitClass {
+        match self {
+            PinFit::Press => FitClass { deviation: "P", grade: 7 },
+            PinFit::Transition => FitClass { deviation: "K", grade: 7 },
+            PinFit::Slip => FitClass { deviation: "G", grade: 7 },
+        }
+    }
+
+    /// The bore for a pin of `pin_diameter`, or `None` outside the ISO 286 tables
+    /// (zero or over 500 mm).
+    pub fn bore(self, pin_diameter: Length) -> Option<PinBore> {
+        let um = pin_diameter.micrometers();
+        if um == 0 {
+            return None;
+        }
+        let range = SIZE_RANGES_MM.iter().position(|max_mm| um <= max_mm * 1000)?;
+        let it7 = IT7_UM[range];
+        let (upper_um, lower_um) = match self {
+            PinFit::Press => (P7_UPPER_UM[range], P7_UPPER_UM[range] - it7),
+            PinFit::Transition => (K7_UPPER_UM[range], K7_UPPER_UM[range] - it7),
+            PinFit::Slip => (G_LOWER_UM[range] + it7, G_LOWER_UM[range]),
+        };
+        Some(PinBore {
+            fit: self,
+            nominal: pin_diameter,
+            upper_um,
+            lower_um,
+        })
+    }
+}
+
+/// A pin bore with its limits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PinBore {
+    pub fit: PinFit,
+    /// The pin's nominal diameter
+    pub nominal: Length,
+    /// Deviation of the largest allowed bore from `nominal`, in micrometers
+    pub upper_um: i32,
+    /// Deviation of the smallest allowed bore from `nominal`, in micrometers
+    pub lower_um: i32,
+}
+
+impl PinBore {
+    /// The drawing callout, e.g. `P7/h6`.
+    pub fn tolerance(&self) -> Tolerance {
+        Tolerance::Fit {
+            hole: self.fit.hole_class(),
+            shaft: Some(PIN_CLASS),
+        }
+    }
+
+    /// Smallest diameter the bore may be cut to.
+    pub fn smallest(&self) -> Length {
+        self.offset(self.lower_um)
+    }
+
+    /// Largest diameter the bore may be cut to.
+    pub fn largest(&self) -> Length {
+        self.offset(self.upper_um)
+    }
+
+    /// Diameter to aim for: the middle of the band, rounded down to the micrometer.
+    pub fn target(&self) -> Length {
+        self.offset((self.upper_um + self.lower_um).div_euclid(2))
+    }
+
+    fn offset(&self, deviation_um: i32) -> Length {
+        let um = self.nominal.micrometers() as i64 + deviation_um as i64;
+        Length::from_micrometers(um.clamp(0, u32::MAX as i64) as u32)
+    }
+}
@@ -0,0 +1,227 @@
+//! How the mounting bolt holes are machined.
+
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+use crate::{BoltSize, Length};
+
+/// Machining for the mounting bolt holes.
+///
+/// Serializes with a `type` tag, e.g. `{"type": "through"}` or
+/// `{"type": "tapped", "thread": "M10x1.25"}`. Plates that don't say get plain
+/// clearance holes.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HoleSpec {
+    /// Plain clearance hole through the plate.
+    #[default]
+    Through,
+    /// Clearance hole with a flat-bottomed recess for a socket head cap screw (ISO 4762).
+    Counterbore {
+        /// Recess diameter; the bolt size's standard counterbore when left out
+        #[cfg_attr(feature = "openapi", schema(required = false))]
+        diameter: Length,
+        /// Recess depth from the top face; deep enough to sink the head when left out
+        #[cfg_attr(feature = "openapi", schema(required = false))]
+        depth: Length,
+    },
+    /// Clearance hole with a 90° cone for a countersunk screw (ISO 10642).
+    Countersink {
+        /// Cone diameter at the top face; seats the head flush when left out
+        #[cfg_attr(feature = "openapi", schema(required = false))]
+        diameter: Length,
+    },
+    /// Threaded hole, so the bolt screws into the plate.
+    Tapped {
+        /// ISO metric thread, e.g. `M10` (coarse) or `M10x1.25`
+        thread: IsoThread,
+    },
+}
+
+impl HoleSpec {
+    /// Diameter of the hole drilled through the plate: the tap drill for tapped
+    /// holes, the clearance hole otherwise.
+    pub fn drill_diameter_mm(&self, bolt_size: BoltSize) -> f32 {
+        match self {
+            HoleSpec::Tapped { thread } => thread.tap_drill_diameter_mm(),
+            _ => bolt_size.clearance_hole_diameter_mm(),
+        }
+    }
+}
+
+/// A [`HoleSpec`] as serialized, where a counterbore or countersink may leave out
+/// its sizes to take the ones the bolt catalog gives its bolt size.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HoleRecord {
+    #[default]
+    Through,
+    Counterbore {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        diameter: Option<Length>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        depth: Option<Length>,
+    },
+    Countersink {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        diameter: Option<Length>,
+    },
+    Tapped {
+        thread: IsoThread,
+    },
+}
+
+impl HoleRecord {
+    /// The holes for `bolt_size`, with sizes left out taken from the catalog.
+    pub fn resolve(self, bolt_size: BoltSize) -> HoleSpec {
+        let catalog = |mm: f32| Length::from_mm_f64(mm as f64).unwrap_or(Length::ZERO);
+        match self {
+            HoleRecord::Through => HoleSpec::Through,
+            HoleRecord::Counterbore { diameter, depth } => HoleSpec::Counterbore {
+                diameter: diameter.unwrap_or_else(|| catalog(bolt_size.counterbore_diameter_mm())),
+                depth: depth.unwrap_or_else(|| catalog(bolt_size.counterbore_depth_mm())),
+            },
+            HoleRecord::Countersink { diameter } => HoleSpec::Countersink {
+                diameter: diameter.unwrap_or_else(|| catalog(bolt_size.countersink_diameter_mm())),
+            },
+            HoleRecord::Tapped { thread } => HoleSpec::Tapped { thread },
+        }
+    }
+}
+
+impl From<HoleSpec> for HoleRecord {
+    fn from(holes: HoleSpec) -> Self {
+        match holes {
+            HoleSpec::Through => HoleRecord::Through,
+            HoleSpec::Counterbore { diameter, depth } => HoleRecord::Counterbore {
+                diameter: Some(diameter),
+                depth: Some(depth),
+            },
+            HoleSpec::Countersink { diameter } => HoleRecord::Countersink { diameter: Some(diameter) },
+            HoleSpec::Tapped { thread } => HoleRecord::Tapped { thread },
+        }
+    }
+}
+
+/// An ISO metric thread designation such as `M10x1.25`.
+///
+/// Parses `M10` as the coarse pitch and `M10x1.25` as an explicit pitch. Always
+/// formats with the pitch. The pitch isn't checked against the size here; see
+/// [`BoltSize::standard_pitches_um`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IsoThread {
+    pub size: BoltSize,
+    /// Thread pitch in micrometers, e.g. 1250 for 1.25 mm
+    pub pitch_um: u16,
+}
+
+impl IsoThread {
+    /// The coarse-pitch thread for a bolt size.
+    pub const fn coarse(size: BoltSize) -> Self {
+        IsoThread {
+            size,
+            pitch_um: size.standard_pitches_um()[0],
+        }
+    }
+
+    pub fn pitch(&self) -> Length {
+        Length::from_micrometers(self.pitch_um as u32)
+    }
+
+    /// Tap drill diameter: nominal diameter less one pitch.
+    pub fn tap_drill_diameter_mm(&self) -> f32 {
+        self.size.nominal_diameter_mm() as f32 - self.pitch_um as f32 / 1000.0
+    }
+}
+
+impl fmt::Display for IsoThread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "M{}x{}", self.size.nominal_diameter_mm(), self.pitch())
+    }
+}
+
+/// Errors parsing an ISO thread designation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThreadError;
+
+impl fmt::Display for ThreadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected an ISO metric thread from M3 to M12, like \"M10\" or \"M10x1.25\"")
+    }
+}
+
+impl core::str::FromStr for IsoThread {
+    type Err = ThreadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let rest = s.strip_prefix(['M', 'm']).ok_or(ThreadError)?;
+        let (nominal, pitch) = match rest.split_once(['x', 'X', '×']) {
+            Some((nominal, pitch)) => (nominal, Some(pitch)),
+            None => (rest, None),
+        };
+        let size = match nominal.trim() {
+            "3" => BoltSize::M3,
+            "4" => BoltSize::M4,
+            "5" => BoltSize::M5,
+            "6" => BoltSize::M6,
+            "8" => BoltSize::M8,
+            "10" => BoltSize::M10,
+            "12" => BoltSize::M12,
+            _ => return Err(ThreadError),
+        };
+        match pitch {
+            None => Ok(IsoThread::coarse(size)),
+            Some(pitch) => {
+                let pitch = Length::from_mm_f64(pitch.trim().parse().map_err(|_| ThreadError)?)
+                    .map_err(|_| ThreadError)?;
+                match u16::try_from(pitch.micrometers()) {
+                    Ok(pitch_um) if pitch_um > 0 => Ok(IsoThread { size, pitch_um }),
+                    _ => Err(ThreadError),
+                }
+            }
+        }
+    }
+}
+
+impl Serialize for IsoThread {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for IsoThread {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ThreadVisitor;
+
+        impl serde::de::Visitor<'_> for ThreadVisitor {
+            type Value = IsoThread;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "an ISO metric thread designation such as \"M10x1.25\"")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<IsoThread, E> {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ThreadVisitor)
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for IsoThread {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::{ObjectBuilder, Type};
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .description(Some("ISO metric thread: `M10` for coarse pitch, or `M10x1.25`"))
+            .examples(["M10x1.25"])
+            .into()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::ToSchema for IsoThread {}
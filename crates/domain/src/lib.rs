@@ -1,74 +1,299 @@
-#![cfg_attr(not(feature = "openapi"), no_std)]
+#![cfg_attr(not(any(feature = "openapi", feature = "testing")), no_std)]
+extern crate alloc;
 
+use alloc::string::String;
+use core::fmt;
 use serde::{Deserialize, Serialize};
 
-/// A type-safe wrapper for millimeter measurements.
-#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
-#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
-#[cfg_attr(feature = "openapi", schema(example = 60))]
-pub struct Millimeters(pub u16);
+mod bolt_catalog;
+mod count;
+mod diff;
+mod family;
+pub mod fits;
+mod holes;
+mod mass_properties;
+pub mod migrate;
+pub mod presets;
+pub mod share;
+pub mod suggestions;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod tolerance;
 
-/// A type-safe wrapper for force in Newtons.
-#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
-#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
-#[cfg_attr(feature = "openapi", schema(example = 500))]
-pub struct Newtons(pub u32);
+pub use bolt_catalog::{BoltSize, ClearanceFit};
+pub use count::{Count, CountError};
+pub use diff::{FieldChange, PlateDiff};
+pub use family::PlateFamily;
+pub use fits::{PinBore, PinFit};
+pub use holes::{HoleRecord, HoleSpec, IsoThread, ThreadError};
+pub use mass_properties::{CenterOfMass, MassProperties};
+pub use tolerance::{Dimension, FitClass, Tolerance, ToleranceError, Tolerances};
 
-/// Standard ISO metric bolt sizes with clearance hole diameters.
+/// A length, stored as whole micrometers.
 ///
-/// Each variant represents a standard metric bolt size (e.g., M3 = 3mm nominal diameter).
-/// The clearance hole diameter is sized to allow the bolt to pass through freely.
-#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
-#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
-#[serde(rename_all = "UPPERCASE")]
-pub enum BoltSize {
-    /// M3 bolt (3mm nominal, 3.4mm clearance hole)
-    M3,
-    /// M4 bolt (4mm nominal, 4.5mm clearance hole)
-    M4,
-    /// M5 bolt (5mm nominal, 5.5mm clearance hole)
-    M5,
-    /// M6 bolt (6mm nominal, 6.6mm clearance hole)
-    M6,
-    /// M8 bolt (8mm nominal, 9.0mm clearance hole)
-    M8,
-    /// M10 bolt (10mm nominal, 11.0mm clearance hole)
-    M10,
-    /// M12 bolt (12mm nominal, 13.5mm clearance hole)
-    M12,
+/// Serializes as a millimeter number (`60`, `63.5`). Deserializes from a millimeter
+/// number or from a string with a unit: `"60mm"`, `"2.5in"`, `"2.5\""`, `"1500um"`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Length(u32);
+
+const MICROMETERS_PER_MM: u32 = 1_000;
+const MICROMETERS_PER_INCH: u32 = 25_400;
+
+/// Errors parsing a length from a string or number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthError {
+    /// Not a number, or an unrecognized unit.
+    Invalid,
+    /// Negative, or too large to represent.
+    OutOfRange,
 }
 
-impl BoltSize {
-    /// Returns the nominal diameter of the bolt in millimeters.
-    pub const fn nominal_diameter_mm(self) -> u16 {
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            BoltSize::M3 => 3,
-            BoltSize::M4 => 4,
-            BoltSize::M5 => 5,
-            BoltSize::M6 => 6,
-            BoltSize::M8 => 8,
-            BoltSize::M10 => 10,
-            BoltSize::M12 => 12,
+            LengthError::Invalid => write!(f, "expected a length like 60, \"60mm\", \"2.5in\", or \"1500um\""),
+            LengthError::OutOfRange => write!(f, "length must be between 0 and 4294 m"),
         }
     }
+}
 
-    /// Returns the clearance hole diameter in millimeters.
-    ///
-    /// Clearance holes are sized to allow the bolt to pass through freely
-    /// without binding. These are standard engineering clearances for close fit.
-    pub const fn clearance_hole_diameter_mm(self) -> f32 {
-        match self {
-            BoltSize::M3 => 3.4,
-            BoltSize::M4 => 4.5,
-            BoltSize::M5 => 5.5,
-            BoltSize::M6 => 6.6,
-            BoltSize::M8 => 9.0,
-            BoltSize::M10 => 11.0,
-            BoltSize::M12 => 13.5,
+impl Length {
+    pub const ZERO: Length = Length(0);
+
+    pub const fn from_micrometers(um: u32) -> Self {
+        Length(um)
+    }
+
+    pub const fn from_mm(mm: u16) -> Self {
+        Length(mm as u32 * MICROMETERS_PER_MM)
+    }
+
+    /// Fractional millimeters, rounded to the nearest micrometer.
+    pub fn from_mm_f64(mm: f64) -> Result<Self, LengthError> {
+        Self::from_scaled(mm, MICROMETERS_PER_MM as f64)
+    }
+
+    /// Inches, rounded to the nearest micrometer.
+    pub fn from_inches(inches: f64) -> Result<Self, LengthError> {
+        Self::from_scaled(inches, MICROMETERS_PER_INCH as f64)
+    }
+
+    fn from_scaled(value: f64, um_per_unit: f64) -> Result<Self, LengthError> {
+        if value.is_nan() {
+            return Err(LengthError::Invalid);
+        }
+        let um = value * um_per_unit + 0.5;
+        if !(0.0..=u32::MAX as f64).contains(&um) {
+            return Err(LengthError::OutOfRange);
+        }
+        Ok(Length(um as u32))
+    }
+
+    pub const fn micrometers(self) -> u32 {
+        self.0
+    }
+
+    pub fn to_mm(self) -> f64 {
+        self.0 as f64 / MICROMETERS_PER_MM as f64
+    }
+
+    pub fn to_inches(self) -> f64 {
+        self.0 as f64 / MICROMETERS_PER_INCH as f64
+    }
+
+    /// Whole millimeters, rounded down and capped at `u16::MAX`.
+    pub const fn whole_mm_floor(self) -> u16 {
+        let mm = self.0 / MICROMETERS_PER_MM;
+        if mm > u16::MAX as u32 { u16::MAX } else { mm as u16 }
+    }
+
+    /// Whole millimeters, rounded up and capped at `u16::MAX`.
+    pub const fn whole_mm_ceil(self) -> u16 {
+        let mm = self.0.div_ceil(MICROMETERS_PER_MM);
+        if mm > u16::MAX as u32 { u16::MAX } else { mm as u16 }
+    }
+
+    /// The length in millimeters if it is a whole number that fits in a `u16`.
+    pub const fn as_whole_mm(self) -> Option<u16> {
+        if self.0.is_multiple_of(MICROMETERS_PER_MM) && self.0 / MICROMETERS_PER_MM <= u16::MAX as u32 {
+            Some((self.0 / MICROMETERS_PER_MM) as u16)
+        } else {
+            None
+        }
+    }
+
+    /// `None` if the sum exceeds the largest length.
+    pub const fn checked_add(self, other: Length) -> Option<Length> {
+        match self.0.checked_add(other.0) {
+            Some(um) => Some(Length(um)),
+            None => None,
+        }
+    }
+
+    /// `None` if `other` is longer than `self`.
+    pub const fn checked_sub(self, other: Length) -> Option<Length> {
+        match self.0.checked_sub(other.0) {
+            Some(um) => Some(Length(um)),
+            None => None,
         }
     }
+
+    /// `None` if the product exceeds the largest length.
+    pub const fn checked_mul(self, factor: u32) -> Option<Length> {
+        match self.0.checked_mul(factor) {
+            Some(um) => Some(Length(um)),
+            None => None,
+        }
+    }
+
+    pub const fn saturating_add(self, other: Length) -> Length {
+        Length(self.0.saturating_add(other.0))
+    }
+
+    /// Zero if `other` is longer than `self`.
+    pub const fn saturating_sub(self, other: Length) -> Length {
+        Length(self.0.saturating_sub(other.0))
+    }
+
+    pub const fn saturating_mul(self, factor: u32) -> Length {
+        Length(self.0.saturating_mul(factor))
+    }
 }
 
+/// Whole millimeters.
+impl TryFrom<u32> for Length {
+    type Error = LengthError;
+
+    fn try_from(mm: u32) -> Result<Self, Self::Error> {
+        mm.checked_mul(MICROMETERS_PER_MM).map(Length).ok_or(LengthError::OutOfRange)
+    }
+}
+
+/// Fractional millimeters, as [`Length::from_mm_f64`].
+impl TryFrom<f64> for Length {
+    type Error = LengthError;
+
+    fn try_from(mm: f64) -> Result<Self, Self::Error> {
+        Length::from_mm_f64(mm)
+    }
+}
+
+/// Formats as a bare millimeter value with no trailing zeros, e.g. `60` or `6.35`.
+/// The alternate form, `{:#}`, adds the unit: `60 mm`.
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / MICROMETERS_PER_MM;
+        let mut frac = self.0 % MICROMETERS_PER_MM;
+        let unit = if f.alternate() { " mm" } else { "" };
+        if frac == 0 {
+            return write!(f, "{}{}", whole, unit);
+        }
+        let mut digits = 3;
+        while frac.is_multiple_of(10) {
+            frac /= 10;
+            digits -= 1;
+        }
+        write!(f, "{}.{:0width$}{}", whole, frac, unit, width = digits)
+    }
+}
+
+impl core::str::FromStr for Length {
+    type Err = LengthError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+            .unwrap_or(s.len());
+        let (number, unit) = s.split_at(split);
+        let value: f64 = number.parse().map_err(|_| LengthError::Invalid)?;
+        match unit.trim() {
+            "" | "mm" => Self::from_mm_f64(value),
+            "in" | "\"" => Self::from_inches(value),
+            "um" | "µm" | "μm" => Self::from_scaled(value, 1.0),
+            _ => Err(LengthError::Invalid),
+        }
+    }
+}
+
+impl Serialize for Length {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.is_multiple_of(MICROMETERS_PER_MM) {
+            serializer.serialize_u32(self.0 / MICROMETERS_PER_MM)
+        } else {
+            serializer.serialize_f64(self.to_mm())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Length {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LengthVisitor;
+
+        impl serde::de::Visitor<'_> for LengthVisitor {
+            type Value = Length;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a length in millimeters, or a string with a unit such as \"2.5in\"")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, mm: u64) -> Result<Length, E> {
+                u32::try_from(mm)
+                    .map_err(|_| LengthError::OutOfRange)
+                    .and_then(Length::try_from)
+                    .map_err(E::custom)
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, mm: i64) -> Result<Length, E> {
+                let mm = u64::try_from(mm).map_err(|_| E::custom(LengthError::OutOfRange))?;
+                self.visit_u64(mm)
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, mm: f64) -> Result<Length, E> {
+                Length::from_mm_f64(mm).map_err(E::custom)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Length, E> {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(LengthVisitor)
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for Length {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::{ObjectBuilder, OneOfBuilder, Type};
+        OneOfBuilder::new()
+            .description(Some(
+                "A length: a number of millimeters, or a string with a unit (mm, in, um)",
+            ))
+            .item(
+                ObjectBuilder::new()
+                    .schema_type(Type::Number)
+                    .minimum(Some(0))
+                    .description(Some("Millimeters")),
+            )
+            .item(
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .description(Some("Number with a unit, e.g. \"2.5in\" or \"63.5mm\"")),
+            )
+            .into()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::ToSchema for Length {}
+
+/// A type-safe wrapper for force in Newtons.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "openapi", schema(example = 500))]
+pub struct Newtons(pub u32);
+
 /// Materials suitable for actuator mounting plates.
 ///
 /// Each material has different properties affecting strength, weight,
@@ -93,6 +318,23 @@ pub enum Material {
 }
 
 impl Material {
+    pub const ALL: [Material; 4] = [
+        Material::Aluminum,
+        Material::StainlessSteel,
+        Material::CarbonSteel,
+        Material::Brass,
+    ];
+
+    /// The name used in JSON, e.g. `stainless_steel`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Material::Aluminum => "aluminum",
+            Material::StainlessSteel => "stainless_steel",
+            Material::CarbonSteel => "carbon_steel",
+            Material::Brass => "brass",
+        }
+    }
+
     /// Returns the material hex color code for KCL.
     pub const fn as_hex_code(&self) -> &'static str {
         match self {
@@ -162,14 +404,18 @@ impl Material {
 ///
 /// Defines the physical dimensions and parameters for manufacturing
 /// a custom actuator plate with mounting bolts and actuator pins.
+///
+/// Serializes with a `schema_version` and deserializes any older version; see
+/// [`migrate`].
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(try_from = "migrate::PlateRecord", into = "migrate::PlateRecord")]
 pub struct ActuatorPlate {
     /// Distance between mounting bolt centers (in millimeters).
     ///
     /// Used for the bolt hole pattern layout. Determines the spacing
     /// between mounting points on the plate.
-    pub bolt_spacing: Millimeters,
+    pub bolt_spacing: Length,
 
     /// Standard ISO metric bolt size for mounting holes.
     ///
@@ -180,12 +426,12 @@ pub struct ActuatorPlate {
     /// Height of the mounting bracket (in millimeters).
     ///
     /// Vertical dimension of the bracket that holds the actuator.
-    pub bracket_height: Millimeters,
+    pub bracket_height: Length,
 
     /// Width of the mounting bracket (in millimeters).
     ///
     /// Horizontal dimension of the bracket that holds the actuator.
-    pub bracket_width: Millimeters,
+    pub bracket_width: Length,
 
     /// Material for the plate.
     ///
@@ -198,19 +444,19 @@ pub struct ActuatorPlate {
     ///
     /// Separate from mounting bolts. These pins are used for the actuator
     /// mechanism's pivot points and articulation.
-    pub pin_diameter: Millimeters,
+    pub pin_diameter: Length,
 
     /// Number of actuator pins.
     ///
     /// Count of pivot pins required for the actuator mechanism.
     /// Must be between 1 and 12 inclusive.
     #[cfg_attr(feature = "openapi", schema(example = 6))]
-    pub pin_count: u16,
+    pub pin_count: Count,
 
     /// Thickness of the base plate material (in millimeters).
     ///
     /// Determines the structural rigidity and extrusion depth of the plate.
-    pub plate_thickness: Millimeters,
+    pub plate_thickness: Length,
 
     /// Expected operating force per actuator pin (in Newtons).
     ///
@@ -218,19 +464,48 @@ pub struct ActuatorPlate {
     /// Stress checks apply a 2× safety factor internally.
     #[cfg_attr(feature = "openapi", schema(example = 500))]
     pub expected_force_per_pin: Newtons,
+
+    /// How the mounting bolt holes are machined.
+    ///
+    /// Defaults to plain through (clearance) holes. Counterbores and countersinks
+    /// recess the screw head; tapped holes take the bolt's thread directly.
+    #[serde(default)]
+    pub bolt_holes: HoleSpec,
+
+    /// Tolerances for the machinist, e.g. `{"pin_diameter": "H7/g6"}`.
+    ///
+    /// Dimensions left out take the shop's general tolerance. Written into the
+    /// STEP file header and the drawing; the model itself is at nominal size,
+    /// except for pin bores sized for a [`pin_fit`](Self::pin_fit).
+    #[serde(default)]
+    pub tolerances: Tolerances,
+
+    /// Which layout to build, e.g. `{"type": "slotted", "slot_length": 12}`.
+    ///
+    /// Defaults to the standard plate: round bolt holes and pins on a circle.
+    #[serde(default)]
+    pub family: PlateFamily,
+
+    /// How the pins sit in their bores: `press`, `transition`, or `slip`.
+    ///
+    /// Sizes the pin bores for the fit and calls out its ISO 286 class in place of
+    /// a `tolerances.pin_diameter`. Without one, the bores are cut at the pin's
+    /// nominal diameter.
+    #[serde(default)]
+    pub pin_fit: Option<PinFit>,
 }
 
 impl ActuatorPlate {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
-        bolt_spacing: Millimeters,
+        bolt_spacing: Length,
         bolt_size: BoltSize,
-        bracket_height: Millimeters,
-        bracket_width: Millimeters,
+        bracket_height: Length,
+        bracket_width: Length,
         material: Material,
-        pin_diameter: Millimeters,
-        pin_count: u16,
-        plate_thickness: Millimeters,
+        pin_diameter: Length,
+        pin_count: Count,
+        plate_thickness: Length,
         expected_force_per_pin: Newtons,
     ) -> Self {
         ActuatorPlate {
@@ -243,47 +518,163 @@ impl ActuatorPlate {
             pin_count,
             plate_thickness,
             expected_force_per_pin,
+            bolt_holes: HoleSpec::Through,
+            tolerances: Tolerances::NONE,
+            family: PlateFamily::Standard,
+            pin_fit: None,
+        }
+    }
+
+    /// Nominal size of a toleranced dimension.
+    pub fn dimension(&self, dimension: Dimension) -> Length {
+        match dimension {
+            Dimension::BoltSpacing => self.bolt_spacing,
+            Dimension::BracketHeight => self.bracket_height,
+            Dimension::BracketWidth => self.bracket_width,
+            Dimension::PinDiameter => self.pin_diameter,
+            Dimension::PlateThickness => self.plate_thickness,
         }
     }
 
+    /// The pin bore for [`pin_fit`](Self::pin_fit), if it has one and the pin is
+    /// within the fit tables.
+    pub fn pin_bore(&self) -> Option<PinBore> {
+        self.pin_fit.and_then(|fit| fit.bore(self.pin_diameter))
+    }
+
+    /// Diameter the pin holes are cut at: the middle of the fit's band, or the
+    /// nominal pin diameter without a fit.
+    pub fn pin_hole_diameter(&self) -> Length {
+        self.pin_bore().map_or(self.pin_diameter, |bore| bore.target())
+    }
+
+    /// The tolerances to call out on the drawing and in the STEP header: those
+    /// given, with the pin fit's class on the pin diameter.
+    pub fn called_out_tolerances(&self) -> Tolerances {
+        let mut tolerances = self.tolerances;
+        if let Some(bore) = self.pin_bore() {
+            tolerances.pin_diameter = Some(bore.tolerance());
+        }
+        tolerances
+    }
+
     /// Generate a deterministic cache key based on plate configuration.
     /// Returns a string in the format "plate-{16_hex_chars}" derived from SHA-256 hash.
-    #[cfg(feature = "openapi")]
+    #[cfg(feature = "cache-key")]
     pub fn cache_key(&self) -> String {
+        use alloc::string::ToString;
         use sha2::{Digest, Sha256};
 
+        // Whole-millimeter lengths hash as the u16 they were stored as before
+        // `Length` existed, so keys for existing cached models don't change.
+        fn hash_length(hasher: &mut Sha256, length: Length) {
+            match length.as_whole_mm() {
+                Some(mm) => hasher.update(mm.to_le_bytes()),
+                None => {
+                    hasher.update([0xFF]);
+                    hasher.update(length.micrometers().to_le_bytes());
+                }
+            }
+        }
+
         let mut hasher = Sha256::new();
-        hasher.update(self.bolt_spacing.0.to_le_bytes());
+        hash_length(&mut hasher, self.bolt_spacing);
         hasher.update(self.bolt_size.nominal_diameter_mm().to_le_bytes());
-        hasher.update(self.bracket_height.0.to_le_bytes());
-        hasher.update(self.bracket_width.0.to_le_bytes());
+        hash_length(&mut hasher, self.bracket_height);
+        hash_length(&mut hasher, self.bracket_width);
         hasher.update(self.material.as_hex_code().as_bytes());
-        hasher.update(self.pin_diameter.0.to_le_bytes());
-        hasher.update(self.pin_count.to_le_bytes());
-        hasher.update(self.plate_thickness.0.to_le_bytes());
+        hash_length(&mut hasher, self.pin_diameter);
+        // Hashed as the u16 it was stored as before `Count`, like lengths above
+        match u16::try_from(self.pin_count.get()) {
+            Ok(pins) => hasher.update(pins.to_le_bytes()),
+            Err(_) => {
+                hasher.update([0xFF]);
+                hasher.update(self.pin_count.get().to_le_bytes());
+            }
+        }
+        hash_length(&mut hasher, self.plate_thickness);
         hasher.update(self.expected_force_per_pin.0.to_le_bytes());
+        // Through holes add nothing, so plates from before hole specs keep their keys
+        match self.bolt_holes {
+            HoleSpec::Through => {}
+            HoleSpec::Counterbore { diameter, depth } => {
+                hasher.update(b"counterbore");
+                hasher.update(diameter.micrometers().to_le_bytes());
+                hasher.update(depth.micrometers().to_le_bytes());
+            }
+            HoleSpec::Countersink { diameter } => {
+                hasher.update(b"countersink");
+                hasher.update(diameter.micrometers().to_le_bytes());
+            }
+            HoleSpec::Tapped { thread } => {
+                hasher.update(b"tapped");
+                hasher.update(thread.size.nominal_diameter_mm().to_le_bytes());
+                hasher.update(thread.pitch_um.to_le_bytes());
+            }
+        }
+        // Tolerances change the drawing and STEP header, so they count too
+        for (dimension, tolerance) in self.tolerances.iter() {
+            hasher.update(dimension.field_name().as_bytes());
+            hasher.update(tolerance.to_string().as_bytes());
+        }
+        // Standard plates add nothing, like through holes above
+        match self.family {
+            PlateFamily::Standard => {}
+            PlateFamily::Slotted { slot_length } => {
+                hasher.update(b"slotted");
+                hasher.update(slot_length.micrometers().to_le_bytes());
+            }
+            PlateFamily::DualPin { pin_spacing } => {
+                hasher.update(b"dual_pin");
+                hasher.update(pin_spacing.micrometers().to_le_bytes());
+            }
+        }
+        if let Some(fit) = self.pin_fit {
+            hasher.update(b"pin_fit");
+            hasher.update(fit.name().as_bytes());
+        }
 
         let result = hasher.finalize();
-        format!("plate-{}", hex::encode(&result[..8]))
+        alloc::format!("plate-{}", hex::encode(&result[..8]))
     }
 }
 
 impl Default for ActuatorPlate {
     fn default() -> Self {
         ActuatorPlate {
-            bolt_spacing: Millimeters(60),
+            bolt_spacing: Length::from_mm(60),
             bolt_size: BoltSize::M10,
-            bracket_height: Millimeters(400),
-            bracket_width: Millimeters(300),
+            bracket_height: Length::from_mm(400),
+            bracket_width: Length::from_mm(300),
             material: Material::Aluminum,
-            pin_diameter: Millimeters(10),
-            pin_count: 6,
-            plate_thickness: Millimeters(8),
+            pin_diameter: Length::from_mm(10),
+            pin_count: Count::of(6),
+            plate_thickness: Length::from_mm(8),
             expected_force_per_pin: Newtons(500),
+            bolt_holes: HoleSpec::Through,
+            tolerances: Tolerances::NONE,
+            family: PlateFamily::Standard,
+            pin_fit: None,
         }
     }
 }
 
+/// A plate configuration saved under a name so it can be reloaded later.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SavedConfig {
+    /// Unique identifier
+    pub id: String,
+    /// Name the user saved the configuration under
+    pub name: String,
+    /// The saved plate parameters
+    pub plate: ActuatorPlate,
+    /// When the configuration was first saved (RFC 3339)
+    pub created_at: String,
+    /// When the configuration was last saved (RFC 3339)
+    pub updated_at: String,
+}
+
 #[cfg(all(test, feature = "openapi"))]
 mod tests {
     use super::*;
@@ -307,11 +698,80 @@ mod tests {
     #[test]
     fn test_cache_key_differs_for_different_plates() {
         let plate1 = ActuatorPlate::default();
-        let plate2 = ActuatorPlate { bolt_spacing: Millimeters(61), ..Default::default() };
+        let plate2 = ActuatorPlate { bolt_spacing: Length::from_mm(61), ..Default::default() };
 
         assert_ne!(plate1.cache_key(), plate2.cache_key());
     }
 
+    #[test]
+    fn test_cache_key_distinguishes_fractional_lengths() {
+        let whole = ActuatorPlate::default();
+        let fractional = ActuatorPlate {
+            plate_thickness: Length::from_micrometers(8_500),
+            ..Default::default()
+        };
+
+        assert_ne!(whole.cache_key(), fractional.cache_key());
+    }
+
+    #[test]
+    fn test_length_parses_units() {
+        assert_eq!("60".parse(), Ok(Length::from_mm(60)));
+        assert_eq!("63.5 mm".parse(), Ok(Length::from_micrometers(63_500)));
+        assert_eq!("2.5in".parse(), Ok(Length::from_micrometers(63_500)));
+        assert_eq!("0.25\"".parse(), Ok(Length::from_micrometers(6_350)));
+        assert_eq!("1500um".parse(), Ok(Length::from_micrometers(1_500)));
+        assert_eq!("2.5ft".parse::<Length>(), Err(LengthError::Invalid));
+        assert_eq!("-1mm".parse::<Length>(), Err(LengthError::OutOfRange));
+    }
+
+    #[test]
+    fn test_length_display_trims_zeros() {
+        assert_eq!(Length::from_mm(60).to_string(), "60");
+        assert_eq!(Length::from_micrometers(6_350).to_string(), "6.35");
+        assert_eq!(Length::from_micrometers(1_005).to_string(), "1.005");
+        assert_eq!(format!("{:#}", Length::from_micrometers(6_350)), "6.35 mm");
+        assert_eq!(format!("{:#}", Length::from_mm(60)), "60 mm");
+    }
+
+    #[test]
+    fn test_length_arithmetic_is_checked() {
+        let big = Length::from_micrometers(u32::MAX);
+        assert_eq!(Length::from_mm(60).checked_add(Length::from_mm(5)), Some(Length::from_mm(65)));
+        assert_eq!(big.checked_add(Length::from_micrometers(1)), None);
+        assert_eq!(Length::from_mm(5).checked_sub(Length::from_mm(6)), None);
+        assert_eq!(Length::from_mm(6).checked_sub(Length::from_mm(5)), Some(Length::from_mm(1)));
+        assert_eq!(Length::from_mm(6).checked_mul(3), Some(Length::from_mm(18)));
+        assert_eq!(big.checked_mul(2), None);
+        assert_eq!(big.saturating_add(Length::from_mm(1)), big);
+        assert_eq!(Length::from_mm(5).saturating_sub(Length::from_mm(6)), Length::ZERO);
+
+        assert_eq!(Length::try_from(60u32), Ok(Length::from_mm(60)));
+        assert_eq!(Length::try_from(5_000_000u32), Err(LengthError::OutOfRange));
+        assert_eq!(Length::try_from(6.35), Ok(Length::from_micrometers(6_350)));
+        assert_eq!(Length::try_from(-1.0), Err(LengthError::OutOfRange));
+        assert_eq!(Length::try_from(f64::NAN), Err(LengthError::Invalid));
+    }
+
+    #[test]
+    fn test_length_serde_round_trip() {
+        let plate = ActuatorPlate {
+            plate_thickness: Length::from_inches(0.25).unwrap(),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(plate).unwrap();
+        assert_eq!(json["bolt_spacing"], 60);
+        assert_eq!(json["plate_thickness"], 6.35);
+
+        let back: ActuatorPlate = serde_json::from_value(json).unwrap();
+        assert_eq!(back, plate);
+
+        let mut json = serde_json::to_value(ActuatorPlate::default()).unwrap();
+        json["bracket_width"] = "12in".into();
+        let parsed: ActuatorPlate = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.bracket_width, Length::from_micrometers(304_800));
+    }
+
     #[test]
     fn test_cache_key_differs_for_different_materials() {
         let plate1 = ActuatorPlate::default();
@@ -327,6 +787,24 @@ mod tests {
         assert_eq!(plate1.cache_key(), plate2.cache_key());
     }
 
+    #[test]
+    fn test_cache_key_is_pinned() {
+        // Little-endian encoding makes the key the same on every platform. If this
+        // changes, every cache entry and model download URL is invalidated.
+        assert_eq!(ActuatorPlate::default().cache_key(), "plate-6127915c44d97ab4");
+    }
+
+    #[test]
+    fn test_cache_key_ignores_field_order_and_units() {
+        let plate: ActuatorPlate = serde_json::from_str(
+            r#"{"expected_force_per_pin":500,"plate_thickness":"8mm","pin_count":6,
+                "pin_diameter":10,"material":"aluminum","bracket_width":300,
+                "bracket_height":"400mm","bolt_size":"M10","bolt_spacing":60}"#,
+        )
+        .unwrap();
+        assert_eq!(plate.cache_key(), ActuatorPlate::default().cache_key());
+    }
+
     #[test]
     fn test_material_kcl_str() {
         assert_eq!(Material::Aluminum.as_hex_code(), "#A9ACB6");
@@ -412,4 +890,397 @@ mod tests {
 
         assert_ne!(plate1.cache_key(), plate2.cache_key());
     }
+
+    #[test]
+    fn test_iso_thread_parses_coarse_and_fine() {
+        let coarse: IsoThread = "M10".parse().unwrap();
+        assert_eq!(coarse, IsoThread { size: BoltSize::M10, pitch_um: 1500 });
+        assert_eq!(coarse.to_string(), "M10x1.5");
+
+        let fine: IsoThread = "m8 x 1".parse().unwrap();
+        assert_eq!(fine, IsoThread { size: BoltSize::M8, pitch_um: 1000 });
+        assert_eq!(fine.tap_drill_diameter_mm(), 7.0);
+
+        assert_eq!("M7".parse::<IsoThread>(), Err(ThreadError));
+        assert_eq!("M10x0".parse::<IsoThread>(), Err(ThreadError));
+        assert_eq!("10x1.5".parse::<IsoThread>(), Err(ThreadError));
+    }
+
+    #[test]
+    fn test_bolt_holes_default_to_through_and_round_trip() {
+        let plate: ActuatorPlate = serde_json::from_value(serde_json::json!({
+            "bolt_spacing": 60, "bolt_size": "M10", "bracket_height": 400,
+            "bracket_width": 300, "material": "aluminum", "pin_diameter": 10,
+            "pin_count": 6, "plate_thickness": 8, "expected_force_per_pin": 500
+        }))
+        .unwrap();
+        assert_eq!(plate.bolt_holes, HoleSpec::Through);
+
+        let tapped = ActuatorPlate {
+            bolt_holes: HoleSpec::Tapped { thread: "M10x1.25".parse().unwrap() },
+            ..Default::default()
+        };
+        let json = serde_json::to_value(tapped).unwrap();
+        assert_eq!(json["bolt_holes"], serde_json::json!({ "type": "tapped", "thread": "M10x1.25" }));
+        assert_eq!(serde_json::from_value::<ActuatorPlate>(json).unwrap(), tapped);
+        assert_ne!(tapped.cache_key(), ActuatorPlate::default().cache_key());
+    }
+
+    #[test]
+    fn test_recess_sizes_come_from_the_bolt_catalog() {
+        let mut json = serde_json::to_value(ActuatorPlate { plate_thickness: Length::from_mm(16), ..Default::default() }).unwrap();
+        json["bolt_holes"] = serde_json::json!({ "type": "counterbore" });
+        let plate: ActuatorPlate = serde_json::from_value(json.clone()).unwrap();
+        let counterbore = HoleSpec::Counterbore { diameter: Length::from_mm(18), depth: Length::from_micrometers(10_600) };
+        assert_eq!((plate.bolt_size, plate.bolt_holes), (BoltSize::M10, counterbore));
+        // Written out in full, so the plate reads back the same whatever the catalog says
+        assert_eq!(serde_json::to_value(plate).unwrap()["bolt_holes"], serde_json::json!({ "type": "counterbore", "diameter": 18, "depth": 10.6 }));
+
+        json["bolt_holes"] = serde_json::json!({ "type": "counterbore", "diameter": 20 });
+        let plate: ActuatorPlate = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(plate.bolt_holes, HoleSpec::Counterbore { diameter: Length::from_mm(20), depth: Length::from_micrometers(10_600) });
+
+        json["bolt_size"] = serde_json::json!("M4");
+        json["bolt_holes"] = serde_json::json!({ "type": "countersink" });
+        let plate: ActuatorPlate = serde_json::from_value(json).unwrap();
+        assert_eq!(plate.bolt_holes, HoleSpec::Countersink { diameter: Length::from_micrometers(9_200) });
+    }
+
+    #[test]
+    fn test_bolt_catalog_sizes_fit_the_bolt() {
+        for size in BoltSize::ALL {
+            assert_eq!(BoltSize::from_name(&size.name().to_lowercase()), Some(size));
+            let d = size.nominal_diameter_mm() as f32;
+            let fits = [ClearanceFit::Close, ClearanceFit::Normal, ClearanceFit::Loose].map(|fit| size.clearance_diameter_mm(fit));
+            assert!(d < fits[0] && fits[0] < fits[1] && fits[1] < fits[2], "{:?} clearances {:?}", size, fits);
+            assert!(size.counterbore_diameter_mm() > size.socket_head_diameter_mm(), "{:?}", size);
+            assert!(size.counterbore_depth_mm() > size.socket_head_height_mm(), "{:?}", size);
+            assert!(size.countersink_diameter_mm() > size.countersunk_head_diameter_mm(), "{:?}", size);
+        }
+        assert_eq!(BoltSize::from_name(" M8 "), Some(BoltSize::M8));
+        assert_eq!(BoltSize::from_name("M7"), None);
+    }
+
+    #[test]
+    fn test_family_defaults_to_standard_and_round_trips() {
+        let json = serde_json::to_value(ActuatorPlate::default()).unwrap();
+        assert_eq!(json["family"], serde_json::json!({ "type": "standard" }));
+        let mut legacy = json.clone();
+        legacy.as_object_mut().unwrap().remove("family");
+        assert_eq!(serde_json::from_value::<ActuatorPlate>(legacy).unwrap(), ActuatorPlate::default());
+
+        let dual_pin = ActuatorPlate {
+            pin_count: Count::of(2),
+            family: PlateFamily::DualPin { pin_spacing: Length::from_mm(80) },
+            ..Default::default()
+        };
+        let json = serde_json::to_value(dual_pin).unwrap();
+        assert_eq!(json["family"], serde_json::json!({ "type": "dual_pin", "pin_spacing": 80 }));
+        assert_eq!(serde_json::from_value::<ActuatorPlate>(json).unwrap(), dual_pin);
+
+        // Only the family differs, so it has to be in the key
+        let two_pins = ActuatorPlate { pin_count: Count::of(2), ..Default::default() };
+        assert_ne!(dual_pin.cache_key(), two_pins.cache_key());
+        let slotted = ActuatorPlate {
+            family: PlateFamily::Slotted { slot_length: Length::from_mm(80) },
+            ..two_pins
+        };
+        assert_ne!(slotted.cache_key(), dual_pin.cache_key());
+    }
+
+    #[test]
+    fn test_tolerance_callouts_parse_and_format() {
+        let cases = [
+            ("\u{B1}0.1", "\u{B1}0.1"),
+            ("+-0.05", "\u{B1}0.05"),
+            ("+0.2/-0.1", "+0.2/-0.1"),
+            ("0 / -0.05", "0/-0.05"),
+            ("H7", "H7"),
+            ("H7/g6", "H7/g6"),
+            ("JS9/js8", "JS9/js8"),
+        ];
+        for (input, formatted) in cases {
+            let tolerance: Tolerance = input.parse().unwrap();
+            assert_eq!(tolerance.to_string(), formatted, "{}", input);
+        }
+
+        assert_eq!(
+            "+0.2/-0.1".parse::<Tolerance>().unwrap().deviations_um(),
+            Some((200, -100))
+        );
+        for bad in ["0.1", "\u{B1}-0.1", "h7", "H7/G6", "g6/H7", "H19", "L7", "+0.1/"] {
+            assert_eq!(bad.parse::<Tolerance>(), Err(ToleranceError), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_tolerances_round_trip_and_change_cache_key() {
+        let plate: ActuatorPlate = serde_json::from_value(serde_json::json!({
+            "bolt_spacing": 60, "bolt_size": "M10", "bracket_height": 400,
+            "bracket_width": 300, "material": "aluminum", "pin_diameter": 10,
+            "pin_count": 6, "plate_thickness": 8, "expected_force_per_pin": 500,
+            "tolerances": { "pin_diameter": "H7/g6", "plate_thickness": "+-0.1" }
+        }))
+        .unwrap();
+        let found: Vec<_> = plate.tolerances.iter().map(|(d, _)| d).collect();
+        assert_eq!(found, [Dimension::PinDiameter, Dimension::PlateThickness]);
+
+        let json = serde_json::to_value(plate).unwrap();
+        assert_eq!(
+            json["tolerances"],
+            serde_json::json!({ "pin_diameter": "H7/g6", "plate_thickness": "\u{B1}0.1" })
+        );
+        assert_eq!(serde_json::from_value::<ActuatorPlate>(json).unwrap(), plate);
+        assert_ne!(plate.cache_key(), ActuatorPlate::default().cache_key());
+        assert!(ActuatorPlate::default().tolerances.is_empty());
+    }
+
+    #[test]
+    fn test_count_rejects_zero_and_overflow() {
+        assert_eq!(Count::new(0), Err(CountError::Zero));
+        assert_eq!(Count::try_from(u32::MAX as u64 + 1), Err(CountError::Overflow));
+        assert_eq!(serde_json::to_value(Count::of(6)).unwrap(), 6);
+        assert_eq!(serde_json::from_str::<Count>("6").unwrap(), Count::of(6));
+        for bad in ["0", "-1", "6.5", "4294967296", "\"6\""] {
+            assert!(serde_json::from_str::<Count>(bad).is_err(), "{}", bad);
+        }
+
+        let mut json = serde_json::to_value(ActuatorPlate::default()).unwrap();
+        json["pin_count"] = 0.into();
+        assert!(serde_json::from_value::<ActuatorPlate>(json).is_err());
+    }
+
+    #[test]
+    fn test_count_arithmetic() {
+        let (two, three) = (Count::of(2), Count::of(3));
+        assert_eq!(two.checked_add(three), Some(Count::of(5)));
+        assert_eq!(two.checked_mul(three), Some(Count::of(6)));
+        assert_eq!(three.checked_sub(two), Some(Count::ONE));
+        assert_eq!(two.checked_sub(two), None);
+        assert_eq!(two.checked_sub(three), None);
+
+        let max = Count::of(u32::MAX);
+        assert_eq!(max.checked_add(Count::ONE), None);
+        assert_eq!(max.saturating_add(two), max);
+        assert_eq!(max.saturating_mul(two), max);
+        assert_eq!(max.times(u32::MAX), u32::MAX as u64 * u32::MAX as u64);
+    }
+
+    #[test]
+    fn test_plate_diff_lists_changed_fields() {
+        let plate = ActuatorPlate::default();
+        assert!(plate.diff(&plate).is_empty());
+
+        let mut other = plate;
+        other.bolt_spacing = Length::from_micrometers(57_500);
+        other.material = Material::Brass;
+        other.tolerances.pin_diameter = Some("H7/g6".parse().unwrap());
+        let diff = plate.diff(&other);
+
+        let fields: Vec<&str> = diff.changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, ["bolt_spacing", "material", "tolerances.pin_diameter"]);
+        assert_eq!(diff.get("bolt_spacing").unwrap().delta_mm, Some(-2.5));
+        assert_eq!(diff.get("material").unwrap().after, "brass");
+        assert_eq!(diff.get("tolerances.pin_diameter").unwrap().before, "general");
+        assert_eq!(
+            serde_json::to_value(diff.get("material").unwrap()).unwrap(),
+            serde_json::json!({ "field": "material", "before": "aluminum", "after": "brass" })
+        );
+        assert_eq!(other.diff(&plate).get("bolt_spacing").unwrap().delta_mm, Some(2.5));
+    }
+
+    #[test]
+    fn test_share_query_round_trips() {
+        let plate = ActuatorPlate::default();
+        assert_eq!(plate.to_query(), "bs=60&bd=10&bh=400&bw=300&m=aluminum&pd=10&pc=6&pt=8&f=500");
+
+        let mut detailed = ActuatorPlate {
+            bolt_spacing: "2.5in".parse().unwrap(),
+            bolt_size: BoltSize::M8,
+            material: Material::StainlessSteel,
+            plate_thickness: Length::from_micrometers(9_525),
+            pin_count: Count::of(11),
+            bolt_holes: HoleSpec::Tapped { thread: "M8x1".parse().unwrap() },
+            ..plate
+        };
+        detailed.tolerances.pin_diameter = Some("H7/g6".parse().unwrap());
+        detailed.tolerances.plate_thickness = Some("+0.2/-0.05".parse().unwrap());
+        detailed.tolerances.bolt_spacing = Some("\u{B1}0.1".parse().unwrap());
+        let counterbored = ActuatorPlate {
+            bolt_holes: HoleSpec::Counterbore {
+                diameter: Length::from_mm(16),
+                depth: Length::from_micrometers(10_500),
+            },
+            ..plate
+        };
+        let countersunk = ActuatorPlate {
+            bolt_holes: HoleSpec::Countersink { diameter: Length::from_mm(20) },
+            ..plate
+        };
+
+        let slotted = ActuatorPlate {
+            family: PlateFamily::Slotted { slot_length: Length::from_micrometers(12_500) },
+            ..plate
+        };
+        let dual_pin = ActuatorPlate {
+            pin_count: Count::of(2),
+            family: PlateFamily::DualPin { pin_spacing: Length::from_mm(80) },
+            ..plate
+        };
+        let press_fit = ActuatorPlate { pin_fit: Some(PinFit::Press), ..plate };
+
+        let presets = presets::PRESETS.iter().map(|preset| preset.plate);
+        for plate in presets.chain([plate, detailed, counterbored, countersunk, slotted, dual_pin, press_fit]) {
+            let query = plate.to_query();
+            assert_eq!(ActuatorPlate::from_query(&query), Ok(plate), "{}", query);
+            assert_eq!(ActuatorPlate::from_query(&format!("?{}", query)), Ok(plate));
+        }
+        assert!(detailed.to_query().contains("&h=tap%3AM8x1&tbs=%C2%B10.1&tpd=H7%2Fg6&tpt=%2B0.2%2F-0.05"));
+        assert!(slotted.to_query().ends_with("&fam=slot%3A12.5"));
+    }
+
+    #[test]
+    fn test_share_query_fills_gaps_and_rejects_bad_values() {
+        let plate = ActuatorPlate::from_query("pc=4&utm_source=mail&tpd=H7").unwrap();
+        assert_eq!(plate.pin_count, Count::of(4));
+        assert_eq!(plate.tolerances.pin_diameter, Some("H7".parse().unwrap()));
+        assert_eq!(plate.bolt_spacing, ActuatorPlate::default().bolt_spacing);
+
+        for (query, key) in [("bd=7", "bd"), ("pc=0", "pc"), ("m=wood", "m"), ("h=cb:10", "h"), ("bs=%ZZ", "bs"), ("fam=slot", "fam")] {
+            assert_eq!(ActuatorPlate::from_query(query).unwrap_err().key, key, "{}", query);
+        }
+
+        #[derive(Debug, PartialEq, Deserialize, Serialize)]
+        struct Link {
+            #[serde(with = "crate::share")]
+            plate: ActuatorPlate,
+        }
+        let link = Link { plate: ActuatorPlate::default() };
+        let json = serde_json::to_value(&link).unwrap();
+        assert_eq!(json["plate"], ActuatorPlate::default().to_query());
+        assert_eq!(serde_json::from_value::<Link>(json).unwrap(), link);
+    }
+
+    #[test]
+    fn test_mass_properties_of_default_plate() {
+        // 300 × 400 × 8 mm less six Ø10 pins and four Ø11 bolt holes
+        let mass = ActuatorPlate::default().mass_properties();
+        assert_eq!(mass.volume_mm3, 953_189);
+        assert_eq!(mass.mass_grams, 2574);
+        assert_eq!(mass.center_of_mass, CenterOfMass { x_um: 0, y_um: 0, z_um: 4000 });
+
+        let brass = ActuatorPlate { material: Material::Brass, ..ActuatorPlate::default() };
+        assert_eq!(brass.mass_properties().volume_mm3, mass.volume_mm3);
+        assert_eq!(brass.mass_properties().mass_grams, 8102);
+    }
+
+    #[test]
+    fn test_mass_properties_follow_holes() {
+        // A lone pin is off center, so the mass shifts away from it
+        let one_pin = ActuatorPlate { pin_count: Count::ONE, ..ActuatorPlate::default() };
+        let center = one_pin.mass_properties().center_of_mass;
+        assert!(center.x_um < 0 && center.x_um > -100, "{:?}", center);
+
+        // Recesses take material from the front face, pushing the mass back
+        let plain = ActuatorPlate::default().mass_properties();
+        for bolt_holes in [
+            HoleSpec::Counterbore { diameter: Length::from_mm(18), depth: Length::from_mm(6) },
+            HoleSpec::Countersink { diameter: Length::from_mm(22) },
+        ] {
+            let recessed = ActuatorPlate { bolt_holes, ..ActuatorPlate::default() }.mass_properties();
+            assert!(recessed.volume_mm3 < plain.volume_mm3, "{:?}", bolt_holes);
+            assert!(recessed.center_of_mass.z_um > 4000, "{:?}", bolt_holes);
+        }
+
+        // Counterbore: 4 × π/4 × (18² − 11²) × 6 = 3826.5 mm³
+        let counterbored = ActuatorPlate {
+            bolt_holes: HoleSpec::Counterbore { diameter: Length::from_mm(18), depth: Length::from_mm(6) },
+            ..ActuatorPlate::default()
+        };
+        let removed = plain.volume_mm3 - counterbored.mass_properties().volume_mm3;
+        assert!((3826..=3827).contains(&removed), "{}", removed);
+
+        // Slots: 4 × 11 × 12 × 8 = 4224 mm³ beyond the round holes
+        let slotted = ActuatorPlate {
+            family: PlateFamily::Slotted { slot_length: Length::from_mm(12) },
+            ..ActuatorPlate::default()
+        };
+        assert_eq!(plain.volume_mm3 - slotted.mass_properties().volume_mm3, 4224);
+    }
+
+    #[test]
+    fn test_plates_serialize_with_schema_version() {
+        let json = serde_json::to_value(ActuatorPlate::default()).unwrap();
+        assert_eq!(json["schema_version"], migrate::SCHEMA_VERSION);
+        assert_eq!(json["material"], "aluminum");
+        assert_eq!(serde_json::from_value::<ActuatorPlate>(json).unwrap(), ActuatorPlate::default());
+    }
+
+    #[test]
+    fn test_older_payloads_migrate() {
+        let v1 = serde_json::json!({
+            "schema_version": 1, "bolt_spacing": 80, "bolt_size": "M8", "bracket_height": 200,
+            "bracket_width": 150, "pin_diameter": 12, "plate_thickness": 10,
+            "expected_force_per_pin": 750
+        });
+        let plate: ActuatorPlate = serde_json::from_value(v1.clone()).unwrap();
+        assert_eq!(plate.material, Material::Aluminum);
+        assert_eq!(plate.pin_count, Count::of(6));
+        assert_eq!(plate.bolt_spacing, Length::from_mm(80));
+
+        // Unversioned payloads are read like version 1, and migrating keeps the cache key
+        let mut unversioned = v1.clone();
+        unversioned.as_object_mut().unwrap().remove("schema_version");
+        let same: ActuatorPlate = serde_json::from_value(unversioned).unwrap();
+        assert_eq!(same, plate);
+        let current = serde_json::to_value(plate).unwrap();
+        assert_eq!(serde_json::from_value::<ActuatorPlate>(current).unwrap().cache_key(), plate.cache_key());
+
+        let mut v2 = v1.clone();
+        v2["schema_version"] = 2.into();
+        v2["material"] = "brass".into();
+        let err = serde_json::from_value::<ActuatorPlate>(v2).unwrap_err();
+        assert!(err.to_string().contains("missing field `pin_count`, required from schema_version 2"), "{}", err);
+
+        let mut future = serde_json::to_value(ActuatorPlate::default()).unwrap();
+        future["schema_version"] = (migrate::SCHEMA_VERSION + 1).into();
+        let err = serde_json::from_value::<ActuatorPlate>(future).unwrap_err();
+        assert!(err.to_string().contains("is newer than the supported version"), "{}", err);
+    }
+
+    #[test]
+    fn test_pin_fits_size_the_bore() {
+        // ISO 286-2, 6 to 10 mm: P7 is -9/-24 µm, K7 +5/-10, G7 +20/+5
+        let pin = Length::from_mm(10);
+        let limits = |fit: PinFit| {
+            let bore = fit.bore(pin).unwrap();
+            (bore.smallest().micrometers(), bore.target().micrometers(), bore.largest().micrometers())
+        };
+        assert_eq!(limits(PinFit::Press), (9_976, 9_983, 9_991));
+        assert_eq!(limits(PinFit::Transition), (9_990, 9_997, 10_005));
+        assert_eq!(limits(PinFit::Slip), (10_005, 10_012, 10_020));
+        assert_eq!(PinFit::Press.bore(pin).unwrap().tolerance().to_string(), "P7/h6");
+
+        // Range ends belong to the lower range
+        assert_eq!(PinFit::Slip.bore(Length::from_mm(10)).unwrap().lower_um, 5);
+        assert_eq!(PinFit::Slip.bore(Length::from_micrometers(10_001)).unwrap().lower_um, 6);
+        assert!(PinFit::Press.bore(fits::MAX_FIT_DIAMETER).is_some());
+        assert!(PinFit::Press.bore(Length::from_micrometers(500_001)).is_none());
+        assert!(PinFit::Press.bore(Length::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_pin_fit_sets_the_hole_and_callout() {
+        let plate = ActuatorPlate { pin_fit: Some(PinFit::Slip), ..ActuatorPlate::default() };
+        assert_eq!(plate.pin_hole_diameter(), Length::from_micrometers(10_012));
+        assert_eq!(plate.called_out_tolerances().pin_diameter, Some("G7/h6".parse().unwrap()));
+        assert_ne!(plate.cache_key(), ActuatorPlate::default().cache_key());
+        assert_eq!(ActuatorPlate::default().pin_hole_diameter(), Length::from_mm(10));
+        assert!(ActuatorPlate::default().called_out_tolerances().is_empty());
+
+        let json = serde_json::to_value(plate).unwrap();
+        assert_eq!(json["pin_fit"], "slip");
+        assert_eq!(serde_json::from_value::<ActuatorPlate>(json).unwrap(), plate);
+        assert!(serde_json::to_value(ActuatorPlate::default()).unwrap().get("pin_fit").is_none());
+    }
 }
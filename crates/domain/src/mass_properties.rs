@@ -0,0 +1,133 @@
+//! Volume, mass, and center of mass of the finished plate.
+//!
+//! Worked in integer micrometers so results are exact across targets: the blank
+//! less the pin and bolt holes and any counterbores or countersinks, with the hole
+//! layout of the plate family's KCL template. π is taken as 355/113, good to better than one part in
+//! ten million.
+
+use serde::Serialize;
+
+use crate::{ActuatorPlate, HoleSpec, PlateFamily};
+
+/// Radius of the circle the pins are patterned on, fixed in `plate.kcl`.
+const PIN_CIRCLE_RADIUS_UM: i128 = 50_000;
+/// Mounting bolts per plate, one at each corner.
+const BOLT_COUNT: i128 = 4;
+
+const PI_NUMERATOR: i128 = 355;
+const PI_DENOMINATOR: i128 = 113;
+/// µm³ per mm³
+const UM3_PER_MM3: i128 = 1_000_000_000;
+/// µm³ of material at 1 kg/m³ that weighs one gram
+const UM3_PER_GRAM_AT_1_KG_M3: i128 = 1_000_000_000_000_000;
+
+/// Physical properties of one finished plate.
+#[derive(Copy, Clone, Debug, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MassProperties {
+    /// Volume of material left after machining, in mm³
+    #[cfg_attr(feature = "openapi", schema(example = 953_189))]
+    pub volume_mm3: u32,
+    /// Mass at the material's density, in grams
+    #[cfg_attr(feature = "openapi", schema(example = 2574))]
+    pub mass_grams: u32,
+    pub center_of_mass: CenterOfMass,
+}
+
+/// Where the center of mass sits, in micrometers from the center of the plate's
+/// front face: `x` across the width toward the first pin, `y` up the height, and `z`
+/// into the plate from the face the bolt recesses are cut in.
+#[derive(Copy, Clone, Debug, Default, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CenterOfMass {
+    #[cfg_attr(feature = "openapi", schema(example = 0))]
+    pub x_um: i32,
+    #[cfg_attr(feature = "openapi", schema(example = 0))]
+    pub y_um: i32,
+    #[cfg_attr(feature = "openapi", schema(example = 4000))]
+    pub z_um: i32,
+}
+
+/// Volume of a cylinder of diameter `d` and length `length`.
+fn cylinder(d: i128, length: i128) -> i128 {
+    PI_NUMERATOR * d * d * length / (4 * PI_DENOMINATOR)
+}
+
+/// Material a bolt recess removes beyond the drilled hole of diameter `hole`, as
+/// `(volume, first moment about the front face)`.
+fn recess(holes: &HoleSpec, hole: i128, thickness: i128) -> (i128, i128) {
+    match *holes {
+        HoleSpec::Counterbore { diameter, depth } => {
+            let (d, depth) = (diameter.micrometers() as i128, (depth.micrometers() as i128).min(thickness));
+            if d <= hole {
+                return (0, 0);
+            }
+            let volume = cylinder(d, depth) - cylinder(hole, depth);
+            (volume, volume * depth / 2)
+        }
+        HoleSpec::Countersink { diameter } => {
+            // A 90° cone from `d` at the face down to the hole, less the hole itself
+            let d = diameter.micrometers() as i128;
+            if d <= hole {
+                return (0, 0);
+            }
+            let depth = ((d - hole) / 2).min(thickness);
+            let pi_depth = PI_NUMERATOR * depth;
+            let volume = pi_depth * (d * d + d * hole - 2 * hole * hole) / (12 * PI_DENOMINATOR);
+            let moment = pi_depth * depth * (d * d + 2 * d * hole - 3 * hole * hole) / (48 * PI_DENOMINATOR);
+            (volume, moment)
+        }
+        HoleSpec::Through | HoleSpec::Tapped { .. } => (0, 0),
+    }
+}
+
+/// Divide, rounding to the nearest integer.
+fn div_round(numerator: i128, denominator: i128) -> i128 {
+    let half = denominator / 2;
+    if numerator >= 0 {
+        (numerator + half) / denominator
+    } else {
+        (numerator - half) / denominator
+    }
+}
+
+impl ActuatorPlate {
+    /// Volume, mass, and center of mass of the finished plate.
+    pub fn mass_properties(&self) -> MassProperties {
+        let width = self.bracket_width.micrometers() as i128;
+        let height = self.bracket_height.micrometers() as i128;
+        let thickness = self.plate_thickness.micrometers() as i128;
+        let pin = self.pin_diameter.micrometers() as i128;
+        let bolt = (self.bolt_holes.drill_diameter_mm(self.bolt_size) * 1000.0 + 0.5) as i128;
+
+        let pin_volume = cylinder(pin, thickness);
+        let pins = self.pin_count.get() as i128;
+        let (recess_volume, recess_moment) = recess(&self.bolt_holes, bolt, thickness);
+        // A slot is a round hole stretched by its length: add the rectangle between the ends
+        let bolt_hole_volume = match self.family {
+            PlateFamily::Slotted { slot_length } => {
+                cylinder(bolt, thickness) + bolt * slot_length.micrometers() as i128 * thickness
+            }
+            PlateFamily::Standard | PlateFamily::DualPin { .. } => cylinder(bolt, thickness),
+        };
+        let through = width * height * thickness - pins * pin_volume - BOLT_COUNT * bolt_hole_volume;
+        let volume = (through - BOLT_COUNT * recess_volume).max(1);
+
+        // The blank, bolt pattern, and recesses are symmetric about the center. Two
+        // or more pins are too, but a lone pin on the circle sits off to one side.
+        let on_circle = !matches!(self.family, PlateFamily::DualPin { .. });
+        let moment_x = if pins == 1 && on_circle { -pin_volume * PIN_CIRCLE_RADIUS_UM } else { 0 };
+        let moment_z = through * thickness / 2 - BOLT_COUNT * recess_moment;
+
+        let density = self.material.density_kg_m3() as i128;
+        MassProperties {
+            volume_mm3: div_round(volume, UM3_PER_MM3) as u32,
+            mass_grams: div_round(volume * density, UM3_PER_GRAM_AT_1_KG_M3) as u32,
+            center_of_mass: CenterOfMass {
+                x_um: div_round(moment_x, volume) as i32,
+                y_um: 0,
+                z_um: div_round(moment_z, volume) as i32,
+            },
+        }
+    }
+}
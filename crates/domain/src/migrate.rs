@@ -0,0 +1,153 @@
+//! Versioned plate payloads, so JSON saved by older versions still loads.
+//!
+//! Every serialized [`ActuatorPlate`] carries a `schema_version`. Reading goes
+//! through [`PlateRecord`], which accepts any version up to [`SCHEMA_VERSION`] and
+//! fills in what older versions didn't have:
+//!
+//! | Version | Fields |
+//! |---------|--------|
+//! | 1 | dimensions, bolt size, and force per pin; every plate was aluminum with six pins |
+//! | 2 | adds `material` and `pin_count` |
+//! | 3 | adds optional `bolt_holes` and `tolerances` |
+//! | 4 | adds optional `family` |
+//! | 5 | adds optional `pin_fit` |
+//!
+//! Counterbore and countersink sizes may be left out in any version; they're
+//! filled in from the bolt catalog for the plate's bolt size.
+//!
+//! Payloads without a `schema_version` predate it; fields they leave out take the
+//! version 1 defaults. Cache keys don't include the version, so migrating a plate
+//! doesn't change its key.
+
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+use crate::{ActuatorPlate, BoltSize, Count, HoleRecord, Length, Material, Newtons, PinFit, PlateFamily, Tolerances};
+
+/// Version written into every serialized plate.
+pub const SCHEMA_VERSION: u32 = 5;
+
+/// Material of every version 1 plate.
+const V1_MATERIAL: Material = Material::Aluminum;
+/// Pins on every version 1 plate.
+const V1_PIN_COUNT: Count = Count::of(6);
+
+/// Why a plate payload couldn't be upgraded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MigrateError {
+    /// Written by a newer version than this one understands.
+    UnsupportedVersion(u32),
+    /// A field the payload's version requires is missing.
+    MissingField { field: &'static str, version: u32 },
+}
+
+impl fmt::Display for MigrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrateError::UnsupportedVersion(version) => write!(
+                f,
+                "plate schema_version {} is newer than the supported version {}",
+                version, SCHEMA_VERSION
+            ),
+            MigrateError::MissingField { field, version } => {
+                write!(f, "missing field `{}`, required from schema_version {}", field, version)
+            }
+        }
+    }
+}
+
+/// A plate as serialized, in any schema version.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct PlateRecord {
+    /// `None` for payloads from before versioning
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+    pub bolt_spacing: Length,
+    pub bolt_size: BoltSize,
+    pub bracket_height: Length,
+    pub bracket_width: Length,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<Material>,
+    pub pin_diameter: Length,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin_count: Option<Count>,
+    pub plate_thickness: Length,
+    pub expected_force_per_pin: Newtons,
+    #[serde(default)]
+    pub bolt_holes: HoleRecord,
+    #[serde(default)]
+    pub tolerances: Tolerances,
+    #[serde(default)]
+    pub family: PlateFamily,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin_fit: Option<PinFit>,
+}
+
+impl PlateRecord {
+    /// Upgrade to the current [`ActuatorPlate`].
+    pub fn migrate(self) -> Result<ActuatorPlate, MigrateError> {
+        let version = self.schema_version;
+        if let Some(version) = version.filter(|v| *v > SCHEMA_VERSION) {
+            return Err(MigrateError::UnsupportedVersion(version));
+        }
+        // Fields added in version 2 fall back to the version 1 defaults, unless the
+        // payload says it is from version 2 or later
+        let since_v2 = |field: &'static str| match version {
+            Some(version) if version >= 2 => Err(MigrateError::MissingField { field, version }),
+            _ => Ok(()),
+        };
+        let material = match self.material {
+            Some(material) => material,
+            None => since_v2("material").map(|()| V1_MATERIAL)?,
+        };
+        let pin_count = match self.pin_count {
+            Some(pins) => pins,
+            None => since_v2("pin_count").map(|()| V1_PIN_COUNT)?,
+        };
+
+        Ok(ActuatorPlate {
+            bolt_spacing: self.bolt_spacing,
+            bolt_size: self.bolt_size,
+            bracket_height: self.bracket_height,
+            bracket_width: self.bracket_width,
+            material,
+            pin_diameter: self.pin_diameter,
+            pin_count,
+            plate_thickness: self.plate_thickness,
+            expected_force_per_pin: self.expected_force_per_pin,
+            bolt_holes: self.bolt_holes.resolve(self.bolt_size),
+            tolerances: self.tolerances,
+            family: self.family,
+            pin_fit: self.pin_fit,
+        })
+    }
+}
+
+impl TryFrom<PlateRecord> for ActuatorPlate {
+    type Error = MigrateError;
+
+    fn try_from(record: PlateRecord) -> Result<Self, Self::Error> {
+        record.migrate()
+    }
+}
+
+impl From<ActuatorPlate> for PlateRecord {
+    fn from(plate: ActuatorPlate) -> Self {
+        PlateRecord {
+            schema_version: Some(SCHEMA_VERSION),
+            bolt_spacing: plate.bolt_spacing,
+            bolt_size: plate.bolt_size,
+            bracket_height: plate.bracket_height,
+            bracket_width: plate.bracket_width,
+            material: Some(plate.material),
+            pin_diameter: plate.pin_diameter,
+            pin_count: Some(plate.pin_count),
+            plate_thickness: plate.plate_thickness,
+            expected_force_per_pin: plate.expected_force_per_pin,
+            bolt_holes: plate.bolt_holes.into(),
+            tolerances: plate.tolerances,
+            family: plate.family,
+            pin_fit: plate.pin_fit,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Named standard plates that users can start from.
+//!
+//! Presets are plain data: add an entry to [`PRESETS`] and it shows up in
+//! `GET /api/presets` and the preset dropdown without other changes.
+
+use serde::Serialize;
+
+use crate::{ActuatorPlate, BoltSize, Count, HoleSpec, Length, Material, Newtons, PlateFamily, Tolerances};
+
+/// A standard plate configuration with a display name.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Preset {
+    /// Stable identifier, e.g. `compact-40`
+    pub id: &'static str,
+    /// Name shown in the UI
+    pub name: &'static str,
+    /// One-line summary of what the preset is for
+    pub description: &'static str,
+    /// The plate parameters
+    pub plate: ActuatorPlate,
+}
+
+/// Every preset, smallest first.
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        id: "compact-40",
+        name: "Compact 40",
+        description: "Small aluminum plate for light-duty actuators",
+        plate: ActuatorPlate {
+            bolt_spacing: Length::from_mm(40),
+            bolt_size: BoltSize::M6,
+            bracket_height: Length::from_mm(120),
+            bracket_width: Length::from_mm(80),
+            material: Material::Aluminum,
+            pin_diameter: Length::from_mm(8),
+            pin_count: Count::of(2),
+            plate_thickness: Length::from_mm(6),
+            expected_force_per_pin: Newtons(200),
+            bolt_holes: HoleSpec::Through,
+            tolerances: Tolerances::NONE,
+            family: PlateFamily::Standard,
+            pin_fit: None,
+        },
+    },
+    Preset {
+        id: "standard-60",
+        name: "Standard 60",
+        description: "General-purpose aluminum plate (the form defaults)",
+        plate: ActuatorPlate {
+            bolt_spacing: Length::from_mm(60),
+            bolt_size: BoltSize::M10,
+            bracket_height: Length::from_mm(400),
+            bracket_width: Length::from_mm(300),
+            material: Material::Aluminum,
+            pin_diameter: Length::from_mm(10),
+            pin_count: Count::of(6),
+            plate_thickness: Length::from_mm(8),
+            expected_force_per_pin: Newtons(500),
+            bolt_holes: HoleSpec::Through,
+            tolerances: Tolerances::NONE,
+            family: PlateFamily::Standard,
+            pin_fit: None,
+        },
+    },
+    Preset {
+        id: "stainless-60",
+        name: "Stainless 60",
+        description: "Corrosion-resistant plate for washdown and outdoor use",
+        plate: ActuatorPlate {
+            bolt_spacing: Length::from_mm(60),
+            bolt_size: BoltSize::M10,
+            bracket_height: Length::from_mm(300),
+            bracket_width: Length::from_mm(200),
+            material: Material::StainlessSteel,
+            pin_diameter: Length::from_mm(12),
+            pin_count: Count::of(4),
+            plate_thickness: Length::from_mm(10),
+            expected_force_per_pin: Newtons(1000),
+            bolt_holes: HoleSpec::Through,
+            tolerances: Tolerances::NONE,
+            family: PlateFamily::Standard,
+            pin_fit: None,
+        },
+    },
+    Preset {
+        id: "heavy-duty-80",
+        name: "Heavy Duty 80",
+        description: "Thick carbon steel plate for high-force actuators",
+        plate: ActuatorPlate {
+            bolt_spacing: Length::from_mm(80),
+            bolt_size: BoltSize::M12,
+            bracket_height: Length::from_mm(500),
+            bracket_width: Length::from_mm(400),
+            material: Material::CarbonSteel,
+            pin_diameter: Length::from_mm(20),
+            pin_count: Count::of(4),
+            plate_thickness: Length::from_mm(16),
+            expected_force_per_pin: Newtons(3000),
+            bolt_holes: HoleSpec::Through,
+            tolerances: Tolerances::NONE,
+            family: PlateFamily::Standard,
+            pin_fit: None,
+        },
+    },
+];
+
+/// Look up a preset by its `id`.
+pub fn find(id: &str) -> Option<&'static Preset> {
+    PRESETS.iter().find(|preset| preset.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_ids_are_unique() {
+        for (i, preset) in PRESETS.iter().enumerate() {
+            assert!(
+                PRESETS[i + 1..].iter().all(|other| other.id != preset.id),
+                "duplicate preset id {}",
+                preset.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_standard_preset_matches_defaults() {
+        assert_eq!(find("standard-60").unwrap().plate, ActuatorPlate::default());
+        assert!(find("no-such-preset").is_none());
+    }
+}
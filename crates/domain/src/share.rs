@@ -0,0 +1,247 @@
+//! Plates encoded as URL query strings, for shareable links.
+//!
+//! Every field has a short key, e.g. `bs=60&bd=10&bh=400&bw=300&m=aluminum&pd=10&pc=6&pt=8&f=500`.
+//! Lengths are millimeters, `bd` is the bolt size's nominal diameter, and values are
+//! percent-encoded. Through holes, general tolerances, the standard family, and no pin
+//! fit are the defaults and are left out; otherwise `h` holds the holes (`cb:11:6.5`,
+//! `cs:20`, `tap:M10x1.25`), `tbs`, `tbh`, `tbw`, `tpd`, and `tpt` the tolerance
+//! callouts, `fam` the family (`slot:12`, `dual:40`), and `fit` the pin fit. Decoding ignores
+//! unknown keys and takes missing ones from [`ActuatorPlate::default`], so a link
+//! keeps working when other parameters are added to the URL.
+//!
+//! [`serialize`] and [`deserialize`] let a plate field be written as its query
+//! string with `#[serde(with = "domain::share")]`.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{ActuatorPlate, BoltSize, Count, Dimension, HoleSpec, Length, Material, Newtons, PinFit, PlateFamily};
+
+/// Why a query string couldn't be read as a plate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShareError {
+    /// The key whose value is malformed
+    pub key: String,
+    /// The value as given, after percent-decoding
+    pub value: String,
+}
+
+impl fmt::Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid value {:?} for `{}` in the plate link", self.value, self.key)
+    }
+}
+
+const fn tolerance_key(dimension: Dimension) -> &'static str {
+    match dimension {
+        Dimension::BoltSpacing => "tbs",
+        Dimension::BracketHeight => "tbh",
+        Dimension::BracketWidth => "tbw",
+        Dimension::PinDiameter => "tpd",
+        Dimension::PlateThickness => "tpt",
+    }
+}
+
+fn encode_holes(holes: &HoleSpec) -> Option<String> {
+    match holes {
+        HoleSpec::Through => None,
+        HoleSpec::Counterbore { diameter, depth } => Some(format!("cb:{}:{}", diameter, depth)),
+        HoleSpec::Countersink { diameter } => Some(format!("cs:{}", diameter)),
+        HoleSpec::Tapped { thread } => Some(format!("tap:{}", thread)),
+    }
+}
+
+fn decode_holes(value: &str) -> Option<HoleSpec> {
+    let mut parts = value.split(':');
+    let holes = match (parts.next()?, parts.next(), parts.next()) {
+        ("through", None, None) => HoleSpec::Through,
+        ("cb", Some(diameter), Some(depth)) => HoleSpec::Counterbore {
+            diameter: diameter.parse().ok()?,
+            depth: depth.parse().ok()?,
+        },
+        ("cs", Some(diameter), None) => HoleSpec::Countersink {
+            diameter: diameter.parse().ok()?,
+        },
+        ("tap", Some(thread), None) => HoleSpec::Tapped {
+            thread: thread.parse().ok()?,
+        },
+        _ => return None,
+    };
+    parts.next().is_none().then_some(holes)
+}
+
+fn encode_family(family: &PlateFamily) -> Option<String> {
+    match family {
+        PlateFamily::Standard => None,
+        PlateFamily::Slotted { slot_length } => Some(format!("slot:{}", slot_length)),
+        PlateFamily::DualPin { pin_spacing } => Some(format!("dual:{}", pin_spacing)),
+    }
+}
+
+fn decode_family(value: &str) -> Option<PlateFamily> {
+    match value.split_once(':') {
+        None if value == "standard" => Some(PlateFamily::Standard),
+        Some(("slot", length)) => Some(PlateFamily::Slotted {
+            slot_length: length.parse().ok()?,
+        }),
+        Some(("dual", spacing)) => Some(PlateFamily::DualPin {
+            pin_spacing: spacing.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
+
+/// Append `value` to `out`, percent-encoding everything but unreserved characters.
+fn encode_value(out: &mut String, value: &str) {
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+}
+
+/// Undo percent-encoding; `+` is a space, as in HTML forms.
+fn decode_value(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.bytes();
+    while let Some(byte) = rest.next() {
+        match byte {
+            b'%' => {
+                let hex = [rest.next()?, rest.next()?];
+                let hex = core::str::from_utf8(&hex).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            }
+            b'+' => bytes.push(b' '),
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+impl ActuatorPlate {
+    /// The plate as a query string, without the leading `?`.
+    pub fn to_query(&self) -> String {
+        let mut pairs: Vec<(&str, String)> = Vec::from([
+            ("bs", self.bolt_spacing.to_string()),
+            ("bd", self.bolt_size.nominal_diameter_mm().to_string()),
+            ("bh", self.bracket_height.to_string()),
+            ("bw", self.bracket_width.to_string()),
+            ("m", self.material.name().to_string()),
+            ("pd", self.pin_diameter.to_string()),
+            ("pc", self.pin_count.to_string()),
+            ("pt", self.plate_thickness.to_string()),
+            ("f", self.expected_force_per_pin.0.to_string()),
+        ]);
+        if let Some(holes) = encode_holes(&self.bolt_holes) {
+            pairs.push(("h", holes));
+        }
+        for (dimension, tolerance) in self.tolerances.iter() {
+            pairs.push((tolerance_key(dimension), tolerance.to_string()));
+        }
+        if let Some(family) = encode_family(&self.family) {
+            pairs.push(("fam", family));
+        }
+        if let Some(fit) = self.pin_fit {
+            pairs.push(("fit", fit.name().to_string()));
+        }
+
+        let mut query = String::new();
+        for (key, value) in pairs {
+            if !query.is_empty() {
+                query.push('&');
+            }
+            query.push_str(key);
+            query.push('=');
+            encode_value(&mut query, &value);
+        }
+        query
+    }
+
+    /// Read a plate from a query string, with or without the leading `?`.
+    pub fn from_query(query: &str) -> Result<Self, ShareError> {
+        let mut plate = ActuatorPlate::default();
+        let query = query.strip_prefix('?').unwrap_or(query);
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, raw) = pair.split_once('=').unwrap_or((pair, ""));
+            let invalid = |value: &str| ShareError {
+                key: key.to_string(),
+                value: value.to_string(),
+            };
+            let value = decode_value(raw).ok_or_else(|| invalid(raw))?;
+            let length = || value.parse::<Length>().map_err(|_| invalid(&value));
+            match key {
+                "bs" => plate.bolt_spacing = length()?,
+                "bd" => {
+                    plate.bolt_size = BoltSize::ALL
+                        .into_iter()
+                        .find(|size| value == size.nominal_diameter_mm().to_string())
+                        .ok_or_else(|| invalid(&value))?
+                }
+                "bh" => plate.bracket_height = length()?,
+                "bw" => plate.bracket_width = length()?,
+                "m" => {
+                    plate.material = Material::ALL
+                        .into_iter()
+                        .find(|material| material.name() == value)
+                        .ok_or_else(|| invalid(&value))?
+                }
+                "pd" => plate.pin_diameter = length()?,
+                "pc" => {
+                    plate.pin_count = value
+                        .parse()
+                        .ok()
+                        .and_then(|pins| Count::new(pins).ok())
+                        .ok_or_else(|| invalid(&value))?
+                }
+                "pt" => plate.plate_thickness = length()?,
+                "f" => plate.expected_force_per_pin = Newtons(value.parse().map_err(|_| invalid(&value))?),
+                "h" => plate.bolt_holes = decode_holes(&value).ok_or_else(|| invalid(&value))?,
+                "fam" => plate.family = decode_family(&value).ok_or_else(|| invalid(&value))?,
+                "fit" => {
+                    plate.pin_fit = Some(
+                        PinFit::ALL
+                            .into_iter()
+                            .find(|fit| fit.name() == value)
+                            .ok_or_else(|| invalid(&value))?,
+                    )
+                }
+                _ => {
+                    let Some(dimension) = Dimension::ALL.into_iter().find(|d| tolerance_key(*d) == key) else {
+                        continue;
+                    };
+                    let tolerance = value.parse().map_err(|_| invalid(&value))?;
+                    plate.tolerances.set(dimension, Some(tolerance));
+                }
+            }
+        }
+        Ok(plate)
+    }
+}
+
+/// Serialize a plate as its query string.
+pub fn serialize<S: serde::Serializer>(plate: &ActuatorPlate, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&plate.to_query())
+}
+
+/// Deserialize a plate from its query string.
+pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<ActuatorPlate, D::Error> {
+    struct QueryVisitor;
+
+    impl serde::de::Visitor<'_> for QueryVisitor {
+        type Value = ActuatorPlate;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a plate query string such as \"bs=60&bd=10\"")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<ActuatorPlate, E> {
+            ActuatorPlate::from_query(v).map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_str(QueryVisitor)
+}
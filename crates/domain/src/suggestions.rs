@@ -0,0 +1,155 @@
+//! Recommended values for fields that follow from the rest of the plate.
+//!
+//! Suggestions are plain data: each [`Rule`] names the field it fills and the
+//! reason shown beside the value, so the form can offer any rule in [`RULES`]
+//! without knowing about it. They are starting points rather than limits; the
+//! plate is validated as usual once a suggestion is applied.
+
+use alloc::vec::Vec;
+use serde::Serialize;
+
+use crate::{ActuatorPlate, Length};
+
+/// A recommended value for one field of a plate.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Suggestion {
+    /// Form field name, as in the `fields` of a validation error (e.g. "bracketHeight")
+    pub field: &'static str,
+    /// Property of the plate in JSON (e.g. "bracket_height")
+    pub key: &'static str,
+    /// The recommended value, in `unit`
+    #[cfg_attr(feature = "openapi", schema(example = 45))]
+    pub value: u32,
+    /// Unit of `value` (e.g. "mm")
+    pub unit: &'static str,
+    /// Why this value, e.g. "Three quarters of the bolt spacing"
+    pub reason: &'static str,
+}
+
+/// How one field's suggestion is worked out.
+pub struct Rule {
+    pub field: &'static str,
+    pub key: &'static str,
+    pub unit: &'static str,
+    /// The value and its reason, or `None` when the rule has nothing to offer
+    pub suggest: fn(&ActuatorPlate) -> Option<(u32, &'static str)>,
+    /// The plate's current value, in `unit`
+    pub current: fn(&ActuatorPlate) -> u32,
+}
+
+/// A band of per-pin loads and the plate thickness that suits it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoadClass {
+    /// Largest force per pin in the class, in Newtons; `None` for the last class
+    pub max_force_per_pin_n: Option<u32>,
+    pub thickness_mm: u16,
+    /// Shown as the suggestion's reason
+    pub reason: &'static str,
+}
+
+/// Every load class, lightest first.
+pub const LOAD_CLASSES: &[LoadClass] = &[
+    LoadClass { max_force_per_pin_n: Some(250), thickness_mm: 6, reason: "Light load class (up to 250 N per pin)" },
+    LoadClass { max_force_per_pin_n: Some(750), thickness_mm: 8, reason: "Medium load class (up to 750 N per pin)" },
+    LoadClass { max_force_per_pin_n: Some(1500), thickness_mm: 10, reason: "Heavy load class (up to 1500 N per pin)" },
+    LoadClass { max_force_per_pin_n: Some(5000), thickness_mm: 16, reason: "Very heavy load class (up to 5000 N per pin)" },
+    LoadClass { max_force_per_pin_n: None, thickness_mm: 25, reason: "Extreme load class (over 5000 N per pin)" },
+];
+
+/// The class a per-pin load falls in.
+pub fn load_class(force_per_pin_n: u32) -> &'static LoadClass {
+    LOAD_CLASSES
+        .iter()
+        .find(|class| class.max_force_per_pin_n.is_none_or(|max| force_per_pin_n <= max))
+        .unwrap_or(&LOAD_CLASSES[LOAD_CLASSES.len() - 1])
+}
+
+/// Every rule, in form order.
+pub const RULES: &[Rule] = &[
+    Rule {
+        field: "bracketHeight",
+        key: "bracket_height",
+        unit: "mm",
+        suggest: suggest_bracket_height,
+        current: |plate| plate.bracket_height.whole_mm_ceil() as u32,
+    },
+    Rule {
+        field: "plateThickness",
+        key: "plate_thickness",
+        unit: "mm",
+        suggest: |plate| {
+            let class = load_class(plate.expected_force_per_pin.0);
+            Some((class.thickness_mm as u32, class.reason))
+        },
+        current: |plate| plate.plate_thickness.whole_mm_ceil() as u32,
+    },
+];
+
+/// Three quarters of the bolt spacing, but never less than the pins need: three
+/// pin diameters each, as the pin clearance check requires.
+fn suggest_bracket_height(plate: &ActuatorPlate) -> Option<(u32, &'static str)> {
+    let from_spacing = Length::from_micrometers(plate.bolt_spacing.micrometers() / 4 * 3).whole_mm_ceil() as u32;
+    let for_pins = u32::try_from(plate.pin_count.times(plate.pin_diameter.whole_mm_ceil() as u32) * 3).ok()?;
+    Some(if for_pins > from_spacing {
+        (for_pins, "Three pin diameters of room for each pin")
+    } else {
+        (from_spacing, "Three quarters of the bolt spacing")
+    })
+}
+
+/// Suggestions for `plate`, leaving out fields that already have the suggested value.
+pub fn suggest(plate: &ActuatorPlate) -> Vec<Suggestion> {
+    RULES
+        .iter()
+        .filter_map(|rule| {
+            let (value, reason) = (rule.suggest)(plate)?;
+            (value != (rule.current)(plate)).then_some(Suggestion {
+                field: rule.field,
+                key: rule.key,
+                value,
+                unit: rule.unit,
+                reason,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{presets::PRESETS, Count, Newtons};
+
+    #[test]
+    fn test_bracket_height_follows_bolt_spacing() {
+        let plate = ActuatorPlate {
+            bolt_spacing: Length::from_mm(60),
+            pin_count: Count::of(1),
+            pin_diameter: Length::from_mm(5),
+            ..ActuatorPlate::default()
+        };
+        let height = suggest(&plate).into_iter().find(|s| s.key == "bracket_height").unwrap();
+        assert_eq!((height.field, height.value, height.unit), ("bracketHeight", 45, "mm"));
+
+        // Six 10 mm pins need 180 mm whatever the spacing
+        let height = suggest(&ActuatorPlate::default()).into_iter().find(|s| s.key == "bracket_height").unwrap();
+        assert_eq!(height.value, 180);
+        assert_eq!(height.reason, "Three pin diameters of room for each pin");
+    }
+
+    #[test]
+    fn test_thickness_follows_load_class() {
+        assert_eq!(load_class(250).thickness_mm, 6);
+        assert_eq!(load_class(251).thickness_mm, 8);
+        assert_eq!(load_class(100_000).thickness_mm, 25);
+
+        let plate = ActuatorPlate { expected_force_per_pin: Newtons(3000), ..ActuatorPlate::default() };
+        let thickness = suggest(&plate).into_iter().find(|s| s.field == "plateThickness").unwrap();
+        assert_eq!(thickness.value, 16);
+
+        // The presets are already as thick as their load class suggests
+        for preset in PRESETS {
+            assert!(suggest(&preset.plate).iter().all(|s| s.field != "plateThickness"), "{}", preset.id);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Random plates for property tests, behind the `testing` feature.
+//!
+//! [`ActuatorPlate`]'s [`Arbitrary`] impl covers every field and variant, with
+//! lengths from zero to just past the default size limits in whole millimeters
+//! and in micrometers, so a run sees both valid plates and every kind of invalid
+//! one. The strategies for single fields are public for tests that hold the rest
+//! of a plate fixed.
+
+use proptest::prelude::*;
+
+use crate::{
+    ActuatorPlate, BoltSize, Count, HoleSpec, IsoThread, Length, Material, Newtons, PinFit, PlateFamily,
+    Tolerance, Tolerances,
+};
+
+/// A length up to `max_mm`, either whole millimeters or any micrometer value.
+pub fn length(max_mm: u16) -> impl Strategy<Value = Length> {
+    prop_oneof![
+        (0..=max_mm).prop_map(Length::from_mm),
+        (0..=max_mm as u32 * 1_000).prop_map(Length::from_micrometers),
+    ]
+}
+
+/// A thread on any bolt size, usually at a standard pitch.
+pub fn thread() -> impl Strategy<Value = IsoThread> {
+    (prop::sample::select(&BoltSize::ALL[..]), any::<prop::sample::Index>(), 200u16..=2_000).prop_map(
+        |(size, index, odd_pitch)| {
+            let standard = size.standard_pitches_um();
+            // One in five threads gets a nonstandard pitch
+            let pitch_um = if index.index(5) == 0 { odd_pitch } else { *index.get(standard) };
+            IsoThread { size, pitch_um }
+        },
+    )
+}
+
+pub fn hole_spec() -> impl Strategy<Value = HoleSpec> {
+    prop_oneof![
+        4 => Just(HoleSpec::Through),
+        1 => (length(40), length(20)).prop_map(|(diameter, depth)| HoleSpec::Counterbore { diameter, depth }),
+        1 => length(40).prop_map(|diameter| HoleSpec::Countersink { diameter }),
+        1 => thread().prop_map(|thread| HoleSpec::Tapped { thread }),
+    ]
+}
+
+pub fn tolerance() -> impl Strategy<Value = Tolerance> {
+    prop_oneof![
+        (0u32..=2_000).prop_map(|um| Tolerance::Symmetric(Length::from_micrometers(um))),
+        (-2_000i32..=2_000, -2_000i32..=2_000)
+            .prop_map(|(upper_um, lower_um)| Tolerance::Deviation { upper_um, lower_um }),
+        prop::sample::select(&["H7", "H7/g6", "H8/f7", "G7/h6", "JS9"][..])
+            .prop_map(|fit| fit.parse().expect("a valid ISO 286 fit")),
+    ]
+}
+
+pub fn tolerances() -> impl Strategy<Value = Tolerances> {
+    let one = || prop::option::weighted(0.1, tolerance());
+    (one(), one(), one(), one(), one()).prop_map(
+        |(bolt_spacing, bracket_height, bracket_width, pin_diameter, plate_thickness)| Tolerances {
+            bolt_spacing,
+            bracket_height,
+            bracket_width,
+            pin_diameter,
+            plate_thickness,
+        },
+    )
+}
+
+pub fn family() -> impl Strategy<Value = PlateFamily> {
+    prop_oneof![
+        2 => Just(PlateFamily::Standard),
+        1 => length(60).prop_map(|slot_length| PlateFamily::Slotted { slot_length }),
+        1 => length(300).prop_map(|pin_spacing| PlateFamily::DualPin { pin_spacing }),
+    ]
+}
+
+pub fn pin_fit() -> impl Strategy<Value = Option<PinFit>> {
+    prop::option::weighted(0.2, prop::sample::select(&PinFit::ALL[..]))
+}
+
+/// A plate with lengths from `lengths` (bolt spacing, bracket height, bracket
+/// width, pin diameter, plate thickness) and forces from `force`; everything else
+/// is drawn from its whole range.
+fn plate(
+    lengths: [BoxedStrategy<Length>; 5],
+    force: impl Strategy<Value = u32> + 'static,
+) -> impl Strategy<Value = ActuatorPlate> {
+    let [bolt_spacing, bracket_height, bracket_width, pin_diameter, plate_thickness] = lengths;
+    (
+        bolt_spacing,
+        prop::sample::select(&BoltSize::ALL[..]),
+        bracket_height,
+        bracket_width,
+        prop::sample::select(&Material::ALL[..]),
+        pin_diameter,
+        (1u32..=14).prop_map(Count::of),
+        plate_thickness,
+        force.prop_map(Newtons),
+        (hole_spec(), prop::bool::weighted(0.8)),
+        tolerances(),
+        (family(), pin_fit()),
+    )
+        .prop_map(
+            |(
+                bolt_spacing,
+                bolt_size,
+                bracket_height,
+                bracket_width,
+                material,
+                pin_diameter,
+                pin_count,
+                plate_thickness,
+                expected_force_per_pin,
+                (mut bolt_holes, thread_fits_bolt),
+                tolerances,
+                (family, pin_fit),
+            )| {
+                // Most tapped holes take the plate's own bolt
+                if let HoleSpec::Tapped { thread } = &mut bolt_holes {
+                    if thread_fits_bolt && thread.size != bolt_size {
+                        *thread = IsoThread::coarse(bolt_size);
+                    }
+                }
+                ActuatorPlate {
+                    bolt_spacing,
+                    bolt_size,
+                    bracket_height,
+                    bracket_width,
+                    material,
+                    pin_diameter,
+                    pin_count,
+                    plate_thickness,
+                    expected_force_per_pin,
+                    bolt_holes,
+                    tolerances,
+                    family,
+                    pin_fit,
+                }
+            },
+        )
+}
+
+/// A length between `min_mm` and `max_mm`, at micrometer resolution.
+fn length_between(min_mm: u16, max_mm: u16) -> BoxedStrategy<Length> {
+    (min_mm as u32 * 1_000..=max_mm as u32 * 1_000).prop_map(Length::from_micrometers).boxed()
+}
+
+/// Half the plates range over every length, which is mostly invalid; the other
+/// half stay near the default plate's proportions, where many pass validation.
+impl Arbitrary for ActuatorPlate {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let anything = [
+            length(1_100).boxed(),
+            length(1_100).boxed(),
+            length(1_100).boxed(),
+            length(110).boxed(),
+            length(110).boxed(),
+        ];
+        let plausible = [
+            length_between(30, 120),
+            length_between(200, 600),
+            length_between(150, 450),
+            length_between(5, 20),
+            length_between(5, 20),
+        ];
+        prop_oneof![plate(anything, 0u32..=110_000), plate(plausible, 50u32..=1_500)].boxed()
+    }
+}
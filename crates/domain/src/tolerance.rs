@@ -0,0 +1,299 @@
+//! Tolerances and fits on the plate's dimensions.
+
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+use crate::Length;
+
+/// A toleranced dimension of [`ActuatorPlate`](crate::ActuatorPlate).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dimension {
+    BoltSpacing,
+    BracketHeight,
+    BracketWidth,
+    PinDiameter,
+    PlateThickness,
+}
+
+impl Dimension {
+    pub const ALL: [Dimension; 5] = [
+        Dimension::BoltSpacing,
+        Dimension::BracketHeight,
+        Dimension::BracketWidth,
+        Dimension::PinDiameter,
+        Dimension::PlateThickness,
+    ];
+
+    /// The plate field this dimension is stored in, e.g. `bolt_spacing`.
+    pub const fn field_name(self) -> &'static str {
+        match self {
+            Dimension::BoltSpacing => "bolt_spacing",
+            Dimension::BracketHeight => "bracket_height",
+            Dimension::BracketWidth => "bracket_width",
+            Dimension::PinDiameter => "pin_diameter",
+            Dimension::PlateThickness => "plate_thickness",
+        }
+    }
+}
+
+/// Permitted variation of one dimension.
+///
+/// Serializes as the drawing callout: `"±0.1"` (or `"+-0.1"`), limit deviations
+/// such as `"+0.2/-0.1"` or `"0/-0.05"`, or an ISO 286 tolerance class or fit such
+/// as `"H7"` or `"H7/g6"`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Tolerance {
+    /// Equal bilateral tolerance, e.g. ±0.1 mm is `Symmetric(100 µm)`.
+    Symmetric(Length),
+    /// Upper and lower deviation from the nominal size, in micrometers.
+    Deviation { upper_um: i32, lower_um: i32 },
+    /// ISO 286 tolerance class for a hole, optionally with the mating shaft's class.
+    Fit { hole: FitClass, shaft: Option<FitClass> },
+}
+
+impl Tolerance {
+    /// Upper and lower deviation in micrometers. `None` for fits, whose limits
+    /// depend on the ISO 286 tables.
+    pub fn deviations_um(&self) -> Option<(i32, i32)> {
+        match *self {
+            Tolerance::Symmetric(band) => {
+                let um = i32::try_from(band.micrometers()).unwrap_or(i32::MAX);
+                Some((um, -um))
+            }
+            Tolerance::Deviation { upper_um, lower_um } => Some((upper_um, lower_um)),
+            Tolerance::Fit { .. } => None,
+        }
+    }
+}
+
+/// ISO 286 fundamental deviations, e.g. the `H` in `H7`. Holes use the uppercase
+/// letters, shafts the lowercase ones.
+const DEVIATIONS: [&str; 28] = [
+    "A", "B", "C", "CD", "D", "E", "EF", "F", "FG", "G", "H", "JS", "J", "K", "M", "N", "P", "R",
+    "S", "T", "U", "V", "X", "Y", "Z", "ZA", "ZB", "ZC",
+];
+const SHAFT_DEVIATIONS: [&str; 28] = [
+    "a", "b", "c", "cd", "d", "e", "ef", "f", "fg", "g", "h", "js", "j", "k", "m", "n", "p", "r",
+    "s", "t", "u", "v", "x", "y", "z", "za", "zb", "zc",
+];
+
+/// An ISO 286 tolerance class: fundamental deviation plus IT grade, e.g. `g6`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FitClass {
+    /// Fundamental deviation letter(s); uppercase for holes, lowercase for shafts
+    pub deviation: &'static str,
+    /// IT grade, 1 to 18
+    pub grade: u8,
+}
+
+impl FitClass {
+    pub fn is_hole(&self) -> bool {
+        self.deviation.starts_with(|c: char| c.is_ascii_uppercase())
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let split = s.find(|c: char| c.is_ascii_digit())?;
+        let (letters, grade) = s.split_at(split);
+        let deviation = DEVIATIONS
+            .iter()
+            .chain(SHAFT_DEVIATIONS.iter())
+            .find(|d| **d == letters)?;
+        let grade: u8 = grade.parse().ok()?;
+        if !(1..=18).contains(&grade) {
+            return None;
+        }
+        Some(FitClass { deviation, grade })
+    }
+}
+
+impl fmt::Display for FitClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.deviation, self.grade)
+    }
+}
+
+impl fmt::Display for Tolerance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tolerance::Symmetric(band) => write!(f, "\u{B1}{}", band),
+            Tolerance::Deviation { upper_um, lower_um } => {
+                write_deviation(f, *upper_um)?;
+                f.write_str("/")?;
+                write_deviation(f, *lower_um)
+            }
+            Tolerance::Fit { hole, shaft: None } => write!(f, "{}", hole),
+            Tolerance::Fit { hole, shaft: Some(shaft) } => write!(f, "{}/{}", hole, shaft),
+        }
+    }
+}
+
+/// A signed deviation in millimeters, e.g. `+0.2`, `-0.05`, or `0`.
+fn write_deviation(f: &mut fmt::Formatter<'_>, um: i32) -> fmt::Result {
+    let sign = match um {
+        0 => "",
+        um if um > 0 => "+",
+        _ => "-",
+    };
+    write!(f, "{}{}", sign, Length::from_micrometers(um.unsigned_abs()))
+}
+
+/// Errors parsing a tolerance callout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ToleranceError;
+
+impl fmt::Display for ToleranceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a tolerance like \"±0.1\", \"+0.2/-0.1\", \"H7\", or \"H7/g6\"")
+    }
+}
+
+/// Signed millimeters, rounded to the nearest micrometer.
+fn parse_deviation(s: &str) -> Result<i32, ToleranceError> {
+    let mm: f64 = s.trim().parse().map_err(|_| ToleranceError)?;
+    let um = mm * 1000.0;
+    let um = if um < 0.0 { um - 0.5 } else { um + 0.5 };
+    if !(i32::MIN as f64..=i32::MAX as f64).contains(&um) {
+        return Err(ToleranceError);
+    }
+    Ok(um as i32)
+}
+
+impl core::str::FromStr for Tolerance {
+    type Err = ToleranceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(band) = ["\u{B1}", "+/-", "+-"].iter().find_map(|p| s.strip_prefix(p)) {
+            let band = parse_deviation(band)?;
+            return u32::try_from(band)
+                .map(|um| Tolerance::Symmetric(Length::from_micrometers(um)))
+                .map_err(|_| ToleranceError);
+        }
+        let (first, second) = match s.split_once('/') {
+            Some((first, second)) => (first.trim(), Some(second.trim())),
+            None => (s, None),
+        };
+        if first.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let hole = FitClass::parse(first).filter(FitClass::is_hole).ok_or(ToleranceError)?;
+            let shaft = match second {
+                Some(second) => Some(
+                    FitClass::parse(second)
+                        .filter(|c| !c.is_hole())
+                        .ok_or(ToleranceError)?,
+                ),
+                None => None,
+            };
+            return Ok(Tolerance::Fit { hole, shaft });
+        }
+        let second = second.ok_or(ToleranceError)?;
+        Ok(Tolerance::Deviation {
+            upper_um: parse_deviation(first)?,
+            lower_um: parse_deviation(second)?,
+        })
+    }
+}
+
+impl Serialize for Tolerance {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tolerance {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ToleranceVisitor;
+
+        impl serde::de::Visitor<'_> for ToleranceVisitor {
+            type Value = Tolerance;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a tolerance callout such as \"±0.1\" or \"H7/g6\"")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Tolerance, E> {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ToleranceVisitor)
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for Tolerance {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::{ObjectBuilder, Type};
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .description(Some(
+                "Tolerance callout: `±0.1` (or `+-0.1`), deviations like `+0.2/-0.1`, or an ISO 286 class or fit like `H7/g6`",
+            ))
+            .examples(["\u{B1}0.1"])
+            .into()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::ToSchema for Tolerance {}
+
+/// Tolerances on the plate's dimensions. A dimension without one takes the
+/// shop's general tolerance.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct Tolerances {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bolt_spacing: Option<Tolerance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bracket_height: Option<Tolerance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bracket_width: Option<Tolerance>,
+    /// The pin bore; the one dimension that takes an ISO 286 fit such as `H7/g6`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pin_diameter: Option<Tolerance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plate_thickness: Option<Tolerance>,
+}
+
+impl Tolerances {
+    /// Every dimension at the general tolerance.
+    pub const NONE: Tolerances = Tolerances {
+        bolt_spacing: None,
+        bracket_height: None,
+        bracket_width: None,
+        pin_diameter: None,
+        plate_thickness: None,
+    };
+
+    pub fn get(&self, dimension: Dimension) -> Option<Tolerance> {
+        match dimension {
+            Dimension::BoltSpacing => self.bolt_spacing,
+            Dimension::BracketHeight => self.bracket_height,
+            Dimension::BracketWidth => self.bracket_width,
+            Dimension::PinDiameter => self.pin_diameter,
+            Dimension::PlateThickness => self.plate_thickness,
+        }
+    }
+
+    pub fn set(&mut self, dimension: Dimension, tolerance: Option<Tolerance>) {
+        let slot = match dimension {
+            Dimension::BoltSpacing => &mut self.bolt_spacing,
+            Dimension::BracketHeight => &mut self.bracket_height,
+            Dimension::BracketWidth => &mut self.bracket_width,
+            Dimension::PinDiameter => &mut self.pin_diameter,
+            Dimension::PlateThickness => &mut self.plate_thickness,
+        };
+        *slot = tolerance;
+    }
+
+    /// The toleranced dimensions, in [`Dimension::ALL`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (Dimension, Tolerance)> + '_ {
+        Dimension::ALL
+            .into_iter()
+            .filter_map(|dimension| self.get(dimension).map(|t| (dimension, t)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
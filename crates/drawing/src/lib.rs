@@ -0,0 +1,746 @@
+//! 2D engineering drawings of actuator plates.
+//!
+//! [`render_svg`] and [`render_pdf`] draw the same A4 landscape sheet: a front view
+//! with the bolt holes, pins, and overall dimensions, a side view with the
+//! thickness callout, hole notes, and a title block. Hole positions follow the
+//! plate family's KCL template, so the drawing matches the exported STEP file.
+//!
+//! Both formats are written by hand from one list of shapes; the sheet only needs
+//! lines, circles, slots, and Helvetica text, which doesn't justify a PDF or SVG crate.
+
+use std::fmt::Write;
+
+use domain::{ActuatorPlate, Dimension, HoleSpec, Material, PlateFamily};
+
+/// Sheet width (A4 landscape) in millimeters.
+pub const SHEET_WIDTH_MM: f64 = 297.0;
+/// Sheet height (A4 landscape) in millimeters.
+pub const SHEET_HEIGHT_MM: f64 = 210.0;
+
+/// Radius of the circle the pins are patterned on, fixed in `plate.kcl`.
+pub const PIN_CIRCLE_RADIUS_MM: f64 = 50.0;
+/// Places the control points of a cubic Bézier that approximates a quarter circle.
+const KAPPA: f64 = 0.552_284_75;
+/// Bolt holes sit at this fraction of the plate's half-width and half-height.
+const BOLT_INSET: f64 = 0.9;
+
+/// Area the front view (without its dimensions) is scaled to fit: x, y, width, height.
+const FRONT_VIEW: (f64, f64, f64, f64) = (35.0, 25.0, 150.0, 125.0);
+/// Horizontal center and maximum width of the side view.
+const SIDE_VIEW_X: f64 = 245.0;
+const SIDE_VIEW_MAX_WIDTH: f64 = 40.0;
+
+const TEXT_SIZE: f64 = 3.0;
+const ARROW_LENGTH: f64 = 2.0;
+const POINTS_PER_MM: f64 = 72.0 / 25.4;
+
+type Point = (f64, f64);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Stroke {
+    /// Visible edges
+    Outline,
+    /// Dimension and extension lines, borders
+    Thin,
+    /// Center lines and the pin circle
+    Center,
+}
+
+impl Stroke {
+    fn width(self) -> f64 {
+        match self {
+            Stroke::Outline => 0.5,
+            Stroke::Thin | Stroke::Center => 0.18,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Anchor {
+    Start,
+    Middle,
+    End,
+}
+
+/// Something drawn on the sheet, in sheet millimeters with y pointing down.
+#[derive(Debug)]
+enum Shape {
+    Line { from: Point, to: Point, stroke: Stroke },
+    Circle { center: Point, radius: f64, stroke: Stroke },
+    /// A horizontal slot: two half circles `length` apart joined by straight sides
+    Slot { center: Point, length: f64, radius: f64, stroke: Stroke },
+    Text { at: Point, size: f64, anchor: Anchor, text: String },
+}
+
+/// Render the drawing as a standalone SVG document sized in millimeters.
+pub fn render_svg(plate: &ActuatorPlate) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}mm" height="{h}mm" viewBox="0 0 {w} {h}">"#,
+        w = SHEET_WIDTH_MM,
+        h = SHEET_HEIGHT_MM
+    );
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    for shape in layout(plate) {
+        let _ = match shape {
+            Shape::Line { from, to, stroke } => writeln!(
+                svg,
+                r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="black" stroke-width="{}"{}/>"#,
+                from.0,
+                from.1,
+                to.0,
+                to.1,
+                stroke.width(),
+                svg_dash(stroke)
+            ),
+            Shape::Circle { center, radius, stroke } => writeln!(
+                svg,
+                r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}" fill="none" stroke="black" stroke-width="{}"{}/>"#,
+                center.0,
+                center.1,
+                radius,
+                stroke.width(),
+                svg_dash(stroke)
+            ),
+            Shape::Slot { center, length, radius, stroke } => writeln!(
+                svg,
+                r#"<path d="M{:.2} {:.2} h{:.2} a{r:.2} {r:.2} 0 0 1 0 {d:.2} h{:.2} a{r:.2} {r:.2} 0 0 1 0 {:.2} z" fill="none" stroke="black" stroke-width="{}"{}/>"#,
+                center.0 - length / 2.0,
+                center.1 - radius,
+                length,
+                -length,
+                -2.0 * radius,
+                stroke.width(),
+                svg_dash(stroke),
+                r = radius,
+                d = 2.0 * radius,
+            ),
+            Shape::Text { at, size, anchor, text } => writeln!(
+                svg,
+                r#"<text x="{:.2}" y="{:.2}" font-family="Helvetica, Arial, sans-serif" font-size="{}" text-anchor="{}">{}</text>"#,
+                at.0,
+                at.1,
+                size,
+                match anchor {
+                    Anchor::Start => "start",
+                    Anchor::Middle => "middle",
+                    Anchor::End => "end",
+                },
+                xml_escape(&text)
+            ),
+        };
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render the drawing as a single-page PDF.
+pub fn render_pdf(plate: &ActuatorPlate) -> Vec<u8> {
+    // PDF has y pointing up and measures in points
+    let pt = |p: Point| (p.0 * POINTS_PER_MM, (SHEET_HEIGHT_MM - p.1) * POINTS_PER_MM);
+
+    let mut content = String::from("1 J 1 j\n");
+    for shape in layout(plate) {
+        let _ = match shape {
+            Shape::Line { from, to, stroke } => {
+                let (from, to) = (pt(from), pt(to));
+                writeln!(
+                    content,
+                    "{:.3} w {} {:.2} {:.2} m {:.2} {:.2} l S",
+                    stroke.width() * POINTS_PER_MM,
+                    pdf_dash(stroke),
+                    from.0,
+                    from.1,
+                    to.0,
+                    to.1
+                )
+            }
+            Shape::Circle { center, radius, stroke } => {
+                // Four cubic Béziers, one per quarter circle
+                let (cx, cy) = pt(center);
+                let r = radius * POINTS_PER_MM;
+                let k = r * KAPPA;
+                writeln!(
+                    content,
+                    "{w:.3} w {dash} {x0:.2} {cy:.2} m \
+                     {x0:.2} {a:.2} {b:.2} {y1:.2} {cx:.2} {y1:.2} c \
+                     {c:.2} {y1:.2} {x2:.2} {a:.2} {x2:.2} {cy:.2} c \
+                     {x2:.2} {d:.2} {c:.2} {y3:.2} {cx:.2} {y3:.2} c \
+                     {b:.2} {y3:.2} {x0:.2} {d:.2} {x0:.2} {cy:.2} c S",
+                    w = stroke.width() * POINTS_PER_MM,
+                    dash = pdf_dash(stroke),
+                    x0 = cx + r,
+                    x2 = cx - r,
+                    y1 = cy + r,
+                    y3 = cy - r,
+                    a = cy + k,
+                    b = cx + k,
+                    c = cx - k,
+                    d = cy - k,
+                    cx = cx,
+                    cy = cy,
+                )
+            }
+            Shape::Slot { center, length, radius, stroke } => {
+                // Straight sides with a half circle of two Béziers at each end
+                let (cx, cy) = pt(center);
+                let r = radius * POINTS_PER_MM;
+                let k = r * KAPPA;
+                let (x1, x2) = (cx - length / 2.0 * POINTS_PER_MM, cx + length / 2.0 * POINTS_PER_MM);
+                writeln!(
+                    content,
+                    "{w:.3} w {dash} {x1:.2} {top:.2} m {x2:.2} {top:.2} l \
+                     {a:.2} {top:.2} {right:.2} {b:.2} {right:.2} {cy:.2} c \
+                     {right:.2} {c:.2} {a:.2} {bottom:.2} {x2:.2} {bottom:.2} c \
+                     {x1:.2} {bottom:.2} l \
+                     {d:.2} {bottom:.2} {left:.2} {c:.2} {left:.2} {cy:.2} c \
+                     {left:.2} {b:.2} {d:.2} {top:.2} {x1:.2} {top:.2} c S",
+                    w = stroke.width() * POINTS_PER_MM,
+                    dash = pdf_dash(stroke),
+                    top = cy + r,
+                    bottom = cy - r,
+                    left = x1 - r,
+                    right = x2 + r,
+                    a = x2 + k,
+                    b = cy + k,
+                    c = cy - k,
+                    d = x1 - k,
+                )
+            }
+            Shape::Text { at, size, anchor, text } => {
+                let size = size * POINTS_PER_MM;
+                // Helvetica averages a little over half an em per character
+                let width = 0.55 * size * text.chars().count() as f64;
+                let (x, y) = pt(at);
+                let x = match anchor {
+                    Anchor::Start => x,
+                    Anchor::Middle => x - width / 2.0,
+                    Anchor::End => x - width,
+                };
+                writeln!(
+                    content,
+                    "BT /F1 {:.2} Tf {:.2} {:.2} Td ({}) Tj ET",
+                    size,
+                    x,
+                    y,
+                    pdf_escape(&text)
+                )
+            }
+        };
+    }
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] \
+             /Resources << /Font << /F1 5 0 R >> >> /Contents 4 0 R >>",
+            SHEET_WIDTH_MM * POINTS_PER_MM,
+            SHEET_HEIGHT_MM * POINTS_PER_MM
+        ),
+        format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+    ];
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+    let xref = pdf.len();
+    let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(trailer, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        trailer,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    );
+    pdf.extend_from_slice(trailer.as_bytes());
+    pdf
+}
+
+/// Lay out the sheet for a plate.
+fn layout(plate: &ActuatorPlate) -> Vec<Shape> {
+    let width = plate.bracket_width.to_mm();
+    let height = plate.bracket_height.to_mm();
+    let thickness = plate.plate_thickness.to_mm();
+    let pin_diameter = plate.pin_diameter.to_mm();
+    let bolt_hole = plate.bolt_holes.drill_diameter_mm(plate.bolt_size) as f64;
+
+    // The pin pattern is a fixed size, so on small plates it sets the extent
+    let pattern = match plate.family {
+        PlateFamily::DualPin { pin_spacing } => pin_spacing.to_mm() + pin_diameter,
+        PlateFamily::Standard | PlateFamily::Slotted { .. } => 2.0 * PIN_CIRCLE_RADIUS_MM + pin_diameter,
+    };
+    let (view_x, view_y, view_w, view_h) = FRONT_VIEW;
+    let scale = (view_w / width.max(pattern))
+        .min(view_h / height.max(pattern))
+        .min(SIDE_VIEW_MAX_WIDTH / thickness.max(f64::EPSILON));
+    let center = (view_x + view_w / 2.0, view_y + view_h / 2.0);
+    // Model coordinates (mm, y up, origin at the plate center) to the sheet
+    let at = |x: f64, y: f64| (center.0 + x * scale, center.1 - y * scale);
+
+    let mut shapes = Vec::new();
+    let line = |shapes: &mut Vec<Shape>, from: Point, to: Point, stroke: Stroke| {
+        shapes.push(Shape::Line { from, to, stroke })
+    };
+
+    // Sheet border
+    rectangle(&mut shapes, (10.0, 10.0), (SHEET_WIDTH_MM - 10.0, SHEET_HEIGHT_MM - 10.0), Stroke::Thin);
+
+    // Front view: outline, center lines, pins, holes
+    let (left, top) = at(-width / 2.0, height / 2.0);
+    let (right, bottom) = at(width / 2.0, -height / 2.0);
+    rectangle(&mut shapes, (left, top), (right, bottom), Stroke::Outline);
+    let reach = width.max(pattern) / 2.0 + 4.0 / scale;
+    line(&mut shapes, at(-reach, 0.0), at(reach, 0.0), Stroke::Center);
+    let reach = height.max(pattern) / 2.0 + 4.0 / scale;
+    line(&mut shapes, at(0.0, -reach), at(0.0, reach), Stroke::Center);
+    let pins: Vec<Point> = match plate.family {
+        PlateFamily::DualPin { pin_spacing } => {
+            let half = pin_spacing.to_mm() / 2.0;
+            vec![(-half, 0.0), (half, 0.0)]
+        }
+        PlateFamily::Standard | PlateFamily::Slotted { .. } => {
+            shapes.push(Shape::Circle {
+                center,
+                radius: PIN_CIRCLE_RADIUS_MM * scale,
+                stroke: Stroke::Center,
+            });
+            (0..plate.pin_count.get())
+                .map(|i| {
+                    let angle = std::f64::consts::TAU * i as f64 / plate.pin_count.get() as f64;
+                    (PIN_CIRCLE_RADIUS_MM * angle.cos(), PIN_CIRCLE_RADIUS_MM * angle.sin())
+                })
+                .collect()
+        }
+    };
+    for (x, y) in pins {
+        shapes.push(Shape::Circle {
+            center: at(x, y),
+            radius: pin_diameter / 2.0 * scale,
+            stroke: Stroke::Outline,
+        });
+    }
+    let bolt_x = width / 2.0 * BOLT_INSET;
+    let bolt_y = height / 2.0 * BOLT_INSET;
+    for (x, y) in [(-bolt_x, -bolt_y), (bolt_x, -bolt_y), (-bolt_x, bolt_y), (bolt_x, bolt_y)] {
+        shapes.push(match plate.family {
+            PlateFamily::Slotted { slot_length } => Shape::Slot {
+                center: at(x, y),
+                length: slot_length.to_mm() * scale,
+                radius: bolt_hole / 2.0 * scale,
+                stroke: Stroke::Outline,
+            },
+            PlateFamily::Standard | PlateFamily::DualPin { .. } => Shape::Circle {
+                center: at(x, y),
+                radius: bolt_hole / 2.0 * scale,
+                stroke: Stroke::Outline,
+            },
+        });
+    }
+
+    // Front view dimensions: overall size below and left, bolt pattern above and right
+    // Pins can stick out past a small plate, so dimensions clear the whole view
+    let pattern_radius = pattern / 2.0 * scale;
+    let view_left = left.min(center.0 - pattern_radius);
+    let view_right = right.max(center.0 + pattern_radius);
+    let view_top = top.min(center.1 - pattern_radius);
+    let view_bottom = bottom.max(center.1 + pattern_radius);
+    horizontal_dimension(&mut shapes, left, right, bottom, view_bottom + 8.0, &toleranced(plate, Dimension::BracketWidth));
+    vertical_dimension(&mut shapes, top, bottom, left, view_left - 8.0, &toleranced(plate, Dimension::BracketHeight));
+    let (bolt_left, bolt_top) = at(-bolt_x, bolt_y);
+    let (bolt_right, bolt_bottom) = at(bolt_x, -bolt_y);
+    horizontal_dimension(&mut shapes, bolt_left, bolt_right, bolt_top, view_top - 6.0, &format_mm(2.0 * bolt_x));
+    vertical_dimension(&mut shapes, bolt_top, bolt_bottom, bolt_right, view_right + 8.0, &format_mm(2.0 * bolt_y));
+    label(&mut shapes, (center.0, view_bottom + 17.0), Anchor::Middle, "FRONT VIEW");
+
+    // Side view with the thickness callout
+    let side_left = SIDE_VIEW_X - thickness * scale / 2.0;
+    let side_right = SIDE_VIEW_X + thickness * scale / 2.0;
+    rectangle(&mut shapes, (side_left, top), (side_right, bottom), Stroke::Outline);
+    horizontal_dimension(&mut shapes, side_left, side_right, top, top - 8.0, &toleranced(plate, Dimension::PlateThickness));
+    label(&mut shapes, (SIDE_VIEW_X, view_bottom + 17.0), Anchor::Middle, "SIDE VIEW");
+
+    // Hole notes
+    let pin_note = match plate.family {
+        PlateFamily::DualPin { pin_spacing } => format!(
+            "2X \u{D8}{} THRU, {} APART",
+            pin_size(plate),
+            pin_spacing
+        ),
+        PlateFamily::Standard | PlateFamily::Slotted { .. } => format!(
+            "{}X \u{D8}{} THRU, EQUALLY SPACED ON \u{D8}{} PCD",
+            plate.pin_count,
+            pin_size(plate),
+            format_mm(2.0 * PIN_CIRCLE_RADIUS_MM)
+        ),
+    };
+    let mut notes = vec![bolt_hole_note(plate, bolt_hole), pin_note];
+    // Bolt spacing isn't drawn, so its tolerance gets a note of its own
+    if plate.tolerances.bolt_spacing.is_some() {
+        notes.push(format!("BOLT SPACING {}", toleranced(plate, Dimension::BoltSpacing)));
+    }
+    notes.push("ALL DIMENSIONS IN MILLIMETERS".to_string());
+    for (i, note) in notes.iter().enumerate() {
+        label(&mut shapes, (15.0, 180.0 + 6.0 * i as f64), Anchor::Start, note);
+    }
+
+    // Title block
+    let (block_left, block_top) = (197.0, 170.0);
+    let (block_right, block_bottom) = (SHEET_WIDTH_MM - 10.0, SHEET_HEIGHT_MM - 10.0);
+    rectangle(&mut shapes, (block_left, block_top), (block_right, block_bottom), Stroke::Thin);
+    line(&mut shapes, (block_left, block_top + 10.0), (block_right, block_top + 10.0), Stroke::Thin);
+    shapes.push(Shape::Text {
+        at: (block_left + 3.0, block_top + 7.0),
+        size: 5.0,
+        anchor: Anchor::Start,
+        text: "ACTUATOR PLATE".to_string(),
+    });
+    let rows = [
+        format!("MATERIAL: {}", material_name(plate.material)),
+        format!("THICKNESS: {:#}", plate.plate_thickness),
+        format!("SCALE: {}", format_scale(scale)),
+    ];
+    for (i, row) in rows.iter().enumerate() {
+        label(&mut shapes, (block_left + 3.0, block_top + 16.0 + 6.0 * i as f64), Anchor::Start, row);
+    }
+
+    shapes
+}
+
+/// A dimension's nominal size followed by its tolerance, if it has one, e.g.
+/// `300 ±0.1` or `10 H7/g6`.
+fn toleranced(plate: &ActuatorPlate, dimension: Dimension) -> String {
+    let nominal = plate.dimension(dimension);
+    match plate.called_out_tolerances().get(dimension) {
+        Some(tolerance) => format!("{} {}", nominal, tolerance),
+        None => nominal.to_string(),
+    }
+}
+
+/// The pin diameter as toleranced, followed by the bore's limits when it is sized
+/// for a fit, e.g. `10 P7/h6 (9.976/9.991)`, so the machinist needn't look them up.
+fn pin_size(plate: &ActuatorPlate) -> String {
+    let toleranced = toleranced(plate, Dimension::PinDiameter);
+    match plate.pin_bore() {
+        Some(bore) => format!("{} ({}/{})", toleranced, bore.smallest(), bore.largest()),
+        None => toleranced,
+    }
+}
+
+/// Callout for the four bolt holes, e.g. `4X Ø11 THRU, CBORE Ø17.5 X 6 DEEP`.
+/// Slots give their width and overall length, e.g. `4X SLOT 11 X 23 THRU`.
+fn bolt_hole_note(plate: &ActuatorPlate, bolt_hole: f64) -> String {
+    let hole = match plate.family {
+        PlateFamily::Slotted { slot_length } => format!(
+            "4X SLOT {} X {} THRU",
+            format_mm(bolt_hole),
+            format_mm(bolt_hole + slot_length.to_mm())
+        ),
+        PlateFamily::Standard | PlateFamily::DualPin { .. } => format!("4X \u{D8}{} THRU", format_mm(bolt_hole)),
+    };
+    match plate.bolt_holes {
+        HoleSpec::Through => format!("{}, {:?} CLEARANCE", hole, plate.bolt_size),
+        HoleSpec::Counterbore { diameter, depth } => {
+            format!("{}, CBORE \u{D8}{} X {} DEEP", hole, diameter, depth)
+        }
+        HoleSpec::Countersink { diameter } => format!("{}, CSK \u{D8}{} X 90\u{B0}", hole, diameter),
+        HoleSpec::Tapped { thread } => format!("{}, TAP {}-6H THRU", hole, thread),
+    }
+}
+
+fn rectangle(shapes: &mut Vec<Shape>, (x1, y1): Point, (x2, y2): Point, stroke: Stroke) {
+    for (from, to) in [
+        ((x1, y1), (x2, y1)),
+        ((x2, y1), (x2, y2)),
+        ((x2, y2), (x1, y2)),
+        ((x1, y2), (x1, y1)),
+    ] {
+        shapes.push(Shape::Line { from, to, stroke });
+    }
+}
+
+fn label(shapes: &mut Vec<Shape>, at: Point, anchor: Anchor, text: &str) {
+    shapes.push(Shape::Text {
+        at,
+        size: TEXT_SIZE,
+        anchor,
+        text: text.to_string(),
+    });
+}
+
+/// Open arrowhead at `tip`, pointing along `(dx, dy)` (a unit vector).
+fn arrow(shapes: &mut Vec<Shape>, tip: Point, (dx, dy): Point) {
+    // Sides at ±20° from the shaft
+    let (sin, cos) = 20f64.to_radians().sin_cos();
+    for side in [1.0, -1.0] {
+        let bx = -(dx * cos - side * dy * sin) * ARROW_LENGTH;
+        let by = -(side * dx * sin + dy * cos) * ARROW_LENGTH;
+        shapes.push(Shape::Line {
+            from: tip,
+            to: (tip.0 + bx, tip.1 + by),
+            stroke: Stroke::Thin,
+        });
+    }
+}
+
+/// Dimension between `x1` and `x2` on a line at `y`, with extension lines from `from_y`.
+fn horizontal_dimension(shapes: &mut Vec<Shape>, x1: f64, x2: f64, from_y: f64, y: f64, text: &str) {
+    let dir = (y - from_y).signum();
+    for x in [x1, x2] {
+        shapes.push(Shape::Line {
+            from: (x, from_y + dir),
+            to: (x, y + dir * 1.5),
+            stroke: Stroke::Thin,
+        });
+    }
+    shapes.push(Shape::Line {
+        from: (x1, y),
+        to: (x2, y),
+        stroke: Stroke::Thin,
+    });
+    arrow(shapes, (x1, y), (-1.0, 0.0));
+    arrow(shapes, (x2, y), (1.0, 0.0));
+    label(shapes, ((x1 + x2) / 2.0, y - 1.0), Anchor::Middle, text);
+}
+
+/// Dimension between `y1` and `y2` on a line at `x`, with extension lines from `from_x`.
+/// The text sits on the side of the line away from the part.
+fn vertical_dimension(shapes: &mut Vec<Shape>, y1: f64, y2: f64, from_x: f64, x: f64, text: &str) {
+    let dir = (x - from_x).signum();
+    for y in [y1, y2] {
+        shapes.push(Shape::Line {
+            from: (from_x + dir, y),
+            to: (x + dir * 1.5, y),
+            stroke: Stroke::Thin,
+        });
+    }
+    shapes.push(Shape::Line {
+        from: (x, y1),
+        to: (x, y2),
+        stroke: Stroke::Thin,
+    });
+    arrow(shapes, (x, y1), (0.0, -1.0));
+    arrow(shapes, (x, y2), (0.0, 1.0));
+    let anchor = if dir < 0.0 { Anchor::End } else { Anchor::Start };
+    label(shapes, (x + dir * 1.5, (y1 + y2) / 2.0 + TEXT_SIZE / 3.0), anchor, text);
+}
+
+fn material_name(material: Material) -> &'static str {
+    match material {
+        Material::Aluminum => "ALUMINUM 6061-T6",
+        Material::StainlessSteel => "STAINLESS STEEL 304",
+        Material::CarbonSteel => "CARBON STEEL",
+        Material::Brass => "BRASS",
+    }
+}
+
+/// A millimeter value to at most two decimals, without trailing zeros.
+fn format_mm(mm: f64) -> String {
+    let text = format!("{:.2}", mm);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Drawing scale as a ratio, e.g. `1:2.5` or `2:1`.
+fn format_scale(scale: f64) -> String {
+    let ratio = |r: f64| {
+        let text = format!("{:.1}", r);
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    };
+    if scale >= 1.0 {
+        format!("{}:1", ratio(scale))
+    } else {
+        format!("1:{}", ratio(1.0 / scale))
+    }
+}
+
+fn svg_dash(stroke: Stroke) -> &'static str {
+    match stroke {
+        Stroke::Center => r#" stroke-dasharray="4 1 1 1""#,
+        Stroke::Outline | Stroke::Thin => "",
+    }
+}
+
+fn pdf_dash(stroke: Stroke) -> &'static str {
+    match stroke {
+        Stroke::Center => "[11.34 2.83 2.83 2.83] 0 d",
+        Stroke::Outline | Stroke::Thin => "[] 0 d",
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Escape a string literal for PDF, writing non-ASCII Latin-1 characters (which
+/// WinAnsiEncoding shares, e.g. Ø) as octal escapes.
+fn pdf_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            c if (c as u32) < 0x100 => {
+                let _ = write!(escaped, "\\{:03o}", c as u32);
+            }
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::{Count, Length};
+
+    #[test]
+    fn test_svg_has_every_hole() {
+        let plate = ActuatorPlate::default();
+        let svg = render_svg(&plate);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        // Pins, four bolts, and the pin circle
+        assert_eq!(svg.matches("<circle").count(), plate.pin_count.get() as usize + 5);
+        assert!(svg.contains(">300</text>"));
+        assert!(svg.contains(">400</text>"));
+        assert!(svg.contains("ALUMINUM 6061-T6"));
+        assert!(svg.contains("6X \u{D8}10 THRU"));
+    }
+
+    #[test]
+    fn test_bolt_hole_note_follows_hole_spec() {
+        let mut plate = ActuatorPlate::default();
+        assert!(render_svg(&plate).contains("4X \u{D8}11 THRU, M10 CLEARANCE"));
+
+        plate.bolt_holes = HoleSpec::Counterbore {
+            diameter: Length::from_micrometers(17_500),
+            depth: Length::from_mm(6),
+        };
+        assert!(render_svg(&plate).contains("4X \u{D8}11 THRU, CBORE \u{D8}17.5 X 6 DEEP"));
+
+        plate.bolt_holes = HoleSpec::Tapped { thread: "M10x1.25".parse().unwrap() };
+        assert!(render_svg(&plate).contains("4X \u{D8}8.75 THRU, TAP M10x1.25-6H THRU"));
+    }
+
+    #[test]
+    fn test_families_draw_their_own_holes() {
+        let slotted = ActuatorPlate {
+            family: PlateFamily::Slotted { slot_length: Length::from_mm(12) },
+            ..ActuatorPlate::default()
+        };
+        let svg = render_svg(&slotted);
+        assert_eq!(svg.matches("<path").count(), 4);
+        assert_eq!(svg.matches("<circle").count(), slotted.pin_count.get() as usize + 1);
+        assert!(svg.contains("4X SLOT 11 X 23 THRU, M10 CLEARANCE"));
+        // Four Béziers per slot and per circle
+        let pdf = String::from_utf8_lossy(&render_pdf(&slotted)).into_owned();
+        assert_eq!(pdf.matches(" c ").count(), 4 * 4 + 4 * (slotted.pin_count.get() as usize + 1));
+
+        let dual_pin = ActuatorPlate {
+            pin_count: Count::of(2),
+            family: PlateFamily::DualPin { pin_spacing: Length::from_mm(80) },
+            ..ActuatorPlate::default()
+        };
+        let svg = render_svg(&dual_pin);
+        // Two pins and four bolts, with no pin circle
+        assert_eq!(svg.matches("<circle").count(), 6);
+        assert!(svg.contains("2X \u{D8}10 THRU, 80 APART"));
+        assert!(!svg.contains("PCD"));
+    }
+
+    #[test]
+    fn test_tolerances_are_called_out() {
+        let mut plate = ActuatorPlate::default();
+        plate.tolerances.bracket_width = Some("+-0.2".parse().unwrap());
+        plate.tolerances.pin_diameter = Some("H7/g6".parse().unwrap());
+        plate.tolerances.bolt_spacing = Some("+0.1/0".parse().unwrap());
+        let svg = render_svg(&plate);
+
+        assert!(svg.contains(">300 \u{B1}0.2</text>"));
+        assert!(svg.contains(">400</text>"));
+        assert!(svg.contains("6X \u{D8}10 H7/g6 THRU"));
+        assert!(svg.contains("BOLT SPACING 60 +0.1/0"));
+
+        // A pin fit replaces the pin tolerance and gives the bore's limits
+        plate.pin_fit = Some(domain::PinFit::Press);
+        assert!(render_svg(&plate).contains("6X \u{D8}10 P7/h6 (9.976/9.991) THRU"));
+        assert!(!render_svg(&ActuatorPlate::default()).contains("BOLT SPACING"));
+    }
+
+    #[test]
+    fn test_pdf_structure() {
+        let pdf = render_pdf(&ActuatorPlate::default());
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        let startxref: usize = text.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert!(text[startxref..].starts_with("xref"));
+        // Every xref entry points at its object
+        let entries = text[startxref..].lines().skip(3).take(5);
+        for (i, entry) in entries.enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", i + 1)), "object {}", i + 1);
+        }
+        // The diameter sign goes through WinAnsi as an octal escape
+        assert!(text.contains("(6X \\33010 THRU"));
+    }
+
+    #[test]
+    fn test_extreme_plates_fit_on_the_sheet() {
+        let tiny = ActuatorPlate {
+            bracket_width: Length::from_mm(1),
+            bracket_height: Length::from_mm(1),
+            plate_thickness: Length::from_mm(1),
+            pin_diameter: Length::from_mm(1),
+            ..ActuatorPlate::default()
+        };
+        let huge = ActuatorPlate {
+            bracket_width: Length::from_mm(1000),
+            bracket_height: Length::from_mm(1000),
+            plate_thickness: Length::from_mm(100),
+            pin_diameter: Length::from_mm(100),
+            pin_count: Count::of(12),
+            ..ActuatorPlate::default()
+        };
+        let on_sheet = |(x, y): Point| (0.0..=SHEET_WIDTH_MM).contains(&x) && (0.0..=SHEET_HEIGHT_MM).contains(&y);
+
+        for plate in [tiny, ActuatorPlate::default(), huge] {
+            for shape in layout(&plate) {
+                match shape {
+                    Shape::Line { from, to, .. } => assert!(on_sheet(from) && on_sheet(to), "{:?}", shape),
+                    Shape::Circle { center, radius, .. } => {
+                        assert!(on_sheet((center.0 - radius, center.1 - radius)), "{:?}", shape);
+                        assert!(on_sheet((center.0 + radius, center.1 + radius)), "{:?}", shape);
+                    }
+                    Shape::Slot { center, length, radius, .. } => {
+                        assert!(on_sheet((center.0 - length / 2.0 - radius, center.1 - radius)), "{:?}", shape);
+                        assert!(on_sheet((center.0 + length / 2.0 + radius, center.1 + radius)), "{:?}", shape);
+                    }
+                    Shape::Text { at, .. } => assert!(on_sheet(at), "{:?}", shape),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_helpers() {
+        assert_eq!(format_mm(11.0), "11");
+        assert_eq!(format_mm(6.6), "6.6");
+        assert_eq!(format_mm(270.0 * 0.9), "243");
+        assert_eq!(format_scale(0.4), "1:2.5");
+        assert_eq!(format_scale(1.5), "1.5:1");
+        assert_eq!(pdf_escape("(Ø)"), "\\(\\330\\)");
+    }
+}
@@ -0,0 +1,260 @@
+//! DXF R12 cut profiles of actuator plates, for waterjet and laser cutting.
+//!
+//! A plate is a flat slab, so a 2D profile is all a cutting machine needs.
+//! [`plate_dxf`] writes it in millimeters: the outline on the [`OUTLINE_LAYER`] and
+//! every through cut on the [`HOLES_LAYER`], as circles, or for slotted plates as
+//! two lines and two arcs per bolt slot. Hole positions come from
+//! [`step_writer::holes`], so they match the STEP file and the KCL templates. Only
+//! what goes all the way through is drawn: counterbores, countersinks, and threads
+//! are second operations, so those holes are cut at their drill diameter.
+//!
+//! R12 (`AC1009`) is the oldest version every CAM package still reads, and needs
+//! nothing more than a header, a layer table, and `LINE`, `CIRCLE`, and `ARC`
+//! entities.
+
+use std::fmt::Write;
+
+use domain::{ActuatorPlate, PlateFamily};
+
+/// Layer holding the plate's outer edge.
+pub const OUTLINE_LAYER: &str = "OUTLINE";
+/// Layer holding every hole and slot.
+pub const HOLES_LAYER: &str = "HOLES";
+
+/// Write the plate's cut profile as a DXF R12 file, in millimeters, with the
+/// origin at the plate center.
+///
+/// The output depends only on the plate, so the same parameters always produce
+/// the same bytes.
+pub fn plate_dxf(plate: &ActuatorPlate) -> String {
+    let half_width = plate.bracket_width.to_mm() / 2.0;
+    let half_height = plate.bracket_height.to_mm() / 2.0;
+    let mut dxf = Dxf::default();
+
+    dxf.section("HEADER");
+    dxf.pair(9, "$ACADVER");
+    dxf.pair(1, "AC1009");
+    dxf.pair(9, "$INSBASE");
+    dxf.point(0, (0.0, 0.0));
+    dxf.pair(9, "$EXTMIN");
+    dxf.point(0, (-half_width, -half_height));
+    dxf.pair(9, "$EXTMAX");
+    dxf.point(0, (half_width, half_height));
+    dxf.pair(0, "ENDSEC");
+
+    dxf.section("TABLES");
+    dxf.pair(0, "TABLE");
+    dxf.pair(2, "LTYPE");
+    dxf.pair(70, 1);
+    dxf.pair(0, "LTYPE");
+    dxf.pair(2, "CONTINUOUS");
+    dxf.pair(70, 0);
+    dxf.pair(3, "Solid line");
+    dxf.pair(72, 65);
+    dxf.pair(73, 0);
+    dxf.number(40, 0.0);
+    dxf.pair(0, "ENDTAB");
+    dxf.pair(0, "TABLE");
+    dxf.pair(2, "LAYER");
+    dxf.pair(70, 2);
+    // White outline, red holes
+    for (layer, color) in [(OUTLINE_LAYER, 7), (HOLES_LAYER, 1)] {
+        dxf.pair(0, "LAYER");
+        dxf.pair(2, layer);
+        dxf.pair(70, 0);
+        dxf.pair(62, color);
+        dxf.pair(6, "CONTINUOUS");
+    }
+    dxf.pair(0, "ENDTAB");
+    dxf.pair(0, "ENDSEC");
+
+    dxf.section("ENTITIES");
+    let corners = [
+        (-half_width, -half_height),
+        (half_width, -half_height),
+        (half_width, half_height),
+        (-half_width, half_height),
+    ];
+    for (i, &from) in corners.iter().enumerate() {
+        dxf.line(OUTLINE_LAYER, from, corners[(i + 1) % corners.len()]);
+    }
+    // The first four holes are the bolts; see `step_writer::holes`
+    for (i, hole) in step_writer::holes(plate).into_iter().enumerate() {
+        let radius = hole.diameter / 2.0;
+        match plate.family {
+            PlateFamily::Slotted { slot_length } if i < 4 => {
+                let half = slot_length.to_mm() / 2.0;
+                let (left, right) = (hole.x - half, hole.x + half);
+                dxf.line(HOLES_LAYER, (left, hole.y - radius), (right, hole.y - radius));
+                dxf.arc(HOLES_LAYER, (right, hole.y), radius, (270.0, 90.0));
+                dxf.line(HOLES_LAYER, (right, hole.y + radius), (left, hole.y + radius));
+                dxf.arc(HOLES_LAYER, (left, hole.y), radius, (90.0, 270.0));
+            }
+            _ => dxf.circle(HOLES_LAYER, (hole.x, hole.y), radius),
+        }
+    }
+    dxf.pair(0, "ENDSEC");
+
+    dxf.pair(0, "EOF");
+    dxf.out
+}
+
+/// A DXF file being written, one group code and value per pair of lines.
+#[derive(Default)]
+struct Dxf {
+    out: String,
+}
+
+impl Dxf {
+    fn pair(&mut self, code: u16, value: impl std::fmt::Display) {
+        let _ = write!(self.out, "{:>3}\n{}\n", code, value);
+    }
+
+    fn number(&mut self, code: u16, value: f64) {
+        self.pair(code, format_number(value));
+    }
+
+    /// A point's X, Y, and Z (always 0) under group codes 10, 20, and 30 plus `offset`.
+    fn point(&mut self, offset: u16, (x, y): (f64, f64)) {
+        self.number(10 + offset, x);
+        self.number(20 + offset, y);
+        self.number(30 + offset, 0.0);
+    }
+
+    fn section(&mut self, name: &str) {
+        self.pair(0, "SECTION");
+        self.pair(2, name);
+    }
+
+    fn line(&mut self, layer: &str, from: (f64, f64), to: (f64, f64)) {
+        self.pair(0, "LINE");
+        self.pair(8, layer);
+        self.point(0, from);
+        self.point(1, to);
+    }
+
+    fn circle(&mut self, layer: &str, center: (f64, f64), radius: f64) {
+        self.pair(0, "CIRCLE");
+        self.pair(8, layer);
+        self.point(0, center);
+        self.number(40, radius);
+    }
+
+    /// An arc drawn counterclockwise from the start angle to the end angle, in degrees.
+    fn arc(&mut self, layer: &str, center: (f64, f64), radius: f64, (start, end): (f64, f64)) {
+        self.pair(0, "ARC");
+        self.pair(8, layer);
+        self.point(0, center);
+        self.number(40, radius);
+        self.number(50, start);
+        self.number(51, end);
+    }
+}
+
+/// Up to six decimals with trailing zeros dropped, and never `-0`.
+fn format_number(value: f64) -> String {
+    let formatted = format!("{:.6}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" => "0".to_string(),
+        _ => trimmed.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::{Count, HoleSpec, Length};
+
+    /// The file's (group code, value) pairs, checking that every code is a number.
+    fn pairs(dxf: &str) -> Vec<(u16, &str)> {
+        let lines: Vec<&str> = dxf.lines().collect();
+        assert_eq!(lines.len() % 2, 0, "odd number of lines");
+        lines
+            .chunks(2)
+            .map(|pair| (pair[0].trim().parse().expect("group code"), pair[1]))
+            .collect()
+    }
+
+    /// How many entities of `kind` are on `layer`.
+    fn count(dxf: &str, kind: &str, layer: &str) -> usize {
+        pairs(dxf)
+            .windows(2)
+            .filter(|w| w[0] == (0, kind) && w[1] == (8, layer))
+            .count()
+    }
+
+    #[test]
+    fn test_standard_plate_is_an_outline_and_circles() {
+        let plate = ActuatorPlate {
+            bolt_holes: HoleSpec::Countersink {
+                diameter: Length::from_mm(23),
+            },
+            ..ActuatorPlate::default()
+        };
+        let dxf = plate_dxf(&plate);
+
+        let pairs = pairs(&dxf);
+        assert_eq!(&pairs[..4], [(0, "SECTION"), (2, "HEADER"), (9, "$ACADVER"), (1, "AC1009")]);
+        assert_eq!(pairs.last(), Some(&(0, "EOF")));
+        assert_eq!(
+            pairs.iter().filter(|p| **p == (0, "SECTION")).count(),
+            pairs.iter().filter(|p| **p == (0, "ENDSEC")).count()
+        );
+        assert_eq!(count(&dxf, "LINE", OUTLINE_LAYER), 4);
+        assert_eq!(count(&dxf, "CIRCLE", HOLES_LAYER), 4 + 6);
+        // The countersink is a second operation; the cut is the M10 clearance hole
+        assert!(dxf.contains(" 40\n5.5\n"), "{}", dxf);
+        assert!(!dxf.contains(" 40\n11.5\n"));
+    }
+
+    #[test]
+    fn test_slotted_plate_cuts_slots_around_the_bolts() {
+        let plate = ActuatorPlate {
+            family: PlateFamily::Slotted {
+                slot_length: Length::from_mm(12),
+            },
+            ..ActuatorPlate::default()
+        };
+        let dxf = plate_dxf(&plate);
+
+        assert_eq!(count(&dxf, "LINE", OUTLINE_LAYER), 4);
+        assert_eq!(count(&dxf, "LINE", HOLES_LAYER), 4 * 2);
+        assert_eq!(count(&dxf, "ARC", HOLES_LAYER), 4 * 2);
+        // Only the pins are round
+        assert_eq!(count(&dxf, "CIRCLE", HOLES_LAYER), 6);
+        // Lower-left slot: 300 wide, so centered at x = -135, ends 6 either side
+        assert!(dxf.contains(" 10\n-141\n 20\n-185.5\n"), "{}", dxf);
+        assert!(dxf.contains(" 11\n-129\n 21\n-185.5\n"), "{}", dxf);
+    }
+
+    #[test]
+    fn test_dual_pin_plate_puts_both_pins_on_the_centerline() {
+        let plate = ActuatorPlate {
+            family: PlateFamily::DualPin {
+                pin_spacing: Length::from_mm(120),
+            },
+            pin_count: Count::of(2),
+            ..ActuatorPlate::default()
+        };
+        let dxf = plate_dxf(&plate);
+
+        assert_eq!(count(&dxf, "CIRCLE", HOLES_LAYER), 4 + 2);
+        assert!(dxf.contains("CIRCLE\n  8\nHOLES\n 10\n-60\n 20\n0\n"), "{}", dxf);
+        assert!(dxf.contains("CIRCLE\n  8\nHOLES\n 10\n60\n 20\n0\n"), "{}", dxf);
+    }
+
+    #[test]
+    fn test_output_is_deterministic_and_trims_numbers() {
+        let plate = ActuatorPlate {
+            bracket_width: Length::from_micrometers(150_250),
+            ..ActuatorPlate::default()
+        };
+        assert_eq!(plate_dxf(&plate), plate_dxf(&plate));
+        assert!(plate_dxf(&plate).contains(" 10\n-75.125\n"));
+
+        assert_eq!(format_number(-0.0000001), "0");
+        assert_eq!(format_number(2.5), "2.5");
+        assert_eq!(format_number(300.0), "300");
+    }
+}
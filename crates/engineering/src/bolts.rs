@@ -0,0 +1,97 @@
+//! Shear in the mounting bolts and bearing where they press on the plate.
+//!
+//! The applied load is shared evenly by the four corner bolts:
+//!
+//! - bolt shear:    τ = F / (n·A_s), threads in the shear plane, so the tensile
+//!   stress area A_s rather than the shank
+//! - plate bearing: σ = F / (n·d·t), nominal bolt diameter against plate thickness
+//!
+//! Bolts are taken as property class 8.8 (ISO 898-1), shearing at the von Mises
+//! fraction of their yield strength; the plate bears up to its material's yield.
+
+use core::fmt;
+use domain::{ActuatorPlate, Newtons};
+
+use crate::DEFAULT_SAFETY_FACTOR;
+
+/// Mounting bolts per plate, one at each corner.
+pub const BOLT_COUNT: u32 = 4;
+/// Yield strength of a property class 8.8 bolt.
+pub const BOLT_YIELD_MPA: f32 = 640.0;
+/// Shear yield as a fraction of tensile yield (von Mises, 1/√3).
+const SHEAR_YIELD_RATIO: f32 = 0.577;
+
+/// Result of checking the bolted joint against a load.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoltCheck {
+    /// Total load on the plate.
+    pub load: Newtons,
+    pub force_per_bolt_n: f32,
+    pub bolt_shear_stress_mpa: f32,
+    pub bolt_shear_yield_mpa: f32,
+    pub plate_bearing_stress_mpa: f32,
+    pub plate_yield_mpa: f32,
+}
+
+impl BoltCheck {
+    /// Bolt shear yield over the shear stress.
+    pub fn shear_safety_factor(&self) -> f32 {
+        self.bolt_shear_yield_mpa / self.bolt_shear_stress_mpa
+    }
+
+    /// Plate yield over the bearing stress.
+    pub fn bearing_safety_factor(&self) -> f32 {
+        self.plate_yield_mpa / self.plate_bearing_stress_mpa
+    }
+
+    /// The governing (smaller) safety factor.
+    pub fn safety_factor(&self) -> f32 {
+        self.shear_safety_factor().min(self.bearing_safety_factor())
+    }
+
+    /// Whether both safety factors reach [`DEFAULT_SAFETY_FACTOR`].
+    pub fn is_adequate(&self) -> bool {
+        self.safety_factor() >= DEFAULT_SAFETY_FACTOR
+    }
+}
+
+/// One line for a design report, e.g.
+/// `Bolt check, 3000 N on 4 bolts: shear 12.9/369.3 MPa (SF 28.6), bearing 9.4/276.0 MPa (SF 29.4): ok`.
+impl fmt::Display for BoltCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Bolt check, {} N on {} bolts: shear {:.1}/{:.1} MPa (SF {:.1}), bearing {:.1}/{:.1} MPa (SF {:.1}): ",
+            self.load.0,
+            BOLT_COUNT,
+            self.bolt_shear_stress_mpa,
+            self.bolt_shear_yield_mpa,
+            self.shear_safety_factor(),
+            self.plate_bearing_stress_mpa,
+            self.plate_yield_mpa,
+            self.bearing_safety_factor(),
+        )?;
+        if self.is_adequate() {
+            write!(f, "ok")
+        } else {
+            write!(f, "safety factor below {:.1}", DEFAULT_SAFETY_FACTOR)
+        }
+    }
+}
+
+/// Check the mounting bolts of `plate` against `load`. Expects a plate that passed
+/// `validation::validate`; zero dimensions produce infinite stresses.
+pub fn check_bolts(plate: &ActuatorPlate, load: Newtons) -> BoltCheck {
+    let force_per_bolt_n = load.0 as f32 / BOLT_COUNT as f32;
+    let bolt_d = plate.bolt_size.nominal_diameter_mm() as f32;
+    let thickness = plate.plate_thickness.to_mm() as f32;
+
+    BoltCheck {
+        load,
+        force_per_bolt_n,
+        bolt_shear_stress_mpa: force_per_bolt_n / plate.bolt_size.stress_area_mm2(),
+        bolt_shear_yield_mpa: BOLT_YIELD_MPA * SHEAR_YIELD_RATIO,
+        plate_bearing_stress_mpa: force_per_bolt_n / (bolt_d * thickness),
+        plate_yield_mpa: plate.material.yield_strength_mpa() as f32,
+    }
+}
@@ -0,0 +1,237 @@
+#![no_std]
+//! Structural load checks for actuator plates.
+//!
+//! Models the plate as a simply supported beam spanning the bolt rows (span =
+//! `bolt_spacing`, section = `bracket_width` × `plate_thickness`) with the whole load
+//! applied at midspan:
+//!
+//! - bending: σ = M·c / I = 3·F·L / (2·w·t²)
+//! - shear:   τ = 1.5·V / (w·t) with V = F / 2, i.e. 3·F / (4·w·t)
+//!
+//! Stresses are compared against yield and shear strength divided by the safety factor.
+//! This is a sanity check for obviously undersized plates, not a substitute for FEA.
+//! [`check_bolts`] checks the bolted joint the same way.
+
+use core::fmt;
+use domain::{ActuatorPlate, Newtons};
+
+mod bolts;
+
+pub use bolts::{check_bolts, BoltCheck, BOLT_COUNT, BOLT_YIELD_MPA};
+
+/// Safety factor used when the customer doesn't supply one. Matches the factor the
+/// validation crate applies to the rated pin forces.
+pub const DEFAULT_SAFETY_FACTOR: f32 = 2.0;
+
+/// The load a plate is checked against.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoadCase {
+    /// Total load applied to the plate.
+    pub load: Newtons,
+    /// Allowable stresses are the material strengths divided by this.
+    pub safety_factor: f32,
+}
+
+impl LoadCase {
+    pub fn new(load: Newtons, safety_factor: f32) -> Self {
+        Self { load, safety_factor }
+    }
+
+    /// Every pin at its expected force, with the default safety factor.
+    pub fn rated(plate: &ActuatorPlate) -> Self {
+        let total = plate
+            .expected_force_per_pin
+            .0
+            .saturating_mul(plate.pin_count.get());
+        Self::new(Newtons(total), DEFAULT_SAFETY_FACTOR)
+    }
+}
+
+/// Result of checking a plate against a [`LoadCase`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoadCheck {
+    pub case: LoadCase,
+    pub bending_stress_mpa: f32,
+    pub allowable_bending_mpa: f32,
+    pub shear_stress_mpa: f32,
+    pub allowable_shear_mpa: f32,
+    /// Thinnest plate (whole millimeters) that passes both checks for this load.
+    pub required_thickness_mm: u16,
+}
+
+impl LoadCheck {
+    /// Bending stress as a fraction of the allowable (> 1.0 fails).
+    pub fn bending_utilization(&self) -> f32 {
+        self.bending_stress_mpa / self.allowable_bending_mpa
+    }
+
+    /// Shear stress as a fraction of the allowable (> 1.0 fails).
+    pub fn shear_utilization(&self) -> f32 {
+        self.shear_stress_mpa / self.allowable_shear_mpa
+    }
+
+    /// The governing (larger) utilization.
+    pub fn utilization(&self) -> f32 {
+        self.bending_utilization().max(self.shear_utilization())
+    }
+
+    pub fn passes(&self) -> bool {
+        self.utilization() <= 1.0
+    }
+}
+
+/// One line for a design report, e.g.
+/// `Load check, 3000 N at safety factor 2.0: bending 93.8/138.0 MPa (68%), shear 0.9/103.5 MPa (1%): pass`.
+impl fmt::Display for LoadCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Load check, {} N at safety factor {:.1}: bending {:.1}/{:.1} MPa ({:.0}%), shear {:.1}/{:.1} MPa ({:.0}%): ",
+            self.case.load.0,
+            self.case.safety_factor,
+            self.bending_stress_mpa,
+            self.allowable_bending_mpa,
+            self.bending_utilization() * 100.0,
+            self.shear_stress_mpa,
+            self.allowable_shear_mpa,
+            self.shear_utilization() * 100.0,
+        )?;
+        if self.passes() {
+            write!(f, "pass")
+        } else {
+            write!(f, "FAIL, needs at least {} mm thickness", self.required_thickness_mm)
+        }
+    }
+}
+
+fn bending_stress_mpa(load: f32, span: f32, width: f32, thickness: f32) -> f32 {
+    3.0 * load * span / (2.0 * width * thickness * thickness)
+}
+
+fn shear_stress_mpa(load: f32, width: f32, thickness: f32) -> f32 {
+    3.0 * load / (4.0 * width * thickness)
+}
+
+/// Check `plate` against `case`. Expects a plate that passed `validation::validate`;
+/// zero dimensions produce infinite stresses and fail.
+pub fn check_load(plate: &ActuatorPlate, case: &LoadCase) -> LoadCheck {
+    let load = case.load.0 as f32;
+    let span = plate.bolt_spacing.to_mm() as f32;
+    let width = plate.bracket_width.to_mm() as f32;
+    let allowable_bending_mpa = plate.material.yield_strength_mpa() as f32 / case.safety_factor;
+    let allowable_shear_mpa = plate.material.shear_strength_mpa() as f32 / case.safety_factor;
+
+    let passes_at = |t: f32| {
+        bending_stress_mpa(load, span, width, t) <= allowable_bending_mpa
+            && shear_stress_mpa(load, width, t) <= allowable_shear_mpa
+    };
+    let required_thickness_mm = (1..u16::MAX)
+        .find(|&t| passes_at(t as f32))
+        .unwrap_or(u16::MAX);
+
+    let thickness = plate.plate_thickness.to_mm() as f32;
+    LoadCheck {
+        case: *case,
+        bending_stress_mpa: bending_stress_mpa(load, span, width, thickness),
+        allowable_bending_mpa,
+        shear_stress_mpa: shear_stress_mpa(load, width, thickness),
+        allowable_shear_mpa,
+        required_thickness_mm,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use super::*;
+    use domain::{Length, Material};
+    use std::string::ToString;
+
+    #[test]
+    fn test_default_plate_passes_rated_load() {
+        let plate = ActuatorPlate::default();
+        let check = check_load(&plate, &LoadCase::rated(&plate));
+
+        assert_eq!(check.case.load, Newtons(3000));
+        assert!(check.passes());
+        assert!(check.required_thickness_mm <= plate.plate_thickness.whole_mm_ceil());
+    }
+
+    #[test]
+    fn test_thin_aluminum_plate_fails_heavy_load() {
+        let plate = ActuatorPlate {
+            material: Material::Aluminum,
+            plate_thickness: Length::from_mm(3),
+            bracket_width: Length::from_mm(50),
+            bolt_spacing: Length::from_mm(200),
+            ..Default::default()
+        };
+        let check = check_load(&plate, &LoadCase::new(Newtons(5000), 2.0));
+
+        // 3 × 5000 × 200 / (2 × 50 × 9) ≈ 3333 MPa against 138 MPa allowable
+        assert!(!check.passes());
+        assert!((check.bending_stress_mpa - 3333.3).abs() < 0.1);
+        assert_eq!(check.allowable_bending_mpa, 138.0);
+        assert_eq!(check.required_thickness_mm, 15);
+        assert!(check.to_string().ends_with("FAIL, needs at least 15 mm thickness"));
+    }
+
+    #[test]
+    fn test_required_thickness_passes_and_one_less_fails() {
+        let plate = ActuatorPlate::default();
+        let case = LoadCase::new(Newtons(40_000), 3.0);
+        let required = check_load(&plate, &case).required_thickness_mm;
+
+        let at = |t: u16| {
+            let plate = ActuatorPlate { plate_thickness: Length::from_mm(t), ..plate };
+            check_load(&plate, &case).passes()
+        };
+        assert!(at(required));
+        assert!(!at(required - 1));
+    }
+
+    #[test]
+    fn test_higher_safety_factor_lowers_allowables() {
+        let plate = ActuatorPlate::default();
+        let relaxed = check_load(&plate, &LoadCase::new(Newtons(1000), 1.0));
+        let strict = check_load(&plate, &LoadCase::new(Newtons(1000), 4.0));
+
+        assert_eq!(relaxed.allowable_shear_mpa, 4.0 * strict.allowable_shear_mpa);
+        assert_eq!(relaxed.bending_stress_mpa, strict.bending_stress_mpa);
+        assert!(strict.utilization() > relaxed.utilization());
+    }
+
+    #[test]
+    fn test_default_plate_bolts_carry_rated_load() {
+        let plate = ActuatorPlate::default();
+        let check = check_bolts(&plate, Newtons(3000));
+
+        // 750 N per M10 bolt: 750 / 58 mm² in shear, 750 / (10 × 8) mm² in bearing
+        assert_eq!(check.force_per_bolt_n, 750.0);
+        assert!((check.bolt_shear_stress_mpa - 12.93).abs() < 0.01);
+        assert_eq!(check.plate_bearing_stress_mpa, 9.375);
+        assert!(check.is_adequate());
+        assert_eq!(
+            check.to_string(),
+            "Bolt check, 3000 N on 4 bolts: shear 12.9/369.3 MPa (SF 28.6), bearing 9.4/276.0 MPa (SF 29.4): ok"
+        );
+    }
+
+    #[test]
+    fn test_small_bolts_in_thin_brass_warn() {
+        let plate = ActuatorPlate {
+            bolt_size: domain::BoltSize::M3,
+            material: Material::Brass,
+            plate_thickness: Length::from_mm(2),
+            ..Default::default()
+        };
+        let check = check_bolts(&plate, Newtons(4000));
+
+        // 1000 N per bolt bears 1000 / (3 × 2) = 167 MPa on 124 MPa brass
+        assert!(check.bearing_safety_factor() < 1.0);
+        assert!(check.shear_safety_factor() < 2.0);
+        assert_eq!(check.safety_factor(), check.bearing_safety_factor());
+        assert!(!check.is_adequate());
+        assert!(check.to_string().ends_with("safety factor below 2.0"));
+    }
+}
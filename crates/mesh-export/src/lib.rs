@@ -0,0 +1,373 @@
+//! OBJ and 3MF meshes of actuator plates, for 3D printing.
+//!
+//! The engine already tessellates every plate into `source.stl`, so rather than
+//! asking it for two more conversions, [`Mesh::from_stl`] reads that file (ASCII or
+//! binary) and [`Mesh::to_obj`] and [`Mesh::to_3mf`] write it back out. STL stores
+//! every triangle's corners separately; both formats here index shared vertices,
+//! so duplicates are merged on the way in.
+//!
+//! A 3MF file is a ZIP package. Its three parts are small XML documents, so they are
+//! stored uncompressed, which every slicer accepts and needs nothing beyond a CRC-32.
+//! Coordinates are written as they appear in the STL, which the engine exports in
+//! millimeters.
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+/// A triangle mesh with shared vertices.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mesh {
+    /// Vertex positions, in the order they first appear in the STL
+    pub vertices: Vec<[f32; 3]>,
+    /// Indices into [`Mesh::vertices`], counterclockwise seen from outside
+    pub triangles: Vec<[u32; 3]>,
+}
+
+/// An STL file that can't be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MeshError {
+    /// Neither a binary STL of the length its header promises nor ASCII from
+    /// `solid` to `endsolid`.
+    NotStl,
+    /// A `vertex` line on this line number doesn't have three numbers.
+    InvalidVertex(usize),
+    /// The number of vertices in an ASCII STL isn't a multiple of three.
+    IncompleteTriangle,
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshError::NotStl => write!(f, "not an ASCII or binary STL file"),
+            MeshError::InvalidVertex(line) => write!(f, "line {}: expected `vertex x y z`", line),
+            MeshError::IncompleteTriangle => write!(f, "the last facet has fewer than three vertices"),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+const MODEL_PATH: &str = "3D/3dmodel.model";
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+ <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+ <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>
+</Types>
+"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+ <Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>
+"#;
+
+impl Mesh {
+    /// Read an ASCII or binary STL file.
+    ///
+    /// A binary file's header may itself start with `solid`, so it's recognized by
+    /// its length matching the triangle count at byte 80.
+    pub fn from_stl(data: &[u8]) -> Result<Mesh, MeshError> {
+        let mut mesh = Builder::default();
+        if let Some(count) = binary_triangle_count(data) {
+            for facet in data[84..].chunks_exact(50).take(count) {
+                // Skip the 12-byte normal; slicers recompute it from the winding
+                let corners: Vec<[f32; 3]> = facet[12..48]
+                    .chunks_exact(12)
+                    .map(|xyz| {
+                        let coordinate = |i: usize| f32::from_le_bytes(xyz[i..i + 4].try_into().unwrap());
+                        [coordinate(0), coordinate(4), coordinate(8)]
+                    })
+                    .collect();
+                mesh.triangle([corners[0], corners[1], corners[2]]);
+            }
+            return Ok(mesh.mesh);
+        }
+
+        let text = std::str::from_utf8(data).map_err(|_| MeshError::NotStl)?;
+        let text = text.trim();
+        let ends = text.lines().last().is_some_and(|line| line.trim().starts_with("endsolid"));
+        if !text.starts_with("solid") || !ends {
+            return Err(MeshError::NotStl);
+        }
+        let mut corners = Vec::with_capacity(3);
+        for (i, line) in text.lines().enumerate() {
+            let mut words = line.split_whitespace();
+            if words.next() != Some("vertex") {
+                continue;
+            }
+            let coordinates: Vec<f32> = words.map_while(|word| word.parse().ok()).collect();
+            let &[x, y, z] = coordinates.as_slice() else {
+                return Err(MeshError::InvalidVertex(i + 1));
+            };
+            corners.push([x, y, z]);
+            if corners.len() == 3 {
+                mesh.triangle([corners[0], corners[1], corners[2]]);
+                corners.clear();
+            }
+        }
+        if !corners.is_empty() {
+            return Err(MeshError::IncompleteTriangle);
+        }
+        Ok(mesh.mesh)
+    }
+
+    /// Write the mesh as a Wavefront OBJ file.
+    pub fn to_obj(&self) -> String {
+        let mut obj = String::from("# Actuator plate, millimeters\no plate\n");
+        for [x, y, z] in &self.vertices {
+            let _ = writeln!(obj, "v {} {} {}", x, y, z);
+        }
+        // OBJ indices start at 1
+        for [a, b, c] in &self.triangles {
+            let _ = writeln!(obj, "f {} {} {}", a + 1, b + 1, c + 1);
+        }
+        obj
+    }
+
+    /// Write the mesh as a 3MF package with a single object, in millimeters.
+    pub fn to_3mf(&self) -> Vec<u8> {
+        let mut model = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<model unit=\"millimeter\" xml:lang=\"en-US\" ",
+            "xmlns=\"http://schemas.microsoft.com/3dmanufacturing/core/2015/02\">\n",
+            " <resources>\n  <object id=\"1\" name=\"plate\" type=\"model\">\n   <mesh>\n    <vertices>\n",
+        ));
+        for [x, y, z] in &self.vertices {
+            let _ = writeln!(model, "     <vertex x=\"{}\" y=\"{}\" z=\"{}\"/>", x, y, z);
+        }
+        model.push_str("    </vertices>\n    <triangles>\n");
+        for [a, b, c] in &self.triangles {
+            let _ = writeln!(model, "     <triangle v1=\"{}\" v2=\"{}\" v3=\"{}\"/>", a, b, c);
+        }
+        model.push_str(concat!(
+            "    </triangles>\n   </mesh>\n  </object>\n </resources>\n",
+            " <build>\n  <item objectid=\"1\"/>\n </build>\n</model>\n",
+        ));
+
+        stored_zip(&[
+            ("[Content_Types].xml", CONTENT_TYPES.as_bytes()),
+            ("_rels/.rels", RELATIONSHIPS.as_bytes()),
+            (MODEL_PATH, model.as_bytes()),
+        ])
+    }
+}
+
+/// The triangle count of a binary STL, if `data` is one.
+fn binary_triangle_count(data: &[u8]) -> Option<usize> {
+    let count = u32::from_le_bytes(data.get(80..84)?.try_into().ok()?) as usize;
+    (data.len() == 84 + count.checked_mul(50)?).then_some(count)
+}
+
+/// Collects triangles, merging corners at the same position into one vertex.
+#[derive(Default)]
+struct Builder {
+    mesh: Mesh,
+    indices: HashMap<[u32; 3], u32>,
+}
+
+impl Builder {
+    fn triangle(&mut self, corners: [[f32; 3]; 3]) {
+        let triangle = corners.map(|corner| self.vertex(corner));
+        // Slivers collapsed by merging have no area to print
+        if triangle[0] != triangle[1] && triangle[1] != triangle[2] && triangle[0] != triangle[2] {
+            self.mesh.triangles.push(triangle);
+        }
+    }
+
+    fn vertex(&mut self, position: [f32; 3]) -> u32 {
+        // `+ 0.0` turns -0 into 0, so the two merge and neither prints as `-0`
+        let position = position.map(|coordinate| coordinate + 0.0);
+        let vertices = &mut self.mesh.vertices;
+        *self.indices.entry(position.map(f32::to_bits)).or_insert_with(|| {
+            vertices.push(position);
+            (vertices.len() - 1) as u32
+        })
+    }
+}
+
+/// A ZIP archive of `files`, stored without compression.
+fn stored_zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+    // 1980-01-01 00:00, the earliest MS-DOS date, so the bytes never change
+    const DOS_DATE: u16 = (1 << 5) | 1;
+    let mut zip = Vec::new();
+    let mut directory = Vec::new();
+
+    for (name, data) in files {
+        let offset = zip.len() as u32;
+        let crc = crc32(data);
+        let size = data.len() as u32;
+        let name_len = name.len() as u16;
+
+        zip.extend(0x0403_4b50u32.to_le_bytes());
+        for field in [20u16, 0, 0, 0, DOS_DATE] {
+            zip.extend(field.to_le_bytes());
+        }
+        for field in [crc, size, size] {
+            zip.extend(field.to_le_bytes());
+        }
+        zip.extend(name_len.to_le_bytes());
+        zip.extend(0u16.to_le_bytes());
+        zip.extend(name.as_bytes());
+        zip.extend(*data);
+
+        directory.extend(0x0201_4b50u32.to_le_bytes());
+        for field in [20u16, 20, 0, 0, 0, DOS_DATE] {
+            directory.extend(field.to_le_bytes());
+        }
+        for field in [crc, size, size] {
+            directory.extend(field.to_le_bytes());
+        }
+        for field in [name_len, 0, 0, 0, 0] {
+            directory.extend(field.to_le_bytes());
+        }
+        for field in [0u32, offset] {
+            directory.extend(field.to_le_bytes());
+        }
+        directory.extend(name.as_bytes());
+    }
+
+    let directory_offset = zip.len() as u32;
+    let directory_size = directory.len() as u32;
+    zip.extend(directory);
+    zip.extend(0x0605_4b50u32.to_le_bytes());
+    let entries = files.len() as u16;
+    for field in [0u16, 0, entries, entries] {
+        zip.extend(field.to_le_bytes());
+    }
+    for field in [directory_size, directory_offset] {
+        zip.extend(field.to_le_bytes());
+    }
+    zip.extend(0u16.to_le_bytes());
+    zip
+}
+
+/// The CRC-32 (IEEE) that ZIP stores for each file.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two triangles sharing an edge: a unit square in the XY plane.
+    const SQUARE: &str = "solid square
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 1 1 0
+    endloop
+  endfacet
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 1 0
+      vertex -0 1 0
+    endloop
+  endfacet
+endsolid square
+";
+
+    fn binary(triangles: &[[[f32; 3]; 3]]) -> Vec<u8> {
+        // A header starting with "solid" must not be mistaken for ASCII
+        let mut stl = b"solid binary".to_vec();
+        stl.resize(80, 0);
+        stl.extend((triangles.len() as u32).to_le_bytes());
+        for triangle in triangles {
+            stl.extend([0u8; 12]);
+            for coordinate in triangle.iter().flatten() {
+                stl.extend(coordinate.to_le_bytes());
+            }
+            stl.extend([0u8; 2]);
+        }
+        stl
+    }
+
+    /// Each file in a ZIP written by [`stored_zip`], checking its CRC.
+    fn unzip(zip: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let u16_at = |i: usize| u16::from_le_bytes([zip[i], zip[i + 1]]) as usize;
+        let u32_at = |i: usize| u32::from_le_bytes(zip[i..i + 4].try_into().unwrap()) as usize;
+        let end = zip.len() - 22;
+        assert_eq!(u32_at(end), 0x0605_4b50);
+        let mut entry = u32_at(end + 16);
+        (0..u16_at(end + 10))
+            .map(|_| {
+                assert_eq!(u32_at(entry), 0x0201_4b50);
+                let (crc, size, name_len) = (u32_at(entry + 16), u32_at(entry + 24), u16_at(entry + 28));
+                let name = String::from_utf8(zip[entry + 46..entry + 46 + name_len].to_vec()).unwrap();
+                let local = u32_at(entry + 42);
+                assert_eq!(u32_at(local), 0x0403_4b50);
+                let start = local + 30 + u16_at(local + 26);
+                let data = zip[start..start + size].to_vec();
+                assert_eq!(crc32(&data) as usize, crc, "{}", name);
+                entry += 46 + name_len;
+                (name, data)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ascii_and_binary_stl_merge_shared_corners() {
+        let square = Mesh::from_stl(SQUARE.as_bytes()).unwrap();
+        assert_eq!(square.vertices, [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]]);
+        assert_eq!(square.triangles, [[0, 1, 2], [0, 2, 3]]);
+
+        let stl = binary(&[
+            [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]],
+            [[0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+        ]);
+        assert_eq!(Mesh::from_stl(&stl), Ok(square));
+        assert_eq!(Mesh::from_stl(&binary(&[])), Ok(Mesh::default()));
+        assert_eq!(Mesh::from_stl(b"solid empty\nendsolid empty\n"), Ok(Mesh::default()));
+    }
+
+    #[test]
+    fn test_malformed_stl_is_rejected() {
+        assert_eq!(Mesh::from_stl(b"ISO-10303-21;"), Err(MeshError::NotStl));
+        assert_eq!(Mesh::from_stl(&binary(&[])[..83]), Err(MeshError::NotStl));
+        assert_eq!(Mesh::from_stl(&SQUARE.as_bytes()[..120]), Err(MeshError::NotStl));
+        assert_eq!(
+            Mesh::from_stl(b"solid s\nfacet\nouter loop\nvertex 0 0\nendsolid s\n"),
+            Err(MeshError::InvalidVertex(4))
+        );
+        assert_eq!(
+            Mesh::from_stl(b"solid s\nvertex 0 0 0\nvertex 1 0 0\nendsolid s\n"),
+            Err(MeshError::IncompleteTriangle)
+        );
+    }
+
+    #[test]
+    fn test_obj_indexes_from_one() {
+        let obj = Mesh::from_stl(SQUARE.as_bytes()).unwrap().to_obj();
+        assert_eq!(
+            obj,
+            "# Actuator plate, millimeters\no plate\nv 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3\nf 1 3 4\n"
+        );
+    }
+
+    #[test]
+    fn test_3mf_is_a_zip_package_with_the_model() {
+        let mesh = Mesh::from_stl(SQUARE.as_bytes()).unwrap();
+        let package = mesh.to_3mf();
+        assert_eq!(package, mesh.to_3mf());
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let files = unzip(&package);
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["[Content_Types].xml", "_rels/.rels", MODEL_PATH]);
+        let model = String::from_utf8(files[2].1.clone()).unwrap();
+        assert!(model.contains("unit=\"millimeter\""));
+        assert!(model.contains("<vertex x=\"0\" y=\"1\" z=\"0\"/>"), "{}", model);
+        assert!(model.contains("<triangle v1=\"0\" v2=\"2\" v3=\"3\"/>"), "{}", model);
+        assert_eq!(model.matches("<vertex ").count(), 4);
+    }
+}
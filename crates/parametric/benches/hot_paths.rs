@@ -0,0 +1,92 @@
+//! Benchmarks for the per-request hot paths: validation, cache-key hashing,
+//! canonical serialization, and KCL emission. Cache keys and the whole KCL program
+//! are also timed for a plate with every option set.
+//!
+//! Compare against the checked-in baseline with `just bench`; refresh it with
+//! `just bench-baseline` after an intentional performance change.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use domain::{ActuatorPlate, HoleSpec, Length, PinFit, PlateFamily, Tolerance};
+use std::hint::black_box;
+
+fn invalid_plate() -> ActuatorPlate {
+    ActuatorPlate {
+        bolt_spacing: Length::from_mm(0),
+        plate_thickness: Length::from_mm(1),
+        ..Default::default()
+    }
+}
+
+/// A plate that takes every optional branch of the cache key and the KCL params:
+/// a fractional length, recessed bolt holes, tolerances, a non-standard family,
+/// and a pin fit.
+fn featured_plate() -> ActuatorPlate {
+    let mut plate = ActuatorPlate {
+        bolt_spacing: Length::from_micrometers(60_500),
+        bolt_holes: HoleSpec::Counterbore {
+            diameter: Length::from_mm(18),
+            depth: Length::from_mm(11),
+        },
+        family: PlateFamily::DualPin { pin_spacing: Length::from_mm(60) },
+        pin_fit: Some(PinFit::Press),
+        ..Default::default()
+    };
+    plate.tolerances.bolt_spacing = Some(Tolerance::Symmetric(Length::from_micrometers(100)));
+    plate.tolerances.plate_thickness = Some(Tolerance::Deviation { upper_um: 50, lower_um: -20 });
+    plate.tolerances.pin_diameter = Some("H7/g6".parse().unwrap());
+    plate
+}
+
+fn bench_validation(c: &mut Criterion) {
+    let valid = ActuatorPlate::default();
+    let invalid = invalid_plate();
+
+    let mut group = c.benchmark_group("validation");
+    group.bench_function("validate_valid", |b| b.iter(|| validation::validate(black_box(&valid))));
+    group.bench_function("validate_invalid", |b| {
+        b.iter(|| validation::validate(black_box(&invalid)))
+    });
+    group.bench_function("stress_utilization", |b| {
+        b.iter(|| validation::stress_utilization(black_box(&valid)))
+    });
+    group.bench_function("minimum_thickness_mm", |b| {
+        b.iter(|| validation::minimum_thickness_mm(black_box(&valid)))
+    });
+    group.finish();
+}
+
+fn bench_cache_key(c: &mut Criterion) {
+    let plate = ActuatorPlate::default();
+    let featured = featured_plate();
+    c.bench_function("cache_key", |b| b.iter(|| black_box(&plate).cache_key()));
+    c.bench_function("cache_key_featured", |b| b.iter(|| black_box(&featured).cache_key()));
+}
+
+fn bench_serialization(c: &mut Criterion) {
+    let plate = ActuatorPlate::default();
+    let json = serde_json::to_vec(&plate).unwrap();
+
+    let mut group = c.benchmark_group("serialization");
+    group.bench_function("to_json", |b| b.iter(|| serde_json::to_vec(black_box(&plate))));
+    group.bench_function("from_json", |b| {
+        b.iter(|| serde_json::from_slice::<ActuatorPlate>(black_box(&json)))
+    });
+    group.finish();
+}
+
+fn bench_kcl(c: &mut Criterion) {
+    let plate = ActuatorPlate::default();
+    let featured = featured_plate();
+    c.bench_function("params_kcl", |b| b.iter(|| parametric::params_kcl(black_box(&plate))));
+    c.bench_function("kcl_emit", |b| b.iter(|| parametric::kcl::emit(black_box(&plate))));
+    c.bench_function("kcl_emit_featured", |b| b.iter(|| parametric::kcl::emit(black_box(&featured))));
+}
+
+criterion_group!(
+    benches,
+    bench_validation,
+    bench_cache_key,
+    bench_serialization,
+    bench_kcl
+);
+criterion_main!(benches);
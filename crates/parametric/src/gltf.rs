@@ -0,0 +1,428 @@
+//! Post-processing for the engine's glTF, the file the configurator's viewer loads.
+//!
+//! [`post_process`] makes up to three changes:
+//!
+//! - Units: glTF is in meters, but the engine can write the plate's millimeters as
+//!   is, so a 150 mm plate loads 150 m across. The mesh's extent is compared with
+//!   the plate's, and a millimeter scene's roots are wrapped in a node scaling them
+//!   by 0.001.
+//! - Naming: the nodes holding the plate's mesh are named `plate-<cache key>`, so a
+//!   downloaded file can be matched to its cached model.
+//! - Compression, with [`GltfCompression::Quantize`]: positions are stored as 16-bit
+//!   and normals as 8-bit integers (`KHR_mesh_quantization`), about half the vertex
+//!   data, and the mesh nodes scale them back. three.js, and so the viewer, reads
+//!   these without a decoder. Draco would shrink the file further, but needs a
+//!   native encoder here and a decoder download in the browser, so it isn't offered.
+//!
+//! Files without meshes come back unchanged. Compression is skipped for files whose
+//! buffers aren't embedded, or that already use extensions.
+
+use std::collections::HashMap;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use domain::ActuatorPlate;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+const FLOAT: u64 = 5126;
+const BYTE: u64 = 5120;
+const UNSIGNED_SHORT: u64 = 5123;
+const ARRAY_BUFFER: u64 = 34962;
+const QUANTIZATION: &str = "KHR_mesh_quantization";
+const DATA_URI: &str = "data:application/octet-stream;base64,";
+
+/// How the glTF's geometry is compressed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GltfCompression {
+    /// 32-bit floats, as the engine wrote them
+    #[default]
+    None,
+    /// `KHR_mesh_quantization`: 16-bit positions and 8-bit normals
+    Quantize,
+}
+
+impl std::str::FromStr for GltfCompression {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "quantize" => Ok(Self::Quantize),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Axis-aligned bounds of every mesh position, from the accessors' `min` and `max`.
+#[derive(Clone, Copy, Debug)]
+struct Bounds {
+    min: [f64; 3],
+    max: [f64; 3],
+}
+
+impl Bounds {
+    /// The largest side.
+    fn extent(&self) -> f64 {
+        (0..3).map(|axis| self.max[axis] - self.min[axis]).fold(0.0, f64::max)
+    }
+}
+
+/// The transform mesh nodes apply to turn quantized positions back into the
+/// originals: scale uniformly, so normals keep their direction, then translate.
+#[derive(Clone, Copy, Debug)]
+struct Dequantize {
+    translation: [f64; 3],
+    scale: f64,
+}
+
+/// Fix the units of the engine's glTF, name its mesh nodes after `plate`, and
+/// apply `compression`. The error is a message for the user.
+pub fn post_process(gltf: &[u8], plate: &ActuatorPlate, compression: GltfCompression) -> Result<Vec<u8>, String> {
+    let mut doc: Value = serde_json::from_slice(gltf).map_err(|e| format!("The engine's glTF is invalid: {}", e))?;
+    let Some(bounds) = bounds(&doc) else {
+        return Ok(gltf.to_vec());
+    };
+
+    let dequantize = match compression {
+        GltfCompression::None => None,
+        GltfCompression::Quantize => quantize(&mut doc, bounds),
+    };
+    name_mesh_nodes(&mut doc, &format!("plate-{}", plate.cache_key()), dequantize);
+
+    // A millimeter file is 1000 times too big, a meter one the right size; anything
+    // nearer the first (on a log scale) is taken to be in millimeters
+    let plate_mm = plate.bracket_width.to_mm().max(plate.bracket_height.to_mm());
+    if bounds.extent() > plate_mm / 1000f64.sqrt() {
+        scale_scenes(&mut doc, 0.001);
+    }
+    serde_json::to_vec(&doc).map_err(|e| format!("Failed to write the glTF: {}", e))
+}
+
+/// Accessor indices of every mesh primitive's `attribute`.
+fn attribute_accessors(doc: &Value, attribute: &str) -> Vec<usize> {
+    let mut accessors: Vec<usize> = doc["meshes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|mesh| mesh["primitives"].as_array().into_iter().flatten())
+        .filter_map(|primitive| primitive["attributes"][attribute].as_u64())
+        .map(|index| index as usize)
+        .collect();
+    accessors.sort_unstable();
+    accessors.dedup();
+    accessors
+}
+
+fn bounds(doc: &Value) -> Option<Bounds> {
+    let mut bounds: Option<Bounds> = None;
+    for index in attribute_accessors(doc, "POSITION") {
+        let accessor = &doc["accessors"][index];
+        let corner = |key: &str| -> Option<[f64; 3]> {
+            let values = accessor[key].as_array()?;
+            Some([values.first()?.as_f64()?, values.get(1)?.as_f64()?, values.get(2)?.as_f64()?])
+        };
+        let (Some(min), Some(max)) = (corner("min"), corner("max")) else {
+            continue;
+        };
+        bounds = Some(match bounds {
+            None => Bounds { min, max },
+            Some(b) => Bounds {
+                min: [0, 1, 2].map(|axis| b.min[axis].min(min[axis])),
+                max: [0, 1, 2].map(|axis| b.max[axis].max(max[axis])),
+            },
+        });
+    }
+    bounds
+}
+
+/// Quantize positions against `bounds` and normals to bytes, repacking everything
+/// into one embedded buffer. `None` if the file can't be quantized, in which case
+/// it's left as it was.
+fn quantize(doc: &mut Value, bounds: Bounds) -> Option<Dequantize> {
+    if doc["extensionsUsed"].as_array().is_some_and(|used| !used.is_empty()) {
+        return None;
+    }
+    let accessors = doc["accessors"].as_array()?;
+    if accessors.iter().any(|accessor| accessor.get("sparse").is_some()) {
+        return None;
+    }
+    let buffers = doc["buffers"]
+        .as_array()?
+        .iter()
+        .map(|buffer| BASE64.decode(buffer["uri"].as_str()?.strip_prefix("data:")?.split_once(";base64,")?.1).ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    // Every position has to be quantized, since the mesh nodes will scale them all
+    let mut positions = Vec::new();
+    for index in attribute_accessors(doc, "POSITION") {
+        positions.push((index, read_vec3(doc, &buffers, index)?));
+    }
+    let normals: Vec<_> = attribute_accessors(doc, "NORMAL")
+        .into_iter()
+        .filter_map(|index| Some((index, read_vec3(doc, &buffers, index)?)))
+        .collect();
+
+    let scale = match bounds.extent() {
+        extent if extent > 0.0 => extent / f64::from(u16::MAX),
+        _ => 1.0,
+    };
+    let to_u16 = |value: f64, axis: usize| ((value - bounds.min[axis]) / scale).round().clamp(0.0, f64::from(u16::MAX)) as u16;
+
+    // Attributes must be 4-byte aligned, so each vertex gets a padding component
+    let mut encoded = HashMap::new();
+    for (index, values) in positions {
+        let mut bytes = Vec::with_capacity(values.len() * 8);
+        for vertex in values {
+            for (axis, value) in vertex.into_iter().enumerate() {
+                bytes.extend(to_u16(value, axis).to_le_bytes());
+            }
+            bytes.extend([0, 0]);
+        }
+        let accessor = &mut doc["accessors"][index];
+        accessor["componentType"] = json!(UNSIGNED_SHORT);
+        for key in ["min", "max"] {
+            let corner: Vec<u16> = (0..3).map(|axis| to_u16(accessor[key][axis].as_f64().unwrap_or(0.0), axis)).collect();
+            accessor[key] = json!(corner);
+        }
+        encoded.insert(index, (bytes, 8));
+    }
+    for (index, values) in normals {
+        let mut bytes = Vec::with_capacity(values.len() * 4);
+        for vertex in values {
+            bytes.extend(vertex.map(|value| (value * 127.0).round().clamp(-127.0, 127.0) as i8 as u8));
+            bytes.push(0);
+        }
+        let accessor = &mut doc["accessors"][index];
+        accessor["componentType"] = json!(BYTE);
+        accessor["normalized"] = json!(true);
+        if let Some(accessor) = accessor.as_object_mut() {
+            accessor.remove("min");
+            accessor.remove("max");
+        }
+        encoded.insert(index, (bytes, 4));
+    }
+
+    repack(doc, &buffers, encoded);
+    doc["extensionsUsed"] = json!([QUANTIZATION]);
+    doc["extensionsRequired"] = json!([QUANTIZATION]);
+    Some(Dequantize {
+        translation: bounds.min,
+        scale,
+    })
+}
+
+/// A float VEC3 accessor's values, or `None` if it's another type or runs past its buffer.
+fn read_vec3(doc: &Value, buffers: &[Vec<u8>], index: usize) -> Option<Vec<[f64; 3]>> {
+    let accessor = &doc["accessors"][index];
+    if accessor["componentType"] != FLOAT || accessor["type"] != "VEC3" {
+        return None;
+    }
+    let view = &doc["bufferViews"][accessor["bufferView"].as_u64()? as usize];
+    let data = buffers.get(view["buffer"].as_u64()? as usize)?;
+    let start = (view["byteOffset"].as_u64().unwrap_or(0) + accessor["byteOffset"].as_u64().unwrap_or(0)) as usize;
+    let stride = view["byteStride"].as_u64().unwrap_or(12) as usize;
+    (0..accessor["count"].as_u64()? as usize)
+        .map(|vertex| {
+            let bytes = data.get(start + vertex * stride..start + vertex * stride + 12)?;
+            let component = |c: usize| f64::from(f32::from_le_bytes(bytes[c * 4..c * 4 + 4].try_into().unwrap()));
+            Some([component(0), component(1), component(2)])
+        })
+        .collect()
+}
+
+/// Rewrite the buffers as one embedded buffer holding the `encoded` accessors' new
+/// data and the views everything else still uses. The old position and normal
+/// views are dropped.
+fn repack(doc: &mut Value, buffers: &[Vec<u8>], mut encoded: HashMap<usize, (Vec<u8>, u64)>) {
+    let old_views = doc["bufferViews"].as_array().cloned().unwrap_or_default();
+    let mut data = Vec::new();
+    let mut views = Vec::new();
+    let mut copied = HashMap::new();
+    let mut push = |view: Value, bytes: &[u8]| {
+        while data.len() % 4 != 0 {
+            data.push(0);
+        }
+        let mut view = view;
+        view["buffer"] = json!(0);
+        view["byteOffset"] = json!(data.len());
+        view["byteLength"] = json!(bytes.len());
+        data.extend_from_slice(bytes);
+        views.push(view);
+        views.len() - 1
+    };
+    let mut copy = |old: u64, push: &mut dyn FnMut(Value, &[u8]) -> usize| {
+        *copied.entry(old).or_insert_with(|| {
+            let view = old_views[old as usize].clone();
+            let start = view["byteOffset"].as_u64().unwrap_or(0) as usize;
+            let end = start + view["byteLength"].as_u64().unwrap_or(0) as usize;
+            let buffer = &buffers[view["buffer"].as_u64().unwrap_or(0) as usize];
+            push(view, &buffer[start.min(buffer.len())..end.min(buffer.len())])
+        })
+    };
+
+    for (index, accessor) in doc["accessors"].as_array_mut().into_iter().flatten().enumerate() {
+        if let Some((bytes, stride)) = encoded.remove(&index) {
+            accessor["bufferView"] = json!(push(json!({ "byteStride": stride, "target": ARRAY_BUFFER }), &bytes));
+            if let Some(accessor) = accessor.as_object_mut() {
+                accessor.remove("byteOffset");
+            }
+        } else if let Some(old) = accessor["bufferView"].as_u64() {
+            accessor["bufferView"] = json!(copy(old, &mut push));
+        }
+    }
+    for image in doc["images"].as_array_mut().into_iter().flatten() {
+        if let Some(old) = image["bufferView"].as_u64() {
+            image["bufferView"] = json!(copy(old, &mut push));
+        }
+    }
+
+    doc["bufferViews"] = json!(views);
+    doc["buffers"] = json!([{ "byteLength": data.len(), "uri": format!("{}{}", DATA_URI, BASE64.encode(&data)) }]);
+}
+
+/// Name each node holding a mesh `name`, numbered when there are several. With
+/// `dequantize`, the mesh moves to a new child node, so the dequantization doesn't
+/// disturb the node's own transform or its other children.
+fn name_mesh_nodes(doc: &mut Value, name: &str, dequantize: Option<Dequantize>) {
+    let Some(nodes) = doc["nodes"].as_array_mut() else {
+        return;
+    };
+    let mesh_nodes: Vec<usize> = (0..nodes.len()).filter(|&index| nodes[index].get("mesh").is_some()).collect();
+    for (n, &index) in mesh_nodes.iter().enumerate() {
+        let label = match mesh_nodes.len() {
+            1 => name.to_string(),
+            _ => format!("{}-{}", name, n + 1),
+        };
+        match dequantize {
+            None => nodes[index]["name"] = json!(label),
+            Some(Dequantize { translation, scale }) => {
+                let mesh = nodes[index].as_object_mut().and_then(|node| node.remove("mesh"));
+                nodes.push(json!({
+                    "name": label,
+                    "mesh": mesh,
+                    "translation": translation,
+                    "scale": [scale, scale, scale],
+                }));
+                let child = nodes.len() - 1;
+                match nodes[index]["children"].as_array_mut() {
+                    Some(children) => children.push(json!(child)),
+                    None => nodes[index]["children"] = json!([child]),
+                }
+            }
+        }
+    }
+}
+
+/// Wrap each scene's root nodes in one node scaled by `scale`.
+fn scale_scenes(doc: &mut Value, scale: f64) {
+    let scene_count = doc["scenes"].as_array().map_or(0, Vec::len);
+    for scene in 0..scene_count {
+        let roots = doc["scenes"][scene]["nodes"].take();
+        let Some(nodes) = doc["nodes"].as_array_mut() else {
+            return;
+        };
+        nodes.push(json!({ "name": "millimeters", "scale": [scale, scale, scale], "children": roots }));
+        doc["scenes"][scene]["nodes"] = json!([nodes.len() - 1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One triangle `size` across with a normal per vertex and u16 indices, as
+    /// glTF with an embedded buffer.
+    fn triangle(size: f32) -> Vec<u8> {
+        let positions = [[0.0, 0.0, 0.0], [size, 0.0, 0.0], [0.0, size * 8.0 / 150.0, size * 2.0 / 3.0]];
+        let mut data = Vec::new();
+        for vertex in positions {
+            data.extend(vertex.iter().flat_map(|c: &f32| c.to_le_bytes()));
+        }
+        for _ in 0..3 {
+            data.extend([0.0f32, 0.6, 0.8].iter().flat_map(|c| c.to_le_bytes()));
+        }
+        data.extend([0u16, 1, 2].iter().flat_map(|i| i.to_le_bytes()));
+        serde_json::to_vec(&json!({
+            "asset": { "version": "2.0" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "nodes": [{ "name": "Body", "mesh": 0 }],
+            "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0, "NORMAL": 1 }, "indices": 2 }] }],
+            "accessors": [
+                { "bufferView": 0, "componentType": FLOAT, "count": 3, "type": "VEC3",
+                  "min": [0.0, 0.0, 0.0], "max": [size, size * 8.0 / 150.0, size * 2.0 / 3.0] },
+                { "bufferView": 1, "componentType": FLOAT, "count": 3, "type": "VEC3" },
+                { "bufferView": 2, "componentType": UNSIGNED_SHORT, "count": 3, "type": "SCALAR" },
+            ],
+            "bufferViews": [
+                { "buffer": 0, "byteOffset": 0, "byteLength": 36, "target": ARRAY_BUFFER },
+                { "buffer": 0, "byteOffset": 36, "byteLength": 36, "target": ARRAY_BUFFER },
+                { "buffer": 0, "byteOffset": 72, "byteLength": 6, "target": 34963 },
+            ],
+            "buffers": [{ "byteLength": data.len(), "uri": format!("{}{}", DATA_URI, BASE64.encode(&data)) }],
+        }))
+        .unwrap()
+    }
+
+    fn process(gltf: &[u8], compression: GltfCompression) -> Value {
+        serde_json::from_slice(&post_process(gltf, &ActuatorPlate::default(), compression).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_millimeter_scenes_are_scaled_and_the_mesh_named() {
+        let name = format!("plate-{}", ActuatorPlate::default().cache_key());
+
+        let doc = process(&triangle(150.0), GltfCompression::None);
+        assert_eq!(doc["nodes"][0]["name"], json!(name));
+        assert_eq!(doc["scenes"][0]["nodes"], json!([1]));
+        assert_eq!(doc["nodes"][1]["scale"], json!([0.001, 0.001, 0.001]));
+        assert_eq!(doc["nodes"][1]["children"], json!([0]));
+        assert_eq!(doc["accessors"][0]["componentType"], json!(FLOAT));
+
+        // Already in meters
+        let doc = process(&triangle(0.15), GltfCompression::None);
+        assert_eq!(doc["nodes"][0]["name"], json!(name));
+        assert_eq!(doc["scenes"][0]["nodes"], json!([0]));
+    }
+
+    #[test]
+    fn test_quantized_positions_dequantize_to_the_originals() {
+        let original = triangle(150.0);
+        let doc = process(&original, GltfCompression::Quantize);
+        assert_eq!(doc["extensionsRequired"], json!([QUANTIZATION]));
+
+        // The mesh moved to a child node that undoes the quantization
+        let mesh_node = &doc["nodes"][1];
+        assert_eq!(doc["nodes"][0]["children"], json!([1]));
+        assert!(doc["nodes"][0].get("mesh").is_none());
+        assert_eq!(mesh_node["name"], json!(format!("plate-{}", ActuatorPlate::default().cache_key())));
+        let scale = mesh_node["scale"][0].as_f64().unwrap();
+        assert!((scale - 150.0 / 65535.0).abs() < 1e-12);
+        assert_eq!(doc["scenes"][0]["nodes"], json!([2]));
+
+        let uri = doc["buffers"][0]["uri"].as_str().unwrap();
+        let data = BASE64.decode(uri.strip_prefix(DATA_URI).unwrap()).unwrap();
+        let view = |accessor: usize| &doc["bufferViews"][doc["accessors"][accessor]["bufferView"].as_u64().unwrap() as usize];
+        let at = view(0)["byteOffset"].as_u64().unwrap() as usize + 8;
+        let x = u16::from_le_bytes([data[at], data[at + 1]]);
+        assert!((f64::from(x) * scale - 150.0).abs() < 1e-9);
+        assert_eq!(doc["accessors"][0]["max"], json!([65535, 3495, 43690]));
+
+        let at = view(1)["byteOffset"].as_u64().unwrap() as usize;
+        assert_eq!(&data[at..at + 4], &[0, 76, 102, 0]);
+        assert_eq!(doc["accessors"][1]["normalized"], json!(true));
+
+        let at = view(2)["byteOffset"].as_u64().unwrap() as usize;
+        assert_eq!(&data[at..at + 6], &[0, 0, 1, 0, 2, 0]);
+        assert!(data.len() < 78);
+    }
+
+    #[test]
+    fn test_files_without_meshes_are_unchanged() {
+        let mock = crate::mock::MOCK_GLTF.as_bytes();
+        assert_eq!(post_process(mock, &ActuatorPlate::default(), GltfCompression::Quantize).unwrap(), mock);
+        assert!(post_process(b"solid", &ActuatorPlate::default(), GltfCompression::None).is_err());
+    }
+}
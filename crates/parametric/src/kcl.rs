@@ -0,0 +1,515 @@
+//! The complete KCL program for a plate, as one string.
+//!
+//! [`emit`] renders everything the engine runs for a plate: the generated
+//! `params.kcl`, then the family template's part file and entry point, each under
+//! a `// file:` header. The templates are the ones compiled into the build and
+//! line endings are normalized, so the output depends only on the plate. Golden
+//! tests pin it for a matrix of plates, so a template regression shows up in
+//! review instead of at the Zoo API.
+//!
+//! [`ZooCli`](crate::ZooCli) copies the templates from `kcl_source_dir` at run time;
+//! the release bundle copies that directory from `src/`, so the two agree. What's
+//! cached with a model is [`read_project`], the files the engine was actually given,
+//! so a deployment whose source directory has drifted still serves its own KCL.
+//!
+//! [`check`] is the pre-flight the CLI runs on the project it wrote before uploading
+//! it. It is not a KCL parser, only a handful of checks for the mistakes a template
+//! edit makes, which the engine otherwise reports as a dropped websocket.
+
+use std::fmt;
+use std::path::Path;
+
+use domain::ActuatorPlate;
+
+use crate::templates::{Template, params_kcl};
+
+/// The files of a plate's KCL project, in dependency order: the name each is
+/// written under in the project directory, and its contents.
+pub fn files(plate: &ActuatorPlate) -> [(&'static str, String); 3] {
+    let template = Template::for_family(&plate.family);
+    [
+        ("params.kcl", normalize(&params_kcl(plate))),
+        (template.part, normalize(template.part_source)),
+        ("main.kcl", normalize(template.main_source)),
+    ]
+}
+
+/// Every file of the plate's KCL project in one string, for snapshots and error
+/// reports.
+pub fn emit(plate: &ActuatorPlate) -> String {
+    join(&files(plate))
+}
+
+/// Every file of the plate's project as written to `dir`, in one string like
+/// [`emit`]'s. `None` if any file is missing, as it is for backends that don't run
+/// KCL.
+pub fn read_project(plate: &ActuatorPlate, dir: &Path) -> Option<String> {
+    let mut project = Vec::new();
+    for (name, _) in files(plate) {
+        let source = std::fs::read_to_string(dir.join(name)).ok()?;
+        project.push((name, normalize(&source)));
+    }
+    Some(join(&project))
+}
+
+fn join(files: &[(&str, String)]) -> String {
+    let mut kcl = String::new();
+    for (i, (name, source)) in files.iter().enumerate() {
+        if i > 0 {
+            kcl.push('\n');
+        }
+        kcl.push_str(&format!("// file: {}\n", name));
+        kcl.push_str(source);
+    }
+    kcl
+}
+
+/// Why a KCL project would fail in the engine, found before it is sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KclError {
+    /// A file doesn't start with `@settings(defaultLengthUnit = ...)`, so the
+    /// engine would read its lengths in its own default unit.
+    MissingUnits { file: String },
+    /// A `(`, `[`, or `{` that is never closed.
+    Unclosed {
+        file: String,
+        line: usize,
+        column: usize,
+        delimiter: char,
+    },
+    /// A closing delimiter that doesn't match the innermost open one.
+    Unexpected {
+        file: String,
+        line: usize,
+        column: usize,
+        delimiter: char,
+    },
+    /// A string literal still open at the end of its line.
+    UnterminatedString { file: String, line: usize, column: usize },
+    /// `main.kcl`, or a file that is imported, is not in the project.
+    MissingFile { file: String },
+    /// A name imported from a file that doesn't export it.
+    UndefinedImport {
+        name: String,
+        file: String,
+        imported_by: String,
+    },
+    /// A value read from a name that is neither imported nor assigned first.
+    UndefinedName { name: String, file: String, line: usize },
+}
+
+impl fmt::Display for KclError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KclError::MissingUnits { file } => {
+                write!(f, "{} does not start with @settings(defaultLengthUnit = ...)", file)
+            }
+            KclError::Unclosed {
+                file,
+                line,
+                column,
+                delimiter,
+            } => write!(f, "{}:{}:{}: `{}` is never closed", file, line, column, delimiter),
+            KclError::Unexpected {
+                file,
+                line,
+                column,
+                delimiter,
+            } => write!(f, "{}:{}:{}: unexpected `{}`", file, line, column, delimiter),
+            KclError::UnterminatedString { file, line, column } => {
+                write!(f, "{}:{}:{}: string is never closed", file, line, column)
+            }
+            KclError::MissingFile { file } => write!(f, "{} is missing from the project", file),
+            KclError::UndefinedImport {
+                name,
+                file,
+                imported_by,
+            } => write!(f, "{} imports {} from {}, which does not export it", imported_by, name, file),
+            KclError::UndefinedName { name, file, line } => {
+                write!(f, "{}:{}: {} is not defined", file, line, name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for KclError {}
+
+/// Check a KCL project given as `(file name, source)` pairs: every file declares
+/// its length unit, delimiters and strings are balanced, every import names a file
+/// in the project and something it exports, and every value the entry point passes
+/// along comes from an import or an earlier assignment.
+pub fn check(files: &[(&str, String)]) -> Result<(), KclError> {
+    if !files.iter().any(|(name, _)| *name == "main.kcl") {
+        return Err(KclError::MissingFile {
+            file: "main.kcl".to_string(),
+        });
+    }
+    for (name, source) in files {
+        check_units(name, source)?;
+        check_delimiters(name, source)?;
+    }
+    for (name, source) in files {
+        check_names(name, source, files)?;
+    }
+    Ok(())
+}
+
+/// [`check`] the files of the plate's project as written to `dir`.
+pub fn check_project(plate: &ActuatorPlate, dir: &Path) -> Result<(), KclError> {
+    let mut project = Vec::new();
+    for (name, _) in files(plate) {
+        // A file that can't be read is reported by whichever file needs it
+        if let Ok(source) = std::fs::read_to_string(dir.join(name)) {
+            project.push((name, source));
+        }
+    }
+    check(&project)
+}
+
+fn check_units(file: &str, source: &str) -> Result<(), KclError> {
+    let first = source
+        .lines()
+        .map(|line| strip_comment(line).trim())
+        .find(|line| !line.is_empty());
+    match first {
+        Some(line) if line.starts_with("@settings(") && line.contains("defaultLengthUnit") => Ok(()),
+        _ => Err(KclError::MissingUnits { file: file.to_string() }),
+    }
+}
+
+fn check_delimiters(file: &str, source: &str) -> Result<(), KclError> {
+    let mut open: Vec<(char, usize, usize)> = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        let mut chars = line.chars().enumerate().peekable();
+        let mut string_start = None;
+        while let Some((column, c)) = chars.next() {
+            if string_start.is_some() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => string_start = None,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' => string_start = Some(column),
+                '/' if chars.peek().is_some_and(|(_, next)| *next == '/') => break,
+                '(' | '[' | '{' => open.push((c, i + 1, column + 1)),
+                ')' | ']' | '}' => match open.pop() {
+                    Some((opener, ..)) if closer(opener) == c => {}
+                    _ => {
+                        return Err(KclError::Unexpected {
+                            file: file.to_string(),
+                            line: i + 1,
+                            column: column + 1,
+                            delimiter: c,
+                        });
+                    }
+                },
+                _ => {}
+            }
+        }
+        if let Some(column) = string_start {
+            return Err(KclError::UnterminatedString {
+                file: file.to_string(),
+                line: i + 1,
+                column: column + 1,
+            });
+        }
+    }
+    match open.pop() {
+        Some((delimiter, line, column)) => Err(KclError::Unclosed {
+            file: file.to_string(),
+            line,
+            column,
+            delimiter,
+        }),
+        None => Ok(()),
+    }
+}
+
+fn closer(opener: char) -> char {
+    match opener {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+/// Names that are always defined, so passing one along needs no import.
+const BUILTINS: [&str; 5] = ["true", "false", "XY", "XZ", "YZ"];
+
+fn check_names(file: &str, source: &str, files: &[(&str, String)]) -> Result<(), KclError> {
+    let mut defined: Vec<&str> = Vec::new();
+    for line in source.lines() {
+        let Some((names, from)) = strip_comment(line)
+            .trim()
+            .strip_prefix("import ")
+            .and_then(|import| import.split_once(" from "))
+        else {
+            continue;
+        };
+        let from = from.trim().trim_matches('"');
+        let Some((_, exporter)) = files.iter().find(|(name, _)| *name == from) else {
+            return Err(KclError::MissingFile { file: from.to_string() });
+        };
+        let exported = exports(exporter);
+        for name in names.split(',').map(str::trim) {
+            if !exported.contains(&name) {
+                return Err(KclError::UndefinedImport {
+                    name: name.to_string(),
+                    file: from.to_string(),
+                    imported_by: file.to_string(),
+                });
+            }
+            defined.push(name);
+        }
+    }
+    // Only entry points import; part files take everything as arguments
+    if defined.is_empty() {
+        return Ok(());
+    }
+
+    for (i, line) in source.lines().enumerate() {
+        let line = strip_comment(line).trim().trim_end_matches(',');
+        let Some((label, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        let value = value.trim();
+        if is_identifier(label) && is_identifier(value) {
+            if !defined.contains(&value) && !BUILTINS.contains(&value) {
+                return Err(KclError::UndefinedName {
+                    name: value.to_string(),
+                    file: file.to_string(),
+                    line: i + 1,
+                });
+            }
+            defined.push(label);
+        }
+    }
+    Ok(())
+}
+
+/// The names a file exports, from `export name = ...` and `export fn name(...)`.
+fn exports(source: &str) -> Vec<&str> {
+    source
+        .lines()
+        .filter_map(|line| {
+            let rest = strip_comment(line).trim().strip_prefix("export ")?;
+            let rest = rest.strip_prefix("fn ").unwrap_or(rest);
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            Some(&rest[..end])
+        })
+        .collect()
+}
+
+fn is_identifier(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && word.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// The line up to a `//` comment that isn't inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '/' if !in_string && line[i + 1..].starts_with('/') => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Unix line endings, no trailing whitespace, and exactly one final newline, so a
+/// checkout with CRLF line endings or an editor's stray spaces emit the same program.
+fn normalize(source: &str) -> String {
+    let mut normalized: String = source.lines().map(|line| format!("{}\n", line.trim_end())).collect();
+    while normalized.ends_with("\n\n") {
+        normalized.pop();
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::{Count, HoleSpec, Length, PlateFamily};
+
+    use super::*;
+
+    /// Every family with every kind of bolt hole it accepts.
+    fn matrix() -> Vec<(String, ActuatorPlate)> {
+        let families = [
+            PlateFamily::Standard,
+            PlateFamily::Slotted { slot_length: Length::from_mm(12) },
+            PlateFamily::DualPin { pin_spacing: Length::from_mm(120) },
+        ];
+        let holes = [
+            ("through", HoleSpec::Through),
+            (
+                "counterbore",
+                HoleSpec::Counterbore {
+                    diameter: Length::from_micrometers(17_500),
+                    depth: Length::from_mm(6),
+                },
+            ),
+            ("countersink", HoleSpec::Countersink { diameter: Length::from_mm(23) }),
+            ("tapped", HoleSpec::Tapped { thread: "M10x1.25".parse().unwrap() }),
+        ];
+
+        let mut plates = Vec::new();
+        for family in families {
+            for (hole_name, bolt_holes) in holes {
+                let plate = ActuatorPlate {
+                    bolt_holes,
+                    pin_count: match family {
+                        PlateFamily::DualPin { .. } => Count::of(2),
+                        _ => Count::of(6),
+                    },
+                    family,
+                    ..ActuatorPlate::default()
+                };
+                if validation::validate(&plate).is_ok() {
+                    plates.push((format!("{}_{}", family.name(), hole_name), plate));
+                }
+            }
+        }
+        plates
+    }
+
+    #[test]
+    fn test_emitted_kcl_matches_golden_files() {
+        let plates = matrix();
+        // Slots only take through holes; everything else takes all four
+        assert_eq!(plates.len(), 9);
+        for (name, plate) in plates {
+            insta::assert_snapshot!(format!("emit_{}", name), emit(&plate));
+        }
+    }
+
+    #[test]
+    fn test_fractional_lengths_are_emitted_exactly() {
+        let plate = ActuatorPlate {
+            plate_thickness: Length::from_micrometers(7_500),
+            bracket_width: Length::from_micrometers(150_250),
+            ..ActuatorPlate::default()
+        };
+        assert!(validation::validate(&plate).is_ok());
+        let source = emit(&plate);
+        assert!(source.contains("export plateThickness = 7.5\n"), "{}", source);
+        assert!(source.contains("export bracketWidth = 150.25\n"), "{}", source);
+        assert_eq!(check(&files(&plate)), Ok(()));
+    }
+
+    #[test]
+    fn test_emit_is_deterministic() {
+        for (name, plate) in matrix() {
+            let first = emit(&plate);
+            assert_eq!(first, emit(&plate), "{}", name);
+            assert!(!first.contains('\r'), "{}", name);
+            assert!(first.ends_with('\n') && !first.ends_with("\n\n"), "{}", name);
+        }
+
+        assert_eq!(normalize("a = 1  \r\nb = 2\r\n\r\n"), "a = 1\nb = 2\n");
+        assert_eq!(normalize("a = 1"), "a = 1\n");
+    }
+
+    #[test]
+    fn test_emitted_kcl_passes_the_preflight_check() {
+        for (name, plate) in matrix() {
+            assert_eq!(check(&files(&plate)), Ok(()), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_preflight_catches_broken_templates() {
+        let plate = ActuatorPlate::default();
+        let broken = |file: &str, from: &str, to: &str| {
+            let mut project = files(&plate);
+            let (_, source) = project.iter_mut().find(|(name, _)| *name == file).unwrap();
+            assert!(source.contains(from), "{} has no {:?}", file, from);
+            *source = source.replacen(from, to, 1);
+            check(&project).unwrap_err()
+        };
+
+        assert_eq!(
+            broken("params.kcl", "@settings(defaultLengthUnit = mm, kclVersion = 1.0)", ""),
+            KclError::MissingUnits {
+                file: "params.kcl".to_string()
+            }
+        );
+        assert_eq!(
+            broken("main.kcl", "  pin_count = pinCount,\n)", "  pin_count = pinCount,\n"),
+            KclError::Unclosed {
+                file: "main.kcl".to_string(),
+                line: 8,
+                column: 6,
+                delimiter: '(',
+            }
+        );
+        assert!(matches!(
+            broken("plate.kcl", "bracket_height / 2 * -0.9\n  ]", "bracket_height / 2 * -0.9\n  )"),
+            KclError::Unexpected { delimiter: ')', .. }
+        ));
+        assert_eq!(
+            broken("params.kcl", "\"none\"", "\"none"),
+            KclError::UnterminatedString {
+                file: "params.kcl".to_string(),
+                line: 5,
+                column: 21,
+            }
+        );
+        assert_eq!(
+            broken("main.kcl", "\"plate.kcl\"", "\"plates.kcl\""),
+            KclError::MissingFile {
+                file: "plates.kcl".to_string()
+            }
+        );
+        assert_eq!(
+            broken("main.kcl", "pinDiameter, pinCount from", "pinDiameter, pinSpacing from"),
+            KclError::UndefinedImport {
+                name: "pinSpacing".to_string(),
+                file: "params.kcl".to_string(),
+                imported_by: "main.kcl".to_string(),
+            }
+        );
+        assert!(matches!(
+            broken("main.kcl", "pin_count = pinCount", "pin_count = pinTotal"),
+            KclError::UndefinedName { ref name, .. } if name == "pinTotal"
+        ));
+
+        // Comments are not code, even with brackets or slashes in strings
+        let project = [
+            ("main.kcl", "@settings(defaultLengthUnit = mm) // (\nx = \"a // b (\"\n".to_string()),
+        ];
+        assert_eq!(check(&project), Ok(()));
+        assert_eq!(
+            check(&[]),
+            Err(KclError::MissingFile {
+                file: "main.kcl".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_files_match_the_project_the_cli_writes() {
+        let source_dir = crate::ZooCli::from_env().kcl_source_dir;
+        for (name, plate) in matrix() {
+            let project = tempfile::TempDir::new().unwrap();
+            crate::copy_kcl_sources(&plate, &source_dir, project.path()).unwrap();
+            crate::write_params_file(&plate, project.path()).unwrap();
+
+            for (file, source) in files(&plate) {
+                let written = std::fs::read_to_string(project.path().join(file)).unwrap();
+                assert_eq!(normalize(&written), source, "{} {}", name, file);
+            }
+        }
+    }
+}
@@ -1,8 +1,103 @@
 use std::path::{Path, PathBuf};
 use std::process::ExitStatus;
+use std::time::Duration;
 
 use domain::ActuatorPlate;
 use tempfile::TempDir;
+use validation::ValidationLimits;
+
+#[cfg(any(test, feature = "mock-engine"))]
+pub mod mock;
+pub mod gltf;
+pub mod kcl;
+pub mod output;
+pub mod sweep;
+pub mod templates;
+
+pub use templates::{Template, params_kcl};
+
+/// Something that can turn plate parameters into 3D geometry.
+///
+/// The pipeline in [`generate_model_with_limits`] validates the plate, hands the
+/// backend a scratch directory, renders the drawings, and checks the output, so a
+/// backend only has to write `output.step`, `source.gltf`, and `source.stl` into
+/// that directory. [`ZooCli`] is the production backend; `mock::MockBackend` stands
+/// in for it in tests.
+pub trait GeometryBackend: Send + Sync {
+    /// Short name for logs, e.g. `zoo`.
+    fn name(&self) -> &str;
+
+    /// Write the model files for `plate` into `dir`. The error is a message for
+    /// the user, such as the engine's stderr.
+    fn generate(&self, plate: &ActuatorPlate, dir: &Path) -> Result<(), String>;
+
+    /// Like [`generate`](Self::generate), calling `progress` as each stage starts.
+    /// The default reports nothing, for backends whose work is a single step.
+    fn generate_with_progress(
+        &self,
+        plate: &ActuatorPlate,
+        dir: &Path,
+        progress: &dyn Fn(Stage),
+    ) -> Result<(), String> {
+        let _ = progress;
+        self.generate(plate, dir)
+    }
+
+    /// Check that the backend can generate right now, e.g. that the engine's
+    /// credentials are valid. Blocks. Backends with nothing to check are ready.
+    fn check(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// A step of model generation, reported as it starts so callers can show progress.
+///
+/// `zoo kcl export` sends the program to the engine and streams the STEP file back in
+/// one call, so [`ZooCli`] reports [`Stage::UploadingToZoo`] for it, and
+/// [`Stage::ExportingStep`] only when it falls back to writing the STEP file locally.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// Checking the plate against the limits
+    Validating,
+    /// Writing the KCL program for the plate
+    GeneratingKcl,
+    /// Running the KCL program on the Zoo engine
+    UploadingToZoo,
+    ExportingStep,
+    ExportingGltf,
+    ExportingStl,
+    /// Storing the finished files. Never reported by the pipeline itself; callers
+    /// that cache the result report it.
+    Caching,
+}
+
+impl Stage {
+    /// Stable snake_case identifier, e.g. `exporting_gltf`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Stage::Validating => "validating",
+            Stage::GeneratingKcl => "generating_kcl",
+            Stage::UploadingToZoo => "uploading_to_zoo",
+            Stage::ExportingStep => "exporting_step",
+            Stage::ExportingGltf => "exporting_gltf",
+            Stage::ExportingStl => "exporting_stl",
+            Stage::Caching => "caching",
+        }
+    }
+
+    /// What the stage is doing, for display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Stage::Validating => "Validating plate parameters",
+            Stage::GeneratingKcl => "Generating KCL",
+            Stage::UploadingToZoo => "Running the model on Zoo",
+            Stage::ExportingStep => "Exporting STEP",
+            Stage::ExportingGltf => "Exporting glTF",
+            Stage::ExportingStl => "Exporting STL",
+            Stage::Caching => "Caching model files",
+        }
+    }
+}
 
 pub trait Validation {
     // TODO: figure out how to mesh `plate` arg here with generic trait
@@ -13,6 +108,23 @@ pub trait Validation {
 #[derive(Debug, PartialEq)]
 pub enum ValidationError {
     NoStep,
+    /// The zoo CLI ran but exited unsuccessfully; holds its stderr.
+    EngineFailed(String),
+}
+
+impl ValidationError {
+    /// Whether the engine failed in a way that a second try might not, such as
+    /// its websocket dropping mid-export. KCL and conversion errors are permanent.
+    pub fn is_transient(&self) -> bool {
+        const TRANSIENT: [&str; 5] = ["closed early", "websocket", "timed out", "connection", "unavailable"];
+        match self {
+            ValidationError::EngineFailed(msg) => {
+                let msg = msg.to_lowercase();
+                TRANSIENT.iter().any(|marker| msg.contains(marker))
+            }
+            ValidationError::NoStep => false,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -27,6 +139,7 @@ pub enum AllErrors {
 }
 
 /// Result of a successful model generation, containing paths to generated files.
+/// Every file is written and non-empty; a partial export is an error instead.
 /// The TempDir is held to prevent cleanup until the caller is done with the files.
 #[derive(Debug)]
 pub struct GenerationResult {
@@ -39,6 +152,258 @@ pub struct GenerationResult {
     pub gltf_file: PathBuf,
     /// Path to the generated STL file
     pub stl_file: PathBuf,
+    /// Path to the STL mesh as Wavefront OBJ
+    pub obj_file: PathBuf,
+    /// Path to the STL mesh as a 3MF package, for slicers
+    pub three_mf_file: PathBuf,
+    /// Path to the dimensioned 2D drawing as SVG
+    pub svg_file: PathBuf,
+    /// Path to the same drawing as PDF
+    pub pdf_file: PathBuf,
+    /// Path to the flat cut profile as DXF, for waterjet and laser cutting
+    pub dxf_file: PathBuf,
+    /// Path to the plate's KCL project in one file, as the engine ran it (see
+    /// [`kcl::read_project`])
+    pub kcl_file: PathBuf,
+}
+
+impl GenerationResult {
+    /// Every file with its extension: the models, then the drawings and cut profile,
+    /// then the KCL source.
+    pub fn files(&self) -> [(&Path, &'static str); 9] {
+        [
+            (&self.step_file, "step"),
+            (&self.gltf_file, "gltf"),
+            (&self.stl_file, "stl"),
+            (&self.obj_file, "obj"),
+            (&self.three_mf_file, "3mf"),
+            (&self.svg_file, "svg"),
+            (&self.pdf_file, "pdf"),
+            (&self.dxf_file, "dxf"),
+            (&self.kcl_file, "kcl"),
+        ]
+    }
+}
+
+/// How often to retry an engine call that failed transiently.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles after each failure, with up to half
+    /// of it randomized so concurrent requests don't retry in lockstep.
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Read ZOO_RETRY_ATTEMPTS and ZOO_RETRY_BASE_DELAY_MS, keeping the default for
+    /// unset or invalid values.
+    pub fn from_env() -> Self {
+        let mut policy = Self::default();
+        if let Some(attempts) = std::env::var("ZOO_RETRY_ATTEMPTS")
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .filter(|n| *n > 0)
+        {
+            policy.max_attempts = attempts;
+        }
+        if let Some(ms) = std::env::var("ZOO_RETRY_BASE_DELAY_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+        {
+            policy.base_delay = Duration::from_millis(ms);
+        }
+        policy
+    }
+
+    /// Randomize `delay` to somewhere in `[delay / 2, delay]`.
+    fn jittered(delay: Duration) -> Duration {
+        use std::hash::{BuildHasher, Hasher};
+
+        // RandomState is seeded per process and bumped per instance, which is
+        // random enough to spread retries without pulling in a rand crate.
+        let random = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        let half = delay / 2;
+        half + half.mul_f64((random % 1024) as f64 / 1024.0)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+/// How to run the Zoo engine: the CLI binary and the KCL sources it exports.
+#[derive(Clone, Debug)]
+pub struct ZooCli {
+    /// Path or name of the `zoo` binary
+    pub program: PathBuf,
+    /// Directory containing the KCL templates; see [`templates`]
+    pub kcl_source_dir: PathBuf,
+    /// Retries for transient engine failures
+    pub retry: RetryPolicy,
+    /// Write the STEP file with `step_writer` when the KCL export still fails
+    /// transiently after retrying, instead of failing the request. Slotted plates
+    /// always fail, since `step_writer` can't draw slots.
+    pub step_fallback: bool,
+    /// Zoo API token, passed to the CLI as ZOO_TOKEN. When unset the CLI uses an
+    /// inherited ZOO_TOKEN or its own login.
+    pub api_token: Option<String>,
+    /// How the glTF the viewer loads is compressed; see [`gltf`]
+    pub gltf_compression: gltf::GltfCompression,
+}
+
+impl ZooCli {
+    /// Use ZOO_CLI (default `zoo` on the PATH) and the KCL sources from KCL_SRC_DIR
+    /// or the local checkout. The local STEP fallback is on unless
+    /// ZOO_STEP_FALLBACK is `0` or `false`, and ZOO_GLTF_COMPRESSION=quantize
+    /// compresses the glTF.
+    pub fn from_env() -> Self {
+        Self {
+            program: std::env::var("ZOO_CLI")
+                .unwrap_or_else(|_| "zoo".to_string())
+                .into(),
+            kcl_source_dir: get_kcl_source_dir().into(),
+            retry: RetryPolicy::from_env(),
+            step_fallback: !matches!(
+                std::env::var("ZOO_STEP_FALLBACK").as_deref(),
+                Ok("0") | Ok("false")
+            ),
+            api_token: None,
+            gltf_compression: match std::env::var("ZOO_GLTF_COMPRESSION") {
+                Ok(value) => value.parse().unwrap_or_else(|()| {
+                    eprintln!("ZOO_GLTF_COMPRESSION must be none or quantize, got {:?}; not compressing", value);
+                    gltf::GltfCompression::None
+                }),
+                Err(_) => gltf::GltfCompression::None,
+            },
+        }
+    }
+
+    /// Run the CLI, retrying transient engine failures with backoff.
+    fn run(&self, args: &[&str]) -> Result<ExitStatus, ValidationError> {
+        let mut delay = self.retry.base_delay;
+        let mut attempt = 1;
+        loop {
+            match self.run_once(args) {
+                Err(e) if e.is_transient() && attempt < self.retry.max_attempts => {
+                    let wait = RetryPolicy::jittered(delay);
+                    eprintln!(
+                        "zoo {} failed transiently (attempt {}); retrying in {:?}",
+                        args[..2].join(" "),
+                        attempt,
+                        wait
+                    );
+                    std::thread::sleep(wait);
+                    delay *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Run the CLI once, treating a non-zero exit as an engine failure.
+    fn run_once(&self, args: &[&str]) -> Result<ExitStatus, ValidationError> {
+        let mut command = std::process::Command::new(&self.program);
+        command.args(args);
+        if let Some(token) = &self.api_token {
+            command.env("ZOO_TOKEN", token);
+        }
+        match command.output() {
+            Ok(output) if output.status.success() => Ok(output.status),
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                eprintln!("zoo {} failed: {}", args[..2].join(" "), stderr);
+                Err(ValidationError::EngineFailed(if stderr.is_empty() {
+                    format!("zoo exited with {}", output.status)
+                } else {
+                    stderr
+                }))
+            }
+            Err(e) => {
+                eprintln!("ouch: {}", e);
+                Err(ValidationError::NoStep)
+            }
+        }
+    }
+}
+
+impl Default for ZooCli {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl GeometryBackend for ZooCli {
+    fn name(&self) -> &str {
+        "zoo"
+    }
+
+    /// `zoo auth status` fails when the CLI is missing or its token is invalid.
+    fn check(&self) -> Result<(), String> {
+        match self.run_once(&["auth", "status"]) {
+            Ok(_) => Ok(()),
+            Err(ValidationError::EngineFailed(stderr)) => Err(stderr),
+            Err(ValidationError::NoStep) => Err(format!("{} could not be run", self.program.display())),
+        }
+    }
+
+    /// Export STEP from the KCL sources, then convert it to glTF, which is
+    /// post-processed for the viewer (see [`gltf`]), and STL.
+    ///
+    /// The conversions are plain API calls, but the export holds a websocket to the
+    /// engine for the whole run and is what drops. When it keeps failing that way,
+    /// the STEP file is written locally (see [`ZooCli::step_fallback`]).
+    fn generate(&self, plate: &ActuatorPlate, dir: &Path) -> Result<(), String> {
+        self.generate_with_progress(plate, dir, &|_| {})
+    }
+
+    fn generate_with_progress(
+        &self,
+        plate: &ActuatorPlate,
+        dir: &Path,
+        progress: &dyn Fn(Stage),
+    ) -> Result<(), String> {
+        progress(Stage::GeneratingKcl);
+        copy_kcl_sources(plate, &self.kcl_source_dir, dir).map_err(|e| {
+            format!(
+                "Failed to copy KCL sources from {}: {}",
+                self.kcl_source_dir.display(),
+                e
+            )
+        })?;
+        write_params_file(plate, dir).map_err(|e| format!("Failed to write params file: {}", e))?;
+        // The engine reports most KCL mistakes as a dropped connection, so catch them here
+        kcl::check_project(plate, dir).map_err(|e| format!("Generated KCL is invalid: {}", e))?;
+
+        progress(Stage::UploadingToZoo);
+        if let Err(e) = generate_step_in_dir(dir, self) {
+            if !(self.step_fallback && e.is_transient() && step_writer::supports(plate)) {
+                return Err(format!("Failed to generate STEP file: {:?}", e));
+            }
+            eprintln!("zoo kcl export failed ({:?}); writing the STEP file locally", e);
+            progress(Stage::ExportingStep);
+            std::fs::write(dir.join("output.step"), step_writer::plate_step(plate))
+                .map_err(|e| format!("Failed to write STEP file: {}", e))?;
+        }
+        progress(Stage::ExportingGltf);
+        generate_gltf_in_dir(dir, self).map_err(|e| format!("Failed to generate glTF file: {:?}", e))?;
+        let gltf_file = dir.join("source.gltf");
+        std::fs::read(&gltf_file)
+            .map_err(|e| format!("Failed to read glTF file: {}", e))
+            .and_then(|gltf| gltf::post_process(&gltf, plate, self.gltf_compression))
+            .and_then(|gltf| std::fs::write(&gltf_file, gltf).map_err(|e| format!("Failed to write glTF file: {}", e)))?;
+        progress(Stage::ExportingStl);
+        generate_stl_in_dir(dir, self).map_err(|e| format!("Failed to generate STL file: {:?}", e))?;
+        Ok(())
+    }
 }
 
 /// Get the source directory containing KCL files.
@@ -59,52 +424,47 @@ fn get_kcl_source_dir() -> String {
 
 /// Write params.kcl to the specified directory
 fn write_params_file(plate: &ActuatorPlate, dir: &Path) -> std::io::Result<()> {
-    // Use clearance hole diameter for mounting bolts
-    let bolt_hole_diameter = plate.bolt_size.clearance_hole_diameter_mm();
-
-    let content = format!(
-        "@settings(defaultLengthUnit = mm, kclVersion = 1.0)\n\n\
-         export plateThickness = {}\n\
-         export boltDiameter = {}\n\
-         export boltSpacing = {}\n\
-         export bracketHeight = {}\n\
-         export bracketWidth = {}\n\
-         export materialColor = \"{}\"\n\
-         export pinDiameter = {}\n\
-         export pinCount = {}",
-        plate.plate_thickness.0,
-        bolt_hole_diameter,
-        plate.bolt_spacing.0,
-        plate.bracket_height.0,
-        plate.bracket_width.0,
-        plate.material.as_hex_code(),
-        plate.pin_diameter.0,
-        plate.pin_count
-    );
-
-    std::fs::write(dir.join("params.kcl"), content)?;
+    std::fs::write(dir.join("params.kcl"), params_kcl(plate))?;
     Ok(())
 }
 
-/// Copy KCL source files to the temp directory
-fn copy_kcl_sources(temp_dir: &Path) -> std::io::Result<()> {
-    let source_dir = get_kcl_source_dir();
+/// Copy the KCL template for the plate's family to the temp directory
+fn copy_kcl_sources(plate: &ActuatorPlate, source_dir: &Path, temp_dir: &Path) -> std::io::Result<()> {
+    Template::for_family(&plate.family).copy_sources(source_dir, temp_dir)
+}
+
+/// Generate a model with the `zoo` CLI configured by the environment.
+pub fn generate_model(plate: &ActuatorPlate) -> Result<GenerationResult, AllErrors> {
+    generate_model_with(plate, &ZooCli::from_env())
+}
 
-    // Copy main.kcl and plate.kcl to temp dir
-    std::fs::copy(
-        Path::new(&source_dir).join("main.kcl"),
-        temp_dir.join("main.kcl"),
-    )?;
-    std::fs::copy(
-        Path::new(&source_dir).join("plate.kcl"),
-        temp_dir.join("plate.kcl"),
-    )?;
+/// Generate a model with a specific backend, e.g. a mock in tests.
+pub fn generate_model_with(
+    plate: &ActuatorPlate,
+    backend: &dyn GeometryBackend,
+) -> Result<GenerationResult, AllErrors> {
+    generate_model_with_limits(plate, backend, &ValidationLimits::DEFAULT)
+}
 
-    Ok(())
+/// Generate a model, validating the plate against deployment-specific limits.
+pub fn generate_model_with_limits(
+    plate: &ActuatorPlate,
+    backend: &dyn GeometryBackend,
+    limits: &ValidationLimits,
+) -> Result<GenerationResult, AllErrors> {
+    generate_model_with_progress(plate, backend, limits, &|_| {})
 }
 
-pub fn generate_model(plate: &ActuatorPlate) -> Result<GenerationResult, AllErrors> {
-    if let Err(errors) = validation::validate(plate) {
+/// Generate a model like [`generate_model_with_limits`], calling `progress` as each
+/// [`Stage`] starts.
+pub fn generate_model_with_progress(
+    plate: &ActuatorPlate,
+    backend: &dyn GeometryBackend,
+    limits: &ValidationLimits,
+    progress: &dyn Fn(Stage),
+) -> Result<GenerationResult, AllErrors> {
+    progress(Stage::Validating);
+    if let Err(errors) = validation::validate_with_limits(plate, limits) {
         return Err(AllErrors::ValidationErrors(errors));
     }
 
@@ -120,90 +480,102 @@ pub fn generate_model(plate: &ActuatorPlate) -> Result<GenerationResult, AllErro
 
     let temp_path = temp_dir.path();
 
-    // Copy KCL source files to temp dir
-    if let Err(e) = copy_kcl_sources(temp_path) {
-        let msg = format!(
-            "Failed to copy KCL sources from {}: {}",
-            get_kcl_source_dir(),
-            e
-        );
-        eprintln!("{}", msg);
+    if let Err(msg) = backend.generate_with_progress(plate, temp_path, progress) {
+        eprintln!("{} backend failed: {}", backend.name(), msg);
         return Err(AllErrors::GeneratorError(msg));
     }
 
-    // Write params.kcl to temp dir
-    if let Err(e) = write_params_file(plate, temp_path) {
-        let msg = format!("Failed to write params file: {}", e);
+    // The drawing and cut profile come from the plate parameters, not the engine, and
+    // the source is what the backend gave it; backends that run no KCL get the
+    // compiled-in templates
+    let kcl = kcl::read_project(plate, temp_path).unwrap_or_else(|| kcl::emit(plate));
+    let svg_file = temp_path.join("drawing.svg");
+    let pdf_file = temp_path.join("drawing.pdf");
+    let dxf_file = temp_path.join("plate.dxf");
+    let kcl_file = temp_path.join("program.kcl");
+    if let Err(e) = std::fs::write(&svg_file, drawing::render_svg(plate))
+        .and_then(|()| std::fs::write(&pdf_file, drawing::render_pdf(plate)))
+        .and_then(|()| std::fs::write(&dxf_file, dxf_export::plate_dxf(plate)))
+        .and_then(|()| std::fs::write(&kcl_file, kcl))
+    {
+        let msg = format!("Failed to write drawing: {}", e);
         eprintln!("{}", msg);
         return Err(AllErrors::GeneratorError(msg));
     }
 
-    // Generate STEP file
-    if let Err(e) = generate_step_in_dir(plate, temp_path) {
-        let msg = format!("Failed to generate STEP file: {:?}", e);
-        eprintln!("{}", msg);
-        return Err(AllErrors::GeneratorError(msg));
+    let step_file = temp_path.join("output.step");
+    let gltf_file = temp_path.join("source.gltf");
+    let stl_file = temp_path.join("source.stl");
+
+    // A backend can succeed without writing a file; never hand back a partial set
+    for file in [&step_file, &gltf_file, &stl_file] {
+        if !std::fs::metadata(file).is_ok_and(|m| m.len() > 0) {
+            let msg = format!("Engine did not produce {}", file.display());
+            eprintln!("{}", msg);
+            return Err(AllErrors::GeneratorError(msg));
+        }
     }
 
-    // Generate glTF file
-    if let Err(e) = generate_gltf_in_dir(plate, temp_path) {
-        let msg = format!("Failed to generate glTF file: {:?}", e);
+    // Printing formats index the engine's tessellation rather than re-exporting it
+    let obj_file = temp_path.join("model.obj");
+    let three_mf_file = temp_path.join("model.3mf");
+    let mesh = std::fs::read(&stl_file)
+        .map_err(|e| e.to_string())
+        .and_then(|stl| mesh_export::Mesh::from_stl(&stl).map_err(|e| e.to_string()));
+    if let Err(e) = mesh.and_then(|mesh| {
+        std::fs::write(&obj_file, mesh.to_obj())
+            .and_then(|()| std::fs::write(&three_mf_file, mesh.to_3mf()))
+            .map_err(|e| e.to_string())
+    }) {
+        let msg = format!("Failed to convert the STL mesh: {}", e);
         eprintln!("{}", msg);
         return Err(AllErrors::GeneratorError(msg));
     }
 
-    // Generate STL file
-    if let Err(e) = generate_stl_in_dir(plate, temp_path) {
-        let msg = format!("Failed to generate STL file: {:?}", e);
+    // The engine knows nothing about tolerances or where the file came from, so
+    // they go into the header here
+    let meta = step_writer::step_meta::StepMetadata::new(
+        plate,
+        &plate.cache_key(),
+        &chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+    );
+    if let Err(e) = std::fs::read_to_string(&step_file)
+        .and_then(|step| std::fs::write(&step_file, step_writer::step_meta::embed(&step, plate, &meta)))
+    {
+        let msg = format!("Failed to write metadata into the STEP file: {}", e);
         eprintln!("{}", msg);
         return Err(AllErrors::GeneratorError(msg));
     }
 
-    let step_file = temp_path.join("output.step");
-    let gltf_file = temp_path.join("source.gltf");
-    let stl_file = temp_path.join("source.stl");
-
     Ok(GenerationResult {
         temp_dir,
         step_file,
         gltf_file,
         stl_file,
+        obj_file,
+        three_mf_file,
+        svg_file,
+        pdf_file,
+        dxf_file,
+        kcl_file,
     })
 }
 
 /// Generate STEP file in the specified directory
-fn generate_step_in_dir(plate: &ActuatorPlate, dir: &Path) -> Result<ExitStatus, ValidationError> {
-    if validation::validate(plate).is_err() {
-        return Err(ValidationError::NoStep);
-    }
-
+fn generate_step_in_dir(dir: &Path, cli: &ZooCli) -> Result<ExitStatus, ValidationError> {
     let main_kcl = dir.join("main.kcl");
 
-    let status = std::process::Command::new("zoo")
-        .args([
-            "kcl",
-            "export",
-            "--output-format=step",
-            main_kcl.to_str().unwrap(),
-            dir.to_str().unwrap(),
-        ])
-        .status();
-
-    match status {
-        Ok(stat) => Ok(stat),
-        Err(e) => {
-            eprintln!("ouch: {}", e);
-            Err(ValidationError::NoStep)
-        }
-    }
+    cli.run(&[
+        "kcl",
+        "export",
+        "--output-format=step",
+        main_kcl.to_str().unwrap(),
+        dir.to_str().unwrap(),
+    ])
 }
 
 /// Generate STL file in the specified directory by converting the STEP file
-fn generate_stl_in_dir(plate: &ActuatorPlate, dir: &Path) -> Result<ExitStatus, ValidationError> {
-    if validation::validate(plate).is_err() {
-        return Err(ValidationError::NoStep);
-    }
-
+fn generate_stl_in_dir(dir: &Path, cli: &ZooCli) -> Result<ExitStatus, ValidationError> {
     let step_file = dir.join("output.step");
 
     if !step_file.exists() {
@@ -211,32 +583,18 @@ fn generate_stl_in_dir(plate: &ActuatorPlate, dir: &Path) -> Result<ExitStatus,
         return Err(ValidationError::NoStep);
     }
 
-    let status = std::process::Command::new("zoo")
-        .args([
-            "file",
-            "convert",
-            "--src-format=step",
-            "--output-format=stl",
-            step_file.to_str().unwrap(),
-            dir.to_str().unwrap(),
-        ])
-        .status();
-
-    match status {
-        Ok(stat) => Ok(stat),
-        Err(e) => {
-            eprintln!("ouch: {}", e);
-            Err(ValidationError::NoStep)
-        }
-    }
+    cli.run(&[
+        "file",
+        "convert",
+        "--src-format=step",
+        "--output-format=stl",
+        step_file.to_str().unwrap(),
+        dir.to_str().unwrap(),
+    ])
 }
 
 /// Generate glTF file in the specified directory by converting the STEP file
-fn generate_gltf_in_dir(plate: &ActuatorPlate, dir: &Path) -> Result<ExitStatus, ValidationError> {
-    if validation::validate(plate).is_err() {
-        return Err(ValidationError::NoStep);
-    }
-
+fn generate_gltf_in_dir(dir: &Path, cli: &ZooCli) -> Result<ExitStatus, ValidationError> {
     let step_file = dir.join("output.step");
 
     // Check if STEP file exists
@@ -246,41 +604,31 @@ fn generate_gltf_in_dir(plate: &ActuatorPlate, dir: &Path) -> Result<ExitStatus,
     }
 
     // Convert STEP file to glTF using zoo file convert
-    let status = std::process::Command::new("zoo")
-        .args([
-            "file",
-            "convert",
-            "--src-format=step",
-            "--output-format=gltf",
-            step_file.to_str().unwrap(),
-            dir.to_str().unwrap(),
-        ])
-        .status();
-
-    match status {
-        Ok(stat) => Ok(stat),
-        Err(e) => {
-            eprintln!("ouch: {}", e);
-            Err(ValidationError::NoStep)
-        }
-    }
+    cli.run(&[
+        "file",
+        "convert",
+        "--src-format=step",
+        "--output-format=gltf",
+        step_file.to_str().unwrap(),
+        dir.to_str().unwrap(),
+    ])
 }
 
 #[cfg(test)]
 mod tests {
-    use domain::Millimeters;
+    use domain::{Count, HoleSpec, Length};
 
     use super::*;
 
     #[test]
-    fn test_generate_step_fails_with_invalid_plate() {
-        let plate = ActuatorPlate { bolt_spacing: Millimeters(0), ..Default::default() }; // Invalid bolt spacing
+    fn test_invalid_plate_never_reaches_backend() {
+        let plate = ActuatorPlate { bolt_spacing: Length::from_mm(0), ..Default::default() }; // Invalid bolt spacing
+        let backend = mock::MockBackend::new();
 
-        let temp_dir = TempDir::new().unwrap();
-        let result = generate_step_in_dir(&plate, temp_dir.path());
+        let result = generate_model_with(&plate, &backend);
 
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), ValidationError::NoStep);
+        assert!(matches!(result, Err(AllErrors::ValidationErrors(_))));
+        assert_eq!(backend.generated(), 0);
     }
 
     #[test]
@@ -304,7 +652,7 @@ mod tests {
 
     #[test]
     fn test_generate_model_fails_with_invalid_plate() {
-        let plate = ActuatorPlate { bolt_spacing: Millimeters(0), ..Default::default() };
+        let plate = ActuatorPlate { bolt_spacing: Length::from_mm(0), ..Default::default() };
 
         let result = generate_model(&plate);
 
@@ -348,11 +696,34 @@ mod tests {
     }
 
     #[test]
-    fn test_generate_gltf_fails_with_invalid_plate() {
-        let plate = ActuatorPlate { plate_thickness: Millimeters(0), ..Default::default() }; // Invalid plate thickness
+    fn test_params_take_recess_sizes_from_the_bolt_catalog() {
+        let mut json = serde_json::to_value(ActuatorPlate { plate_thickness: Length::from_mm(16), ..Default::default() }).unwrap();
+        json["bolt_holes"] = serde_json::json!({ "type": "counterbore" });
+        let plate: ActuatorPlate = serde_json::from_value(json).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        write_params_file(&plate, temp_dir.path()).unwrap();
+
+        let content = std::fs::read_to_string(temp_dir.path().join("params.kcl")).unwrap();
+        assert!(content.contains("export boltRecess = \"counterbore\""), "{}", content);
+        assert!(content.contains("export boltRecessDiameter = 18"), "{}", content);
+        assert!(content.contains("export boltRecessDepth = 10.6"), "{}", content);
+    }
+
+    #[test]
+    fn test_params_bore_the_pins_for_their_fit() {
+        let plate = ActuatorPlate { pin_fit: Some(domain::PinFit::Press), ..Default::default() };
+        let temp_dir = TempDir::new().unwrap();
+        write_params_file(&plate, temp_dir.path()).unwrap();
+
+        // P7 on a Ø10 pin is 9.976 to 9.991; the bore is cut in the middle
+        let content = std::fs::read_to_string(temp_dir.path().join("params.kcl")).unwrap();
+        assert!(content.contains("export pinDiameter = 9.983"), "{}", content);
+    }
 
+    #[test]
+    fn test_generate_gltf_fails_without_step_file() {
         let temp_dir = TempDir::new().unwrap();
-        let result = generate_gltf_in_dir(&plate, temp_dir.path());
+        let result = generate_gltf_in_dir(temp_dir.path(), &ZooCli::from_env());
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ValidationError::NoStep);
@@ -366,13 +737,13 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
 
         // Copy KCL sources and write params
-        copy_kcl_sources(temp_dir.path()).unwrap();
+        copy_kcl_sources(&plate, &ZooCli::from_env().kcl_source_dir, temp_dir.path()).unwrap();
         write_params_file(&plate, temp_dir.path()).unwrap();
 
         // This will only pass if, as pre-requisites:
         // 1. zoo CLI is installed
         // 2. user is authenticated against zoo
-        let result = generate_step_in_dir(&plate, temp_dir.path());
+        let result = generate_step_in_dir(temp_dir.path(), &ZooCli::from_env());
 
         match result {
             Ok(status) => {
@@ -397,18 +768,18 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
 
         // Copy KCL sources and write params
-        copy_kcl_sources(temp_dir.path()).unwrap();
+        copy_kcl_sources(&plate, &ZooCli::from_env().kcl_source_dir, temp_dir.path()).unwrap();
         write_params_file(&plate, temp_dir.path()).unwrap();
 
         // Generate STEP file first (glTF generation now converts from STEP)
-        let step_result = generate_step_in_dir(&plate, temp_dir.path());
+        let step_result = generate_step_in_dir(temp_dir.path(), &ZooCli::from_env());
         assert!(step_result.is_ok(), "STEP generation should succeed");
         assert!(
             step_result.unwrap().success(),
             "STEP generation should succeed"
         );
 
-        let result = generate_gltf_in_dir(&plate, temp_dir.path());
+        let result = generate_gltf_in_dir(temp_dir.path(), &ZooCli::from_env());
 
         match result {
             Ok(status) => {
@@ -424,4 +795,344 @@ mod tests {
 
         // Temp directory is automatically cleaned up
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_generate_model_with_mock_engine() {
+        let engine = mock::MockEngine::new(mock::MockBehavior::Succeed).unwrap();
+
+        let result = generate_model_with(&ActuatorPlate::default(), &engine.cli()).unwrap();
+
+        let step = std::fs::read_to_string(&result.step_file).unwrap();
+        assert!(step.ends_with("ENDSEC;\nDATA;\nENDSEC;\nEND-ISO-10303-21;\n"), "{}", step);
+        assert!(result.gltf_file.exists());
+        assert!(result.stl_file.exists());
+        assert!(std::fs::read_to_string(&result.svg_file).unwrap().starts_with("<svg"));
+        assert!(std::fs::read(&result.pdf_file).unwrap().starts_with(b"%PDF"));
+        assert!(std::fs::read_to_string(&result.dxf_file).unwrap().ends_with("EOF\n"));
+        assert_eq!(engine.invocations().len(), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_zoo_backend_reports_each_stage() {
+        let engine = mock::MockEngine::new(mock::MockBehavior::Succeed).unwrap();
+        let stages = std::sync::Mutex::new(Vec::new());
+
+        generate_model_with_progress(&ActuatorPlate::default(), &engine.cli(), &ValidationLimits::DEFAULT, &|stage| {
+            stages.lock().unwrap().push(stage)
+        })
+        .unwrap();
+
+        assert_eq!(
+            stages.into_inner().unwrap(),
+            [
+                Stage::Validating,
+                Stage::GeneratingKcl,
+                Stage::UploadingToZoo,
+                Stage::ExportingGltf,
+                Stage::ExportingStl
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tolerances_are_written_into_the_step_header() {
+        let mut plate = ActuatorPlate::default();
+        plate.tolerances.pin_diameter = Some("H7".parse().unwrap());
+        let result = generate_model_with(&plate, &mock::MockBackend::new()).unwrap();
+
+        let step = std::fs::read_to_string(&result.step_file).unwrap();
+        assert!(step.contains("'TOLERANCE pin_diameter 10 H7'"), "{}", step);
+        assert!(step.contains("FILE_NAME('mock.step','"), "{}", step);
+    }
+
+    #[test]
+    fn test_engine_closed_early_is_a_generator_error() {
+        let engine = mock::MockEngine::new(mock::MockBehavior::CloseEarly).unwrap();
+
+        match generate_model_with(&ActuatorPlate::default(), &engine.cli()) {
+            Err(AllErrors::GeneratorError(msg)) => assert!(msg.contains("closed early"), "{}", msg),
+            other => panic!("Expected GeneratorError, got {:?}", other.map(|_| ())),
+        }
+        // Every attempt was an export; the pipeline stopped before any conversion
+        assert_eq!(engine.exports(), RetryPolicy::default().max_attempts as usize);
+        assert_eq!(engine.invocations().len(), engine.exports());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_transient_engine_failures_are_retried() {
+        let engine = mock::MockEngine::new(mock::MockBehavior::CloseEarlyTimes(2)).unwrap();
+
+        let result = generate_model_with(&ActuatorPlate::default(), &engine.cli()).unwrap();
+
+        assert!(result.gltf_file.exists());
+        assert_eq!(engine.exports(), 3);
+    }
+
+    #[test]
+    fn test_generate_model_with_in_process_backend() {
+        let backend = mock::MockBackend::new();
+
+        let result = generate_model_with(&ActuatorPlate::default(), &backend).unwrap();
+
+        let meta = step_writer::step_meta::read(&std::fs::read_to_string(&result.step_file).unwrap()).unwrap();
+        assert_eq!(meta.cache_key, ActuatorPlate::default().cache_key());
+        assert_eq!(meta.plate(), Some(ActuatorPlate::default()));
+        assert_eq!(std::fs::read_to_string(&result.stl_file).unwrap(), mock::MOCK_STL);
+        assert!(std::fs::read_to_string(&result.obj_file).unwrap().ends_with("f 1 2 3\n"));
+        assert!(std::fs::read(&result.three_mf_file).unwrap().starts_with(b"PK\x03\x04"));
+        assert!(std::fs::read_to_string(&result.svg_file).unwrap().starts_with("<svg"));
+        assert_eq!(std::fs::read_to_string(&result.kcl_file).unwrap(), kcl::emit(&ActuatorPlate::default()));
+        assert_eq!(backend.generated(), 1);
+    }
+
+    #[test]
+    fn test_kcl_file_is_the_project_the_backend_wrote() {
+        struct EditedTemplates;
+        impl GeometryBackend for EditedTemplates {
+            fn name(&self) -> &str {
+                "edited-templates"
+            }
+            fn generate(&self, plate: &ActuatorPlate, dir: &Path) -> Result<(), String> {
+                for (file, source) in kcl::files(plate) {
+                    std::fs::write(dir.join(file), format!("{}// edited on this host\n", source)).map_err(|e| e.to_string())?;
+                }
+                mock::MockBackend::new().generate(plate, dir)
+            }
+        }
+
+        let result = generate_model_with(&ActuatorPlate::default(), &EditedTemplates).unwrap();
+
+        let kcl = std::fs::read_to_string(&result.kcl_file).unwrap();
+        assert_ne!(kcl, kcl::emit(&ActuatorPlate::default()));
+        assert_eq!(kcl.matches("// edited on this host").count(), 3);
+    }
+
+    #[test]
+    fn test_backend_that_skips_a_file_is_an_error() {
+        struct StepOnly;
+        impl GeometryBackend for StepOnly {
+            fn name(&self) -> &str {
+                "step-only"
+            }
+            fn generate(&self, _plate: &ActuatorPlate, dir: &Path) -> Result<(), String> {
+                std::fs::write(dir.join("output.step"), mock::MOCK_STEP).map_err(|e| e.to_string())
+            }
+        }
+
+        match generate_model_with(&ActuatorPlate::default(), &StepOnly) {
+            Err(AllErrors::GeneratorError(msg)) => assert!(msg.contains("source.gltf"), "{}", msg),
+            other => panic!("Expected GeneratorError, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_unreadable_stl_is_an_error() {
+        struct BadStl;
+        impl GeometryBackend for BadStl {
+            fn name(&self) -> &str {
+                "bad-stl"
+            }
+            fn generate(&self, _plate: &ActuatorPlate, dir: &Path) -> Result<(), String> {
+                for (file, contents) in [
+                    ("output.step", mock::MOCK_STEP),
+                    ("source.gltf", mock::MOCK_GLTF),
+                    ("source.stl", "solid truncated\nvertex 0 0 0\n"),
+                ] {
+                    std::fs::write(dir.join(file), contents).map_err(|e| e.to_string())?;
+                }
+                Ok(())
+            }
+        }
+
+        match generate_model_with(&ActuatorPlate::default(), &BadStl) {
+            Err(AllErrors::GeneratorError(msg)) => assert!(msg.contains("STL"), "{}", msg),
+            other => panic!("Expected GeneratorError, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_engine_closed_early_falls_back_to_local_step() {
+        let engine = mock::MockEngine::new(mock::MockBehavior::CloseEarly).unwrap();
+        let cli = ZooCli {
+            step_fallback: true,
+            ..engine.cli()
+        };
+
+        let result = generate_model_with(&ActuatorPlate::default(), &cli).unwrap();
+
+        let step = std::fs::read_to_string(&result.step_file).unwrap();
+        assert!(step.contains(step_writer::ORIGINATING_SYSTEM));
+        // The local STEP file is still converted by the engine
+        assert_eq!(std::fs::read_to_string(&result.gltf_file).unwrap(), mock::MOCK_GLTF);
+        assert_eq!(engine.exports(), RetryPolicy::default().max_attempts as usize);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_invalid_kcl_never_reaches_the_engine() {
+        let engine = mock::MockEngine::new(mock::MockBehavior::Succeed).unwrap();
+        let source_dir = TempDir::new().unwrap();
+        copy_kcl_sources(&ActuatorPlate::default(), &engine.cli().kcl_source_dir, source_dir.path()).unwrap();
+        let main = std::fs::read_to_string(source_dir.path().join("main.kcl")).unwrap();
+        std::fs::write(source_dir.path().join("main.kcl"), main.replace("pinCount,\n)", "pinCount,\n")).unwrap();
+        let cli = ZooCli {
+            kcl_source_dir: source_dir.path().to_path_buf(),
+            ..engine.cli()
+        };
+
+        match generate_model_with(&ActuatorPlate::default(), &cli) {
+            Err(AllErrors::GeneratorError(msg)) => {
+                assert!(msg.contains("main.kcl:") && msg.contains("never closed"), "{}", msg)
+            }
+            other => panic!("Expected GeneratorError, got {:?}", other.map(|_| ())),
+        }
+        assert!(engine.invocations().is_empty());
+    }
+
+    #[test]
+    fn test_only_connection_failures_are_transient() {
+        let closed_early = "Error: engine: websocket closed early".to_string();
+        assert!(ValidationError::EngineFailed(closed_early).is_transient());
+        assert!(!ValidationError::EngineFailed("KCL syntax error at 3:1".to_string()).is_transient());
+        assert!(!ValidationError::NoStep.is_transient());
+    }
+
+    #[test]
+    fn test_retry_delay_is_jittered_within_bounds() {
+        let delay = Duration::from_millis(400);
+        for _ in 0..50 {
+            let wait = RetryPolicy::jittered(delay);
+            assert!(wait >= delay / 2 && wait <= delay, "{:?}", wait);
+        }
+    }
+
+    /// Representative configurations whose emitted KCL is pinned by snapshots.
+    fn presets() -> Vec<(&'static str, ActuatorPlate)> {
+        use domain::{BoltSize, Material, Newtons, PlateFamily};
+        vec![
+            ("default", ActuatorPlate::default()),
+            (
+                "compact_m4_aluminum",
+                ActuatorPlate {
+                    bolt_spacing: Length::from_mm(30),
+                    bolt_size: BoltSize::M4,
+                    bracket_height: Length::from_mm(80),
+                    bracket_width: Length::from_mm(60),
+                    pin_diameter: Length::from_mm(6),
+                    pin_count: Count::of(2),
+                    plate_thickness: Length::from_mm(5),
+                    expected_force_per_pin: Newtons(100),
+                    ..Default::default()
+                },
+            ),
+            (
+                "heavy_m12_stainless",
+                ActuatorPlate {
+                    bolt_spacing: Length::from_mm(100),
+                    bolt_size: BoltSize::M12,
+                    material: Material::StainlessSteel,
+                    pin_diameter: Length::from_mm(20),
+                    pin_count: Count::of(4),
+                    plate_thickness: Length::from_mm(20),
+                    expected_force_per_pin: Newtons(5000),
+                    ..Default::default()
+                },
+            ),
+            (
+                "brass_many_pins",
+                ActuatorPlate {
+                    material: Material::Brass,
+                    pin_count: Count::of(12),
+                    ..Default::default()
+                },
+            ),
+            (
+                "counterbored_m10",
+                ActuatorPlate {
+                    bolt_holes: HoleSpec::Counterbore {
+                        diameter: Length::from_micrometers(17_500),
+                        depth: Length::from_mm(6),
+                    },
+                    ..Default::default()
+                },
+            ),
+            (
+                "countersunk_m10",
+                ActuatorPlate {
+                    bolt_holes: HoleSpec::Countersink { diameter: Length::from_mm(23) },
+                    ..Default::default()
+                },
+            ),
+            (
+                "tapped_m10_fine",
+                ActuatorPlate {
+                    bolt_holes: HoleSpec::Tapped { thread: "M10x1.25".parse().unwrap() },
+                    ..Default::default()
+                },
+            ),
+            (
+                "slotted_m10",
+                ActuatorPlate {
+                    family: PlateFamily::Slotted { slot_length: Length::from_mm(12) },
+                    ..Default::default()
+                },
+            ),
+            (
+                "dual_pin_clevis",
+                ActuatorPlate {
+                    pin_count: Count::of(2),
+                    pin_diameter: Length::from_mm(16),
+                    bolt_holes: HoleSpec::Counterbore {
+                        diameter: Length::from_micrometers(17_500),
+                        depth: Length::from_mm(6),
+                    },
+                    family: PlateFamily::DualPin { pin_spacing: Length::from_mm(120) },
+                    ..Default::default()
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_params_kcl_snapshots() {
+        for (name, plate) in presets() {
+            assert!(validation::validate(&plate).is_ok(), "preset {} should be valid", name);
+            insta::assert_snapshot!(format!("params_kcl_{}", name), params_kcl(&plate));
+        }
+    }
+
+    #[test]
+    fn test_kcl_template_snapshot() {
+        // The templates are static, but a refactor that changes them changes every part
+        let source_dir = ZooCli::from_env().kcl_source_dir;
+        for file in templates::ALL.iter().flat_map(|template| [template.main, template.part]) {
+            let template = std::fs::read_to_string(source_dir.join(file)).unwrap();
+            insta::assert_snapshot!(format!("template_{}", file.replace('.', "_")), template);
+        }
+    }
+
+    #[test]
+    fn test_each_template_finds_its_params_and_part() {
+        let source_dir = ZooCli::from_env().kcl_source_dir;
+        for (name, plate) in presets() {
+            let template = Template::for_family(&plate.family);
+            let project = TempDir::new().unwrap();
+            copy_kcl_sources(&plate, &source_dir, project.path()).unwrap();
+            write_params_file(&plate, project.path()).unwrap();
+
+            let main = std::fs::read_to_string(project.path().join("main.kcl")).unwrap();
+            assert!(main.contains(&format!("import plate from \"{}\"", template.part)), "{}", name);
+            assert!(project.path().join(template.part).exists(), "{}", name);
+            let params = std::fs::read_to_string(project.path().join("params.kcl")).unwrap();
+            let imports = main.lines().find_map(|line| line.strip_prefix("import ")?.strip_suffix(" from \"params.kcl\""));
+            for param in imports.unwrap().split(", ") {
+                assert!(params.contains(&format!("export {} = ", param)), "{} has no {}", name, param);
+            }
+        }
+    }
 }
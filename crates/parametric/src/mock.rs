@@ -0,0 +1,186 @@
+//! Fake geometry backends for tests that exercise generation without credentials or
+//! network.
+//!
+//! [`MockBackend`] writes canned files in-process and runs on any platform.
+//! [`MockEngine`] (Unix only) exercises the real [`ZooCli`] backend instead: it writes a small shell script that answers `zoo kcl export` and
+//! `zoo file convert` the way the real CLI does: it writes canned STEP, glTF, and STL
+//! files into the output directory. It can also fail the way the real engine does
+//! when its websocket drops mid-export, either always or for the first N exports.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(unix)]
+use std::{io, os::unix::fs::PermissionsExt, path::PathBuf, time::Duration};
+
+use domain::ActuatorPlate;
+#[cfg(unix)]
+use tempfile::TempDir;
+
+use crate::{GeometryBackend, Stage};
+#[cfg(unix)]
+use crate::{RetryPolicy, ZooCli};
+
+/// STEP file written by the mock engine.
+pub const MOCK_STEP: &str = "ISO-10303-21;\nHEADER;\nFILE_NAME('mock.step');\nENDSEC;\nDATA;\nENDSEC;\nEND-ISO-10303-21;\n";
+/// glTF file written by the mock engine.
+pub const MOCK_GLTF: &str = "{\"asset\":{\"version\":\"2.0\",\"generator\":\"mock-zoo\"}}\n";
+/// STL file written by the mock engine: one triangle, so the OBJ and 3MF converted
+/// from it aren't empty.
+pub const MOCK_STL: &str = "solid mock\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nvertex 0 1 0\nendloop\nendfacet\nendsolid mock\n";
+/// Error the mock prints when it simulates a dropped engine connection.
+pub const CLOSED_EARLY: &str = "Error: engine: websocket closed early";
+
+/// A backend that writes [`MOCK_STEP`], [`MOCK_GLTF`], and [`MOCK_STL`] directly,
+/// without running anything.
+#[derive(Debug, Default)]
+pub struct MockBackend {
+    generated: AtomicUsize,
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many models this backend has generated.
+    pub fn generated(&self) -> usize {
+        self.generated.load(Ordering::SeqCst)
+    }
+}
+
+impl GeometryBackend for MockBackend {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn generate(&self, plate: &ActuatorPlate, dir: &Path) -> Result<(), String> {
+        self.generate_with_progress(plate, dir, &|_| {})
+    }
+
+    fn generate_with_progress(
+        &self,
+        _plate: &ActuatorPlate,
+        dir: &Path,
+        progress: &dyn Fn(Stage),
+    ) -> Result<(), String> {
+        self.generated.fetch_add(1, Ordering::SeqCst);
+        for (stage, file, contents) in [
+            (Stage::ExportingStep, "output.step", MOCK_STEP),
+            (Stage::ExportingGltf, "source.gltf", MOCK_GLTF),
+            (Stage::ExportingStl, "source.stl", MOCK_STL),
+        ] {
+            progress(stage);
+            std::fs::write(dir.join(file), contents).map_err(|e| format!("Failed to write {}: {}", file, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// How the mock engine responds to `zoo kcl export`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MockBehavior {
+    /// Every export succeeds.
+    Succeed,
+    /// Every export fails with [`CLOSED_EARLY`].
+    CloseEarly,
+    /// The first N exports fail with [`CLOSED_EARLY`]; later ones succeed.
+    CloseEarlyTimes(u32),
+}
+
+/// A fake `zoo` binary in a temporary directory.
+#[cfg(unix)]
+pub struct MockEngine {
+    dir: TempDir,
+}
+
+#[cfg(unix)]
+impl MockEngine {
+    pub fn new(behavior: MockBehavior) -> io::Result<Self> {
+        Self::with_latency(behavior, Duration::ZERO)
+    }
+
+    /// Like [`MockEngine::new`], but each export takes `latency`, roughly like a real
+    /// engine round trip. Used by the load test to size concurrency limits.
+    pub fn with_latency(behavior: MockBehavior, latency: Duration) -> io::Result<Self> {
+        let dir = TempDir::new()?;
+        let failures = match behavior {
+            MockBehavior::Succeed => 0,
+            MockBehavior::CloseEarly => u32::MAX,
+            MockBehavior::CloseEarlyTimes(n) => n,
+        };
+        let log = dir.path().join("invocations.log");
+        let counter = dir.path().join("exports");
+
+        let script = format!(
+            r#"#!/bin/sh
+echo "$*" >> '{log}'
+for arg; do out_dir=$arg; done
+if [ "$1" = kcl ]; then
+  sleep {latency}
+  count=$(( $(cat '{counter}' 2>/dev/null || echo 0) + 1 ))
+  echo $count > '{counter}'
+  if [ $count -le {failures} ]; then
+    echo '{closed_early}' >&2
+    exit 1
+  fi
+fi
+case "$*" in
+  *--output-format=step*) cat > "$out_dir/output.step" <<'EOF'
+{step}EOF
+  ;;
+  *--output-format=gltf*) cat > "$out_dir/source.gltf" <<'EOF'
+{gltf}EOF
+  ;;
+  *--output-format=stl*) cat > "$out_dir/source.stl" <<'EOF'
+{stl}EOF
+  ;;
+esac
+"#,
+            log = log.display(),
+            counter = counter.display(),
+            failures = failures,
+            latency = latency.as_secs_f64(),
+            closed_early = CLOSED_EARLY,
+            step = MOCK_STEP,
+            gltf = MOCK_GLTF,
+            stl = MOCK_STL,
+        );
+
+        let program = dir.path().join("zoo");
+        std::fs::write(&program, script)?;
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755))?;
+        Ok(Self { dir })
+    }
+
+    /// Engine configuration that runs this mock against the repo's KCL sources.
+    /// Retries use the default attempt count but don't wait between attempts, and
+    /// failed exports aren't replaced by a local STEP file.
+    pub fn cli(&self) -> ZooCli {
+        ZooCli {
+            program: self.dir.path().join("zoo"),
+            kcl_source_dir: PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src")),
+            retry: RetryPolicy {
+                base_delay: Duration::ZERO,
+                ..RetryPolicy::default()
+            },
+            step_fallback: false,
+            api_token: None,
+            gltf_compression: crate::gltf::GltfCompression::None,
+        }
+    }
+
+    /// Every command line the mock has been run with, oldest first.
+    pub fn invocations(&self) -> Vec<String> {
+        std::fs::read_to_string(self.dir.path().join("invocations.log"))
+            .map(|log| log.lines().map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    /// How many `kcl export` calls the mock has answered, including failures.
+    pub fn exports(&self) -> usize {
+        self.invocations()
+            .iter()
+            .filter(|line| line.starts_with("kcl export"))
+            .count()
+    }
+}
@@ -0,0 +1,260 @@
+//! Where generated files are saved, and what they are called.
+//!
+//! The engine always writes `output.step`, `source.gltf`, and so on into a
+//! temporary directory. An [`OutputConfig`] copies them out to a directory under a
+//! name rendered from a template, so a file can be traced back to the plate that
+//! made it without renaming it by hand. Placeholders:
+//!
+//! | Placeholder | Value |
+//! |---|---|
+//! | `{hash}` | The plate's cache key without `plate-`, as the web server's cache uses |
+//! | `{date}` | The UTC date the files were saved, `YYYY-MM-DD` |
+//! | `{family}` | `standard`, `slotted`, or `dual_pin` |
+//! | `{material}` | e.g. `stainless_steel` |
+//! | `{bolt_size}` | e.g. `M8` |
+//! | `{pin_count}` | Number of pins |
+//! | `{bolt_spacing}`, `{bracket_height}`, `{bracket_width}`, `{pin_diameter}`, `{plate_thickness}` | Millimeters, e.g. `60` or `8.5` |
+//!
+//! `{{` and `}}` are literal braces. Each file is the rendered name plus its
+//! extension, e.g. `bracket-60-M8.step` for `bracket-{bolt_spacing}-{bolt_size}`.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use domain::ActuatorPlate;
+
+use crate::GenerationResult;
+
+/// Every placeholder a name template may use.
+pub const PLACEHOLDERS: [&str; 11] = [
+    "hash",
+    "date",
+    "family",
+    "material",
+    "bolt_size",
+    "pin_count",
+    "bolt_spacing",
+    "bracket_height",
+    "bracket_width",
+    "pin_diameter",
+    "plate_thickness",
+];
+
+/// A name template that can't be rendered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OutputError {
+    /// `{name}` where `name` isn't one of [`PLACEHOLDERS`].
+    UnknownPlaceholder(String),
+    /// A `{` without a `}`, or a `}` without a `{`.
+    UnmatchedBrace,
+    /// The template is empty or contains a path separator, so the name wouldn't be
+    /// a file in the output directory.
+    InvalidName(String),
+}
+
+impl fmt::Display for OutputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputError::UnknownPlaceholder(name) => write!(
+                f,
+                "unknown placeholder {{{}}}; expected one of {{{}}}",
+                name,
+                PLACEHOLDERS.join("}, {")
+            ),
+            OutputError::UnmatchedBrace => write!(f, "unmatched brace; write {{{{ or }}}} for a literal one"),
+            OutputError::InvalidName(name) => write!(f, "{:?} is not a file name", name),
+        }
+    }
+}
+
+impl std::error::Error for OutputError {}
+
+/// Where to save generated files and how to name them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutputConfig {
+    /// Directory the files are copied into; created if missing
+    pub directory: PathBuf,
+    /// File name without its extension, with placeholders filled in from the plate
+    pub name: String,
+}
+
+impl Default for OutputConfig {
+    /// `plate.step` and friends in the working directory.
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("."),
+            name: "plate".to_string(),
+        }
+    }
+}
+
+impl OutputConfig {
+    /// Check the name template without a plate, e.g. when parsing arguments.
+    pub fn validate(&self) -> Result<(), OutputError> {
+        self.render(&ActuatorPlate::default(), UNIX_EPOCH).map(|_| ())
+    }
+
+    /// The file name, without extension, for `plate` saved at `now`.
+    pub fn render(&self, plate: &ActuatorPlate, now: SystemTime) -> Result<String, OutputError> {
+        let mut name = String::new();
+        let mut rest = self.name.as_str();
+        while let Some(i) = rest.find(['{', '}']) {
+            name.push_str(&rest[..i]);
+            rest = &rest[i..];
+            if let Some(after) = rest.strip_prefix("{{") {
+                name.push('{');
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("}}") {
+                name.push('}');
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('{') {
+                let (placeholder, after) = after.split_once('}').ok_or(OutputError::UnmatchedBrace)?;
+                name.push_str(&value(placeholder, plate, now)?);
+                rest = after;
+            } else {
+                return Err(OutputError::UnmatchedBrace);
+            }
+        }
+        name.push_str(rest);
+
+        if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+            return Err(OutputError::InvalidName(name));
+        }
+        Ok(name)
+    }
+
+    /// Copy the generated files into [`OutputConfig::directory`], returning their
+    /// paths in [`GenerationResult::files`] order.
+    pub fn save(&self, plate: &ActuatorPlate, result: &GenerationResult) -> Result<Vec<PathBuf>, String> {
+        let name = self.render(plate, SystemTime::now()).map_err(|e| e.to_string())?;
+        std::fs::create_dir_all(&self.directory)
+            .map_err(|e| format!("can't create {}: {}", self.directory.display(), e))?;
+        result
+            .files()
+            .into_iter()
+            .map(|(source, extension)| copy(source, &self.directory.join(format!("{}.{}", name, extension))))
+            .collect()
+    }
+}
+
+fn copy(source: &Path, dest: &Path) -> Result<PathBuf, String> {
+    std::fs::copy(source, dest)
+        .map(|_| dest.to_path_buf())
+        .map_err(|e| format!("can't write {}: {}", dest.display(), e))
+}
+
+fn value(placeholder: &str, plate: &ActuatorPlate, now: SystemTime) -> Result<String, OutputError> {
+    Ok(match placeholder {
+        "hash" => plate.cache_key().trim_start_matches("plate-").to_string(),
+        "date" => date(now),
+        "family" => plate.family.name().to_string(),
+        "material" => plate.material.name().to_string(),
+        "bolt_size" => format!("M{}", plate.bolt_size.nominal_diameter_mm()),
+        "pin_count" => plate.pin_count.to_string(),
+        "bolt_spacing" => plate.bolt_spacing.to_string(),
+        "bracket_height" => plate.bracket_height.to_string(),
+        "bracket_width" => plate.bracket_width.to_string(),
+        "pin_diameter" => plate.pin_diameter.to_string(),
+        "plate_thickness" => plate.plate_thickness.to_string(),
+        _ => return Err(OutputError::UnknownPlaceholder(placeholder.to_string())),
+    })
+}
+
+/// `YYYY-MM-DD` in UTC, from Howard Hinnant's `civil_from_days`.
+fn date(now: SystemTime) -> String {
+    let days = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() / 86_400) as i64;
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use domain::{BoltSize, Length, Material};
+
+    use super::*;
+
+    fn named(name: &str) -> OutputConfig {
+        OutputConfig {
+            name: name.to_string(),
+            ..OutputConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_placeholders_are_filled_in_from_the_plate() {
+        let plate = ActuatorPlate {
+            bolt_size: BoltSize::M8,
+            material: Material::StainlessSteel,
+            plate_thickness: Length::from_micrometers(8_500),
+            ..ActuatorPlate::default()
+        };
+        // 2026-10-17T13:00:00Z
+        let now = UNIX_EPOCH + Duration::from_secs(1_792_242_000);
+
+        assert_eq!(
+            named("{date}_{material}-{bolt_size}-{bolt_spacing}x{plate_thickness}").render(&plate, now),
+            Ok("2026-10-17_stainless_steel-M8-60x8.5".to_string())
+        );
+        assert_eq!(
+            named("{family}-{{{pin_count}}}").render(&plate, now),
+            Ok("standard-{6}".to_string())
+        );
+        assert_eq!(
+            named("{hash}").render(&plate, now).unwrap(),
+            plate.cache_key().trim_start_matches("plate-")
+        );
+        for placeholder in PLACEHOLDERS {
+            assert!(named(&format!("{{{}}}", placeholder)).validate().is_ok(), "{}", placeholder);
+        }
+    }
+
+    #[test]
+    fn test_bad_templates_are_rejected() {
+        assert_eq!(
+            named("plate-{spacing}").validate(),
+            Err(OutputError::UnknownPlaceholder("spacing".to_string()))
+        );
+        assert_eq!(named("plate-{hash").validate(), Err(OutputError::UnmatchedBrace));
+        assert_eq!(named("plate}").validate(), Err(OutputError::UnmatchedBrace));
+        assert!(matches!(named("out/{hash}").validate(), Err(OutputError::InvalidName(_))));
+        assert!(matches!(named("").validate(), Err(OutputError::InvalidName(_))));
+    }
+
+    #[test]
+    fn test_dates_are_utc_calendar_days() {
+        assert_eq!(date(UNIX_EPOCH), "1970-01-01");
+        // Leap day, and the last second before the next day
+        assert_eq!(date(UNIX_EPOCH + Duration::from_secs(951_782_400)), "2000-02-29");
+        assert_eq!(date(UNIX_EPOCH + Duration::from_secs(1_709_251_199)), "2024-02-29");
+        assert_eq!(date(UNIX_EPOCH + Duration::from_secs(1_735_689_600)), "2025-01-01");
+    }
+
+    #[test]
+    fn test_save_copies_each_file_under_the_rendered_name() {
+        let plate = ActuatorPlate::default();
+        let result = crate::generate_model_with(&plate, &crate::mock::MockBackend::new()).unwrap();
+        let out = tempfile::TempDir::new().unwrap();
+        let config = OutputConfig {
+            directory: out.path().join("models"),
+            name: "bracket-{bolt_spacing}".to_string(),
+        };
+
+        let written = config.save(&plate, &result).unwrap();
+        assert_eq!(written.len(), 9);
+        assert_eq!(written[0], out.path().join("models/bracket-60.step"));
+        assert_eq!(std::fs::read_to_string(&written[0]).unwrap(), std::fs::read_to_string(&result.step_file).unwrap());
+        assert!(out.path().join("models/bracket-60.3mf").exists());
+        assert!(out.path().join("models/bracket-60.pdf").exists());
+    }
+}
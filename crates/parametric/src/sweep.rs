@@ -0,0 +1,406 @@
+//! Parameter sweeps: every combination of a few varied fields around a base plate.
+//!
+//! A [`Sweep`] takes a base [`ActuatorPlate`] and one [`SweepRange`] per varied
+//! field, e.g. `bolt_spacing=40..=80:10` and `pin_count=2..=6:2`, and expands them
+//! into their cartesian product. Combinations that fail validation are skipped.
+//! [`run`] generates the rest into a directory next to a `manifest.json` that lists
+//! each plate and its files; the web server instead starts a job per plate so the
+//! models land in its cache.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use domain::{ActuatorPlate, Count, Length, Newtons};
+use serde::{Deserialize, Serialize};
+use validation::{PlateValidationError, ValidationLimits};
+
+use crate::{generate_model_with_limits, AllErrors, GeometryBackend};
+
+/// Most combinations one sweep may expand to, valid or not.
+pub const MAX_SWEEP_PLATES: usize = 1000;
+
+/// A numeric plate field that can be swept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SweepField {
+    BoltSpacing,
+    BracketHeight,
+    BracketWidth,
+    PinDiameter,
+    PinCount,
+    PlateThickness,
+    ExpectedForcePerPin,
+}
+
+impl SweepField {
+    const ALL: [SweepField; 7] = [
+        SweepField::BoltSpacing,
+        SweepField::BracketHeight,
+        SweepField::BracketWidth,
+        SweepField::PinDiameter,
+        SweepField::PinCount,
+        SweepField::PlateThickness,
+        SweepField::ExpectedForcePerPin,
+    ];
+
+    /// The plate field name, e.g. `bolt_spacing`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            SweepField::BoltSpacing => "bolt_spacing",
+            SweepField::BracketHeight => "bracket_height",
+            SweepField::BracketWidth => "bracket_width",
+            SweepField::PinDiameter => "pin_diameter",
+            SweepField::PinCount => "pin_count",
+            SweepField::PlateThickness => "plate_thickness",
+            SweepField::ExpectedForcePerPin => "expected_force_per_pin",
+        }
+    }
+
+    /// Whether the field only takes whole numbers (pins and newtons).
+    const fn is_whole(self) -> bool {
+        matches!(self, SweepField::PinCount | SweepField::ExpectedForcePerPin)
+    }
+
+    fn set(self, plate: &mut ActuatorPlate, value: f64) -> Result<(), SweepError> {
+        let length = || Length::from_mm_f64(value).map_err(|e| SweepError::Value(self, e.to_string()));
+        match self {
+            SweepField::BoltSpacing => plate.bolt_spacing = length()?,
+            SweepField::BracketHeight => plate.bracket_height = length()?,
+            SweepField::BracketWidth => plate.bracket_width = length()?,
+            SweepField::PinDiameter => plate.pin_diameter = length()?,
+            SweepField::PlateThickness => plate.plate_thickness = length()?,
+            SweepField::PinCount => {
+                plate.pin_count = Count::new(value as u32).map_err(|e| SweepError::Value(self, e.to_string()))?
+            }
+            SweepField::ExpectedForcePerPin => plate.expected_force_per_pin = Newtons(value as u32),
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SweepField {
+    type Err = SweepError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SweepField::ALL
+            .into_iter()
+            .find(|field| field.name() == s)
+            .ok_or_else(|| SweepError::Parse(format!("`{}` can't be swept", s)))
+    }
+}
+
+/// Values for one field: `start`, `start + step`, … up to and including `end`.
+///
+/// Lengths are in millimeters, forces in newtons.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SweepRange {
+    pub field: SweepField,
+    #[cfg_attr(feature = "openapi", schema(example = 40))]
+    pub start: f64,
+    #[cfg_attr(feature = "openapi", schema(example = 80))]
+    pub end: f64,
+    #[cfg_attr(feature = "openapi", schema(example = 10))]
+    pub step: f64,
+}
+
+impl SweepRange {
+    /// The values in the range, in increasing order.
+    pub fn values(&self) -> Result<Vec<f64>, SweepError> {
+        let invalid = |msg: &str| Err(SweepError::Range(self.field, msg.to_string()));
+        if !(self.start.is_finite() && self.end.is_finite() && self.step.is_finite()) {
+            return invalid("start, end, and step must be numbers");
+        }
+        if self.step <= 0.0 {
+            return invalid("step must be positive");
+        }
+        if self.end < self.start {
+            return invalid("end must not be less than start");
+        }
+        if self.field.is_whole() && (self.start.fract() != 0.0 || self.step.fract() != 0.0) {
+            return invalid("start and step must be whole numbers");
+        }
+        // Allow for rounding, so 0.1..=0.3:0.1 includes 0.3
+        let steps = ((self.end - self.start) / self.step + 1e-9).floor();
+        if steps >= MAX_SWEEP_PLATES as f64 {
+            return Err(SweepError::TooMany);
+        }
+        Ok((0..=steps as usize).map(|i| self.start + i as f64 * self.step).collect())
+    }
+}
+
+/// Parses `field=start..=end:step`, e.g. `bolt_spacing=40..=80:10`.
+impl FromStr for SweepRange {
+    type Err = SweepError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let usage = || SweepError::Parse(format!("expected FIELD=START..=END:STEP, got `{}`", s));
+        let (field, range) = s.split_once('=').ok_or_else(usage)?;
+        let (bounds, step) = range.split_once(':').ok_or_else(usage)?;
+        let (start, end) = bounds.split_once("..=").ok_or_else(usage)?;
+        let number = |v: &str| v.trim().parse::<f64>().map_err(|_| usage());
+        Ok(SweepRange {
+            field: field.trim().parse()?,
+            start: number(start)?,
+            end: number(end)?,
+            step: number(step)?,
+        })
+    }
+}
+
+/// Why a sweep couldn't be expanded.
+#[derive(Debug, PartialEq)]
+pub enum SweepError {
+    /// A range couldn't be parsed.
+    Parse(String),
+    /// A range is empty or malformed.
+    Range(SweepField, String),
+    /// The same field has two ranges.
+    Duplicate(SweepField),
+    /// A value in a range can't be stored in its field.
+    Value(SweepField, String),
+    /// The ranges expand to more than [`MAX_SWEEP_PLATES`] combinations.
+    TooMany,
+    /// The output directory or manifest couldn't be written.
+    Io(String),
+}
+
+impl fmt::Display for SweepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SweepError::Parse(msg) => f.write_str(msg),
+            SweepError::Range(field, msg) => write!(f, "{}: {}", field.name(), msg),
+            SweepError::Duplicate(field) => write!(f, "{} is swept more than once", field.name()),
+            SweepError::Value(field, msg) => write!(f, "{}: {}", field.name(), msg),
+            SweepError::TooMany => write!(f, "a sweep can cover at most {} plates", MAX_SWEEP_PLATES),
+            SweepError::Io(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for SweepError {}
+
+/// A base plate and the fields to vary around it.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Sweep {
+    /// Values for every field that isn't swept
+    pub base: ActuatorPlate,
+    /// One range per swept field; the first varies slowest
+    pub ranges: Vec<SweepRange>,
+}
+
+/// One combination of a sweep.
+#[derive(Debug)]
+pub struct SweepPoint {
+    /// Position in the cartesian product, counting invalid combinations
+    pub index: usize,
+    pub plate: ActuatorPlate,
+    /// Why the plate is invalid; empty for valid plates
+    pub errors: Vec<PlateValidationError>,
+}
+
+impl Sweep {
+    /// Every combination, in order, validated against `limits`.
+    pub fn points(&self, limits: &ValidationLimits) -> Result<Vec<SweepPoint>, SweepError> {
+        let mut axes = Vec::with_capacity(self.ranges.len());
+        for (i, range) in self.ranges.iter().enumerate() {
+            if self.ranges[..i].iter().any(|other| other.field == range.field) {
+                return Err(SweepError::Duplicate(range.field));
+            }
+            axes.push((range.field, range.values()?));
+        }
+        let total = axes
+            .iter()
+            .try_fold(1usize, |total, (_, values)| total.checked_mul(values.len()))
+            .filter(|&total| total <= MAX_SWEEP_PLATES)
+            .ok_or(SweepError::TooMany)?;
+
+        let mut points = Vec::with_capacity(total);
+        for index in 0..total {
+            let mut plate = self.base;
+            // Mixed-radix digits of `index`, last range fastest
+            let mut rest = index;
+            for (field, values) in axes.iter().rev() {
+                field.set(&mut plate, values[rest % values.len()])?;
+                rest /= values.len();
+            }
+            let errors = validation::validate_with_limits(&plate, limits).err().unwrap_or_default();
+            points.push(SweepPoint { index, plate, errors });
+        }
+        Ok(points)
+    }
+}
+
+/// The file written next to a sweep's models, describing each of them.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SweepManifest {
+    pub sweep: Sweep,
+    /// Combinations in the sweep, valid or not
+    pub total: usize,
+    /// Combinations skipped because they failed validation
+    pub skipped: usize,
+    /// One entry per valid combination, in order
+    pub plates: Vec<ManifestEntry>,
+}
+
+/// A generated (or failed) plate in a [`SweepManifest`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ManifestEntry {
+    pub index: usize,
+    pub plate: ActuatorPlate,
+    /// File names relative to the manifest; empty if generation failed
+    pub files: Vec<String>,
+    /// True if the files were already there from an earlier run
+    pub cached: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SweepManifest {
+    /// Plates whose generation failed.
+    pub fn failed(&self) -> usize {
+        self.plates.iter().filter(|entry| entry.error.is_some()).count()
+    }
+}
+
+const EXTENSIONS: [&str; 9] = ["step", "gltf", "stl", "obj", "3mf", "svg", "pdf", "dxf", "kcl"];
+
+/// Generate every valid plate of `sweep` into `out` as `plate-NNNN.<ext>`, and
+/// write `manifest.json` there.
+///
+/// Plates whose files are all in `out` already are not generated again, so an
+/// interrupted sweep can be rerun. A plate the engine fails on is recorded in the
+/// manifest and the rest carry on.
+pub fn run(
+    sweep: &Sweep,
+    backend: &dyn GeometryBackend,
+    limits: &ValidationLimits,
+    out: &Path,
+) -> Result<SweepManifest, SweepError> {
+    let points = sweep.points(limits)?;
+    std::fs::create_dir_all(out).map_err(|e| SweepError::Io(format!("can't create {}: {}", out.display(), e)))?;
+
+    let total = points.len();
+    let mut plates = Vec::new();
+    for point in points.into_iter().filter(|point| point.errors.is_empty()) {
+        let files: Vec<String> = EXTENSIONS
+            .iter()
+            .map(|extension| format!("plate-{:04}.{}", point.index, extension))
+            .collect();
+        let mut entry = ManifestEntry {
+            index: point.index,
+            plate: point.plate,
+            files: Vec::new(),
+            cached: files.iter().all(|file| out.join(file).exists()),
+            error: None,
+        };
+        if !entry.cached {
+            let written = generate_model_with_limits(&point.plate, backend, limits)
+                .map_err(|e| match e {
+                    AllErrors::GeneratorError(msg) => msg,
+                    AllErrors::ValidationErrors(errors) => format!("{} validation errors", errors.len()),
+                })
+                .and_then(|result| {
+                    result.files().into_iter().zip(&files).try_for_each(|((source, _), file)| {
+                        std::fs::copy(source, out.join(file))
+                            .map(|_| ())
+                            .map_err(|e| format!("can't write {}: {}", file, e))
+                    })
+                });
+            if let Err(msg) = written {
+                entry.error = Some(msg);
+                plates.push(entry);
+                continue;
+            }
+        }
+        entry.files = files;
+        plates.push(entry);
+    }
+
+    let manifest = SweepManifest {
+        sweep: sweep.clone(),
+        total,
+        skipped: total - plates.len(),
+        plates,
+    };
+    let path = out.join("manifest.json");
+    let json = serde_json::to_string_pretty(&manifest).expect("manifest serializes");
+    std::fs::write(&path, json).map_err(|e| SweepError::Io(format!("can't write {}: {}", path.display(), e)))?;
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockBackend;
+
+    fn range(s: &str) -> SweepRange {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parses_ranges() {
+        assert_eq!(
+            range("bolt_spacing=40..=80:10"),
+            SweepRange { field: SweepField::BoltSpacing, start: 40.0, end: 80.0, step: 10.0 }
+        );
+        assert_eq!(range("plate_thickness=0.1..=0.3:0.1").values().unwrap().len(), 3);
+        assert!("bolt_size=3..=5:1".parse::<SweepRange>().is_err());
+        assert!("bolt_spacing=40..80:10".parse::<SweepRange>().is_err());
+        assert!(range("pin_count=1..=4:0.5").values().is_err());
+        assert!(range("bolt_spacing=80..=40:10").values().is_err());
+        assert_eq!(range("bolt_spacing=0..=1000000:1").values(), Err(SweepError::TooMany));
+    }
+
+    #[test]
+    fn test_points_are_the_cartesian_product() {
+        let sweep = Sweep {
+            base: ActuatorPlate::default(),
+            ranges: vec![range("bolt_spacing=40..=80:10"), range("pin_count=2..=6:2")],
+        };
+        let points = sweep.points(&ValidationLimits::DEFAULT).unwrap();
+        assert_eq!(points.len(), 15);
+        // The last range varies fastest
+        assert_eq!(points[1].plate.bolt_spacing, Length::from_mm(40));
+        assert_eq!(points[1].plate.pin_count, Count::of(4));
+        assert_eq!(points[3].plate.bolt_spacing, Length::from_mm(50));
+        assert_eq!(points[14].plate.plate_thickness, ActuatorPlate::default().plate_thickness);
+
+        let duplicate = Sweep {
+            ranges: vec![range("pin_count=1..=2:1"), range("pin_count=3..=4:1")],
+            ..sweep
+        };
+        assert_eq!(
+            duplicate.points(&ValidationLimits::DEFAULT).unwrap_err(),
+            SweepError::Duplicate(SweepField::PinCount)
+        );
+    }
+
+    #[test]
+    fn test_run_writes_valid_plates_and_manifest() {
+        let sweep = Sweep {
+            base: ActuatorPlate::default(),
+            // 13 pins is over the limit, so one of the three is skipped
+            ranges: vec![range("pin_count=11..=13:1")],
+        };
+        let backend = MockBackend::new();
+        let out = tempfile::tempdir().unwrap();
+
+        let manifest = run(&sweep, &backend, &ValidationLimits::DEFAULT, out.path()).unwrap();
+        assert_eq!((manifest.total, manifest.skipped, manifest.failed()), (3, 1, 0));
+        assert_eq!(manifest.plates[1].files[0], "plate-0001.step");
+        assert!(out.path().join("plate-0001.pdf").exists());
+        assert!(out.path().join("plate-0001.dxf").exists());
+        let written: SweepManifest =
+            serde_json::from_str(&std::fs::read_to_string(out.path().join("manifest.json")).unwrap()).unwrap();
+        assert_eq!(written.plates.len(), 2);
+
+        // A second run finds the files and doesn't generate again
+        let manifest = run(&sweep, &backend, &ValidationLimits::DEFAULT, out.path()).unwrap();
+        assert!(manifest.plates.iter().all(|entry| entry.cached));
+        assert_eq!(backend.generated(), 2);
+    }
+}
@@ -0,0 +1,119 @@
+//! KCL templates, one per [`PlateFamily`].
+//!
+//! A template is an entry point and the part file it imports, both kept in the KCL
+//! source directory. The entry point is copied into the project as `main.kcl`, the
+//! file `zoo kcl export` runs, and reads its values from the `params.kcl` that
+//! [`params_kcl`] renders. Every family shares the standard plate's parameters and
+//! adds its own: `slotLength` for slotted plates, `pinSpacing` for dual-pin plates.
+//! The rules those values must follow are in `validation::validate_family`.
+
+use std::path::Path;
+
+use domain::{ActuatorPlate, HoleSpec, Length, PlateFamily};
+
+/// The KCL files that build one plate family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Template {
+    /// Entry point in the source directory, copied into the project as `main.kcl`
+    pub main: &'static str,
+    /// Part file the entry point imports, copied under its own name
+    pub part: &'static str,
+    /// The entry point as compiled into this build, for [`crate::kcl::emit`]
+    pub main_source: &'static str,
+    /// The part file as compiled into this build
+    pub part_source: &'static str,
+}
+
+/// Round bolt holes in the corners and pins on a circle.
+pub const STANDARD: Template = Template {
+    main: "main.kcl",
+    part: "plate.kcl",
+    main_source: include_str!("main.kcl"),
+    part_source: include_str!("plate.kcl"),
+};
+
+/// Bolt slots across the width.
+pub const SLOTTED: Template = Template {
+    main: "slotted_main.kcl",
+    part: "slotted_plate.kcl",
+    main_source: include_str!("slotted_main.kcl"),
+    part_source: include_str!("slotted_plate.kcl"),
+};
+
+/// Two pins on the horizontal centerline.
+pub const DUAL_PIN: Template = Template {
+    main: "dual_pin_main.kcl",
+    part: "dual_pin_plate.kcl",
+    main_source: include_str!("dual_pin_main.kcl"),
+    part_source: include_str!("dual_pin_plate.kcl"),
+};
+
+/// Every template, in [`PlateFamily`] declaration order.
+pub const ALL: [Template; 3] = [STANDARD, SLOTTED, DUAL_PIN];
+
+impl Template {
+    /// The template that builds `family`.
+    pub fn for_family(family: &PlateFamily) -> Template {
+        match family {
+            PlateFamily::Standard => STANDARD,
+            PlateFamily::Slotted { .. } => SLOTTED,
+            PlateFamily::DualPin { .. } => DUAL_PIN,
+        }
+    }
+
+    /// Copy the template from `source_dir` into the project directory.
+    pub fn copy_sources(&self, source_dir: &Path, project_dir: &Path) -> std::io::Result<()> {
+        std::fs::copy(source_dir.join(self.main), project_dir.join("main.kcl"))?;
+        std::fs::copy(source_dir.join(self.part), project_dir.join(self.part))?;
+        Ok(())
+    }
+}
+
+/// Render the params.kcl source for a plate.
+///
+/// Exposed so callers can attach the exact generated KCL to error reports.
+pub fn params_kcl(plate: &ActuatorPlate) -> String {
+    let mut kcl = String::from("@settings(defaultLengthUnit = mm, kclVersion = 1.0)\n");
+    for (name, value) in params(plate) {
+        kcl.push_str(&format!("\nexport {} = {}", name, value));
+    }
+    kcl
+}
+
+/// The values params.kcl exports for a plate, as KCL expressions: the ones every
+/// template reads, then the family's own.
+fn params(plate: &ActuatorPlate) -> Vec<(&'static str, String)> {
+    // Clearance hole for the mounting bolts, or the tap drill for tapped holes
+    let bolt_hole_diameter = plate.bolt_holes.drill_diameter_mm(plate.bolt_size);
+    let (recess, recess_diameter, recess_depth) = match plate.bolt_holes {
+        HoleSpec::Counterbore { diameter, depth } => ("counterbore", diameter, depth),
+        HoleSpec::Countersink { diameter } => {
+            // 90° cone, so it is half as deep as it is wider than the hole
+            let hole = Length::from_mm_f64(bolt_hole_diameter as f64).unwrap_or(Length::ZERO);
+            let depth = diameter.micrometers().saturating_sub(hole.micrometers()) / 2;
+            ("countersink", diameter, Length::from_micrometers(depth))
+        }
+        HoleSpec::Through | HoleSpec::Tapped { .. } => ("none", Length::ZERO, Length::ZERO),
+    };
+
+    let mut params = vec![
+        ("plateThickness", plate.plate_thickness.to_string()),
+        ("boltDiameter", bolt_hole_diameter.to_string()),
+        ("boltRecess", format!("\"{}\"", recess)),
+        ("boltRecessDiameter", recess_diameter.to_string()),
+        ("boltRecessDepth", recess_depth.to_string()),
+        ("boltSpacing", plate.bolt_spacing.to_string()),
+        ("bracketHeight", plate.bracket_height.to_string()),
+        ("bracketWidth", plate.bracket_width.to_string()),
+        ("materialColor", format!("\"{}\"", plate.material.as_hex_code())),
+        // Bored for the pin fit, if there is one
+        ("pinDiameter", plate.pin_hole_diameter().to_string()),
+        ("pinCount", plate.pin_count.to_string()),
+    ];
+    match plate.family {
+        PlateFamily::Standard => {}
+        PlateFamily::Slotted { slot_length } => params.push(("slotLength", slot_length.to_string())),
+        PlateFamily::DualPin { pin_spacing } => params.push(("pinSpacing", pin_spacing.to_string())),
+    }
+    params
+}
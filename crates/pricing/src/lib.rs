@@ -0,0 +1,209 @@
+#![cfg_attr(not(feature = "openapi"), no_std)]
+//! Estimated manufacturing prices for actuator plates.
+//!
+//! A unit price adds up, in cents:
+//!
+//! 1. material: the stock blank (bracket width × height × thickness, plus a cutting
+//!    allowance) by density, at the material's price per kilogram
+//! 2. machining: profile plus per-hole cycle time, scaled by machinability, at the
+//!    shop rate
+//! 3. setup: a fixed charge per order, spread over the quantity
+//!
+//! less a quantity discount. Prices are estimates for the UI and for orders; the
+//! third-party quotes from `POST /api/quote` are the real numbers.
+
+use domain::{ActuatorPlate, Count, Material};
+use serde::Serialize;
+
+/// Mounting bolts per plate, one at each corner.
+const BOLT_COUNT: u32 = 4;
+
+/// Prices and shop parameters used for estimates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceTable {
+    /// Stock price per kilogram, per material
+    pub cents_per_kg: [(Material, u32); 4],
+    /// Machine time, per hour
+    pub shop_rate_cents_per_hour: u32,
+    /// Programming and fixturing, per order
+    pub setup_cents: u32,
+    /// Extra stock bought beyond the finished blank, in percent
+    pub stock_allowance_percent: u32,
+    /// `(minimum quantity, percent off)`, smallest quantity first
+    pub discounts: [(u32, u8); 3],
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        Self {
+            cents_per_kg: [
+                (Material::Aluminum, 800),
+                (Material::CarbonSteel, 300),
+                (Material::StainlessSteel, 1_000),
+                (Material::Brass, 1_400),
+            ],
+            shop_rate_cents_per_hour: 9_000,
+            setup_cents: 15_000,
+            stock_allowance_percent: 10,
+            discounts: [(10, 5), (50, 10), (100, 15)],
+        }
+    }
+}
+
+/// Breakdown of an estimated price. Per-plate amounts are in cents.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PriceEstimate {
+    /// Finished mass of one plate, in grams
+    pub mass_grams: u32,
+    /// Stock material for one plate
+    pub material_cents: u64,
+    /// Machine time for one plate, in minutes
+    pub machining_minutes: u32,
+    /// Machining cost for one plate
+    pub machining_cents: u64,
+    /// Each plate's share of the order setup charge
+    pub setup_cents: u64,
+    /// Quantity discount applied to the unit price, in percent
+    pub discount_percent: u8,
+    /// Price per plate after the discount
+    pub unit_price_cents: u64,
+    /// Price for the whole quantity
+    pub total_price_cents: u64,
+    /// Currency of all amounts (ISO 4217)
+    pub currency: &'static str,
+}
+
+/// Cycle time relative to aluminum.
+pub fn machinability_factor(material: Material) -> f32 {
+    match material {
+        Material::Aluminum => 1.0,
+        Material::Brass => 1.1,
+        Material::CarbonSteel => 1.5,
+        Material::StainlessSteel => 2.0,
+    }
+}
+
+/// Machining minutes for one plate: profile plus each drilled hole.
+pub fn cycle_minutes(plate: &ActuatorPlate) -> f32 {
+    let holes = (plate.pin_count.get() + BOLT_COUNT) as f32;
+    (10.0 + 2.0 * holes) * machinability_factor(plate.material)
+}
+
+fn blank_volume_mm3(plate: &ActuatorPlate) -> f32 {
+    plate.bracket_width.to_mm() as f32
+        * plate.bracket_height.to_mm() as f32
+        * plate.plate_thickness.to_mm() as f32
+}
+
+/// Mass in kilograms of `volume_mm3` of `material`.
+fn mass_kg(volume_mm3: f32, material: Material) -> f32 {
+    volume_mm3 * 1e-9 * material.density_kg_m3() as f32
+}
+
+/// Round a non-negative amount to the nearest whole unit.
+fn round(value: f32) -> u64 {
+    (value + 0.5) as u64
+}
+
+impl PriceTable {
+    fn cents_per_kg(&self, material: Material) -> u32 {
+        self.cents_per_kg
+            .iter()
+            .find(|(m, _)| *m == material)
+            .map_or(0, |(_, cents)| *cents)
+    }
+
+    /// Percent off for ordering `quantity` plates.
+    pub fn discount_percent(&self, quantity: u32) -> u8 {
+        self.discounts
+            .iter()
+            .rev()
+            .find(|(min_quantity, _)| quantity >= *min_quantity)
+            .map_or(0, |(_, percent)| *percent)
+    }
+
+    /// Estimate the price of `quantity` plates.
+    pub fn estimate(&self, plate: &ActuatorPlate, quantity: Count) -> PriceEstimate {
+        let stock_volume = blank_volume_mm3(plate) * (100 + self.stock_allowance_percent) as f32 / 100.0;
+        let material_cents = round(mass_kg(stock_volume, plate.material) * self.cents_per_kg(plate.material) as f32);
+
+        let minutes = cycle_minutes(plate);
+        let machining_cents = round(minutes / 60.0 * self.shop_rate_cents_per_hour as f32);
+        let setup_cents = (self.setup_cents as u64).div_ceil(quantity.into());
+
+        let discount_percent = self.discount_percent(quantity.get());
+        let list_price = material_cents + machining_cents + setup_cents;
+        let unit_price_cents = (list_price * (100 - discount_percent as u64)).div_ceil(100);
+
+        PriceEstimate {
+            mass_grams: plate.mass_properties().mass_grams,
+            material_cents,
+            machining_minutes: round(minutes) as u32,
+            machining_cents,
+            setup_cents,
+            discount_percent,
+            unit_price_cents,
+            total_price_cents: unit_price_cents * u64::from(quantity),
+            currency: "USD",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::Length;
+
+    #[test]
+    fn test_default_plate_estimate() {
+        // 300 × 400 × 8 mm aluminum, 6 pins: 1.056 L of stock at 2700 kg/m³ = 2.85 kg
+        let estimate = PriceTable::default().estimate(&ActuatorPlate::default(), Count::ONE);
+        assert_eq!(estimate.material_cents, 2281);
+        assert_eq!(estimate.machining_minutes, 30);
+        assert_eq!(estimate.machining_cents, 4500);
+        assert_eq!(estimate.setup_cents, 15_000);
+        assert_eq!(estimate.discount_percent, 0);
+        assert_eq!(estimate.unit_price_cents, 2281 + 4500 + 15_000);
+        assert_eq!(estimate.total_price_cents, estimate.unit_price_cents);
+        // The holes come off the finished mass, not the stock
+        assert!(estimate.mass_grams < 2592 && estimate.mass_grams > 2500);
+    }
+
+    #[test]
+    fn test_quantity_spreads_setup_and_earns_discounts() {
+        let table = PriceTable::default();
+        let plate = ActuatorPlate::default();
+        let one = table.estimate(&plate, Count::of(1));
+        let hundred = table.estimate(&plate, Count::of(100));
+
+        assert_eq!(hundred.setup_cents, 150);
+        assert_eq!(hundred.discount_percent, 15);
+        assert!(hundred.unit_price_cents < one.unit_price_cents);
+        assert_eq!(hundred.total_price_cents, hundred.unit_price_cents * 100);
+
+        assert_eq!(table.discount_percent(9), 0);
+        assert_eq!(table.discount_percent(10), 5);
+        assert_eq!(table.discount_percent(99), 10);
+    }
+
+    #[test]
+    fn test_denser_and_harder_materials_cost_more() {
+        let table = PriceTable::default();
+        let aluminum = ActuatorPlate::default();
+        let stainless = ActuatorPlate {
+            material: Material::StainlessSteel,
+            ..aluminum
+        };
+        let thicker = ActuatorPlate {
+            plate_thickness: Length::from_mm(16),
+            ..aluminum
+        };
+
+        let base = table.estimate(&aluminum, Count::of(10));
+        let steel = table.estimate(&stainless, Count::of(10));
+        assert!(steel.material_cents > base.material_cents);
+        assert_eq!(steel.machining_minutes, base.machining_minutes * 2);
+        assert_eq!(table.estimate(&thicker, Count::of(10)).material_cents, round(base.material_cents as f32 * 2.0));
+    }
+}
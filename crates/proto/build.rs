@@ -0,0 +1,12 @@
+// Compiles the .proto definitions with protox (a pure-Rust protobuf compiler), so
+// neither developers nor CI need a system `protoc`.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let files = ["proto/platerator/v1/plate.proto"];
+    let descriptors = protox::compile(files, ["proto"])?;
+    prost_build::Config::new().compile_fds(descriptors)?;
+
+    for file in files {
+        println!("cargo:rerun-if-changed={file}");
+    }
+    Ok(())
+}
@@ -0,0 +1,527 @@
+//! Protobuf definitions and conversions for domain types.
+//!
+//! The `.proto` sources live in `proto/platerator/v1/` and are compiled at build time.
+//! Conversions into protobuf types are infallible; conversions back into domain types
+//! fail on unspecified enum values, numbers that don't fit the domain's integer widths, or
+//! fit classes that don't parse.
+
+use core::fmt;
+
+use domain::{
+    BoltSize, HoleSpec, IsoThread, Length, Material, Newtons, PinFit, PlateFamily, Tolerance, Tolerances,
+};
+
+pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/platerator.v1.rs"));
+}
+
+/// Errors converting a protobuf message into a domain type.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConversionError {
+    /// An enum field held an unknown or unspecified value.
+    InvalidEnum { field: &'static str, value: i32 },
+    /// A numeric field was outside the range the domain type allows, e.g. a
+    /// zero count.
+    OutOfRange { field: &'static str, value: u32 },
+    /// A string field didn't parse, e.g. an unknown ISO 286 fit class.
+    Unparseable { field: &'static str, value: String },
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionError::InvalidEnum { field, value } => {
+                write!(f, "Invalid value {} for enum field {}", value, field)
+            }
+            ConversionError::OutOfRange { field, value } => {
+                write!(f, "Value {} is out of range for field {}", value, field)
+            }
+            ConversionError::Unparseable { field, value } => {
+                write!(f, "Value {:?} can't be parsed for field {}", value, field)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+impl From<BoltSize> for v1::BoltSize {
+    fn from(size: BoltSize) -> Self {
+        match size {
+            BoltSize::M3 => v1::BoltSize::M3,
+            BoltSize::M4 => v1::BoltSize::M4,
+            BoltSize::M5 => v1::BoltSize::M5,
+            BoltSize::M6 => v1::BoltSize::M6,
+            BoltSize::M8 => v1::BoltSize::M8,
+            BoltSize::M10 => v1::BoltSize::M10,
+            BoltSize::M12 => v1::BoltSize::M12,
+        }
+    }
+}
+
+impl TryFrom<v1::BoltSize> for BoltSize {
+    type Error = ConversionError;
+
+    fn try_from(size: v1::BoltSize) -> Result<Self, Self::Error> {
+        match size {
+            v1::BoltSize::M3 => Ok(BoltSize::M3),
+            v1::BoltSize::M4 => Ok(BoltSize::M4),
+            v1::BoltSize::M5 => Ok(BoltSize::M5),
+            v1::BoltSize::M6 => Ok(BoltSize::M6),
+            v1::BoltSize::M8 => Ok(BoltSize::M8),
+            v1::BoltSize::M10 => Ok(BoltSize::M10),
+            v1::BoltSize::M12 => Ok(BoltSize::M12),
+            v1::BoltSize::Unspecified => Err(ConversionError::InvalidEnum {
+                field: "bolt_size",
+                value: size as i32,
+            }),
+        }
+    }
+}
+
+impl From<Material> for v1::Material {
+    fn from(material: Material) -> Self {
+        match material {
+            Material::Aluminum => v1::Material::Aluminum,
+            Material::StainlessSteel => v1::Material::StainlessSteel,
+            Material::CarbonSteel => v1::Material::CarbonSteel,
+            Material::Brass => v1::Material::Brass,
+        }
+    }
+}
+
+impl TryFrom<v1::Material> for Material {
+    type Error = ConversionError;
+
+    fn try_from(material: v1::Material) -> Result<Self, Self::Error> {
+        match material {
+            v1::Material::Aluminum => Ok(Material::Aluminum),
+            v1::Material::StainlessSteel => Ok(Material::StainlessSteel),
+            v1::Material::CarbonSteel => Ok(Material::CarbonSteel),
+            v1::Material::Brass => Ok(Material::Brass),
+            v1::Material::Unspecified => Err(ConversionError::InvalidEnum {
+                field: "material",
+                value: material as i32,
+            }),
+        }
+    }
+}
+
+impl From<Option<PinFit>> for v1::PinFit {
+    fn from(fit: Option<PinFit>) -> Self {
+        match fit {
+            None => v1::PinFit::Unspecified,
+            Some(PinFit::Press) => v1::PinFit::Press,
+            Some(PinFit::Transition) => v1::PinFit::Transition,
+            Some(PinFit::Slip) => v1::PinFit::Slip,
+        }
+    }
+}
+
+impl From<v1::PinFit> for Option<PinFit> {
+    fn from(fit: v1::PinFit) -> Self {
+        match fit {
+            v1::PinFit::Unspecified => None,
+            v1::PinFit::Press => Some(PinFit::Press),
+            v1::PinFit::Transition => Some(PinFit::Transition),
+            v1::PinFit::Slip => Some(PinFit::Slip),
+        }
+    }
+}
+
+impl From<HoleSpec> for v1::HoleSpec {
+    fn from(spec: HoleSpec) -> Self {
+        use v1::hole_spec::{Counterbore, Countersink, Kind, Tapped, Through};
+
+        let kind = match spec {
+            HoleSpec::Through => Kind::Through(Through {}),
+            HoleSpec::Counterbore { diameter, depth } => Kind::Counterbore(Counterbore {
+                diameter_um: diameter.micrometers(),
+                depth_um: depth.micrometers(),
+            }),
+            HoleSpec::Countersink { diameter } => Kind::Countersink(Countersink {
+                diameter_um: diameter.micrometers(),
+            }),
+            HoleSpec::Tapped { thread } => Kind::Tapped(Tapped {
+                size: v1::BoltSize::from(thread.size) as i32,
+                pitch_um: thread.pitch_um.into(),
+            }),
+        };
+        v1::HoleSpec { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<&v1::HoleSpec> for HoleSpec {
+    type Error = ConversionError;
+
+    fn try_from(spec: &v1::HoleSpec) -> Result<Self, Self::Error> {
+        use v1::hole_spec::Kind;
+
+        Ok(match &spec.kind {
+            None | Some(Kind::Through(_)) => HoleSpec::Through,
+            Some(Kind::Counterbore(counterbore)) => HoleSpec::Counterbore {
+                diameter: Length::from_micrometers(counterbore.diameter_um),
+                depth: Length::from_micrometers(counterbore.depth_um),
+            },
+            Some(Kind::Countersink(countersink)) => HoleSpec::Countersink {
+                diameter: Length::from_micrometers(countersink.diameter_um),
+            },
+            Some(Kind::Tapped(tapped)) => HoleSpec::Tapped {
+                thread: IsoThread {
+                    size: bolt_size("bolt_holes.tapped.size", tapped.size)?,
+                    pitch_um: narrow("bolt_holes.tapped.pitch_um", tapped.pitch_um)?,
+                },
+            },
+        })
+    }
+}
+
+impl From<PlateFamily> for v1::PlateFamily {
+    fn from(family: PlateFamily) -> Self {
+        use v1::plate_family::{DualPin, Kind, Slotted, Standard};
+
+        let kind = match family {
+            PlateFamily::Standard => Kind::Standard(Standard {}),
+            PlateFamily::Slotted { slot_length } => Kind::Slotted(Slotted {
+                slot_length_um: slot_length.micrometers(),
+            }),
+            PlateFamily::DualPin { pin_spacing } => Kind::DualPin(DualPin {
+                pin_spacing_um: pin_spacing.micrometers(),
+            }),
+        };
+        v1::PlateFamily { kind: Some(kind) }
+    }
+}
+
+impl From<&v1::PlateFamily> for PlateFamily {
+    fn from(family: &v1::PlateFamily) -> Self {
+        use v1::plate_family::Kind;
+
+        match &family.kind {
+            None | Some(Kind::Standard(_)) => PlateFamily::Standard,
+            Some(Kind::Slotted(slotted)) => PlateFamily::Slotted {
+                slot_length: Length::from_micrometers(slotted.slot_length_um),
+            },
+            Some(Kind::DualPin(dual_pin)) => PlateFamily::DualPin {
+                pin_spacing: Length::from_micrometers(dual_pin.pin_spacing_um),
+            },
+        }
+    }
+}
+
+impl From<Tolerance> for v1::Tolerance {
+    fn from(tolerance: Tolerance) -> Self {
+        use v1::tolerance::{Deviation, Kind};
+
+        let kind = match tolerance {
+            Tolerance::Symmetric(band) => Kind::SymmetricUm(band.micrometers()),
+            Tolerance::Deviation { upper_um, lower_um } => Kind::Deviation(Deviation { upper_um, lower_um }),
+            Tolerance::Fit { .. } => Kind::Fit(tolerance.to_string()),
+        };
+        v1::Tolerance { kind: Some(kind) }
+    }
+}
+
+/// The callout for one dimension; unset, or set without a kind, means none.
+fn tolerance(field: &'static str, tolerance: &Option<v1::Tolerance>) -> Result<Option<Tolerance>, ConversionError> {
+    use v1::tolerance::Kind;
+
+    let Some(kind) = tolerance.as_ref().and_then(|tolerance| tolerance.kind.as_ref()) else {
+        return Ok(None);
+    };
+    Ok(Some(match kind {
+        Kind::SymmetricUm(um) => Tolerance::Symmetric(Length::from_micrometers(*um)),
+        Kind::Deviation(deviation) => Tolerance::Deviation {
+            upper_um: deviation.upper_um,
+            lower_um: deviation.lower_um,
+        },
+        Kind::Fit(fit) => fit
+            .parse()
+            .ok()
+            .filter(|parsed| matches!(parsed, Tolerance::Fit { .. }))
+            .ok_or_else(|| ConversionError::Unparseable { field, value: fit.clone() })?,
+    }))
+}
+
+impl From<Tolerances> for v1::Tolerances {
+    fn from(tolerances: Tolerances) -> Self {
+        v1::Tolerances {
+            bolt_spacing: tolerances.bolt_spacing.map(Into::into),
+            bracket_height: tolerances.bracket_height.map(Into::into),
+            bracket_width: tolerances.bracket_width.map(Into::into),
+            pin_diameter: tolerances.pin_diameter.map(Into::into),
+            plate_thickness: tolerances.plate_thickness.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<&v1::Tolerances> for Tolerances {
+    type Error = ConversionError;
+
+    fn try_from(tolerances: &v1::Tolerances) -> Result<Self, Self::Error> {
+        Ok(Tolerances {
+            bolt_spacing: tolerance("tolerances.bolt_spacing", &tolerances.bolt_spacing)?,
+            bracket_height: tolerance("tolerances.bracket_height", &tolerances.bracket_height)?,
+            bracket_width: tolerance("tolerances.bracket_width", &tolerances.bracket_width)?,
+            pin_diameter: tolerance("tolerances.pin_diameter", &tolerances.pin_diameter)?,
+            plate_thickness: tolerance("tolerances.plate_thickness", &tolerances.plate_thickness)?,
+        })
+    }
+}
+
+impl From<&domain::ActuatorPlate> for v1::ActuatorPlate {
+    fn from(plate: &domain::ActuatorPlate) -> Self {
+        v1::ActuatorPlate {
+            bolt_spacing_mm: rounded_mm(plate.bolt_spacing),
+            bolt_size: v1::BoltSize::from(plate.bolt_size) as i32,
+            bracket_height_mm: rounded_mm(plate.bracket_height),
+            bracket_width_mm: rounded_mm(plate.bracket_width),
+            material: v1::Material::from(plate.material) as i32,
+            pin_diameter_mm: rounded_mm(plate.pin_diameter),
+            pin_count: plate.pin_count.get(),
+            plate_thickness_mm: rounded_mm(plate.plate_thickness),
+            expected_force_per_pin_n: plate.expected_force_per_pin.0,
+            bolt_spacing_um: plate.bolt_spacing.micrometers(),
+            bracket_height_um: plate.bracket_height.micrometers(),
+            bracket_width_um: plate.bracket_width.micrometers(),
+            pin_diameter_um: plate.pin_diameter.micrometers(),
+            plate_thickness_um: plate.plate_thickness.micrometers(),
+            bolt_holes: Some(plate.bolt_holes.into()),
+            tolerances: Some(plate.tolerances.into()),
+            family: Some(plate.family.into()),
+            pin_fit: v1::PinFit::from(plate.pin_fit) as i32,
+        }
+    }
+}
+
+/// Nearest whole millimeter, for the legacy `_mm` fields.
+fn rounded_mm(length: Length) -> u32 {
+    length.micrometers().saturating_add(500) / 1_000
+}
+
+fn narrow(field: &'static str, value: u32) -> Result<u16, ConversionError> {
+    u16::try_from(value).map_err(|_| ConversionError::OutOfRange { field, value })
+}
+
+fn bolt_size(field: &'static str, value: i32) -> Result<BoltSize, ConversionError> {
+    v1::BoltSize::try_from(value)
+        .map_err(|_| ConversionError::InvalidEnum { field, value })?
+        .try_into()
+        .map_err(|_| ConversionError::InvalidEnum { field, value })
+}
+
+/// The exact `_um` value if the sender set one, otherwise the legacy `_mm` value.
+fn length(field: &'static str, um: u32, mm: u32) -> Result<Length, ConversionError> {
+    if um != 0 {
+        return Ok(Length::from_micrometers(um));
+    }
+    narrow(field, mm).map(Length::from_mm)
+}
+
+impl TryFrom<&v1::ActuatorPlate> for domain::ActuatorPlate {
+    type Error = ConversionError;
+
+    fn try_from(plate: &v1::ActuatorPlate) -> Result<Self, Self::Error> {
+        let material = v1::Material::try_from(plate.material).map_err(|_| {
+            ConversionError::InvalidEnum {
+                field: "material",
+                value: plate.material,
+            }
+        })?;
+        let pin_fit = v1::PinFit::try_from(plate.pin_fit).map_err(|_| ConversionError::InvalidEnum {
+            field: "pin_fit",
+            value: plate.pin_fit,
+        })?;
+
+        Ok(domain::ActuatorPlate {
+            bolt_spacing: length("bolt_spacing_mm", plate.bolt_spacing_um, plate.bolt_spacing_mm)?,
+            bolt_size: bolt_size("bolt_size", plate.bolt_size)?,
+            bracket_height: length("bracket_height_mm", plate.bracket_height_um, plate.bracket_height_mm)?,
+            bracket_width: length("bracket_width_mm", plate.bracket_width_um, plate.bracket_width_mm)?,
+            material: material.try_into()?,
+            pin_diameter: length("pin_diameter_mm", plate.pin_diameter_um, plate.pin_diameter_mm)?,
+            pin_count: domain::Count::new(plate.pin_count).map_err(|_| ConversionError::OutOfRange {
+                field: "pin_count",
+                value: plate.pin_count,
+            })?,
+            plate_thickness: length("plate_thickness_mm", plate.plate_thickness_um, plate.plate_thickness_mm)?,
+            expected_force_per_pin: Newtons(plate.expected_force_per_pin_n),
+            bolt_holes: match &plate.bolt_holes {
+                Some(bolt_holes) => bolt_holes.try_into()?,
+                None => HoleSpec::Through,
+            },
+            tolerances: match &plate.tolerances {
+                Some(tolerances) => tolerances.try_into()?,
+                None => Tolerances::NONE,
+            },
+            family: plate.family.as_ref().map_or(PlateFamily::Standard, Into::into),
+            pin_fit: pin_fit.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn test_plate_round_trips_through_wire_format() {
+        let plate = domain::ActuatorPlate {
+            material: Material::Brass,
+            bolt_size: BoltSize::M6,
+            ..Default::default()
+        };
+
+        let bytes = v1::ActuatorPlate::from(&plate).encode_to_vec();
+        let decoded = v1::ActuatorPlate::decode(bytes.as_slice()).unwrap();
+
+        assert_eq!(domain::ActuatorPlate::try_from(&decoded).unwrap(), plate);
+    }
+
+    #[test]
+    fn test_hole_spec_round_trips_through_wire_format() {
+        let plate = domain::ActuatorPlate {
+            bolt_holes: HoleSpec::Tapped {
+                thread: "M10x1.25".parse().unwrap(),
+            },
+            ..Default::default()
+        };
+
+        let bytes = v1::ActuatorPlate::from(&plate).encode_to_vec();
+        let decoded = v1::ActuatorPlate::decode(bytes.as_slice()).unwrap();
+        assert_eq!(domain::ActuatorPlate::try_from(&decoded).unwrap(), plate);
+
+        // A sender that predates the field gets through holes
+        let mut legacy = decoded;
+        legacy.bolt_holes = None;
+        assert_eq!(domain::ActuatorPlate::try_from(&legacy).unwrap().bolt_holes, HoleSpec::Through);
+    }
+
+    #[test]
+    fn test_tolerances_round_trip_through_wire_format() {
+        let plate = domain::ActuatorPlate {
+            tolerances: Tolerances {
+                bolt_spacing: Some("\u{B1}0.05".parse().unwrap()),
+                pin_diameter: Some("H7/g6".parse().unwrap()),
+                plate_thickness: Some("+0.2/-0.1".parse().unwrap()),
+                ..Tolerances::NONE
+            },
+            ..Default::default()
+        };
+
+        let bytes = v1::ActuatorPlate::from(&plate).encode_to_vec();
+        let decoded = v1::ActuatorPlate::decode(bytes.as_slice()).unwrap();
+        assert_eq!(domain::ActuatorPlate::try_from(&decoded).unwrap(), plate);
+
+        let mut message = decoded;
+        message.tolerances.as_mut().unwrap().pin_diameter = Some(v1::Tolerance {
+            kind: Some(v1::tolerance::Kind::Fit("Q7".to_string())),
+        });
+        assert_eq!(
+            domain::ActuatorPlate::try_from(&message),
+            Err(ConversionError::Unparseable {
+                field: "tolerances.pin_diameter",
+                value: "Q7".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_family_round_trips_and_keeps_the_cache_key() {
+        let plate = domain::ActuatorPlate {
+            family: PlateFamily::Slotted {
+                slot_length: Length::from_mm(12),
+            },
+            ..Default::default()
+        };
+
+        let bytes = v1::ActuatorPlate::from(&plate).encode_to_vec();
+        let decoded = v1::ActuatorPlate::decode(bytes.as_slice()).unwrap();
+        let converted = domain::ActuatorPlate::try_from(&decoded).unwrap();
+        assert_eq!(converted, plate);
+        assert_eq!(converted.cache_key(), plate.cache_key());
+    }
+
+    #[test]
+    fn test_pin_fit_round_trips_through_wire_format() {
+        for pin_fit in PinFit::ALL.map(Some).into_iter().chain([None]) {
+            let plate = domain::ActuatorPlate {
+                pin_fit,
+                ..Default::default()
+            };
+
+            let bytes = v1::ActuatorPlate::from(&plate).encode_to_vec();
+            let decoded = v1::ActuatorPlate::decode(bytes.as_slice()).unwrap();
+            assert_eq!(domain::ActuatorPlate::try_from(&decoded).unwrap(), plate);
+        }
+
+        let mut message = v1::ActuatorPlate::from(&domain::ActuatorPlate::default());
+        message.pin_fit = 9;
+        assert_eq!(
+            domain::ActuatorPlate::try_from(&message),
+            Err(ConversionError::InvalidEnum { field: "pin_fit", value: 9 })
+        );
+    }
+
+    #[test]
+    fn test_unspecified_material_is_rejected() {
+        let mut message = v1::ActuatorPlate::from(&domain::ActuatorPlate::default());
+        message.material = v1::Material::Unspecified as i32;
+
+        assert_eq!(
+            domain::ActuatorPlate::try_from(&message),
+            Err(ConversionError::InvalidEnum { field: "material", value: 0 })
+        );
+    }
+
+    #[test]
+    fn test_fractional_lengths_round_trip_and_fall_back_to_mm() {
+        let plate = domain::ActuatorPlate {
+            plate_thickness: Length::from_inches(0.25).unwrap(),
+            ..Default::default()
+        };
+
+        let mut message = v1::ActuatorPlate::from(&plate);
+        assert_eq!(message.plate_thickness_um, 6_350);
+        assert_eq!(message.plate_thickness_mm, 6);
+        assert_eq!(domain::ActuatorPlate::try_from(&message).unwrap(), plate);
+
+        // A sender that predates the `_um` fields
+        message.plate_thickness_um = 0;
+        let legacy = domain::ActuatorPlate::try_from(&message).unwrap();
+        assert_eq!(legacy.plate_thickness, Length::from_mm(6));
+    }
+
+    #[test]
+    fn test_oversized_dimension_is_rejected() {
+        let mut message = v1::ActuatorPlate::from(&domain::ActuatorPlate::default());
+        message.bracket_width_um = 0;
+        message.bracket_width_mm = 70_000;
+
+        assert_eq!(
+            domain::ActuatorPlate::try_from(&message),
+            Err(ConversionError::OutOfRange { field: "bracket_width_mm", value: 70_000 })
+        );
+    }
+
+    #[test]
+    fn test_job_carries_plate_and_artifacts() {
+        let job = v1::Job {
+            id: "job-1".to_string(),
+            status: v1::JobStatus::Succeeded as i32,
+            plate: Some(v1::ActuatorPlate::from(&domain::ActuatorPlate::default())),
+            artifacts: vec![v1::Artifact {
+                format: v1::ArtifactFormat::Step as i32,
+                cache_key: "plate-0123456789abcdef".to_string(),
+                size_bytes: 1024,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let decoded = v1::Job::decode(job.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, job);
+        assert_eq!(decoded.status(), v1::JobStatus::Succeeded);
+    }
+}
@@ -0,0 +1,548 @@
+//! AP214 STEP files of actuator plates, written without the Zoo engine.
+//!
+//! An actuator plate is a rectangular slab with drilled through holes, so its
+//! boundary is six planes plus one cylinder per hole. [`plate_step`] writes that
+//! boundary directly as a manifold solid B-rep. The layout follows the KCL templates:
+//! the profile is centered on the origin in the XZ plane and extruded `plate_thickness`
+//! along -Y, with the pins on a fixed circle (or side by side, for dual-pin plates)
+//! and a bolt hole near each corner. Counterbores and countersinks are left out, so
+//! recessed bolt holes come out as plain drilled holes. Slots aren't cylinders, so
+//! slotted plates aren't supported; see [`supports`].
+//!
+//! parametric falls back to this when the engine's KCL export fails, so a flaky
+//! engine connection still yields a usable part file. [`step_meta`] stamps every
+//! STEP file, whichever exporter wrote it, with the configuration it came from.
+
+pub mod step_meta;
+
+use std::fmt::Write;
+
+use domain::{ActuatorPlate, PlateFamily};
+
+/// Radius of the circle the pins are patterned on, fixed in `plate.kcl`.
+pub const PIN_CIRCLE_RADIUS_MM: f64 = 50.0;
+/// Bolt holes sit at this fraction of the plate's half-width and half-height.
+const BOLT_INSET: f64 = 0.9;
+
+/// Value written to FILE_NAME's originating_system, so fallback files are easy to spot.
+pub const ORIGINATING_SYSTEM: &str = "platerator step-writer";
+
+type Vec3 = [f64; 3];
+
+/// A through hole in sketch coordinates (mm, origin at the plate center).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hole {
+    pub x: f64,
+    pub y: f64,
+    pub diameter: f64,
+}
+
+/// Whether [`plate_step`] can write the plate's holes: everything but slots.
+pub fn supports(plate: &ActuatorPlate) -> bool {
+    !matches!(plate.family, PlateFamily::Slotted { .. })
+}
+
+/// Every hole in the plate: the four bolt holes, then the pins counterclockwise
+/// from +X, or from left to right for dual-pin plates.
+pub fn holes(plate: &ActuatorPlate) -> Vec<Hole> {
+    let bolt_x = plate.bracket_width.to_mm() / 2.0 * BOLT_INSET;
+    let bolt_y = plate.bracket_height.to_mm() / 2.0 * BOLT_INSET;
+    let bolt_diameter = plate.bolt_holes.drill_diameter_mm(plate.bolt_size) as f64;
+    let bolts = [(-bolt_x, -bolt_y), (bolt_x, -bolt_y), (-bolt_x, bolt_y), (bolt_x, bolt_y)]
+        .into_iter()
+        .map(|(x, y)| Hole { x, y, diameter: bolt_diameter });
+
+    let pin_diameter = plate.pin_hole_diameter().to_mm();
+    let pins: Vec<Hole> = match plate.family {
+        PlateFamily::DualPin { pin_spacing } => {
+            let half = pin_spacing.to_mm() / 2.0;
+            vec![
+                Hole { x: -half, y: 0.0, diameter: pin_diameter },
+                Hole { x: half, y: 0.0, diameter: pin_diameter },
+            ]
+        }
+        PlateFamily::Standard | PlateFamily::Slotted { .. } => (0..plate.pin_count.get())
+            .map(|i| {
+                let angle = std::f64::consts::TAU * i as f64 / plate.pin_count.get() as f64;
+                Hole {
+                    x: PIN_CIRCLE_RADIUS_MM * angle.cos(),
+                    y: PIN_CIRCLE_RADIUS_MM * angle.sin(),
+                    diameter: pin_diameter,
+                }
+            })
+            .collect(),
+    };
+
+    bolts.chain(pins).collect()
+}
+
+/// Write the plate as an AP214 (`AUTOMOTIVE_DESIGN`) STEP file, in millimeters.
+///
+/// The output depends only on the plate, so the same parameters always produce
+/// the same bytes.
+pub fn plate_step(plate: &ActuatorPlate) -> String {
+    let mut step = Step::default();
+    let brep = step.solid(plate);
+    step.product(brep);
+
+    let mut file = String::new();
+    file.push_str("ISO-10303-21;\nHEADER;\n");
+    file.push_str(&file_description(plate));
+    file.push('\n');
+    let _ = writeln!(
+        file,
+        "FILE_NAME('output.step','',(''),(''),'','{}','');",
+        ORIGINATING_SYSTEM
+    );
+    file.push_str("FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));\n");
+    file.push_str("ENDSEC;\nDATA;\n");
+    file.push_str(&step.data);
+    file.push_str("ENDSEC;\nEND-ISO-10303-21;\n");
+    file
+}
+
+/// The header's FILE_DESCRIPTION: the part name, then one
+/// `TOLERANCE <field> <nominal> <tolerance>` entry per toleranced dimension, e.g.
+/// `TOLERANCE pin_diameter 10 H7/g6`.
+pub(crate) fn file_description(plate: &ActuatorPlate) -> String {
+    let mut entries = vec![step_string("Actuator plate")];
+    for (dimension, tolerance) in plate.called_out_tolerances().iter() {
+        entries.push(step_string(&format!(
+            "TOLERANCE {} {} {}",
+            dimension.field_name(),
+            plate.dimension(dimension),
+            tolerance
+        )));
+    }
+    format!("FILE_DESCRIPTION(({}),'2;1');", entries.join(","))
+}
+
+/// Write the plate's tolerances into the header of a STEP file from another
+/// exporter, replacing its FILE_DESCRIPTION. Files without a header, or plates
+/// without tolerances, come back unchanged.
+pub fn annotate(step: &str, plate: &ActuatorPlate) -> String {
+    if plate.called_out_tolerances().is_empty() {
+        return step.to_string();
+    }
+    let Some(header) = step.find("HEADER;").map(|at| at + "HEADER;".len()) else {
+        return step.to_string();
+    };
+    let header_end = step[header..].find("ENDSEC;").map_or(step.len(), |at| header + at);
+    let description = file_description(plate);
+    match step[header..header_end].find("FILE_DESCRIPTION") {
+        Some(at) => {
+            let start = header + at;
+            let end = step[start..header_end].find(");").map_or(header_end, |at| start + at + 2);
+            format!("{}{}{}", &step[..start], description, &step[end..])
+        }
+        None => format!("{}\n{}{}", &step[..header], description, &step[header..]),
+    }
+}
+
+/// A STEP string literal. Quotes and backslashes are doubled, and anything
+/// outside printable ASCII (e.g. ±) becomes a `\X2\` escape.
+pub(crate) fn step_string(text: &str) -> String {
+    let mut literal = String::from("'");
+    for c in text.chars() {
+        match c {
+            '\'' => literal.push_str("''"),
+            '\\' => literal.push_str("\\\\"),
+            ' '..='~' => literal.push(c),
+            _ => {
+                let mut units = [0u16; 2];
+                literal.push_str("\\X2\\");
+                for unit in c.encode_utf16(&mut units) {
+                    let _ = write!(literal, "{:04X}", unit);
+                }
+                literal.push_str("\\X0\\");
+            }
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
+/// The DATA section under construction; each `add` returns the new entity's id.
+struct Step {
+    data: String,
+    next_id: usize,
+}
+
+impl Default for Step {
+    fn default() -> Self {
+        Self {
+            data: String::new(),
+            next_id: 1,
+        }
+    }
+}
+
+/// An edge as used by one face: its id and whether it runs along its curve.
+type OrientedEdge = (usize, bool);
+
+impl Step {
+    fn add(&mut self, entity: &str) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        let _ = writeln!(self.data, "#{}={};", id, entity);
+        id
+    }
+
+    fn point(&mut self, p: Vec3) -> usize {
+        self.add(&format!("CARTESIAN_POINT('',{})", triple(to_world(p))))
+    }
+
+    fn direction(&mut self, d: Vec3) -> usize {
+        self.add(&format!("DIRECTION('',{})", triple(to_world(d))))
+    }
+
+    fn placement(&mut self, origin: Vec3, axis: Vec3, ref_direction: Vec3) -> usize {
+        let origin = self.point(origin);
+        let axis = self.direction(axis);
+        let ref_direction = self.direction(ref_direction);
+        self.add(&format!("AXIS2_PLACEMENT_3D('',#{},#{},#{})", origin, axis, ref_direction))
+    }
+
+    fn vertex(&mut self, p: Vec3) -> usize {
+        let point = self.point(p);
+        self.add(&format!("VERTEX_POINT('',#{})", point))
+    }
+
+    /// A straight edge between two vertices at `from` and `to`.
+    fn line_edge(&mut self, (start, from): (usize, Vec3), (end, to): (usize, Vec3)) -> usize {
+        let delta = [to[0] - from[0], to[1] - from[1], to[2] - from[2]];
+        let length = (delta[0] * delta[0] + delta[1] * delta[1] + delta[2] * delta[2]).sqrt();
+        let origin = self.point(from);
+        let direction = self.direction(delta.map(|c| c / length));
+        let vector = self.add(&format!("VECTOR('',#{},{})", direction, real(length)));
+        let line = self.add(&format!("LINE('',#{},#{})", origin, vector));
+        self.add(&format!("EDGE_CURVE('',#{},#{},#{},.T.)", start, end, line))
+    }
+
+    /// A full circle around +Z, starting and ending at `vertex` on its +X side.
+    fn circle_edge(&mut self, vertex: usize, center: Vec3, radius: f64) -> usize {
+        let placement = self.placement(center, [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]);
+        let circle = self.add(&format!("CIRCLE('',#{},{})", placement, real(radius)));
+        self.add(&format!("EDGE_CURVE('',#{},#{},#{},.T.)", vertex, vertex, circle))
+    }
+
+    /// A face bounded by an outer loop and any number of holes. Each loop runs
+    /// counterclockwise seen from outside the solid, holes clockwise.
+    fn face(&mut self, outer: &[OrientedEdge], holes: &[Vec<OrientedEdge>], surface: usize, same_sense: bool) -> usize {
+        let mut bounds = vec![self.bound(outer, "FACE_OUTER_BOUND")];
+        for hole in holes {
+            bounds.push(self.bound(hole, "FACE_BOUND"));
+        }
+        self.add(&format!("ADVANCED_FACE('',({}),#{},{})", refs(&bounds), surface, logical(same_sense)))
+    }
+
+    fn bound(&mut self, edges: &[OrientedEdge], kind: &str) -> usize {
+        let oriented: Vec<usize> = edges
+            .iter()
+            .map(|&(edge, forward)| self.add(&format!("ORIENTED_EDGE('',*,*,#{},{})", edge, logical(forward))))
+            .collect();
+        let edge_loop = self.add(&format!("EDGE_LOOP('',({}))", refs(&oriented)));
+        self.add(&format!("{}('',#{},.T.)", kind, edge_loop))
+    }
+
+    /// The plate as a MANIFOLD_SOLID_BREP, in local coordinates: sketch X and Y,
+    /// with the thickness along +Z.
+    fn solid(&mut self, plate: &ActuatorPlate) -> usize {
+        let half_width = plate.bracket_width.to_mm() / 2.0;
+        let half_height = plate.bracket_height.to_mm() / 2.0;
+        let thickness = plate.plate_thickness.to_mm();
+
+        // Corners counterclockwise seen from +Z, on the bottom (z = 0) and top
+        let corners = [
+            [-half_width, -half_height],
+            [half_width, -half_height],
+            [half_width, half_height],
+            [-half_width, half_height],
+        ];
+        let bottom: Vec<(usize, Vec3)> = corners
+            .iter()
+            .map(|&[x, y]| (self.vertex([x, y, 0.0]), [x, y, 0.0]))
+            .collect();
+        let top: Vec<(usize, Vec3)> = corners
+            .iter()
+            .map(|&[x, y]| (self.vertex([x, y, thickness]), [x, y, thickness]))
+            .collect();
+        let bottom_edges: Vec<usize> = (0..4).map(|i| self.line_edge(bottom[i], bottom[(i + 1) % 4])).collect();
+        let top_edges: Vec<usize> = (0..4).map(|i| self.line_edge(top[i], top[(i + 1) % 4])).collect();
+        let vertical_edges: Vec<usize> = (0..4).map(|i| self.line_edge(bottom[i], top[i])).collect();
+
+        let mut faces = Vec::new();
+
+        // Each hole: a circle on both caps joined by a seam line on the +X side
+        let mut bottom_holes = Vec::new();
+        let mut top_holes = Vec::new();
+        for hole in holes(plate) {
+            let radius = hole.diameter / 2.0;
+            let low = [hole.x + radius, hole.y, 0.0];
+            let high = [hole.x + radius, hole.y, thickness];
+            let low = (self.vertex(low), low);
+            let high = (self.vertex(high), high);
+            let low_circle = self.circle_edge(low.0, [hole.x, hole.y, 0.0], radius);
+            let high_circle = self.circle_edge(high.0, [hole.x, hole.y, thickness], radius);
+            let seam = self.line_edge(low, high);
+
+            // The wall faces into the hole, against the cylinder's outward normal
+            let axis = self.placement([hole.x, hole.y, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]);
+            let cylinder = self.add(&format!("CYLINDRICAL_SURFACE('',#{},{})", axis, real(radius)));
+            let wall = [(high_circle, true), (seam, false), (low_circle, false), (seam, true)];
+            faces.push(self.face(&wall, &[], cylinder, false));
+
+            bottom_holes.push(vec![(low_circle, true)]);
+            top_holes.push(vec![(high_circle, false)]);
+        }
+
+        // Bottom cap faces -Z, so its outline runs clockwise seen from +Z
+        let plane = self.placement([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]);
+        let surface = self.add(&format!("PLANE('',#{})", plane));
+        let outline = [0, 3, 2, 1].map(|i| (bottom_edges[i], false));
+        faces.push(self.face(&outline, &bottom_holes, surface, false));
+
+        let plane = self.placement([0.0, 0.0, thickness], [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]);
+        let surface = self.add(&format!("PLANE('',#{})", plane));
+        let outline = [0, 1, 2, 3].map(|i| (top_edges[i], true));
+        faces.push(self.face(&outline, &top_holes, surface, true));
+
+        // Sides, each facing away from the center
+        for i in 0..4 {
+            let next = (i + 1) % 4;
+            let (from, to) = (bottom[i].1, bottom[next].1);
+            let length = ((to[0] - from[0]).powi(2) + (to[1] - from[1]).powi(2)).sqrt();
+            let along = [(to[0] - from[0]) / length, (to[1] - from[1]) / length, 0.0];
+            let outward = [along[1], -along[0], 0.0];
+            let plane = self.placement(from, outward, along);
+            let surface = self.add(&format!("PLANE('',#{})", plane));
+            let outline = [
+                (bottom_edges[i], true),
+                (vertical_edges[next], true),
+                (top_edges[i], false),
+                (vertical_edges[i], false),
+            ];
+            faces.push(self.face(&outline, &[], surface, true));
+        }
+
+        let shell = self.add(&format!("CLOSED_SHELL('',({}))", refs(&faces)));
+        self.add(&format!("MANIFOLD_SOLID_BREP('Actuator plate',#{})", shell))
+    }
+
+    /// The product, units, and shape representation around the solid that AP214
+    /// readers expect.
+    fn product(&mut self, brep: usize) {
+        let application = self.add("APPLICATION_CONTEXT('automotive_design')");
+        self.add(&format!(
+            "APPLICATION_PROTOCOL_DEFINITION('international standard','automotive_design',2000,#{})",
+            application
+        ));
+        let product_context = self.add(&format!("PRODUCT_CONTEXT('',#{},'mechanical')", application));
+        let product = self.add(&format!("PRODUCT('plate','Actuator plate','',(#{}))", product_context));
+        self.add(&format!("PRODUCT_RELATED_PRODUCT_CATEGORY('part',$,(#{}))", product));
+        let formation = self.add(&format!("PRODUCT_DEFINITION_FORMATION('','',#{})", product));
+        let definition_context = self.add(&format!(
+            "PRODUCT_DEFINITION_CONTEXT('part definition',#{},'design')",
+            application
+        ));
+        let definition = self.add(&format!("PRODUCT_DEFINITION('design','',#{},#{})", formation, definition_context));
+        let shape = self.add(&format!("PRODUCT_DEFINITION_SHAPE('','',#{})", definition));
+
+        let millimeter = self.add("( LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.) )");
+        let radian = self.add("( NAMED_UNIT(*) PLANE_ANGLE_UNIT() SI_UNIT($,.RADIAN.) )");
+        let steradian = self.add("( NAMED_UNIT(*) SI_UNIT($,.STERADIAN.) SOLID_ANGLE_UNIT() )");
+        let uncertainty = self.add(&format!(
+            "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E-07),#{},'distance_accuracy_value','confusion accuracy')",
+            millimeter
+        ));
+        let context = self.add(&format!(
+            "( GEOMETRIC_REPRESENTATION_CONTEXT(3) GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{})) \
+             GLOBAL_UNIT_ASSIGNED_CONTEXT((#{},#{},#{})) REPRESENTATION_CONTEXT('','3D') )",
+            uncertainty, millimeter, radian, steradian
+        ));
+
+        // The world frame, which `to_world` has already mapped the solid into
+        let origin = self.add("CARTESIAN_POINT('',(0.,0.,0.))");
+        let z = self.add("DIRECTION('',(0.,0.,1.))");
+        let x = self.add("DIRECTION('',(1.,0.,0.))");
+        let frame = self.add(&format!("AXIS2_PLACEMENT_3D('',#{},#{},#{})", origin, z, x));
+        let representation = self.add(&format!(
+            "ADVANCED_BREP_SHAPE_REPRESENTATION('',(#{},#{}),#{})",
+            frame, brep, context
+        ));
+        self.add(&format!("SHAPE_DEFINITION_REPRESENTATION(#{},#{})", shape, representation));
+    }
+}
+
+/// Map local coordinates (sketch X and Y, thickness along Z) onto `plate.kcl`'s
+/// XZ sketch plane, whose normal is -Y.
+fn to_world([x, y, z]: Vec3) -> Vec3 {
+    [x, -z, y]
+}
+
+fn triple([x, y, z]: Vec3) -> String {
+    format!("({},{},{})", real(x), real(y), real(z))
+}
+
+/// Format a STEP REAL, which always has a decimal point. Rounds away floating
+/// point noise so e.g. cos(90°) is written as 0.
+fn real(value: f64) -> String {
+    let value = (value * 1e9).round() / 1e9;
+    // Avoid writing negative zero
+    let value = if value == 0.0 { 0.0 } else { value };
+    let text = value.to_string();
+    if text.contains('.') {
+        text
+    } else {
+        text + "."
+    }
+}
+
+fn logical(value: bool) -> &'static str {
+    if value {
+        ".T."
+    } else {
+        ".F."
+    }
+}
+
+fn refs(ids: &[usize]) -> String {
+    ids.iter().map(|id| format!("#{}", id)).collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn count(step: &str, entity: &str) -> usize {
+        step.matches(&format!("={}(", entity)).count()
+    }
+
+    #[test]
+    fn test_default_plate_topology() {
+        let plate = ActuatorPlate::default();
+        let step = plate_step(&plate);
+        let hole_count = 4 + plate.pin_count.get() as usize;
+
+        assert!(step.starts_with("ISO-10303-21;\nHEADER;"));
+        assert!(step.ends_with("END-ISO-10303-21;\n"));
+        assert!(step.contains("AUTOMOTIVE_DESIGN"));
+        assert_eq!(count(&step, "MANIFOLD_SOLID_BREP"), 1);
+        // Two caps, four sides, and one wall per hole
+        assert_eq!(count(&step, "ADVANCED_FACE"), 6 + hole_count);
+        assert_eq!(count(&step, "CYLINDRICAL_SURFACE"), hole_count);
+        assert_eq!(count(&step, "PLANE"), 6);
+        // Box edges, plus two circles and a seam per hole
+        assert_eq!(count(&step, "EDGE_CURVE"), 12 + 3 * hole_count);
+        assert_eq!(count(&step, "VERTEX_POINT"), 8 + 2 * hole_count);
+    }
+
+    #[test]
+    fn test_every_edge_is_used_once_in_each_direction() {
+        // A closed shell is watertight when each edge bounds two faces, once each way
+        let step = plate_step(&ActuatorPlate::default());
+        let mut uses: HashMap<&str, (u32, u32)> = HashMap::new();
+        for line in step.lines().filter(|line| line.contains("=ORIENTED_EDGE(")) {
+            let fields: Vec<&str> = line.trim_end_matches(");").split(',').collect();
+            let entry = uses.entry(fields[3]).or_default();
+            match fields[4] {
+                ".T." => entry.0 += 1,
+                ".F." => entry.1 += 1,
+                other => panic!("bad orientation {}", other),
+            }
+        }
+        assert_eq!(uses.len(), count(&step, "EDGE_CURVE"));
+        assert!(uses.values().all(|&counts| counts == (1, 1)), "{:?}", uses);
+    }
+
+    #[test]
+    fn test_references_point_to_earlier_entities() {
+        let step = plate_step(&ActuatorPlate::default());
+        let data: Vec<&str> = step.lines().filter(|line| line.starts_with('#')).collect();
+        for (index, line) in data.iter().enumerate() {
+            let id = index + 1;
+            assert!(line.starts_with(&format!("#{}=", id)), "{}", line);
+            for reference in line[1..].split('#').skip(1) {
+                let target: usize = reference
+                    .split(|c: char| !c.is_ascii_digit())
+                    .next()
+                    .unwrap()
+                    .parse()
+                    .unwrap();
+                assert!(target < id, "{} refers forward to #{}", line, target);
+            }
+        }
+    }
+
+    #[test]
+    fn test_holes_match_plate_kcl_layout() {
+        let plate = ActuatorPlate::default();
+        let holes = holes(&plate);
+
+        assert_eq!(holes.len(), 4 + plate.pin_count.get() as usize);
+        assert_eq!(holes[3].x, plate.bracket_width.to_mm() / 2.0 * 0.9);
+        assert_eq!(holes[3].diameter, plate.bolt_size.clearance_hole_diameter_mm() as f64);
+        assert_eq!((holes[4].x, holes[4].y), (PIN_CIRCLE_RADIUS_MM, 0.0));
+
+        // The plate lies in XZ and is extruded along -Y
+        let step = plate_step(&plate);
+        let thickness = real(-plate.plate_thickness.to_mm());
+        assert!(step.contains(&format!("CARTESIAN_POINT('',(-150.,{},-200.))", thickness)));
+    }
+
+    #[test]
+    fn test_dual_pin_holes_sit_on_the_centerline() {
+        let plate = ActuatorPlate {
+            pin_count: domain::Count::of(2),
+            family: PlateFamily::DualPin { pin_spacing: domain::Length::from_mm(80) },
+            ..ActuatorPlate::default()
+        };
+        let holes = holes(&plate);
+        assert_eq!((holes[4].x, holes[4].y), (-40.0, 0.0));
+        assert_eq!((holes[5].x, holes[5].y), (40.0, 0.0));
+        assert!(supports(&plate));
+
+        let slotted = ActuatorPlate {
+            family: PlateFamily::Slotted { slot_length: domain::Length::from_mm(12) },
+            ..ActuatorPlate::default()
+        };
+        assert!(!supports(&slotted));
+    }
+
+    #[test]
+    fn test_tolerances_go_in_the_header() {
+        let mut plate = ActuatorPlate::default();
+        assert!(plate_step(&plate).contains("FILE_DESCRIPTION(('Actuator plate'),'2;1');"));
+
+        plate.tolerances.pin_diameter = Some("H7/g6".parse().unwrap());
+        plate.tolerances.plate_thickness = Some("+-0.1".parse().unwrap());
+        let description = "FILE_DESCRIPTION(('Actuator plate','TOLERANCE pin_diameter 10 H7/g6',\
+                           'TOLERANCE plate_thickness 8 \\X2\\00B1\\X0\\0.1'),'2;1');";
+        assert!(plate_step(&plate).contains(description));
+
+        // Another exporter's description is replaced, the rest of the file kept
+        let exported = "ISO-10303-21;\nHEADER;\nFILE_DESCRIPTION((''),'2;1');\nFILE_NAME('a.step');\nENDSEC;\nDATA;\nENDSEC;\n";
+        let annotated = annotate(exported, &plate);
+        assert_eq!(annotated, exported.replace("FILE_DESCRIPTION((''),'2;1');", description));
+        assert_eq!(annotate(exported, &ActuatorPlate::default()), exported);
+
+        let bare = "ISO-10303-21;\nHEADER;\nFILE_NAME('mock.step');\nENDSEC;\n";
+        assert!(annotate(bare, &plate).starts_with(&format!("ISO-10303-21;\nHEADER;\n{}\nFILE_NAME", description)));
+    }
+
+    #[test]
+    fn test_step_string_escapes() {
+        assert_eq!(step_string("it's"), "'it''s'");
+        assert_eq!(step_string("a\\b"), "'a\\\\b'");
+        assert_eq!(step_string("\u{B1}1"), "'\\X2\\00B1\\X0\\1'");
+    }
+
+    #[test]
+    fn test_real_formatting() {
+        assert_eq!(real(150.0), "150.");
+        assert_eq!(real(-0.0), "0.");
+        assert_eq!(real(2.5), "2.5");
+        assert_eq!(real(50.0 * std::f64::consts::FRAC_PI_2.cos()), "0.");
+    }
+}
@@ -0,0 +1,265 @@
+//! Traceability metadata in STEP headers, so a file found on a shared drive can be
+//! traced back to the configuration that produced it.
+//!
+//! [`embed`] rewrites the header of any STEP file, ours or the engine's:
+//!
+//! - FILE_DESCRIPTION keeps the part name and tolerances (see [`crate::annotate`])
+//!   and gains a `PARAMETERS <plate JSON>` and a `CACHE_KEY <key>` entry.
+//! - FILE_NAME gets the generation time as its time_stamp, `platerator` as its
+//!   author, and `platerator <version>` as its preprocessor_version. The name and
+//!   originating_system are kept, so fallback files still say
+//!   [`crate::ORIGINATING_SYSTEM`].
+//!
+//! [`read`] gets the metadata back out.
+
+use domain::ActuatorPlate;
+
+use crate::{file_description, step_string};
+
+/// FILE_NAME's author.
+pub const AUTHOR: &str = "platerator";
+
+/// FILE_NAME's preprocessor_version: this release of platerator.
+pub const PREPROCESSOR_VERSION: &str = concat!("platerator ", env!("CARGO_PKG_VERSION"));
+
+const PARAMETERS: &str = "PARAMETERS ";
+const CACHE_KEY: &str = "CACHE_KEY ";
+
+/// What a STEP file says about where it came from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepMetadata {
+    /// The plate's parameters as JSON, the same shape the API accepts
+    pub parameters: String,
+    /// The plate's cache key, which names its cached and saved files
+    pub cache_key: String,
+    /// ISO 8601 time the file was generated
+    pub generated_at: String,
+    /// The platerator release that generated it, e.g. `platerator 0.1.0`
+    pub generator: String,
+}
+
+impl StepMetadata {
+    /// Metadata for `plate`, generated at `generated_at` by this release.
+    pub fn new(plate: &ActuatorPlate, cache_key: &str, generated_at: &str) -> Self {
+        Self {
+            parameters: serde_json::to_string(plate).expect("plates serialize"),
+            cache_key: cache_key.to_string(),
+            generated_at: generated_at.to_string(),
+            generator: PREPROCESSOR_VERSION.to_string(),
+        }
+    }
+
+    /// The plate the parameters describe, if this release can still read them.
+    pub fn plate(&self) -> Option<ActuatorPlate> {
+        serde_json::from_str(&self.parameters).ok()
+    }
+}
+
+/// Write `plate`'s tolerances and `meta` into the header of `step`. A missing
+/// FILE_DESCRIPTION or FILE_NAME is added; files without a header come back
+/// unchanged.
+pub fn embed(step: &str, plate: &ActuatorPlate, meta: &StepMetadata) -> String {
+    let Some(header) = header(step) else {
+        return step.to_string();
+    };
+    let mut description = file_description(plate);
+    let extra = format!(
+        ",{},{}),'2;1');",
+        step_string(&format!("{}{}", PARAMETERS, meta.parameters)),
+        step_string(&format!("{}{}", CACHE_KEY, meta.cache_key))
+    );
+    description.replace_range(description.len() - "),'2;1');".len().., &extra);
+
+    let mut file_name = entity(step, header.clone(), "FILE_NAME")
+        .map(|(_, args)| args)
+        .unwrap_or_else(|| vec!["''".to_string()]);
+    let defaults = ["''", "''", "('')", "('')", "''", "''", "''"];
+    file_name.extend(defaults[file_name.len().min(defaults.len())..].iter().map(|arg| arg.to_string()));
+    file_name[1] = step_string(&meta.generated_at);
+    file_name[2] = format!("({})", step_string(AUTHOR));
+    file_name[4] = step_string(&meta.generator);
+    let file_name = format!("FILE_NAME({});", file_name.join(","));
+
+    let edits = [
+        (entity(step, header.clone(), "FILE_DESCRIPTION").map(|(span, _)| span), description),
+        (entity(step, header.clone(), "FILE_NAME").map(|(span, _)| span), file_name),
+    ];
+    let mut out = step.to_string();
+    let mut present: Vec<_> = edits.iter().filter_map(|(span, text)| span.clone().map(|span| (span, text))).collect();
+    // Replace from the back so earlier spans stay valid
+    present.sort_by_key(|(span, _)| std::cmp::Reverse(span.start));
+    for (span, text) in present {
+        out.replace_range(span, text);
+    }
+    let missing: Vec<&str> = edits.iter().filter(|(span, _)| span.is_none()).map(|(_, text)| text.as_str()).collect();
+    if !missing.is_empty() {
+        out.insert_str(header.start, &format!("\n{}", missing.join("\n")));
+    }
+    out
+}
+
+/// The metadata [`embed`] wrote into `step`, or `None` if it has none.
+pub fn read(step: &str) -> Option<StepMetadata> {
+    let header = header(step)?;
+    let (_, description) = entity(step, header.clone(), "FILE_DESCRIPTION")?;
+    let entries = description.first().map(|list| list_items(list))?;
+    let entry = |prefix: &str| entries.iter().find_map(|entry| entry.strip_prefix(prefix).map(str::to_string));
+    let (_, file_name) = entity(step, header, "FILE_NAME")?;
+    let field = |index: usize| file_name.get(index).and_then(|arg| parse_string(arg)).unwrap_or_default();
+    Some(StepMetadata {
+        parameters: entry(PARAMETERS)?,
+        cache_key: entry(CACHE_KEY)?,
+        generated_at: field(1),
+        generator: field(4),
+    })
+}
+
+/// The span of the header's contents, between `HEADER;` and its `ENDSEC;`.
+fn header(step: &str) -> Option<std::ops::Range<usize>> {
+    let start = step.find("HEADER;")? + "HEADER;".len();
+    let end = step[start..].find("ENDSEC;").map_or(step.len(), |at| start + at);
+    Some(start..end)
+}
+
+/// Find `keyword(...);` in the header: its span, through the semicolon, and its
+/// top-level arguments as written.
+fn entity(step: &str, header: std::ops::Range<usize>, keyword: &str) -> Option<(std::ops::Range<usize>, Vec<String>)> {
+    let body = &step[header.clone()];
+    let mut search = 0;
+    let start = loop {
+        let at = search + body[search..].find(keyword)?;
+        let before = body[..at].chars().next_back();
+        if before.is_none_or(|c| c.is_whitespace() || c == ';') && body[at + keyword.len()..].starts_with('(') {
+            break at;
+        }
+        search = at + keyword.len();
+    };
+    let open = start + keyword.len();
+    let (args, close) = split_args(&body[open..])?;
+    let end = body[open + close..].find(';').map_or(open + close, |at| open + close + at + 1);
+    Some((header.start + start..header.start + end, args))
+}
+
+/// Split `(a,(b,c),'d,e')...` into its top-level arguments, returning them and the
+/// offset just past the closing parenthesis.
+fn split_args(text: &str) -> Option<(Vec<String>, usize)> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quoted = false;
+    for (at, c) in text.char_indices() {
+        match c {
+            // A doubled quote closes and reopens, which leaves it unchanged
+            '\'' => quoted = !quoted,
+            '(' if !quoted => {
+                depth += 1;
+                if depth == 1 {
+                    continue;
+                }
+            }
+            ')' if !quoted => {
+                depth -= 1;
+                if depth == 0 {
+                    args.push(current.trim().to_string());
+                    return Some((args, at + 1));
+                }
+            }
+            ',' if !quoted && depth == 1 => {
+                args.push(std::mem::take(&mut current).trim().to_string());
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    None
+}
+
+/// The strings in a list argument such as `('a','b')`.
+fn list_items(list: &str) -> Vec<String> {
+    split_args(list)
+        .map(|(items, _)| items.iter().filter_map(|item| parse_string(item)).collect())
+        .unwrap_or_default()
+}
+
+/// Decode a STEP string literal written by [`step_string`].
+fn parse_string(literal: &str) -> Option<String> {
+    let inner = literal.strip_prefix('\'')?.strip_suffix('\'')?;
+    let mut text = String::new();
+    let mut rest = inner;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("''") {
+            text.push('\'');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("\\\\") {
+            text.push('\\');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("\\X2\\") {
+            let (hex, after) = after.split_once("\\X0\\")?;
+            let units = (0..hex.len())
+                .step_by(4)
+                .map(|at| hex.get(at..at + 4).and_then(|unit| u16::from_str_radix(unit, 16).ok()))
+                .collect::<Option<Vec<u16>>>()?;
+            text.push_str(&String::from_utf16(&units).ok()?);
+            rest = after;
+        } else {
+            text.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{plate_step, ORIGINATING_SYSTEM};
+
+    fn metadata(plate: &ActuatorPlate) -> StepMetadata {
+        StepMetadata::new(plate, "3f2a9c", "2026-10-17T09:30:00Z")
+    }
+
+    #[test]
+    fn test_metadata_round_trips_through_the_header() {
+        let mut plate = ActuatorPlate::default();
+        plate.tolerances.plate_thickness = Some("+-0.1".parse().unwrap());
+        let meta = metadata(&plate);
+
+        let step = embed(&plate_step(&plate), &plate, &meta);
+        assert!(step.contains("'TOLERANCE plate_thickness 8 \\X2\\00B1\\X0\\0.1','PARAMETERS {"), "{}", step);
+        assert!(step.contains(&format!(
+            "FILE_NAME('output.step','2026-10-17T09:30:00Z',('platerator'),(''),'{}','{}','');",
+            PREPROCESSOR_VERSION, ORIGINATING_SYSTEM
+        )));
+        assert_eq!(read(&step), Some(meta.clone()));
+        assert_eq!(meta.plate(), Some(plate));
+
+        // Embedding again replaces rather than repeats
+        assert_eq!(embed(&step, &plate, &meta), step);
+        assert_eq!(read(&plate_step(&plate)), None);
+    }
+
+    #[test]
+    fn test_sparse_headers_are_filled_in() {
+        let plate = ActuatorPlate::default();
+        let meta = metadata(&plate);
+        let bare = "ISO-10303-21;\nHEADER;\nFILE_NAME('mock.step');\nENDSEC;\nDATA;\nENDSEC;\nEND-ISO-10303-21;\n";
+        let step = embed(bare, &plate, &meta);
+        assert!(step.starts_with("ISO-10303-21;\nHEADER;\nFILE_DESCRIPTION(('Actuator plate','PARAMETERS {"), "{}", step);
+        assert!(step.contains("FILE_NAME('mock.step','2026-10-17T09:30:00Z',('platerator'),(''),"));
+        assert!(step.ends_with("ENDSEC;\nDATA;\nENDSEC;\nEND-ISO-10303-21;\n"));
+        assert_eq!(read(&step), Some(meta.clone()));
+
+        let empty = "ISO-10303-21;\nHEADER;\nENDSEC;\n";
+        assert_eq!(read(&embed(empty, &plate, &meta)), Some(meta.clone()));
+        assert_eq!(embed("DATA;\nENDSEC;\n", &plate, &meta), "DATA;\nENDSEC;\n");
+    }
+
+    #[test]
+    fn test_parse_string_reverses_escapes() {
+        for text in ["it's", "a\\b", "\u{B1}0.1", "(a,b);"] {
+            assert_eq!(parse_string(&step_string(text)).as_deref(), Some(text));
+        }
+        assert_eq!(split_args("('a,b',(c,d),'')x").unwrap(), (vec!["'a,b'".into(), "(c,d)".into(), "''".into()], 16));
+    }
+}
@@ -0,0 +1,545 @@
+//! Validation messages in English, Spanish, and German.
+//!
+//! [`PlateValidationError::message`] formats an error for a [`Locale`]; its
+//! `Display` is the English message. Error codes and field names stay the same in
+//! every locale, so clients match on those and show the message.
+
+use core::fmt;
+use domain::Dimension;
+
+use crate::{PlateValidationError, PlateValidationWarning};
+
+/// A language validation messages are available in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Es, Locale::De];
+
+    /// The primary language subtag, e.g. `es`.
+    pub const fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::De => "de",
+        }
+    }
+
+    /// The locale for a language tag such as `de`, `es-MX`, or `en_GB`, if supported.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.trim().split(['-', '_']).next()?;
+        Locale::ALL
+            .into_iter()
+            .find(|locale| language.eq_ignore_ascii_case(locale.tag()))
+    }
+
+    /// The most preferred supported locale in an `Accept-Language` header, e.g.
+    /// `fr-CH, fr;q=0.9, de;q=0.8` gives German. English when none is supported.
+    pub fn from_accept_language(header: &str) -> Locale {
+        let mut best = None;
+        for range in header.split(',') {
+            let mut parts = range.split(';');
+            let Some(locale) = parts.next().and_then(Locale::from_tag) else {
+                continue;
+            };
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok());
+            match quality {
+                Some(q) if q > 0.0 && best.is_none_or(|(_, best_q)| q > best_q) => best = Some((locale, q)),
+                _ => {}
+            }
+        }
+        best.map_or(Locale::En, |(locale, _)| locale)
+    }
+}
+
+/// A validation error formatted for a locale; see [`PlateValidationError::message`].
+pub struct Message<'a> {
+    error: &'a PlateValidationError,
+    locale: Locale,
+}
+
+impl PlateValidationError {
+    /// The error's message in `locale`.
+    pub fn message(&self, locale: Locale) -> Message<'_> {
+        Message { error: self, locale }
+    }
+}
+
+impl fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.locale {
+            Locale::En => english(self.error, f),
+            Locale::Es => spanish(self.error, f),
+            Locale::De => german(self.error, f),
+        }
+    }
+}
+
+/// A validation warning formatted for a locale; see [`PlateValidationWarning::message`].
+pub struct WarningMessage<'a> {
+    warning: &'a PlateValidationWarning,
+    locale: Locale,
+}
+
+impl PlateValidationWarning {
+    /// The warning's message in `locale`.
+    pub fn message(&self, locale: Locale) -> WarningMessage<'_> {
+        WarningMessage { warning: self, locale }
+    }
+}
+
+impl fmt::Display for WarningMessage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.locale, self.warning) {
+            (Locale::En, PlateValidationWarning::ThinPlate { suggested_thickness }) => write!(
+                f,
+                "Thin plate for the bolt size; consider stock at least {} mm thick",
+                suggested_thickness
+            ),
+            (Locale::Es, PlateValidationWarning::ThinPlate { suggested_thickness }) => write!(
+                f,
+                "Placa delgada para el tamaño del perno; considere material de al menos {} mm de espesor",
+                suggested_thickness
+            ),
+            (Locale::De, PlateValidationWarning::ThinPlate { suggested_thickness }) => write!(
+                f,
+                "Dünne Platte für die Schraubengröße; erwägen Sie Material von mindestens {} mm Dicke",
+                suggested_thickness
+            ),
+        }
+    }
+}
+
+/// How a dimension is named at the start of a message.
+fn dimension_label(dimension: Dimension, locale: Locale) -> &'static str {
+    match (locale, dimension) {
+        (Locale::En, Dimension::BoltSpacing) => "Bolt spacing",
+        (Locale::En, Dimension::BracketHeight) => "Bracket height",
+        (Locale::En, Dimension::BracketWidth) => "Bracket width",
+        (Locale::En, Dimension::PinDiameter) => "Pin diameter",
+        (Locale::En, Dimension::PlateThickness) => "Plate thickness",
+        (Locale::Es, Dimension::BoltSpacing) => "Separación entre pernos",
+        (Locale::Es, Dimension::BracketHeight) => "Altura del soporte",
+        (Locale::Es, Dimension::BracketWidth) => "Ancho del soporte",
+        (Locale::Es, Dimension::PinDiameter) => "Diámetro del pasador",
+        (Locale::Es, Dimension::PlateThickness) => "Espesor de la placa",
+        (Locale::De, Dimension::BoltSpacing) => "Schraubenabstand",
+        (Locale::De, Dimension::BracketHeight) => "Halterungshöhe",
+        (Locale::De, Dimension::BracketWidth) => "Halterungsbreite",
+        (Locale::De, Dimension::PinDiameter) => "Stiftdurchmesser",
+        (Locale::De, Dimension::PlateThickness) => "Plattendicke",
+    }
+}
+
+/// Write a force in Newtons with a thousands separator. Forces never exceed
+/// `MAX_FORCE_PER_PIN`, so one separator is enough.
+fn write_newtons(f: &mut fmt::Formatter<'_>, newtons: u32, separator: char) -> fmt::Result {
+    if newtons >= 1000 {
+        write!(f, "{}{}{:03} N", newtons / 1000, separator, newtons % 1000)
+    } else {
+        write!(f, "{} N", newtons)
+    }
+}
+
+fn english(error: &PlateValidationError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use PlateValidationError::*;
+    let label = |dimension| dimension_label(dimension, Locale::En);
+    match error {
+        BoltSpacingTooSmall => write!(f, "Bolt spacing must be greater than 0"),
+        BoltSpacingTooLarge { max_mm } => write!(f, "Bolt spacing must not exceed {} mm", max_mm),
+        BoltSizeInvalid => write!(
+            f,
+            "Bolt size must be a standard ISO metric size: M3, M4, M5, M6, M8, M10, or M12"
+        ),
+        BracketHeightInvalid => write!(f, "Bracket height must be greater than 0"),
+        BracketHeightTooLarge { max_mm } => write!(f, "Bracket height must not exceed {} mm", max_mm),
+        BracketWidthInvalid => write!(f, "Bracket width must be greater than 0"),
+        BracketWidthTooLarge { max_mm } => write!(f, "Bracket width must not exceed {} mm", max_mm),
+        MaterialInvalid => write!(
+            f,
+            "Material must be one of: aluminum, stainless_steel, carbon_steel, or brass"
+        ),
+        PinDiameterInvalid => write!(f, "Pin diameter must be greater than 0"),
+        PinDiameterTooLarge { max_mm } => write!(f, "Pin diameter must not exceed {} mm", max_mm),
+        PinCountTooSmall => write!(f, "Pin count must be at least 1"),
+        PinCountTooLarge { max } => write!(f, "Pin count must not exceed {}", max),
+        PlateThicknessInvalid => write!(f, "Plate thickness must be greater than 0"),
+        PlateThicknessTooLarge { max_mm } => write!(f, "Plate thickness must not exceed {} mm", max_mm),
+        CounterboreTooNarrow { min_diameter } => write!(
+            f,
+            "Counterbore diameter must be larger than the {} mm screw head",
+            min_diameter
+        ),
+        CounterboreDepthInvalid { plate_thickness } => write!(
+            f,
+            "Counterbore depth must be greater than 0 and less than the {} mm plate thickness",
+            plate_thickness
+        ),
+        CountersinkTooNarrow { min_diameter } => write!(
+            f,
+            "Countersink diameter must be larger than the {} mm screw head",
+            min_diameter
+        ),
+        CountersinkTooWide { max_diameter } => write!(
+            f,
+            "Countersink diameter must be less than {} mm or the cone cuts through the plate",
+            max_diameter
+        ),
+        ThreadSizeMismatch => write!(f, "Tapped hole thread must match the bolt size"),
+        ThreadPitchNonstandard => {
+            write!(f, "Tapped hole thread pitch must be an ISO 261 pitch for the bolt size")
+        }
+        TappedPlateTooThin { min_thickness } => write!(
+            f,
+            "Plate must be at least {} mm thick for three full threads",
+            min_thickness
+        ),
+        ToleranceInvalid { dimension } => write!(
+            f,
+            "{} tolerance must allow some variation, with the upper deviation above the lower",
+            label(*dimension)
+        ),
+        ToleranceTooLoose { dimension } => write!(
+            f,
+            "{} tolerance band must be at most half the nominal size",
+            label(*dimension)
+        ),
+        FitNotAllowed { dimension } => write!(
+            f,
+            "{} takes a ± or deviation tolerance; ISO fits only apply to the pin diameter",
+            label(*dimension)
+        ),
+        PinFitConflictsWithTolerance => write!(f, "A pin fit sets the pin diameter tolerance; give one or the other"),
+        PinFitSizeUnsupported { max_diameter } => {
+            write!(f, "Pin fits only cover pins up to {} mm in diameter", max_diameter)
+        }
+        SlotLengthInvalid => write!(f, "Slot length must be greater than 0"),
+        SlotTooLong { max_length } => write!(
+            f,
+            "Slot length must be less than {} mm or the slots run off the edge of the plate",
+            max_length
+        ),
+        SlotNeedsThroughHoles => write!(f, "Slotted plates take plain through holes only"),
+        DualPinCountInvalid => write!(f, "Dual-pin plates must have exactly 2 pins"),
+        PinSpacingTooSmall { min_spacing } => write!(
+            f,
+            "Pin spacing must be at least {} mm, two pin diameters",
+            min_spacing
+        ),
+        PinSpacingTooLarge { max_spacing } => write!(
+            f,
+            "Pin spacing must not exceed {} mm or the pins are too close to the edge",
+            max_spacing
+        ),
+        ExpectedForceTooSmall => write!(f, "Expected force per pin must be greater than 0"),
+        ExpectedForceTooLarge { max_n } => {
+            write!(f, "Expected force per pin must not exceed ")?;
+            write_newtons(f, *max_n, ',')
+        }
+        PinBearingStressExceeded { design_force_n, allowable_force_n } => write!(
+            f,
+            "Pin bearing stress exceeded: design force {} N exceeds allowable {} N",
+            design_force_n, allowable_force_n
+        ),
+        BoltBearingStressExceeded { force_per_bolt_n, allowable_per_bolt_n } => write!(
+            f,
+            "Bolt bearing stress exceeded: {} N per bolt exceeds allowable {} N",
+            force_per_bolt_n, allowable_per_bolt_n
+        ),
+        PlateBendingStressExceeded => {
+            write!(f, "Plate bending stress exceeded: plate too thin for the applied load")
+        }
+        BoltEdgeDistanceTooSmall { available_mm, required_mm } => write!(
+            f,
+            "Bolt edge distance too small: {} mm available, {} mm required",
+            available_mm, required_mm
+        ),
+        InsufficientPinClearance { bracket_height_mm, required_mm } => write!(
+            f,
+            "Insufficient pin clearance: bracket height {} mm, need at least {} mm",
+            bracket_height_mm, required_mm
+        ),
+    }
+}
+
+fn spanish(error: &PlateValidationError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use PlateValidationError::*;
+    let label = |dimension| dimension_label(dimension, Locale::Es);
+    match error {
+        BoltSpacingTooSmall => write!(f, "La separación entre pernos debe ser mayor que 0"),
+        BoltSpacingTooLarge { max_mm } => {
+            write!(f, "La separación entre pernos no debe superar {} mm", max_mm)
+        }
+        BoltSizeInvalid => write!(
+            f,
+            "El tamaño de perno debe ser una métrica ISO estándar: M3, M4, M5, M6, M8, M10 o M12"
+        ),
+        BracketHeightInvalid => write!(f, "La altura del soporte debe ser mayor que 0"),
+        BracketHeightTooLarge { max_mm } => write!(f, "La altura del soporte no debe superar {} mm", max_mm),
+        BracketWidthInvalid => write!(f, "El ancho del soporte debe ser mayor que 0"),
+        BracketWidthTooLarge { max_mm } => write!(f, "El ancho del soporte no debe superar {} mm", max_mm),
+        MaterialInvalid => write!(
+            f,
+            "El material debe ser uno de: aluminum, stainless_steel, carbon_steel o brass"
+        ),
+        PinDiameterInvalid => write!(f, "El diámetro del pasador debe ser mayor que 0"),
+        PinDiameterTooLarge { max_mm } => {
+            write!(f, "El diámetro del pasador no debe superar {} mm", max_mm)
+        }
+        PinCountTooSmall => write!(f, "Debe haber al menos 1 pasador"),
+        PinCountTooLarge { max } => write!(f, "No puede haber más de {} pasadores", max),
+        PlateThicknessInvalid => write!(f, "El espesor de la placa debe ser mayor que 0"),
+        PlateThicknessTooLarge { max_mm } => {
+            write!(f, "El espesor de la placa no debe superar {} mm", max_mm)
+        }
+        CounterboreTooNarrow { min_diameter } => write!(
+            f,
+            "El diámetro del avellanado cilíndrico debe ser mayor que la cabeza del tornillo de {} mm",
+            min_diameter
+        ),
+        CounterboreDepthInvalid { plate_thickness } => write!(
+            f,
+            "La profundidad del avellanado cilíndrico debe ser mayor que 0 y menor que el espesor de la placa de {} mm",
+            plate_thickness
+        ),
+        CountersinkTooNarrow { min_diameter } => write!(
+            f,
+            "El diámetro del avellanado cónico debe ser mayor que la cabeza del tornillo de {} mm",
+            min_diameter
+        ),
+        CountersinkTooWide { max_diameter } => write!(
+            f,
+            "El diámetro del avellanado cónico debe ser menor que {} mm o el cono atraviesa la placa",
+            max_diameter
+        ),
+        ThreadSizeMismatch => {
+            write!(f, "La rosca del agujero roscado debe coincidir con el tamaño del perno")
+        }
+        ThreadPitchNonstandard => write!(
+            f,
+            "El paso de rosca del agujero roscado debe ser un paso ISO 261 para el tamaño del perno"
+        ),
+        TappedPlateTooThin { min_thickness } => write!(
+            f,
+            "La placa debe tener al menos {} mm de espesor para tres hilos completos",
+            min_thickness
+        ),
+        ToleranceInvalid { dimension } => write!(
+            f,
+            "{}: la tolerancia debe permitir alguna variación, con la desviación superior mayor que la inferior",
+            label(*dimension)
+        ),
+        ToleranceTooLoose { dimension } => write!(
+            f,
+            "{}: el intervalo de tolerancia debe ser como máximo la mitad de la medida nominal",
+            label(*dimension)
+        ),
+        FitNotAllowed { dimension } => write!(
+            f,
+            "{} admite una tolerancia ± o de desviaciones; los ajustes ISO solo se aplican al diámetro del pasador",
+            label(*dimension)
+        ),
+        PinFitConflictsWithTolerance => write!(
+            f,
+            "El ajuste del pasador ya fija la tolerancia de su diámetro; indique solo uno de los dos"
+        ),
+        PinFitSizeUnsupported { max_diameter } => write!(
+            f,
+            "Los ajustes de pasador solo abarcan pasadores de hasta {} mm de diámetro",
+            max_diameter
+        ),
+        SlotLengthInvalid => write!(f, "La longitud de la ranura debe ser mayor que 0"),
+        SlotTooLong { max_length } => write!(
+            f,
+            "La longitud de la ranura debe ser menor que {} mm o las ranuras se salen del borde de la placa",
+            max_length
+        ),
+        SlotNeedsThroughHoles => write!(f, "Las placas ranuradas solo admiten agujeros pasantes simples"),
+        DualPinCountInvalid => write!(f, "Las placas de dos pasadores deben tener exactamente 2 pasadores"),
+        PinSpacingTooSmall { min_spacing } => write!(
+            f,
+            "La separación entre pasadores debe ser de al menos {} mm, dos diámetros de pasador",
+            min_spacing
+        ),
+        PinSpacingTooLarge { max_spacing } => write!(
+            f,
+            "La separación entre pasadores no debe superar {} mm o los pasadores quedan demasiado cerca del borde",
+            max_spacing
+        ),
+        ExpectedForceTooSmall => write!(f, "La fuerza esperada por pasador debe ser mayor que 0"),
+        ExpectedForceTooLarge { max_n } => {
+            write!(f, "La fuerza esperada por pasador no debe superar ")?;
+            write_newtons(f, *max_n, '.')
+        }
+        PinBearingStressExceeded { design_force_n, allowable_force_n } => write!(
+            f,
+            "Tensión de aplastamiento en el pasador excedida: la fuerza de diseño de {} N supera la admisible de {} N",
+            design_force_n, allowable_force_n
+        ),
+        BoltBearingStressExceeded { force_per_bolt_n, allowable_per_bolt_n } => write!(
+            f,
+            "Tensión de aplastamiento en los pernos excedida: {} N por perno supera los {} N admisibles",
+            force_per_bolt_n, allowable_per_bolt_n
+        ),
+        PlateBendingStressExceeded => write!(
+            f,
+            "Tensión de flexión de la placa excedida: la placa es demasiado delgada para la carga aplicada"
+        ),
+        BoltEdgeDistanceTooSmall { available_mm, required_mm } => write!(
+            f,
+            "Distancia al borde de los pernos insuficiente: {} mm disponibles, {} mm necesarios",
+            available_mm, required_mm
+        ),
+        InsufficientPinClearance { bracket_height_mm, required_mm } => write!(
+            f,
+            "Holgura de pasadores insuficiente: altura del soporte de {} mm, se necesitan al menos {} mm",
+            bracket_height_mm, required_mm
+        ),
+    }
+}
+
+fn german(error: &PlateValidationError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use PlateValidationError::*;
+    let label = |dimension| dimension_label(dimension, Locale::De);
+    match error {
+        BoltSpacingTooSmall => write!(f, "Der Schraubenabstand muss größer als 0 sein"),
+        BoltSpacingTooLarge { max_mm } => {
+            write!(f, "Der Schraubenabstand darf {} mm nicht überschreiten", max_mm)
+        }
+        BoltSizeInvalid => write!(
+            f,
+            "Die Schraubengröße muss eine metrische ISO-Normgröße sein: M3, M4, M5, M6, M8, M10 oder M12"
+        ),
+        BracketHeightInvalid => write!(f, "Die Halterungshöhe muss größer als 0 sein"),
+        BracketHeightTooLarge { max_mm } => {
+            write!(f, "Die Halterungshöhe darf {} mm nicht überschreiten", max_mm)
+        }
+        BracketWidthInvalid => write!(f, "Die Halterungsbreite muss größer als 0 sein"),
+        BracketWidthTooLarge { max_mm } => {
+            write!(f, "Die Halterungsbreite darf {} mm nicht überschreiten", max_mm)
+        }
+        MaterialInvalid => write!(
+            f,
+            "Der Werkstoff muss einer der folgenden sein: aluminum, stainless_steel, carbon_steel oder brass"
+        ),
+        PinDiameterInvalid => write!(f, "Der Stiftdurchmesser muss größer als 0 sein"),
+        PinDiameterTooLarge { max_mm } => {
+            write!(f, "Der Stiftdurchmesser darf {} mm nicht überschreiten", max_mm)
+        }
+        PinCountTooSmall => write!(f, "Es muss mindestens 1 Stift vorhanden sein"),
+        PinCountTooLarge { max } => write!(f, "Es dürfen höchstens {} Stifte sein", max),
+        PlateThicknessInvalid => write!(f, "Die Plattendicke muss größer als 0 sein"),
+        PlateThicknessTooLarge { max_mm } => {
+            write!(f, "Die Plattendicke darf {} mm nicht überschreiten", max_mm)
+        }
+        CounterboreTooNarrow { min_diameter } => write!(
+            f,
+            "Der Durchmesser der Flachsenkung muss größer als der {} mm Schraubenkopf sein",
+            min_diameter
+        ),
+        CounterboreDepthInvalid { plate_thickness } => write!(
+            f,
+            "Die Tiefe der Flachsenkung muss größer als 0 und kleiner als die Plattendicke von {} mm sein",
+            plate_thickness
+        ),
+        CountersinkTooNarrow { min_diameter } => write!(
+            f,
+            "Der Durchmesser der Kegelsenkung muss größer als der {} mm Schraubenkopf sein",
+            min_diameter
+        ),
+        CountersinkTooWide { max_diameter } => write!(
+            f,
+            "Der Durchmesser der Kegelsenkung muss kleiner als {} mm sein, sonst durchdringt der Kegel die Platte",
+            max_diameter
+        ),
+        ThreadSizeMismatch => write!(f, "Das Gewinde der Gewindebohrung muss zur Schraubengröße passen"),
+        ThreadPitchNonstandard => write!(
+            f,
+            "Die Gewindesteigung muss eine ISO-261-Steigung für die Schraubengröße sein"
+        ),
+        TappedPlateTooThin { min_thickness } => write!(
+            f,
+            "Die Platte muss für drei volle Gewindegänge mindestens {} mm dick sein",
+            min_thickness
+        ),
+        ToleranceInvalid { dimension } => write!(
+            f,
+            "{}: Die Toleranz muss eine Abweichung zulassen, mit dem oberen Abmaß über dem unteren",
+            label(*dimension)
+        ),
+        ToleranceTooLoose { dimension } => write!(
+            f,
+            "{}: Das Toleranzfeld darf höchstens die Hälfte des Nennmaßes betragen",
+            label(*dimension)
+        ),
+        FitNotAllowed { dimension } => write!(
+            f,
+            "{} erlaubt nur ±- oder Abmaßtoleranzen; ISO-Passungen gelten nur für den Stiftdurchmesser",
+            label(*dimension)
+        ),
+        PinFitConflictsWithTolerance => write!(
+            f,
+            "Die Stiftpassung legt die Toleranz des Stiftdurchmessers bereits fest; bitte nur eines angeben"
+        ),
+        PinFitSizeUnsupported { max_diameter } => write!(
+            f,
+            "Stiftpassungen gibt es nur für Stifte bis {} mm Durchmesser",
+            max_diameter
+        ),
+        SlotLengthInvalid => write!(f, "Die Langlochlänge muss größer als 0 sein"),
+        SlotTooLong { max_length } => write!(
+            f,
+            "Die Langlochlänge muss kleiner als {} mm sein, sonst reichen die Langlöcher über den Plattenrand",
+            max_length
+        ),
+        SlotNeedsThroughHoles => write!(f, "Platten mit Langlöchern erlauben nur einfache Durchgangsbohrungen"),
+        DualPinCountInvalid => write!(f, "Platten mit zwei Stiften müssen genau 2 Stifte haben"),
+        PinSpacingTooSmall { min_spacing } => write!(
+            f,
+            "Der Stiftabstand muss mindestens {} mm betragen, zwei Stiftdurchmesser",
+            min_spacing
+        ),
+        PinSpacingTooLarge { max_spacing } => write!(
+            f,
+            "Der Stiftabstand darf {} mm nicht überschreiten, sonst liegen die Stifte zu nahe am Rand",
+            max_spacing
+        ),
+        ExpectedForceTooSmall => write!(f, "Die erwartete Kraft pro Stift muss größer als 0 sein"),
+        ExpectedForceTooLarge { max_n } => {
+            write!(f, "Die erwartete Kraft pro Stift darf ")?;
+            write_newtons(f, *max_n, '.')?;
+            write!(f, " nicht überschreiten")
+        }
+        PinBearingStressExceeded { design_force_n, allowable_force_n } => write!(
+            f,
+            "Lochleibungsspannung am Stift überschritten: Bemessungskraft {} N übersteigt zulässige {} N",
+            design_force_n, allowable_force_n
+        ),
+        BoltBearingStressExceeded { force_per_bolt_n, allowable_per_bolt_n } => write!(
+            f,
+            "Lochleibungsspannung an den Schrauben überschritten: {} N pro Schraube übersteigt zulässige {} N",
+            force_per_bolt_n, allowable_per_bolt_n
+        ),
+        PlateBendingStressExceeded => write!(
+            f,
+            "Biegespannung der Platte überschritten: Platte zu dünn für die aufgebrachte Last"
+        ),
+        BoltEdgeDistanceTooSmall { available_mm, required_mm } => write!(
+            f,
+            "Randabstand der Schrauben zu gering: {} mm vorhanden, {} mm erforderlich",
+            available_mm, required_mm
+        ),
+        InsufficientPinClearance { bracket_height_mm, required_mm } => write!(
+            f,
+            "Stiftfreiraum unzureichend: Halterungshöhe {} mm, mindestens {} mm erforderlich",
+            bracket_height_mm, required_mm
+        ),
+    }
+}
@@ -1,22 +1,20 @@
-#![no_std]
+#![cfg_attr(not(feature = "openapi"), no_std)]
 extern crate alloc;
 
 use alloc::vec::Vec;
-use domain::ActuatorPlate;
+use domain::{fits, ActuatorPlate, BoltSize, Dimension, HoleSpec, Length, PlateFamily, Tolerance};
 
 // WebAssembly bindings (only compiled for wasm32 target)
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 
-// TODO: move into just-actuator-only file
+pub mod i18n;
+pub use i18n::Locale;
+
+pub mod rules;
+pub use rules::{rules, rules_with_limits, FieldKind, FieldRule};
 
-// TODO: make a trait that works for items besides plates
-// ---this will allow us to accept a Validator trait in other
-// crates, eg crates/parameteric
-// pub trait Validation {
-//     fn is_valid() -> Result<(), ValidationError>;
-// }
-// pub enum ValidationError {}
+// TODO: move into just-actuator-only file
 
 /// Safety factor applied to all force-based calculations.
 /// Design force = SAFETY_FACTOR × expected force.
@@ -25,57 +23,241 @@ const SAFETY_FACTOR: u32 = 2;
 /// Assumed number of mounting bolts in a standard rectangular pattern.
 const ASSUMED_BOLT_COUNT: u32 = 4;
 
-pub fn validate(plate: &ActuatorPlate) -> Result<(), Vec<PlateValidationError>> {
-    let mut errors = Vec::new();
+/// Maximum allowed force per pin (100 kN). Prevents u64 overflow in stress calculations,
+/// so a configured [`ValidationLimits::max_force_per_pin_n`] above this is ignored.
+pub const MAX_FORCE_PER_PIN: u32 = 100_000;
+
+/// Upper bounds on plate parameters, so shops can match the allowed range to their
+/// machine envelope. [`validate`] uses [`ValidationLimits::DEFAULT`]; servers that read
+/// overrides at runtime call [`validate_with_limits`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationLimits {
+    pub max_bolt_spacing_mm: u16,
+    pub max_bracket_height_mm: u16,
+    pub max_bracket_width_mm: u16,
+    pub max_pin_diameter_mm: u16,
+    pub max_plate_thickness_mm: u16,
+    pub max_pin_count: u16,
+    pub max_force_per_pin_n: u32,
+}
 
-    // Phase 1: Basic geometry constraints — collect all failures.
-    // bolt_size and material are validated by the type system.
-    macro_rules! collect {
-        ($result:expr) => {
-            if let Err(e) = $result {
-                errors.push(e);
-            }
-        };
+impl ValidationLimits {
+    pub const DEFAULT: Self = Self {
+        max_bolt_spacing_mm: 1000,
+        max_bracket_height_mm: 1000,
+        max_bracket_width_mm: 1000,
+        max_pin_diameter_mm: 100,
+        max_plate_thickness_mm: 100,
+        max_pin_count: 12,
+        max_force_per_pin_n: MAX_FORCE_PER_PIN,
+    };
+
+    pub fn check_bolt_spacing(&self, value: Length) -> Result<(), PlateValidationError> {
+        check_length(value, self.max_bolt_spacing_mm, PlateValidationError::BoltSpacingTooSmall, |max_mm| {
+            PlateValidationError::BoltSpacingTooLarge { max_mm }
+        })
+    }
+
+    pub fn check_bracket_height(&self, value: Length) -> Result<(), PlateValidationError> {
+        check_length(value, self.max_bracket_height_mm, PlateValidationError::BracketHeightInvalid, |max_mm| {
+            PlateValidationError::BracketHeightTooLarge { max_mm }
+        })
+    }
+
+    pub fn check_bracket_width(&self, value: Length) -> Result<(), PlateValidationError> {
+        check_length(value, self.max_bracket_width_mm, PlateValidationError::BracketWidthInvalid, |max_mm| {
+            PlateValidationError::BracketWidthTooLarge { max_mm }
+        })
+    }
+
+    pub fn check_pin_diameter(&self, value: Length) -> Result<(), PlateValidationError> {
+        check_length(value, self.max_pin_diameter_mm, PlateValidationError::PinDiameterInvalid, |max_mm| {
+            PlateValidationError::PinDiameterTooLarge { max_mm }
+        })
+    }
+
+    pub fn check_plate_thickness(&self, value: Length) -> Result<(), PlateValidationError> {
+        check_length(value, self.max_plate_thickness_mm, PlateValidationError::PlateThicknessInvalid, |max_mm| {
+            PlateValidationError::PlateThicknessTooLarge { max_mm }
+        })
+    }
+
+    pub fn check_pin_count(&self, value: u32) -> Result<(), PlateValidationError> {
+        if value == 0 {
+            return Err(PlateValidationError::PinCountTooSmall);
+        }
+        if value > self.max_pin_count as u32 {
+            return Err(PlateValidationError::PinCountTooLarge { max: self.max_pin_count });
+        }
+        Ok(())
+    }
+
+    pub fn check_expected_force(&self, value: u32) -> Result<(), PlateValidationError> {
+        let max_n = self.max_force_per_pin_n.min(MAX_FORCE_PER_PIN);
+        if value == 0 {
+            return Err(PlateValidationError::ExpectedForceTooSmall);
+        }
+        if value > max_n {
+            return Err(PlateValidationError::ExpectedForceTooLarge { max_n });
+        }
+        Ok(())
     }
-    collect!(validate_bolt_spacing(plate.bolt_spacing.0));
-    collect!(validate_bracket_height(plate.bracket_height.0));
-    collect!(validate_bracket_width(plate.bracket_width.0));
-    collect!(validate_pin_diameter(plate.pin_diameter.0));
-    collect!(validate_pin_count(plate.pin_count));
-    collect!(validate_plate_thickness(plate.plate_thickness.0));
-    collect!(validate_expected_force(plate.expected_force_per_pin.0));
-
-    // Phase 2: Stress analysis — only runs when Phase 1 is clean, since stress
-    // math requires non-zero, valid inputs to avoid divide-by-zero.
-    if errors.is_empty() {
-        collect!(validate_pin_bearing_stress(plate));
-        collect!(validate_bolt_bearing_stress(plate));
-        collect!(validate_plate_bending_stress(plate));
-        collect!(validate_bolt_edge_distance(plate));
-        collect!(validate_pin_clearance(plate));
-    }
-
-    if errors.is_empty() { Ok(()) } else { Err(errors) }
 }
 
-pub fn validate_bolt_spacing(value: u16) -> Result<(), PlateValidationError> {
-    if value == 0 {
-        return Err(PlateValidationError::BoltSpacingTooSmall);
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Lengths must be at least 1 mm (the stress math works in whole millimeters) and
+/// no more than `max_mm`.
+fn check_length(
+    value: Length,
+    max_mm: u16,
+    too_small: PlateValidationError,
+    too_large: fn(u16) -> PlateValidationError,
+) -> Result<(), PlateValidationError> {
+    if value.whole_mm_floor() == 0 {
+        return Err(too_small);
+    }
+    if value > Length::from_mm(max_mm) {
+        return Err(too_large(max_mm));
     }
     Ok(())
 }
 
+/// A validation failure and the field whose check found it.
+#[derive(Debug, PartialEq)]
+pub struct FieldError {
+    /// Form field name, e.g. `boltSpacing`; for checks across fields, the one
+    /// listed first in the check table
+    pub field: &'static str,
+    pub error: PlateValidationError,
+}
+
+/// One entry in a [`Validate`] table: the field it's filed under and the check.
+pub struct Check<T> {
+    pub field: &'static str,
+    pub run: fn(&T, &ValidationLimits) -> Result<(), PlateValidationError>,
+}
+
+/// Something validated by running tables of checks, reporting every failure
+/// rather than stopping at the first.
+pub trait Validate: Sized + 'static {
+    /// Checks on single fields, all run against the limits.
+    const FIELD_CHECKS: &'static [Check<Self>];
+
+    /// Checks that combine fields; they assume in-range inputs (the stress math
+    /// divides by them), so they run only once every field check passes.
+    const CROSS_CHECKS: &'static [Check<Self>];
+
+    /// Every failure from the field checks, or else from the cross checks.
+    fn validate(&self, limits: &ValidationLimits) -> Result<(), Vec<FieldError>> {
+        let run = |checks: &[Check<Self>]| -> Vec<FieldError> {
+            checks
+                .iter()
+                .filter_map(|check| (check.run)(self, limits).err().map(|error| FieldError { field: check.field, error }))
+                .collect()
+        };
+        let mut errors = run(Self::FIELD_CHECKS);
+        if errors.is_empty() {
+            errors = run(Self::CROSS_CHECKS);
+        }
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+}
+
+impl Validate for ActuatorPlate {
+    // bolt_size and material are validated by the type system.
+    const FIELD_CHECKS: &'static [Check<Self>] = &[
+        Check { field: "boltSpacing", run: |plate, limits| limits.check_bolt_spacing(plate.bolt_spacing) },
+        Check { field: "bracketHeight", run: |plate, limits| limits.check_bracket_height(plate.bracket_height) },
+        Check { field: "bracketWidth", run: |plate, limits| limits.check_bracket_width(plate.bracket_width) },
+        Check { field: "pinDiameter", run: |plate, limits| limits.check_pin_diameter(plate.pin_diameter) },
+        Check { field: "pinCount", run: |plate, limits| limits.check_pin_count(plate.pin_count.get()) },
+        Check { field: "plateThickness", run: |plate, limits| limits.check_plate_thickness(plate.plate_thickness) },
+        Check { field: "expectedForce", run: |plate, limits| limits.check_expected_force(plate.expected_force_per_pin.0) },
+        // A zero thickness is already reported, and hole depths are checked against it
+        Check {
+            field: "boltHoles",
+            run: |plate, _| if plate.plate_thickness == Length::ZERO { Ok(()) } else { validate_bolt_holes(plate) },
+        },
+        Check { field: "tolerances", run: |plate, _| validate_tolerances(plate) },
+        Check { field: "pinFit", run: |plate, _| validate_pin_fit(plate) },
+        Check { field: "family", run: |plate, _| validate_family(plate) },
+    ];
+
+    // The integer checks work in whole millimeters, rounding fractional lengths in
+    // whichever direction makes the check stricter (section dimensions down, spans up).
+    const CROSS_CHECKS: &'static [Check<Self>] = &[
+        Check { field: "pinDiameter", run: |plate, _| validate_pin_bearing_stress(plate) },
+        Check { field: "boltSize", run: |plate, _| validate_bolt_bearing_stress(plate) },
+        Check { field: "plateThickness", run: |plate, _| validate_plate_bending_stress(plate) },
+        Check { field: "bracketWidth", run: |plate, _| validate_bolt_edge_distance(plate) },
+        Check { field: "bracketHeight", run: |plate, _| validate_pin_clearance(plate) },
+    ];
+}
+
+pub fn validate(plate: &ActuatorPlate) -> Result<(), Vec<PlateValidationError>> {
+    validate_with_limits(plate, &ValidationLimits::DEFAULT)
+}
+
+pub fn validate_with_limits(
+    plate: &ActuatorPlate,
+    limits: &ValidationLimits,
+) -> Result<(), Vec<PlateValidationError>> {
+    Validate::validate(plate, limits).map_err(|errors| errors.into_iter().map(|e| e.error).collect())
+}
+
+/// Everything validation found: errors make the plate invalid, warnings don't.
+#[derive(Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    pub errors: Vec<PlateValidationError>,
+    pub warnings: Vec<PlateValidationWarning>,
+}
+
+impl ValidationReport {
+    /// Whether the plate can be generated; warnings don't stop it.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+pub fn report(plate: &ActuatorPlate) -> ValidationReport {
+    report_with_limits(plate, &ValidationLimits::DEFAULT)
+}
+
+/// The errors of [`validate_with_limits`] and the plate's [`warnings`].
+pub fn report_with_limits(plate: &ActuatorPlate, limits: &ValidationLimits) -> ValidationReport {
+    ValidationReport {
+        errors: validate_with_limits(plate, limits).err().unwrap_or_default(),
+        warnings: warnings(plate),
+    }
+}
+
+/// Things worth a second look that don't make the plate invalid.
+pub fn warnings(plate: &ActuatorPlate) -> Vec<PlateValidationWarning> {
+    let mut warnings = Vec::new();
+    // Under half the bolt diameter, the bolt heads bear on very little stock. A zero
+    // thickness is an error already.
+    let half_bolt = Length::from_micrometers(plate.bolt_size.nominal_diameter_mm() as u32 * 500);
+    if plate.plate_thickness != Length::ZERO && plate.plate_thickness < half_bolt {
+        warnings.push(PlateValidationWarning::ThinPlate { suggested_thickness: half_bolt });
+    }
+    warnings
+}
+
+pub fn validate_bolt_spacing(value: u16) -> Result<(), PlateValidationError> {
+    ValidationLimits::DEFAULT.check_bolt_spacing(Length::from_mm(value))
+}
+
 /// Validate that a bolt size string is a valid ISO metric size.
 ///
 /// Accepts standard metric bolt designations: "M3", "M4", "M5", "M6", "M8", "M10", "M12"
 /// (case-insensitive).
 pub fn validate_bolt_size(value: &str) -> Result<(), PlateValidationError> {
-    let upper = value.trim();
-    match upper {
-        "M3" | "m3" | "M4" | "m4" | "M5" | "m5" | "M6" | "m6" | "M8" | "m8" | "M10" | "m10"
-        | "M12" | "m12" => Ok(()),
-        _ => Err(PlateValidationError::BoltSizeInvalid),
-    }
+    BoltSize::from_name(value).map(|_| ()).ok_or(PlateValidationError::BoltSizeInvalid)
 }
 
 /// Validate that a material string is a valid material type.
@@ -94,54 +276,27 @@ pub fn validate_material(value: &str) -> Result<(), PlateValidationError> {
 }
 
 pub fn validate_bracket_height(value: u16) -> Result<(), PlateValidationError> {
-    if value == 0 {
-        return Err(PlateValidationError::BracketHeightInvalid);
-    }
-    Ok(())
+    ValidationLimits::DEFAULT.check_bracket_height(Length::from_mm(value))
 }
 
 pub fn validate_bracket_width(value: u16) -> Result<(), PlateValidationError> {
-    if value == 0 {
-        return Err(PlateValidationError::BracketWidthInvalid);
-    }
-    Ok(())
+    ValidationLimits::DEFAULT.check_bracket_width(Length::from_mm(value))
 }
 
 pub fn validate_pin_diameter(value: u16) -> Result<(), PlateValidationError> {
-    if value == 0 {
-        return Err(PlateValidationError::PinDiameterInvalid);
-    }
-    Ok(())
+    ValidationLimits::DEFAULT.check_pin_diameter(Length::from_mm(value))
 }
 
 pub fn validate_pin_count(value: u16) -> Result<(), PlateValidationError> {
-    if value == 0 {
-        return Err(PlateValidationError::PinCountTooSmall);
-    }
-    if value > 12 {
-        return Err(PlateValidationError::PinCountTooLarge);
-    }
-    Ok(())
+    ValidationLimits::DEFAULT.check_pin_count(value.into())
 }
 
 pub fn validate_plate_thickness(value: u16) -> Result<(), PlateValidationError> {
-    if value == 0 {
-        return Err(PlateValidationError::PlateThicknessInvalid);
-    }
-    Ok(())
+    ValidationLimits::DEFAULT.check_plate_thickness(Length::from_mm(value))
 }
 
-/// Maximum allowed force per pin (100 kN). Prevents u64 overflow in stress calculations.
-const MAX_FORCE_PER_PIN: u32 = 100_000;
-
 pub fn validate_expected_force(value: u32) -> Result<(), PlateValidationError> {
-    if value == 0 {
-        return Err(PlateValidationError::ExpectedForceTooSmall);
-    }
-    if value > MAX_FORCE_PER_PIN {
-        return Err(PlateValidationError::ExpectedForceTooLarge);
-    }
-    Ok(())
+    ValidationLimits::DEFAULT.check_expected_force(value)
 }
 
 /// Check that the plate won't crush at pin holes under the design load.
@@ -152,8 +307,8 @@ pub fn validate_expected_force(value: u32) -> Result<(), PlateValidationError> {
 pub fn validate_pin_bearing_stress(plate: &ActuatorPlate) -> Result<(), PlateValidationError> {
     let design_force = (plate.expected_force_per_pin.0 as u64) * (SAFETY_FACTOR as u64);
     let yield_mpa = plate.material.yield_strength_mpa() as u64;
-    let pin_d = plate.pin_diameter.0 as u64;
-    let thickness = plate.plate_thickness.0 as u64;
+    let pin_d = plate.pin_diameter.whole_mm_floor() as u64;
+    let thickness = plate.plate_thickness.whole_mm_floor() as u64;
 
     let allowable = yield_mpa * pin_d * thickness;
 
@@ -171,9 +326,7 @@ pub fn validate_pin_bearing_stress(plate: &ActuatorPlate) -> Result<(), PlateVal
 /// Total force from all pins is distributed across ASSUMED_BOLT_COUNT bolts.
 /// Uses ceiling division for conservative force-per-bolt.
 pub fn validate_bolt_bearing_stress(plate: &ActuatorPlate) -> Result<(), PlateValidationError> {
-    let total_design_force = (plate.expected_force_per_pin.0 as u64)
-        * (SAFETY_FACTOR as u64)
-        * (plate.pin_count as u64);
+    let total_design_force = plate.pin_count.times(plate.expected_force_per_pin.0) * (SAFETY_FACTOR as u64);
 
     // Ceiling division: (total + bolt_count - 1) / bolt_count
     let bolt_count = ASSUMED_BOLT_COUNT as u64;
@@ -181,7 +334,7 @@ pub fn validate_bolt_bearing_stress(plate: &ActuatorPlate) -> Result<(), PlateVa
 
     let yield_mpa = plate.material.yield_strength_mpa() as u64;
     let bolt_d = plate.bolt_size.nominal_diameter_mm() as u64;
-    let thickness = plate.plate_thickness.0 as u64;
+    let thickness = plate.plate_thickness.whole_mm_floor() as u64;
 
     let allowable = yield_mpa * bolt_d * thickness;
 
@@ -200,16 +353,14 @@ pub fn validate_bolt_bearing_stress(plate: &ActuatorPlate) -> Result<(), PlateVa
 /// σ_bending = (3 × F × L) / (2 × w × t²)
 /// Rearranged: 3 × F × L ≤ 2 × σ_yield × w × t²
 pub fn validate_plate_bending_stress(plate: &ActuatorPlate) -> Result<(), PlateValidationError> {
-    let total_design_force = (plate.expected_force_per_pin.0 as u64)
-        * (SAFETY_FACTOR as u64)
-        * (plate.pin_count as u64);
-    let span = plate.bolt_spacing.0 as u64;
+    let total_design_force = plate.pin_count.times(plate.expected_force_per_pin.0) * (SAFETY_FACTOR as u64);
+    let span = plate.bolt_spacing.whole_mm_ceil() as u64;
 
     let lhs: u64 = 3 * total_design_force * span;
 
     let yield_mpa = plate.material.yield_strength_mpa() as u64;
-    let width = plate.bracket_width.0 as u64;
-    let thickness = plate.plate_thickness.0 as u64;
+    let width = plate.bracket_width.whole_mm_floor() as u64;
+    let thickness = plate.plate_thickness.whole_mm_floor() as u64;
 
     let rhs: u64 = 2 * yield_mpa * width * thickness * thickness;
 
@@ -225,8 +376,8 @@ pub fn validate_plate_bending_stress(plate: &ActuatorPlate) -> Result<(), PlateV
 /// Conservative check using nominal bolt diameter (smaller than clearance hole):
 /// (bracket_width - bolt_spacing) ≥ 3 × nominal_bolt_diameter
 pub fn validate_bolt_edge_distance(plate: &ActuatorPlate) -> Result<(), PlateValidationError> {
-    let width = plate.bracket_width.0;
-    let spacing = plate.bolt_spacing.0;
+    let width = plate.bracket_width.whole_mm_floor();
+    let spacing = plate.bolt_spacing.whole_mm_ceil();
 
     if width <= spacing {
         return Err(PlateValidationError::BoltEdgeDistanceTooSmall {
@@ -252,17 +403,156 @@ pub fn validate_bolt_edge_distance(plate: &ActuatorPlate) -> Result<(), PlateVal
 /// Each pin needs 3× its diameter of vertical space (1.5× clearance on each side).
 /// required_height = pin_count × pin_diameter × 3
 pub fn validate_pin_clearance(plate: &ActuatorPlate) -> Result<(), PlateValidationError> {
-    let required = (plate.pin_count as u32) * (plate.pin_diameter.0 as u32) * 3;
+    let required = plate.pin_count.times(plate.pin_diameter.whole_mm_ceil() as u32) * 3;
+    let height = plate.bracket_height.whole_mm_floor();
 
-    if (plate.bracket_height.0 as u32) < required {
+    if (height as u64) < required {
         return Err(PlateValidationError::InsufficientPinClearance {
-            bracket_height_mm: plate.bracket_height.0,
-            required_mm: required as u16,
+            bracket_height_mm: height,
+            required_mm: u16::try_from(required).unwrap_or(u16::MAX),
         });
     }
     Ok(())
 }
 
+/// Check that the bolt hole features fit the bolt and the plate.
+///
+/// - Counterbores must be wider than the socket head, and shallower than the plate.
+/// - Countersinks must be wider than the countersunk head, and the 90° cone
+///   (depth = (diameter − clearance hole) / 2) must end inside the plate.
+/// - Tapped threads must match the bolt size and use an ISO 261 pitch, and the plate
+///   must be thick enough for three full threads.
+pub fn validate_bolt_holes(plate: &ActuatorPlate) -> Result<(), PlateValidationError> {
+    let thickness = plate.plate_thickness;
+    match plate.bolt_holes {
+        HoleSpec::Through => {}
+        HoleSpec::Counterbore { diameter, depth } => {
+            let head = mm(plate.bolt_size.socket_head_diameter_mm());
+            if diameter <= head {
+                return Err(PlateValidationError::CounterboreTooNarrow { min_diameter: head });
+            }
+            if depth == Length::ZERO || depth >= thickness {
+                return Err(PlateValidationError::CounterboreDepthInvalid { plate_thickness: thickness });
+            }
+        }
+        HoleSpec::Countersink { diameter } => {
+            let head = mm(plate.bolt_size.countersunk_head_diameter_mm());
+            if diameter <= head {
+                return Err(PlateValidationError::CountersinkTooNarrow { min_diameter: head });
+            }
+            let clearance = mm(plate.bolt_size.clearance_hole_diameter_mm());
+            let max = clearance.saturating_add(thickness.saturating_mul(2));
+            if diameter >= max {
+                return Err(PlateValidationError::CountersinkTooWide { max_diameter: max });
+            }
+        }
+        HoleSpec::Tapped { thread } => {
+            if thread.size != plate.bolt_size {
+                return Err(PlateValidationError::ThreadSizeMismatch);
+            }
+            if !plate.bolt_size.standard_pitches_um().contains(&thread.pitch_um) {
+                return Err(PlateValidationError::ThreadPitchNonstandard);
+            }
+            let min = Length::from_micrometers(thread.pitch_um as u32 * 3);
+            if thickness < min {
+                return Err(PlateValidationError::TappedPlateTooThin { min_thickness: min });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that each tolerance makes sense for its dimension.
+///
+/// - A ± band must be above zero, and limit deviations need upper above lower.
+/// - The band can be at most half the nominal size, and the smallest allowed
+///   size must stay above zero.
+/// - ISO 286 fits only go on the pin bore.
+pub fn validate_tolerances(plate: &ActuatorPlate) -> Result<(), PlateValidationError> {
+    for (dimension, tolerance) in plate.tolerances.iter() {
+        if let Tolerance::Fit { .. } = tolerance {
+            if dimension != Dimension::PinDiameter {
+                return Err(PlateValidationError::FitNotAllowed { dimension });
+            }
+            continue;
+        }
+        let Some((upper, lower)) = tolerance.deviations_um() else {
+            continue;
+        };
+        if upper <= lower {
+            return Err(PlateValidationError::ToleranceInvalid { dimension });
+        }
+        let nominal = plate.dimension(dimension).micrometers() as i64;
+        let band = upper as i64 - lower as i64;
+        if band * 2 > nominal || nominal + lower as i64 <= 0 {
+            return Err(PlateValidationError::ToleranceTooLoose { dimension });
+        }
+    }
+    Ok(())
+}
+
+/// Check that a pin fit can size the pin bores.
+///
+/// - The fit sets the pin diameter's tolerance, so the plate can't give one too.
+/// - The ISO 286 tables stop at 500 mm.
+pub fn validate_pin_fit(plate: &ActuatorPlate) -> Result<(), PlateValidationError> {
+    if plate.pin_fit.is_none() {
+        return Ok(());
+    }
+    if plate.tolerances.pin_diameter.is_some() {
+        return Err(PlateValidationError::PinFitConflictsWithTolerance);
+    }
+    if plate.pin_diameter > fits::MAX_FIT_DIAMETER {
+        return Err(PlateValidationError::PinFitSizeUnsupported { max_diameter: fits::MAX_FIT_DIAMETER });
+    }
+    Ok(())
+}
+
+/// Check the rules particular to the plate's family.
+///
+/// - Slots must have some length, and must end inside the plate: the bolt holes
+///   sit 5% of the width in from each side, so `slot_length + clearance hole`
+///   must be less than a tenth of the width. Slots are plain through holes.
+/// - Dual-pin plates have exactly two pins. They must be at least two pin
+///   diameters apart, and at least one pin diameter in from each side.
+pub fn validate_family(plate: &ActuatorPlate) -> Result<(), PlateValidationError> {
+    match plate.family {
+        PlateFamily::Standard => {}
+        PlateFamily::Slotted { slot_length } => {
+            if slot_length == Length::ZERO {
+                return Err(PlateValidationError::SlotLengthInvalid);
+            }
+            if plate.bolt_holes != HoleSpec::Through {
+                return Err(PlateValidationError::SlotNeedsThroughHoles);
+            }
+            let hole = mm(plate.bolt_size.clearance_hole_diameter_mm());
+            let max = Length::from_micrometers(plate.bracket_width.micrometers() / 10).saturating_sub(hole);
+            if slot_length >= max {
+                return Err(PlateValidationError::SlotTooLong { max_length: max });
+            }
+        }
+        PlateFamily::DualPin { pin_spacing } => {
+            if plate.pin_count.get() != 2 {
+                return Err(PlateValidationError::DualPinCountInvalid);
+            }
+            let min = plate.pin_diameter.saturating_mul(2);
+            if pin_spacing < min {
+                return Err(PlateValidationError::PinSpacingTooSmall { min_spacing: min });
+            }
+            let max = plate.bracket_width.saturating_sub(plate.pin_diameter.saturating_mul(3));
+            if pin_spacing > max {
+                return Err(PlateValidationError::PinSpacingTooLarge { max_spacing: max });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A catalog dimension in fractional millimeters as a [`Length`].
+fn mm(value: f32) -> Length {
+    Length::from_mm_f64(value as f64).unwrap_or(Length::ZERO)
+}
+
 /// Stress utilization ratios (0.0–1.0+). Values > 1.0 indicate failure.
 pub struct StressUtilization {
     pub pin_bearing: f32,
@@ -275,13 +565,13 @@ pub struct StressUtilization {
 pub fn stress_utilization(plate: &ActuatorPlate) -> StressUtilization {
     let design_force = (plate.expected_force_per_pin.0 as f32) * (SAFETY_FACTOR as f32);
     let yield_mpa = plate.material.yield_strength_mpa() as f32;
-    let pin_d = plate.pin_diameter.0 as f32;
-    let thickness = plate.plate_thickness.0 as f32;
+    let pin_d = plate.pin_diameter.to_mm() as f32;
+    let thickness = plate.plate_thickness.to_mm() as f32;
     let bolt_d = plate.bolt_size.nominal_diameter_mm() as f32;
-    let pin_count = plate.pin_count as f32;
+    let pin_count = plate.pin_count.get() as f32;
     let bolt_count = ASSUMED_BOLT_COUNT as f32;
-    let span = plate.bolt_spacing.0 as f32;
-    let width = plate.bracket_width.0 as f32;
+    let span = plate.bolt_spacing.to_mm() as f32;
+    let width = plate.bracket_width.to_mm() as f32;
 
     let pin_allowable = yield_mpa * pin_d * thickness;
     let pin_bearing = if pin_allowable > 0.0 {
@@ -323,7 +613,7 @@ pub fn stress_utilization(plate: &ActuatorPlate) -> StressUtilization {
 pub fn minimum_thickness_mm(plate: &ActuatorPlate) -> u16 {
     let design_force = (plate.expected_force_per_pin.0 as u64) * (SAFETY_FACTOR as u64);
     let yield_mpa = plate.material.yield_strength_mpa() as u64;
-    let pin_d = plate.pin_diameter.0 as u64;
+    let pin_d = plate.pin_diameter.whole_mm_floor() as u64;
 
     // From bearing: t ≥ design_force / (yield × pin_diameter)
     // Ceiling division
@@ -334,9 +624,9 @@ pub fn minimum_thickness_mm(plate: &ActuatorPlate) -> u16 {
     };
 
     // From bending: t² ≥ (3 × F_total × L) / (2 × yield × w)
-    let total_design_force = design_force * (plate.pin_count as u64);
-    let span = plate.bolt_spacing.0 as u64;
-    let width = plate.bracket_width.0 as u64;
+    let total_design_force = design_force * u64::from(plate.pin_count);
+    let span = plate.bolt_spacing.whole_mm_ceil() as u64;
+    let width = plate.bracket_width.whole_mm_floor() as u64;
 
     let numerator = 3 * total_design_force * span;
     let denominator = 2 * yield_mpa * width;
@@ -363,22 +653,51 @@ pub fn minimum_thickness_mm(plate: &ActuatorPlate) -> u16 {
     if min < 1 { 1 } else { min as u16 }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum PlateValidationError {
     // Basic constraint errors
     BoltSpacingTooSmall,
+    BoltSpacingTooLarge { max_mm: u16 },
     BoltSizeInvalid,
     BracketHeightInvalid,
+    BracketHeightTooLarge { max_mm: u16 },
     BracketWidthInvalid,
+    BracketWidthTooLarge { max_mm: u16 },
     MaterialInvalid,
     PinDiameterInvalid,
+    PinDiameterTooLarge { max_mm: u16 },
     PinCountTooSmall,
-    PinCountTooLarge,
+    PinCountTooLarge { max: u16 },
     PlateThicknessInvalid,
+    PlateThicknessTooLarge { max_mm: u16 },
+
+    // Bolt hole errors
+    CounterboreTooNarrow { min_diameter: Length },
+    CounterboreDepthInvalid { plate_thickness: Length },
+    CountersinkTooNarrow { min_diameter: Length },
+    CountersinkTooWide { max_diameter: Length },
+    ThreadSizeMismatch,
+    ThreadPitchNonstandard,
+    TappedPlateTooThin { min_thickness: Length },
+
+    // Tolerance errors
+    ToleranceInvalid { dimension: Dimension },
+    ToleranceTooLoose { dimension: Dimension },
+    FitNotAllowed { dimension: Dimension },
+    PinFitConflictsWithTolerance,
+    PinFitSizeUnsupported { max_diameter: Length },
+
+    // Plate family errors
+    SlotLengthInvalid,
+    SlotTooLong { max_length: Length },
+    SlotNeedsThroughHoles,
+    DualPinCountInvalid,
+    PinSpacingTooSmall { min_spacing: Length },
+    PinSpacingTooLarge { max_spacing: Length },
 
     // Force/stress errors
     ExpectedForceTooSmall,
-    ExpectedForceTooLarge,
+    ExpectedForceTooLarge { max_n: u32 },
     PinBearingStressExceeded {
         design_force_n: u32,
         allowable_force_n: u32,
@@ -403,15 +722,38 @@ impl PlateValidationError {
     /// Used by the frontend to highlight relevant inputs.
     pub fn related_fields(&self) -> &'static [&'static str] {
         match self {
-            Self::BoltSpacingTooSmall => &["boltSpacing"],
+            Self::BoltSpacingTooSmall | Self::BoltSpacingTooLarge { .. } => &["boltSpacing"],
             Self::BoltSizeInvalid => &["boltSize"],
-            Self::BracketHeightInvalid => &["bracketHeight"],
-            Self::BracketWidthInvalid => &["bracketWidth"],
+            Self::BracketHeightInvalid | Self::BracketHeightTooLarge { .. } => &["bracketHeight"],
+            Self::BracketWidthInvalid | Self::BracketWidthTooLarge { .. } => &["bracketWidth"],
             Self::MaterialInvalid => &["material"],
-            Self::PinDiameterInvalid => &["pinDiameter"],
-            Self::PinCountTooSmall | Self::PinCountTooLarge => &["pinCount"],
-            Self::PlateThicknessInvalid => &["plateThickness"],
-            Self::ExpectedForceTooSmall | Self::ExpectedForceTooLarge => &["expectedForce"],
+            Self::PinDiameterInvalid | Self::PinDiameterTooLarge { .. } => &["pinDiameter"],
+            Self::PinCountTooSmall | Self::PinCountTooLarge { .. } => &["pinCount"],
+            Self::PlateThicknessInvalid | Self::PlateThicknessTooLarge { .. } => &["plateThickness"],
+            Self::ExpectedForceTooSmall | Self::ExpectedForceTooLarge { .. } => &["expectedForce"],
+            Self::CounterboreTooNarrow { .. }
+            | Self::CountersinkTooNarrow { .. }
+            | Self::ThreadSizeMismatch
+            | Self::ThreadPitchNonstandard => &["boltHoles", "boltSize"],
+            Self::CounterboreDepthInvalid { .. }
+            | Self::CountersinkTooWide { .. }
+            | Self::TappedPlateTooThin { .. } => &["boltHoles", "plateThickness"],
+            Self::ToleranceInvalid { dimension }
+            | Self::ToleranceTooLoose { dimension }
+            | Self::FitNotAllowed { dimension } => match dimension {
+                Dimension::BoltSpacing => &["boltSpacing"],
+                Dimension::BracketHeight => &["bracketHeight"],
+                Dimension::BracketWidth => &["bracketWidth"],
+                Dimension::PinDiameter => &["pinDiameter"],
+                Dimension::PlateThickness => &["plateThickness"],
+            },
+            Self::PinFitConflictsWithTolerance | Self::PinFitSizeUnsupported { .. } => &["pinFit", "pinDiameter"],
+            Self::SlotLengthInvalid => &["slotLength"],
+            Self::SlotTooLong { .. } => &["slotLength", "bracketWidth", "boltSize"],
+            Self::SlotNeedsThroughHoles => &["family", "boltHoles"],
+            Self::DualPinCountInvalid => &["family", "pinCount"],
+            Self::PinSpacingTooSmall { .. } => &["pinSpacing", "pinDiameter"],
+            Self::PinSpacingTooLarge { .. } => &["pinSpacing", "bracketWidth", "pinDiameter"],
             Self::PinBearingStressExceeded { .. } => {
                 &["pinDiameter", "plateThickness", "expectedForce"]
             }
@@ -425,72 +767,92 @@ impl PlateValidationError {
             Self::InsufficientPinClearance { .. } => &["bracketHeight", "pinDiameter", "pinCount"],
         }
     }
+
+    /// Stable machine-readable identifier for this error, e.g. `BOLT_SPACING_TOO_SMALL`.
+    /// Unlike the message, API clients can match on it.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::BoltSpacingTooSmall => "BOLT_SPACING_TOO_SMALL",
+            Self::BoltSpacingTooLarge { .. } => "BOLT_SPACING_TOO_LARGE",
+            Self::BoltSizeInvalid => "BOLT_SIZE_INVALID",
+            Self::BracketHeightInvalid => "BRACKET_HEIGHT_INVALID",
+            Self::BracketHeightTooLarge { .. } => "BRACKET_HEIGHT_TOO_LARGE",
+            Self::BracketWidthInvalid => "BRACKET_WIDTH_INVALID",
+            Self::BracketWidthTooLarge { .. } => "BRACKET_WIDTH_TOO_LARGE",
+            Self::MaterialInvalid => "MATERIAL_INVALID",
+            Self::PinDiameterInvalid => "PIN_DIAMETER_INVALID",
+            Self::PinDiameterTooLarge { .. } => "PIN_DIAMETER_TOO_LARGE",
+            Self::PinCountTooSmall => "PIN_COUNT_TOO_SMALL",
+            Self::PinCountTooLarge { .. } => "PIN_COUNT_TOO_LARGE",
+            Self::PlateThicknessInvalid => "PLATE_THICKNESS_INVALID",
+            Self::PlateThicknessTooLarge { .. } => "PLATE_THICKNESS_TOO_LARGE",
+            Self::CounterboreTooNarrow { .. } => "COUNTERBORE_TOO_NARROW",
+            Self::CounterboreDepthInvalid { .. } => "COUNTERBORE_DEPTH_INVALID",
+            Self::CountersinkTooNarrow { .. } => "COUNTERSINK_TOO_NARROW",
+            Self::CountersinkTooWide { .. } => "COUNTERSINK_TOO_WIDE",
+            Self::ThreadSizeMismatch => "THREAD_SIZE_MISMATCH",
+            Self::ThreadPitchNonstandard => "THREAD_PITCH_NONSTANDARD",
+            Self::TappedPlateTooThin { .. } => "TAPPED_PLATE_TOO_THIN",
+            Self::ToleranceInvalid { .. } => "TOLERANCE_INVALID",
+            Self::ToleranceTooLoose { .. } => "TOLERANCE_TOO_LOOSE",
+            Self::FitNotAllowed { .. } => "FIT_NOT_ALLOWED",
+            Self::PinFitConflictsWithTolerance => "PIN_FIT_CONFLICTS_WITH_TOLERANCE",
+            Self::PinFitSizeUnsupported { .. } => "PIN_FIT_SIZE_UNSUPPORTED",
+            Self::SlotLengthInvalid => "SLOT_LENGTH_INVALID",
+            Self::SlotTooLong { .. } => "SLOT_TOO_LONG",
+            Self::SlotNeedsThroughHoles => "SLOT_NEEDS_THROUGH_HOLES",
+            Self::DualPinCountInvalid => "DUAL_PIN_COUNT_INVALID",
+            Self::PinSpacingTooSmall { .. } => "PIN_SPACING_TOO_SMALL",
+            Self::PinSpacingTooLarge { .. } => "PIN_SPACING_TOO_LARGE",
+            Self::ExpectedForceTooSmall => "EXPECTED_FORCE_TOO_SMALL",
+            Self::ExpectedForceTooLarge { .. } => "EXPECTED_FORCE_TOO_LARGE",
+            Self::PinBearingStressExceeded { .. } => "PIN_BEARING_STRESS_EXCEEDED",
+            Self::BoltBearingStressExceeded { .. } => "BOLT_BEARING_STRESS_EXCEEDED",
+            Self::PlateBendingStressExceeded => "PLATE_BENDING_STRESS_EXCEEDED",
+            Self::BoltEdgeDistanceTooSmall { .. } => "BOLT_EDGE_DISTANCE_TOO_SMALL",
+            Self::InsufficientPinClearance { .. } => "INSUFFICIENT_PIN_CLEARANCE",
+        }
+    }
 }
 
+/// The English message; see [`PlateValidationError::message`] for other locales.
 impl core::fmt::Display for PlateValidationError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.message(Locale::En).fmt(f)
+    }
+}
+
+impl core::error::Error for PlateValidationError {}
+
+/// A problem that doesn't make the plate invalid or stop generation.
+#[derive(Debug, PartialEq)]
+pub enum PlateValidationWarning {
+    /// Thinner than half the bolt diameter
+    ThinPlate { suggested_thickness: Length },
+}
+
+impl PlateValidationWarning {
+    /// Form field name(s) the warning is about, as for errors.
+    pub fn related_fields(&self) -> &'static [&'static str] {
         match self {
-            Self::BoltSpacingTooSmall => write!(f, "Bolt spacing must be greater than 0"),
-            Self::BoltSizeInvalid => write!(
-                f,
-                "Bolt size must be a standard ISO metric size: M3, M4, M5, M6, M8, M10, or M12"
-            ),
-            Self::BracketHeightInvalid => write!(f, "Bracket height must be greater than 0"),
-            Self::BracketWidthInvalid => write!(f, "Bracket width must be greater than 0"),
-            Self::MaterialInvalid => write!(
-                f,
-                "Material must be one of: aluminum, stainless_steel, carbon_steel, or brass"
-            ),
-            Self::PinDiameterInvalid => write!(f, "Pin diameter must be greater than 0"),
-            Self::PinCountTooSmall => write!(f, "Pin count must be at least 1"),
-            Self::PinCountTooLarge => write!(f, "Pin count must not exceed 12"),
-            Self::PlateThicknessInvalid => write!(f, "Plate thickness must be greater than 0"),
-            Self::ExpectedForceTooSmall => {
-                write!(f, "Expected force per pin must be greater than 0")
-            }
-            Self::ExpectedForceTooLarge => {
-                write!(f, "Expected force per pin must not exceed 100,000 N")
-            }
-            Self::PinBearingStressExceeded {
-                design_force_n,
-                allowable_force_n,
-            } => write!(
-                f,
-                "Pin bearing stress exceeded: design force {} N exceeds allowable {} N",
-                design_force_n, allowable_force_n
-            ),
-            Self::BoltBearingStressExceeded {
-                force_per_bolt_n,
-                allowable_per_bolt_n,
-            } => write!(
-                f,
-                "Bolt bearing stress exceeded: {} N per bolt exceeds allowable {} N",
-                force_per_bolt_n, allowable_per_bolt_n
-            ),
-            Self::PlateBendingStressExceeded => {
-                write!(f, "Plate bending stress exceeded: plate too thin for the applied load")
-            }
-            Self::BoltEdgeDistanceTooSmall {
-                available_mm,
-                required_mm,
-            } => write!(
-                f,
-                "Bolt edge distance too small: {} mm available, {} mm required",
-                available_mm, required_mm
-            ),
-            Self::InsufficientPinClearance {
-                bracket_height_mm,
-                required_mm,
-            } => write!(
-                f,
-                "Insufficient pin clearance: bracket height {} mm, need at least {} mm",
-                bracket_height_mm, required_mm
-            ),
+            Self::ThinPlate { .. } => &["plateThickness", "boltSize"],
+        }
+    }
+
+    /// Stable machine-readable identifier, e.g. `THIN_PLATE`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ThinPlate { .. } => "THIN_PLATE",
         }
     }
 }
 
-impl core::error::Error for PlateValidationError {}
+/// The English message; see [`PlateValidationWarning::message`] for other locales.
+impl core::fmt::Display for PlateValidationWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.message(Locale::En).fmt(f)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -498,13 +860,70 @@ mod tests {
     use alloc::string::ToString;
 
     use super::*;
-    use domain::{BoltSize, Material, Millimeters, Newtons};
+    use domain::{BoltSize, Count, HoleSpec, IsoThread, Length, Material, Newtons, PlateFamily, Tolerances};
 
     #[test]
     fn test_validate_bolt_spacing_valid() {
         assert!(validate_bolt_spacing(60).is_ok());
         assert!(validate_bolt_spacing(1).is_ok());
-        assert!(validate_bolt_spacing(u16::MAX).is_ok());
+        assert!(validate_bolt_spacing(1000).is_ok());
+    }
+
+    #[test]
+    fn test_validate_bolt_spacing_too_large() {
+        assert!(matches!(
+            validate_bolt_spacing(1001).unwrap_err(),
+            PlateValidationError::BoltSpacingTooLarge { max_mm: 1000 }
+        ));
+    }
+
+    #[test]
+    fn test_default_limits_reject_oversized_plate() {
+        let mut plate = valid_plate();
+        plate.bracket_height = Length::from_mm(1001);
+        plate.plate_thickness = Length::from_mm(101);
+        let errors = validate(&plate).unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, PlateValidationError::BracketHeightTooLarge { max_mm: 1000 })));
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, PlateValidationError::PlateThicknessTooLarge { max_mm: 100 })));
+    }
+
+    #[test]
+    fn test_limits_reject_fractional_length_just_over_max() {
+        let mut plate = valid_plate();
+        plate.plate_thickness = Length::from_micrometers(100_001);
+        assert!(validate(&plate)
+            .unwrap_err()
+            .iter()
+            .any(|e| matches!(e, PlateValidationError::PlateThicknessTooLarge { .. })));
+    }
+
+    #[test]
+    fn test_custom_limits_widen_and_narrow_range() {
+        let mut plate = valid_plate();
+        plate.bracket_height = Length::from_mm(1500);
+        assert!(validate(&plate).is_err());
+
+        let wide = ValidationLimits { max_bracket_height_mm: 2000, ..ValidationLimits::DEFAULT };
+        assert!(validate_with_limits(&plate, &wide).is_ok());
+
+        let narrow = ValidationLimits { max_pin_count: 4, ..ValidationLimits::DEFAULT };
+        let plate = ActuatorPlate { pin_count: Count::of(6), ..valid_plate() };
+        let errors = validate_with_limits(&plate, &narrow).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "Pin count must not exceed 4");
+    }
+
+    #[test]
+    fn test_configured_force_limit_is_capped() {
+        let limits = ValidationLimits { max_force_per_pin_n: u32::MAX, ..ValidationLimits::DEFAULT };
+        assert!(matches!(
+            limits.check_expected_force(MAX_FORCE_PER_PIN + 1).unwrap_err(),
+            PlateValidationError::ExpectedForceTooLarge { max_n: MAX_FORCE_PER_PIN }
+        ));
     }
 
     #[test]
@@ -616,7 +1035,7 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
-            PlateValidationError::PinCountTooLarge
+            PlateValidationError::PinCountTooLarge { .. }
         ));
     }
 
@@ -638,15 +1057,19 @@ mod tests {
     // Helper: a structurally sound plate that passes all checks
     fn valid_plate() -> ActuatorPlate {
         ActuatorPlate {
-            bolt_spacing: Millimeters(60),
+            bolt_spacing: Length::from_mm(60),
             bolt_size: BoltSize::M10,
-            bracket_height: Millimeters(200),
-            bracket_width: Millimeters(100),
+            bracket_height: Length::from_mm(200),
+            bracket_width: Length::from_mm(100),
             material: Material::Aluminum,
-            pin_diameter: Millimeters(10),
-            pin_count: 4,
-            plate_thickness: Millimeters(10),
+            pin_diameter: Length::from_mm(10),
+            pin_count: Count::of(4),
+            plate_thickness: Length::from_mm(10),
             expected_force_per_pin: Newtons(500),
+            bolt_holes: HoleSpec::Through,
+            tolerances: Tolerances::NONE,
+            family: PlateFamily::Standard,
+            pin_fit: None,
         }
     }
 
@@ -658,12 +1081,64 @@ mod tests {
     #[test]
     fn test_validate_full_plate_invalid_bolt_spacing() {
         let mut plate = valid_plate();
-        plate.bolt_spacing = Millimeters(0);
+        plate.bolt_spacing = Length::from_mm(0);
         let result = validate(&plate);
         assert!(result.is_err());
         assert!(result.unwrap_err().iter().any(|e| matches!(e, PlateValidationError::BoltSpacingTooSmall)));
     }
 
+    #[test]
+    fn test_validate_reports_every_field_error() {
+        let mut plate = valid_plate();
+        plate.bolt_spacing = Length::ZERO;
+        plate.pin_diameter = Length::from_mm(150);
+        plate.expected_force_per_pin = Newtons(0);
+        let errors = Validate::validate(&plate, &ValidationLimits::DEFAULT).unwrap_err();
+        assert_eq!(errors, [
+            FieldError { field: "boltSpacing", error: PlateValidationError::BoltSpacingTooSmall },
+            FieldError { field: "pinDiameter", error: PlateValidationError::PinDiameterTooLarge { max_mm: 100 } },
+            FieldError { field: "expectedForce", error: PlateValidationError::ExpectedForceTooSmall },
+        ]);
+    }
+
+    #[test]
+    fn test_cross_checks_wait_for_the_fields() {
+        let mut plate = valid_plate();
+        plate.plate_thickness = Length::from_mm(1);
+        plate.expected_force_per_pin = Newtons(20_000);
+        let fields: Vec<_> = Validate::validate(&plate, &ValidationLimits::DEFAULT).unwrap_err().iter().map(|e| e.field).collect();
+        assert!(fields.contains(&"plateThickness"), "{:?}", fields);
+
+        // With a field out of range, the stress math isn't run at all
+        plate.bracket_width = Length::ZERO;
+        let errors = Validate::validate(&plate, &ValidationLimits::DEFAULT).unwrap_err();
+        assert_eq!(errors, [FieldError { field: "bracketWidth", error: PlateValidationError::BracketWidthInvalid }]);
+    }
+
+    #[test]
+    fn test_thin_plate_is_a_warning_not_an_error() {
+        let mut plate = valid_plate();
+        plate.bolt_size = BoltSize::M5;
+        plate.plate_thickness = Length::from_mm(2);
+        plate.expected_force_per_pin = Newtons(50);
+        let report = report(&plate);
+        assert!(report.is_valid(), "{:?}", report.errors);
+        let suggested_thickness = Length::from_micrometers(2500);
+        assert_eq!(report.warnings, [PlateValidationWarning::ThinPlate { suggested_thickness }]);
+        assert_eq!(report.warnings[0].code(), "THIN_PLATE");
+        assert_eq!(report.warnings[0].to_string(), "Thin plate for the bolt size; consider stock at least 2.5 mm thick");
+        assert!(report.warnings[0].message(Locale::De).to_string().contains("2.5 mm"));
+
+        // Half the bolt diameter is enough, and an invalid plate still gets its warnings
+        plate.plate_thickness = suggested_thickness;
+        assert!(warnings(&plate).is_empty());
+        plate.plate_thickness = Length::from_mm(2);
+        plate.bolt_spacing = Length::ZERO;
+        let report = report_with_limits(&plate, &ValidationLimits::DEFAULT);
+        assert!(!report.is_valid());
+        assert_eq!(report.warnings.len(), 1);
+    }
+
     // --- Force validation ---
 
     #[test]
@@ -700,8 +1175,8 @@ mod tests {
     fn test_pin_bearing_fail_thin_brass() {
         let mut plate = valid_plate();
         plate.material = Material::Brass; // yield 124 MPa
-        plate.pin_diameter = Millimeters(3);
-        plate.plate_thickness = Millimeters(2);
+        plate.pin_diameter = Length::from_mm(3);
+        plate.plate_thickness = Length::from_mm(2);
         plate.expected_force_per_pin = Newtons(500);
         // allowable = 124 * 3 * 2 = 744 N, design = 1000 N → fail
         let result = validate_pin_bearing_stress(&plate);
@@ -723,8 +1198,8 @@ mod tests {
         // Set up so design_force == allowable exactly → should pass
         let mut plate = valid_plate();
         plate.material = Material::Aluminum; // yield 276
-        plate.pin_diameter = Millimeters(10);
-        plate.plate_thickness = Millimeters(10);
+        plate.pin_diameter = Length::from_mm(10);
+        plate.plate_thickness = Length::from_mm(10);
         // allowable = 276 * 10 * 10 = 27,600. design = force * 2
         // So force = 13,800 → design = 27,600 = allowable → pass
         plate.expected_force_per_pin = Newtons(13800);
@@ -735,8 +1210,8 @@ mod tests {
     fn test_pin_bearing_boundary_just_over() {
         let mut plate = valid_plate();
         plate.material = Material::Aluminum;
-        plate.pin_diameter = Millimeters(10);
-        plate.plate_thickness = Millimeters(10);
+        plate.pin_diameter = Length::from_mm(10);
+        plate.plate_thickness = Length::from_mm(10);
         // allowable = 27,600, design = 13,801 * 2 = 27,602 → fail
         plate.expected_force_per_pin = Newtons(13801);
         assert!(validate_pin_bearing_stress(&plate).is_err());
@@ -747,8 +1222,8 @@ mod tests {
         // A plate that would pass at 1× but fails at 2×
         let mut plate = valid_plate();
         plate.material = Material::Brass; // yield 124
-        plate.pin_diameter = Millimeters(5);
-        plate.plate_thickness = Millimeters(3);
+        plate.pin_diameter = Length::from_mm(5);
+        plate.plate_thickness = Length::from_mm(3);
         // allowable = 124 * 5 * 3 = 1,860 N
         // force = 1000 → design = 2000 > 1860 → fail
         // But at 1×: 1000 < 1860 → would pass
@@ -768,8 +1243,8 @@ mod tests {
         let mut plate = valid_plate();
         plate.material = Material::Brass; // yield 124
         plate.bolt_size = BoltSize::M3; // 3mm nominal
-        plate.plate_thickness = Millimeters(2);
-        plate.pin_count = 12;
+        plate.plate_thickness = Length::from_mm(2);
+        plate.pin_count = Count::of(12);
         plate.expected_force_per_pin = Newtons(500);
         // total_design = 500 * 2 * 12 = 12,000
         // per_bolt (ceiling) = ceil(12000/4) = 3,000
@@ -798,10 +1273,10 @@ mod tests {
     #[test]
     fn test_bending_fail_thin_wide_span() {
         let mut plate = valid_plate();
-        plate.bolt_spacing = Millimeters(200); // wide span
-        plate.plate_thickness = Millimeters(2); // very thin
-        plate.bracket_width = Millimeters(250); // must be wider than spacing for edge check
-        plate.pin_count = 10;
+        plate.bolt_spacing = Length::from_mm(200); // wide span
+        plate.plate_thickness = Length::from_mm(2); // very thin
+        plate.bracket_width = Length::from_mm(250); // must be wider than spacing for edge check
+        plate.pin_count = Count::of(10);
         plate.expected_force_per_pin = Newtons(2000);
         // lhs = 3 * (2000*2*10) * 200 = 3 * 40,000 * 200 = 24,000,000
         // rhs = 2 * 276 * 250 * 2 * 2 = 2 * 276 * 250 * 4 = 552,000
@@ -822,8 +1297,8 @@ mod tests {
     #[test]
     fn test_edge_distance_fail_tight() {
         let mut plate = valid_plate();
-        plate.bolt_spacing = Millimeters(90);
-        plate.bracket_width = Millimeters(100);
+        plate.bolt_spacing = Length::from_mm(90);
+        plate.bracket_width = Length::from_mm(100);
         plate.bolt_size = BoltSize::M10;
         // available = 100 - 90 = 10, required = 10 * 3 = 30 → fail
         let result = validate_bolt_edge_distance(&plate);
@@ -843,8 +1318,8 @@ mod tests {
     #[test]
     fn test_edge_distance_fail_spacing_exceeds_width() {
         let mut plate = valid_plate();
-        plate.bolt_spacing = Millimeters(120);
-        plate.bracket_width = Millimeters(100);
+        plate.bolt_spacing = Length::from_mm(120);
+        plate.bracket_width = Length::from_mm(100);
         assert!(matches!(
             validate_bolt_edge_distance(&plate).unwrap_err(),
             PlateValidationError::BoltEdgeDistanceTooSmall { available_mm: 0, .. }
@@ -861,9 +1336,9 @@ mod tests {
     #[test]
     fn test_pin_clearance_fail() {
         let mut plate = valid_plate();
-        plate.bracket_height = Millimeters(50);
-        plate.pin_count = 6;
-        plate.pin_diameter = Millimeters(10);
+        plate.bracket_height = Length::from_mm(50);
+        plate.pin_count = Count::of(6);
+        plate.pin_diameter = Length::from_mm(10);
         // required = 6 * 10 * 3 = 180, available = 50 → fail
         let result = validate_pin_clearance(&plate);
         assert!(result.is_err());
@@ -879,6 +1354,215 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pin_clearance_rounds_fractional_lengths_conservatively() {
+        let mut plate = valid_plate();
+        plate.pin_count = Count::of(6);
+        plate.pin_diameter = Length::from_micrometers(9_500);
+        plate.bracket_height = Length::from_micrometers(180_900);
+        // pin diameter rounds up to 10 (required 180), height rounds down to 180
+        assert!(validate_pin_clearance(&plate).is_ok());
+
+        plate.bracket_height = Length::from_micrometers(179_900);
+        match validate_pin_clearance(&plate) {
+            Err(PlateValidationError::InsufficientPinClearance { bracket_height_mm, required_mm }) => {
+                assert_eq!(bracket_height_mm, 179);
+                assert_eq!(required_mm, 180);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    // --- Bolt hole features ---
+
+    #[test]
+    fn test_counterbore_must_clear_head_and_stay_in_plate() {
+        // M10 socket head is 16 mm; plate is 10 mm thick
+        let mut plate = valid_plate();
+        plate.bolt_holes = HoleSpec::Counterbore { diameter: Length::from_mm(17), depth: Length::from_mm(6) };
+        assert!(validate_bolt_holes(&plate).is_ok());
+
+        plate.bolt_holes = HoleSpec::Counterbore { diameter: Length::from_mm(16), depth: Length::from_mm(6) };
+        assert!(matches!(
+            validate_bolt_holes(&plate),
+            Err(PlateValidationError::CounterboreTooNarrow { min_diameter }) if min_diameter == Length::from_mm(16)
+        ));
+
+        plate.bolt_holes = HoleSpec::Counterbore { diameter: Length::from_mm(17), depth: Length::from_mm(10) };
+        assert!(matches!(
+            validate_bolt_holes(&plate),
+            Err(PlateValidationError::CounterboreDepthInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_countersink_cone_must_end_inside_plate() {
+        // M10: head 22.4 mm, clearance 11 mm, so the cone may not reach 11 + 2 × 10 = 31 mm
+        let mut plate = valid_plate();
+        plate.bolt_holes = HoleSpec::Countersink { diameter: Length::from_mm(23) };
+        assert!(validate_bolt_holes(&plate).is_ok());
+
+        plate.bolt_holes = HoleSpec::Countersink { diameter: Length::from_mm(22) };
+        assert!(matches!(
+            validate_bolt_holes(&plate),
+            Err(PlateValidationError::CountersinkTooNarrow { min_diameter }) if min_diameter == Length::from_micrometers(22_400)
+        ));
+
+        plate.bolt_holes = HoleSpec::Countersink { diameter: Length::from_mm(31) };
+        assert!(matches!(
+            validate_bolt_holes(&plate),
+            Err(PlateValidationError::CountersinkTooWide { max_diameter }) if max_diameter == Length::from_mm(31)
+        ));
+    }
+
+    #[test]
+    fn test_tapped_thread_checked_against_bolt_size() {
+        let mut plate = valid_plate();
+        plate.bolt_holes = HoleSpec::Tapped { thread: IsoThread { size: BoltSize::M10, pitch_um: 1250 } };
+        assert!(validate_bolt_holes(&plate).is_ok());
+
+        plate.bolt_holes = HoleSpec::Tapped { thread: IsoThread::coarse(BoltSize::M8) };
+        assert!(matches!(validate_bolt_holes(&plate), Err(PlateValidationError::ThreadSizeMismatch)));
+
+        plate.bolt_holes = HoleSpec::Tapped { thread: IsoThread { size: BoltSize::M10, pitch_um: 1100 } };
+        assert!(matches!(validate_bolt_holes(&plate), Err(PlateValidationError::ThreadPitchNonstandard)));
+
+        plate.bolt_holes = HoleSpec::Tapped { thread: IsoThread::coarse(BoltSize::M10) };
+        plate.plate_thickness = Length::from_micrometers(4_000);
+        assert!(matches!(
+            validate_bolt_holes(&plate),
+            Err(PlateValidationError::TappedPlateTooThin { min_thickness }) if min_thickness == Length::from_micrometers(4_500)
+        ));
+    }
+
+    #[test]
+    fn test_full_validation_reports_bad_hole_spec() {
+        let plate = ActuatorPlate { bolt_holes: HoleSpec::Tapped { thread: IsoThread::coarse(BoltSize::M6) }, ..valid_plate() };
+        let errors = validate(&plate).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code(), "THREAD_SIZE_MISMATCH");
+        assert_eq!(errors[0].related_fields(), &["boltHoles", "boltSize"]);
+    }
+
+    // --- Tolerances ---
+
+    #[test]
+    fn test_sensible_tolerances_pass() {
+        let plate = ActuatorPlate {
+            tolerances: Tolerances {
+                pin_diameter: Some("H7/g6".parse().unwrap()),
+                plate_thickness: Some("\u{B1}0.1".parse().unwrap()),
+                bracket_width: Some("0/-0.5".parse().unwrap()),
+                ..Tolerances::NONE
+            },
+            ..valid_plate()
+        };
+        assert!(validate(&plate).is_ok());
+    }
+
+    #[test]
+    fn test_tolerance_sanity_checks() {
+        let with = |dimension: &str, tolerance: &str| {
+            let mut plate = valid_plate();
+            let tolerance = Some(tolerance.parse().unwrap());
+            match dimension {
+                "plate_thickness" => plate.tolerances.plate_thickness = tolerance,
+                _ => plate.tolerances.bolt_spacing = tolerance,
+            }
+            validate_tolerances(&plate)
+        };
+        // 10 mm plate
+        assert!(matches!(
+            with("plate_thickness", "+-0"),
+            Err(PlateValidationError::ToleranceInvalid { dimension: Dimension::PlateThickness })
+        ));
+        assert!(matches!(
+            with("plate_thickness", "-0.1/+0.1"),
+            Err(PlateValidationError::ToleranceInvalid { .. })
+        ));
+        assert!(with("plate_thickness", "+-2.5").is_ok());
+        assert!(matches!(
+            with("plate_thickness", "+-2.6"),
+            Err(PlateValidationError::ToleranceTooLoose { .. })
+        ));
+        assert!(matches!(
+            with("plate_thickness", "-9/-10"),
+            Err(PlateValidationError::ToleranceTooLoose { .. })
+        ));
+        let err = with("bolt_spacing", "H7").unwrap_err();
+        assert!(matches!(err, PlateValidationError::FitNotAllowed { dimension: Dimension::BoltSpacing }));
+        assert_eq!(err.code(), "FIT_NOT_ALLOWED");
+        assert_eq!(err.related_fields(), &["boltSpacing"]);
+    }
+
+    #[test]
+    fn test_pin_fit_rules() {
+        let fitted = ActuatorPlate { pin_fit: Some(domain::PinFit::Press), ..valid_plate() };
+        assert!(validate(&fitted).is_ok());
+
+        let mut toleranced = fitted;
+        toleranced.tolerances.pin_diameter = Some("H7".parse().unwrap());
+        let err = validate_pin_fit(&toleranced).unwrap_err();
+        assert_eq!(err.code(), "PIN_FIT_CONFLICTS_WITH_TOLERANCE");
+        assert_eq!(err.related_fields(), &["pinFit", "pinDiameter"]);
+
+        let oversized = ActuatorPlate { pin_diameter: Length::from_micrometers(500_001), ..fitted };
+        assert!(matches!(
+            validate_pin_fit(&oversized),
+            Err(PlateValidationError::PinFitSizeUnsupported { max_diameter }) if max_diameter == Length::from_mm(500)
+        ));
+    }
+
+    // --- Plate families ---
+
+    #[test]
+    fn test_slotted_plate_rules() {
+        let slotted = |millimeters: u16| ActuatorPlate {
+            bracket_width: Length::from_mm(300),
+            family: PlateFamily::Slotted { slot_length: Length::from_mm(millimeters) },
+            ..valid_plate()
+        };
+        assert!(validate(&slotted(12)).is_ok());
+        assert!(matches!(validate_family(&slotted(0)), Err(PlateValidationError::SlotLengthInvalid)));
+
+        // 300 mm wide: the slot plus the 11 mm clearance hole must stay under 30 mm
+        let err = validate_family(&slotted(19)).unwrap_err();
+        assert!(matches!(err, PlateValidationError::SlotTooLong { max_length } if max_length == Length::from_mm(19)));
+        assert_eq!(err.related_fields(), &["slotLength", "bracketWidth", "boltSize"]);
+
+        let counterbored = ActuatorPlate {
+            bolt_holes: HoleSpec::Counterbore { diameter: Length::from_mm(18), depth: Length::from_mm(6) },
+            ..slotted(12)
+        };
+        assert_eq!(validate_family(&counterbored).unwrap_err().code(), "SLOT_NEEDS_THROUGH_HOLES");
+    }
+
+    #[test]
+    fn test_dual_pin_plate_rules() {
+        let dual_pin = |millimeters: u16| ActuatorPlate {
+            pin_count: Count::of(2),
+            family: PlateFamily::DualPin { pin_spacing: Length::from_mm(millimeters) },
+            ..valid_plate()
+        };
+        // 100 mm wide with Ø10 pins: 20 to 70 mm apart
+        assert!(validate(&dual_pin(20)).is_ok());
+        assert!(validate(&dual_pin(70)).is_ok());
+        assert!(matches!(
+            validate_family(&dual_pin(19)),
+            Err(PlateValidationError::PinSpacingTooSmall { min_spacing }) if min_spacing == Length::from_mm(20)
+        ));
+        assert!(matches!(
+            validate_family(&dual_pin(71)),
+            Err(PlateValidationError::PinSpacingTooLarge { max_spacing }) if max_spacing == Length::from_mm(70)
+        ));
+
+        let four_pins = ActuatorPlate { pin_count: Count::of(4), ..dual_pin(40) };
+        let errors = validate(&four_pins).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code(), "DUAL_PIN_COUNT_INVALID");
+        assert_eq!(errors[0].message(Locale::De).to_string(), "Platten mit zwei Stiften müssen genau 2 Stifte haben");
+    }
+
     // --- Minimum thickness advisory ---
 
     #[test]
@@ -922,13 +1606,13 @@ mod tests {
     fn test_material_change_flips_result() {
         // A plate right at the edge for steel but failing for brass
         let mut plate = valid_plate();
-        plate.pin_diameter = Millimeters(5);
-        plate.plate_thickness = Millimeters(3);
+        plate.pin_diameter = Length::from_mm(5);
+        plate.plate_thickness = Length::from_mm(3);
         plate.expected_force_per_pin = Newtons(900);
-        plate.bracket_height = Millimeters(200);
-        plate.bracket_width = Millimeters(100);
-        plate.bolt_spacing = Millimeters(60);
-        plate.pin_count = 2;
+        plate.bracket_height = Length::from_mm(200);
+        plate.bracket_width = Length::from_mm(100);
+        plate.bolt_spacing = Length::from_mm(60);
+        plate.pin_count = Count::of(2);
 
         // Steel: allowable pin bearing = 250 * 5 * 3 = 3,750. design = 1,800. Pass.
         plate.material = Material::CarbonSteel;
@@ -954,14 +1638,14 @@ mod tests {
     fn test_increasing_thickness_fixes_bearing() {
         let mut plate = valid_plate();
         plate.material = Material::Brass;
-        plate.pin_diameter = Millimeters(3);
-        plate.plate_thickness = Millimeters(2);
+        plate.pin_diameter = Length::from_mm(3);
+        plate.plate_thickness = Length::from_mm(2);
         plate.expected_force_per_pin = Newtons(500);
         // allowable = 124 * 3 * 2 = 744, design = 1000 → fail
         assert!(validate_pin_bearing_stress(&plate).is_err());
 
         // Increase thickness to 3mm: allowable = 124 * 3 * 3 = 1,116 > 1,000 → pass
-        plate.plate_thickness = Millimeters(3);
+        plate.plate_thickness = Length::from_mm(3);
         assert!(validate_pin_bearing_stress(&plate).is_ok());
     }
 
@@ -1023,7 +1707,7 @@ mod tests {
             "Pin count must be at least 1"
         );
         assert_eq!(
-            PlateValidationError::PinCountTooLarge.to_string(),
+            PlateValidationError::PinCountTooLarge { max: 12 }.to_string(),
             "Pin count must not exceed 12"
         );
         assert_eq!(
@@ -1035,7 +1719,7 @@ mod tests {
             "Expected force per pin must be greater than 0"
         );
         assert_eq!(
-            PlateValidationError::ExpectedForceTooLarge.to_string(),
+            PlateValidationError::ExpectedForceTooLarge { max_n: 100_000 }.to_string(),
             "Expected force per pin must not exceed 100,000 N"
         );
         assert_eq!(
@@ -1050,7 +1734,7 @@ mod tests {
     fn test_force_upper_bound_rejected() {
         assert!(matches!(
             validate_expected_force(100_001).unwrap_err(),
-            PlateValidationError::ExpectedForceTooLarge
+            PlateValidationError::ExpectedForceTooLarge { .. }
         ));
     }
 
@@ -1063,7 +1747,7 @@ mod tests {
     fn test_full_plate_rejects_excessive_force() {
         let mut plate = valid_plate();
         plate.expected_force_per_pin = Newtons(100_001);
-        assert!(validate(&plate).unwrap_err().iter().any(|e| matches!(e, PlateValidationError::ExpectedForceTooLarge)));
+        assert!(validate(&plate).unwrap_err().iter().any(|e| matches!(e, PlateValidationError::ExpectedForceTooLarge { .. })));
     }
 
     #[test]
@@ -1072,18 +1756,32 @@ mod tests {
         // Bending lhs = 3 * (100_000 * 2 * 12) * 65535 = 3 * 2_400_000 * 65535 = 471_852_000_000
         // This must not panic (fits in u64)
         let plate = ActuatorPlate {
-            bolt_spacing: Millimeters(65535),
+            bolt_spacing: Length::from_mm(65535),
             bolt_size: BoltSize::M3,
-            bracket_height: Millimeters(65535),
-            bracket_width: Millimeters(65535),
+            bracket_height: Length::from_mm(65535),
+            bracket_width: Length::from_mm(65535),
             material: Material::Aluminum,
-            pin_diameter: Millimeters(65535),
-            pin_count: 12,
-            plate_thickness: Millimeters(65535),
+            pin_diameter: Length::from_mm(65535),
+            pin_count: Count::of(12),
+            plate_thickness: Length::from_mm(65535),
             expected_force_per_pin: Newtons(100_000),
+            bolt_holes: HoleSpec::Through,
+            tolerances: Tolerances::NONE,
+            family: PlateFamily::Standard,
+            pin_fit: None,
+        };
+        // Should not panic — may pass or fail on stress, but must not overflow.
+        // Limits opened all the way so the stress checks run.
+        let unlimited = ValidationLimits {
+            max_bolt_spacing_mm: u16::MAX,
+            max_bracket_height_mm: u16::MAX,
+            max_bracket_width_mm: u16::MAX,
+            max_pin_diameter_mm: u16::MAX,
+            max_plate_thickness_mm: u16::MAX,
+            max_pin_count: 12,
+            max_force_per_pin_n: MAX_FORCE_PER_PIN,
         };
-        // Should not panic — may pass or fail on stress, but must not overflow
-        let _ = validate(&plate);
+        let _ = validate_with_limits(&plate, &unlimited);
         let _ = minimum_thickness_mm(&plate);
         let _ = stress_utilization(&plate);
     }
@@ -1105,8 +1803,8 @@ mod tests {
     fn test_pin_bearing_matrix_aluminum_10_8_500() {
         let mut plate = valid_plate();
         plate.material = Material::Aluminum;
-        plate.pin_diameter = Millimeters(10);
-        plate.plate_thickness = Millimeters(8);
+        plate.pin_diameter = Length::from_mm(10);
+        plate.plate_thickness = Length::from_mm(8);
         plate.expected_force_per_pin = Newtons(500);
         // allowable = 276 * 10 * 8 = 22,080, design = 1000 → PASS
         assert!(validate_pin_bearing_stress(&plate).is_ok());
@@ -1116,8 +1814,8 @@ mod tests {
     fn test_pin_bearing_matrix_brass_5_3_300() {
         let mut plate = valid_plate();
         plate.material = Material::Brass;
-        plate.pin_diameter = Millimeters(5);
-        plate.plate_thickness = Millimeters(3);
+        plate.pin_diameter = Length::from_mm(5);
+        plate.plate_thickness = Length::from_mm(3);
         plate.expected_force_per_pin = Newtons(300);
         // allowable = 124 * 5 * 3 = 1,860, design = 600 → PASS
         assert!(validate_pin_bearing_stress(&plate).is_ok());
@@ -1127,8 +1825,8 @@ mod tests {
     fn test_pin_bearing_matrix_brass_3_2_200() {
         let mut plate = valid_plate();
         plate.material = Material::Brass;
-        plate.pin_diameter = Millimeters(3);
-        plate.plate_thickness = Millimeters(2);
+        plate.pin_diameter = Length::from_mm(3);
+        plate.plate_thickness = Length::from_mm(2);
         plate.expected_force_per_pin = Newtons(200);
         // allowable = 124 * 3 * 2 = 744, design = 400 → PASS
         assert!(validate_pin_bearing_stress(&plate).is_ok());
@@ -1138,8 +1836,8 @@ mod tests {
     fn test_pin_bearing_matrix_brass_3_2_500() {
         let mut plate = valid_plate();
         plate.material = Material::Brass;
-        plate.pin_diameter = Millimeters(3);
-        plate.plate_thickness = Millimeters(2);
+        plate.pin_diameter = Length::from_mm(3);
+        plate.plate_thickness = Length::from_mm(2);
         plate.expected_force_per_pin = Newtons(500);
         // allowable = 124 * 3 * 2 = 744, design = 1000 → FAIL
         assert!(validate_pin_bearing_stress(&plate).is_err());
@@ -1177,4 +1875,40 @@ mod tests {
         assert!(u_high.bolt_bearing > u_low.bolt_bearing);
         assert!(u_high.bending > u_low.bending);
     }
+
+    #[test]
+    fn test_presets_are_valid() {
+        for preset in domain::presets::PRESETS {
+            assert!(validate(&preset.plate).is_ok(), "preset {} should be valid", preset.id);
+        }
+    }
+
+    #[test]
+    fn test_messages_are_localized() {
+        let error = PlateValidationError::ExpectedForceTooLarge { max_n: 50_000 };
+        assert_eq!(error.message(Locale::En).to_string(), error.to_string());
+        assert_eq!(
+            error.message(Locale::Es).to_string(),
+            "La fuerza esperada por pasador no debe superar 50.000 N"
+        );
+        assert_eq!(
+            error.message(Locale::De).to_string(),
+            "Die erwartete Kraft pro Stift darf 50.000 N nicht überschreiten"
+        );
+
+        let tolerance = PlateValidationError::ToleranceTooLoose { dimension: Dimension::PinDiameter };
+        assert!(tolerance.message(Locale::Es).to_string().starts_with("Diámetro del pasador:"));
+        assert!(tolerance.message(Locale::De).to_string().starts_with("Stiftdurchmesser:"));
+    }
+
+    #[test]
+    fn test_locale_from_accept_language() {
+        assert_eq!(Locale::from_tag("es-MX"), Some(Locale::Es));
+        assert_eq!(Locale::from_tag("DE_at"), Some(Locale::De));
+        assert_eq!(Locale::from_tag("fr"), None);
+        assert_eq!(Locale::from_accept_language("fr-CH, fr;q=0.9, de;q=0.8, es;q=0.5"), Locale::De);
+        assert_eq!(Locale::from_accept_language("en;q=0.4, es"), Locale::Es);
+        assert_eq!(Locale::from_accept_language("es;q=0, *;q=0.1"), Locale::En);
+        assert_eq!(Locale::from_accept_language(""), Locale::En);
+    }
 }
@@ -0,0 +1,171 @@
+//! Input constraints for each plate field, for clients that build a form.
+//!
+//! [`rules_with_limits`] describes what [`validate_with_limits`](crate::validate_with_limits)
+//! accepts field by field: the range and unit of numbers and the values a choice
+//! allows. Cross-field checks (stress, clearances, hole sizes) can't be put as a
+//! per-field range, so a plate within every rule can still fail validation.
+
+use alloc::vec::Vec;
+use domain::{BoltSize, Material, PinFit};
+use serde::Serialize;
+
+use crate::{ValidationLimits, MAX_FORCE_PER_PIN};
+
+/// Smallest length the checks accept: the stress math works in whole millimeters.
+const MIN_LENGTH_MM: f64 = 1.0;
+
+/// Lengths are kept to the micrometer.
+const LENGTH_STEP_MM: f64 = 0.001;
+
+/// What kind of value a field takes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    /// A number between `min` and `max`, in multiples of `step`
+    Number,
+    /// One of `allowed`; for tagged objects such as `family`, the `type` tag
+    Choice,
+}
+
+/// The constraints on one plate field.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FieldRule {
+    /// Form field name, as in the `fields` of a validation error (e.g. "boltSpacing")
+    pub field: &'static str,
+    /// Property of the plate in JSON (e.g. "bolt_spacing")
+    pub key: &'static str,
+    pub kind: FieldKind,
+    /// Smallest allowed value, inclusive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    /// Largest allowed value, inclusive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// Finest increment the value is kept to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<f64>,
+    /// Unit of `min`, `max`, and `step` (e.g. "mm", "N")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<&'static str>,
+    /// Accepted values of a choice
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<&'static str>,
+}
+
+impl FieldRule {
+    fn number(field: &'static str, key: &'static str, min: f64, max: f64, step: f64, unit: Option<&'static str>) -> Self {
+        Self {
+            field,
+            key,
+            kind: FieldKind::Number,
+            min: Some(min),
+            max: Some(max),
+            step: Some(step),
+            unit,
+            allowed: Vec::new(),
+        }
+    }
+
+    fn length(field: &'static str, key: &'static str, max_mm: u16) -> Self {
+        Self::number(field, key, MIN_LENGTH_MM, max_mm.into(), LENGTH_STEP_MM, Some("mm"))
+    }
+
+    fn choice(field: &'static str, key: &'static str, allowed: Vec<&'static str>) -> Self {
+        Self {
+            field,
+            key,
+            kind: FieldKind::Choice,
+            min: None,
+            max: None,
+            step: None,
+            unit: None,
+            allowed,
+        }
+    }
+}
+
+/// The rules for [`ValidationLimits::DEFAULT`].
+pub fn rules() -> Vec<FieldRule> {
+    rules_with_limits(&ValidationLimits::DEFAULT)
+}
+
+/// The rules for a plate checked against `limits`, in form order.
+pub fn rules_with_limits(limits: &ValidationLimits) -> Vec<FieldRule> {
+    let max_force_n = limits.max_force_per_pin_n.min(MAX_FORCE_PER_PIN);
+    alloc::vec![
+        FieldRule::length("boltSpacing", "bolt_spacing", limits.max_bolt_spacing_mm),
+        FieldRule::choice("boltSize", "bolt_size", BoltSize::ALL.iter().map(BoltSize::name).collect()),
+        FieldRule::length("bracketHeight", "bracket_height", limits.max_bracket_height_mm),
+        FieldRule::length("bracketWidth", "bracket_width", limits.max_bracket_width_mm),
+        FieldRule::choice("material", "material", Material::ALL.iter().map(Material::name).collect()),
+        FieldRule::length("pinDiameter", "pin_diameter", limits.max_pin_diameter_mm),
+        FieldRule::number("pinCount", "pin_count", 1.0, limits.max_pin_count.into(), 1.0, None),
+        FieldRule::length("plateThickness", "plate_thickness", limits.max_plate_thickness_mm),
+        FieldRule::number("expectedForce", "expected_force_per_pin", 1.0, max_force_n.into(), 1.0, Some("N")),
+        FieldRule::choice("boltHoles", "bolt_holes", alloc::vec!["through", "counterbore", "countersink", "tapped"]),
+        FieldRule::choice("family", "family", alloc::vec!["standard", "slotted", "dual_pin"]),
+        FieldRule::choice("pinFit", "pin_fit", PinFit::ALL.iter().map(PinFit::name).collect()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use domain::Length;
+
+    #[test]
+    fn test_number_rules_match_the_checks() {
+        let limits = ValidationLimits { max_bracket_width_mm: 400, max_pin_count: 8, ..ValidationLimits::DEFAULT };
+        for rule in rules_with_limits(&limits).iter().filter(|rule| rule.kind == FieldKind::Number) {
+            let (min, max) = (rule.min.unwrap(), rule.max.unwrap());
+            let check = |value: f64| match rule.field {
+                "pinCount" => limits.check_pin_count(value as u32),
+                "expectedForce" => limits.check_expected_force(value as u32),
+                field => {
+                    let length = Length::from_micrometers((value * 1000.0 + 0.5) as u32);
+                    match field {
+                        "boltSpacing" => limits.check_bolt_spacing(length),
+                        "bracketHeight" => limits.check_bracket_height(length),
+                        "bracketWidth" => limits.check_bracket_width(length),
+                        "pinDiameter" => limits.check_pin_diameter(length),
+                        "plateThickness" => limits.check_plate_thickness(length),
+                        other => panic!("no check for {}", other),
+                    }
+                }
+            };
+            let step = rule.step.unwrap();
+            assert!(check(min).is_ok() && check(max).is_ok(), "{} bounds rejected", rule.field);
+            assert!(check(min - step).is_err() && check(max + step).is_err(), "{} accepts past its bounds", rule.field);
+        }
+        let rules = rules_with_limits(&limits);
+        assert_eq!(rules.iter().find(|rule| rule.field == "bracketWidth").unwrap().max, Some(400.0));
+        assert_eq!(rules.iter().find(|rule| rule.field == "pinCount").unwrap().max, Some(8.0));
+    }
+
+    #[test]
+    fn test_choice_rules_list_what_deserializes() {
+        for rule in rules() {
+            for value in &rule.allowed {
+                let json = match rule.key {
+                    "bolt_holes" | "family" => alloc::format!(r#"{{"type":"{}"}}"#, value),
+                    _ => alloc::format!(r#""{}""#, value),
+                };
+                let parsed = match rule.key {
+                    "bolt_size" => serde_json::from_str::<BoltSize>(&json).is_ok(),
+                    "material" => serde_json::from_str::<Material>(&json).is_ok(),
+                    "pin_fit" => serde_json::from_str::<PinFit>(&json).is_ok(),
+                    // Variants with fields are refused for missing them, not for the tag
+                    "bolt_holes" => serde_json::from_str::<domain::HoleSpec>(&json)
+                        .map_or_else(|e| e.to_string().contains("missing field"), |_| true),
+                    "family" => serde_json::from_str::<domain::PlateFamily>(&json)
+                        .map_or_else(|e| e.to_string().contains("missing field"), |_| true),
+                    other => panic!("no type for {}", other),
+                };
+                assert!(parsed, "{} {} doesn't deserialize", rule.key, value);
+            }
+        }
+    }
+}
@@ -6,20 +6,40 @@
 extern crate alloc;
 
 use alloc::string::{String, ToString};
+use core::sync::atomic::{AtomicU8, Ordering};
 use wasm_bindgen::prelude::*;
 
 use crate::{
-    validate_bolt_size, validate_bolt_spacing, validate_bracket_height, validate_bracket_width,
+    Locale, PlateValidationError, validate_bolt_size, validate_bolt_spacing, validate_bracket_height, validate_bracket_width,
     validate_expected_force, validate_material, validate_pin_count, validate_pin_diameter,
     validate_plate_thickness,
 };
 
+/// Index into [`Locale::ALL`] of the locale error messages are written in.
+static LOCALE: AtomicU8 = AtomicU8::new(0);
+
+/// Set the language of error messages from a tag such as `navigator.language`.
+///
+/// Unsupported languages fall back to English.
+#[wasm_bindgen]
+pub fn wasm_set_locale(tag: &str) {
+    let locale = Locale::from_tag(tag).unwrap_or_default();
+    let index = Locale::ALL.iter().position(|l| *l == locale).unwrap_or(0);
+    LOCALE.store(index as u8, Ordering::Relaxed);
+}
+
+/// An error's message in the locale set by [`wasm_set_locale`].
+fn message(error: &PlateValidationError) -> String {
+    let locale = Locale::ALL[LOCALE.load(Ordering::Relaxed) as usize];
+    error.message(locale).to_string()
+}
+
 /// Validate bolt spacing value.
 ///
 /// Returns Ok(()) if valid, or an error message if invalid.
 #[wasm_bindgen]
 pub fn wasm_validate_bolt_spacing(value: u16) -> Result<(), String> {
-    validate_bolt_spacing(value).map_err(|e| e.to_string())
+    validate_bolt_spacing(value).map_err(|e| message(&e))
 }
 
 /// Validate bolt size value.
@@ -28,7 +48,7 @@ pub fn wasm_validate_bolt_spacing(value: u16) -> Result<(), String> {
 /// Returns Ok(()) if valid, or an error message if invalid.
 #[wasm_bindgen]
 pub fn wasm_validate_bolt_size(value: &str) -> Result<(), String> {
-    validate_bolt_size(value).map_err(|e| e.to_string())
+    validate_bolt_size(value).map_err(|e| message(&e))
 }
 
 /// Validate bracket height value.
@@ -36,7 +56,7 @@ pub fn wasm_validate_bolt_size(value: &str) -> Result<(), String> {
 /// Returns Ok(()) if valid, or an error message if invalid.
 #[wasm_bindgen]
 pub fn wasm_validate_bracket_height(value: u16) -> Result<(), String> {
-    validate_bracket_height(value).map_err(|e| e.to_string())
+    validate_bracket_height(value).map_err(|e| message(&e))
 }
 
 /// Validate bracket width value.
@@ -44,7 +64,7 @@ pub fn wasm_validate_bracket_height(value: u16) -> Result<(), String> {
 /// Returns Ok(()) if valid, or an error message if invalid.
 #[wasm_bindgen]
 pub fn wasm_validate_bracket_width(value: u16) -> Result<(), String> {
-    validate_bracket_width(value).map_err(|e| e.to_string())
+    validate_bracket_width(value).map_err(|e| message(&e))
 }
 
 /// Validate pin diameter value.
@@ -52,7 +72,7 @@ pub fn wasm_validate_bracket_width(value: u16) -> Result<(), String> {
 /// Returns Ok(()) if valid, or an error message if invalid.
 #[wasm_bindgen]
 pub fn wasm_validate_pin_diameter(value: u16) -> Result<(), String> {
-    validate_pin_diameter(value).map_err(|e| e.to_string())
+    validate_pin_diameter(value).map_err(|e| message(&e))
 }
 
 /// Validate pin count value.
@@ -60,7 +80,7 @@ pub fn wasm_validate_pin_diameter(value: u16) -> Result<(), String> {
 /// Returns Ok(()) if valid, or an error message if invalid.
 #[wasm_bindgen]
 pub fn wasm_validate_pin_count(value: u16) -> Result<(), String> {
-    validate_pin_count(value).map_err(|e| e.to_string())
+    validate_pin_count(value).map_err(|e| message(&e))
 }
 
 /// Validate plate thickness value.
@@ -68,7 +88,7 @@ pub fn wasm_validate_pin_count(value: u16) -> Result<(), String> {
 /// Returns Ok(()) if valid, or an error message if invalid.
 #[wasm_bindgen]
 pub fn wasm_validate_plate_thickness(value: u16) -> Result<(), String> {
-    validate_plate_thickness(value).map_err(|e| e.to_string())
+    validate_plate_thickness(value).map_err(|e| message(&e))
 }
 
 /// Validate material value.
@@ -77,7 +97,7 @@ pub fn wasm_validate_plate_thickness(value: u16) -> Result<(), String> {
 /// Returns Ok(()) if valid, or an error message if invalid.
 #[wasm_bindgen]
 pub fn wasm_validate_material(value: &str) -> Result<(), String> {
-    validate_material(value).map_err(|e| e.to_string())
+    validate_material(value).map_err(|e| message(&e))
 }
 
 /// Validate expected force per pin value.
@@ -85,7 +105,7 @@ pub fn wasm_validate_material(value: &str) -> Result<(), String> {
 /// Returns Ok(()) if valid, or an error message if invalid.
 #[wasm_bindgen]
 pub fn wasm_validate_expected_force(value: u32) -> Result<(), String> {
-    validate_expected_force(value).map_err(|e| e.to_string())
+    validate_expected_force(value).map_err(|e| message(&e))
 }
 
 /// Run full stress analysis on a plate configuration.
@@ -105,25 +125,28 @@ pub fn wasm_validate_stress(
     plate_thickness: u16,
     expected_force_per_pin: u32,
 ) -> Result<(), String> {
-    use domain::{ActuatorPlate, Millimeters, Newtons};
+    use domain::{ActuatorPlate, Count, Length, Newtons};
 
     let bolt_size = parse_bolt_size(bolt_size)?;
     let material = parse_material(material)?;
+    let pin_count = Count::new(pin_count.into())
+        .map_err(|_| message(&PlateValidationError::PinCountTooSmall))?;
 
     let plate = ActuatorPlate {
-        bolt_spacing: Millimeters(bolt_spacing),
+        bolt_spacing: Length::from_mm(bolt_spacing),
         bolt_size,
-        bracket_height: Millimeters(bracket_height),
-        bracket_width: Millimeters(bracket_width),
+        bracket_height: Length::from_mm(bracket_height),
+        bracket_width: Length::from_mm(bracket_width),
         material,
-        pin_diameter: Millimeters(pin_diameter),
+        pin_diameter: Length::from_mm(pin_diameter),
         pin_count,
-        plate_thickness: Millimeters(plate_thickness),
+        plate_thickness: Length::from_mm(plate_thickness),
         expected_force_per_pin: Newtons(expected_force_per_pin),
+        ..ActuatorPlate::default()
     };
 
     crate::validate(&plate).map_err(|errors| {
-        errors.iter().map(|e| e.to_string()).collect::<alloc::vec::Vec<_>>().join("; ")
+        errors.iter().map(message).collect::<alloc::vec::Vec<_>>().join("; ")
     })
 }
 
@@ -141,22 +164,25 @@ pub fn wasm_minimum_thickness(
     pin_count: u16,
     expected_force_per_pin: u32,
 ) -> Result<u16, String> {
-    use domain::{ActuatorPlate, Millimeters, Newtons};
+    use domain::{ActuatorPlate, Count, Length, Newtons};
 
     let bolt_size = parse_bolt_size(bolt_size)?;
     let material = parse_material(material)?;
+    let pin_count = Count::new(pin_count.into())
+        .map_err(|_| message(&PlateValidationError::PinCountTooSmall))?;
 
     // Use placeholder values for fields not needed by minimum_thickness_mm
     let plate = ActuatorPlate {
-        bolt_spacing: Millimeters(bolt_spacing),
+        bolt_spacing: Length::from_mm(bolt_spacing),
         bolt_size,
-        bracket_height: Millimeters(1000), // not used in thickness calc
-        bracket_width: Millimeters(bracket_width),
+        bracket_height: Length::from_mm(1000), // not used in thickness calc
+        bracket_width: Length::from_mm(bracket_width),
         material,
-        pin_diameter: Millimeters(pin_diameter),
+        pin_diameter: Length::from_mm(pin_diameter),
         pin_count,
-        plate_thickness: Millimeters(1), // not used — we're computing this
+        plate_thickness: Length::from_mm(1), // not used — we're computing this
         expected_force_per_pin: Newtons(expected_force_per_pin),
+        ..ActuatorPlate::default()
     };
 
     Ok(crate::minimum_thickness_mm(&plate))
@@ -164,17 +190,7 @@ pub fn wasm_minimum_thickness(
 
 /// Parse a bolt size string into a BoltSize enum.
 fn parse_bolt_size(value: &str) -> Result<domain::BoltSize, String> {
-    let trimmed = value.trim();
-    match trimmed {
-        "M3" | "m3" => Ok(domain::BoltSize::M3),
-        "M4" | "m4" => Ok(domain::BoltSize::M4),
-        "M5" | "m5" => Ok(domain::BoltSize::M5),
-        "M6" | "m6" => Ok(domain::BoltSize::M6),
-        "M8" | "m8" => Ok(domain::BoltSize::M8),
-        "M10" | "m10" => Ok(domain::BoltSize::M10),
-        "M12" | "m12" => Ok(domain::BoltSize::M12),
-        _ => Err(crate::PlateValidationError::BoltSizeInvalid.to_string()),
-    }
+    domain::BoltSize::from_name(value).ok_or_else(|| message(&PlateValidationError::BoltSizeInvalid))
 }
 
 /// Parse a material string into a Material enum.
@@ -188,6 +204,6 @@ fn parse_material(value: &str) -> Result<domain::Material, String> {
             Ok(domain::Material::CarbonSteel)
         }
         "brass" | "Brass" | "BRASS" => Ok(domain::Material::Brass),
-        _ => Err(crate::PlateValidationError::MaterialInvalid.to_string()),
+        _ => Err(message(&PlateValidationError::MaterialInvalid)),
     }
 }
@@ -0,0 +1,74 @@
+//! Property tests over random plates from `domain::testing`.
+
+use domain::{ActuatorPlate, Length};
+use proptest::prelude::*;
+use validation::{PlateValidationError, ValidationLimits};
+
+/// Every validator's verdict on `plate`, in the order `validate` reports them. Like
+/// `validate`, hole recesses aren't checked against a zero thickness, which is
+/// already an error, and the stress checks, which divide by plate dimensions, only
+/// run once the basic checks pass.
+fn individual_errors(plate: &ActuatorPlate) -> Vec<PlateValidationError> {
+    let limits = ValidationLimits::DEFAULT;
+    let basic = [
+        limits.check_bolt_spacing(plate.bolt_spacing),
+        limits.check_bracket_height(plate.bracket_height),
+        limits.check_bracket_width(plate.bracket_width),
+        limits.check_pin_diameter(plate.pin_diameter),
+        limits.check_pin_count(plate.pin_count.get()),
+        limits.check_plate_thickness(plate.plate_thickness),
+        limits.check_expected_force(plate.expected_force_per_pin.0),
+        if plate.plate_thickness == Length::ZERO { Ok(()) } else { validation::validate_bolt_holes(plate) },
+        validation::validate_tolerances(plate),
+        validation::validate_pin_fit(plate),
+        validation::validate_family(plate),
+    ];
+    let errors: Vec<_> = basic.into_iter().filter_map(Result::err).collect();
+    if !errors.is_empty() {
+        return errors;
+    }
+    [
+        validation::validate_pin_bearing_stress(plate),
+        validation::validate_bolt_bearing_stress(plate),
+        validation::validate_plate_bending_stress(plate),
+        validation::validate_bolt_edge_distance(plate),
+        validation::validate_pin_clearance(plate),
+    ]
+    .into_iter()
+    .filter_map(Result::err)
+    .collect()
+}
+
+proptest! {
+    // Shrunk failures are printed rather than saved, since proptest can't find this
+    // file's crate root to put a regressions file next to
+    #![proptest_config(ProptestConfig { cases: 512, failure_persistence: None, ..ProptestConfig::default() })]
+
+    #[test]
+    fn validate_accepts_exactly_what_every_validator_accepts(plate: ActuatorPlate) {
+        let expected = individual_errors(&plate);
+        match validation::validate(&plate) {
+            Ok(()) => prop_assert!(expected.is_empty(), "{:?}", expected),
+            Err(errors) => prop_assert_eq!(errors, expected),
+        }
+    }
+
+    #[test]
+    fn json_round_trip_keeps_the_plate_and_its_verdict(plate: ActuatorPlate) {
+        let json = serde_json::to_string(&plate).unwrap();
+        let back: ActuatorPlate = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(back, plate);
+        prop_assert_eq!(validation::validate(&back).is_ok(), validation::validate(&plate).is_ok());
+    }
+
+    #[test]
+    fn cache_key_is_stable_and_tracks_the_plate(plate: ActuatorPlate, other: ActuatorPlate) {
+        let key = plate.cache_key();
+        prop_assert!(key.starts_with("plate-"));
+        prop_assert_eq!(&key, &plate.cache_key());
+
+        let back: ActuatorPlate = serde_json::from_value(serde_json::to_value(plate).unwrap()).unwrap();
+        prop_assert_eq!(&key, &back.cache_key());
+        prop_assert_eq!(key == other.cache_key(), plate == other);
+    }
+}
@@ -0,0 +1,379 @@
+//! Audit log of generation requests, generations, engine failures, and orders, for
+//! traceability and the admin dashboard.
+//!
+//! Every plate submitted for generation, directly or as a job, is recorded with who
+//! sent it and its full parameters. Events are appended as they happen and never
+//! updated; AUDIT_RETENTION_DAYS removes them once they are that old. The memory
+//! store keeps the most recent [`MEMORY_AUDIT_LIMIT`]; set AUDIT_DATABASE_URL to keep
+//! them in SQLite.
+
+use async_trait::async_trait;
+use domain::ActuatorPlate;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::time::Duration;
+use utoipa::ToSchema;
+
+/// Most events [`MemoryAuditStore`] keeps; older ones are dropped.
+pub const MEMORY_AUDIT_LIMIT: usize = 1000;
+
+/// Errors that can occur during audit log operations.
+#[derive(Debug)]
+pub enum AuditError {
+    /// The backing store failed.
+    StorageError(String),
+    /// An audit setting in the environment is invalid.
+    Config(String),
+}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditError::StorageError(msg) => write!(f, "Audit storage error: {}", msg),
+            AuditError::Config(msg) => write!(f, "Audit configuration error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AuditError {}
+
+impl From<sqlx::Error> for AuditError {
+    fn from(e: sqlx::Error) -> Self {
+        AuditError::StorageError(e.to_string())
+    }
+}
+
+/// What an audit event records.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditKind {
+    /// A plate was submitted for generation; `detail` is the route, `plate` the parameters
+    Request,
+    /// A plate was served from the cache
+    CacheHit,
+    /// A plate was generated by the engine
+    Generated,
+    /// The engine or file export failed; `detail` has the error text
+    EngineFailure,
+    /// A customer placed an order; `subject` is the order number
+    Order,
+}
+
+impl AuditKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditKind::Request => "request",
+            AuditKind::CacheHit => "cache_hit",
+            AuditKind::Generated => "generated",
+            AuditKind::EngineFailure => "engine_failure",
+            AuditKind::Order => "order",
+        }
+    }
+}
+
+impl FromStr for AuditKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "request" => Ok(AuditKind::Request),
+            "cache_hit" => Ok(AuditKind::CacheHit),
+            "generated" => Ok(AuditKind::Generated),
+            "engine_failure" => Ok(AuditKind::EngineFailure),
+            "order" => Ok(AuditKind::Order),
+            other => Err(other.to_string()),
+        }
+    }
+}
+
+/// One entry in the audit log.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AuditEvent {
+    pub kind: AuditKind,
+    /// When it happened (RFC 3339, UTC, to the millisecond)
+    pub at: String,
+    /// Cache key of the plate, or the order number for orders
+    pub subject: String,
+    /// Who made the request: `key:<name>` for an API key, `ip:<address>` otherwise,
+    /// or empty when neither is known
+    #[serde(default)]
+    pub actor: String,
+    /// The route for requests, error text for engine failures, a summary for orders,
+    /// otherwise empty
+    pub detail: String,
+    /// How long generation took, for generations and engine failures
+    pub duration_ms: Option<u64>,
+    /// The submitted parameters, for requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plate: Option<ActuatorPlate>,
+}
+
+impl AuditEvent {
+    /// An event of `kind` about `subject`, happening now.
+    pub fn new(kind: AuditKind, subject: impl Into<String>) -> Self {
+        Self {
+            kind,
+            at: timestamp(chrono::Utc::now()),
+            subject: subject.into(),
+            actor: String::new(),
+            detail: String::new(),
+            duration_ms: None,
+            plate: None,
+        }
+    }
+
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = actor.into();
+        self
+    }
+
+    pub fn with_plate(mut self, plate: &ActuatorPlate) -> Self {
+        self.plate = Some(*plate);
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = detail.into();
+        self
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration_ms = Some(duration.as_millis() as u64);
+        self
+    }
+}
+
+/// Format `time` the way events are stored. The fixed width keeps string order the
+/// same as time order, so retention can compare stored timestamps directly.
+pub fn timestamp(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+/// Read how long events are kept from AUDIT_RETENTION_DAYS. Unset or 0 keeps them
+/// forever.
+pub fn retention_from_env() -> Result<Option<Duration>, AuditError> {
+    match std::env::var("AUDIT_RETENTION_DAYS") {
+        Ok(days) if !days.is_empty() => days
+            .parse::<u64>()
+            .map(|days| (days > 0).then(|| Duration::from_secs(days.saturating_mul(24 * 60 * 60))))
+            .map_err(|_| AuditError::Config(format!("AUDIT_RETENTION_DAYS must be a whole number, got {:?}", days))),
+        _ => Ok(None),
+    }
+}
+
+/// Trait for persisting the audit log.
+#[async_trait]
+pub trait AuditStore: Send + Sync {
+    /// Append an event.
+    async fn record(&self, event: &AuditEvent) -> Result<(), AuditError>;
+
+    /// The most recent `limit` events, newest first.
+    async fn recent(&self, limit: usize) -> Result<Vec<AuditEvent>, AuditError>;
+
+    /// Remove events recorded before `before`, a timestamp from [`timestamp`].
+    /// Returns how many were removed.
+    async fn prune(&self, before: &str) -> Result<u64, AuditError>;
+
+    /// Check that the store is reachable, for the readiness probe.
+    async fn check(&self) -> Result<(), AuditError> {
+        Ok(())
+    }
+}
+
+/// In-memory audit log for testing and development, holding the last
+/// [`MEMORY_AUDIT_LIMIT`] events.
+pub struct MemoryAuditStore {
+    events: RwLock<VecDeque<AuditEvent>>,
+}
+
+impl MemoryAuditStore {
+    pub fn new() -> Self {
+        Self {
+            events: RwLock::new(VecDeque::new()),
+        }
+    }
+}
+
+impl Default for MemoryAuditStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl AuditStore for MemoryAuditStore {
+    async fn record(&self, event: &AuditEvent) -> Result<(), AuditError> {
+        let mut events = self.events.write().unwrap();
+        if events.len() == MEMORY_AUDIT_LIMIT {
+            events.pop_front();
+        }
+        events.push_back(event.clone());
+        Ok(())
+    }
+
+    async fn recent(&self, limit: usize) -> Result<Vec<AuditEvent>, AuditError> {
+        Ok(self.events.read().unwrap().iter().rev().take(limit).cloned().collect())
+    }
+
+    async fn prune(&self, before: &str) -> Result<u64, AuditError> {
+        let mut events = self.events.write().unwrap();
+        let count = events.len();
+        events.retain(|event| event.at.as_str() >= before);
+        Ok((count - events.len()) as u64)
+    }
+}
+
+/// SQLite audit log. The plate is stored as JSON so new plate fields don't need a
+/// schema migration.
+pub struct SqliteAuditStore {
+    pool: SqlitePool,
+}
+
+impl SqliteAuditStore {
+    /// Open (creating if needed) the database at `url`, e.g. `sqlite:///var/lib/platerator/audit.db`.
+    pub async fn connect(url: &str) -> Result<Self, AuditError> {
+        let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+        let pool = SqlitePoolOptions::new().connect_with(options).await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS audit (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                at TEXT NOT NULL,
+                subject TEXT NOT NULL,
+                actor TEXT NOT NULL,
+                detail TEXT NOT NULL,
+                duration_ms INTEGER,
+                plate TEXT
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        Ok(Self { pool })
+    }
+
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<AuditEvent, AuditError> {
+        let kind: String = row.try_get("kind")?;
+        let duration_ms: Option<i64> = row.try_get("duration_ms")?;
+        let plate: Option<String> = row.try_get("plate")?;
+        let plate = plate
+            .map(|plate| serde_json::from_str(&plate))
+            .transpose()
+            .map_err(|e| AuditError::StorageError(format!("Unreadable audited plate: {}", e)))?;
+        Ok(AuditEvent {
+            kind: kind
+                .parse()
+                .map_err(|k| AuditError::StorageError(format!("Unknown audit event kind {:?}", k)))?,
+            at: row.try_get("at")?,
+            subject: row.try_get("subject")?,
+            actor: row.try_get("actor")?,
+            detail: row.try_get("detail")?,
+            duration_ms: duration_ms.map(|ms| ms as u64),
+            plate,
+        })
+    }
+}
+
+#[async_trait]
+impl AuditStore for SqliteAuditStore {
+    async fn check(&self) -> Result<(), AuditError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn record(&self, event: &AuditEvent) -> Result<(), AuditError> {
+        let plate = event
+            .plate
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| AuditError::StorageError(e.to_string()))?;
+        sqlx::query(
+            "INSERT INTO audit (kind, at, subject, actor, detail, duration_ms, plate) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(event.kind.as_str())
+        .bind(&event.at)
+        .bind(&event.subject)
+        .bind(&event.actor)
+        .bind(&event.detail)
+        .bind(event.duration_ms.map(|ms| ms as i64))
+        .bind(plate)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn recent(&self, limit: usize) -> Result<Vec<AuditEvent>, AuditError> {
+        let rows = sqlx::query("SELECT * FROM audit ORDER BY seq DESC LIMIT ?")
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(Self::from_row).collect()
+    }
+
+    async fn prune(&self, before: &str) -> Result<u64, AuditError> {
+        let result = sqlx::query("DELETE FROM audit WHERE at < ?").bind(before).execute(&self.pool).await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sqlite_store_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}", dir.path().join("audit.db").display());
+        let store = SqliteAuditStore::connect(&url).await.unwrap();
+        let request = AuditEvent::new(AuditKind::Request, "plate-b")
+            .with_actor("key:CI pipeline")
+            .with_detail("/api/generate")
+            .with_plate(&ActuatorPlate::default());
+        let failure = AuditEvent::new(AuditKind::EngineFailure, "plate-a")
+            .with_detail("zoo exited with status 1")
+            .with_duration(Duration::from_millis(1500));
+        store.record(&request).await.unwrap();
+        store.record(&failure).await.unwrap();
+
+        let recent = store.recent(10).await.unwrap();
+        assert_eq!(recent, [failure.clone(), request]);
+        assert_eq!(store.recent(1).await.unwrap(), [failure]);
+    }
+
+    #[tokio::test]
+    async fn test_prune_removes_only_older_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}", dir.path().join("audit.db").display());
+        let stores: [Box<dyn AuditStore>; 2] = [
+            Box::new(MemoryAuditStore::new()),
+            Box::new(SqliteAuditStore::connect(&url).await.unwrap()),
+        ];
+        let at = |at: &str| AuditEvent { at: at.to_string(), ..AuditEvent::new(AuditKind::CacheHit, "plate-a") };
+
+        for store in stores {
+            store.record(&at("2026-01-01T00:00:00.000Z")).await.unwrap();
+            store.record(&at("2026-03-01T00:00:00.000Z")).await.unwrap();
+            assert_eq!(store.prune("2026-02-01T00:00:00.000Z").await.unwrap(), 1);
+            let recent = store.recent(10).await.unwrap();
+            assert_eq!(recent.iter().map(|e| e.at.as_str()).collect::<Vec<_>>(), ["2026-03-01T00:00:00.000Z"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_drops_oldest_events() {
+        let store = MemoryAuditStore::new();
+        for n in 0..=MEMORY_AUDIT_LIMIT {
+            store.record(&AuditEvent::new(AuditKind::Order, format!("PL-{:06}", n))).await.unwrap();
+        }
+
+        let recent = store.recent(usize::MAX).await.unwrap();
+        assert_eq!(recent.len(), MEMORY_AUDIT_LIMIT);
+        assert_eq!(recent[0].subject, format!("PL-{:06}", MEMORY_AUDIT_LIMIT));
+        assert_eq!(recent.last().unwrap().subject, "PL-000001");
+    }
+}
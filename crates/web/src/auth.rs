@@ -0,0 +1,394 @@
+//! API keys for programmatic clients.
+//!
+//! Keys are created and revoked through the admin API, or listed in the file named
+//! by API_KEYS_FILE. Only a SHA-256 hash of each key is stored, so a leaked database
+//! or key file can't be used to call the API. Clients send the key in the
+//! `X-API-Key` header.
+//!
+//! With REQUIRE_API_KEY=true, the generation and order endpoints reject requests
+//! without a valid key. Otherwise keys are optional, but a key that is sent must
+//! still be valid.
+
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::RwLock;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{AppState, ErrorResponse};
+
+/// Header clients send their key in.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Every generated key starts with this, so leaked keys are easy to search for.
+const KEY_PREFIX: &str = "pk_";
+
+/// Characters of a key kept in the clear to tell keys apart.
+const VISIBLE_CHARS: usize = 10;
+
+/// An API key. The key itself is only known when it's created.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ApiKey {
+    /// Key identifier, used to revoke it
+    pub id: String,
+    /// Who the key was issued to
+    pub name: String,
+    /// Start of the key, e.g. `pk_3f9a1c0`, to recognize it in client configs
+    pub prefix: String,
+    /// When the key was created (RFC 3339)
+    pub created_at: String,
+    /// When the key was revoked (RFC 3339); revoked keys are rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<String>,
+    /// The full key (only returned when the key is created)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+/// Errors that can occur during API key storage operations.
+#[derive(Debug)]
+pub enum ApiKeyError {
+    /// No key with the requested ID.
+    NotFound,
+    /// The backing store failed.
+    StorageError(String),
+}
+
+impl fmt::Display for ApiKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiKeyError::NotFound => write!(f, "API key not found"),
+            ApiKeyError::StorageError(msg) => write!(f, "API key storage error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ApiKeyError {}
+
+impl From<sqlx::Error> for ApiKeyError {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            sqlx::Error::RowNotFound => ApiKeyError::NotFound,
+            e => ApiKeyError::StorageError(e.to_string()),
+        }
+    }
+}
+
+/// Hex SHA-256 of a key, as stored.
+pub fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Whether a presented secret is `expected`. Like keys, they're compared by their
+/// SHA-256 hashes, so the time taken doesn't reveal how much of the secret matched.
+pub(crate) fn secret_matches(presented: &str, expected: &str) -> bool {
+    Sha256::digest(presented.as_bytes()) == Sha256::digest(expected.as_bytes())
+}
+
+/// Create a key with a fresh random secret. Returns the key, including the secret,
+/// and the hash to store.
+pub fn new_key(name: &str) -> (ApiKey, String) {
+    let secret = format!("{}{}{}", KEY_PREFIX, Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let hash = hash_key(&secret);
+    let key = ApiKey {
+        id: Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        prefix: secret[..VISIBLE_CHARS].to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        revoked_at: None,
+        key: Some(secret),
+    };
+    (key, hash)
+}
+
+/// Trait for persisting API keys by hash.
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// Store a new key. Its `key` field is not stored.
+    async fn insert(&self, key: &ApiKey, hash: &str) -> Result<(), ApiKeyError>;
+
+    /// The key with this hash, revoked or not.
+    async fn find(&self, hash: &str) -> Result<Option<ApiKey>, ApiKeyError>;
+
+    /// All keys, oldest first.
+    async fn list(&self) -> Result<Vec<ApiKey>, ApiKeyError>;
+
+    /// Mark a key revoked, returning it.
+    async fn revoke(&self, id: &str) -> Result<ApiKey, ApiKeyError>;
+
+    /// Check that the store is reachable, for the readiness probe.
+    async fn check(&self) -> Result<(), ApiKeyError> {
+        Ok(())
+    }
+}
+
+/// In-memory key store for testing and development.
+#[derive(Default)]
+pub struct MemoryApiKeyStore {
+    keys: RwLock<Vec<(ApiKey, String)>>,
+}
+
+impl MemoryApiKeyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for MemoryApiKeyStore {
+    async fn insert(&self, key: &ApiKey, hash: &str) -> Result<(), ApiKeyError> {
+        let key = ApiKey { key: None, ..key.clone() };
+        self.keys.write().unwrap().push((key, hash.to_string()));
+        Ok(())
+    }
+
+    async fn find(&self, hash: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        let keys = self.keys.read().unwrap();
+        Ok(keys.iter().find(|(_, h)| h == hash).map(|(key, _)| key.clone()))
+    }
+
+    async fn list(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        Ok(self.keys.read().unwrap().iter().map(|(key, _)| key.clone()).collect())
+    }
+
+    async fn revoke(&self, id: &str) -> Result<ApiKey, ApiKeyError> {
+        let mut keys = self.keys.write().unwrap();
+        let (key, _) = keys.iter_mut().find(|(key, _)| key.id == id).ok_or(ApiKeyError::NotFound)?;
+        key.revoked_at.get_or_insert_with(|| chrono::Utc::now().to_rfc3339());
+        Ok(key.clone())
+    }
+}
+
+/// SQLite key store.
+pub struct SqliteApiKeyStore {
+    pool: SqlitePool,
+}
+
+impl SqliteApiKeyStore {
+    /// Open (creating if needed) the database at `url`, e.g. `sqlite:///var/lib/platerator/keys.db`.
+    pub async fn connect(url: &str) -> Result<Self, ApiKeyError> {
+        let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+        let pool = SqlitePoolOptions::new().connect_with(options).await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS api_keys (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                prefix TEXT NOT NULL,
+                key_hash TEXT NOT NULL UNIQUE,
+                created_at TEXT NOT NULL,
+                revoked_at TEXT
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        Ok(Self { pool })
+    }
+
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<ApiKey, ApiKeyError> {
+        Ok(ApiKey {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            prefix: row.try_get("prefix")?,
+            created_at: row.try_get("created_at")?,
+            revoked_at: row.try_get("revoked_at")?,
+            key: None,
+        })
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for SqliteApiKeyStore {
+    async fn check(&self) -> Result<(), ApiKeyError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn insert(&self, key: &ApiKey, hash: &str) -> Result<(), ApiKeyError> {
+        sqlx::query("INSERT INTO api_keys (id, name, prefix, key_hash, created_at, revoked_at) VALUES (?, ?, ?, ?, ?, ?)")
+            .bind(&key.id)
+            .bind(&key.name)
+            .bind(&key.prefix)
+            .bind(hash)
+            .bind(&key.created_at)
+            .bind(&key.revoked_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn find(&self, hash: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        let row = sqlx::query("SELECT * FROM api_keys WHERE key_hash = ?")
+            .bind(hash)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(Self::from_row).transpose()
+    }
+
+    async fn list(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        let rows = sqlx::query("SELECT * FROM api_keys ORDER BY created_at")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(Self::from_row).collect()
+    }
+
+    async fn revoke(&self, id: &str) -> Result<ApiKey, ApiKeyError> {
+        sqlx::query("UPDATE api_keys SET revoked_at = COALESCE(revoked_at, ?) WHERE id = ?")
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        let row = sqlx::query("SELECT * FROM api_keys WHERE id = ?")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+        Self::from_row(&row)
+    }
+}
+
+/// Add the keys listed in a key file to `store`, skipping ones it already has.
+///
+/// Each line is a hex SHA-256 hash and a name, as printed by
+/// `printf %s "$KEY" | sha256sum` with the name in place of the `-`. Blank lines
+/// and lines starting with `#` are ignored. Returns how many keys were added.
+pub async fn load_key_file(store: &dyn ApiKeyStore, path: &Path) -> Result<usize, ApiKeyError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| ApiKeyError::StorageError(format!("{}: {}", path.display(), e)))?;
+    let mut added = 0;
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (hash, name) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let hash = hash.to_ascii_lowercase();
+        if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ApiKeyError::StorageError(format!(
+                "{} line {}: expected a SHA-256 hash and a name",
+                path.display(),
+                number + 1
+            )));
+        }
+        if store.find(&hash).await?.is_some() {
+            continue;
+        }
+        let key = ApiKey {
+            // Stable, so the same file loaded twice doesn't make two keys
+            id: format!("file-{}", &hash[..16]),
+            name: name.trim().to_string(),
+            prefix: String::new(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            revoked_at: None,
+            key: None,
+        };
+        store.insert(&key, &hash).await?;
+        added += 1;
+    }
+    Ok(added)
+}
+
+/// The API key a request was made with, if any.
+///
+/// Rejects the request with 401 if it sent an unknown or revoked key, or sent none
+/// while keys are required.
+pub struct ApiKeyAuth(pub Option<ApiKey>);
+
+fn unauthorized(message: &str) -> Response {
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec![message.to_string()],
+    };
+    (StatusCode::UNAUTHORIZED, Json(res)).into_response()
+}
+
+fn presented_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+}
+
+impl FromRequestParts<AppState> for ApiKeyAuth {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let Some(presented) = presented_key(&parts.headers) else {
+            if state.require_api_key {
+                return Err(unauthorized("An API key is required in the X-API-Key header"));
+            }
+            return Ok(ApiKeyAuth(None));
+        };
+        match state.api_keys.find(&hash_key(presented)).await {
+            Ok(Some(key)) if key.revoked_at.is_none() => Ok(ApiKeyAuth(Some(key))),
+            Ok(_) => Err(unauthorized("Invalid or revoked API key")),
+            Err(e) => {
+                tracing::error!("API key lookup failed: {}", e);
+                let res = ErrorResponse {
+                    success: false,
+                    got_it: false,
+                    errors: vec!["Could not check the API key. Please retry.".to_string()],
+                };
+                Err((StatusCode::SERVICE_UNAVAILABLE, Json(res)).into_response())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sqlite_store_finds_and_revokes_by_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}", dir.path().join("keys.db").display());
+        let store = SqliteApiKeyStore::connect(&url).await.unwrap();
+
+        let (key, hash) = new_key("CI pipeline");
+        let secret = key.key.clone().unwrap();
+        assert!(secret.starts_with("pk_") && secret.starts_with(&key.prefix));
+        assert_eq!(hash, hash_key(&secret));
+        store.insert(&key, &hash).await.unwrap();
+
+        let found = store.find(&hash).await.unwrap().unwrap();
+        assert_eq!(found, ApiKey { key: None, ..key.clone() });
+        assert!(store.find(&hash_key("pk_guess")).await.unwrap().is_none());
+
+        let revoked = store.revoke(&key.id).await.unwrap();
+        assert!(revoked.revoked_at.is_some());
+        assert_eq!(store.find(&hash).await.unwrap().unwrap().revoked_at, revoked.revoked_at);
+        assert!(matches!(store.revoke("missing").await, Err(ApiKeyError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_key_file_is_loaded_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys");
+        std::fs::write(
+            &path,
+            format!("# issued keys\n{}  ERP importer\n\n", hash_key("pk_erp")),
+        )
+        .unwrap();
+
+        let store = MemoryApiKeyStore::new();
+        assert_eq!(load_key_file(&store, &path).await.unwrap(), 1);
+        assert_eq!(load_key_file(&store, &path).await.unwrap(), 0);
+        let key = store.find(&hash_key("pk_erp")).await.unwrap().unwrap();
+        assert_eq!(key.name, "ERP importer");
+
+        std::fs::write(&path, "pk_erp ERP importer\n").unwrap();
+        assert!(load_key_file(&store, &path).await.is_err());
+    }
+}
@@ -0,0 +1,437 @@
+//! Backup and restore of orders and cache contents.
+//!
+//! A backup is an uncompressed tar archive:
+//!
+//! ```text
+//! manifest.json                  format version, creation time, build, cache keys
+//! orders.json                    every order, oldest first
+//! artifacts/<key>/<file>         every cached file, only when artifacts are included
+//! ```
+//!
+//! Restoring into a fresh deployment re-inserts the orders and repopulates the cache
+//! from any included artifacts. Cache keys without artifacts are regenerated on demand.
+//!
+//! Both directions stream: a backup reads one cache entry at a time as the archive is
+//! sent, and a restore writes each entry as soon as its files have arrived.
+//!
+//! Only orders and the cache are covered. Saved configurations, API keys, the audit
+//! log (each a SQLite database when configured) and the Zoo quota file are not; back
+//! those files up directly.
+
+use crate::cache::{is_valid_key, Artifact, CacheError, CachedFiles, ModelCache};
+use crate::orders::{Order, OrderError, OrderStore};
+use bytes::Bytes;
+use futures_util::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use utoipa::ToSchema;
+
+/// Archive layout version written to the manifest.
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Describes the contents of a backup archive.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    /// When the backup was taken (RFC 3339)
+    pub created_at: String,
+    /// Git hash of the build that took the backup
+    pub git_hash: String,
+    /// Every cache entry at backup time
+    pub cache_keys: Vec<String>,
+    /// Whether model files are included under `artifacts/`
+    pub includes_artifacts: bool,
+}
+
+/// What a restore put back.
+#[derive(Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub struct RestoreSummary {
+    /// Orders inserted into the order store
+    pub orders: usize,
+    /// Cache entries written from included artifacts
+    pub artifacts: usize,
+    /// Cache keys listed in the backup without artifacts; regenerated on demand
+    pub missing_artifacts: usize,
+}
+
+/// Errors that can occur while creating or restoring a backup.
+#[derive(Debug)]
+pub enum BackupError {
+    Cache(CacheError),
+    Orders(OrderError),
+    /// The archive could not be written or read.
+    Archive(String),
+    /// The archive was written by an incompatible version.
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackupError::Cache(e) => write!(f, "Backup cache error: {}", e),
+            BackupError::Orders(e) => write!(f, "Backup order error: {}", e),
+            BackupError::Archive(msg) => write!(f, "Invalid backup archive: {}", msg),
+            BackupError::UnsupportedVersion(v) => {
+                write!(f, "Unsupported backup format version {}", v)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BackupError {}
+
+impl From<CacheError> for BackupError {
+    fn from(e: CacheError) -> Self {
+        BackupError::Cache(e)
+    }
+}
+
+impl From<OrderError> for BackupError {
+    fn from(e: OrderError) -> Self {
+        BackupError::Orders(e)
+    }
+}
+
+impl From<std::io::Error> for BackupError {
+    fn from(e: std::io::Error) -> Self {
+        BackupError::Archive(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for BackupError {
+    fn from(e: serde_json::Error) -> Self {
+        BackupError::Archive(e.to_string())
+    }
+}
+
+/// Snapshot orders and cache metadata, plus model files if `include_artifacts`,
+/// as a stream of tar archive chunks.
+///
+/// The orders and the key list are read up front, so a store that can't be read
+/// fails the backup before anything is sent. Cache entries are then read one at a
+/// time as the archive is consumed; an error reading one ends the stream early.
+pub async fn create_backup(
+    cache: Arc<dyn ModelCache>,
+    orders: &dyn OrderStore,
+    include_artifacts: bool,
+) -> Result<BoxStream<'static, Result<Bytes, BackupError>>, BackupError> {
+    let mut cache_keys = cache.keys().await?;
+    cache_keys.sort();
+    let orders = orders.list().await?;
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        git_hash: env!("GIT_HASH").to_string(),
+        cache_keys: cache_keys.clone(),
+        includes_artifacts: include_artifacts,
+    };
+
+    let mut head = tar_entry("manifest.json", serde_json::to_vec_pretty(&manifest)?.into())?;
+    head.extend(tar_entry("orders.json", serde_json::to_vec_pretty(&orders)?.into())?);
+
+    let artifact_keys = if include_artifacts { cache_keys } else { Vec::new() };
+    let artifacts = stream::iter(artifact_keys)
+        .then(move |key| {
+            let cache = cache.clone();
+            async move {
+                let files = match cache.get(&key).await {
+                    Ok(files) => files,
+                    // Evicted between listing and reading
+                    Err(CacheError::NotFound) => return Ok(Vec::new()),
+                    // Regenerated on its next request; not worth restoring
+                    Err(e @ CacheError::Corrupt { .. }) => {
+                        tracing::warn!("Leaving {} out of the backup: {}", key, e);
+                        return Ok(Vec::new());
+                    }
+                    Err(e) => return Err(BackupError::from(e)),
+                };
+                let mut chunks = Vec::new();
+                for (artifact, data) in files.iter() {
+                    let name = format!("artifacts/{}/{}", key, artifact.file_name());
+                    chunks.extend(tar_entry(&name, data.clone())?);
+                }
+                Ok(chunks)
+            }
+        })
+        .map_ok(|chunks| stream::iter(chunks.into_iter().map(Ok)))
+        .try_flatten();
+
+    // Two zero blocks end the archive
+    let end = stream::once(async { Ok(Bytes::from_static(&[0; 2 * BLOCK])) });
+    Ok(stream::iter(head.into_iter().map(Ok)).chain(artifacts).chain(end).boxed())
+}
+
+/// Tar blocks are 512 bytes; each entry is a header block and its data, padded to a
+/// whole block.
+const BLOCK: usize = 512;
+
+/// One archive entry as its header, its data (not copied), and the padding.
+fn tar_entry(path: &str, data: Bytes) -> Result<Vec<Bytes>, BackupError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_path(path)?;
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp() as u64);
+    header.set_cksum();
+    let padding = (BLOCK - data.len() % BLOCK) % BLOCK;
+    Ok(vec![
+        Bytes::copy_from_slice(header.as_bytes()),
+        data,
+        Bytes::from(vec![0; padding]),
+    ])
+}
+
+/// Read the next entry's path and contents, or `None` at the end of the archive.
+/// Only one entry is held in memory at a time.
+async fn next_entry<R: AsyncRead + Unpin>(archive: &mut R) -> Result<Option<(String, Vec<u8>)>, BackupError> {
+    loop {
+        let mut block = [0; BLOCK];
+        match archive.read_exact(&mut block).await {
+            Ok(_) => {}
+            // Archives may end without their zero blocks
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+        if block.iter().all(|b| *b == 0) {
+            return Ok(None);
+        }
+
+        let header = tar::Header::from_byte_slice(&block);
+        let mut expected = header.clone();
+        expected.set_cksum();
+        if header.cksum()? != expected.cksum()? {
+            return Err(BackupError::Archive("corrupt tar header".to_string()));
+        }
+        let size = header.entry_size()?;
+        let mut data = Vec::new();
+        (&mut *archive).take(size).read_to_end(&mut data).await?;
+        if data.len() as u64 != size {
+            return Err(BackupError::Archive("archive ends partway through an entry".to_string()));
+        }
+        let padding = (BLOCK - size as usize % BLOCK) % BLOCK;
+        archive.read_exact(&mut [0; BLOCK][..padding]).await?;
+
+        // Directories and links aren't part of a backup
+        if header.entry_type().is_file() {
+            return Ok(Some((header.path()?.to_string_lossy().into_owned(), data)));
+        }
+    }
+}
+
+/// Restore a backup created by [`create_backup`] into the given stores, reading the
+/// archive as it arrives.
+///
+/// `manifest.json` must come first, as [`create_backup`] writes it, and its cache
+/// keys are checked before anything is written. Orders are inserted when
+/// `orders.json` is read, and each cache entry is written once its files have been
+/// read, so an archive that breaks off partway keeps what came before the break.
+pub async fn restore_backup<R: AsyncRead + Unpin>(
+    cache: &dyn ModelCache,
+    orders: &dyn OrderStore,
+    mut archive: R,
+) -> Result<RestoreSummary, BackupError> {
+    let manifest: BackupManifest = match next_entry(&mut archive).await? {
+        Some((path, data)) if path == "manifest.json" => serde_json::from_slice(&data)?,
+        _ => return Err(BackupError::Archive("missing manifest.json".to_string())),
+    };
+    if manifest.format_version != BACKUP_FORMAT_VERSION {
+        return Err(BackupError::UnsupportedVersion(manifest.format_version));
+    }
+    // Checked before anything is written, so a bad key leaves the stores untouched
+    if let Some(key) = manifest.cache_keys.iter().find(|key| !is_valid_key(key)) {
+        return Err(BackupError::Archive(format!("invalid cache key {:?}", key)));
+    }
+    let cache_keys: HashSet<&str> = manifest.cache_keys.iter().map(String::as_str).collect();
+
+    let mut summary = RestoreSummary {
+        orders: 0,
+        artifacts: 0,
+        missing_artifacts: 0,
+    };
+    // The files of the entry being read; an entry's files are consecutive
+    let mut entry: Option<(String, CachedFiles)> = None;
+    while let Some((path, data)) = next_entry(&mut archive).await? {
+        if path == "orders.json" {
+            let restored: Vec<Order> = serde_json::from_slice(&data)?;
+            for order in &restored {
+                orders.insert(order).await?;
+            }
+            summary.orders += restored.len();
+            continue;
+        }
+        let Some((key, name)) = path.strip_prefix("artifacts/").and_then(|rest| rest.split_once('/')) else {
+            continue;
+        };
+        // Files for keys the manifest doesn't list are ignored
+        if !cache_keys.contains(key) {
+            continue;
+        }
+        let Some(artifact) = Artifact::ALL.into_iter().find(|artifact| artifact.file_name() == name) else {
+            continue;
+        };
+        match &mut entry {
+            Some((current, files)) if current == key => files.insert(artifact, data),
+            _ => {
+                let next = (key.to_string(), CachedFiles::new().with(artifact, data));
+                if let Some((key, files)) = entry.replace(next) {
+                    put_entry(cache, &key, files, &mut summary).await?;
+                }
+            }
+        }
+    }
+    if let Some((key, files)) = entry {
+        put_entry(cache, &key, files, &mut summary).await?;
+    }
+    summary.missing_artifacts = manifest.cache_keys.len() - summary.artifacts;
+
+    tracing::info!(
+        "Restored backup from {}: {} orders, {} artifacts, {} keys without artifacts",
+        manifest.created_at,
+        summary.orders,
+        summary.artifacts,
+        summary.missing_artifacts
+    );
+    Ok(summary)
+}
+
+/// Archives from before a format was added lack its file; those entries are left
+/// out and regenerated.
+async fn put_entry(
+    cache: &dyn ModelCache,
+    key: &str,
+    files: CachedFiles,
+    summary: &mut RestoreSummary,
+) -> Result<(), BackupError> {
+    if files.is_complete() {
+        cache.put(key, &files).await?;
+        summary.artifacts += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache_memory::MemoryCache;
+    use crate::orders::MemoryOrderStore;
+    use crate::orders::OrderStatus;
+    use domain::{Count, Material};
+
+    fn order() -> Order {
+        Order {
+            id: "ord-1".to_string(),
+            order_number: String::new(),
+            status: OrderStatus::Received,
+            created_at: "2026-01-02T03:04:05+00:00".to_string(),
+            part_number: "plate-abc".to_string(),
+            material: Material::Aluminum,
+            plate: None,
+            quantity: Count::of(10),
+            unit_price_cents: 1500,
+            total_price_cents: 15_000,
+            customer_name: "Acme".to_string(),
+            customer_email: "buyer@acme.test".to_string(),
+            customer_phone: None,
+        }
+    }
+
+    async fn archive(cache: Arc<dyn ModelCache>, orders: &dyn OrderStore, include_artifacts: bool) -> Vec<u8> {
+        let chunks: Vec<Bytes> = create_backup(cache, orders, include_artifacts)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        chunks.concat()
+    }
+
+    async fn populated() -> (Arc<MemoryCache>, MemoryOrderStore) {
+        let cache = MemoryCache::new();
+        let files = CachedFiles::new()
+            .with(Artifact::Step, "step")
+            .with(Artifact::Gltf, "gltf")
+            .with(Artifact::Stl, "stl")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
+        cache.put("plate-abc", &files).await.unwrap();
+        let orders = MemoryOrderStore::new();
+        orders.insert(&order()).await.unwrap();
+        (Arc::new(cache), orders)
+    }
+
+    #[tokio::test]
+    async fn test_backup_with_artifacts_restores_into_empty_stores() {
+        let (cache, orders) = populated().await;
+        let archive = archive(cache, &orders, true).await;
+        // Every entry, and the archive, is a whole number of blocks
+        assert_eq!(archive.len() % BLOCK, 0);
+
+        let fresh_cache = MemoryCache::new();
+        let fresh_orders = MemoryOrderStore::new();
+        let summary = restore_backup(&fresh_cache, &fresh_orders, archive.as_slice()).await.unwrap();
+
+        assert_eq!(
+            summary,
+            RestoreSummary { orders: 1, artifacts: 1, missing_artifacts: 0 }
+        );
+        assert_eq!(fresh_orders.list().await.unwrap()[0].id, "ord-1");
+        assert_eq!(fresh_cache.get("plate-abc").await.unwrap().get(Artifact::Stl).unwrap(), &b"stl"[..]);
+    }
+
+    #[tokio::test]
+    async fn test_metadata_only_backup_reports_missing_artifacts() {
+        let (cache, orders) = populated().await;
+        let archive = archive(cache, &orders, false).await;
+
+        let fresh_cache = MemoryCache::new();
+        let summary = restore_backup(&fresh_cache, &MemoryOrderStore::new(), archive.as_slice())
+            .await
+            .unwrap();
+
+        assert_eq!(summary.missing_artifacts, 1);
+        assert!(!fresh_cache.exists("plate-abc").await);
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_invalid_cache_keys() {
+        let (cache, orders) = populated().await;
+        let mut archive = archive(cache, &orders, true).await;
+        // The manifest comes first; the same length keeps the tar header's size right
+        let at = archive.windows(11).position(|w| w == b"\"plate-abc\"").unwrap();
+        archive[at..at + 11].copy_from_slice(b"\"../../etc\"");
+
+        let fresh_orders = MemoryOrderStore::new();
+        let result = restore_backup(&MemoryCache::new(), &fresh_orders, archive.as_slice()).await;
+        assert!(matches!(result, Err(BackupError::Archive(_))));
+        assert!(fresh_orders.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_archive_without_manifest() {
+        let result = restore_backup(&MemoryCache::new(), &MemoryOrderStore::new(), &b""[..]).await;
+        assert!(matches!(result, Err(BackupError::Archive(_))));
+    }
+
+    #[tokio::test]
+    async fn test_backup_is_a_standard_tar_archive() {
+        let (cache, orders) = populated().await;
+        let archive = archive(cache, &orders, true).await;
+
+        let mut entries = tar::Archive::new(archive.as_slice());
+        let paths: Vec<String> = entries
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(paths[..3], ["manifest.json", "orders.json", "artifacts/plate-abc/model.step"]);
+        assert_eq!(paths.len(), 2 + Artifact::ALL.len());
+    }
+}
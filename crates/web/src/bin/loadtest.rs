@@ -0,0 +1,276 @@
+//! Load test for the web API.
+//!
+//! Starts the server in-process against the mock zoo engine (or targets `--url`) and
+//! runs validate → generate → download with concurrent workers, then reports latency
+//! percentiles and error rates per endpoint.
+//!
+//! ```text
+//! cargo run -p web --features loadtest --bin loadtest -- \
+//!     --concurrency 16 --requests 400 --designs 40 --engine-latency-ms 800
+//! ```
+//!
+//! `--designs` controls how many distinct plates are requested, and so the cache hit
+//! rate: with 400 requests over 40 designs, at most 40 reach the engine.
+
+use domain::{ActuatorPlate, Length};
+use parametric::mock::{MockBehavior, MockEngine};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use web::{AppStateInner, MemoryCache};
+
+struct Options {
+    concurrency: usize,
+    requests: usize,
+    designs: usize,
+    engine_latency: Duration,
+    url: Option<String>,
+}
+
+impl Options {
+    fn parse() -> Result<Self, String> {
+        let mut options = Options {
+            concurrency: 8,
+            requests: 200,
+            designs: 20,
+            engine_latency: Duration::from_millis(500),
+            url: None,
+        };
+
+        let mut args = std::env::args().skip(1);
+        while let Some(flag) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{} needs a value", flag));
+            match flag.as_str() {
+                "--concurrency" => options.concurrency = parse_number(&flag, &value()?)?,
+                "--requests" => options.requests = parse_number(&flag, &value()?)?,
+                "--designs" => options.designs = parse_number(&flag, &value()?)?,
+                "--engine-latency-ms" => {
+                    options.engine_latency = Duration::from_millis(parse_number(&flag, &value()?)?)
+                }
+                "--url" => options.url = Some(value()?.trim_end_matches('/').to_string()),
+                "--help" | "-h" => return Err(USAGE.to_string()),
+                other => return Err(format!("Unknown option {}\n\n{}", other, USAGE)),
+            }
+        }
+        if options.concurrency == 0 || options.designs == 0 {
+            return Err("--concurrency and --designs must be at least 1".to_string());
+        }
+        Ok(options)
+    }
+}
+
+const USAGE: &str = "Usage: loadtest [--concurrency N] [--requests N] [--designs N] \
+[--engine-latency-ms MS] [--url http://host:port]";
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got {:?}", flag, value))
+}
+
+/// The `i`th distinct, valid plate design.
+fn design(i: usize) -> ActuatorPlate {
+    ActuatorPlate {
+        bolt_spacing: Length::from_mm(40 + (i % 200) as u16),
+        bracket_height: Length::from_mm(400 + (i / 200) as u16),
+        ..Default::default()
+    }
+}
+
+/// One request's outcome.
+struct Sample {
+    endpoint: &'static str,
+    elapsed: Duration,
+    ok: bool,
+}
+
+async fn timed<F, T>(samples: &mut Vec<Sample>, endpoint: &'static str, request: F) -> Option<T>
+where
+    F: std::future::Future<Output = Result<T, String>>,
+{
+    let start = Instant::now();
+    let result = request.await;
+    samples.push(Sample {
+        endpoint,
+        elapsed: start.elapsed(),
+        ok: result.is_ok(),
+    });
+    result.ok()
+}
+
+async fn run_iteration(client: &reqwest::Client, base: &str, plate: &ActuatorPlate) -> Vec<Sample> {
+    let mut samples = Vec::with_capacity(3);
+
+    timed(&mut samples, "POST /api/validate", async {
+        let response = client
+            .post(format!("{}/api/validate", base))
+            .json(plate)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        response.error_for_status().map(|_| ()).map_err(|e| e.to_string())
+    })
+    .await;
+
+    let generated: Option<serde_json::Value> = timed(&mut samples, "POST /api/generate", async {
+        let response = client
+            .post(format!("{}/api/generate", base))
+            .json(plate)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| e.to_string())?;
+        response.json().await.map_err(|e| e.to_string())
+    })
+    .await;
+
+    let Some(download_url) = generated
+        .as_ref()
+        .and_then(|json| json["download_url"].as_str())
+    else {
+        return samples;
+    };
+
+    timed(&mut samples, "GET /api/download/step", async {
+        client
+            .get(format!("{}{}", base, download_url))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| e.to_string())?
+            .bytes()
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    })
+    .await;
+
+    samples
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (p / 100.0 * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank]
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+fn report(samples: Vec<Sample>, wall: Duration, iterations: usize) {
+    let mut by_endpoint: BTreeMap<&str, (Vec<Duration>, usize)> = BTreeMap::new();
+    for sample in samples {
+        let entry = by_endpoint.entry(sample.endpoint).or_default();
+        entry.0.push(sample.elapsed);
+        if !sample.ok {
+            entry.1 += 1;
+        }
+    }
+
+    println!(
+        "\n{} iterations in {:.1}s ({:.1} iterations/s)\n",
+        iterations,
+        wall.as_secs_f64(),
+        iterations as f64 / wall.as_secs_f64()
+    );
+    println!(
+        "{:<26} {:>8} {:>7} {:>7} {:>9} {:>9} {:>9}",
+        "endpoint", "requests", "errors", "err %", "p50 ms", "p99 ms", "max ms"
+    );
+    for (endpoint, (mut latencies, errors)) in by_endpoint {
+        latencies.sort();
+        let count = latencies.len();
+        println!(
+            "{:<26} {:>8} {:>7} {:>6.1}% {:>9.1} {:>9.1} {:>9.1}",
+            endpoint,
+            count,
+            errors,
+            errors as f64 * 100.0 / count as f64,
+            millis(percentile(&latencies, 50.0)),
+            millis(percentile(&latencies, 99.0)),
+            millis(latencies.last().copied().unwrap_or_default()),
+        );
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let options = match Options::parse() {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+
+    // Keep the mock engine alive for the whole run; dropping it deletes the fake CLI.
+    let mut _engine = None;
+    let base = match &options.url {
+        Some(url) => url.clone(),
+        None => {
+            let engine = MockEngine::with_latency(MockBehavior::Succeed, options.engine_latency)?;
+            let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+            inner.engine = Arc::new(engine.cli());
+            _engine = Some(engine);
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+            let addr = listener.local_addr()?;
+            let app = web::create_router(Arc::new(inner));
+            tokio::spawn(async move { axum::serve(listener, app).await });
+            format!("http://{}", addr)
+        }
+    };
+
+    for i in 0..options.designs {
+        if validation::validate(&design(i)).is_err() {
+            return Err(format!("design {} is not a valid plate; lower --designs", i).into());
+        }
+    }
+
+    println!(
+        "Driving {} with {} workers: {} iterations over {} designs{}",
+        base,
+        options.concurrency,
+        options.requests,
+        options.designs,
+        match options.url {
+            Some(_) => String::new(),
+            None => format!(", mock engine latency {:?}", options.engine_latency),
+        }
+    );
+
+    let client = reqwest::Client::new();
+    let next = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+
+    let workers: Vec<_> = (0..options.concurrency)
+        .map(|_| {
+            let client = client.clone();
+            let base = base.clone();
+            let next = next.clone();
+            let (requests, designs) = (options.requests, options.designs);
+            tokio::spawn(async move {
+                let mut samples = Vec::new();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= requests {
+                        return samples;
+                    }
+                    samples.extend(run_iteration(&client, &base, &design(i % designs)).await);
+                }
+            })
+        })
+        .collect();
+
+    let mut samples = Vec::new();
+    for worker in workers {
+        samples.extend(worker.await?);
+    }
+
+    report(samples, started.elapsed(), options.requests);
+    Ok(())
+}
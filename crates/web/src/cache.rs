@@ -1,43 +1,307 @@
 //! Cache trait and types for storing generated model files.
 
 use async_trait::async_trait;
-use std::fmt;
+use bytes::Bytes;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::Arc;
 
-/// Cached model files containing STEP, glTF, and STL data.
-#[derive(Clone)]
+use crate::{AppConfig, AwsCache, CacheBackend, LocalCache, MemoryCache, RedisCache};
+
+/// Cached model files: the STEP, glTF, and STL models, the OBJ and 3MF meshes
+/// for printing, the 2D drawing as SVG and PDF, the DXF cut profile, and the KCL
+/// source they were built from, keyed by [`Artifact`].
+///
+/// A complete entry has every [`Artifact::ALL`]; [`ModelCache::get`] only returns
+/// complete ones, so adding a format makes older entries misses rather than errors.
+/// The buffers are reference-counted, so cloning an entry (for a session, a
+/// download, or an upload) never copies the file contents.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct CachedFiles {
-    pub step_data: Vec<u8>,
-    pub gltf_data: Vec<u8>,
-    pub stl_data: Vec<u8>,
+    files: BTreeMap<Artifact, Bytes>,
+}
+
+impl CachedFiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// This entry with `artifact` set to `data`.
+    pub fn with(mut self, artifact: Artifact, data: impl Into<Bytes>) -> Self {
+        self.insert(artifact, data);
+        self
+    }
+
+    pub fn insert(&mut self, artifact: Artifact, data: impl Into<Bytes>) {
+        self.files.insert(artifact, data.into());
+    }
+
+    pub fn get(&self, artifact: Artifact) -> Option<&Bytes> {
+        self.files.get(&artifact)
+    }
+
+    /// The files present, in [`Artifact::ALL`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (Artifact, &Bytes)> {
+        self.files.iter().map(|(artifact, data)| (*artifact, data))
+    }
+
+    /// Whether every [`Artifact::ALL`] is present.
+    pub fn is_complete(&self) -> bool {
+        Artifact::ALL.iter().all(|artifact| self.files.contains_key(artifact))
+    }
+
+    /// Total size of the files in bytes.
+    pub fn size(&self) -> usize {
+        self.files.values().map(Bytes::len).sum()
+    }
+}
+
+impl FromIterator<(Artifact, Bytes)> for CachedFiles {
+    fn from_iter<I: IntoIterator<Item = (Artifact, Bytes)>>(iter: I) -> Self {
+        Self {
+            files: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// One file of a cache entry. Serialized as its extension, e.g. in checksum
+/// manifests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Artifact {
+    Step,
+    Gltf,
+    Stl,
+    Obj,
+    #[serde(rename = "3mf")]
+    ThreeMf,
+    Svg,
+    Pdf,
+    Dxf,
+    Kcl,
+}
+
+impl Artifact {
+    pub const ALL: [Artifact; 9] = [
+        Artifact::Step,
+        Artifact::Gltf,
+        Artifact::Stl,
+        Artifact::Obj,
+        Artifact::ThreeMf,
+        Artifact::Svg,
+        Artifact::Pdf,
+        Artifact::Dxf,
+        Artifact::Kcl,
+    ];
+
+    /// Name the file is stored under within its entry.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Artifact::Step => "model.step",
+            Artifact::Gltf => "model.gltf",
+            Artifact::Stl => "model.stl",
+            Artifact::Obj => "model.obj",
+            Artifact::ThreeMf => "model.3mf",
+            Artifact::Svg => "drawing.svg",
+            Artifact::Pdf => "drawing.pdf",
+            Artifact::Dxf => "plate.dxf",
+            Artifact::Kcl => "program.kcl",
+        }
+    }
+
+    /// MIME type the file is stored and served with.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Artifact::Step => "application/STEP",
+            Artifact::Gltf => "model/gltf+json",
+            Artifact::Stl => "model/stl",
+            Artifact::Obj => "model/obj",
+            Artifact::ThreeMf => "model/3mf",
+            Artifact::Svg => "image/svg+xml",
+            Artifact::Pdf => "application/pdf",
+            Artifact::Dxf => "image/vnd.dxf",
+            Artifact::Kcl => "text/plain; charset=utf-8",
+        }
+    }
+
+    /// The file's extension, e.g. `3mf`.
+    pub fn extension(self) -> &'static str {
+        let name = self.file_name();
+        &name[name.rfind('.').map_or(0, |dot| dot + 1)..]
+    }
+
+    /// The artifact with this extension, e.g. from `GenerationResult::files`.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|artifact| artifact.extension() == extension)
+    }
+}
+
+/// One cached file, read a chunk at a time so a large file is never held in
+/// memory whole.
+pub struct ArtifactStream {
+    /// Size in bytes, when the backend knows it up front
+    pub length: Option<u64>,
+    pub body: BoxStream<'static, Result<Bytes, CacheError>>,
+}
+
+impl ArtifactStream {
+    /// A file already in memory, as a single chunk.
+    pub fn from_bytes(data: Bytes) -> Self {
+        Self {
+            length: Some(data.len() as u64),
+            body: stream::once(async move { Ok(data) }).boxed(),
+        }
+    }
+
+    /// Hash the chunks as they pass through and end with [`CacheError::Corrupt`]
+    /// if they don't match `checksum`. By then the earlier chunks have been sent,
+    /// so the error aborts the response instead of completing a damaged file.
+    pub fn verified(self, artifact: Artifact, checksum: String) -> Self {
+        let body = stream::unfold(Some((self.body, Sha256::new(), checksum)), move |state| async move {
+            let (mut body, mut hasher, checksum) = state?;
+            match body.next().await {
+                Some(Ok(chunk)) => {
+                    hasher.update(&chunk);
+                    Some((Ok(chunk), Some((body, hasher, checksum))))
+                }
+                Some(Err(e)) => Some((Err(e), None)),
+                None if checksum.eq_ignore_ascii_case(&hex::encode(hasher.finalize())) => None,
+                None => Some((Err(CacheError::Corrupt { file: artifact.file_name() }), None)),
+            }
+        });
+        Self { length: self.length, body: body.boxed() }
+    }
+}
+
+/// Hex SHA-256 of each file in an entry, recorded when the entry is written and
+/// checked when it is read back, so a truncated or damaged file is never served.
+///
+/// Serialized as an object keyed by [`Artifact`] extension, e.g. `{"step": "..."}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Checksums {
+    checksums: BTreeMap<Artifact, String>,
+}
+
+impl Checksums {
+    pub fn of(files: &CachedFiles) -> Self {
+        files
+            .iter()
+            .map(|(artifact, data)| (artifact, hex::encode(Sha256::digest(data))))
+            .collect()
+    }
+
+    /// The checksum recorded for one file.
+    pub fn of_artifact(&self, artifact: Artifact) -> Option<&str> {
+        self.checksums.get(&artifact).map(String::as_str)
+    }
+
+    /// The checksums present, in [`Artifact::ALL`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (Artifact, &str)> {
+        self.checksums.iter().map(|(artifact, checksum)| (*artifact, checksum.as_str()))
+    }
+
+    /// Check `files` against these checksums, naming the first file that differs
+    /// or has no checksum.
+    pub fn verify(&self, files: &CachedFiles) -> Result<(), CacheError> {
+        let actual = Self::of(files);
+        for (artifact, checksum) in actual.iter() {
+            if !self.of_artifact(artifact).is_some_and(|expected| expected.eq_ignore_ascii_case(checksum)) {
+                return Err(CacheError::Corrupt { file: artifact.file_name() });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<(Artifact, String)> for Checksums {
+    fn from_iter<I: IntoIterator<Item = (Artifact, String)>>(iter: I) -> Self {
+        Self {
+            checksums: iter.into_iter().collect(),
+        }
+    }
 }
 
 /// Errors that can occur during cache operations.
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum CacheError {
     /// The requested cache key was not found.
+    #[error("Cache entry not found")]
     NotFound,
     /// An I/O error occurred during cache operations.
-    IoError(String),
-    /// An AWS service error occurred.
-    AwsError(String),
+    #[error("Cache I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// An AWS service call failed.
+    #[error("AWS {operation} failed: {source}")]
+    Aws {
+        /// The SDK operation that failed, e.g. `GetObject`.
+        operation: &'static str,
+        /// Whether the failure was transient (timeout, throttling, 5xx).
+        retryable: bool,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A Redis command or connection failed.
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+    /// The entry is larger than the backend is configured to store.
+    #[error("Cache entry is {size} bytes, over the {limit} byte limit")]
+    TooLarge { size: usize, limit: usize },
+    /// A cached file doesn't match the checksum recorded when it was written. The
+    /// entry is unusable; regenerating the model overwrites it.
+    #[error("Cached {file} doesn't match its checksum")]
+    Corrupt { file: &'static str },
+    /// The backend is missing required configuration.
+    #[error("Cache configuration error: {0}")]
+    Config(String),
+    /// The key isn't shaped like one [`domain::ActuatorPlate::cache_key`] returns.
+    #[error("Invalid cache key {0:?}")]
+    InvalidKey(String),
 }
 
-impl fmt::Display for CacheError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl CacheError {
+    /// Whether retrying the same operation might succeed.
+    pub fn is_retryable(&self) -> bool {
         match self {
-            CacheError::NotFound => write!(f, "Cache entry not found"),
-            CacheError::IoError(msg) => write!(f, "Cache I/O error: {}", msg),
-            CacheError::AwsError(msg) => write!(f, "AWS error: {}", msg),
+            CacheError::NotFound
+            | CacheError::TooLarge { .. }
+            | CacheError::Corrupt { .. }
+            | CacheError::Config(_)
+            | CacheError::InvalidKey(_) => false,
+            CacheError::Io(e) => matches!(
+                e.kind(),
+                io::ErrorKind::Interrupted
+                    | io::ErrorKind::TimedOut
+                    | io::ErrorKind::WouldBlock
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+            ),
+            CacheError::Aws { retryable, .. } => *retryable,
+            CacheError::Redis(e) => e.is_timeout() || e.is_connection_dropped() || e.is_connection_refusal(),
         }
     }
 }
 
-impl std::error::Error for CacheError {}
+/// Whether `cache_key` has the shape of a plate's cache key: `plate-` followed by
+/// hex digits. Keys end up in file paths and object names, so anything else is
+/// turned away before it reaches a backend.
+pub fn is_valid_key(cache_key: &str) -> bool {
+    cache_key
+        .strip_prefix("plate-")
+        .is_some_and(|hex| !hex.is_empty() && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+}
 
 /// Trait for caching generated model files.
 ///
-/// Implementations store and retrieve STEP and glTF files using a deterministic
-/// cache key derived from the plate configuration.
+/// Implementations store and retrieve [`CachedFiles`] using a deterministic cache
+/// key derived from the plate configuration.
+///
+/// Backends configured with a TTL treat entries older than it as missing. Whether
+/// the expired data is removed by [`ModelCache::evict_expired`] or by the store
+/// itself (DynamoDB TTL, S3 lifecycle rules, Redis expiry) depends on the backend.
 #[async_trait]
 pub trait ModelCache: Send + Sync {
     /// Check if a cache entry exists for the given key.
@@ -46,6 +310,186 @@ pub trait ModelCache: Send + Sync {
     /// Retrieve cached files for the given key.
     async fn get(&self, cache_key: &str) -> Result<CachedFiles, CacheError>;
 
+    /// Stream one file of the entry for the given key, for downloads. The default
+    /// reads the whole entry with [`ModelCache::get`]; backends that can read a
+    /// single file in chunks override it.
+    async fn stream(&self, cache_key: &str, artifact: Artifact) -> Result<ArtifactStream, CacheError> {
+        let files = self.get(cache_key).await?;
+        files.get(artifact).cloned().map(ArtifactStream::from_bytes).ok_or(CacheError::NotFound)
+    }
+
     /// Store files in the cache with the given key.
     async fn put(&self, cache_key: &str, files: &CachedFiles) -> Result<(), CacheError>;
+
+    /// List the keys of every complete cache entry.
+    async fn keys(&self) -> Result<Vec<String>, CacheError>;
+
+    /// Remove the entry for the given key. Removing a missing entry is not an error.
+    async fn delete(&self, cache_key: &str) -> Result<(), CacheError>;
+
+    /// Remove every entry, returning how many complete entries were removed.
+    async fn clear(&self) -> Result<usize, CacheError> {
+        let keys = self.keys().await?;
+        for key in &keys {
+            self.delete(key).await?;
+        }
+        Ok(keys.len())
+    }
+
+    /// Check that the backing store is reachable, for the readiness probe. Backends
+    /// with nothing remote to reach are always ready.
+    async fn check(&self) -> Result<(), CacheError> {
+        Ok(())
+    }
+
+    /// Remove entries older than the backend's TTL, returning how many were removed.
+    /// Backends without a TTL, or whose store expires entries on its own, remove nothing.
+    async fn evict_expired(&self) -> Result<usize, CacheError> {
+        Ok(0)
+    }
+}
+
+/// The backend `config` selects, with its TTL applied. Redis reads the rest of
+/// its settings, including its own TTL, from the REDIS_* variables.
+pub async fn from_config(config: &AppConfig) -> Result<Arc<dyn ModelCache>, CacheError> {
+    let ttl = config.cache.ttl;
+    Ok(match config.cache.backend {
+        CacheBackend::Aws => {
+            let bucket = config
+                .aws
+                .s3_bucket
+                .clone()
+                .ok_or_else(|| CacheError::Config("The aws cache backend needs an S3 bucket".to_string()))?;
+            tracing::info!("Using AWS S3/DynamoDB cache backend");
+            Arc::new(AwsCache::connect(bucket, config.aws.dynamodb_table.clone()).await?.with_ttl(ttl))
+        }
+        CacheBackend::Redis => {
+            tracing::info!("Using Redis cache backend");
+            Arc::new(RedisCache::from_env()?)
+        }
+        CacheBackend::Memory => {
+            tracing::info!("Using in-memory cache backend");
+            Arc::new(MemoryCache::new().with_ttl(ttl))
+        }
+        CacheBackend::Local => {
+            tracing::info!("Using local filesystem cache backend in {}", config.cache.dir.display());
+            Arc::new(LocalCache::new(config.cache.dir.clone()).with_ttl(ttl))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retryable_classification() {
+        assert!(!CacheError::NotFound.is_retryable());
+        assert!(CacheError::Io(io::Error::from(io::ErrorKind::TimedOut)).is_retryable());
+        assert!(!CacheError::Io(io::Error::from(io::ErrorKind::PermissionDenied)).is_retryable());
+
+        let throttled = CacheError::Aws {
+            operation: "PutObject",
+            retryable: true,
+            source: "SlowDown".into(),
+        };
+        assert!(throttled.is_retryable());
+        assert_eq!(throttled.to_string(), "AWS PutObject failed: SlowDown");
+    }
+
+    #[test]
+    fn test_cache_key_shape() {
+        assert!(is_valid_key(&domain::ActuatorPlate::default().cache_key()));
+        assert!(is_valid_key("plate-abc"));
+        for key in ["plate-", "plate-xyz", "../plate-abc", "plate-abc/..", "plate-ab\"c", "..", ""] {
+            assert!(!is_valid_key(key), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_checksums_catch_truncated_files() {
+        let files: CachedFiles = Artifact::ALL
+            .into_iter()
+            .map(|artifact| (artifact, Bytes::from_static(artifact.extension().as_bytes())))
+            .collect();
+        let checksums = Checksums::of(&files);
+        assert_eq!(
+            checksums.of_artifact(Artifact::Step),
+            Some("3e64cc41cf8e07b43936d7bc4eaf8bdb8e2abcec1f44c7b915ac0a0c27ebc41a")
+        );
+        assert!(checksums.verify(&files).is_ok());
+
+        let gltf = files.get(Artifact::Gltf).unwrap().slice(..2);
+        let err = checksums.verify(&files.clone().with(Artifact::Gltf, gltf)).unwrap_err();
+        assert!(matches!(err, CacheError::Corrupt { file: "model.gltf" }));
+        assert!(!err.is_retryable());
+
+        // A file written after the checksums were recorded isn't trusted either
+        let older: Checksums = checksums
+            .iter()
+            .filter(|(artifact, _)| *artifact != Artifact::Dxf)
+            .map(|(artifact, checksum)| (artifact, checksum.to_string()))
+            .collect();
+        assert!(matches!(older.verify(&files), Err(CacheError::Corrupt { file: "plate.dxf" })));
+    }
+
+    #[test]
+    fn test_checksums_are_keyed_by_extension() {
+        let files = CachedFiles::new().with(Artifact::Step, "step").with(Artifact::ThreeMf, "3mf");
+        let json = serde_json::to_value(Checksums::of(&files)).unwrap();
+        assert_eq!(json.as_object().unwrap().len(), 2);
+        assert!(json["step"].is_string() && json["3mf"].is_string(), "{}", json);
+        assert_eq!(serde_json::from_value::<Checksums>(json).unwrap(), Checksums::of(&files));
+        assert!(!files.is_complete());
+        assert_eq!(files.size(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_verified_stream_fails_at_the_end() {
+        let chunks = |parts: &'static [&'static [u8]]| ArtifactStream {
+            length: None,
+            body: stream::iter(parts.iter().map(|part| Ok(Bytes::from_static(part)))).boxed(),
+        };
+        let checksum = hex::encode(Sha256::digest(b"model.step"));
+
+        let good: Vec<_> = chunks(&[b"model", b".step"])
+            .verified(Artifact::Step, checksum.clone())
+            .body
+            .collect()
+            .await;
+        assert_eq!(good.len(), 2);
+        assert!(good.iter().all(Result::is_ok));
+
+        let truncated: Vec<_> = chunks(&[b"model"]).verified(Artifact::Step, checksum).body.collect().await;
+        assert_eq!(truncated.len(), 2);
+        assert!(matches!(truncated[1], Err(CacheError::Corrupt { file: "model.step" })));
+    }
+
+    fn config(backend: CacheBackend) -> AppConfig {
+        let mut config = AppConfig::default();
+        config.cache.backend = backend;
+        config
+    }
+
+    #[tokio::test]
+    async fn test_from_config_builds_the_selected_backend() {
+        let files = CachedFiles::new().with(Artifact::Step, "step");
+
+        let memory = from_config(&config(CacheBackend::Memory)).await.unwrap();
+        memory.put("plate-a", &files).await.unwrap();
+        assert!(memory.exists("plate-a").await);
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut local = config(CacheBackend::Local);
+        local.cache.dir = dir.path().join("models");
+        let local = from_config(&local).await.unwrap();
+        local.put("plate-a", &files).await.unwrap();
+        assert!(dir.path().join("models").join("plate-a").is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_from_config_needs_a_bucket_for_aws() {
+        let err = from_config(&config(CacheBackend::Aws)).await.err().unwrap();
+        assert!(matches!(err, CacheError::Config(_)), "{}", err);
+    }
 }
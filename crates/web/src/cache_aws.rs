@@ -1,17 +1,123 @@
 //! AWS S3 + DynamoDB cache implementation for production.
+//!
+//! Also works against S3-compatible stores such as MinIO: set a custom endpoint,
+//! path-style addressing, and static credentials, and omit the DynamoDB table to
+//! use S3 object lookups as the index instead.
+//!
+//! With a TTL, expiry is left to the stores: each DynamoDB item carries a
+//! `ttl` attribute (epoch seconds) for DynamoDB TTL, and each S3 object is
+//! tagged `cache-ttl-days=N` so a lifecycle rule filtered on that tag can expire it.
+//! Until the stores catch up, expired entries are reported as missing.
+//!
+//! Each entry's file checksums are stored as `sha256_*` attributes on its DynamoDB
+//! item, or in a `manifest.json` object without DynamoDB, and checked on `get`.
 
 use async_trait::async_trait;
 use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::operation::get_object::GetObjectOutput;
 use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+use futures_util::stream::{self, StreamExt};
+use std::time::Duration;
 
-use crate::cache::{CacheError, CachedFiles, ModelCache};
+use crate::cache::{Artifact, ArtifactStream, CacheError, CachedFiles, Checksums, ModelCache};
+
+/// DynamoDB attribute holding the expiry time; the table's TTL setting must name it.
+const TTL_ATTRIBUTE: &str = "ttl";
+
+/// Attribute holding one file's checksum, e.g. `sha256_step`.
+fn checksum_attribute(artifact: Artifact) -> String {
+    format!("sha256_{}", artifact.extension())
+}
+
+fn checksum_attributes(checksums: &Checksums) -> Vec<(String, String)> {
+    checksums
+        .iter()
+        .map(|(artifact, checksum)| (checksum_attribute(artifact), checksum.to_string()))
+        .collect()
+}
+
+/// The checksums recorded on an item. Items written before checksums existed
+/// have none, and their entries are served unverified; items written before a
+/// format was added lack its checksum, and its file, so they are misses.
+fn item_checksums(item: &std::collections::HashMap<String, AttributeValue>) -> Option<Checksums> {
+    let checksums: Checksums = Artifact::ALL
+        .into_iter()
+        .filter_map(|artifact| Some((artifact, item.get(&checksum_attribute(artifact))?.as_s().ok()?.clone())))
+        .collect();
+    (checksums != Checksums::default()).then_some(checksums)
+}
 
 /// AWS cache implementation using S3 for file storage and DynamoDB for lookup.
+///
+/// When no DynamoDB table is configured, the presence of the STL object (the last
+/// file written by `put`) marks a complete entry.
 pub struct AwsCache {
     s3_client: aws_sdk_s3::Client,
-    dynamo_client: aws_sdk_dynamodb::Client,
+    dynamo: Option<(aws_sdk_dynamodb::Client, String)>,
     bucket: String,
-    table: String,
+    ttl: Option<Duration>,
+}
+
+/// Connection options for S3-compatible object stores.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct S3Options {
+    /// Custom endpoint, e.g. `http://minio:9000`. Uses AWS when unset.
+    pub endpoint_url: Option<String>,
+    /// Address buckets as `{endpoint}/{bucket}` rather than `{bucket}.{endpoint}`.
+    /// MinIO and most on-prem stores need this.
+    pub force_path_style: bool,
+    /// Region name; many S3-compatible stores accept any value.
+    pub region: Option<String>,
+    /// Static access key; uses the default AWS credential chain when unset.
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+}
+
+impl S3Options {
+    /// Read options from S3_ENDPOINT_URL, S3_FORCE_PATH_STYLE, S3_REGION,
+    /// S3_ACCESS_KEY_ID, and S3_SECRET_ACCESS_KEY.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let non_empty = |key: &str| lookup(key).filter(|v| !v.is_empty());
+        Self {
+            endpoint_url: non_empty("S3_ENDPOINT_URL"),
+            force_path_style: non_empty("S3_FORCE_PATH_STYLE")
+                .is_some_and(|v| matches!(v.as_str(), "1" | "true" | "yes")),
+            region: non_empty("S3_REGION"),
+            access_key_id: non_empty("S3_ACCESS_KEY_ID"),
+            secret_access_key: non_empty("S3_SECRET_ACCESS_KEY"),
+        }
+    }
+
+    /// Build an S3 client from the shared AWS config with these overrides applied.
+    pub fn build_client(&self, sdk_config: &aws_config::SdkConfig) -> aws_sdk_s3::Client {
+        let mut builder = aws_sdk_s3::config::Builder::from(sdk_config);
+        if let Some(endpoint_url) = &self.endpoint_url {
+            builder = builder.endpoint_url(endpoint_url);
+        }
+        if self.force_path_style {
+            builder = builder.force_path_style(true);
+        }
+        if let Some(region) = &self.region {
+            builder = builder.region(aws_sdk_s3::config::Region::new(region.clone()));
+        }
+        if let (Some(key), Some(secret)) = (&self.access_key_id, &self.secret_access_key) {
+            builder = builder.credentials_provider(aws_sdk_s3::config::Credentials::new(
+                key.clone(),
+                secret.clone(),
+                None,
+                None,
+                "platerator-static",
+            ));
+        }
+        aws_sdk_s3::Client::from_conf(builder.build())
+    }
 }
 
 impl AwsCache {
@@ -24,185 +130,448 @@ impl AwsCache {
     ) -> Self {
         Self {
             s3_client,
-            dynamo_client,
+            dynamo: Some((dynamo_client, table)),
             bucket,
-            table,
+            ttl: None,
         }
     }
 
+    /// Create an AwsCache that uses only S3, for S3-compatible stores without DynamoDB.
+    pub fn s3_only(s3_client: aws_sdk_s3::Client, bucket: String) -> Self {
+        Self {
+            s3_client,
+            dynamo: None,
+            bucket,
+            ttl: None,
+        }
+    }
+
+    /// Expire entries `ttl` after they are written. See the module docs for how.
+    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
     /// Create a new AwsCache from environment variables.
-    /// Requires S3_BUCKET_NAME and DYNAMODB_TABLE to be set.
+    /// Requires S3_BUCKET_NAME. DYNAMODB_TABLE is required unless S3_ENDPOINT_URL
+    /// points at an S3-compatible store; see [`S3Options::from_env`] for the rest.
     pub async fn from_env() -> Result<Self, CacheError> {
         let bucket = std::env::var("S3_BUCKET_NAME")
-            .map_err(|_| CacheError::AwsError("S3_BUCKET_NAME not set".to_string()))?;
-        let table = std::env::var("DYNAMODB_TABLE")
-            .map_err(|_| CacheError::AwsError("DYNAMODB_TABLE not set".to_string()))?;
+            .map_err(|_| CacheError::Config("S3_BUCKET_NAME not set".to_string()))?;
+        Self::connect(bucket, std::env::var("DYNAMODB_TABLE").ok()).await
+    }
+
+    /// Connect to `bucket`, indexed by `table`, with the AWS credentials and
+    /// [`S3Options`] from the environment. `table` may only be omitted when
+    /// S3_ENDPOINT_URL points at an S3-compatible store.
+    pub async fn connect(bucket: String, table: Option<String>) -> Result<Self, CacheError> {
+        let options = S3Options::from_env();
 
         let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
-        let s3_client = aws_sdk_s3::Client::new(&config);
-        let dynamo_client = aws_sdk_dynamodb::Client::new(&config);
+        let s3_client = options.build_client(&config);
 
-        Ok(Self::new(s3_client, dynamo_client, bucket, table))
+        match table {
+            Some(table) => {
+                let dynamo_client = aws_sdk_dynamodb::Client::new(&config);
+                Ok(Self::new(s3_client, dynamo_client, bucket, table))
+            }
+            None if options.endpoint_url.is_some() => {
+                tracing::info!("No DYNAMODB_TABLE set; indexing cache entries by S3 object");
+                Ok(Self::s3_only(s3_client, bucket))
+            }
+            None => Err(CacheError::Config("DYNAMODB_TABLE not set".to_string())),
+        }
     }
 
-    fn step_key(&self, cache_key: &str) -> String {
-        format!("{}/model.step", cache_key)
+    fn manifest_key(&self, cache_key: &str) -> String {
+        format!("{}/manifest.json", cache_key)
     }
 
-    fn gltf_key(&self, cache_key: &str) -> String {
-        format!("{}/model.gltf", cache_key)
+    /// Tag for lifecycle rules, e.g. `cache-ttl-days=7`. Lifecycle expiration works
+    /// in whole days, so the TTL is rounded up.
+    fn ttl_tag(&self) -> Option<String> {
+        self.ttl
+            .map(|ttl| format!("cache-ttl-days={}", ttl.as_secs().div_ceil(86_400).max(1)))
     }
 
-    fn stl_key(&self, cache_key: &str) -> String {
-        format!("{}/model.stl", cache_key)
+    fn is_expired(&self, written_at_secs: i64) -> bool {
+        self.ttl.is_some_and(|ttl| {
+            written_at_secs.saturating_add(ttl.as_secs() as i64) <= chrono::Utc::now().timestamp()
+        })
     }
-}
 
-#[async_trait]
-impl ModelCache for AwsCache {
-    async fn exists(&self, cache_key: &str) -> bool {
-        let result = self
-            .dynamo_client
-            .get_item()
-            .table_name(&self.table)
-            .key("plate_hash", AttributeValue::S(cache_key.to_string()))
-            .send()
-            .await;
+    /// Whether a DynamoDB item is past its `ttl`. DynamoDB TTL deletes
+    /// expired items lazily, typically within a few days.
+    fn item_expired(&self, item: &std::collections::HashMap<String, AttributeValue>) -> bool {
+        item.get(TTL_ATTRIBUTE)
+            .and_then(|v| v.as_n().ok())
+            .and_then(|n| n.parse::<i64>().ok())
+            .is_some_and(|expires_at| expires_at <= chrono::Utc::now().timestamp())
+    }
 
-        match result {
-            Ok(output) => output.item.is_some(),
+    /// Whether the object exists and, with a TTL, hasn't outlived it.
+    async fn s3_object_exists(&self, key: &str) -> bool {
+        match self
+            .s3_client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(output) => !output
+                .last_modified()
+                .is_some_and(|modified| self.is_expired(modified.secs())),
             Err(e) => {
-                tracing::warn!("DynamoDB lookup error: {}", e);
+                if !e.as_service_error().is_some_and(|e| e.is_not_found()) {
+                    tracing::warn!("S3 lookup error: {}", e);
+                }
                 false
             }
         }
     }
+}
 
-    async fn get(&self, cache_key: &str) -> Result<CachedFiles, CacheError> {
-        // Check DynamoDB first
-        let dynamo_result = self
-            .dynamo_client
-            .get_item()
-            .table_name(&self.table)
-            .key("plate_hash", AttributeValue::S(cache_key.to_string()))
+impl AwsCache {
+    /// Start downloading one file of an entry. A missing object means the entry is
+    /// incomplete (e.g. cached before drawings existed), which is a cache miss.
+    async fn get_object(&self, key: String) -> Result<GetObjectOutput, CacheError> {
+        match self
+            .s3_client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
             .send()
             .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?;
+        {
+            Ok(output) => Ok(output),
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_no_such_key()) => Err(CacheError::NotFound),
+            Err(e) => Err(aws_error("GetObject", e)),
+        }
+    }
+
+    /// Download one file of an entry whole.
+    async fn get_artifact(&self, key: String) -> Result<Bytes, CacheError> {
+        let output = self.get_object(key).await?;
+        Ok(output.body.collect().await.map_err(body_error)?.into_bytes())
+    }
 
-        if dynamo_result.item.is_none() {
-            return Err(CacheError::NotFound);
+    fn artifact_key(&self, cache_key: &str, artifact: Artifact) -> String {
+        format!("{}/{}", cache_key, artifact.file_name())
+    }
+
+    /// Confirm the entry is complete and unexpired, returning its checksums if it
+    /// has any. Entries written before checksums existed are served unverified.
+    async fn lookup(&self, cache_key: &str) -> Result<Option<Checksums>, CacheError> {
+        match &self.dynamo {
+            Some((dynamo_client, table)) => {
+                let dynamo_result = dynamo_client
+                    .get_item()
+                    .table_name(table)
+                    .key("plate_hash", AttributeValue::S(cache_key.to_string()))
+                    .send()
+                    .await
+                    .map_err(|e| aws_error("GetItem", e))?;
+
+                match dynamo_result.item {
+                    Some(item) if !self.item_expired(&item) => Ok(item_checksums(&item)),
+                    _ => Err(CacheError::NotFound),
+                }
+            }
+            None => {
+                if !self.s3_object_exists(&self.artifact_key(cache_key, Artifact::Stl)).await {
+                    return Err(CacheError::NotFound);
+                }
+                match self.get_artifact(self.manifest_key(cache_key)).await {
+                    Ok(manifest) => serde_json::from_slice(&manifest)
+                        .map(Some)
+                        .map_err(|_| CacheError::Corrupt { file: "manifest.json" }),
+                    Err(CacheError::NotFound) => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
         }
+    }
 
-        // Fetch STEP file from S3
-        let step_result = self
-            .s3_client
-            .get_object()
+    async fn put_artifact(&self, key: String, data: &Bytes, content_type: &str) -> Result<(), CacheError> {
+        self.s3_client
+            .put_object()
             .bucket(&self.bucket)
-            .key(self.step_key(cache_key))
+            .key(key)
+            .body(ByteStream::from(data.clone()))
+            .content_type(content_type)
+            .set_tagging(self.ttl_tag())
             .send()
             .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?;
+            .map_err(|e| aws_error("PutObject", e))?;
+        Ok(())
+    }
+}
 
-        let step_data = step_result
-            .body
-            .collect()
-            .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?
-            .into_bytes()
-            .to_vec();
+/// Classify an SDK failure: timeouts, dropped connections, throttling, and 5xx
+/// responses are worth retrying; anything else is not.
+fn aws_error<E>(operation: &'static str, err: SdkError<E, HttpResponse>) -> CacheError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let retryable = match &err {
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) | SdkError::ResponseError(_) => true,
+        SdkError::ServiceError(e) => {
+            let status = e.raw().status().as_u16();
+            status == 429 || status >= 500
+        }
+        _ => false,
+    };
+    CacheError::Aws {
+        operation,
+        retryable,
+        source: Box::new(err),
+    }
+}
 
-        // Fetch glTF file from S3
-        let gltf_result = self
-            .s3_client
-            .get_object()
+/// A failure streaming an object body, which is always a transport problem.
+fn body_error(err: aws_sdk_s3::primitives::ByteStreamError) -> CacheError {
+    CacheError::Aws {
+        operation: "GetObject",
+        retryable: true,
+        source: Box::new(err),
+    }
+}
+
+#[async_trait]
+impl ModelCache for AwsCache {
+    /// Head the bucket and look up a key that never exists in the index, which
+    /// needs no permissions beyond what caching already uses.
+    async fn check(&self) -> Result<(), CacheError> {
+        self.s3_client
+            .head_bucket()
             .bucket(&self.bucket)
-            .key(self.gltf_key(cache_key))
             .send()
             .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?;
+            .map_err(|e| aws_error("HeadBucket", e))?;
+        if let Some((dynamo_client, table)) = &self.dynamo {
+            dynamo_client
+                .get_item()
+                .table_name(table)
+                .key("plate_hash", AttributeValue::S("readiness-probe".to_string()))
+                .send()
+                .await
+                .map_err(|e| aws_error("GetItem", e))?;
+        }
+        Ok(())
+    }
 
-        let gltf_data = gltf_result
-            .body
-            .collect()
-            .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?
-            .into_bytes()
-            .to_vec();
+    async fn exists(&self, cache_key: &str) -> bool {
+        let Some((dynamo_client, table)) = &self.dynamo else {
+            return self.s3_object_exists(&self.artifact_key(cache_key, Artifact::Stl)).await;
+        };
 
-        // Fetch STL file from S3
-        let stl_result = self
-            .s3_client
-            .get_object()
-            .bucket(&self.bucket)
-            .key(self.stl_key(cache_key))
+        let result = dynamo_client
+            .get_item()
+            .table_name(table)
+            .key("plate_hash", AttributeValue::S(cache_key.to_string()))
             .send()
-            .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?;
+            .await;
 
-        let stl_data = stl_result
-            .body
-            .collect()
-            .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?
-            .into_bytes()
-            .to_vec();
+        match result {
+            Ok(output) => output.item.is_some_and(|item| !self.item_expired(&item)),
+            Err(e) => {
+                tracing::warn!("DynamoDB lookup error: {}", e);
+                false
+            }
+        }
+    }
+
+    async fn get(&self, cache_key: &str) -> Result<CachedFiles, CacheError> {
+        // Check the index first
+        let checksums = self.lookup(cache_key).await?;
+
+        let mut files = CachedFiles::new();
+        for artifact in Artifact::ALL {
+            files.insert(artifact, self.get_artifact(self.artifact_key(cache_key, artifact)).await?);
+        }
+        if let Some(checksums) = checksums {
+            checksums.verify(&files)?;
+        }
 
         tracing::info!("Cache hit for key: {}", cache_key);
 
-        Ok(CachedFiles {
-            step_data,
-            gltf_data,
-            stl_data,
+        Ok(files)
+    }
+
+    /// Pass the object body through as S3 sends it, so large STEP files never sit
+    /// in memory whole.
+    async fn stream(&self, cache_key: &str, artifact: Artifact) -> Result<ArtifactStream, CacheError> {
+        let checksums = self.lookup(cache_key).await?;
+        let output = self.get_object(self.artifact_key(cache_key, artifact)).await?;
+        let length = output.content_length().and_then(|n| u64::try_from(n).ok());
+        // Stop at the first error rather than asking a failed body for more
+        let body = stream::unfold(Some(output.body), |body| async move {
+            let mut body = body?;
+            match body.try_next().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(body))),
+                Ok(None) => None,
+                Err(e) => Some((Err(body_error(e)), None)),
+            }
+        });
+        let stream = ArtifactStream { length, body: body.boxed() };
+        Ok(match checksums {
+            Some(checksums) => {
+                let checksum = checksums.of_artifact(artifact).ok_or(CacheError::NotFound)?;
+                stream.verified(artifact, checksum.to_string())
+            }
+            None => stream,
         })
     }
 
     async fn put(&self, cache_key: &str, files: &CachedFiles) -> Result<(), CacheError> {
-        // Upload STEP file to S3
-        self.s3_client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(self.step_key(cache_key))
-            .body(ByteStream::from(files.step_data.clone()))
-            .content_type("application/STEP")
-            .send()
-            .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?;
-
-        // Upload glTF file to S3
-        self.s3_client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(self.gltf_key(cache_key))
-            .body(ByteStream::from(files.gltf_data.clone()))
-            .content_type("model/gltf+json")
-            .send()
-            .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?;
-
-        // Upload STL file to S3
-        self.s3_client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(self.stl_key(cache_key))
-            .body(ByteStream::from(files.stl_data.clone()))
-            .content_type("model/stl")
-            .send()
-            .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?;
+        for (artifact, data) in files.iter().filter(|(artifact, _)| *artifact != Artifact::Stl) {
+            self.put_artifact(self.artifact_key(cache_key, artifact), data, artifact.content_type())
+                .await?;
+        }
+        let checksums = Checksums::of(files);
+        if self.dynamo.is_none() {
+            let manifest = serde_json::to_vec(&checksums).map_err(|e| CacheError::Io(std::io::Error::other(e)))?;
+            self.put_artifact(self.manifest_key(cache_key), &Bytes::from(manifest), "application/json")
+                .await?;
+        }
+        // The STL goes last: its presence marks the entry complete
+        if let Some(stl) = files.get(Artifact::Stl) {
+            self.put_artifact(self.artifact_key(cache_key, Artifact::Stl), stl, Artifact::Stl.content_type())
+                .await?;
+        }
 
         // Record in DynamoDB
-        let now = chrono::Utc::now().to_rfc3339();
-        self.dynamo_client
-            .put_item()
-            .table_name(&self.table)
-            .item("plate_hash", AttributeValue::S(cache_key.to_string()))
-            .item("created_at", AttributeValue::S(now))
-            .send()
-            .await
-            .map_err(|e| CacheError::AwsError(e.to_string()))?;
+        if let Some((dynamo_client, table)) = &self.dynamo {
+            let now = chrono::Utc::now();
+            let mut item = dynamo_client
+                .put_item()
+                .table_name(table)
+                .item("plate_hash", AttributeValue::S(cache_key.to_string()))
+                .item("created_at", AttributeValue::S(now.to_rfc3339()));
+            for (name, checksum) in checksum_attributes(&checksums) {
+                item = item.item(name, AttributeValue::S(checksum));
+            }
+            if let Some(ttl) = self.ttl {
+                let expires_at = now.timestamp() + ttl.as_secs() as i64;
+                item = item.item(TTL_ATTRIBUTE, AttributeValue::N(expires_at.to_string()));
+            }
+            item.send()
+                .await
+                .map_err(|e| aws_error("PutItem", e))?;
+        }
 
         tracing::info!("Cached files for key: {}", cache_key);
 
         Ok(())
     }
+
+    async fn keys(&self) -> Result<Vec<String>, CacheError> {
+        // The STL is written last, so its presence marks a complete entry
+        let mut keys = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .s3_client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .map_err(|e| aws_error("ListObjectsV2", e))?;
+
+            keys.extend(
+                page.contents()
+                    .iter()
+                    .filter_map(|object| object.key()?.strip_suffix("/model.stl"))
+                    .map(str::to_string),
+            );
+
+            match page.next_continuation_token() {
+                Some(token) => continuation_token = Some(token.to_string()),
+                None => return Ok(keys),
+            }
+        }
+    }
+
+    async fn delete(&self, cache_key: &str) -> Result<(), CacheError> {
+        // Drop the index first, then the STL that marks a complete entry, so a
+        // partially deleted entry is never reported as present.
+        if let Some((dynamo_client, table)) = &self.dynamo {
+            dynamo_client
+                .delete_item()
+                .table_name(table)
+                .key("plate_hash", AttributeValue::S(cache_key.to_string()))
+                .send()
+                .await
+                .map_err(|e| aws_error("DeleteItem", e))?;
+        }
+
+        // DeleteObject succeeds for keys that don't exist
+        let keys = Artifact::ALL.map(|artifact| self.artifact_key(cache_key, artifact));
+        for key in keys.into_iter().chain([self.manifest_key(cache_key)]) {
+            self.s3_client
+                .delete_object()
+                .bucket(&self.bucket)
+                .key(key)
+                .send()
+                .await
+                .map_err(|e| aws_error("DeleteObject", e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_s3_options_for_minio() {
+        let env: HashMap<&str, &str> = [
+            ("S3_ENDPOINT_URL", "http://minio:9000"),
+            ("S3_FORCE_PATH_STYLE", "true"),
+            ("S3_ACCESS_KEY_ID", "minioadmin"),
+            ("S3_SECRET_ACCESS_KEY", "minioadmin"),
+        ]
+        .into_iter()
+        .collect();
+
+        let options = S3Options::from_lookup(|key| env.get(key).map(|v| v.to_string()));
+
+        assert_eq!(options.endpoint_url.as_deref(), Some("http://minio:9000"));
+        assert!(options.force_path_style);
+        assert_eq!(options.region, None);
+        assert_eq!(options.access_key_id.as_deref(), Some("minioadmin"));
+    }
+
+    #[test]
+    fn test_s3_options_default_to_aws() {
+        let options = S3Options::from_lookup(|_| None);
+        assert_eq!(options, S3Options::default());
+    }
+
+    #[test]
+    fn test_checksums_round_trip_through_item_attributes() {
+        let checksums = Checksums::of(
+            &Artifact::ALL
+                .into_iter()
+                .map(|artifact| (artifact, Bytes::from_static(artifact.extension().as_bytes())))
+                .collect(),
+        );
+        let mut item: HashMap<String, AttributeValue> = checksum_attributes(&checksums)
+            .into_iter()
+            .map(|(name, checksum)| (name, AttributeValue::S(checksum)))
+            .collect();
+        assert!(item.contains_key("sha256_3mf"));
+        assert_eq!(item_checksums(&item), Some(checksums));
+
+        // Written before PDFs existed: the PDF is missing, not unverified
+        item.remove("sha256_pdf");
+        assert_eq!(item_checksums(&item).unwrap().of_artifact(Artifact::Pdf), None);
+
+        item.clear();
+        assert_eq!(item_checksums(&item), None);
+    }
 }
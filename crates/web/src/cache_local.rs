@@ -1,19 +1,31 @@
 //! Local filesystem cache implementation for development.
 
 use async_trait::async_trait;
-use std::path::PathBuf;
+use bytes::Bytes;
+use futures_util::{StreamExt, TryStreamExt};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use crate::cache::{CacheError, CachedFiles, ModelCache};
+use crate::cache::{is_valid_key, Artifact, ArtifactStream, CacheError, CachedFiles, Checksums, ModelCache};
 
 /// Local filesystem cache implementation.
-/// Stores files in a directory structure: `{base_dir}/{cache_key}/model.step` and `model.gltf`.
+/// Stores files in a directory structure: `{base_dir}/{cache_key}/model.step` and so on,
+/// one file per [`Artifact::file_name`], with their checksums in `manifest.json`.
 pub struct LocalCache {
     base_dir: PathBuf,
+    ttl: Option<Duration>,
 }
 
 impl LocalCache {
     pub fn new(base_dir: PathBuf) -> Self {
-        Self { base_dir }
+        Self { base_dir, ttl: None }
+    }
+
+    /// Treat entries written more than `ttl` ago as missing; `evict_expired`
+    /// deletes them.
+    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
     }
 
     /// Create a LocalCache with the default cache directory ("./cache").
@@ -21,103 +33,266 @@ impl LocalCache {
         Self::new(PathBuf::from("./cache"))
     }
 
-    fn cache_dir(&self, cache_key: &str) -> PathBuf {
-        self.base_dir.join(cache_key)
+    /// The entry's directory. Keys are checked first, since a key like `../x`
+    /// would otherwise name a directory outside `base_dir`.
+    fn cache_dir(&self, cache_key: &str) -> Result<PathBuf, CacheError> {
+        if !is_valid_key(cache_key) {
+            return Err(CacheError::InvalidKey(cache_key.to_string()));
+        }
+        Ok(self.base_dir.join(cache_key))
+    }
+
+    fn artifact_path(&self, cache_key: &str, artifact: Artifact) -> Result<PathBuf, CacheError> {
+        Ok(self.cache_dir(cache_key)?.join(artifact.file_name()))
+    }
+
+    fn manifest_path(&self, cache_key: &str) -> Result<PathBuf, CacheError> {
+        Ok(self.cache_dir(cache_key)?.join("manifest.json"))
     }
 
-    fn step_path(&self, cache_key: &str) -> PathBuf {
-        self.cache_dir(cache_key).join("model.step")
+    /// The entry's recorded checksums. Entries cached before checksums existed
+    /// have none and are served unverified.
+    async fn checksums(&self, cache_key: &str) -> Result<Option<Checksums>, CacheError> {
+        let manifest = match tokio::fs::read(self.manifest_path(cache_key)?).await {
+            Ok(manifest) => manifest,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_slice(&manifest)
+            .map(Some)
+            .map_err(|_| CacheError::Corrupt { file: "manifest.json" })
+    }
+
+    /// Entries cached before a format was added are incomplete, so they are
+    /// regenerated once on their next request.
+    async fn is_complete(&self, cache_key: &str) -> bool {
+        let Ok(dir) = self.cache_dir(cache_key) else {
+            return false;
+        };
+        for artifact in Artifact::ALL {
+            if !tokio::fs::try_exists(dir.join(artifact.file_name())).await.unwrap_or(false) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether the entry is older than the TTL. The STL is written last, so its
+    /// modification time is when the entry was completed; entries without one
+    /// (interrupted writes) are aged by their directory instead.
+    async fn is_expired(&self, cache_key: &str) -> bool {
+        let (Some(ttl), Ok(dir)) = (self.ttl, self.cache_dir(cache_key)) else {
+            return false;
+        };
+        let modified = match tokio::fs::metadata(dir.join(Artifact::Stl.file_name())).await {
+            Ok(metadata) => metadata.modified(),
+            Err(_) => match tokio::fs::metadata(&dir).await {
+                Ok(metadata) => metadata.modified(),
+                Err(_) => return false,
+            },
+        };
+        modified
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= ttl)
+    }
+
+    /// Names of every entry directory, complete or not. Directories that aren't
+    /// named like a cache key aren't entries and are left alone.
+    async fn entry_dirs(&self) -> Result<Vec<String>, CacheError> {
+        let mut dir = match tokio::fs::read_dir(&self.base_dir).await {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut names = Vec::new();
+        while let Some(entry) = dir.next_entry().await? {
+            if entry.file_type().await?.is_dir() {
+                if let Some(name) = entry.file_name().to_str().filter(|name| is_valid_key(name)) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        Ok(names)
     }
+}
 
-    fn gltf_path(&self, cache_key: &str) -> PathBuf {
-        self.cache_dir(cache_key).join("model.gltf")
+async fn remove_dir(path: &Path) -> Result<(), CacheError> {
+    match tokio::fs::remove_dir_all(path).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
     }
+}
+
+/// Files at least this large are memory-mapped instead of read onto the heap.
+const MMAP_THRESHOLD: u64 = 1024 * 1024;
+
+/// Load a cached file. Large files are memory-mapped, so serving them is backed by the
+/// page cache rather than a heap copy per concurrent download.
+async fn read_artifact(path: PathBuf) -> Result<Bytes, CacheError> {
+    let result = tokio::task::spawn_blocking(move || {
+        let file = std::fs::File::open(&path)?;
+        if file.metadata()?.len() >= MMAP_THRESHOLD {
+            map_file(&file)
+        } else {
+            let mut data = Vec::new();
+            std::io::Read::read_to_end(&mut &file, &mut data)?;
+            Ok(Bytes::from(data))
+        }
+    })
+    .await
+    .map_err(std::io::Error::other)?;
+
+    result.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            CacheError::NotFound
+        } else {
+            CacheError::Io(e)
+        }
+    })
+}
+
+#[cfg(unix)]
+fn map_file(file: &std::fs::File) -> std::io::Result<Bytes> {
+    // SAFETY: cache files are never modified in place. `write_atomic` replaces them by
+    // renaming a new file over the old one, which leaves existing mappings of the old
+    // inode intact.
+    let map = unsafe { memmap2::Mmap::map(file)? };
+    Ok(Bytes::from_owner(map))
+}
+
+// Windows can't rename over a mapped file, so read it instead.
+#[cfg(not(unix))]
+fn map_file(file: &std::fs::File) -> std::io::Result<Bytes> {
+    let mut data = Vec::new();
+    std::io::Read::read_to_end(&mut &*file, &mut data)?;
+    Ok(Bytes::from(data))
+}
 
-    fn stl_path(&self, cache_key: &str) -> PathBuf {
-        self.cache_dir(cache_key).join("model.stl")
+/// Write `data` to a temporary file beside `path`, then rename it into place.
+async fn write_atomic(path: &std::path::Path, data: &[u8]) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", uuid::Uuid::new_v4()));
+    tokio::fs::write(&tmp, data).await?;
+    if let Err(e) = tokio::fs::rename(&tmp, path).await {
+        tokio::fs::remove_file(&tmp).await.ok();
+        return Err(e);
     }
+    Ok(())
 }
 
 #[async_trait]
 impl ModelCache for LocalCache {
+    /// The cache directory exists, or can be created, and is a directory.
+    async fn check(&self) -> Result<(), CacheError> {
+        tokio::fs::create_dir_all(&self.base_dir).await?;
+        Ok(())
+    }
+
     async fn exists(&self, cache_key: &str) -> bool {
-        let step_path = self.step_path(cache_key);
-        let gltf_path = self.gltf_path(cache_key);
-        let stl_path = self.stl_path(cache_key);
-        tokio::fs::try_exists(&step_path).await.unwrap_or(false)
-            && tokio::fs::try_exists(&gltf_path).await.unwrap_or(false)
-            && tokio::fs::try_exists(&stl_path).await.unwrap_or(false)
+        self.is_complete(cache_key).await && !self.is_expired(cache_key).await
     }
 
     async fn get(&self, cache_key: &str) -> Result<CachedFiles, CacheError> {
-        let step_path = self.step_path(cache_key);
-        let gltf_path = self.gltf_path(cache_key);
-        let stl_path = self.stl_path(cache_key);
-
-        let step_data = tokio::fs::read(&step_path)
-            .await
-            .map_err(|e| {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    CacheError::NotFound
-                } else {
-                    CacheError::IoError(e.to_string())
-                }
-            })?;
-
-        let gltf_data = tokio::fs::read(&gltf_path)
-            .await
-            .map_err(|e| {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    CacheError::NotFound
-                } else {
-                    CacheError::IoError(e.to_string())
-                }
-            })?;
-
-        let stl_data = tokio::fs::read(&stl_path)
-            .await
-            .map_err(|e| {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    CacheError::NotFound
-                } else {
-                    CacheError::IoError(e.to_string())
-                }
-            })?;
+        if self.is_expired(cache_key).await {
+            return Err(CacheError::NotFound);
+        }
+
+        let mut files = CachedFiles::new();
+        for artifact in Artifact::ALL {
+            files.insert(artifact, read_artifact(self.artifact_path(cache_key, artifact)?).await?);
+        }
+        if let Some(checksums) = self.checksums(cache_key).await? {
+            checksums.verify(&files)?;
+        }
+        Ok(files)
+    }
 
-        Ok(CachedFiles {
-            step_data,
-            gltf_data,
-            stl_data,
+    /// Read the file in chunks rather than mapping it, so its pages needn't stay
+    /// resident while a slow client downloads it.
+    async fn stream(&self, cache_key: &str, artifact: Artifact) -> Result<ArtifactStream, CacheError> {
+        let path = self.artifact_path(cache_key, artifact)?;
+        if !self.exists(cache_key).await {
+            return Err(CacheError::NotFound);
+        }
+        let checksums = self.checksums(cache_key).await?;
+        let file = match tokio::fs::File::open(path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(CacheError::NotFound),
+            Err(e) => return Err(e.into()),
+        };
+        let length = file.metadata().await?.len();
+        let body = tokio_util::io::ReaderStream::new(file).map_err(CacheError::Io);
+        let stream = ArtifactStream { length: Some(length), body: body.boxed() };
+        Ok(match checksums {
+            Some(checksums) => {
+                let checksum = checksums.of_artifact(artifact).ok_or(CacheError::Corrupt { file: "manifest.json" })?;
+                stream.verified(artifact, checksum.to_string())
+            }
+            None => stream,
         })
     }
 
     async fn put(&self, cache_key: &str, files: &CachedFiles) -> Result<(), CacheError> {
-        let cache_dir = self.cache_dir(cache_key);
+        let cache_dir = self.cache_dir(cache_key)?;
 
         // Create the cache directory if it doesn't exist
-        tokio::fs::create_dir_all(&cache_dir)
-            .await
-            .map_err(|e| CacheError::IoError(e.to_string()))?;
+        tokio::fs::create_dir_all(&cache_dir).await?;
+
+        for (artifact, data) in files.iter().filter(|(artifact, _)| *artifact != Artifact::Stl) {
+            write_atomic(&cache_dir.join(artifact.file_name()), data).await?;
+        }
+        let manifest = serde_json::to_vec(&Checksums::of(files)).map_err(std::io::Error::other)?;
+        write_atomic(&self.manifest_path(cache_key)?, &manifest).await?;
+        // The STL goes last: its modification time marks the entry complete
+        if let Some(stl) = files.get(Artifact::Stl) {
+            write_atomic(&cache_dir.join(Artifact::Stl.file_name()), stl).await?;
+        }
 
-        let step_path = self.step_path(cache_key);
-        let gltf_path = self.gltf_path(cache_key);
-        let stl_path = self.stl_path(cache_key);
+        tracing::info!("Cached files for key: {}", cache_key);
 
-        tokio::fs::write(&step_path, &files.step_data)
-            .await
-            .map_err(|e| CacheError::IoError(e.to_string()))?;
+        Ok(())
+    }
 
-        tokio::fs::write(&gltf_path, &files.gltf_data)
-            .await
-            .map_err(|e| CacheError::IoError(e.to_string()))?;
+    async fn keys(&self) -> Result<Vec<String>, CacheError> {
+        let mut keys = Vec::new();
+        for key in self.entry_dirs().await? {
+            // Skip directories left behind by interrupted writes
+            if self.exists(&key).await {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    }
 
-        tokio::fs::write(&stl_path, &files.stl_data)
-            .await
-            .map_err(|e| CacheError::IoError(e.to_string()))?;
+    async fn delete(&self, cache_key: &str) -> Result<(), CacheError> {
+        remove_dir(&self.cache_dir(cache_key)?).await
+    }
 
-        tracing::info!("Cached files for key: {}", cache_key);
+    async fn clear(&self) -> Result<usize, CacheError> {
+        let mut removed = 0;
+        for key in self.entry_dirs().await? {
+            if self.is_complete(&key).await {
+                removed += 1;
+            }
+            remove_dir(&self.cache_dir(&key)?).await?;
+        }
+        Ok(removed)
+    }
 
-        Ok(())
+    async fn evict_expired(&self) -> Result<usize, CacheError> {
+        if self.ttl.is_none() {
+            return Ok(0);
+        }
+        let mut removed = 0;
+        for key in self.entry_dirs().await? {
+            if self.is_expired(&key).await {
+                remove_dir(&self.cache_dir(&key)?).await?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
     }
 }
 
@@ -131,22 +306,27 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let cache = LocalCache::new(temp_dir.path().to_path_buf());
 
-        let files = CachedFiles {
-            step_data: b"step content".to_vec(),
-            gltf_data: b"gltf content".to_vec(),
-            stl_data: b"stl content".to_vec(),
-        };
+        let files = CachedFiles::new()
+            .with(Artifact::Step, "step content")
+            .with(Artifact::Gltf, "gltf content")
+            .with(Artifact::Stl, "stl content")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
 
-        assert!(!cache.exists("test-key").await);
+        assert!(!cache.exists("plate-7e57").await);
 
-        cache.put("test-key", &files).await.unwrap();
+        cache.put("plate-7e57", &files).await.unwrap();
 
-        assert!(cache.exists("test-key").await);
+        assert!(cache.exists("plate-7e57").await);
 
-        let retrieved = cache.get("test-key").await.unwrap();
-        assert_eq!(retrieved.step_data, files.step_data);
-        assert_eq!(retrieved.gltf_data, files.gltf_data);
-        assert_eq!(retrieved.stl_data, files.stl_data);
+        let retrieved = cache.get("plate-7e57").await.unwrap();
+        assert_eq!(retrieved.get(Artifact::Step).unwrap(), files.get(Artifact::Step).unwrap());
+        assert_eq!(retrieved.get(Artifact::Gltf).unwrap(), files.get(Artifact::Gltf).unwrap());
+        assert_eq!(retrieved.get(Artifact::Stl).unwrap(), files.get(Artifact::Stl).unwrap());
     }
 
     #[tokio::test]
@@ -154,7 +334,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let cache = LocalCache::new(temp_dir.path().to_path_buf());
 
-        let result = cache.get("nonexistent").await;
+        let result = cache.get("plate-0").await;
         assert!(matches!(result, Err(CacheError::NotFound)));
     }
 
@@ -163,11 +343,16 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let cache = LocalCache::new(temp_dir.path().to_path_buf());
 
-        let files = CachedFiles {
-            step_data: b"step".to_vec(),
-            gltf_data: b"gltf".to_vec(),
-            stl_data: b"stl".to_vec(),
-        };
+        let files = CachedFiles::new()
+            .with(Artifact::Step, "step")
+            .with(Artifact::Gltf, "gltf")
+            .with(Artifact::Stl, "stl")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
 
         cache.put("plate-abc123", &files).await.unwrap();
 
@@ -177,4 +362,165 @@ mod tests {
         assert!(temp_dir.path().join("plate-abc123/model.gltf").exists());
         assert!(temp_dir.path().join("plate-abc123/model.stl").exists());
     }
+
+    #[tokio::test]
+    async fn test_local_cache_delete_clear_and_expiry() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = LocalCache::new(temp_dir.path().to_path_buf()).with_ttl(Some(Duration::from_millis(50)));
+        let files = CachedFiles::new()
+            .with(Artifact::Step, "step")
+            .with(Artifact::Gltf, "gltf")
+            .with(Artifact::Stl, "stl")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
+
+        cache.put("plate-a", &files).await.unwrap();
+        cache.delete("plate-a").await.unwrap();
+        cache.delete("plate-a").await.unwrap();
+        assert!(!temp_dir.path().join("plate-a").exists());
+
+        cache.put("plate-b", &files).await.unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("plate-0a")).unwrap();
+        assert_eq!(cache.evict_expired().await.unwrap(), 0);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(!cache.exists("plate-b").await);
+        assert!(matches!(cache.get("plate-b").await, Err(CacheError::NotFound)));
+        assert_eq!(cache.evict_expired().await.unwrap(), 2);
+        assert!(!temp_dir.path().join("plate-0a").exists());
+
+        cache.put("plate-c", &files).await.unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("plate-0a")).unwrap();
+        assert_eq!(cache.clear().await.unwrap(), 1);
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_local_cache_refuses_keys_outside_its_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = LocalCache::new(temp_dir.path().join("cache"));
+        std::fs::create_dir_all(temp_dir.path().join("cache")).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("keep")).unwrap();
+
+        assert!(matches!(cache.delete("../keep").await, Err(CacheError::InvalidKey(_))));
+        assert!(matches!(cache.put("../keep", &CachedFiles::new()).await, Err(CacheError::InvalidKey(_))));
+        assert!(matches!(cache.get("../keep").await, Err(CacheError::InvalidKey(_))));
+        assert!(!cache.exists("../keep").await);
+        assert!(temp_dir.path().join("keep").exists());
+    }
+
+    #[tokio::test]
+    async fn test_local_cache_keys_skip_incomplete_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = LocalCache::new(temp_dir.path().join("cache"));
+        assert!(cache.keys().await.unwrap().is_empty());
+
+        let files = CachedFiles::new()
+            .with(Artifact::Step, "step")
+            .with(Artifact::Gltf, "gltf")
+            .with(Artifact::Stl, "stl")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
+        cache.put("plate-abc123", &files).await.unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("cache/plate-0a")).unwrap();
+
+        assert_eq!(cache.keys().await.unwrap(), vec!["plate-abc123".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_local_cache_large_file_survives_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = LocalCache::new(temp_dir.path().to_path_buf());
+
+        let large = Bytes::from(vec![7u8; MMAP_THRESHOLD as usize + 1]);
+        let files = CachedFiles::new()
+            .with(Artifact::Step, large.clone())
+            .with(Artifact::Gltf, "gltf")
+            .with(Artifact::Stl, "stl")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
+        cache.put("plate-b16", &files).await.unwrap();
+        let mapped = cache.get("plate-b16").await.unwrap();
+
+        // Rewriting the entry must not disturb a download already holding the old file
+        let replacement = files.with(Artifact::Step, "small");
+        cache.put("plate-b16", &replacement).await.unwrap();
+
+        assert_eq!(mapped.get(Artifact::Step).unwrap(), &large);
+        assert_eq!(cache.get("plate-b16").await.unwrap().get(Artifact::Step).unwrap(), &b"small"[..]);
+    }
+
+    #[tokio::test]
+    async fn test_local_cache_detects_corrupt_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = LocalCache::new(temp_dir.path().to_path_buf());
+        let files = CachedFiles::new()
+            .with(Artifact::Step, "step")
+            .with(Artifact::Gltf, "{\"asset\":{\"version\":\"2.0\"}}")
+            .with(Artifact::Stl, "stl")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
+        cache.put("plate-a", &files).await.unwrap();
+        assert!(temp_dir.path().join("plate-a/manifest.json").exists());
+
+        std::fs::write(temp_dir.path().join("plate-a/model.gltf"), b"{\"asset\":").unwrap();
+        assert!(matches!(
+            cache.get("plate-a").await,
+            Err(CacheError::Corrupt { file: "model.gltf" })
+        ));
+
+        // Regenerating overwrites the damaged entry
+        cache.put("plate-a", &files).await.unwrap();
+        assert_eq!(cache.get("plate-a").await.unwrap().get(Artifact::Gltf).unwrap(), files.get(Artifact::Gltf).unwrap());
+
+        // Entries from before checksums are served as they are
+        std::fs::remove_file(temp_dir.path().join("plate-a/manifest.json")).unwrap();
+        assert!(cache.get("plate-a").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_local_cache_streams_one_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = LocalCache::new(temp_dir.path().to_path_buf());
+        let step = Bytes::from(vec![3u8; MMAP_THRESHOLD as usize * 2]);
+        let files = CachedFiles::new()
+            .with(Artifact::Step, step.clone())
+            .with(Artifact::Gltf, "gltf")
+            .with(Artifact::Stl, "stl")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
+        assert!(matches!(cache.stream("plate-a", Artifact::Step).await, Err(CacheError::NotFound)));
+        cache.put("plate-a", &files).await.unwrap();
+
+        let stream = cache.stream("plate-a", Artifact::Step).await.unwrap();
+        assert_eq!(stream.length, Some(step.len() as u64));
+        let chunks: Vec<Bytes> = stream.body.try_collect().await.unwrap();
+        assert!(chunks.len() > 1, "read in one piece");
+        assert_eq!(chunks.concat(), step);
+
+        // A file damaged after it was cached fails once it has been read through
+        std::fs::write(temp_dir.path().join("plate-a/model.stl"), b"st").unwrap();
+        let chunks: Vec<_> = cache.stream("plate-a", Artifact::Stl).await.unwrap().body.collect().await;
+        assert!(matches!(chunks.last(), Some(Err(CacheError::Corrupt { file: "model.stl" }))));
+    }
 }
@@ -3,21 +3,34 @@
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::RwLock;
+use std::time::{Duration, Instant};
 
 use crate::cache::{CacheError, CachedFiles, ModelCache};
 
 /// In-memory cache implementation using a HashMap.
 /// Useful for testing and development without filesystem dependencies.
 pub struct MemoryCache {
-    entries: RwLock<HashMap<String, CachedFiles>>,
+    entries: RwLock<HashMap<String, (CachedFiles, Instant)>>,
+    ttl: Option<Duration>,
 }
 
 impl MemoryCache {
     pub fn new() -> Self {
         Self {
             entries: RwLock::new(HashMap::new()),
+            ttl: None,
         }
     }
+
+    /// Treat entries older than `ttl` as missing; `evict_expired` drops them.
+    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    fn is_live(&self, stored_at: Instant) -> bool {
+        self.ttl.is_none_or(|ttl| stored_at.elapsed() < ttl)
+    }
 }
 
 impl Default for MemoryCache {
@@ -30,36 +43,73 @@ impl Default for MemoryCache {
 impl ModelCache for MemoryCache {
     async fn exists(&self, cache_key: &str) -> bool {
         let entries = self.entries.read().unwrap();
-        entries.contains_key(cache_key)
+        entries
+            .get(cache_key)
+            .is_some_and(|(_, stored_at)| self.is_live(*stored_at))
     }
 
     async fn get(&self, cache_key: &str) -> Result<CachedFiles, CacheError> {
         let entries = self.entries.read().unwrap();
         entries
             .get(cache_key)
-            .cloned()
+            .filter(|(_, stored_at)| self.is_live(*stored_at))
+            .map(|(files, _)| files.clone())
             .ok_or(CacheError::NotFound)
     }
 
     async fn put(&self, cache_key: &str, files: &CachedFiles) -> Result<(), CacheError> {
         let mut entries = self.entries.write().unwrap();
-        entries.insert(cache_key.to_string(), files.clone());
+        entries.insert(cache_key.to_string(), (files.clone(), Instant::now()));
+        Ok(())
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, CacheError> {
+        let entries = self.entries.read().unwrap();
+        Ok(entries
+            .iter()
+            .filter(|(_, (_, stored_at))| self.is_live(*stored_at))
+            .map(|(key, _)| key.clone())
+            .collect())
+    }
+
+    async fn delete(&self, cache_key: &str) -> Result<(), CacheError> {
+        self.entries.write().unwrap().remove(cache_key);
         Ok(())
     }
+
+    async fn clear(&self) -> Result<usize, CacheError> {
+        let mut entries = self.entries.write().unwrap();
+        let removed = entries.len();
+        entries.clear();
+        Ok(removed)
+    }
+
+    async fn evict_expired(&self) -> Result<usize, CacheError> {
+        let mut entries = self.entries.write().unwrap();
+        let before = entries.len();
+        entries.retain(|_, (_, stored_at)| self.is_live(*stored_at));
+        Ok(before - entries.len())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::Artifact;
 
     #[tokio::test]
     async fn test_memory_cache_put_and_get() {
         let cache = MemoryCache::new();
-        let files = CachedFiles {
-            step_data: b"step content".to_vec(),
-            gltf_data: b"gltf content".to_vec(),
-            stl_data: b"stl content".to_vec(),
-        };
+        let files = CachedFiles::new()
+            .with(Artifact::Step, "step content")
+            .with(Artifact::Gltf, "gltf content")
+            .with(Artifact::Stl, "stl content")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
 
         assert!(!cache.exists("test-key").await);
 
@@ -68,9 +118,36 @@ mod tests {
         assert!(cache.exists("test-key").await);
 
         let retrieved = cache.get("test-key").await.unwrap();
-        assert_eq!(retrieved.step_data, files.step_data);
-        assert_eq!(retrieved.gltf_data, files.gltf_data);
-        assert_eq!(retrieved.stl_data, files.stl_data);
+        assert_eq!(retrieved.get(Artifact::Step).unwrap(), files.get(Artifact::Step).unwrap());
+        assert_eq!(retrieved.get(Artifact::Gltf).unwrap(), files.get(Artifact::Gltf).unwrap());
+        assert_eq!(retrieved.get(Artifact::Stl).unwrap(), files.get(Artifact::Stl).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_delete_and_expiry() {
+        let cache = MemoryCache::new().with_ttl(Some(Duration::from_millis(20)));
+        let files = CachedFiles::new()
+            .with(Artifact::Step, "step")
+            .with(Artifact::Gltf, "gltf")
+            .with(Artifact::Stl, "stl")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
+        cache.put("a", &files).await.unwrap();
+        cache.put("b", &files).await.unwrap();
+
+        cache.delete("a").await.unwrap();
+        cache.delete("a").await.unwrap();
+        assert!(!cache.exists("a").await);
+        assert_eq!(cache.evict_expired().await.unwrap(), 0);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(matches!(cache.get("b").await, Err(CacheError::NotFound)));
+        assert!(cache.keys().await.unwrap().is_empty());
+        assert_eq!(cache.evict_expired().await.unwrap(), 1);
     }
 
     #[tokio::test]
@@ -0,0 +1,297 @@
+//! Hit, miss, and latency counters for the model cache.
+//!
+//! [`MeteredCache`] wraps whichever [`ModelCache`] the deployment uses and records
+//! every lookup and write in a shared [`CacheMetrics`]. [`crate::TieredCache`] also
+//! records hits served from its memory tier, so the two tiers can be sized
+//! separately. `/api/cache/stats` serves a snapshot as JSON or in the Prometheus
+//! text format.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
+
+use crate::cache::{Artifact, ArtifactStream, CacheError, CachedFiles, ModelCache};
+
+/// Upper bounds of the latency histogram buckets, in milliseconds.
+const BUCKETS_MS: [u64; 11] = [1, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+/// Cumulative latency histogram, in the shape Prometheus expects.
+#[derive(Debug, Default)]
+struct Histogram {
+    /// Observations at or under each of [`BUCKETS_MS`]
+    buckets: [AtomicU64; BUCKETS_MS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    fn observe(&self, elapsed: Duration) {
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        for (bucket, &bound) in self.buckets.iter().zip(&BUCKETS_MS) {
+            if ms <= bound as f64 {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> LatencyHistogram {
+        LatencyHistogram {
+            buckets: BUCKETS_MS
+                .iter()
+                .zip(&self.buckets)
+                .map(|(&le_ms, count)| LatencyBucket { le_ms, count: count.load(Ordering::Relaxed) })
+                .collect(),
+            count: self.count.load(Ordering::Relaxed),
+            sum_ms: self.sum_micros.load(Ordering::Relaxed) as f64 / 1000.0,
+        }
+    }
+}
+
+/// Counters shared by the cache wrappers and the stats endpoint.
+#[derive(Debug, Default)]
+pub struct CacheMetrics {
+    hits: AtomicU64,
+    memory_hits: AtomicU64,
+    misses: AtomicU64,
+    get_errors: AtomicU64,
+    puts: AtomicU64,
+    put_errors: AtomicU64,
+    deletes: AtomicU64,
+    get_latency: Histogram,
+    put_latency: Histogram,
+}
+
+impl CacheMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a hit answered by a cache's memory tier, without going to its backend.
+    pub fn record_memory_hit(&self) {
+        self.memory_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_get<T>(&self, result: &Result<T, CacheError>, elapsed: Duration) {
+        let counter = match result {
+            Ok(_) => &self.hits,
+            Err(CacheError::NotFound) => &self.misses,
+            Err(_) => &self.get_errors,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.get_latency.observe(elapsed);
+    }
+
+    fn record_put(&self, result: &Result<(), CacheError>, elapsed: Duration) {
+        let counter = if result.is_ok() { &self.puts } else { &self.put_errors };
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.put_latency.observe(elapsed);
+    }
+
+    /// Current values, as served by `/api/cache/stats`.
+    pub fn snapshot(&self) -> CacheStats {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        CacheStats {
+            hits,
+            memory_hits: self.memory_hits.load(Ordering::Relaxed),
+            misses,
+            get_errors: self.get_errors.load(Ordering::Relaxed),
+            hit_ratio: if hits + misses == 0 { 0.0 } else { hits as f64 / (hits + misses) as f64 },
+            puts: self.puts.load(Ordering::Relaxed),
+            put_errors: self.put_errors.load(Ordering::Relaxed),
+            deletes: self.deletes.load(Ordering::Relaxed),
+            get_latency: self.get_latency.snapshot(),
+            put_latency: self.put_latency.snapshot(),
+        }
+    }
+
+    /// Append the metrics in the Prometheus text exposition format.
+    pub fn render_prometheus(&self, out: &mut String) {
+        let stats = self.snapshot();
+        let counters = [
+            ("platerator_cache_hits_total", "Cache lookups that found an entry", stats.hits),
+            (
+                "platerator_cache_memory_hits_total",
+                "Cache hits served from the in-memory tier",
+                stats.memory_hits,
+            ),
+            ("platerator_cache_misses_total", "Cache lookups that found no entry", stats.misses),
+            ("platerator_cache_get_errors_total", "Cache lookups that failed", stats.get_errors),
+            ("platerator_cache_puts_total", "Entries written to the cache", stats.puts),
+            ("platerator_cache_put_errors_total", "Cache writes that failed", stats.put_errors),
+            ("platerator_cache_deletes_total", "Entries deleted from the cache", stats.deletes),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} counter\n{} {}", name, help, name, name, value);
+        }
+        for (name, help, histogram) in [
+            ("platerator_cache_get_seconds", "Cache lookup latency", &stats.get_latency),
+            ("platerator_cache_put_seconds", "Cache write latency", &stats.put_latency),
+        ] {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} histogram", name, help, name);
+            for bucket in &histogram.buckets {
+                let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bucket.le_ms as f64 / 1000.0, bucket.count);
+            }
+            let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, histogram.count);
+            let _ = writeln!(out, "{}_sum {}", name, histogram.sum_ms / 1000.0);
+            let _ = writeln!(out, "{}_count {}", name, histogram.count);
+        }
+    }
+}
+
+/// Snapshot of the cache counters since the server started.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CacheStats {
+    /// Lookups that found an entry, in memory or in the backend
+    pub hits: u64,
+    /// Hits answered from the in-memory tier without asking the backend
+    pub memory_hits: u64,
+    /// Lookups that found no entry
+    pub misses: u64,
+    /// Lookups that failed, e.g. the backend was unreachable
+    pub get_errors: u64,
+    /// `hits / (hits + misses)`, or 0 before the first lookup
+    pub hit_ratio: f64,
+    /// Entries written
+    pub puts: u64,
+    /// Writes that failed
+    pub put_errors: u64,
+    /// Entries deleted through the admin API
+    pub deletes: u64,
+    /// How long lookups took
+    pub get_latency: LatencyHistogram,
+    /// How long writes took
+    pub put_latency: LatencyHistogram,
+}
+
+/// Latency distribution with cumulative buckets.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LatencyHistogram {
+    /// Observations at or under each bound
+    pub buckets: Vec<LatencyBucket>,
+    /// Total observations
+    pub count: u64,
+    /// Total time across all observations, in milliseconds
+    pub sum_ms: f64,
+}
+
+/// One histogram bucket.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LatencyBucket {
+    /// Upper bound in milliseconds
+    pub le_ms: u64,
+    /// Observations at or under the bound
+    pub count: u64,
+}
+
+/// A [`ModelCache`] that records every call to `inner` in a [`CacheMetrics`].
+pub struct MeteredCache {
+    inner: Arc<dyn ModelCache>,
+    metrics: Arc<CacheMetrics>,
+}
+
+impl MeteredCache {
+    pub fn new(inner: Arc<dyn ModelCache>, metrics: Arc<CacheMetrics>) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl ModelCache for MeteredCache {
+    async fn exists(&self, cache_key: &str) -> bool {
+        self.inner.exists(cache_key).await
+    }
+
+    async fn get(&self, cache_key: &str) -> Result<CachedFiles, CacheError> {
+        let start = Instant::now();
+        let result = self.inner.get(cache_key).await;
+        self.metrics.record_get(&result, start.elapsed());
+        result
+    }
+
+    /// Counted as a lookup, timed until the file starts streaming.
+    async fn stream(&self, cache_key: &str, artifact: Artifact) -> Result<ArtifactStream, CacheError> {
+        let start = Instant::now();
+        let result = self.inner.stream(cache_key, artifact).await;
+        self.metrics.record_get(&result, start.elapsed());
+        result
+    }
+
+    async fn put(&self, cache_key: &str, files: &CachedFiles) -> Result<(), CacheError> {
+        let start = Instant::now();
+        let result = self.inner.put(cache_key, files).await;
+        self.metrics.record_put(&result, start.elapsed());
+        result
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, CacheError> {
+        self.inner.keys().await
+    }
+
+    async fn check(&self) -> Result<(), CacheError> {
+        self.inner.check().await
+    }
+
+    async fn delete(&self, cache_key: &str) -> Result<(), CacheError> {
+        self.inner.delete(cache_key).await?;
+        self.metrics.deletes.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<usize, CacheError> {
+        let removed = self.inner.clear().await?;
+        self.metrics.deletes.fetch_add(removed as u64, Ordering::Relaxed);
+        Ok(removed)
+    }
+
+    async fn evict_expired(&self) -> Result<usize, CacheError> {
+        self.inner.evict_expired().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache_memory::MemoryCache;
+
+    #[tokio::test]
+    async fn test_metered_cache_counts_hits_misses_and_puts() {
+        let metrics = Arc::new(CacheMetrics::new());
+        let cache = MeteredCache::new(Arc::new(MemoryCache::new()), metrics.clone());
+        let files = CachedFiles::new()
+            .with(Artifact::Step, "step")
+            .with(Artifact::Gltf, "gltf")
+            .with(Artifact::Stl, "stl")
+            .with(Artifact::Obj, "obj")
+            .with(Artifact::ThreeMf, "3mf")
+            .with(Artifact::Svg, "svg")
+            .with(Artifact::Pdf, "pdf")
+            .with(Artifact::Dxf, "dxf")
+            .with(Artifact::Kcl, "kcl");
+
+        assert!(cache.get("a").await.is_err());
+        cache.put("a", &files).await.unwrap();
+        cache.get("a").await.unwrap();
+        cache.get("a").await.unwrap();
+        cache.delete("a").await.unwrap();
+
+        let stats = metrics.snapshot();
+        assert_eq!((stats.hits, stats.misses, stats.puts, stats.deletes), (2, 1, 1, 1));
+        assert!((stats.hit_ratio - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats.get_latency.count, 3);
+        // Buckets are cumulative, and an in-memory lookup fits in the last one
+        assert_eq!(stats.get_latency.buckets.last().unwrap().count, 3);
+
+        let mut text = String::new();
+        metrics.render_prometheus(&mut text);
+        assert!(text.contains("platerator_cache_hits_total 2\n"));
+        assert!(text.contains("platerator_cache_get_seconds_bucket{le=\"+Inf\"} 3\n"));
+        assert!(text.contains("platerator_cache_put_seconds_count 1\n"));
+    }
+}
@@ -0,0 +1,249 @@
+//! Redis cache implementation, for deployments that already run Redis.
+//!
+//! Each entry is one Redis hash, `{prefix}{cache_key}`, with a field per file named
+//! by its extension: `step`, `gltf`, `stl`, `3mf`, and so on. The fields and the
+//! expiry are written in a single MULTI/EXEC, so readers never see a partial entry
+//! and every entry gets its TTL.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use crate::cache::{Artifact, CacheError, CachedFiles, ModelCache};
+
+/// Tuning for [`RedisCache`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RedisCacheOptions {
+    /// Prepended to every cache key, so the cache can share a Redis database.
+    pub key_prefix: String,
+    /// How long an entry lives after it is written. `None` keeps entries until
+    /// Redis evicts them under its own `maxmemory-policy`.
+    pub ttl: Option<Duration>,
+    /// Entries larger than this (all of an entry's files together) are not stored.
+    pub max_entry_bytes: usize,
+}
+
+impl Default for RedisCacheOptions {
+    fn default() -> Self {
+        Self {
+            key_prefix: "platerator:model:".to_string(),
+            ttl: Some(Duration::from_secs(7 * 24 * 60 * 60)),
+            max_entry_bytes: 32 * 1024 * 1024,
+        }
+    }
+}
+
+impl RedisCacheOptions {
+    /// Read options from REDIS_CACHE_PREFIX, REDIS_CACHE_TTL_SECS (0 disables
+    /// expiry), and REDIS_CACHE_MAX_ENTRY_BYTES, falling back to the defaults.
+    pub fn from_env() -> Result<Self, CacheError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, CacheError> {
+        let non_empty = |key: &str| lookup(key).filter(|v| !v.is_empty());
+        let number = |key: &str| -> Result<Option<u64>, CacheError> {
+            non_empty(key)
+                .map(|v| {
+                    v.parse()
+                        .map_err(|_| CacheError::Config(format!("{} must be a whole number, got {:?}", key, v)))
+                })
+                .transpose()
+        };
+
+        let mut options = Self::default();
+        if let Some(prefix) = non_empty("REDIS_CACHE_PREFIX") {
+            options.key_prefix = prefix;
+        }
+        if let Some(secs) = number("REDIS_CACHE_TTL_SECS")? {
+            options.ttl = (secs > 0).then(|| Duration::from_secs(secs));
+        }
+        if let Some(bytes) = number("REDIS_CACHE_MAX_ENTRY_BYTES")? {
+            options.max_entry_bytes = bytes as usize;
+        }
+        Ok(options)
+    }
+}
+
+/// Redis cache implementation. Connects on first use and reconnects automatically.
+pub struct RedisCache {
+    client: redis::Client,
+    connection: OnceCell<ConnectionManager>,
+    options: RedisCacheOptions,
+}
+
+impl RedisCache {
+    /// Create a cache for the Redis server at `url`, e.g. `redis://cache:6379/0`.
+    pub fn new(url: &str, options: RedisCacheOptions) -> Result<Self, CacheError> {
+        Ok(Self {
+            client: redis::Client::open(url)?,
+            connection: OnceCell::new(),
+            options,
+        })
+    }
+
+    /// Create a RedisCache from REDIS_URL and [`RedisCacheOptions::from_env`].
+    pub fn from_env() -> Result<Self, CacheError> {
+        let url = std::env::var("REDIS_URL")
+            .map_err(|_| CacheError::Config("REDIS_URL not set".to_string()))?;
+        Self::new(&url, RedisCacheOptions::from_env()?)
+    }
+
+    async fn connection(&self) -> Result<ConnectionManager, CacheError> {
+        let connection = self
+            .connection
+            .get_or_try_init(|| self.client.get_connection_manager())
+            .await?;
+        Ok(connection.clone())
+    }
+
+    fn redis_key(&self, cache_key: &str) -> String {
+        format!("{}{}", self.options.key_prefix, cache_key)
+    }
+}
+
+#[async_trait]
+impl ModelCache for RedisCache {
+    async fn check(&self) -> Result<(), CacheError> {
+        let mut connection = self.connection().await?;
+        redis::cmd("PING").query_async::<()>(&mut connection).await?;
+        Ok(())
+    }
+
+    async fn exists(&self, cache_key: &str) -> bool {
+        let result: Result<bool, CacheError> = async {
+            let mut connection = self.connection().await?;
+            Ok(connection.exists(self.redis_key(cache_key)).await?)
+        }
+        .await;
+
+        result.unwrap_or_else(|e| {
+            tracing::warn!("Redis lookup error: {}", e);
+            false
+        })
+    }
+
+    async fn get(&self, cache_key: &str) -> Result<CachedFiles, CacheError> {
+        let mut connection = self.connection().await?;
+        let fields: Vec<Option<Vec<u8>>> = redis::cmd("HMGET")
+            .arg(self.redis_key(cache_key))
+            .arg(&Artifact::ALL.map(Artifact::extension))
+            .query_async(&mut connection)
+            .await?;
+
+        // Entries cached before a format was added lack its field and count as a miss
+        let files: CachedFiles = Artifact::ALL
+            .into_iter()
+            .zip(fields)
+            .filter_map(|(artifact, data)| Some((artifact, Bytes::from(data?))))
+            .collect();
+        if files.is_complete() {
+            Ok(files)
+        } else {
+            Err(CacheError::NotFound)
+        }
+    }
+
+    async fn put(&self, cache_key: &str, files: &CachedFiles) -> Result<(), CacheError> {
+        let size = files.size();
+        if size > self.options.max_entry_bytes {
+            return Err(CacheError::TooLarge {
+                size,
+                limit: self.options.max_entry_bytes,
+            });
+        }
+
+        let key = self.redis_key(cache_key);
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .hset_multiple(
+                &key,
+                &files
+                    .iter()
+                    .map(|(artifact, data)| (artifact.extension(), data.as_ref()))
+                    .collect::<Vec<_>>(),
+            )
+            .ignore();
+        if let Some(ttl) = self.options.ttl {
+            pipe.expire(&key, ttl.as_secs().max(1) as i64).ignore();
+        }
+
+        let mut connection = self.connection().await?;
+        pipe.query_async::<()>(&mut connection).await?;
+        Ok(())
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, CacheError> {
+        let mut connection = self.connection().await?;
+        let mut iter = connection
+            .scan_match::<_, String>(format!("{}*", self.options.key_prefix))
+            .await?;
+
+        let mut keys = Vec::new();
+        while let Some(key) = iter.next_item().await {
+            if let Some(cache_key) = key?.strip_prefix(&self.options.key_prefix) {
+                keys.push(cache_key.to_string());
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn delete(&self, cache_key: &str) -> Result<(), CacheError> {
+        let mut connection = self.connection().await?;
+        connection.del::<_, ()>(self.redis_key(cache_key)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn test_options_from_env() {
+        assert_eq!(RedisCacheOptions::from_lookup(lookup(&[])).unwrap(), RedisCacheOptions::default());
+
+        let options = RedisCacheOptions::from_lookup(lookup(&[
+            ("REDIS_CACHE_PREFIX", "staging:"),
+            ("REDIS_CACHE_TTL_SECS", "0"),
+            ("REDIS_CACHE_MAX_ENTRY_BYTES", "1024"),
+        ]))
+        .unwrap();
+        assert_eq!(options.key_prefix, "staging:");
+        assert_eq!(options.ttl, None);
+        assert_eq!(options.max_entry_bytes, 1024);
+
+        let err = RedisCacheOptions::from_lookup(lookup(&[("REDIS_CACHE_TTL_SECS", "1h")])).unwrap_err();
+        assert!(matches!(err, CacheError::Config(_)));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_entry_is_rejected_before_connecting() {
+        // Nothing listens on port 1; the guard must fail first.
+        let cache = RedisCache::new(
+            "redis://127.0.0.1:1",
+            RedisCacheOptions {
+                max_entry_bytes: 10,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let files = CachedFiles::new().with(Artifact::Step, "step content");
+
+        let err = cache.put("plate-0", &files).await.unwrap_err();
+        assert!(matches!(err, CacheError::TooLarge { size: 12, limit: 10 }));
+        assert!(!err.is_retryable());
+    }
+}
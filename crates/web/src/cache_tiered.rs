@@ -0,0 +1,347 @@
+//! Two-tier cache: a bounded in-memory LRU in front of a slower backend.
+//!
+//! Hits on S3/DynamoDB, Redis, or disk cost a round trip even when the entry is
+//! there. [`TieredCache`] keeps the most recently used entries in memory, bounded
+//! by entry count and total bytes, and serves repeat requests from there. Writes go
+//! to the backend first and are only kept in memory once the backend has them, so
+//! the backend stays the source of truth and a restart loses nothing.
+
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::cache::{Artifact, ArtifactStream, CacheError, CachedFiles, ModelCache};
+use crate::cache_metrics::CacheMetrics;
+
+/// Bounds on the in-memory tier of a [`TieredCache`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TieredCacheOptions {
+    /// Most entries kept in memory. `0` turns the memory tier off.
+    pub max_entries: usize,
+    /// Most bytes (all files of all entries together) kept in memory.
+    pub max_bytes: usize,
+    /// How long an entry is served from memory before the backend is asked again,
+    /// so entries the backend expires or another server deletes don't live on here.
+    pub ttl: Option<Duration>,
+}
+
+impl Default for TieredCacheOptions {
+    fn default() -> Self {
+        Self {
+            max_entries: 64,
+            max_bytes: 256 * 1024 * 1024,
+            ttl: None,
+        }
+    }
+}
+
+impl TieredCacheOptions {
+    /// Read options from CACHE_MEMORY_ENTRIES (0 disables the memory tier) and
+    /// CACHE_MEMORY_BYTES, falling back to the defaults.
+    pub fn from_env() -> Result<Self, CacheError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, CacheError> {
+        let number = |key: &str| -> Result<Option<usize>, CacheError> {
+            lookup(key)
+                .filter(|v| !v.is_empty())
+                .map(|v| {
+                    v.parse()
+                        .map_err(|_| CacheError::Config(format!("{} must be a whole number, got {:?}", key, v)))
+                })
+                .transpose()
+        };
+
+        let mut options = Self::default();
+        if let Some(entries) = number("CACHE_MEMORY_ENTRIES")? {
+            options.max_entries = entries;
+        }
+        if let Some(bytes) = number("CACHE_MEMORY_BYTES")? {
+            options.max_bytes = bytes;
+        }
+        Ok(options)
+    }
+
+    /// Whether the memory tier can hold anything.
+    pub fn is_enabled(&self) -> bool {
+        self.max_entries > 0 && self.max_bytes > 0
+    }
+}
+
+struct Entry {
+    files: CachedFiles,
+    size: usize,
+    stored_at: Instant,
+    /// Position in [`Lru::order`]; higher is more recently used
+    last_used: u64,
+}
+
+/// Entries by key, plus their keys by recency so the oldest is found quickly.
+#[derive(Default)]
+struct Lru {
+    entries: HashMap<String, Entry>,
+    order: BTreeMap<u64, String>,
+    bytes: usize,
+    clock: u64,
+}
+
+impl Lru {
+    fn touch(&mut self, cache_key: &str) {
+        let Some(entry) = self.entries.get_mut(cache_key) else {
+            return;
+        };
+        self.clock += 1;
+        let key = self.order.remove(&entry.last_used).expect("every entry is ordered");
+        entry.last_used = self.clock;
+        self.order.insert(self.clock, key);
+    }
+
+    fn remove(&mut self, cache_key: &str) -> Option<Entry> {
+        let entry = self.entries.remove(cache_key)?;
+        self.order.remove(&entry.last_used);
+        self.bytes -= entry.size;
+        Some(entry)
+    }
+
+    fn insert(&mut self, cache_key: &str, files: &CachedFiles, options: &TieredCacheOptions) {
+        self.remove(cache_key);
+        let size = files.size();
+        if size > options.max_bytes {
+            return;
+        }
+        while self.entries.len() >= options.max_entries || self.bytes + size > options.max_bytes {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.bytes -= entry.size;
+            }
+        }
+        self.clock += 1;
+        self.order.insert(self.clock, cache_key.to_string());
+        self.entries.insert(
+            cache_key.to_string(),
+            Entry {
+                files: files.clone(),
+                size,
+                stored_at: Instant::now(),
+                last_used: self.clock,
+            },
+        );
+        self.bytes += size;
+    }
+
+    fn clear(&mut self) {
+        *self = Lru::default();
+    }
+}
+
+/// A [`ModelCache`] that serves recently used entries from memory and everything
+/// else from `backend`.
+pub struct TieredCache {
+    backend: Arc<dyn ModelCache>,
+    memory: Mutex<Lru>,
+    options: TieredCacheOptions,
+    metrics: Option<Arc<CacheMetrics>>,
+}
+
+impl TieredCache {
+    pub fn new(backend: Arc<dyn ModelCache>, options: TieredCacheOptions) -> Self {
+        Self {
+            backend,
+            memory: Mutex::new(Lru::default()),
+            options,
+            metrics: None,
+        }
+    }
+
+    /// Count hits served from memory in `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<CacheMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// The in-memory copy of `cache_key`, if it is there and fresh.
+    fn get_memory(&self, cache_key: &str) -> Option<CachedFiles> {
+        let mut memory = self.memory.lock().unwrap();
+        let stored_at = memory.entries.get(cache_key)?.stored_at;
+        if self.options.ttl.is_some_and(|ttl| stored_at.elapsed() >= ttl) {
+            memory.remove(cache_key);
+            return None;
+        }
+        memory.touch(cache_key);
+        memory.entries.get(cache_key).map(|entry| entry.files.clone())
+    }
+
+    fn put_memory(&self, cache_key: &str, files: &CachedFiles) {
+        if self.options.is_enabled() {
+            self.memory.lock().unwrap().insert(cache_key, files, &self.options);
+        }
+    }
+
+    /// Entries and bytes currently held in memory.
+    pub fn memory_usage(&self) -> (usize, usize) {
+        let memory = self.memory.lock().unwrap();
+        (memory.entries.len(), memory.bytes)
+    }
+}
+
+#[async_trait]
+impl ModelCache for TieredCache {
+    async fn exists(&self, cache_key: &str) -> bool {
+        self.get_memory(cache_key).is_some() || self.backend.exists(cache_key).await
+    }
+
+    async fn get(&self, cache_key: &str) -> Result<CachedFiles, CacheError> {
+        if let Some(files) = self.get_memory(cache_key) {
+            if let Some(metrics) = &self.metrics {
+                metrics.record_memory_hit();
+            }
+            return Ok(files);
+        }
+        let files = self.backend.get(cache_key).await?;
+        self.put_memory(cache_key, &files);
+        Ok(files)
+    }
+
+    /// Serve from memory when the entry is there; otherwise stream from the backend
+    /// without loading the rest of the entry into memory.
+    async fn stream(&self, cache_key: &str, artifact: Artifact) -> Result<ArtifactStream, CacheError> {
+        if let Some(files) = self.get_memory(cache_key) {
+            if let Some(metrics) = &self.metrics {
+                metrics.record_memory_hit();
+            }
+            let data = files.get(artifact).cloned().ok_or(CacheError::NotFound)?;
+            return Ok(ArtifactStream::from_bytes(data));
+        }
+        self.backend.stream(cache_key, artifact).await
+    }
+
+    async fn put(&self, cache_key: &str, files: &CachedFiles) -> Result<(), CacheError> {
+        if let Err(e) = self.backend.put(cache_key, files).await {
+            // Don't serve an older copy the backend no longer agrees with
+            self.memory.lock().unwrap().remove(cache_key);
+            return Err(e);
+        }
+        self.put_memory(cache_key, files);
+        Ok(())
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, CacheError> {
+        self.backend.keys().await
+    }
+
+    async fn check(&self) -> Result<(), CacheError> {
+        self.backend.check().await
+    }
+
+    async fn delete(&self, cache_key: &str) -> Result<(), CacheError> {
+        self.memory.lock().unwrap().remove(cache_key);
+        self.backend.delete(cache_key).await
+    }
+
+    async fn clear(&self) -> Result<usize, CacheError> {
+        self.memory.lock().unwrap().clear();
+        self.backend.clear().await
+    }
+
+    async fn evict_expired(&self) -> Result<usize, CacheError> {
+        if let Some(ttl) = self.options.ttl {
+            let mut memory = self.memory.lock().unwrap();
+            let expired: Vec<String> = memory
+                .entries
+                .iter()
+                .filter(|(_, entry)| entry.stored_at.elapsed() >= ttl)
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in expired {
+                memory.remove(&key);
+            }
+        }
+        self.backend.evict_expired().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache_memory::MemoryCache;
+    use bytes::Bytes;
+
+    fn files(size: usize) -> CachedFiles {
+        CachedFiles::new()
+            .with(Artifact::Step, Bytes::from(vec![0; size]))
+            .with(Artifact::Gltf, Bytes::new())
+            .with(Artifact::Stl, Bytes::new())
+            .with(Artifact::Obj, Bytes::new())
+            .with(Artifact::ThreeMf, Bytes::new())
+            .with(Artifact::Svg, Bytes::new())
+            .with(Artifact::Pdf, Bytes::new())
+            .with(Artifact::Dxf, Bytes::new())
+            .with(Artifact::Kcl, Bytes::new())
+    }
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used_within_bounds() {
+        let backend = Arc::new(MemoryCache::new());
+        let options = TieredCacheOptions {
+            max_entries: 2,
+            max_bytes: 100,
+            ttl: None,
+        };
+        let cache = TieredCache::new(backend.clone(), options);
+
+        cache.put("a", &files(10)).await.unwrap();
+        cache.put("b", &files(10)).await.unwrap();
+        // Reading "a" makes "b" the least recently used
+        cache.get("a").await.unwrap();
+        cache.put("c", &files(10)).await.unwrap();
+        assert_eq!(cache.memory_usage(), (2, 20));
+        assert!(cache.get_memory("a").is_some());
+        assert!(cache.get_memory("b").is_none());
+        // Everything was written through
+        assert_eq!(backend.keys().await.unwrap().len(), 3);
+
+        // A miss in memory is filled from the backend
+        cache.get("b").await.unwrap();
+        assert!(cache.get_memory("b").is_some());
+
+        // The byte budget evicts as well, and oversized entries skip memory
+        cache.put("big", &files(91)).await.unwrap();
+        assert_eq!(cache.memory_usage(), (1, 91));
+        cache.put("huge", &files(101)).await.unwrap();
+        assert!(cache.get_memory("huge").is_none());
+        assert!(cache.get("huge").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_clear_reach_both_tiers() {
+        let backend = Arc::new(MemoryCache::new());
+        let cache = TieredCache::new(backend.clone(), TieredCacheOptions::default());
+        cache.put("a", &files(1)).await.unwrap();
+        cache.put("b", &files(1)).await.unwrap();
+
+        cache.delete("a").await.unwrap();
+        assert!(!cache.exists("a").await);
+        assert!(!backend.exists("a").await);
+
+        assert_eq!(cache.clear().await.unwrap(), 1);
+        assert_eq!(cache.memory_usage(), (0, 0));
+        assert!(matches!(cache.get("b").await, Err(CacheError::NotFound)));
+    }
+
+    #[test]
+    fn test_options_from_env() {
+        let options = TieredCacheOptions::from_lookup(|key| match key {
+            "CACHE_MEMORY_ENTRIES" => Some("0".to_string()),
+            "CACHE_MEMORY_BYTES" => Some("1024".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(options.max_bytes, 1024);
+        assert!(!options.is_enabled());
+        assert!(TieredCacheOptions::from_lookup(|_| Some("lots".to_string())).is_err());
+    }
+}
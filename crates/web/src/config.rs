@@ -0,0 +1,524 @@
+//! Server configuration, layered: built-in defaults, then `steel-thread.toml`,
+//! then environment variables, each overriding the last.
+//!
+//! The file is read from STEEL_THREAD_CONFIG when set (it must exist), otherwise
+//! from `steel-thread.toml` in the working directory if there is one. See
+//! `steel-thread.example.toml` at the repository root for its layout.
+//!
+//! Each setting's environment variable is listed on its field. Everything is
+//! checked up front, and every problem is reported together, so a bad deployment
+//! fails at startup with one list to fix. Settings not covered here are still
+//! read from the environment only.
+
+use parametric::gltf::GltfCompression;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::rate_limit::{DEFAULT_MAX_BODY_BYTES, DEFAULT_REQUESTS_PER_MINUTE};
+
+/// Config file read when STEEL_THREAD_CONFIG is unset, if it exists.
+pub const DEFAULT_CONFIG_FILE: &str = "steel-thread.toml";
+
+/// The server's configuration after all layers are applied.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppConfig {
+    /// Port to listen on (PORT)
+    pub port: u16,
+    pub cache: CacheConfig,
+    pub aws: AwsConfig,
+    pub zoo: ZooConfig,
+    pub rate_limit: RateLimitConfig,
+    pub csrf: CsrfConfig,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheConfig {
+    /// CACHE_BACKEND
+    pub backend: CacheBackend,
+    /// Directory for the local backend (CACHE_DIR)
+    pub dir: PathBuf,
+    /// Entry lifetime; `None` never expires (CACHE_TTL_SECS, 0 for none)
+    pub ttl: Option<Duration>,
+    /// Generate the presets in the background at startup (CACHE_WARM_PRESETS)
+    pub warm_presets: bool,
+    /// Warm the presets again this often; `None` only at startup
+    /// (CACHE_WARM_INTERVAL_SECS, 0 for none)
+    pub warm_interval: Option<Duration>,
+}
+
+/// Where generated models are cached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    Local,
+    Memory,
+    Aws,
+    Redis,
+}
+
+impl std::str::FromStr for CacheBackend {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(Self::Local),
+            "memory" => Ok(Self::Memory),
+            "aws" => Ok(Self::Aws),
+            "redis" => Ok(Self::Redis),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AwsConfig {
+    /// S3_BUCKET_NAME
+    pub s3_bucket: Option<String>,
+    /// DYNAMODB_TABLE
+    pub dynamodb_table: Option<String>,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ZooConfig {
+    /// The `zoo` binary (ZOO_CLI)
+    pub cli: PathBuf,
+    /// ZOO_TOKEN; the CLI's own login is used when unset
+    pub api_token: Option<String>,
+    /// KCL templates (KCL_SRC_DIR); found relative to the checkout when unset
+    pub kcl_source_dir: Option<PathBuf>,
+    /// Compression of the viewer's glTF (ZOO_GLTF_COMPRESSION)
+    pub gltf_compression: GltfCompression,
+    /// Engine calls allowed per UTC day; `None` has no cap (ZOO_DAILY_QUOTA, 0 for none)
+    pub daily_quota: Option<u64>,
+    /// Engine calls allowed per UTC month; `None` has no cap (ZOO_MONTHLY_QUOTA, 0 for none)
+    pub monthly_quota: Option<u64>,
+    /// Where the call counts are kept across restarts (ZOO_QUOTA_FILE); memory only when unset
+    pub quota_file: Option<PathBuf>,
+}
+
+/// Keeps the token out of logs.
+impl std::fmt::Debug for ZooConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZooConfig")
+            .field("cli", &self.cli)
+            .field("api_token", &self.api_token.as_ref().map(|_| "<redacted>"))
+            .field("kcl_source_dir", &self.kcl_source_dir)
+            .field("gltf_compression", &self.gltf_compression)
+            .field("daily_quota", &self.daily_quota)
+            .field("monthly_quota", &self.monthly_quota)
+            .field("quota_file", &self.quota_file)
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimitConfig {
+    /// Generation requests per client per minute, 0 for no limit (RATE_LIMIT_PER_MINUTE)
+    pub per_minute: u32,
+    /// Take the client address from X-Forwarded-For (RATE_LIMIT_TRUST_PROXY)
+    pub trust_proxy: bool,
+    /// Largest request body most routes accept (MAX_BODY_BYTES)
+    pub max_body_bytes: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CsrfConfig {
+    /// Other origins allowed to send state-changing requests, such as
+    /// `https://portal.example.com` (CSRF_TRUSTED_ORIGINS, comma-separated)
+    pub trusted_origins: Vec<String>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            port: 3030,
+            cache: CacheConfig {
+                backend: CacheBackend::Local,
+                dir: PathBuf::from("./cache"),
+                ttl: None,
+                warm_presets: false,
+                warm_interval: None,
+            },
+            aws: AwsConfig::default(),
+            zoo: ZooConfig {
+                cli: PathBuf::from("zoo"),
+                api_token: None,
+                kcl_source_dir: None,
+                gltf_compression: GltfCompression::None,
+                daily_quota: None,
+                monthly_quota: None,
+                quota_file: None,
+            },
+            rate_limit: RateLimitConfig {
+                per_minute: DEFAULT_REQUESTS_PER_MINUTE,
+                trust_proxy: false,
+                max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            },
+            csrf: CsrfConfig::default(),
+        }
+    }
+}
+
+/// Why the configuration couldn't be loaded.
+#[derive(Debug, thiserror::Error)]
+pub enum AppConfigError {
+    #[error("Could not read config file {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Config file {} is invalid: {message}", path.display())]
+    Parse { path: PathBuf, message: String },
+    #[error("Invalid configuration:{}", .0.iter().map(|p| format!("\n  - {}", p)).collect::<String>())]
+    Invalid(Vec<String>),
+}
+
+/// The config file as written; every setting is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    port: Option<u16>,
+    cache: FileCache,
+    aws: FileAws,
+    zoo: FileZoo,
+    rate_limit: FileRateLimit,
+    csrf: FileCsrf,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileCache {
+    backend: Option<CacheBackend>,
+    dir: Option<PathBuf>,
+    ttl_secs: Option<u64>,
+    warm_presets: Option<bool>,
+    warm_interval_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileAws {
+    s3_bucket: Option<String>,
+    dynamodb_table: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileZoo {
+    cli: Option<PathBuf>,
+    api_token: Option<String>,
+    kcl_source_dir: Option<PathBuf>,
+    gltf_compression: Option<GltfCompression>,
+    daily_quota: Option<u64>,
+    monthly_quota: Option<u64>,
+    quota_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileRateLimit {
+    per_minute: Option<u32>,
+    trust_proxy: Option<bool>,
+    max_body_bytes: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileCsrf {
+    trusted_origins: Option<Vec<String>>,
+}
+
+impl AppConfig {
+    /// Load from the config file, if any, and the process environment.
+    pub fn load() -> Result<Self, AppConfigError> {
+        let path = match std::env::var_os("STEEL_THREAD_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => Some(PathBuf::from(DEFAULT_CONFIG_FILE)).filter(|path| path.exists()),
+        };
+        let file = match &path {
+            Some(path) => {
+                let text = std::fs::read_to_string(path).map_err(|source| AppConfigError::Read {
+                    path: path.clone(),
+                    source,
+                })?;
+                tracing::info!("Reading configuration from {}", path.display());
+                Some((path.as_path(), text))
+            }
+            None => None,
+        };
+        Self::from_sources(
+            file.as_ref().map(|(path, text)| (*path, text.as_str())),
+            |key| std::env::var(key).ok(),
+        )
+    }
+
+    /// Layer `file` (its path and contents) and then `lookup` over the defaults.
+    fn from_sources(
+        file: Option<(&Path, &str)>,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, AppConfigError> {
+        let file: FileConfig = match file {
+            Some((path, text)) => toml::from_str(text).map_err(|e| AppConfigError::Parse {
+                path: path.to_path_buf(),
+                message: e.to_string().trim_end().to_string(),
+            })?,
+            None => FileConfig::default(),
+        };
+        let env = |key: &str| lookup(key).filter(|v| !v.is_empty());
+        let mut problems = Vec::new();
+
+        let mut config = AppConfig::default();
+
+        config.port = parse_var(&env, "PORT", "a port number", &mut problems)
+            .or(file.port)
+            .unwrap_or(config.port);
+
+        config.aws.s3_bucket = env("S3_BUCKET_NAME").or(file.aws.s3_bucket).filter(|v| !v.is_empty());
+        config.aws.dynamodb_table = env("DYNAMODB_TABLE").or(file.aws.dynamodb_table).filter(|v| !v.is_empty());
+
+        let backend = match env("CACHE_BACKEND") {
+            Some(name) => match name.parse() {
+                Ok(backend) => Some(backend),
+                Err(()) => {
+                    problems.push(format!(
+                        "CACHE_BACKEND must be local, memory, aws, or redis, got {:?}",
+                        name
+                    ));
+                    None
+                }
+            },
+            None => file.cache.backend,
+        };
+        config.cache.backend = backend.unwrap_or(if config.aws.s3_bucket.is_some() {
+            CacheBackend::Aws
+        } else {
+            CacheBackend::Local
+        });
+        config.cache.dir = env("CACHE_DIR").map(PathBuf::from).or(file.cache.dir).unwrap_or(config.cache.dir);
+        let ttl_secs = parse_var(&env, "CACHE_TTL_SECS", "a whole number of seconds", &mut problems).or(file.cache.ttl_secs);
+        config.cache.ttl = ttl_secs.filter(|secs| *secs > 0).map(Duration::from_secs);
+        config.cache.warm_presets = match env("CACHE_WARM_PRESETS") {
+            Some(value) => value == "true" || value == "1",
+            None => file.cache.warm_presets.unwrap_or(false),
+        };
+        let warm_secs = parse_var(&env, "CACHE_WARM_INTERVAL_SECS", "a whole number of seconds", &mut problems)
+            .or(file.cache.warm_interval_secs);
+        config.cache.warm_interval = warm_secs.filter(|secs| *secs > 0).map(Duration::from_secs);
+
+        config.zoo.cli = env("ZOO_CLI").map(PathBuf::from).or(file.zoo.cli).unwrap_or(config.zoo.cli);
+        config.zoo.api_token = env("ZOO_TOKEN").or(file.zoo.api_token).filter(|v| !v.is_empty());
+        config.zoo.kcl_source_dir = env("KCL_SRC_DIR").map(PathBuf::from).or(file.zoo.kcl_source_dir);
+        config.zoo.gltf_compression = parse_var(&env, "ZOO_GLTF_COMPRESSION", "none or quantize", &mut problems)
+            .or(file.zoo.gltf_compression)
+            .unwrap_or(config.zoo.gltf_compression);
+        let daily_quota = parse_var(&env, "ZOO_DAILY_QUOTA", "a whole number of calls", &mut problems).or(file.zoo.daily_quota);
+        config.zoo.daily_quota = daily_quota.filter(|calls| *calls > 0);
+        let monthly_quota = parse_var(&env, "ZOO_MONTHLY_QUOTA", "a whole number of calls", &mut problems).or(file.zoo.monthly_quota);
+        config.zoo.monthly_quota = monthly_quota.filter(|calls| *calls > 0);
+        config.zoo.quota_file = env("ZOO_QUOTA_FILE").map(PathBuf::from).or(file.zoo.quota_file);
+
+        config.rate_limit.per_minute = parse_var(&env, "RATE_LIMIT_PER_MINUTE", "a whole number", &mut problems)
+            .or(file.rate_limit.per_minute)
+            .unwrap_or(config.rate_limit.per_minute);
+        config.rate_limit.trust_proxy = match env("RATE_LIMIT_TRUST_PROXY") {
+            Some(value) => value == "true" || value == "1",
+            None => file.rate_limit.trust_proxy.unwrap_or(false),
+        };
+        config.rate_limit.max_body_bytes = parse_var(&env, "MAX_BODY_BYTES", "a whole number of bytes", &mut problems)
+            .or(file.rate_limit.max_body_bytes)
+            .unwrap_or(config.rate_limit.max_body_bytes);
+
+        config.csrf.trusted_origins = match env("CSRF_TRUSTED_ORIGINS") {
+            Some(origins) => origins.split(',').map(|o| o.trim().to_string()).filter(|o| !o.is_empty()).collect(),
+            None => file.csrf.trusted_origins.unwrap_or_default(),
+        };
+
+        problems.extend(config.problems(|key| env(key).is_some()));
+        if problems.is_empty() {
+            Ok(config)
+        } else {
+            Err(AppConfigError::Invalid(problems))
+        }
+    }
+
+    /// Settings that are each valid but don't work together, or name missing files.
+    fn problems(&self, is_set: impl Fn(&str) -> bool) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.cache.backend == CacheBackend::Aws {
+            if self.aws.s3_bucket.is_none() {
+                problems.push("The aws cache backend needs a bucket: set aws.s3_bucket or S3_BUCKET_NAME".to_string());
+            }
+            if self.aws.dynamodb_table.is_none() && !is_set("S3_ENDPOINT_URL") {
+                problems.push(
+                    "The aws cache backend needs a DynamoDB table (aws.dynamodb_table or DYNAMODB_TABLE) \
+                     unless S3_ENDPOINT_URL points at an S3-compatible store"
+                        .to_string(),
+                );
+            }
+        }
+        if self.cache.backend == CacheBackend::Redis && !is_set("REDIS_URL") {
+            problems.push("The redis cache backend needs REDIS_URL".to_string());
+        }
+        if self.cache.backend == CacheBackend::Local && self.cache.dir.exists() && !self.cache.dir.is_dir() {
+            problems.push(format!("Cache directory {} is not a directory", self.cache.dir.display()));
+        }
+        if let Some(dir) = &self.zoo.kcl_source_dir {
+            if !dir.join("main.kcl").is_file() {
+                problems.push(format!("KCL source directory {} has no main.kcl", dir.display()));
+            }
+        }
+        if self.rate_limit.max_body_bytes == 0 {
+            problems.push("The largest request body (rate_limit.max_body_bytes or MAX_BODY_BYTES) must be positive".to_string());
+        }
+        for origin in &self.csrf.trusted_origins {
+            let host = origin.strip_prefix("https://").or_else(|| origin.strip_prefix("http://"));
+            if !host.is_some_and(|host| !host.trim_end_matches('/').is_empty() && !host.trim_end_matches('/').contains('/')) {
+                problems.push(format!(
+                    "Trusted origin {:?} must be a scheme and host, such as https://portal.example.com",
+                    origin
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// The variable `key` parsed, recording a problem when it's set but doesn't parse.
+fn parse_var<T: std::str::FromStr>(
+    env: &impl Fn(&str) -> Option<String>,
+    key: &str,
+    expected: &str,
+    problems: &mut Vec<String>,
+) -> Option<T> {
+    let value = env(key)?;
+    match value.parse() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            problems.push(format!("{} must be {}, got {:?}", key, expected, value));
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    fn load(file: Option<&str>, vars: &[(&str, &str)]) -> Result<AppConfig, AppConfigError> {
+        AppConfig::from_sources(file.map(|text| (Path::new("steel-thread.toml"), text)), lookup(vars))
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(load(None, &[]).unwrap(), AppConfig::default());
+    }
+
+    #[test]
+    fn test_environment_overrides_file() {
+        let file = r#"
+            port = 8080
+
+            [cache]
+            backend = "memory"
+            ttl_secs = 60
+            warm_presets = true
+
+            [zoo]
+            api_token = "file-token"
+            gltf_compression = "quantize"
+            daily_quota = 50
+            monthly_quota = 1000
+
+            [rate_limit]
+            per_minute = 10
+            trust_proxy = true
+        "#;
+        let env = [
+            ("PORT", "9090"),
+            ("ZOO_TOKEN", "env-token"),
+            ("RATE_LIMIT_TRUST_PROXY", "false"),
+            ("CACHE_WARM_INTERVAL_SECS", "3600"),
+            ("ZOO_DAILY_QUOTA", "0"),
+        ];
+        let config = load(Some(file), &env).unwrap();
+        assert_eq!(config.port, 9090);
+        assert_eq!(config.cache.backend, CacheBackend::Memory);
+        assert_eq!(config.cache.ttl, Some(Duration::from_secs(60)));
+        assert!(config.cache.warm_presets);
+        assert_eq!(config.cache.warm_interval, Some(Duration::from_secs(3600)));
+        assert_eq!(config.zoo.api_token.as_deref(), Some("env-token"));
+        assert_eq!(config.zoo.gltf_compression, GltfCompression::Quantize);
+        assert_eq!((config.zoo.daily_quota, config.zoo.monthly_quota), (None, Some(1000)));
+        assert_eq!(config.rate_limit.per_minute, 10);
+        assert!(!config.rate_limit.trust_proxy);
+        assert!(!format!("{:?}", config).contains("env-token"));
+
+        let origins = "[csrf]\ntrusted_origins = [\"https://a.example\"]\n";
+        let config = load(Some(origins), &[("CSRF_TRUSTED_ORIGINS", "https://b.example, http://c.example:8080")]).unwrap();
+        assert_eq!(config.csrf.trusted_origins, vec!["https://b.example", "http://c.example:8080"]);
+
+        // An empty variable doesn't override the file
+        let config = load(Some(file), &[("PORT", "")]).unwrap();
+        assert_eq!(config.port, 8080);
+    }
+
+    #[test]
+    fn test_bucket_selects_the_aws_backend() {
+        let file = "[aws]\ns3_bucket = \"models\"\ndynamodb_table = \"cache\"\n";
+        let config = load(Some(file), &[]).unwrap();
+        assert_eq!(config.cache.backend, CacheBackend::Aws);
+        assert_eq!(config.aws.dynamodb_table.as_deref(), Some("cache"));
+
+        let config = load(Some(file), &[("CACHE_BACKEND", "local")]).unwrap();
+        assert_eq!(config.cache.backend, CacheBackend::Local);
+    }
+
+    #[test]
+    fn test_every_problem_is_reported() {
+        let err = load(
+            None,
+            &[
+                ("CACHE_BACKEND", "aws"),
+                ("PORT", "http"),
+                ("MAX_BODY_BYTES", "0"),
+                ("KCL_SRC_DIR", "/no/such/dir"),
+                ("CSRF_TRUSTED_ORIGINS", "portal.example.com"),
+            ],
+        )
+        .unwrap_err();
+        let AppConfigError::Invalid(problems) = &err else {
+            panic!("expected Invalid, got {:?}", err);
+        };
+        assert_eq!(problems.len(), 6, "{:#?}", problems);
+        let report = err.to_string();
+        assert!(report.starts_with("Invalid configuration:\n  - PORT must be a port number, got \"http\""), "{}", report);
+        assert!(report.contains("S3_BUCKET_NAME") && report.contains("DYNAMODB_TABLE"), "{}", report);
+
+        let err = load(None, &[("CACHE_BACKEND", "s3")]).unwrap_err();
+        assert!(err.to_string().contains("CACHE_BACKEND must be local, memory, aws, or redis"), "{}", err);
+    }
+
+    #[test]
+    fn test_example_file_loads() {
+        let example = include_str!("../../../steel-thread.example.toml");
+        assert_eq!(load(Some(example), &[]).unwrap(), AppConfig::default());
+    }
+
+    #[test]
+    fn test_file_errors_name_the_file() {
+        let err = load(Some("[cache]\nbackend = \"s3\"\n"), &[]).unwrap_err();
+        assert!(matches!(err, AppConfigError::Parse { .. }));
+        assert!(err.to_string().starts_with("Config file steel-thread.toml is invalid"), "{}", err);
+
+        let err = load(Some("[rate_limit]\nper_minit = 5\n"), &[]).unwrap_err();
+        assert!(err.to_string().contains("per_minit"), "{}", err);
+    }
+}
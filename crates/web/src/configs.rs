@@ -0,0 +1,203 @@
+//! Saved plate configurations and their storage.
+
+use async_trait::async_trait;
+use domain::{ActuatorPlate, SavedConfig};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// Errors that can occur during configuration storage operations.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The requested configuration was not found.
+    NotFound,
+    /// The backing store failed.
+    StorageError(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::NotFound => write!(f, "Configuration not found"),
+            ConfigError::StorageError(msg) => write!(f, "Configuration storage error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<sqlx::Error> for ConfigError {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            sqlx::Error::RowNotFound => ConfigError::NotFound,
+            e => ConfigError::StorageError(e.to_string()),
+        }
+    }
+}
+
+/// Trait for persisting saved plate configurations.
+#[async_trait]
+pub trait ConfigStore: Send + Sync {
+    /// Save a new configuration.
+    async fn insert(&self, config: &SavedConfig) -> Result<(), ConfigError>;
+
+    /// List all configurations, most recently saved first.
+    async fn list(&self) -> Result<Vec<SavedConfig>, ConfigError>;
+
+    /// Fetch one configuration by ID.
+    async fn get(&self, id: &str) -> Result<SavedConfig, ConfigError>;
+
+    /// Check that the store is reachable, for the readiness probe.
+    async fn check(&self) -> Result<(), ConfigError> {
+        Ok(())
+    }
+}
+
+/// In-memory configuration store for testing and development.
+pub struct MemoryConfigStore {
+    configs: RwLock<Vec<SavedConfig>>,
+}
+
+impl MemoryConfigStore {
+    pub fn new() -> Self {
+        Self {
+            configs: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl Default for MemoryConfigStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ConfigStore for MemoryConfigStore {
+    async fn insert(&self, config: &SavedConfig) -> Result<(), ConfigError> {
+        self.configs.write().unwrap().push(config.clone());
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<SavedConfig>, ConfigError> {
+        let mut configs = self.configs.read().unwrap().clone();
+        configs.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(configs)
+    }
+
+    async fn get(&self, id: &str) -> Result<SavedConfig, ConfigError> {
+        let configs = self.configs.read().unwrap();
+        configs
+            .iter()
+            .find(|c| c.id == id)
+            .cloned()
+            .ok_or(ConfigError::NotFound)
+    }
+}
+
+/// SQLite configuration store. The plate is stored as JSON so new plate fields
+/// don't need a schema migration.
+pub struct SqliteConfigStore {
+    pool: SqlitePool,
+}
+
+impl SqliteConfigStore {
+    /// Open (creating if needed) the database at `url`, e.g. `sqlite:///var/lib/platerator/configs.db`.
+    pub async fn connect(url: &str) -> Result<Self, ConfigError> {
+        let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+        let pool = SqlitePoolOptions::new().connect_with(options).await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS saved_configs (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                plate TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        Ok(Self { pool })
+    }
+
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<SavedConfig, ConfigError> {
+        let plate: String = row.try_get("plate")?;
+        let plate: ActuatorPlate = serde_json::from_str(&plate)
+            .map_err(|e| ConfigError::StorageError(format!("Unreadable saved plate: {}", e)))?;
+        Ok(SavedConfig {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            plate,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl ConfigStore for SqliteConfigStore {
+    async fn check(&self) -> Result<(), ConfigError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn insert(&self, config: &SavedConfig) -> Result<(), ConfigError> {
+        let plate = serde_json::to_string(&config.plate)
+            .map_err(|e| ConfigError::StorageError(e.to_string()))?;
+        sqlx::query("INSERT INTO saved_configs (id, name, plate, created_at, updated_at) VALUES (?, ?, ?, ?, ?)")
+            .bind(&config.id)
+            .bind(&config.name)
+            .bind(plate)
+            .bind(&config.created_at)
+            .bind(&config.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<SavedConfig>, ConfigError> {
+        let rows = sqlx::query("SELECT * FROM saved_configs ORDER BY updated_at DESC")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(Self::from_row).collect()
+    }
+
+    async fn get(&self, id: &str) -> Result<SavedConfig, ConfigError> {
+        let row = sqlx::query("SELECT * FROM saved_configs WHERE id = ?")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+        Self::from_row(&row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(id: &str, updated_at: &str) -> SavedConfig {
+        SavedConfig {
+            id: id.to_string(),
+            name: format!("config {}", id),
+            plate: ActuatorPlate::default(),
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            updated_at: updated_at.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}", dir.path().join("configs.db").display());
+        let store = SqliteConfigStore::connect(&url).await.unwrap();
+        store.insert(&config("a", "2026-01-01T00:00:00+00:00")).await.unwrap();
+        store.insert(&config("b", "2026-02-01T00:00:00+00:00")).await.unwrap();
+
+        let listed = store.list().await.unwrap();
+        assert_eq!(listed.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), ["b", "a"]);
+        assert_eq!(store.get("a").await.unwrap(), config("a", "2026-01-01T00:00:00+00:00"));
+        assert!(matches!(store.get("missing").await, Err(ConfigError::NotFound)));
+    }
+}
@@ -0,0 +1,120 @@
+//! Cross-site request forgery protection for the routes that change state.
+//!
+//! Nothing is authorized by a cookie: API keys and the admin token travel in
+//! headers a page on another site can't set. The `platerator_session` cookie only
+//! keys a browser's recent plates and is `SameSite=Lax`, so browsers leave it off
+//! cross-site POSTs, and the theme cookies only pick colors. So rather than a
+//! synchronizer or double-submit token, the guard relies on fetch metadata and
+//! `Origin`: a browser's unsafe request (anything but GET, HEAD, or OPTIONS) must
+//! come from our own origin or one listed in CSRF_TRUSTED_ORIGINS, such as a
+//! customer portal that calls the API directly:
+//!
+//! - `Sec-Fetch-Site: same-origin` or `none` (typed by the user) passes.
+//! - Otherwise the `Origin` header must be trusted, or, from browsers too old to
+//!   send `Sec-Fetch-Site`, match the request's `Host`.
+//! - Requests with neither header come from scripts and servers, which a page on
+//!   another site can't forge, and pass.
+//!
+//! A configurator embedded in a portal's iframe is served from our origin, so its
+//! own requests pass without listing the portal. Rejected requests get a 403.
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::sync::Arc;
+
+use crate::ErrorResponse;
+
+/// Decides which cross-origin requests may change state.
+#[derive(Debug, Default)]
+pub struct CsrfGuard {
+    /// Origins such as `https://portal.example.com`, without a trailing slash
+    trusted_origins: Vec<String>,
+}
+
+impl CsrfGuard {
+    /// A guard also trusting `trusted_origins`, compared without case or a trailing slash.
+    pub fn new(trusted_origins: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            trusted_origins: trusted_origins.into_iter().map(|origin| normalize(&origin)).collect(),
+        }
+    }
+
+    /// Whether a request with `method` and `headers` may go through.
+    fn allows(&self, method: &Method, headers: &HeaderMap) -> bool {
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            return true;
+        }
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        let origin = header("origin");
+        if origin.is_some_and(|origin| self.trusted_origins.contains(&normalize(origin))) {
+            return true;
+        }
+        match (header("sec-fetch-site"), origin) {
+            (Some(site), _) => site == "same-origin" || site == "none",
+            (None, Some(origin)) => {
+                let host = origin.split_once("://").map(|(_, host)| host.trim_end_matches('/'));
+                host.is_some_and(|host| header("host").is_some_and(|expected| host.eq_ignore_ascii_case(expected)))
+            }
+            (None, None) => true,
+        }
+    }
+}
+
+fn normalize(origin: &str) -> String {
+    origin.trim().trim_end_matches('/').to_ascii_lowercase()
+}
+
+/// Middleware rejecting cross-site requests that change state with 403 Forbidden.
+pub async fn protect(State(guard): State<Arc<CsrfGuard>>, request: Request, next: Next) -> Response {
+    if guard.allows(request.method(), request.headers()) {
+        return next.run(request).await;
+    }
+    let origin = request.headers().get(header::ORIGIN).and_then(|v| v.to_str().ok()).unwrap_or("unknown");
+    tracing::warn!("Rejected a cross-site {} {} from {}", request.method(), request.uri().path(), origin);
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec!["Cross-site requests can't change data on this server".to_string()],
+    };
+    (StatusCode::FORBIDDEN, Json(res)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        pairs.iter().map(|(name, value)| (header::HeaderName::from_static(name), value.parse().unwrap())).collect()
+    }
+
+    #[test]
+    fn test_browsers_must_be_same_origin_or_trusted() {
+        let guard = CsrfGuard::new(["https://Portal.example.com/".to_string()]);
+        let cross_site = headers(&[("sec-fetch-site", "cross-site"), ("origin", "https://evil.example")]);
+        assert!(!guard.allows(&Method::POST, &cross_site));
+        assert!(!guard.allows(&Method::DELETE, &cross_site));
+        assert!(guard.allows(&Method::GET, &cross_site));
+
+        let same_site = headers(&[("sec-fetch-site", "same-site"), ("origin", "https://shop.platerator.example")]);
+        assert!(!guard.allows(&Method::POST, &same_site));
+        assert!(guard.allows(&Method::POST, &headers(&[("sec-fetch-site", "same-origin")])));
+
+        let portal = headers(&[("sec-fetch-site", "cross-site"), ("origin", "https://portal.example.com")]);
+        assert!(guard.allows(&Method::POST, &portal));
+    }
+
+    #[test]
+    fn test_origin_is_checked_without_fetch_metadata() {
+        let guard = CsrfGuard::default();
+        let request = |origin: &str| headers(&[("origin", origin), ("host", "platerator.example:8443")]);
+        assert!(guard.allows(&Method::POST, &request("https://platerator.example:8443")));
+        assert!(!guard.allows(&Method::POST, &request("https://evil.example")));
+        assert!(!guard.allows(&Method::POST, &request("null")));
+
+        // Scripts and servers send neither header
+        assert!(guard.allows(&Method::POST, &HeaderMap::new()));
+    }
+}
@@ -0,0 +1,155 @@
+//! The operator dashboard at `/admin`: a single server-rendered HTML page built
+//! from the audit log and the cache counters.
+//!
+//! Browsers sign in with HTTP basic auth, using any user name and the admin token
+//! as the password, so the page needs no login form or session of its own.
+
+use axum::http::{header, HeaderMap};
+use base64::engine::{general_purpose, DecodePaddingMode, GeneralPurpose};
+use base64::{alphabet, Engine};
+use std::fmt::Write;
+
+use crate::audit::{AuditEvent, AuditKind};
+use crate::auth::secret_matches;
+use crate::cache_metrics::CacheStats;
+
+/// Realm sent in the `WWW-Authenticate` challenge.
+pub const REALM: &str = "Platerator admin";
+
+/// Standard base64, padded or not.
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    general_purpose::PAD.with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Whether the request's basic-auth password is `expected`. The user name is ignored.
+pub fn has_password(headers: &HeaderMap, expected: &str) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Basic "))
+        .and_then(|encoded| BASE64.decode(encoded.trim()).ok())
+        .and_then(|credentials| String::from_utf8(credentials).ok())
+        .is_some_and(|credentials| {
+            credentials
+                .split_once(':')
+                .is_some_and(|(_, password)| secret_matches(password, expected))
+        })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render the dashboard from the newest-first `events` and the current cache counters.
+pub fn render(stats: &CacheStats, events: &[AuditEvent]) -> String {
+    let generations: Vec<&AuditEvent> = events
+        .iter()
+        .filter(|e| matches!(e.kind, AuditKind::CacheHit | AuditKind::Generated | AuditKind::EngineFailure))
+        .collect();
+    let failures = events.iter().filter(|e| e.kind == AuditKind::EngineFailure);
+    let orders = events.iter().filter(|e| e.kind == AuditKind::Order);
+
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Platerator admin</title>\n<style>\n\
+         body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2933; }\n\
+         table { border-collapse: collapse; margin-bottom: 2rem; }\n\
+         th, td { border-bottom: 1px solid #d9e2ec; padding: 0.3rem 0.8rem; text-align: left; vertical-align: top; }\n\
+         td.error { font-family: monospace; white-space: pre-wrap; }\n\
+         </style>\n</head>\n<body>\n<h1>Platerator admin</h1>\n",
+    );
+
+    let _ = write!(
+        html,
+        "<h2>Cache</h2>\n<table>\n<tr><th>Hit rate</th><td>{:.1}%</td></tr>\n\
+         <tr><th>Hits</th><td>{} ({} from memory)</td></tr>\n<tr><th>Misses</th><td>{}</td></tr>\n\
+         <tr><th>Lookup errors</th><td>{}</td></tr>\n</table>\n",
+        stats.hit_ratio * 100.0,
+        stats.hits,
+        stats.memory_hits,
+        stats.misses,
+        stats.get_errors,
+    );
+
+    html.push_str("<h2>Recent generations</h2>\n<table>\n<tr><th>Time</th><th>Cache key</th><th>Outcome</th><th>Duration</th></tr>\n");
+    for event in &generations {
+        let outcome = match event.kind {
+            AuditKind::CacheHit => "cache hit",
+            AuditKind::Generated => "generated",
+            _ => "failed",
+        };
+        let duration = event.duration_ms.map(|ms| format!("{:.1} s", ms as f64 / 1000.0)).unwrap_or_default();
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&event.at),
+            escape(&event.subject),
+            outcome,
+            duration
+        );
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Failed Zoo calls</h2>\n<table>\n<tr><th>Time</th><th>Cache key</th><th>Error</th></tr>\n");
+    for event in failures {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td class=\"error\">{}</td></tr>",
+            escape(&event.at),
+            escape(&event.subject),
+            escape(&event.detail)
+        );
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Orders</h2>\n<table>\n<tr><th>Time</th><th>Order</th><th>Details</th></tr>\n");
+    for event in orders {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&event.at),
+            escape(&event.subject),
+            escape(&event.detail)
+        );
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache_metrics::CacheMetrics;
+
+    #[test]
+    fn test_basic_auth_checks_only_the_password() {
+        let headers = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::AUTHORIZATION, value.parse().unwrap());
+            headers
+        };
+        // "ops:s3cret" and "anyone:s3cret"
+        assert!(has_password(&headers("Basic b3BzOnMzY3JldA=="), "s3cret"));
+        assert!(has_password(&headers("Basic YW55b25lOnMzY3JldA"), "s3cret"));
+        assert!(!has_password(&headers("Basic b3BzOnMzY3JldA=="), "other"));
+        assert!(!has_password(&headers("Bearer s3cret"), "s3cret"));
+        assert!(!has_password(&headers("Basic !!!"), "s3cret"));
+        assert!(!has_password(&HeaderMap::new(), "s3cret"));
+    }
+
+    #[test]
+    fn test_render_escapes_error_text() {
+        let events = [
+            AuditEvent::new(AuditKind::EngineFailure, "plate-a").with_detail("expected <number>"),
+            AuditEvent::new(AuditKind::Order, "PL-000001").with_detail("2 × $120.00"),
+        ];
+        let html = render(&CacheMetrics::new().snapshot(), &events);
+        assert!(html.contains("expected &lt;number&gt;"));
+        assert!(!html.contains("<number>"));
+        assert!(html.contains("PL-000001"));
+    }
+}
@@ -0,0 +1,293 @@
+//! Error reporting for panics, engine failures, and failed requests.
+//!
+//! Reports go to an `ErrorReporter`. By default they are only logged; building with
+//! the `sentry` feature and setting SENTRY_DSN sends them to Sentry instead. Reports
+//! carry the request ID (from the `x-request-id` header) so they can be matched with
+//! access logs, and engine failures attach the generated KCL parameters.
+//!
+//! The same ID is a field of each request's tracing span, so every log line written
+//! while handling the request (including its background cache write or generation
+//! job) carries it, and JSON error bodies repeat it as `request_id` for bug reports.
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::Response;
+use std::fmt;
+use std::sync::Arc;
+
+/// Header carrying the per-request ID, set by the router if the client didn't send one.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// What kind of failure a report describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportKind {
+    /// A thread panicked.
+    Panic,
+    /// The KCL engine failed to produce a model.
+    EngineFailure,
+    /// A request handler returned a server error.
+    HandlerError,
+}
+
+impl ReportKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReportKind::Panic => "panic",
+            ReportKind::EngineFailure => "engine_failure",
+            ReportKind::HandlerError => "handler_error",
+        }
+    }
+}
+
+/// A text file attached to a report, such as the generated KCL program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attachment {
+    pub filename: String,
+    pub contents: String,
+}
+
+/// A single error report.
+#[derive(Clone, Debug)]
+pub struct ErrorReport {
+    pub kind: ReportKind,
+    pub message: String,
+    /// ID of the request that failed, if the failure happened while serving one.
+    pub request_id: Option<String>,
+    pub attachments: Vec<Attachment>,
+}
+
+impl ErrorReport {
+    pub fn new(kind: ReportKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            request_id: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
+    pub fn with_attachment(mut self, filename: impl Into<String>, contents: impl Into<String>) -> Self {
+        self.attachments.push(Attachment {
+            filename: filename.into(),
+            contents: contents.into(),
+        });
+        self
+    }
+}
+
+impl fmt::Display for ErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.kind.as_str(), self.message)?;
+        if let Some(id) = &self.request_id {
+            write!(f, " (request {})", id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Trait for sending error reports somewhere a human will see them.
+///
+/// Reporting is synchronous so it can be called from a panic hook; implementations
+/// should hand off to a background transport rather than block.
+pub trait ErrorReporter: Send + Sync {
+    fn report(&self, report: &ErrorReport);
+}
+
+/// Reporter that writes reports to the tracing log.
+pub struct LogReporter;
+
+impl ErrorReporter for LogReporter {
+    fn report(&self, report: &ErrorReport) {
+        tracing::error!("{}", report);
+        for attachment in &report.attachments {
+            tracing::debug!("{}:\n{}", attachment.filename, attachment.contents);
+        }
+    }
+}
+
+/// Reporter that sends reports to Sentry.
+#[cfg(feature = "sentry")]
+pub struct SentryReporter {
+    // Keeps the client alive; dropping it flushes pending events.
+    _guard: sentry::ClientInitGuard,
+}
+
+#[cfg(feature = "sentry")]
+impl SentryReporter {
+    /// Initialize Sentry from SENTRY_DSN, with an optional SENTRY_ENVIRONMENT.
+    /// Returns None when no DSN is configured.
+    pub fn from_env() -> Option<Self> {
+        let dsn = std::env::var("SENTRY_DSN").ok().filter(|d| !d.is_empty())?;
+        let guard = sentry::init((
+            dsn,
+            sentry::ClientOptions {
+                release: Some(env!("GIT_HASH").into()),
+                environment: std::env::var("SENTRY_ENVIRONMENT").ok().map(Into::into),
+                ..Default::default()
+            },
+        ));
+        Some(Self { _guard: guard })
+    }
+}
+
+#[cfg(feature = "sentry")]
+impl ErrorReporter for SentryReporter {
+    fn report(&self, report: &ErrorReport) {
+        sentry::with_scope(
+            |scope| {
+                scope.set_tag("kind", report.kind.as_str());
+                if let Some(id) = &report.request_id {
+                    scope.set_tag("request_id", id);
+                }
+                for attachment in &report.attachments {
+                    scope.add_attachment(sentry::protocol::Attachment {
+                        buffer: attachment.contents.clone().into_bytes(),
+                        filename: attachment.filename.clone(),
+                        content_type: Some("text/plain".to_string()),
+                        ..Default::default()
+                    });
+                }
+            },
+            || sentry::capture_message(&report.message, sentry::Level::Error),
+        );
+    }
+}
+
+/// Build the reporter for this deployment: Sentry when compiled in and configured,
+/// otherwise the log.
+pub fn reporter_from_env() -> Arc<dyn ErrorReporter> {
+    #[cfg(feature = "sentry")]
+    if let Some(reporter) = SentryReporter::from_env() {
+        tracing::info!("Reporting errors to Sentry");
+        return Arc::new(reporter);
+    }
+    Arc::new(LogReporter)
+}
+
+/// Report panics through `reporter`, then run the previously installed hook.
+pub fn install_panic_hook(reporter: Arc<dyn ErrorReporter>) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let payload = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic with non-string payload".to_string());
+        let message = match info.location() {
+            Some(location) => format!("{} at {}", payload, location),
+            None => payload,
+        };
+        reporter.report(&ErrorReport::new(ReportKind::Panic, message));
+        previous(info);
+    }));
+}
+
+/// The request ID header value, if present.
+pub fn request_id(headers: &axum::http::HeaderMap) -> Option<String> {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Span for one HTTP request, carrying its request ID. Handlers fill in
+/// `cache_key` once they know it.
+pub fn request_span<B>(request: &axum::http::Request<B>) -> tracing::Span {
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id = request_id(request.headers()).as_deref().unwrap_or("-"),
+        cache_key = tracing::field::Empty,
+    )
+}
+
+/// Largest error body rewritten by [`tag_error_responses`]; error bodies are a few
+/// hundred bytes, so anything bigger is passed through untouched.
+const MAX_TAGGED_BODY: usize = 64 * 1024;
+
+/// Middleware adding the request ID to JSON error bodies as `request_id`, so users
+/// can quote it when reporting a problem.
+pub async fn tag_error_responses(request: Request, next: Next) -> Response {
+    let id = request_id(request.headers());
+    let response = next.run(request).await;
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+    let Some(id) = id.filter(|_| is_json && (response.status().is_client_error() || response.status().is_server_error()))
+    else {
+        return response;
+    };
+
+    let small = axum::body::HttpBody::size_hint(response.body())
+        .upper()
+        .is_some_and(|len| len <= MAX_TAGGED_BODY as u64);
+    if !small {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_TAGGED_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Could not read error body to tag it: {}", e);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let body = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(serde_json::Value::Object(mut fields)) => {
+            fields.insert("request_id".to_string(), serde_json::Value::String(id));
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Body::from(serde_json::Value::Object(fields).to_string())
+        }
+        _ => Body::from(bytes),
+    };
+    Response::from_parts(parts, body)
+}
+
+/// Middleware that reports every 5xx response along with its request ID.
+pub async fn report_server_errors(
+    State(reporter): State<Arc<dyn ErrorReporter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let id = request_id(request.headers());
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+
+    let response = next.run(request).await;
+    if response.status().is_server_error() {
+        reporter.report(
+            &ErrorReport::new(
+                ReportKind::HandlerError,
+                format!("{} {} returned {}", method, path, response.status()),
+            )
+            .with_request_id(id),
+        );
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_display_includes_kind_and_request_id() {
+        let report = ErrorReport::new(ReportKind::EngineFailure, "engine closed early")
+            .with_request_id(Some("req-1".to_string()))
+            .with_attachment("params.kcl", "export pinCount = 2");
+
+        assert_eq!(report.to_string(), "[engine_failure] engine closed early (request req-1)");
+        assert_eq!(report.attachments[0].filename, "params.kcl");
+    }
+}
@@ -0,0 +1,222 @@
+//! Runtime feature flags for experimental generation paths.
+//!
+//! Each [`Flag`] starts at its default, then takes its value from the JSON file named
+//! by FEATURE_FLAGS_FILE (e.g. `{"async_jobs": false}`), then from a
+//! `FEATURE_<NAME>` environment variable such as `FEATURE_ASYNC_JOBS=false`, which
+//! wins. The file is re-read while the server runs, so flags checked per request
+//! change without a restart; flags that pick a backend apply at startup.
+//!
+//! `/api/flags` lists the current values so the frontend can follow the same
+//! experiments as the server instead of being redeployed alongside it.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// An experimental behavior that can be switched on or off per deployment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Flag {
+    /// Write the STEP file locally when the engine's export drops. Read at startup.
+    StepFallback,
+    /// Allow `CACHE_BACKEND=redis`; otherwise the local cache is used. Read at startup.
+    RedisCache,
+    /// Background generation: `/api/jobs`, `/api/plates/batch`, `/api/sweep`, and
+    /// the progress WebSocket. When off, clients should call `/api/generate`.
+    AsyncJobs,
+}
+
+impl Flag {
+    pub const ALL: [Flag; 3] = [Flag::StepFallback, Flag::RedisCache, Flag::AsyncJobs];
+
+    /// Name used in the flags file, `/api/flags`, and (uppercased) the environment.
+    pub fn name(self) -> &'static str {
+        match self {
+            Flag::StepFallback => "step_fallback",
+            Flag::RedisCache => "redis_cache",
+            Flag::AsyncJobs => "async_jobs",
+        }
+    }
+
+    /// Value when neither the file nor the environment sets it. Each experiment
+    /// defaults to how it behaved before it had a flag.
+    pub fn default_enabled(self) -> bool {
+        match self {
+            Flag::StepFallback | Flag::RedisCache | Flag::AsyncJobs => true,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Flag> {
+        Flag::ALL.into_iter().find(|flag| flag.name() == name)
+    }
+
+    fn env_var(self) -> String {
+        format!("FEATURE_{}", self.name().to_ascii_uppercase())
+    }
+}
+
+/// Current flag values, shared by every request.
+#[derive(Debug)]
+pub struct FeatureFlags {
+    values: RwLock<BTreeMap<Flag, bool>>,
+    /// Environment overrides, kept so they still win after the file is reloaded
+    overrides: BTreeMap<Flag, bool>,
+    file: Option<PathBuf>,
+}
+
+impl Default for FeatureFlags {
+    /// Every flag at its default, with no file or overrides.
+    fn default() -> Self {
+        Self::new(None, BTreeMap::new())
+    }
+}
+
+impl FeatureFlags {
+    fn new(file: Option<PathBuf>, overrides: BTreeMap<Flag, bool>) -> Self {
+        let flags = Self {
+            values: RwLock::new(BTreeMap::new()),
+            overrides,
+            file,
+        };
+        flags.reload();
+        flags
+    }
+
+    /// Read FEATURE_FLAGS_FILE and the `FEATURE_<NAME>` variables.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let mut overrides = BTreeMap::new();
+        for flag in Flag::ALL {
+            let var = flag.env_var();
+            let Some(value) = lookup(&var).filter(|v| !v.is_empty()) else {
+                continue;
+            };
+            match parse_bool(&value) {
+                Some(enabled) => {
+                    overrides.insert(flag, enabled);
+                }
+                None => tracing::warn!("{} must be true or false, got {:?}; ignoring it", var, value),
+            }
+        }
+        let file = lookup("FEATURE_FLAGS_FILE").filter(|f| !f.is_empty()).map(PathBuf::from);
+        Self::new(file, overrides)
+    }
+
+    /// Flags with fixed values, e.g. for tests.
+    pub fn with(values: &[(Flag, bool)]) -> Self {
+        Self::new(None, values.iter().copied().collect())
+    }
+
+    pub fn is_enabled(&self, flag: Flag) -> bool {
+        let values = self.values.read().unwrap();
+        values.get(&flag).copied().unwrap_or_else(|| flag.default_enabled())
+    }
+
+    /// Every flag by name, as served at `/api/flags`.
+    pub fn snapshot(&self) -> BTreeMap<&'static str, bool> {
+        Flag::ALL.into_iter().map(|flag| (flag.name(), self.is_enabled(flag))).collect()
+    }
+
+    /// The file to watch for changes, if there is one.
+    pub fn file(&self) -> Option<&std::path::Path> {
+        self.file.as_deref()
+    }
+
+    /// Re-read the flags file. A missing or malformed file keeps the current values.
+    pub fn reload(&self) {
+        let mut values = self.values.read().unwrap().clone();
+        if let Some(path) = &self.file {
+            match std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| serde_json::from_str::<BTreeMap<String, bool>>(&text).map_err(|e| e.to_string()))
+            {
+                Ok(file) => {
+                    values.clear();
+                    for (name, enabled) in file {
+                        match Flag::from_name(&name) {
+                            Some(flag) => {
+                                values.insert(flag, enabled);
+                            }
+                            None => tracing::warn!("Unknown feature flag {:?} in {}", name, path.display()),
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Could not read feature flags from {}: {}", path.display(), e),
+            }
+        }
+        values.extend(&self.overrides);
+
+        let mut current = self.values.write().unwrap();
+        for flag in Flag::ALL {
+            let enabled = |values: &BTreeMap<Flag, bool>| values.get(&flag).copied().unwrap_or(flag.default_enabled());
+            if enabled(&current) != enabled(&values) {
+                tracing::info!("Feature flag {} is {}", flag.name(), if enabled(&values) { "on" } else { "off" });
+            }
+        }
+        *current = values;
+    }
+}
+
+/// How often the flags file is re-read.
+pub const RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "on" | "yes" => Some(true),
+        "0" | "false" | "off" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn test_defaults_without_configuration() {
+        let flags = FeatureFlags::from_lookup(lookup(&[]));
+        for flag in Flag::ALL {
+            assert_eq!(flags.is_enabled(flag), flag.default_enabled());
+        }
+        assert_eq!(flags.snapshot().len(), Flag::ALL.len());
+    }
+
+    #[test]
+    fn test_environment_overrides_the_file_across_reloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flags.json");
+        std::fs::write(&path, r#"{"async_jobs": false, "redis_cache": false, "no_such_flag": true}"#).unwrap();
+
+        let flags = FeatureFlags::from_lookup(lookup(&[
+            ("FEATURE_FLAGS_FILE", path.to_str().unwrap()),
+            ("FEATURE_REDIS_CACHE", "on"),
+            ("FEATURE_STEP_FALLBACK", "maybe"),
+        ]));
+        assert!(!flags.is_enabled(Flag::AsyncJobs));
+        assert!(flags.is_enabled(Flag::RedisCache));
+        // Unparsable overrides are ignored
+        assert!(flags.is_enabled(Flag::StepFallback));
+
+        std::fs::write(&path, r#"{"async_jobs": true, "step_fallback": false}"#).unwrap();
+        flags.reload();
+        assert!(flags.is_enabled(Flag::AsyncJobs));
+        assert!(!flags.is_enabled(Flag::StepFallback));
+
+        // A broken file keeps the last good values
+        std::fs::write(&path, "{").unwrap();
+        flags.reload();
+        assert!(!flags.is_enabled(Flag::StepFallback));
+    }
+}
@@ -0,0 +1,150 @@
+//! Single-flight generation: concurrent requests for the same plate share one run
+//! of the engine.
+//!
+//! The first request to [`InFlight::claim`] a cache key gets a [`Lead`] and
+//! generates. Requests for the key while it's held wait and get the leader's
+//! outcome instead: its files, or the engine's error. The leader hands the lead to
+//! its cache write, so requests that arrive before the files are cached still
+//! share them rather than generating again. A leader that gives up without an
+//! outcome, e.g. because the plate was invalid, lets one of the waiters lead.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+use crate::CachedFiles;
+
+/// A generation's result as seen by the requests that waited for it: the files, or
+/// the engine's error message.
+pub type Outcome = Result<CachedFiles, String>;
+
+type Flights = Arc<Mutex<HashMap<String, watch::Receiver<Option<Outcome>>>>>;
+
+/// Generations running now, by cache key.
+#[derive(Debug, Default)]
+pub struct InFlight {
+    flights: Flights,
+}
+
+/// What [`InFlight::claim`] found.
+#[derive(Debug)]
+pub enum Claim {
+    /// Nobody else is generating the plate; this request should
+    Lead(Lead),
+    /// Another request generated the plate while this one waited
+    Shared(Outcome),
+}
+
+/// The right to generate a plate. Requests for the same plate wait until it's
+/// dropped or [`finish`](Lead::finish) is called.
+#[derive(Debug)]
+pub struct Lead {
+    cache_key: String,
+    sender: watch::Sender<Option<Outcome>>,
+    /// Identifies this lead's entry in `flights`
+    receiver: watch::Receiver<Option<Outcome>>,
+    flights: Flights,
+}
+
+impl InFlight {
+    /// Lead the generation of `cache_key`, or wait for the request already leading it.
+    pub async fn claim(&self, cache_key: &str) -> Claim {
+        loop {
+            let mut waiting = {
+                let mut flights = self.flights.lock().unwrap();
+                match flights.get(cache_key) {
+                    Some(flight) => flight.clone(),
+                    None => {
+                        let (sender, receiver) = watch::channel(None);
+                        flights.insert(cache_key.to_string(), receiver.clone());
+                        return Claim::Lead(Lead {
+                            cache_key: cache_key.to_string(),
+                            sender,
+                            receiver,
+                            flights: self.flights.clone(),
+                        });
+                    }
+                }
+            };
+            // An error means the leader gave up without an outcome; try to lead instead
+            let outcome = waiting.wait_for(Option::is_some).await.ok().and_then(|outcome| outcome.clone());
+            if let Some(outcome) = outcome {
+                tracing::info!("Sharing the generation of {} with a concurrent request", cache_key);
+                return Claim::Shared(outcome);
+            }
+        }
+    }
+}
+
+impl Lead {
+    /// Hand `outcome` to the requests waiting now, and to any that arrive before
+    /// this lead is dropped.
+    pub fn finish(&self, outcome: Outcome) {
+        self.sender.send_replace(Some(outcome));
+    }
+}
+
+impl Drop for Lead {
+    fn drop(&mut self) {
+        let mut flights = self.flights.lock().unwrap();
+        if flights.get(&self.cache_key).is_some_and(|flight| flight.same_channel(&self.receiver)) {
+            flights.remove(&self.cache_key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Artifact;
+    use std::time::Duration;
+
+    fn lead(claim: Claim) -> Lead {
+        match claim {
+            Claim::Lead(lead) => lead,
+            Claim::Shared(outcome) => panic!("expected to lead, got {:?}", outcome),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_waiters_share_the_leaders_outcome() {
+        let in_flight = Arc::new(InFlight::default());
+        let leader = lead(in_flight.claim("plate-a").await);
+        // Other plates aren't held up
+        drop(lead(in_flight.claim("plate-b").await));
+
+        let waiter = tokio::spawn({
+            let in_flight = in_flight.clone();
+            async move { in_flight.claim("plate-a").await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        let files = CachedFiles::new().with(Artifact::Step, "step");
+        leader.finish(Ok(files.clone()));
+        assert!(matches!(waiter.await.unwrap(), Claim::Shared(Ok(shared)) if shared == files));
+        // Until the lead is dropped, later requests share it too
+        assert!(matches!(in_flight.claim("plate-a").await, Claim::Shared(Ok(_))));
+
+        drop(leader);
+        assert!(in_flight.flights.lock().unwrap().is_empty());
+        drop(lead(in_flight.claim("plate-a").await));
+    }
+
+    #[tokio::test]
+    async fn test_a_waiter_leads_when_the_leader_gives_up() {
+        let in_flight = Arc::new(InFlight::default());
+        let leader = lead(in_flight.claim("plate-a").await);
+        let waiter = tokio::spawn({
+            let in_flight = in_flight.clone();
+            async move { in_flight.claim("plate-a").await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        drop(leader);
+        let next = lead(waiter.await.unwrap());
+        assert_eq!(in_flight.flights.lock().unwrap().len(), 1);
+        next.finish(Err("engine down".to_string()));
+        assert!(matches!(in_flight.claim("plate-a").await, Claim::Shared(Err(msg)) if msg == "engine down"));
+    }
+}
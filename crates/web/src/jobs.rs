@@ -0,0 +1,390 @@
+//! Background generation jobs.
+//!
+//! A Zoo export takes tens of seconds, so `POST /api/jobs` validates the plate,
+//! records a job, and returns straight away. The export runs on the blocking pool
+//! under the supervisor, and clients poll `GET /api/jobs/{id}` until the job has
+//! succeeded or failed. At most `JOB_CONCURRENCY` jobs generate at once; the rest
+//! wait in `queued`. Jobs started together by `POST /api/plates/batch` can be held to
+//! a smaller limit of their own so one batch doesn't take every slot.
+//!
+//! Clients that would rather not poll can open `/ws/generation/{id}`, which sends
+//! the job as a JSON text message each time it changes stage and closes once it
+//! has finished.
+
+use axum::extract::ws::{Message, WebSocket};
+use domain::ActuatorPlate;
+use parametric::{generate_model_with_progress, Stage};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Semaphore};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validation::Locale;
+
+use crate::inflight::Claim;
+use crate::{AppState, ErrorDetail, GenerateSuccessResponse, SessionData, WebhookEvent};
+
+/// Where a job is in its lifecycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum JobStatus {
+    /// Waiting for a free generation slot.
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobStatus {
+    fn is_finished(self) -> bool {
+        matches!(self, JobStatus::Succeeded | JobStatus::Failed)
+    }
+}
+
+/// A model generation job.
+#[derive(Clone, Serialize, ToSchema)]
+pub(crate) struct Job {
+    /// Job identifier, used to poll `/api/jobs/{id}`
+    pub id: String,
+    pub status: JobStatus,
+    /// Rough completion percentage, 0-100
+    pub progress: u8,
+    /// What the job is doing right now
+    pub stage: String,
+    /// Machine-readable stage while running, e.g. `exporting_gltf`
+    pub stage_code: Option<String>,
+    /// Cache key of the plate being generated
+    pub cache_key: String,
+    /// When the job was created (RFC 3339)
+    pub created_at: String,
+    /// When the job last changed (RFC 3339)
+    pub updated_at: String,
+    /// Download URLs, once the job has succeeded
+    pub result: Option<GenerateSuccessResponse>,
+    /// Why the job failed; empty unless it has
+    pub errors: Vec<ErrorDetail>,
+    /// ID of the API key that started the job, whose webhooks hear when it finishes
+    #[serde(skip)]
+    pub owner: Option<String>,
+}
+
+impl Job {
+    fn new(cache_key: &str, owner: Option<String>) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            status: JobStatus::Queued,
+            progress: 0,
+            stage: "Waiting for a free generation slot".to_string(),
+            stage_code: None,
+            cache_key: cache_key.to_string(),
+            created_at: now.clone(),
+            updated_at: now,
+            result: None,
+            errors: Vec::new(),
+            owner,
+        }
+    }
+
+    fn advance(&mut self, status: JobStatus, progress: u8, stage: &str) {
+        self.status = status;
+        self.progress = progress;
+        self.stage = stage.to_string();
+        self.stage_code = None;
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+    }
+
+    /// Mark the job as running `stage`, with progress to match.
+    fn enter(&mut self, stage: Stage) {
+        let progress = match stage {
+            Stage::Validating => 5,
+            Stage::GeneratingKcl => 10,
+            Stage::UploadingToZoo => 20,
+            Stage::ExportingStep => 45,
+            Stage::ExportingGltf => 60,
+            Stage::ExportingStl => 75,
+            Stage::Caching => 90,
+        };
+        self.advance(JobStatus::Running, progress, stage.label());
+        self.stage_code = Some(stage.code().to_string());
+    }
+
+    pub(crate) fn succeed(&mut self, result: GenerateSuccessResponse) {
+        self.advance(JobStatus::Succeeded, 100, "Model files are ready");
+        self.result = Some(result);
+    }
+
+    fn fail(&mut self, errors: Vec<ErrorDetail>) {
+        self.advance(JobStatus::Failed, 100, "Generation failed");
+        self.errors = errors;
+    }
+}
+
+struct Entry {
+    job: Job,
+    finished_at: Option<Instant>,
+    /// Latest copy of the job for progress streams; dropped with the entry.
+    updates: watch::Sender<Job>,
+}
+
+/// In-memory record of generation jobs, plus the slots that limit how many run at once.
+///
+/// Finished jobs are kept for an hour so slow pollers still see the outcome; the
+/// download sessions they point at live as long as the server.
+pub struct JobStore {
+    jobs: Mutex<HashMap<String, Entry>>,
+    slots: Arc<Semaphore>,
+    retention: Duration,
+}
+
+impl Default for JobStore {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl JobStore {
+    /// Create a store that runs at most `concurrency` generations at a time.
+    pub fn new(concurrency: usize) -> Self {
+        Self {
+            jobs: Mutex::new(HashMap::new()),
+            slots: Arc::new(Semaphore::new(concurrency.max(1))),
+            retention: Duration::from_secs(60 * 60),
+        }
+    }
+
+    /// Read the concurrency limit from JOB_CONCURRENCY, defaulting to 2.
+    pub fn from_env() -> Self {
+        match std::env::var("JOB_CONCURRENCY").ok().map(|v| v.parse::<usize>()) {
+            Some(Ok(concurrency)) => Self::new(concurrency),
+            Some(Err(_)) => {
+                tracing::warn!("JOB_CONCURRENCY must be a whole number; using the default");
+                Self::default()
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Record a new queued job, dropping finished jobs past their retention.
+    pub(crate) fn create(&self, cache_key: &str, owner: Option<String>) -> Job {
+        let job = Job::new(cache_key, owner);
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.retain(|_, entry| entry.finished_at.is_none_or(|at| at.elapsed() < self.retention));
+        jobs.insert(
+            job.id.clone(),
+            Entry {
+                job: job.clone(),
+                finished_at: None,
+                updates: watch::Sender::new(job.clone()),
+            },
+        );
+        job
+    }
+
+    pub(crate) fn get(&self, id: &str) -> Option<Job> {
+        self.jobs.lock().unwrap().get(id).map(|entry| entry.job.clone())
+    }
+
+    /// Apply `change` to a job and return the updated copy.
+    pub(crate) fn update(&self, id: &str, change: impl FnOnce(&mut Job)) -> Option<Job> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let entry = jobs.get_mut(id)?;
+        change(&mut entry.job);
+        if entry.job.status.is_finished() && entry.finished_at.is_none() {
+            entry.finished_at = Some(Instant::now());
+        }
+        entry.updates.send_replace(entry.job.clone());
+        Some(entry.job.clone())
+    }
+
+    /// Watch a job's changes, starting from its current state.
+    pub(crate) fn subscribe(&self, id: &str) -> Option<watch::Receiver<Job>> {
+        self.jobs.lock().unwrap().get(id).map(|entry| entry.updates.subscribe())
+    }
+}
+
+/// Run a queued job to completion. Spawned with [`crate::Supervisor::spawn_task`] so
+/// shutdown waits for jobs that are already generating.
+///
+/// `batch_slots` is shared by the jobs of one batch and is taken before a server-wide
+/// slot, so it caps how many of them generate at once.
+pub(crate) async fn run(
+    state: AppState,
+    job_id: String,
+    plate: ActuatorPlate,
+    request_id: Option<String>,
+    batch_slots: Option<Arc<Semaphore>>,
+) {
+    let cache_key = plate.cache_key();
+    let lead = match state.in_flight.claim(&cache_key).await {
+        Claim::Lead(lead) => lead,
+        Claim::Shared(outcome) => {
+            let job = match outcome {
+                Ok(files) => {
+                    let message = "Model files generated for a concurrent request";
+                    let res = crate::open_session(&state, &plate, &cache_key, SessionData::Cached(files), message, Locale::En).await;
+                    state.jobs.update(&job_id, |job| job.succeed(res))
+                }
+                Err(msg) => state.jobs.update(&job_id, |job| job.fail(vec![ErrorDetail::generation_failed(msg)])),
+            };
+            if let Some(job) = job {
+                notify_finished(&state, &job);
+            }
+            return;
+        }
+    };
+
+    // The semaphores are never closed, so acquiring only waits.
+    let _batch_slot = match batch_slots {
+        Some(slots) => slots.acquire_owned().await.ok(),
+        None => None,
+    };
+    let _slot = state.jobs.slots.clone().acquire_owned().await.ok();
+    if let Err(e) = crate::spend_zoo_quota(&state) {
+        if let Some(job) = state.jobs.update(&job_id, |job| job.fail(vec![ErrorDetail::quota_exhausted(&e)])) {
+            notify_finished(&state, &job);
+        }
+        return;
+    }
+    let started = std::time::Instant::now();
+    let generated = {
+        let state = state.clone();
+        let job_id = job_id.clone();
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || {
+            let progress = |stage| {
+                state.jobs.update(&job_id, |job| job.enter(stage));
+            };
+            span.in_scope(|| generate_model_with_progress(&plate, state.engine.as_ref(), &state.limits, &progress))
+        })
+        .await
+    };
+
+    if let Ok(result) = &generated {
+        crate::observe_generation(&state, &cache_key, result, started.elapsed());
+    }
+    if let Ok(Err(parametric::AllErrors::GeneratorError(msg))) = &generated {
+        lead.finish(Err(msg.clone()));
+    }
+    let job = match generated {
+        Ok(Ok(result)) => {
+            state.jobs.update(&job_id, |job| job.enter(Stage::Caching));
+            let res = crate::store_generated(&state, lead, &cache_key, &plate, result, Locale::En).await;
+            state.jobs.update(&job_id, |job| job.succeed(res))
+        }
+        Ok(Err(e)) => {
+            // Plates are validated before they're queued, so only engine failures,
+            // which aren't translated, get here
+            let (errors, _) = crate::generation_errors(&state, request_id, Locale::En, &cache_key, &plate, e);
+            state.jobs.update(&job_id, |job| job.fail(errors))
+        }
+        Err(e) => {
+            tracing::error!("generation job {} panicked: {}", job_id, e);
+            let errors = vec![ErrorDetail::generation_failed("Model generation crashed".to_string())];
+            state.jobs.update(&job_id, |job| job.fail(errors))
+        }
+    };
+
+    if let Some(job) = job {
+        notify_finished(&state, &job);
+    }
+}
+
+/// Send `job.completed` to the operator's webhooks and those of the job's API key.
+pub(crate) fn notify_finished(state: &AppState, job: &Job) {
+    state.webhooks.dispatch(
+        &state.supervisor,
+        WebhookEvent::JobCompleted,
+        job.owner.as_deref(),
+        serde_json::json!({
+            "job_id": job.id,
+            "status": job.status,
+            "cache_key": job.cache_key,
+            "result": job.result,
+            "errors": job.errors,
+        }),
+    );
+}
+
+/// Send `updates` over `socket` as JSON until the job finishes, the job expires, or
+/// the client goes away.
+pub(crate) async fn stream_progress(mut socket: WebSocket, mut updates: watch::Receiver<Job>) {
+    loop {
+        let job = updates.borrow_and_update().clone();
+        let Ok(text) = serde_json::to_string(&job) else {
+            return;
+        };
+        if socket.send(Message::Text(text.into())).await.is_err() {
+            return;
+        }
+        if job.status.is_finished() {
+            break;
+        }
+        tokio::select! {
+            changed = updates.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => {
+                if matches!(message, None | Some(Err(_)) | Some(Ok(Message::Close(_)))) {
+                    return;
+                }
+            }
+        }
+    }
+    socket.send(Message::Close(None)).await.ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finished_jobs_are_pruned_after_retention() {
+        let mut store = JobStore::new(1);
+        store.retention = Duration::ZERO;
+
+        let running = store.create("plate-a", None);
+        let finished = store.create("plate-b", None);
+        store.update(&finished.id, |job| job.fail(vec![]));
+
+        store.create("plate-c", None);
+        assert!(store.get(&running.id).is_some());
+        assert!(store.get(&finished.id).is_none());
+    }
+
+    #[test]
+    fn test_update_tracks_status_and_progress() {
+        let store = JobStore::default();
+        let job = store.create("plate-a", None);
+        assert_eq!(job.status, JobStatus::Queued);
+        assert_eq!(job.progress, 0);
+
+        let job = store
+            .update(&job.id, |job| job.advance(JobStatus::Running, 10, "Generating"))
+            .unwrap();
+        assert_eq!(job.status, JobStatus::Running);
+        assert_eq!(store.get(&job.id).unwrap().stage, "Generating");
+        assert!(store.update("missing", |_| {}).is_none());
+    }
+
+    #[test]
+    fn test_subscribers_see_stage_changes() {
+        let store = JobStore::default();
+        let job = store.create("plate-a", None);
+        let mut updates = store.subscribe(&job.id).unwrap();
+
+        store.update(&job.id, |job| job.enter(Stage::ExportingGltf));
+        assert!(updates.has_changed().unwrap());
+        let seen = updates.borrow_and_update().clone();
+        assert_eq!(seen.stage_code.as_deref(), Some("exporting_gltf"));
+        assert_eq!(seen.progress, 60);
+
+        store.update(&job.id, |job| job.fail(vec![]));
+        assert_eq!(updates.borrow_and_update().stage_code, None);
+        assert!(store.subscribe("missing").is_none());
+    }
+}
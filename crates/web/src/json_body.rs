@@ -0,0 +1,174 @@
+//! JSON request bodies for the plate routes, rejected with structured errors.
+//!
+//! axum's `Json` answers a bad body with a plain-text message, which clients of
+//! `/api/validate`, `/api/suggestions`, `/api/analysis`, `/api/generate`, and
+//! `/api/jobs` can't tell apart from any other failure. [`JsonBody`] accepts the
+//! same bodies but rejects them with the `errors` list those routes already return
+//! for invalid plates:
+//!
+//! - 415 `UNSUPPORTED_MEDIA_TYPE` without `Content-Type: application/json`
+//! - 400 `INVALID_JSON` when the body isn't JSON, or nests too deeply
+//! - 422 `INVALID_FIELD` when it's JSON but not the expected shape: a missing field,
+//!   a wrong type, a negative or out-of-range number. `fields` holds the path to the
+//!   offending value, e.g. `["plate_thickness"]` or `["plate.bolt_size"]`.
+//! - 413 `BODY_TOO_LARGE` over the body size cap
+
+use axum::body::Bytes;
+use axum::extract::{FromRequest, Request};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::de::{DeserializeOwned, IgnoredAny};
+
+use crate::{ErrorDetail, GenerateErrorResponse};
+
+/// A JSON request body, like `axum::Json`, whose rejection is a structured error.
+pub struct JsonBody<T>(pub T);
+
+/// Why a [`JsonBody`] couldn't be extracted.
+pub struct JsonBodyRejection {
+    status: StatusCode,
+    detail: ErrorDetail,
+}
+
+impl JsonBodyRejection {
+    fn new(status: StatusCode, code: &str, message: String, fields: Vec<String>) -> Self {
+        Self {
+            status,
+            detail: ErrorDetail {
+                code: code.to_string(),
+                message,
+                fields,
+            },
+        }
+    }
+}
+
+impl IntoResponse for JsonBodyRejection {
+    fn into_response(self) -> Response {
+        let res = GenerateErrorResponse {
+            success: false,
+            errors: vec![self.detail],
+            minimum_thickness_mm: None,
+        };
+        (self.status, Json(res)).into_response()
+    }
+}
+
+impl<T, S> FromRequest<S> for JsonBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = JsonBodyRejection;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !is_json(request.headers()) {
+            return Err(JsonBodyRejection::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "UNSUPPORTED_MEDIA_TYPE",
+                "Expected a request with Content-Type: application/json".to_string(),
+                vec![],
+            ));
+        }
+        let bytes = Bytes::from_request(request, state).await.map_err(|e| {
+            let code = if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                "BODY_TOO_LARGE"
+            } else {
+                "UNREADABLE_BODY"
+            };
+            JsonBodyRejection::new(e.status(), code, e.body_text(), vec![])
+        })?;
+        parse(&bytes).map(JsonBody)
+    }
+}
+
+/// Whether the request says its body is JSON: `application/json` or any
+/// `application/*+json`, with or without parameters.
+fn is_json(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers.get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    let mime = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    mime == "application/json" || (mime.starts_with("application/") && mime.ends_with("+json"))
+}
+
+fn parse<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, JsonBodyRejection> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+        let path = e.path().to_string();
+        let inner = e.into_inner();
+        // serde_json reports some wrong types, such as a number for an enum, as
+        // syntax errors, so only call the body invalid JSON if it really is
+        let well_formed = inner.is_data() || serde_json::from_slice::<IgnoredAny>(bytes).is_ok();
+        if well_formed {
+            let fields = if path == "." { vec![] } else { vec![path] };
+            JsonBodyRejection::new(StatusCode::UNPROCESSABLE_ENTITY, "INVALID_FIELD", inner.to_string(), fields)
+        } else {
+            invalid_json(&inner)
+        }
+    })?;
+    deserializer.end().map_err(|e| invalid_json(&e))?;
+    Ok(value)
+}
+
+fn invalid_json(e: &serde_json::Error) -> JsonBodyRejection {
+    JsonBodyRejection::new(
+        StatusCode::BAD_REQUEST,
+        "INVALID_JSON",
+        format!("Request body is not valid JSON: {}", e),
+        vec![],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::ActuatorPlate;
+
+    fn rejection(body: &str) -> JsonBodyRejection {
+        match parse::<ActuatorPlate>(body.as_bytes()) {
+            Ok(_) => panic!("{} should be rejected", body),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn test_data_errors_name_the_field() {
+        let mut plate = serde_json::to_value(ActuatorPlate::default()).unwrap();
+        plate["plate_thickness"] = serde_json::json!(-4);
+        let e = rejection(&plate.to_string());
+        assert_eq!(e.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(e.detail.code, "INVALID_FIELD");
+        assert_eq!(e.detail.fields, vec!["plate_thickness"]);
+    }
+
+    #[test]
+    fn test_syntax_errors_are_invalid_json() {
+        for body in ["", "{", "{\"bolt_spacing\": }", "nul"] {
+            let e = rejection(body);
+            assert_eq!(e.status, StatusCode::BAD_REQUEST, "{}", body);
+            assert_eq!(e.detail.code, "INVALID_JSON", "{}", body);
+        }
+
+        let plate = serde_json::to_string(&ActuatorPlate::default()).unwrap();
+        let e = rejection(&format!("{} {}", plate, plate));
+        assert_eq!(e.detail.code, "INVALID_JSON");
+    }
+
+    #[test]
+    fn test_json_content_types() {
+        let mut headers = HeaderMap::new();
+        assert!(!is_json(&headers));
+        for (value, json) in [
+            ("application/json", true),
+            ("Application/JSON; charset=utf-8", true),
+            ("application/vnd.api+json", true),
+            ("text/plain", false),
+            ("application/jsonx", false),
+        ] {
+            headers.insert(header::CONTENT_TYPE, value.parse().unwrap());
+            assert_eq!(is_json(&headers), json, "{}", value);
+        }
+    }
+}
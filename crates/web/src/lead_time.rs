@@ -0,0 +1,229 @@
+//! Lead-time estimation for manufacturing orders.
+//!
+//! An estimate adds up, in business days:
+//!
+//! 1. material: days until stock for the plate material is on hand
+//! 2. machining: setup plus per-part cycle time for the quantity, in shop days
+//! 3. finishing: processing days for the requested surface finish
+//! 4. one day for inspection and packing
+//!
+//! and projects a ship date from the order date, skipping weekends.
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use domain::{ActuatorPlate, Count, Material};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Surface finish applied after machining.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Finish {
+    /// Deburred, no coating
+    #[default]
+    AsMachined,
+    /// Bead blasted for a uniform matte surface
+    BeadBlasted,
+    /// Anodized (aluminum only)
+    Anodized,
+    /// Powder coated
+    PowderCoated,
+    /// Zinc plated (carbon steel)
+    ZincPlated,
+}
+
+impl Finish {
+    /// Business days the finishing vendor needs.
+    pub const fn processing_days(self) -> u32 {
+        match self {
+            Finish::AsMachined => 0,
+            Finish::BeadBlasted => 1,
+            Finish::Anodized => 3,
+            Finish::PowderCoated => 4,
+            Finish::ZincPlated => 3,
+        }
+    }
+}
+
+/// Breakdown of an estimated lead time.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub struct LeadTimeEstimate {
+    /// Business days until material is in stock
+    pub material_days: u32,
+    /// Business days of machining for the whole quantity, including setup
+    pub machining_days: u32,
+    /// Business days of finish processing
+    pub finish_days: u32,
+    /// Business days for inspection and packing
+    pub inspection_days: u32,
+    /// Sum of the above
+    pub total_business_days: u32,
+    /// Projected ship date (YYYY-MM-DD)
+    pub ship_date: String,
+}
+
+/// Tables and shop parameters used to estimate lead times.
+#[derive(Clone, Debug)]
+pub struct LeadTimeEstimator {
+    /// Business days until stock is on hand, per material
+    pub stock_days: [(Material, u32); 4],
+    /// Machine setup per order, in minutes
+    pub setup_minutes: u32,
+    /// Spindle minutes available per business day
+    pub shop_minutes_per_day: u32,
+}
+
+impl Default for LeadTimeEstimator {
+    fn default() -> Self {
+        Self {
+            stock_days: [
+                (Material::Aluminum, 1),
+                (Material::CarbonSteel, 2),
+                (Material::StainlessSteel, 3),
+                (Material::Brass, 5),
+            ],
+            setup_minutes: 120,
+            shop_minutes_per_day: 6 * 60,
+        }
+    }
+}
+
+const INSPECTION_DAYS: u32 = 1;
+
+fn material_key(material: Material) -> &'static str {
+    match material {
+        Material::Aluminum => "aluminum",
+        Material::StainlessSteel => "stainless_steel",
+        Material::CarbonSteel => "carbon_steel",
+        Material::Brass => "brass",
+    }
+}
+
+impl LeadTimeEstimator {
+    /// Apply overrides like `brass=10,stainless_steel=4` to the stock table.
+    pub fn with_stock_days(mut self, spec: &str) -> Result<Self, String> {
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (key, days) = entry.split_once('=').ok_or_else(|| entry.to_string())?;
+            let days: u32 = days.trim().parse().map_err(|_| entry.to_string())?;
+            let slot = self
+                .stock_days
+                .iter_mut()
+                .find(|(material, _)| material_key(*material) == key.trim())
+                .ok_or_else(|| key.to_string())?;
+            slot.1 = days;
+        }
+        Ok(self)
+    }
+
+    /// Default tables, with stock overrides from MATERIAL_STOCK_DAYS.
+    pub fn from_env() -> Self {
+        match std::env::var("MATERIAL_STOCK_DAYS") {
+            Ok(spec) => Self::default().with_stock_days(&spec).unwrap_or_else(|entry| {
+                tracing::warn!("Invalid entry {:?} in MATERIAL_STOCK_DAYS, using defaults", entry);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Estimate the lead time for `quantity` plates ordered on `ordered_on`.
+    pub fn estimate(
+        &self,
+        plate: &ActuatorPlate,
+        quantity: Count,
+        finish: Finish,
+        ordered_on: NaiveDate,
+    ) -> LeadTimeEstimate {
+        let material_days = self
+            .stock_days
+            .iter()
+            .find(|(material, _)| *material == plate.material)
+            .map_or(0, |(_, days)| *days);
+
+        let minutes = self.setup_minutes as f32 + pricing::cycle_minutes(plate) * quantity.get() as f32;
+        let machining_days = (minutes / self.shop_minutes_per_day as f32).ceil().max(1.0) as u32;
+        let finish_days = finish.processing_days();
+
+        let total_business_days = material_days + machining_days + finish_days + INSPECTION_DAYS;
+        LeadTimeEstimate {
+            material_days,
+            machining_days,
+            finish_days,
+            inspection_days: INSPECTION_DAYS,
+            total_business_days,
+            ship_date: add_business_days(ordered_on, total_business_days).to_string(),
+        }
+    }
+}
+
+/// The date `days` business days after `start`, skipping Saturdays and Sundays.
+fn add_business_days(start: NaiveDate, days: u32) -> NaiveDate {
+    let mut date = start;
+    let mut remaining = days;
+    while remaining > 0 {
+        date = date.succ_opt().unwrap_or(date);
+        if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            remaining -= 1;
+        }
+    }
+    date
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_business_days_skip_weekends() {
+        // Friday + 1 business day is Monday
+        assert_eq!(add_business_days(date(2026, 1, 2), 1), date(2026, 1, 5));
+        assert_eq!(add_business_days(date(2026, 1, 5), 5), date(2026, 1, 12));
+        assert_eq!(add_business_days(date(2026, 1, 5), 0), date(2026, 1, 5));
+    }
+
+    #[test]
+    fn test_estimate_sums_stages() {
+        let plate = ActuatorPlate::default();
+        let estimate = LeadTimeEstimator::default().estimate(&plate, Count::of(25), Finish::Anodized, date(2026, 1, 5));
+
+        // 120 min setup + 25 × (10 + 2 × 10) min = 870 min over 360 min/day
+        assert_eq!(estimate.material_days, 1);
+        assert_eq!(estimate.machining_days, 3);
+        assert_eq!(estimate.finish_days, 3);
+        assert_eq!(estimate.total_business_days, 8);
+        assert_eq!(estimate.ship_date, "2026-01-15");
+    }
+
+    #[test]
+    fn test_larger_orders_and_harder_materials_take_longer() {
+        let estimator = LeadTimeEstimator::default();
+        let today = date(2026, 1, 5);
+        let aluminum = ActuatorPlate::default();
+        let stainless = ActuatorPlate { material: Material::StainlessSteel, ..aluminum };
+
+        let small = estimator.estimate(&aluminum, Count::of(1), Finish::AsMachined, today);
+        let large = estimator.estimate(&aluminum, Count::of(500), Finish::AsMachined, today);
+        let harder = estimator.estimate(&stainless, Count::of(500), Finish::AsMachined, today);
+
+        assert_eq!(small.machining_days, 1);
+        assert!(large.machining_days > small.machining_days);
+        assert!(harder.machining_days > large.machining_days);
+    }
+
+    #[test]
+    fn test_stock_day_overrides() {
+        let estimator = LeadTimeEstimator::default()
+            .with_stock_days("brass=10, aluminum=0")
+            .unwrap();
+        assert!(estimator.stock_days.contains(&(Material::Brass, 10)));
+        assert!(estimator.stock_days.contains(&(Material::Aluminum, 0)));
+
+        assert_eq!(
+            LeadTimeEstimator::default().with_stock_days("titanium=3").unwrap_err(),
+            "titanium"
+        );
+    }
+}
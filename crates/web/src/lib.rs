@@ -1,30 +1,107 @@
+mod audit;
+mod auth;
+mod backup;
 mod cache;
 mod cache_aws;
 mod cache_local;
+mod cache_metrics;
 mod cache_memory;
-
-pub use cache::{CacheError, CachedFiles, ModelCache};
-pub use cache_aws::AwsCache;
+mod cache_redis;
+mod cache_tiered;
+mod config;
+mod configs;
+mod csrf;
+mod dashboard;
+mod email;
+mod error_reporting;
+mod features;
+mod inflight;
+mod jobs;
+mod json_body;
+mod lead_time;
+mod limits;
+mod metrics;
+mod notify;
+mod order_export;
+mod orders;
+mod quote;
+mod rate_limit;
+mod ready;
+mod recent;
+mod supervisor;
+mod theme;
+mod warm;
+mod webhooks;
+mod zoo_quota;
+
+pub use audit::{AuditError, AuditEvent, AuditKind, AuditStore, MemoryAuditStore, SqliteAuditStore};
+pub use auth::{hash_key, ApiKey, ApiKeyAuth, ApiKeyError, ApiKeyStore, MemoryApiKeyStore, SqliteApiKeyStore};
+pub use backup::{create_backup, restore_backup, BackupError, BackupManifest, RestoreSummary};
+pub use cache::{Artifact, ArtifactStream, CacheError, CachedFiles, ModelCache};
+pub use cache_aws::{AwsCache, S3Options};
 pub use cache_local::LocalCache;
+pub use cache_metrics::{CacheMetrics, CacheStats, LatencyBucket, LatencyHistogram, MeteredCache};
 pub use cache_memory::MemoryCache;
+pub use cache_redis::{RedisCache, RedisCacheOptions};
+pub use cache_tiered::{TieredCache, TieredCacheOptions};
+pub use config::{AppConfig, AppConfigError, AwsConfig, CacheBackend, CacheConfig, CsrfConfig, RateLimitConfig, ZooConfig};
+pub use configs::{ConfigError, ConfigStore, MemoryConfigStore, SqliteConfigStore};
+pub use csrf::CsrfGuard;
+pub use email::{Email, EmailAttachment, LogMailer, MailError, Mailer, ModelEmailOptions, SmtpMailer};
+pub use error_reporting::{
+    install_panic_hook, Attachment, ErrorReport, ErrorReporter, LogReporter, ReportKind,
+    REQUEST_ID_HEADER,
+};
+#[cfg(feature = "sentry")]
+pub use error_reporting::SentryReporter;
+pub use features::{FeatureFlags, Flag};
+pub use inflight::InFlight;
+use inflight::{Claim, Lead};
+pub use jobs::JobStore;
+use jobs::{Job, JobStatus};
+use json_body::JsonBody;
+pub use lead_time::{Finish, LeadTimeEstimate, LeadTimeEstimator};
+pub use limits::validation_limits_from_env;
+pub use metrics::{GenerationOutcome, Metrics};
+pub use notify::{Notification, Notifier, NotifyError, WebhookFlavor, WebhookNotifier};
+pub use order_export::{ColumnMapping, ExportColumn};
+pub use orders::{MemoryOrderStore, Order, OrderError, OrderStatus, OrderStore, SqliteOrderStore};
+pub use quote::{ExternalQuote, HttpQuoteProvider, QuoteError, QuoteProvider, QuoteRequest};
+pub use rate_limit::{ClientAddr, RateLimiter, DEFAULT_MAX_BODY_BYTES};
+pub use ready::{DependencyStatus, ReadinessResponse};
+pub use recent::{BoundedMemoryStore, RecentPlate};
+pub use supervisor::{RestartPolicy, Supervisor, WorkerHealth, WorkerState};
+pub use webhooks::{
+    RetryPolicy, WebhookDelivery, WebhookDispatcher, WebhookEndpoint, WebhookEvent, WebhookRegistry,
+    SIGNATURE_HEADER,
+};
+pub use zoo_quota::{QuotaExhausted, QuotaPeriod, QuotaStatus, QuotaWindow, ZooQuota};
 
 use axum::{
-    extract::{Json, Path, State},
-    http::{header, StatusCode},
+    body::Body,
+    extract::{ws::WebSocketUpgrade, DefaultBodyLimit, Json, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware,
     response::IntoResponse,
     routing::{get, post},
     Router,
 };
-use domain::ActuatorPlate;
-use parametric::{generate_model, GenerationResult};
-use validation::PlateValidationError;
-use serde::Serialize;
+use domain::{ActuatorPlate, CenterOfMass, Count, FieldChange, MassProperties, PlateDiff, SavedConfig};
+use futures_util::TryStreamExt;
+use parametric::sweep::{Sweep, SweepField, SweepRange};
+use parametric::{generate_model_with_limits, GenerationResult, GeometryBackend, ZooCli};
+use pricing::{PriceEstimate, PriceTable};
+use validation::{Locale, PlateValidationError, PlateValidationWarning, ValidationLimits};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tower_http::services::{ServeDir, ServeFile};
+use tower_http::services::ServeDir;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
 use tower_http::trace::TraceLayer;
+use tower_sessions::Session;
+use tracing::Instrument;
 use utoipa::OpenApi;
 use utoipa::ToSchema;
 use utoipa_swagger_ui::SwaggerUi;
@@ -35,33 +112,145 @@ use uuid::Uuid;
 #[openapi(
     paths(
         health,
+        ready,
         version,
+        list_flags,
+        cache_stats,
+        quota,
+        prometheus_metrics,
         validate_plate,
+        validation_rules,
+        suggest_values,
+        analyze_plate,
         generate_plate_model,
+        create_job,
+        get_job,
+        create_batch,
+        create_sweep,
         download_step,
         download_gltf,
         download_stl,
+        download_obj,
+        download_three_mf,
+        download_svg,
+        download_pdf,
+        download_dxf,
+        download_kcl,
+        download_model,
+        model_gltf,
+        model_kcl,
+        email_model,
+        list_presets,
+        create_config,
+        list_configs,
+        get_config,
+        list_recent_plates,
+        compare_plates,
+        request_quote,
+        create_order,
+        export_orders,
+        update_order_status,
+        list_webhooks,
+        create_webhook,
+        delete_webhook,
+        webhook_deliveries,
+        list_client_webhooks,
+        create_client_webhook,
+        delete_client_webhook,
+        client_webhook_deliveries,
+        list_api_keys,
+        create_api_key,
+        revoke_api_key,
+        backup,
+        restore,
+        list_workers,
+        clear_cache,
+        delete_cache_entry,
     ),
     components(
         schemas(
             ActuatorPlate,
-            domain::Millimeters,
+            SavedConfig,
+            RecentPlate,
+            domain::presets::Preset,
+            validation::FieldRule,
+            validation::FieldKind,
+            domain::suggestions::Suggestion,
+            SaveConfigRequest,
+            ComparePlatesRequest,
+            PlateDiff,
+            FieldChange,
+            domain::Length,
             domain::Newtons,
             domain::BoltSize,
             OkResponse,
+            ReadinessResponse,
+            DependencyStatus,
             VersionResponse,
+            FlagsResponse,
+            CacheStats,
+            LatencyHistogram,
+            LatencyBucket,
+            StatsFormat,
+            QuotaStatus,
+            QuotaWindow,
             ValidationSuccessResponse,
             StressSummary,
+            LoadCheckSummary,
             ValidationErrorResponse,
+            AnalysisRequest,
+            AnalysisResponse,
+            StressCheck,
             GenerateSuccessResponse,
             GenerateErrorResponse,
             ErrorDetail,
+            Job,
+            JobStatus,
+            BatchItem,
+            BatchResponse,
+            Sweep,
+            SweepRange,
+            SweepField,
+            SweepItem,
+            SweepResponse,
+            QuoteRequestBody,
+            QuoteResponse,
+            ExternalQuote,
+            Finish,
+            LeadTimeEstimate,
+            PriceEstimate,
+            MassProperties,
+            CenterOfMass,
+            EmailModelRequest,
+            EmailModelResponse,
+            PlaceOrderRequest,
+            Order,
+            OrderStatus,
+            OrderStatusRequest,
+            ExportFormat,
+            ModelFormat,
+            WebhookEndpoint,
+            WebhookEvent,
+            WebhookDelivery,
+            CreateWebhookRequest,
+            ApiKey,
+            CreateApiKeyRequest,
+            RestoreSummary,
+            RestoreResponse,
+            WorkerHealth,
+            WorkerState,
+            ClearCacheResponse,
         )
     ),
     tags(
         (name = "health", description = "Health check and version endpoints"),
         (name = "validation", description = "Plate parameter validation endpoints"),
         (name = "generation", description = "Model generation and download endpoints"),
+        (name = "quote", description = "Manufacturing quote endpoints"),
+        (name = "configs", description = "Saved plate configurations, standard presets, and this browser's recent plates"),
+        (name = "orders", description = "Order submission"),
+        (name = "webhooks", description = "Webhooks for an API key's own generation jobs"),
+        (name = "admin", description = "Operator endpoints (require the admin token)"),
     ),
     info(
         title = "Platerator API",
@@ -126,90 +315,474 @@ pub struct ApiDoc;
 /// Session data can be either generated files (on disk) or cached files (in memory).
 pub enum SessionData {
     /// Files generated by the parametric model, stored on disk.
-    Generated(GenerationResult),
+    Generated(Box<GenerationResult>),
     /// Files retrieved from cache, stored in memory.
     Cached(CachedFiles),
 }
 
-/// Inner state containing sessions and cache.
+/// Inner state containing sessions, cache, and external integrations.
 pub struct AppStateInner {
     pub sessions: RwLock<HashMap<String, SessionData>>,
     pub cache: Arc<dyn ModelCache>,
+    /// Hit, miss, and latency counters for `cache`.
+    pub cache_metrics: Arc<CacheMetrics>,
+    /// Request, generation, and engine metrics served at `/metrics`.
+    pub metrics: Arc<Metrics>,
+    /// Bearer token required by `/metrics`; open when unset.
+    pub metrics_token: Option<String>,
+    /// Third-party manufacturing services asked for quotes alongside ours.
+    pub quote_providers: Vec<Arc<dyn QuoteProvider>>,
+    /// Chat channels told about orders, failed generations, and quota exhaustion.
+    pub notifiers: Vec<Arc<dyn Notifier>>,
+    pub orders: Arc<dyn OrderStore>,
+    /// Sends order confirmations and emailed models to customers.
+    pub mailer: Arc<dyn Mailer>,
+    /// When emailed models are attached and where larger ones are linked.
+    pub model_email: ModelEmailOptions,
+    /// Plate configurations users saved by name.
+    pub configs: Arc<dyn ConfigStore>,
+    /// Generations, engine failures, and orders, shown on the `/admin` dashboard.
+    pub audit: Arc<dyn AuditStore>,
+    /// Column names used when exporting orders for the ERP.
+    pub order_export_columns: ColumnMapping,
+    /// Material stock and shop capacity used for quoted lead times.
+    pub lead_times: LeadTimeEstimator,
+    /// Material prices and shop rates used for our own price estimate.
+    pub prices: PriceTable,
+    /// Bearer token required by `/api/admin/*`; admin endpoints are disabled when unset.
+    pub admin_token: Option<String>,
+    /// Registered outgoing webhooks and their delivery policy.
+    pub webhooks: Arc<WebhookDispatcher>,
+    /// Where panics, engine failures, and 5xx responses are reported.
+    pub error_reporter: Arc<dyn ErrorReporter>,
+    /// Geometry backend used for generation: the zoo CLI, or a mock in tests.
+    pub engine: Arc<dyn GeometryBackend>,
+    /// Owns background workers and tasks so they are restarted and drained on shutdown.
+    pub supervisor: Arc<Supervisor>,
+    /// Background generation jobs started through `/api/jobs`.
+    pub jobs: JobStore,
+    /// Generations running now, which concurrent requests for the same plate share.
+    pub in_flight: InFlight,
+    /// Engine calls made today and this month, and the caps that stop generation.
+    pub zoo_quota: Arc<ZooQuota>,
+    /// Largest plates this deployment accepts.
+    pub limits: ValidationLimits,
+    /// Experimental behaviors switched on or off for this deployment.
+    pub flags: Arc<FeatureFlags>,
+    /// Per-client limit on the routes that start generation; off by default.
+    pub rate_limiter: Arc<RateLimiter>,
+    /// Largest request body accepted, except by `/api/admin/restore`.
+    pub max_body_bytes: usize,
+    /// Which other origins may send state-changing requests.
+    pub csrf: Arc<CsrfGuard>,
+    /// API keys issued to programmatic clients, stored hashed.
+    pub api_keys: Arc<dyn ApiKeyStore>,
+    /// Reject generation and order requests without a valid API key.
+    pub require_api_key: bool,
+    /// Remembered dependency checks for `/api/ready`.
+    pub readiness: ready::ReadinessProbe,
+    /// Settings the server started with; the defaults unless built with
+    /// [`AppStateInner::from_config`].
+    pub config: AppConfig,
 }
 
-/// Shared application state for storing generation results and cache.
-pub type AppState = Arc<AppStateInner>;
-
-pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    tracing_subscriber::fmt::init();
+impl AppStateInner {
+    /// Create state with the given cache, no sessions, and no integrations configured.
+    pub fn new(cache: Arc<dyn ModelCache>) -> Self {
+        Self::with_cache_metrics(cache, Arc::new(CacheMetrics::new()))
+    }
 
-    // Initialize cache based on CACHE_BACKEND environment variable
-    // Auto-detect AWS if S3_BUCKET_NAME is set (for Lightsail deployment)
-    let cache_backend = std::env::var("CACHE_BACKEND").unwrap_or_else(|_| {
-        if std::env::var("S3_BUCKET_NAME").is_ok() {
-            "aws".to_string()
-        } else {
-            "local".to_string()
+    /// Like [`AppStateInner::new`], recording cache use in `cache_metrics`, which the
+    /// cache may also share, e.g. for a [`TieredCache`]'s memory hits.
+    pub fn with_cache_metrics(cache: Arc<dyn ModelCache>, cache_metrics: Arc<CacheMetrics>) -> Self {
+        Self {
+            sessions: RwLock::new(HashMap::new()),
+            cache: Arc::new(MeteredCache::new(cache, cache_metrics.clone())),
+            cache_metrics,
+            metrics: Arc::new(Metrics::new()),
+            metrics_token: None,
+            quote_providers: Vec::new(),
+            notifiers: Vec::new(),
+            orders: Arc::new(MemoryOrderStore::new()),
+            mailer: Arc::new(LogMailer),
+            model_email: ModelEmailOptions::default(),
+            configs: Arc::new(MemoryConfigStore::new()),
+            audit: Arc::new(MemoryAuditStore::new()),
+            order_export_columns: ColumnMapping::default(),
+            lead_times: LeadTimeEstimator::default(),
+            prices: PriceTable::default(),
+            admin_token: None,
+            webhooks: Arc::new(WebhookDispatcher::default()),
+            error_reporter: Arc::new(LogReporter),
+            engine: Arc::new(ZooCli::from_env()),
+            supervisor: Arc::new(Supervisor::default()),
+            jobs: JobStore::default(),
+            in_flight: InFlight::default(),
+            zoo_quota: Arc::new(ZooQuota::default()),
+            limits: ValidationLimits::DEFAULT,
+            flags: Arc::new(FeatureFlags::default()),
+            rate_limiter: Arc::new(RateLimiter::disabled()),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            csrf: Arc::new(CsrfGuard::default()),
+            api_keys: Arc::new(MemoryApiKeyStore::new()),
+            require_api_key: false,
+            readiness: ready::ReadinessProbe::default(),
+            config: AppConfig::default(),
         }
-    });
+    }
 
-    let cache: Arc<dyn ModelCache> = match cache_backend.as_str() {
-        "aws" => {
-            tracing::info!("Using AWS S3/DynamoDB cache backend");
-            Arc::new(AwsCache::from_env().await?)
+    /// State for a server started with `config`: the zoo CLI, its quota (counted in
+    /// memory), rate limiter, and body cap are built from it, and `flags` gate the
+    /// engine's STEP fallback.
+    pub fn from_config(
+        config: AppConfig,
+        cache: Arc<dyn ModelCache>,
+        cache_metrics: Arc<CacheMetrics>,
+        flags: Arc<FeatureFlags>,
+    ) -> Self {
+        let mut engine = ZooCli::from_env();
+        engine.program = config.zoo.cli.clone();
+        if let Some(dir) = &config.zoo.kcl_source_dir {
+            engine.kcl_source_dir = dir.clone();
         }
-        "memory" => {
-            tracing::info!("Using in-memory cache backend");
-            Arc::new(MemoryCache::new())
+        engine.api_token = config.zoo.api_token.clone();
+        engine.gltf_compression = config.zoo.gltf_compression;
+        engine.step_fallback &= flags.is_enabled(Flag::StepFallback);
+        if config.rate_limit.per_minute == 0 {
+            tracing::info!("Rate limiting is off");
         }
-        _ => {
-            tracing::info!("Using local filesystem cache backend");
-            Arc::new(LocalCache::default_dir())
+        Self {
+            engine: Arc::new(engine),
+            zoo_quota: Arc::new(ZooQuota::new(config.zoo.daily_quota, config.zoo.monthly_quota)),
+            flags,
+            rate_limiter: Arc::new(RateLimiter::new(config.rate_limit.per_minute, config.rate_limit.trust_proxy)),
+            max_body_bytes: config.rate_limit.max_body_bytes,
+            csrf: Arc::new(CsrfGuard::new(config.csrf.trusted_origins.clone())),
+            config,
+            ..Self::with_cache_metrics(cache, cache_metrics)
         }
+    }
+}
+
+/// Shared application state for storing generation results and cache.
+pub type AppState = Arc<AppStateInner>;
+
+pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    init_tracing();
+
+    let mut config = AppConfig::load()?;
+    let flags = Arc::new(FeatureFlags::from_env());
+
+    if config.cache.backend == CacheBackend::Redis && !flags.is_enabled(Flag::RedisCache) {
+        tracing::warn!("The cache backend is redis but the redis_cache flag is off; using the local cache");
+        config.cache.backend = CacheBackend::Local;
+    }
+    // Redis applies its own REDIS_CACHE_TTL_SECS; the other backends use the configured TTL.
+    let cache_ttl = config.cache.ttl;
+    let backend = cache::from_config(&config).await?;
+    let cache_metrics = Arc::new(CacheMetrics::new());
+    // Keep recently used entries in memory to skip the backend's round trip on hits
+    let memory_tier = TieredCacheOptions {
+        ttl: cache_ttl,
+        ..TieredCacheOptions::from_env()?
+    };
+    let cache: Arc<dyn ModelCache> = if config.cache.backend != CacheBackend::Memory && memory_tier.is_enabled() {
+        tracing::info!(
+            "Keeping up to {} cache entries ({} bytes) in memory",
+            memory_tier.max_entries,
+            memory_tier.max_bytes
+        );
+        Arc::new(TieredCache::new(backend, memory_tier).with_metrics(cache_metrics.clone()))
+    } else {
+        backend
     };
 
-    let state: AppState = Arc::new(AppStateInner {
-        sessions: RwLock::new(HashMap::new()),
-        cache,
-    });
-    let app = create_router(state);
+    let mut inner = AppStateInner::from_config(config, cache, cache_metrics, flags);
+    inner.error_reporter = error_reporting::reporter_from_env();
+    if let Some(file) = &inner.config.zoo.quota_file {
+        tracing::info!("Keeping Zoo API call counts in {}", file.display());
+        inner.zoo_quota = Arc::new(ZooQuota::open(inner.config.zoo.daily_quota, inner.config.zoo.monthly_quota, file)?);
+    }
+    install_panic_hook(inner.error_reporter.clone());
+    if let Some(provider) = HttpQuoteProvider::from_env() {
+        tracing::info!("Requesting third-party quotes from {}", provider.name());
+        inner.quote_providers.push(Arc::new(provider));
+    }
+    inner.notifiers = notify::notifiers_from_env();
+    inner.order_export_columns = ColumnMapping::from_env();
+    inner.lead_times = LeadTimeEstimator::from_env();
+    inner.admin_token = std::env::var("ADMIN_TOKEN").ok().filter(|t| !t.is_empty());
+    inner.metrics_token = std::env::var("METRICS_TOKEN").ok().filter(|t| !t.is_empty());
+    inner.jobs = JobStore::from_env();
+    inner.limits = validation_limits_from_env();
+    if let Ok(url) = std::env::var("CONFIG_DATABASE_URL") {
+        tracing::info!("Storing saved configurations in {}", url);
+        inner.configs = Arc::new(SqliteConfigStore::connect(&url).await?);
+    }
+    if let Ok(url) = std::env::var("ORDER_DATABASE_URL") {
+        tracing::info!("Storing orders in {}", url);
+        inner.orders = Arc::new(SqliteOrderStore::connect(&url).await?);
+    }
+    if let Ok(url) = std::env::var("AUDIT_DATABASE_URL") {
+        tracing::info!("Storing the audit log in {}", url);
+        inner.audit = Arc::new(SqliteAuditStore::connect(&url).await?);
+    }
+    if let Ok(url) = std::env::var("API_KEY_DATABASE_URL") {
+        tracing::info!("Storing API keys in {}", url);
+        inner.api_keys = Arc::new(SqliteApiKeyStore::connect(&url).await?);
+    }
+    if let Some(path) = std::env::var_os("API_KEYS_FILE") {
+        let added = auth::load_key_file(inner.api_keys.as_ref(), std::path::Path::new(&path)).await?;
+        tracing::info!("Loaded {} API keys from {}", added, std::path::Path::new(&path).display());
+    }
+    inner.require_api_key = std::env::var("REQUIRE_API_KEY").is_ok_and(|v| v == "true" || v == "1");
+    inner.mailer = email::mailer_from_env();
+    inner.model_email = ModelEmailOptions::from_env();
+    if let Some(ttl) = cache_ttl {
+        spawn_cache_gc(&inner, ttl);
+    }
+    if let Some(retention) = audit::retention_from_env()? {
+        tracing::info!("Keeping audit events for {} days", retention.as_secs() / (24 * 60 * 60));
+        spawn_audit_retention(&inner, retention);
+    }
+    spawn_flag_reload(&inner);
 
-    // Read port from environment variable (for App Runner compatibility)
-    let port: u16 = std::env::var("PORT")
-        .unwrap_or_else(|_| "3030".to_string())
-        .parse()
-        .unwrap_or(3030);
+    let state: AppState = Arc::new(inner);
+    if state.config.cache.warm_presets {
+        warm::spawn(&state, state.config.cache.warm_interval);
+    }
+    let app = create_router(state.clone());
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    let addr = SocketAddr::from(([0, 0, 0, 0], state.config.port));
     let listener = tokio::net::TcpListener::bind(addr).await?;
 
     tracing::info!("listening on {}", listener.local_addr()?);
 
-    axum::serve(listener, app).await?;
+    // The rate limiter needs the client's address
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    tracing::info!("Server stopped; waiting for background tasks");
+    state.supervisor.shutdown(SHUTDOWN_GRACE).await;
 
     Ok(())
 }
 
+/// Log to stdout, filtered by RUST_LOG. LOG_FORMAT=json writes one JSON object per
+/// line, with the request span's fields (request ID, route, cache key) on every
+/// event, for CloudWatch Logs Insights.
+fn init_tracing() {
+    let filter = tracing_subscriber::EnvFilter::from_default_env();
+    if std::env::var("LOG_FORMAT").is_ok_and(|format| format == "json") {
+        tracing_subscriber::fmt()
+            .json()
+            .with_env_filter(filter)
+            .with_current_span(true)
+            .with_span_list(false)
+            .init();
+    } else {
+        tracing_subscriber::fmt().with_env_filter(filter).init();
+    }
+}
+
+/// Longest time between sweeps for expired cache entries.
+const CACHE_GC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Periodically remove expired cache entries. Backends whose store expires entries
+/// itself (AWS, Redis) have nothing to remove, so the sweep is cheap for them.
+fn spawn_cache_gc(inner: &AppStateInner, ttl: std::time::Duration) {
+    let cache = inner.cache.clone();
+    let period = CACHE_GC_INTERVAL.min(ttl);
+    inner.supervisor.spawn_worker("cache-gc", move |shutdown| {
+        let cache = cache.clone();
+        async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = interval.tick() => {}
+                }
+                match cache.evict_expired().await {
+                    Ok(0) => {}
+                    Ok(removed) => tracing::info!("Evicted {} expired cache entries", removed),
+                    Err(e) => tracing::warn!("Cache eviction failed: {}", e),
+                }
+            }
+        }
+    });
+}
+
+/// How often audit events past their retention are removed.
+const AUDIT_RETENTION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+/// Daily, remove audit events older than `retention`.
+fn spawn_audit_retention(inner: &AppStateInner, retention: std::time::Duration) {
+    let store = inner.audit.clone();
+    let retention = chrono::Duration::from_std(retention).unwrap_or(chrono::Duration::MAX);
+    inner.supervisor.spawn_worker("audit-retention", move |shutdown| {
+        let store = store.clone();
+        async move {
+            let mut interval = tokio::time::interval(AUDIT_RETENTION_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = interval.tick() => {}
+                }
+                // A retention reaching back before year 1 has nothing to remove
+                let Some(cutoff) = chrono::Utc::now().checked_sub_signed(retention) else {
+                    continue;
+                };
+                let cutoff = audit::timestamp(cutoff);
+                match store.prune(&cutoff).await {
+                    Ok(0) => {}
+                    Ok(removed) => tracing::info!("Removed {} audit events from before {}", removed, cutoff),
+                    Err(e) => tracing::warn!("Audit retention failed: {}", e),
+                }
+            }
+        }
+    });
+}
+
+/// Re-read the feature flags file, if there is one, so flags checked per request
+/// follow it without a restart.
+fn spawn_flag_reload(inner: &AppStateInner) {
+    if inner.flags.file().is_none() {
+        return;
+    }
+    let flags = inner.flags.clone();
+    inner.supervisor.spawn_worker("feature-flags", move |shutdown| {
+        let flags = flags.clone();
+        async move {
+            let mut interval = tokio::time::interval(features::RELOAD_INTERVAL);
+            // The flags were read at startup
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = interval.tick() => flags.reload(),
+                }
+            }
+        }
+    });
+}
+
+/// How long shutdown waits for cache writes and deliveries still in flight.
+const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(20);
+
+/// Resolves on Ctrl-C or SIGTERM (sent by the container runtime before it kills us).
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.ok();
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    tracing::info!("Shutdown signal received");
+}
+
 pub fn create_router(state: AppState) -> Router {
-    // Serve static files from dist/, fallback to index.html for SPA routing
-    let serve_dir = ServeDir::new("dist").fallback(ServeFile::new("dist/index.html"));
+    // Serve static files from dist/, fallback to index.html for SPA routing. The
+    // page itself is served in the browser's theme
+    let serve_dir = ServeDir::new("dist").fallback(get(theme::index));
+
+    let reporter = state.error_reporter.clone();
+    let metrics = state.metrics.clone();
+    let max_body_bytes = state.max_body_bytes;
+    let csrf = state.csrf.clone();
+
+    // Routes that can spend Zoo credits or send mail are rate limited per client
+    let generation_routes = Router::new()
+        .route("/api/generate", post(generate_plate_model))
+        .route("/api/jobs", post(create_job))
+        .route("/api/plates/batch", post(create_batch))
+        .route("/api/sweep", post(create_sweep))
+        .route("/api/models/{cache_key}/email", post(email_model))
+        .route("/api/orders", post(create_order))
+        .route_layer(middleware::from_fn_with_state(state.rate_limiter.clone(), rate_limit::limit));
 
     // Create API routes
     let api_routes = Router::new()
         .route("/api/health", get(health))
+        .route("/api/ready", get(ready))
         .route("/api/version", get(version))
+        .route("/api/flags", get(list_flags))
+        .route("/api/cache/stats", get(cache_stats))
+        .route("/api/quota", get(quota))
+        .route("/metrics", get(prometheus_metrics))
         .route("/api/validate", post(validate_plate))
-        .route("/api/generate", post(generate_plate_model))
+        .route("/api/validation-rules", get(validation_rules))
+        .route("/api/suggestions", post(suggest_values))
+        .route("/api/analysis", post(analyze_plate))
+        .merge(generation_routes)
+        .route("/api/jobs/{job_id}", get(get_job))
+        .route("/ws/generation/{job_id}", get(generation_progress))
         .route("/api/download/step/{session_id}", get(download_step))
         .route("/api/download/gltf/{session_id}", get(download_gltf))
         .route("/api/download/stl/{session_id}", get(download_stl))
+        .route("/api/download/obj/{session_id}", get(download_obj))
+        .route("/api/download/3mf/{session_id}", get(download_three_mf))
+        .route("/api/download/svg/{session_id}", get(download_svg))
+        .route("/api/download/pdf/{session_id}", get(download_pdf))
+        .route("/api/download/dxf/{session_id}", get(download_dxf))
+        .route("/api/download/kcl/{session_id}", get(download_kcl))
+        .route("/api/models/{cache_key}/download", get(download_model))
+        .route("/api/models/{cache_key}/gltf", get(model_gltf))
+        .route("/api/models/{cache_key}/kcl", get(model_kcl))
+        .route("/api/quote", post(request_quote))
+        .route("/api/presets", get(list_presets))
+        .route("/api/configs", get(list_configs).post(create_config))
+        .route("/api/configs/{id}", get(get_config))
+        .route("/api/session/recent", get(list_recent_plates))
+        .route("/api/compare", post(compare_plates))
+        .route("/api/admin/orders/export", get(export_orders))
+        .route("/api/admin/orders/{order_number}/status", post(update_order_status))
+        .route("/api/admin/webhooks", get(list_webhooks).post(create_webhook))
+        .route("/api/admin/webhooks/{id}", axum::routing::delete(delete_webhook))
+        .route("/api/admin/webhooks/{id}/deliveries", get(webhook_deliveries))
+        .route("/api/webhooks", get(list_client_webhooks).post(create_client_webhook))
+        .route("/api/webhooks/{id}", axum::routing::delete(delete_client_webhook))
+        .route("/api/webhooks/{id}/deliveries", get(client_webhook_deliveries))
+        .route("/api/admin/api-keys", get(list_api_keys).post(create_api_key))
+        .route("/api/admin/api-keys/{id}", axum::routing::delete(revoke_api_key))
+        .route("/api/admin/workers", get(list_workers))
+        .route("/api/admin/cache", axum::routing::delete(clear_cache))
+        .route("/api/admin/cache/{cache_key}", axum::routing::delete(delete_cache_entry))
+        .route("/api/admin/backup", get(backup))
+        .route("/admin", get(admin_dashboard))
+        // Streams its body, an entry at a time, so the body limit doesn't apply
+        .route("/api/admin/restore", post(restore))
+        .layer(DefaultBodyLimit::max(max_body_bytes))
+        .layer(recent::layer())
+        // Browsers on other sites can't submit anything that changes state
+        .layer(middleware::from_fn_with_state(csrf, csrf::protect))
         .with_state(state);
 
     // Merge with Swagger UI
     api_routes
         .merge(SwaggerUi::new("/api/docs").url("/api/openapi.json", ApiDoc::openapi()))
+        .route("/", get(theme::index))
+        .route("/index.html", get(theme::index))
         .fallback_service(serve_dir)
-        .layer(TraceLayer::new_for_http())
+        .layer(middleware::from_fn_with_state(metrics, metrics::track))
+        .layer(middleware::from_fn_with_state(reporter, error_reporting::report_server_errors))
+        .layer(middleware::from_fn(error_reporting::tag_error_responses))
+        .layer(TraceLayer::new_for_http().make_span_with(error_reporting::request_span))
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
 }
 
 /// Health check endpoint
@@ -228,6 +801,27 @@ async fn health() -> impl IntoResponse {
     (StatusCode::OK, Json(res)).into_response()
 }
 
+/// Readiness check
+///
+/// Checks every dependency this instance needs to serve requests: the cache
+/// backend, the geometry engine's credentials, and the configuration, order, and
+/// API key stores. Returns 503 if any check fails, so load balancers stop routing
+/// here. The engine check is repeated at most once a minute.
+#[utoipa::path(
+    get,
+    path = "/api/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Every dependency is reachable", body = ReadinessResponse),
+        (status = 503, description = "At least one dependency failed its check", body = ReadinessResponse)
+    )
+)]
+async fn ready(State(state): State<AppState>) -> impl IntoResponse {
+    let res = ready::check(&state).await;
+    let status = if res.ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(res)).into_response()
+}
+
 /// Version endpoint
 ///
 /// Returns the git commit hash of the running build.
@@ -244,64 +838,343 @@ async fn version() -> impl IntoResponse {
     (StatusCode::OK, Json(res)).into_response()
 }
 
+/// Feature flags
+///
+/// Lists every experimental behavior and whether it is on for this deployment, so
+/// the frontend can follow the server: e.g. when `async_jobs` is off, generate with
+/// `/api/generate` instead of `/api/jobs`.
+#[utoipa::path(
+    get,
+    path = "/api/flags",
+    tag = "health",
+    responses(
+        (status = 200, description = "Current feature flags", body = FlagsResponse)
+    )
+)]
+async fn list_flags(State(state): State<AppState>) -> impl IntoResponse {
+    let res = FlagsResponse { flags: state.flags.snapshot() };
+    (StatusCode::OK, Json(res)).into_response()
+}
+
+/// Cache statistics
+///
+/// Hits, misses, writes, and lookup and write latency since the server started, for
+/// sizing the cache. `format=prometheus` returns the same counters in the
+/// Prometheus text format.
+#[utoipa::path(
+    get,
+    path = "/api/cache/stats",
+    tag = "health",
+    params(StatsQuery),
+    responses(
+        (status = 200, description = "Cache counters", body = CacheStats)
+    )
+)]
+async fn cache_stats(State(state): State<AppState>, Query(query): Query<StatsQuery>) -> impl IntoResponse {
+    match query.format {
+        StatsFormat::Json => (StatusCode::OK, Json(state.cache_metrics.snapshot())).into_response(),
+        StatsFormat::Prometheus => {
+            let mut text = String::new();
+            state.cache_metrics.render_prometheus(&mut text);
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+                text,
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Zoo API quota
+///
+/// Calls to the geometry engine made today and this month (UTC), the deployment's
+/// caps, and how many calls are left. While `exhausted` is true, generation fails
+/// with `QUOTA_EXHAUSTED` and nothing new is cached; cached plates are still served.
+#[utoipa::path(
+    get,
+    path = "/api/quota",
+    tag = "generation",
+    responses(
+        (status = 200, description = "Engine calls used and left", body = QuotaStatus)
+    )
+)]
+async fn quota(State(state): State<AppState>) -> impl IntoResponse {
+    (StatusCode::OK, Json(state.zoo_quota.status())).into_response()
+}
+
+/// Prometheus metrics
+///
+/// Request counts and latency per route, generation time, engine failures, and cache
+/// counters in the Prometheus text format. Requires `Authorization: Bearer <token>`
+/// when the deployment sets METRICS_TOKEN.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "health",
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text format", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid metrics token", body = ErrorResponse)
+    )
+)]
+async fn prometheus_metrics(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Some(expected) = state.metrics_token.as_deref() {
+        let presented = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        if presented != Some(expected) {
+            let res = ErrorResponse {
+                success: false,
+                got_it: false,
+                errors: vec!["Metrics token required".to_string()],
+            };
+            return (StatusCode::UNAUTHORIZED, Json(res)).into_response();
+        }
+    }
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(&state.cache_metrics),
+    )
+        .into_response()
+}
+
+/// Validation rules
+///
+/// The range, step, and unit of each numeric plate field and the values each choice
+/// allows, with this deployment's limits, so forms can constrain their inputs the
+/// way `/api/validate` will. Checks across fields, such as stress, aren't described;
+/// a plate within every rule can still be invalid.
+#[utoipa::path(
+    get,
+    path = "/api/validation-rules",
+    tag = "validation",
+    responses(
+        (status = 200, description = "Constraints on each plate field, in form order", body = [validation::FieldRule])
+    )
+)]
+async fn validation_rules(State(state): State<AppState>) -> impl IntoResponse {
+    (StatusCode::OK, Json(validation::rules_with_limits(&state.limits)))
+}
+
+/// Suggested values
+///
+/// Recommended values for fields that follow from the rest of the plate, such as a
+/// bracket height from the bolt spacing or a thickness from the load per pin, each
+/// with the reason for it. Fields already at their suggested value are left out.
+/// The plate isn't validated, so a form can ask while it is still being filled in.
+#[utoipa::path(
+    post,
+    path = "/api/suggestions",
+    tag = "validation",
+    request_body = ActuatorPlate,
+    responses(
+        (status = 200, description = "Suggestions, in form order", body = [domain::suggestions::Suggestion]),
+        (status = 422, description = "Body isn't a plate: a missing field, wrong type, or out-of-range number", body = GenerateErrorResponse)
+    )
+)]
+async fn suggest_values(JsonBody(plate): JsonBody<ActuatorPlate>) -> impl IntoResponse {
+    (StatusCode::OK, Json(domain::suggestions::suggest(&plate)))
+}
+
 /// Validate actuator plate parameters
 ///
 /// Validates the actuator plate configuration without generating model files.
 /// Useful for client-side validation before submitting a generation request.
+///
+/// Pass `load_n` (and optionally `safety_factor`) to also check the plate against the
+/// load it will actually carry. A plate that fails that check is still valid, but the
+/// response carries a warning and the thickness that would pass.
 #[utoipa::path(
     post,
     path = "/api/validate",
     tag = "validation",
+    params(LoadQuery),
     request_body = ActuatorPlate,
     responses(
         (status = 200, description = "Plate parameters are valid", body = ValidationSuccessResponse),
-        (status = 400, description = "Plate parameters are invalid", body = ValidationErrorResponse)
+        (status = 400, description = "Plate parameters are invalid", body = ValidationErrorResponse),
+        (status = 422, description = "Body isn't a plate: a missing field, wrong type, or out-of-range number", body = GenerateErrorResponse)
     )
 )]
-async fn validate_plate(Json(payload): Json<ActuatorPlate>) -> impl IntoResponse {
-    match validation::validate(&payload) {
-        Ok(()) => {
-            let utilization = validation::stress_utilization(&payload);
-            let min_thickness = validation::minimum_thickness_mm(&payload);
-
-            let res = ValidationSuccessResponse {
-                valid: true,
-                message: "Actuator plate parameters are valid".to_string(),
-                stress_summary: StressSummary {
-                    safety_factor: 2.0,
-                    pin_bearing_utilization: utilization.pin_bearing,
-                    bolt_bearing_utilization: utilization.bolt_bearing,
-                    bending_utilization: utilization.bending,
-                    minimum_thickness_mm: min_thickness,
-                },
-            };
-            (StatusCode::OK, Json(res)).into_response()
-        }
-        Err(errors) => {
-            let has_stress_error = errors.iter().any(|e| {
-                matches!(
-                    e,
-                    PlateValidationError::PinBearingStressExceeded { .. }
-                        | PlateValidationError::BoltBearingStressExceeded { .. }
-                        | PlateValidationError::PlateBendingStressExceeded
-                )
-            });
-            let minimum_thickness_mm =
-                if has_stress_error { Some(validation::minimum_thickness_mm(&payload)) } else { None };
-            let res = ValidationErrorResponse {
-                valid: false,
-                errors: errors
-                    .iter()
-                    .map(|e| ErrorDetail {
-                        message: e.to_string(),
-                        fields: e.related_fields().iter().map(|s| s.to_string()).collect(),
-                    })
-                    .collect(),
-                minimum_thickness_mm,
-            };
-            (StatusCode::BAD_REQUEST, Json(res)).into_response()
-        }
+async fn validate_plate(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(load): Query<LoadQuery>,
+    JsonBody(payload): JsonBody<ActuatorPlate>,
+) -> impl IntoResponse {
+    let safety_factor = load.safety_factor.unwrap_or(engineering::DEFAULT_SAFETY_FACTOR);
+    if !(safety_factor.is_finite() && safety_factor >= 1.0) {
+        let res = ValidationErrorResponse {
+            valid: false,
+            errors: vec![ErrorDetail {
+                code: "SAFETY_FACTOR_INVALID".to_string(),
+                message: "Safety factor must be at least 1".to_string(),
+                fields: vec!["safetyFactor".to_string()],
+            }],
+            minimum_thickness_mm: None,
+            warnings: vec![],
+        };
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    }
+
+    let report = validation::report_with_limits(&payload, &state.limits);
+    let mut warnings: Vec<ErrorDetail> =
+        report.warnings.iter().map(|w| ErrorDetail::warning(w, locale(&headers))).collect();
+    if report.is_valid() {
+        let utilization = validation::stress_utilization(&payload);
+        let min_thickness = validation::minimum_thickness_mm(&payload);
+
+        let load_check = load.load_n.map(|load_n| {
+            let case = engineering::LoadCase::new(domain::Newtons(load_n), safety_factor);
+            engineering::check_load(&payload, &case)
+        });
+        warnings.extend(load_check.iter().filter(|check| !check.passes()).map(|check| ErrorDetail {
+            code: "LOAD_CHECK_FAILED".to_string(),
+            message: format!(
+                "Plate is likely to yield under {} N at safety factor {:.1}; use at least {} mm thickness",
+                check.case.load.0, check.case.safety_factor, check.required_thickness_mm
+            ),
+            fields: vec!["plateThickness".to_string(), "material".to_string()],
+        }));
+
+        let res = ValidationSuccessResponse {
+            valid: true,
+            message: "Actuator plate parameters are valid".to_string(),
+            stress_summary: StressSummary {
+                safety_factor: 2.0,
+                pin_bearing_utilization: utilization.pin_bearing,
+                bolt_bearing_utilization: utilization.bolt_bearing,
+                bending_utilization: utilization.bending,
+                minimum_thickness_mm: min_thickness,
+            },
+            load_check: load_check.map(|check| LoadCheckSummary {
+                load_n: check.case.load.0,
+                safety_factor: check.case.safety_factor,
+                bending_utilization: check.bending_utilization(),
+                shear_utilization: check.shear_utilization(),
+                required_thickness_mm: check.required_thickness_mm,
+                passes: check.passes(),
+                report_line: check.to_string(),
+            }),
+            warnings,
+        };
+        (StatusCode::OK, Json(res)).into_response()
+    } else {
+        let errors = report.errors;
+        let has_stress_error = errors.iter().any(|e| {
+            matches!(
+                e,
+                PlateValidationError::PinBearingStressExceeded { .. }
+                    | PlateValidationError::BoltBearingStressExceeded { .. }
+                    | PlateValidationError::PlateBendingStressExceeded
+            )
+        });
+        let minimum_thickness_mm =
+            if has_stress_error { Some(validation::minimum_thickness_mm(&payload)) } else { None };
+        let res = ValidationErrorResponse {
+            valid: false,
+            errors: errors.iter().map(|e| ErrorDetail::localized(e, locale(&headers))).collect(),
+            minimum_thickness_mm,
+            warnings,
+        };
+        (StatusCode::BAD_REQUEST, Json(res)).into_response()
+    }
+}
+
+/// Check the mounting bolts against a load
+///
+/// Shares `load_n` across the four corner bolts and reports the shear stress in the
+/// bolts (property class 8.8) and the bearing stress they put on the plate, each with
+/// its safety factor against yield. Safety factors below 2 come back as warnings;
+/// the plate itself must be valid.
+#[utoipa::path(
+    post,
+    path = "/api/analysis",
+    tag = "validation",
+    request_body = AnalysisRequest,
+    responses(
+        (status = 200, description = "Stresses at the bolts", body = AnalysisResponse),
+        (status = 400, description = "Invalid plate or load", body = ValidationErrorResponse),
+        (status = 422, description = "Body isn't a plate: a missing field, wrong type, or out-of-range number", body = GenerateErrorResponse)
+    )
+)]
+async fn analyze_plate(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    JsonBody(payload): JsonBody<AnalysisRequest>,
+) -> impl IntoResponse {
+    let mut errors: Vec<ErrorDetail> = match validation::validate_with_limits(&payload.plate, &state.limits) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| ErrorDetail::localized(e, locale(&headers))).collect(),
+    };
+    if payload.load_n == 0 {
+        errors.push(ErrorDetail {
+            code: "LOAD_INVALID".to_string(),
+            message: "Load must be at least 1 N".to_string(),
+            fields: vec!["load".to_string()],
+        });
+    }
+    if !errors.is_empty() {
+        let res = ValidationErrorResponse {
+            valid: false,
+            errors,
+            minimum_thickness_mm: None,
+            warnings: vec![],
+        };
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    }
+
+    let check = engineering::check_bolts(&payload.plate, domain::Newtons(payload.load_n));
+    let mut warnings = Vec::new();
+    if check.shear_safety_factor() < engineering::DEFAULT_SAFETY_FACTOR {
+        warnings.push(ErrorDetail {
+            code: "BOLT_SHEAR_SAFETY_FACTOR_LOW".to_string(),
+            message: format!(
+                "Bolts shear at a safety factor of {:.1} under {} N; use a larger bolt size",
+                check.shear_safety_factor(),
+                payload.load_n
+            ),
+            fields: vec!["boltSize".to_string(), "load".to_string()],
+        });
+    }
+    if check.bearing_safety_factor() < engineering::DEFAULT_SAFETY_FACTOR {
+        warnings.push(ErrorDetail {
+            code: "PLATE_BEARING_SAFETY_FACTOR_LOW".to_string(),
+            message: format!(
+                "Plate bears on the bolts at a safety factor of {:.1} under {} N; use a thicker plate, larger bolts, or a stronger material",
+                check.bearing_safety_factor(),
+                payload.load_n
+            ),
+            fields: vec![
+                "plateThickness".to_string(),
+                "boltSize".to_string(),
+                "material".to_string(),
+                "load".to_string(),
+            ],
+        });
     }
+
+    let res = AnalysisResponse {
+        load_n: payload.load_n,
+        bolt_count: engineering::BOLT_COUNT,
+        force_per_bolt_n: check.force_per_bolt_n,
+        bolt_shear: StressCheck {
+            stress_mpa: check.bolt_shear_stress_mpa,
+            yield_mpa: check.bolt_shear_yield_mpa,
+            safety_factor: check.shear_safety_factor(),
+        },
+        plate_bearing: StressCheck {
+            stress_mpa: check.plate_bearing_stress_mpa,
+            yield_mpa: check.plate_yield_mpa,
+            safety_factor: check.bearing_safety_factor(),
+        },
+        safety_factor: check.safety_factor(),
+        report_line: check.to_string(),
+        warnings,
+    };
+    (StatusCode::OK, Json(res)).into_response()
 }
 
 /// Generate actuator plate model files
@@ -316,37 +1189,32 @@ async fn validate_plate(Json(payload): Json<ActuatorPlate>) -> impl IntoResponse
     request_body = ActuatorPlate,
     responses(
         (status = 200, description = "Model files generated successfully", body = GenerateSuccessResponse),
-        (status = 400, description = "Invalid plate configuration", body = GenerateErrorResponse)
+        (status = 400, description = "Invalid plate configuration", body = GenerateErrorResponse),
+        (status = 422, description = "Body isn't a plate: a missing field, wrong type, or out-of-range number", body = GenerateErrorResponse),
+        (status = 401, description = "Missing, invalid, or revoked API key", body = ErrorResponse),
+        (status = 429, description = "Too many generation requests from this client", body = ErrorResponse),
+        (status = 500, description = "Model generation crashed", body = GenerateErrorResponse),
+        (status = 503, description = "The Zoo API quota is spent; nothing was generated or cached", body = GenerateErrorResponse)
     )
 )]
 pub async fn generate_plate_model(
     State(state): State<AppState>,
-    Json(payload): Json<ActuatorPlate>,
+    ApiKeyAuth(api_key): ApiKeyAuth,
+    ClientAddr(client): ClientAddr,
+    session: Session,
+    headers: HeaderMap,
+    JsonBody(payload): JsonBody<ActuatorPlate>,
 ) -> impl IntoResponse {
     let cache_key = payload.cache_key();
+    tracing::Span::current().record("cache_key", cache_key.as_str());
+    record_submission(&state, "/api/generate", &actor(&api_key, client), &payload);
 
     // Check cache first
-    if let Ok(cached_files) = state.cache.get(&cache_key).await {
+    if let Some(cached_files) = cached_for_generation(&state, &cache_key).await {
         tracing::info!("Cache hit for key: {}", cache_key);
-        let session_id = Uuid::new_v4().to_string();
-        let download_url = format!("/api/download/step/{}", session_id);
-        let gltf_url = format!("/api/download/gltf/{}", session_id);
-        let stl_url = format!("/api/download/stl/{}", session_id);
-
-        // Store the cached files in session
-        {
-            let mut sessions = state.sessions.write().await;
-            sessions.insert(session_id.clone(), SessionData::Cached(cached_files));
-        }
-
-        let res = GenerateSuccessResponse {
-            success: true,
-            message: "Model files retrieved from cache".to_string(),
-            download_url,
-            gltf_url,
-            stl_url,
-            session_id,
-        };
+        let message = "Model files retrieved from cache";
+        let res = open_session(&state, &payload, &cache_key, SessionData::Cached(cached_files), message, locale(&headers)).await;
+        recent::remember(&session, &payload).await;
         return (
             StatusCode::OK,
             [(header::HeaderName::from_static("x-cache"), "HIT")],
@@ -355,50 +1223,72 @@ pub async fn generate_plate_model(
             .into_response();
     }
 
-    tracing::info!("Cache miss for key: {}, generating model", cache_key);
-
-    match generate_model(&payload) {
-        Ok(result) => {
-            let session_id = Uuid::new_v4().to_string();
-            let download_url = format!("/api/download/step/{}", session_id);
-            let gltf_url = format!("/api/download/gltf/{}", session_id);
-            let stl_url = format!("/api/download/stl/{}", session_id);
-
-            // Read files for caching (do this before moving result)
-            let step_data = tokio::fs::read(&result.step_file).await.ok();
-            let gltf_data = tokio::fs::read(&result.gltf_file).await.ok();
-            let stl_data = tokio::fs::read(&result.stl_file).await.ok();
-
-            // Store the generation result in session
-            {
-                let mut sessions = state.sessions.write().await;
-                sessions.insert(session_id.clone(), SessionData::Generated(result));
-            }
+    let lead = match state.in_flight.claim(&cache_key).await {
+        Claim::Lead(lead) => lead,
+        Claim::Shared(Ok(files)) => {
+            let message = "Model files generated for a concurrent request";
+            let res = open_session(&state, &payload, &cache_key, SessionData::Cached(files), message, locale(&headers)).await;
+            recent::remember(&session, &payload).await;
+            return (
+                StatusCode::OK,
+                [(header::HeaderName::from_static("x-cache"), "SHARED")],
+                Json(res),
+            )
+                .into_response();
+        }
+        // The leader already reported the failure
+        Claim::Shared(Err(msg)) => {
+            let res = GenerateErrorResponse {
+                success: false,
+                errors: vec![ErrorDetail::generation_failed(msg)],
+                minimum_thickness_mm: None,
+            };
+            return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+        }
+    };
 
-            // Cache the files in the background (non-blocking)
-            if let (Some(step_data), Some(gltf_data), Some(stl_data)) = (step_data, gltf_data, stl_data) {
-                let cache = state.cache.clone();
-                let cache_key = cache_key.clone();
-                tokio::spawn(async move {
-                    let files = CachedFiles {
-                        step_data,
-                        gltf_data,
-                        stl_data,
-                    };
-                    if let Err(e) = cache.put(&cache_key, &files).await {
-                        tracing::warn!("Failed to cache files for key {}: {}", cache_key, e);
-                    }
-                });
-            }
+    tracing::info!("Cache miss for key: {}, generating model", cache_key);
 
-            let res = GenerateSuccessResponse {
-                success: true,
-                message: "Model files generated successfully".to_string(),
-                download_url,
-                gltf_url,
-                stl_url,
-                session_id,
+    // Invalid plates never reach the engine, so only valid ones count against the quota
+    if validation::validate_with_limits(&payload, &state.limits).is_ok() {
+        if let Err(e) = spend_zoo_quota(&state) {
+            let res = GenerateErrorResponse {
+                success: false,
+                errors: vec![ErrorDetail::quota_exhausted(&e)],
+                minimum_thickness_mm: None,
             };
+            return (StatusCode::SERVICE_UNAVAILABLE, Json(res)).into_response();
+        }
+    }
+
+    let started = std::time::Instant::now();
+    // The engine blocks on the Zoo CLI, so keep it off the async workers
+    let generated = {
+        let state = state.clone();
+        let plate = payload;
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || {
+            span.in_scope(|| generate_model_with_limits(&plate, state.engine.as_ref(), &state.limits))
+        })
+        .await
+    };
+    let Ok(generated) = generated else {
+        tracing::error!("Generating {} panicked", cache_key);
+        let res = GenerateErrorResponse {
+            success: false,
+            errors: vec![ErrorDetail::generation_failed("Model generation crashed".to_string())],
+            minimum_thickness_mm: None,
+        };
+        return (StatusCode::INTERNAL_SERVER_ERROR, Json(res)).into_response();
+    };
+    observe_generation(&state, &cache_key, &generated, started.elapsed());
+    if let Err(parametric::AllErrors::GeneratorError(msg)) = &generated {
+        lead.finish(Err(msg.clone()));
+    }
+    match generated {
+        Ok(result) => {
+            let res = store_generated(&state, lead, &cache_key, &payload, result, locale(&headers)).await;
+            recent::remember(&session, &payload).await;
             (
                 StatusCode::OK,
                 [(header::HeaderName::from_static("x-cache"), "MISS")],
@@ -407,219 +1297,2156 @@ pub async fn generate_plate_model(
                 .into_response()
         }
         Err(e) => {
-            tracing::error!("generation error: {:?}", e);
-            let (errors, minimum_thickness_mm) = match e {
-                parametric::AllErrors::ValidationErrors(errs) => {
-                    let has_stress_error = errs.iter().any(|e| {
-                        matches!(
-                            e,
-                            PlateValidationError::PinBearingStressExceeded { .. }
-                                | PlateValidationError::BoltBearingStressExceeded { .. }
-                                | PlateValidationError::PlateBendingStressExceeded
-                        )
-                    });
-                    let min_t = if has_stress_error {
-                        Some(validation::minimum_thickness_mm(&payload))
-                    } else {
-                        None
-                    };
-                    let details = errs
-                        .iter()
-                        .map(|e| ErrorDetail {
-                            message: e.to_string(),
-                            fields: e.related_fields().iter().map(|s| s.to_string()).collect(),
-                        })
-                        .collect();
-                    (details, min_t)
-                }
-                parametric::AllErrors::GeneratorError(msg) => (
-                    vec![ErrorDetail { message: msg, fields: vec![] }],
-                    None,
-                ),
-            };
+            let (errors, minimum_thickness_mm) =
+                generation_errors(&state, error_reporting::request_id(&headers), locale(&headers), &cache_key, &payload, e);
             let res = GenerateErrorResponse { success: false, errors, minimum_thickness_mm };
             (StatusCode::BAD_REQUEST, Json(res)).into_response()
         }
     }
 }
 
-/// Download STEP file
-///
-/// Downloads the generated STEP model file for a given session ID.
-/// The session ID is obtained from the generate endpoint response.
-#[utoipa::path(
-    get,
-    path = "/api/download/step/{session_id}",
-    tag = "generation",
-    params(
-        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
-    ),
-    responses(
-        (status = 200, description = "STEP file downloaded successfully", content_type = "application/STEP"),
-        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
-    )
-)]
-async fn download_step(
-    State(state): State<AppState>,
-    Path(session_id): Path<String>,
-) -> impl IntoResponse {
-    let sessions = state.sessions.read().await;
-
-    let Some(session_data) = sessions.get(&session_id) else {
-        let res = ErrorResponse {
-            success: false,
-            got_it: false,
-            errors: vec!["Session not found. Please generate the model first.".to_string()],
-        };
-        return (StatusCode::NOT_FOUND, Json(res)).into_response();
-    };
+/// Store files under a new download session and describe where to fetch them,
+/// with the plate's warnings in `locale`.
+async fn open_session(
+    state: &AppStateInner,
+    plate: &ActuatorPlate,
+    cache_key: &str,
+    data: SessionData,
+    message: &str,
+    locale: Locale,
+) -> GenerateSuccessResponse {
+    let session_id = Uuid::new_v4().to_string();
+    state.sessions.write().await.insert(session_id.clone(), data);
+    GenerateSuccessResponse {
+        success: true,
+        message: message.to_string(),
+        cache_key: cache_key.to_string(),
+        download_url: format!("/api/download/step/{}", session_id),
+        gltf_url: format!("/api/download/gltf/{}", session_id),
+        stl_url: format!("/api/download/stl/{}", session_id),
+        obj_url: format!("/api/download/obj/{}", session_id),
+        three_mf_url: format!("/api/download/3mf/{}", session_id),
+        svg_url: format!("/api/download/svg/{}", session_id),
+        pdf_url: format!("/api/download/pdf/{}", session_id),
+        dxf_url: format!("/api/download/dxf/{}", session_id),
+        kcl_url: format!("/api/download/kcl/{}", session_id),
+        session_id,
+        mass_properties: plate.mass_properties(),
+        warnings: validation::warnings(plate).iter().map(|w| ErrorDetail::warning(w, locale)).collect(),
+    }
+}
 
-    let contents = match session_data {
-        SessionData::Cached(cached) => Ok(cached.step_data.clone()),
-        SessionData::Generated(result) => tokio::fs::read(&result.step_file).await,
+/// Hand freshly generated files to a download session and to requests waiting on
+/// `lead`, announce the design, and cache the files in the background. The lead is
+/// held until they're cached.
+async fn store_generated(
+    state: &AppStateInner,
+    lead: Lead,
+    cache_key: &str,
+    plate: &ActuatorPlate,
+    result: GenerationResult,
+    locale: Locale,
+) -> GenerateSuccessResponse {
+    // Read files for caching (do this before moving result)
+    let files = read_generated(&result).await;
+    lead.finish(Ok(files.clone()));
+    let message = "Model files generated successfully";
+    let res = open_session(state, plate, cache_key, SessionData::Generated(Box::new(result)), message, locale).await;
+
+    state.webhooks.dispatch(
+        &state.supervisor,
+        WebhookEvent::DesignCreated,
+        None,
+        serde_json::json!({ "cache_key": cache_key, "plate": plate }),
+    );
+
+    // Cache the files in the background (non-blocking)
+    if files.is_complete() {
+        let cache = state.cache.clone();
+        let cache_key = cache_key.to_string();
+        state.supervisor.spawn_task("cache-write", async move {
+            // Transient store failures get a couple more tries; anything else won't improve
+            let mut delay = std::time::Duration::from_millis(200);
+            let _lead = lead;
+            for attempt in 1..=3 {
+                match cache.put(&cache_key, &files).await {
+                    Ok(()) => break,
+                    Err(e) if e.is_retryable() && attempt < 3 => {
+                        tracing::info!("Retrying cache write for key {}: {}", cache_key, e);
+                        tokio::time::sleep(delay).await;
+                        delay *= 2;
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to cache files for key {}: {}", cache_key, e);
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    res
+}
+
+/// Read a generation's files into memory for the cache. Files that can't be read
+/// are left out, which leaves the entry incomplete.
+async fn read_generated(result: &GenerationResult) -> CachedFiles {
+    let mut files = CachedFiles::new();
+    for (path, extension) in result.files() {
+        if let (Some(artifact), Ok(data)) = (Artifact::from_extension(extension), tokio::fs::read(path).await) {
+            files.insert(artifact, data);
+        }
+    }
+    files
+}
+
+/// Record a finished generation in the metrics and, unless the plate was invalid, the
+/// audit log.
+fn observe_generation(
+    state: &AppStateInner,
+    cache_key: &str,
+    result: &Result<GenerationResult, parametric::AllErrors>,
+    elapsed: std::time::Duration,
+) {
+    state.metrics.observe_generation(GenerationOutcome::of(result), elapsed);
+    let event = match result {
+        Ok(_) => AuditEvent::new(AuditKind::Generated, cache_key),
+        Err(parametric::AllErrors::GeneratorError(msg)) => AuditEvent::new(AuditKind::EngineFailure, cache_key).with_detail(msg),
+        Err(parametric::AllErrors::ValidationErrors(_)) => return,
+    };
+    record_audit(state, event.with_duration(elapsed));
+}
+
+/// Count a generation against the Zoo quota, or refuse it. One generation is one
+/// call however many CLI runs and retries it takes. The call that spends the last
+/// of a budget tells the chat notifiers.
+fn spend_zoo_quota(state: &AppStateInner) -> Result<(), QuotaExhausted> {
+    match state.zoo_quota.acquire() {
+        Ok(None) => Ok(()),
+        Ok(Some(spent)) => {
+            tracing::warn!("Zoo API {} quota used up: {} of {} calls", spent.period, spent.used, spent.limit);
+            notify::notify_all(
+                &state.supervisor,
+                &state.notifiers,
+                Notification::QuotaExhausted { used: spent.used, limit: spent.limit },
+            );
+            Ok(())
+        }
+        Err(e) => {
+            tracing::warn!("{}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Who made a request, for the audit log: the API key's name if it sent one,
+/// otherwise the client address.
+fn actor(api_key: &Option<ApiKey>, client: Option<std::net::IpAddr>) -> String {
+    match (api_key, client) {
+        (Some(key), _) => format!("key:{}", key.name),
+        (None, Some(ip)) => format!("ip:{}", ip),
+        (None, None) => String::new(),
+    }
+}
+
+/// Record a plate submitted for generation through `route` in the audit log.
+fn record_submission(state: &AppStateInner, route: &str, actor: &str, plate: &ActuatorPlate) {
+    let event = AuditEvent::new(AuditKind::Request, plate.cache_key())
+        .with_actor(actor)
+        .with_detail(route)
+        .with_plate(plate);
+    record_audit(state, event);
+}
+
+/// Append to the audit log in the background; a failed write is only logged.
+fn record_audit(state: &AppStateInner, event: AuditEvent) {
+    let audit = state.audit.clone();
+    state.supervisor.spawn_task("audit", async move {
+        if let Err(e) = audit.record(&event).await {
+            tracing::warn!("Failed to record {} for {}: {}", event.kind.as_str(), event.subject, e);
+        }
+    });
+}
+
+/// Turn a failed generation into error details, reporting engine failures to the
+/// error reporter and chat. Also returns the thickness that would pass, when the
+/// plate failed a stress check.
+fn generation_errors(
+    state: &AppStateInner,
+    request_id: Option<String>,
+    locale: Locale,
+    cache_key: &str,
+    plate: &ActuatorPlate,
+    e: parametric::AllErrors,
+) -> (Vec<ErrorDetail>, Option<u16>) {
+    tracing::error!("generation error: {:?}", e);
+    match e {
+        parametric::AllErrors::ValidationErrors(errs) => {
+            let has_stress_error = errs.iter().any(|e| {
+                matches!(
+                    e,
+                    PlateValidationError::PinBearingStressExceeded { .. }
+                        | PlateValidationError::BoltBearingStressExceeded { .. }
+                        | PlateValidationError::PlateBendingStressExceeded
+                )
+            });
+            let min_t = if has_stress_error {
+                Some(validation::minimum_thickness_mm(plate))
+            } else {
+                None
+            };
+            (errs.iter().map(|e| ErrorDetail::localized(e, locale)).collect(), min_t)
+        }
+        parametric::AllErrors::GeneratorError(msg) => {
+            state.metrics.record_engine_failure("generation");
+            state.error_reporter.report(
+                &ErrorReport::new(ReportKind::EngineFailure, msg.clone())
+                    .with_request_id(request_id)
+                    .with_attachment("program.kcl", parametric::kcl::emit(plate)),
+            );
+            notify::notify_all(
+                &state.supervisor,
+                &state.notifiers,
+                Notification::GenerationFailed {
+                    cache_key: cache_key.to_string(),
+                    message: msg.clone(),
+                },
+            );
+            (vec![ErrorDetail::generation_failed(msg)], None)
+        }
+    }
+}
+
+/// Start a generation job
+///
+/// Validates the plate and returns a job at once instead of waiting for the model
+/// files. Poll `/api/jobs/{job_id}` (also given in the `Location` header) until its
+/// status is `succeeded`, then use the download URLs in `result`. A plate that is
+/// already cached yields a job that has already succeeded.
+#[utoipa::path(
+    post,
+    path = "/api/jobs",
+    tag = "generation",
+    request_body = ActuatorPlate,
+    responses(
+        (status = 202, description = "Job accepted", body = Job),
+        (status = 400, description = "Invalid plate configuration", body = GenerateErrorResponse),
+        (status = 422, description = "Body isn't a plate: a missing field, wrong type, or out-of-range number", body = GenerateErrorResponse),
+        (status = 401, description = "Missing, invalid, or revoked API key", body = ErrorResponse),
+        (status = 404, description = "The async_jobs flag is off", body = ErrorResponse),
+        (status = 429, description = "Too many generation requests from this client", body = ErrorResponse)
+    )
+)]
+async fn create_job(
+    State(state): State<AppState>,
+    ApiKeyAuth(api_key): ApiKeyAuth,
+    ClientAddr(client): ClientAddr,
+    session: Session,
+    headers: HeaderMap,
+    JsonBody(payload): JsonBody<ActuatorPlate>,
+) -> impl IntoResponse {
+    if !state.flags.is_enabled(Flag::AsyncJobs) {
+        return async_jobs_disabled();
+    }
+    record_submission(&state, "/api/jobs", &actor(&api_key, client), &payload);
+    if let Err(errs) = validation::validate_with_limits(&payload, &state.limits) {
+        let (errors, minimum_thickness_mm) = generation_errors(
+            &state,
+            None,
+            locale(&headers),
+            &payload.cache_key(),
+            &payload,
+            parametric::AllErrors::ValidationErrors(errs),
+        );
+        let res = GenerateErrorResponse { success: false, errors, minimum_thickness_mm };
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    }
+
+    recent::remember(&session, &payload).await;
+    let owner = api_key.map(|key| key.id);
+    let job = start_job(&state, payload, owner, error_reporting::request_id(&headers), None).await;
+    let location = format!("/api/jobs/{}", job.id);
+    (StatusCode::ACCEPTED, [(header::LOCATION, location)], Json(job)).into_response()
+}
+
+/// Record a job for a validated plate, started with the API key `owner`. A cached
+/// plate's job succeeds at once; otherwise generation is queued in the background.
+async fn start_job(
+    state: &AppState,
+    plate: ActuatorPlate,
+    owner: Option<String>,
+    request_id: Option<String>,
+    batch_slots: Option<Arc<tokio::sync::Semaphore>>,
+) -> Job {
+    let cache_key = plate.cache_key();
+    tracing::Span::current().record("cache_key", cache_key.as_str());
+    let job = state.jobs.create(&cache_key, owner);
+    if let Some(cached_files) = cached_for_generation(state, &cache_key).await {
+        tracing::info!("Cache hit for key: {}", cache_key);
+        let message = "Model files retrieved from cache";
+        let res = open_session(state, &plate, &cache_key, SessionData::Cached(cached_files), message, Locale::En).await;
+        let Some(job) = state.jobs.update(&job.id, |job| job.succeed(res)) else {
+            return job;
+        };
+        jobs::notify_finished(state, &job);
+        return job;
+    }
+
+    tracing::info!("Cache miss for key: {}, queueing job {}", cache_key, job.id);
+    let span = tracing::info_span!("generation_job", job_id = %job.id, cache_key = %cache_key);
+    state.supervisor.spawn_task(
+        "generation-job",
+        jobs::run(state.clone(), job.id.clone(), plate, request_id, batch_slots).instrument(span),
+    );
+    job
+}
+
+/// Stream a generation job's progress over a WebSocket.
+///
+/// Sends the job, in the same JSON form as `GET /api/jobs/{job_id}`, as a text
+/// message straight away and again each time it changes stage, then closes once the
+/// job has succeeded or failed. Not part of the OpenAPI spec, which can't describe
+/// WebSockets.
+async fn generation_progress(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+    ws: WebSocketUpgrade,
+) -> axum::response::Response {
+    if !state.flags.is_enabled(Flag::AsyncJobs) {
+        return async_jobs_disabled();
+    }
+    match state.jobs.subscribe(&job_id) {
+        Some(updates) => ws.on_upgrade(move |socket| jobs::stream_progress(socket, updates)),
+        None => {
+            let res = ErrorResponse {
+                success: false,
+                got_it: false,
+                errors: vec!["Job not found".to_string()],
+            };
+            (StatusCode::NOT_FOUND, Json(res)).into_response()
+        }
+    }
+}
+
+/// Response for the job endpoints while the `async_jobs` flag is off.
+fn async_jobs_disabled() -> axum::response::Response {
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec!["Background jobs are turned off on this server; use /api/generate".to_string()],
+    };
+    (StatusCode::NOT_FOUND, Json(res)).into_response()
+}
+
+/// Most plates accepted in one batch request.
+const MAX_BATCH_SIZE: usize = 100;
+
+/// How many of a batch's jobs generate at once when `parallelism` isn't given.
+const DEFAULT_BATCH_PARALLELISM: usize = 2;
+
+/// Start generation jobs for several plates
+///
+/// Validates every plate in the array and starts a job for each valid one, the same
+/// as `/api/jobs`. Invalid plates don't stop the rest of the batch: `items` has one
+/// entry per submitted plate, in order, with either its job or its errors. Poll each
+/// job at `/api/jobs/{job_id}`.
+///
+/// At most `parallelism` of the batch's jobs generate at once; the server-wide job
+/// limit still applies on top of that.
+#[utoipa::path(
+    post,
+    path = "/api/plates/batch",
+    tag = "generation",
+    params(BatchQuery),
+    request_body = Vec<ActuatorPlate>,
+    responses(
+        (status = 202, description = "Batch accepted; see each item for its job or errors", body = BatchResponse),
+        (status = 400, description = "Empty batch, too many plates, or invalid parallelism", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid, or revoked API key", body = ErrorResponse),
+        (status = 404, description = "The async_jobs flag is off", body = ErrorResponse),
+        (status = 429, description = "Too many generation requests from this client", body = ErrorResponse)
+    )
+)]
+async fn create_batch(
+    State(state): State<AppState>,
+    ApiKeyAuth(api_key): ApiKeyAuth,
+    ClientAddr(client): ClientAddr,
+    headers: HeaderMap,
+    Query(query): Query<BatchQuery>,
+    Json(payload): Json<Vec<ActuatorPlate>>,
+) -> impl IntoResponse {
+    if !state.flags.is_enabled(Flag::AsyncJobs) {
+        return async_jobs_disabled();
+    }
+    let mut errors = Vec::new();
+    if payload.is_empty() || payload.len() > MAX_BATCH_SIZE {
+        errors.push(format!("A batch must contain 1 to {} plates", MAX_BATCH_SIZE));
+    }
+    let parallelism = query.parallelism.unwrap_or(DEFAULT_BATCH_PARALLELISM);
+    if parallelism == 0 {
+        errors.push("Parallelism must be at least 1".to_string());
+    }
+    if !errors.is_empty() {
+        let res = ErrorResponse {
+            success: false,
+            got_it: false,
+            errors,
+        };
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    }
+
+    let request_id = error_reporting::request_id(&headers);
+    let actor = actor(&api_key, client);
+    let owner = api_key.map(|key| key.id);
+    let batch_slots = Arc::new(tokio::sync::Semaphore::new(parallelism.min(payload.len())));
+    let mut items = Vec::with_capacity(payload.len());
+    for (index, plate) in payload.into_iter().enumerate() {
+        let cache_key = plate.cache_key();
+        record_submission(&state, "/api/plates/batch", &actor, &plate);
+        let item = match validation::validate_with_limits(&plate, &state.limits) {
+            Ok(()) => {
+                let job = start_job(&state, plate, owner.clone(), request_id.clone(), Some(batch_slots.clone())).await;
+                BatchItem { index, cache_key, job: Some(job), errors: Vec::new() }
+            }
+            Err(errs) => BatchItem {
+                index,
+                cache_key,
+                job: None,
+                errors: errs.iter().map(|e| ErrorDetail::localized(e, locale(&headers))).collect(),
+            },
+        };
+        items.push(item);
+    }
+
+    let accepted = items.iter().filter(|item| item.job.is_some()).count();
+    tracing::info!("Batch of {} plates: {} jobs started", items.len(), accepted);
+    let res = BatchResponse {
+        accepted,
+        rejected: items.len() - accepted,
+        items,
+    };
+    (StatusCode::ACCEPTED, Json(res)).into_response()
+}
+
+/// Generate every combination of a parameter sweep
+///
+/// Expands `ranges` around `base` into their cartesian product, e.g. `bolt_spacing`
+/// 40–80 mm in steps of 10 and `pin_count` 2–6 in steps of 2 for 15 plates. Invalid
+/// combinations are skipped; a job is started for each valid one, the same as
+/// `/api/plates/batch`, so its model ends up in the cache. The response is the
+/// sweep's manifest: every valid plate with its cache key and job.
+///
+/// A sweep may expand to at most 1000 combinations, of which at most 100 may be valid.
+#[utoipa::path(
+    post,
+    path = "/api/sweep",
+    tag = "generation",
+    params(BatchQuery),
+    request_body = Sweep,
+    responses(
+        (status = 202, description = "Jobs started for every valid combination", body = SweepResponse),
+        (status = 400, description = "Malformed ranges, no valid combinations, or too many", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid, or revoked API key", body = ErrorResponse),
+        (status = 404, description = "The async_jobs flag is off", body = ErrorResponse),
+        (status = 429, description = "Too many generation requests from this client", body = ErrorResponse)
+    )
+)]
+async fn create_sweep(
+    State(state): State<AppState>,
+    ApiKeyAuth(api_key): ApiKeyAuth,
+    ClientAddr(client): ClientAddr,
+    headers: HeaderMap,
+    Query(query): Query<BatchQuery>,
+    Json(payload): Json<Sweep>,
+) -> impl IntoResponse {
+    if !state.flags.is_enabled(Flag::AsyncJobs) {
+        return async_jobs_disabled();
+    }
+    let bad_request = |errors: Vec<String>| {
+        let res = ErrorResponse {
+            success: false,
+            got_it: false,
+            errors,
+        };
+        (StatusCode::BAD_REQUEST, Json(res)).into_response()
+    };
+    let parallelism = query.parallelism.unwrap_or(DEFAULT_BATCH_PARALLELISM);
+    if parallelism == 0 {
+        return bad_request(vec!["Parallelism must be at least 1".to_string()]);
+    }
+    let points = match payload.points(&state.limits) {
+        Ok(points) => points,
+        Err(e) => return bad_request(vec![e.to_string()]),
+    };
+    let total = points.len();
+    let valid: Vec<_> = points.into_iter().filter(|point| point.errors.is_empty()).collect();
+    if valid.is_empty() || valid.len() > MAX_BATCH_SIZE {
+        return bad_request(vec![format!(
+            "A sweep must have 1 to {} valid combinations; this one has {}",
+            MAX_BATCH_SIZE,
+            valid.len()
+        )]);
+    }
+
+    let request_id = error_reporting::request_id(&headers);
+    let actor = actor(&api_key, client);
+    let owner = api_key.map(|key| key.id);
+    let batch_slots = Arc::new(tokio::sync::Semaphore::new(parallelism.min(valid.len())));
+    let mut plates = Vec::with_capacity(valid.len());
+    for point in valid {
+        record_submission(&state, "/api/sweep", &actor, &point.plate);
+        let job = start_job(&state, point.plate, owner.clone(), request_id.clone(), Some(batch_slots.clone())).await;
+        plates.push(SweepItem {
+            index: point.index,
+            cache_key: point.plate.cache_key(),
+            plate: point.plate,
+            job,
+        });
+    }
+
+    tracing::info!("Sweep of {} combinations: {} jobs started", total, plates.len());
+    let res = SweepResponse {
+        total,
+        skipped: total - plates.len(),
+        sweep: payload,
+        plates,
+    };
+    (StatusCode::ACCEPTED, Json(res)).into_response()
+}
+
+/// Get a generation job
+///
+/// Returns the job's status and progress. Once it has succeeded, `result` holds the
+/// download URLs; if it failed, `errors` says why. Finished jobs are kept for an hour.
+#[utoipa::path(
+    get,
+    path = "/api/jobs/{job_id}",
+    tag = "generation",
+    params(
+        ("job_id" = String, Path, description = "Job ID from the create job endpoint")
+    ),
+    responses(
+        (status = 200, description = "Job status", body = Job),
+        (status = 404, description = "Job not found or expired", body = ErrorResponse)
+    )
+)]
+async fn get_job(State(state): State<AppState>, Path(job_id): Path<String>) -> impl IntoResponse {
+    match state.jobs.get(&job_id) {
+        Some(job) => (StatusCode::OK, Json(job)).into_response(),
+        None => {
+            let res = ErrorResponse {
+                success: false,
+                got_it: false,
+                errors: vec!["Job not found".to_string()],
+            };
+            (StatusCode::NOT_FOUND, Json(res)).into_response()
+        }
+    }
+}
+
+/// Stream a generated file from disk rather than reading it into memory.
+async fn file_body(path: &std::path::Path) -> std::io::Result<Body> {
+    let file = tokio::fs::File::open(path).await?;
+    Ok(Body::from_stream(tokio_util::io::ReaderStream::new(file)))
+}
+
+/// Download STEP file
+///
+/// Downloads the generated STEP model file for a given session ID.
+/// The session ID is obtained from the generate endpoint response.
+#[utoipa::path(
+    get,
+    path = "/api/download/step/{session_id}",
+    tag = "generation",
+    params(
+        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
+    ),
+    responses(
+        (status = 200, description = "STEP file downloaded successfully", content_type = "application/STEP"),
+        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+    )
+)]
+async fn download_step(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    session_download(&state, &session_id, ModelFormat::Step).await
+}
+
+/// Download glTF file
+///
+/// Downloads the generated glTF model file for a given session ID.
+/// The session ID is obtained from the generate endpoint response.
+#[utoipa::path(
+    get,
+    path = "/api/download/gltf/{session_id}",
+    tag = "generation",
+    params(
+        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
+    ),
+    responses(
+        (status = 200, description = "glTF file downloaded successfully", content_type = "model/gltf+json"),
+        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+    )
+)]
+async fn download_gltf(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    session_download(&state, &session_id, ModelFormat::Gltf).await
+}
+
+/// Download STL file
+///
+/// Downloads the generated STL model file for a given session ID.
+/// The session ID is obtained from the generate endpoint response.
+#[utoipa::path(
+    get,
+    path = "/api/download/stl/{session_id}",
+    tag = "generation",
+    params(
+        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
+    ),
+    responses(
+        (status = 200, description = "STL file downloaded successfully", content_type = "model/stl"),
+        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+    )
+)]
+async fn download_stl(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    session_download(&state, &session_id, ModelFormat::Stl).await
+}
+
+/// Download mesh as OBJ
+///
+/// Downloads the STL's triangles as a Wavefront OBJ file with shared vertices, in
+/// millimeters, for a given session ID.
+#[utoipa::path(
+    get,
+    path = "/api/download/obj/{session_id}",
+    tag = "generation",
+    params(
+        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
+    ),
+    responses(
+        (status = 200, description = "OBJ mesh downloaded successfully", content_type = "model/obj"),
+        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+    )
+)]
+async fn download_obj(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    session_download(&state, &session_id, ModelFormat::Obj).await
+}
+
+/// Download mesh as 3MF
+///
+/// Downloads the same mesh as `/api/download/obj/{session_id}` as a 3MF package in
+/// millimeters, which slicers open with its units intact.
+#[utoipa::path(
+    get,
+    path = "/api/download/3mf/{session_id}",
+    tag = "generation",
+    params(
+        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
+    ),
+    responses(
+        (status = 200, description = "3MF package downloaded successfully", content_type = "model/3mf"),
+        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+    )
+)]
+async fn download_three_mf(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    session_download(&state, &session_id, ModelFormat::ThreeMf).await
+}
+
+/// Download drawing as SVG
+///
+/// Downloads the dimensioned 2D drawing (front and side views, hole callouts, title
+/// block) for a given session ID.
+#[utoipa::path(
+    get,
+    path = "/api/download/svg/{session_id}",
+    tag = "generation",
+    params(
+        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
+    ),
+    responses(
+        (status = 200, description = "SVG drawing downloaded successfully", content_type = "image/svg+xml"),
+        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+    )
+)]
+async fn download_svg(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    session_download(&state, &session_id, ModelFormat::Svg).await
+}
+
+/// Download drawing as PDF
+///
+/// Downloads the same drawing as `/api/download/svg/{session_id}`, as a one-page A4 PDF.
+#[utoipa::path(
+    get,
+    path = "/api/download/pdf/{session_id}",
+    tag = "generation",
+    params(
+        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
+    ),
+    responses(
+        (status = 200, description = "PDF drawing downloaded successfully", content_type = "application/pdf"),
+        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+    )
+)]
+async fn download_pdf(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    session_download(&state, &session_id, ModelFormat::Pdf).await
+}
+
+/// Download cut profile as DXF
+///
+/// Downloads the plate's flat profile for waterjet or laser cutting: a DXF R12 file in
+/// millimeters with the outline on the `OUTLINE` layer and every hole and slot on the
+/// `HOLES` layer. Recessed and tapped holes are drawn at their drill diameter.
+#[utoipa::path(
+    get,
+    path = "/api/download/dxf/{session_id}",
+    tag = "generation",
+    params(
+        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
+    ),
+    responses(
+        (status = 200, description = "DXF cut profile downloaded successfully", content_type = "image/vnd.dxf"),
+        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+    )
+)]
+async fn download_dxf(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    session_download(&state, &session_id, ModelFormat::Dxf).await
+}
+
+/// Download KCL source
+///
+/// Downloads the KCL program the model was built from, with its files joined into one
+/// and each marked by a `// file:` comment, for editing the plate in a KCL editor.
+#[utoipa::path(
+    get,
+    path = "/api/download/kcl/{session_id}",
+    tag = "generation",
+    params(
+        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
+    ),
+    responses(
+        (status = 200, description = "KCL source downloaded successfully", content_type = "text/plain"),
+        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+    )
+)]
+async fn download_kcl(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    session_download(&state, &session_id, ModelFormat::Kcl).await
+}
+
+/// Serve one file of a download session.
+async fn session_download(state: &AppStateInner, session_id: &str, format: ModelFormat) -> axum::response::Response {
+    let sessions = state.sessions.read().await;
+
+    let Some(session_data) = sessions.get(session_id) else {
+        let res = ErrorResponse {
+            success: false,
+            got_it: false,
+            errors: vec!["Session not found. Please generate the model first.".to_string()],
+        };
+        return (StatusCode::NOT_FOUND, Json(res)).into_response();
+    };
+
+    let contents = match session_data {
+        SessionData::Cached(cached) => cached
+            .get(format.artifact())
+            .map(|data| Body::from(data.clone()))
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound)),
+        SessionData::Generated(result) => file_body(format.path(result)).await,
+    };
+
+    match contents {
+        Ok(contents) => {
+            // The glTF is shown in the 3D viewer; everything else is saved
+            let disposition = match format {
+                ModelFormat::Gltf => "inline",
+                _ => "attachment",
+            };
+            let headers = [
+                (header::CONTENT_TYPE, format.content_type().to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("{}; filename=\"actuator_plate.{}\"", disposition, format.extension()),
+                ),
+            ];
+            (StatusCode::OK, headers, contents).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to read {} file: {}", format.label(), e);
+            let res = ErrorResponse {
+                success: false,
+                got_it: false,
+                errors: vec![format!("{} file not found. Please generate the model first.", format.label())],
+            };
+            (StatusCode::NOT_FOUND, Json(res)).into_response()
+        }
+    }
+}
+
+/// Download a cached model by cache key
+///
+/// Downloads the STEP, glTF, or STL file, the OBJ or 3MF mesh, the SVG or PDF drawing, or the DXF cut profile, for a plate that has already been generated,
+/// addressed by its cache key rather than a session. The key is stable for a given
+/// configuration, so the URL stays valid across restarts for as long as the model is cached.
+#[utoipa::path(
+    get,
+    path = "/api/models/{cache_key}/download",
+    tag = "generation",
+    params(
+        ("cache_key" = String, Path, description = "Cache key of a generated plate"),
+        ModelDownloadQuery
+    ),
+    responses(
+        (status = 200, description = "Model file", content_type = "application/octet-stream"),
+        (status = 400, description = "Not a cache key, or an unknown format", body = ErrorResponse),
+        (status = 404, description = "No cached model for this key", body = ErrorResponse),
+        (status = 500, description = "The cache could not be read", body = ErrorResponse)
+    )
+)]
+async fn download_model(
+    State(state): State<AppState>,
+    Path(cache_key): Path<String>,
+    Query(query): Query<ModelDownloadQuery>,
+) -> impl IntoResponse {
+    if !cache::is_valid_key(&cache_key) {
+        return invalid_cache_key();
+    }
+    let format = query.format;
+    // Only a checked key goes in the header, so it can't close the quotes
+    let disposition = format!("attachment; filename=\"{}.{}\"", cache_key, format.extension());
+    stream_model(&state, &cache_key, format.artifact(), format.content_type(), disposition).await
+}
+
+/// View a cached model's glTF by cache key
+///
+/// Serves the glTF inline for 3D viewers, addressed by cache key so a page can show a
+/// plate it generated earlier without a session.
+#[utoipa::path(
+    get,
+    path = "/api/models/{cache_key}/gltf",
+    tag = "generation",
+    params(
+        ("cache_key" = String, Path, description = "Cache key of a generated plate")
+    ),
+    responses(
+        (status = 200, description = "glTF model", content_type = "model/gltf+json"),
+        (status = 400, description = "Not a cache key", body = ErrorResponse),
+        (status = 404, description = "No cached model for this key", body = ErrorResponse),
+        (status = 500, description = "The cache could not be read", body = ErrorResponse)
+    )
+)]
+async fn model_gltf(State(state): State<AppState>, Path(cache_key): Path<String>) -> impl IntoResponse {
+    if !cache::is_valid_key(&cache_key) {
+        return invalid_cache_key();
+    }
+    let disposition = "inline; filename=\"actuator_plate.gltf\"".to_string();
+    stream_model(&state, &cache_key, Artifact::Gltf, "model/gltf+json", disposition).await
+}
+
+/// View a cached model's KCL source by cache key
+///
+/// Serves the exact KCL program the cached files were built from (`params.kcl`, the
+/// family's part file, and `main.kcl`, each marked by a `// file:` comment) inline, so
+/// an engineer can audit the geometry code behind a STEP file downloaded earlier.
+#[utoipa::path(
+    get,
+    path = "/api/models/{cache_key}/kcl",
+    tag = "generation",
+    params(
+        ("cache_key" = String, Path, description = "Cache key of a generated plate")
+    ),
+    responses(
+        (status = 200, description = "KCL source", content_type = "text/plain"),
+        (status = 400, description = "Not a cache key", body = ErrorResponse),
+        (status = 404, description = "No cached model for this key", body = ErrorResponse),
+        (status = 500, description = "The cache could not be read", body = ErrorResponse)
+    )
+)]
+async fn model_kcl(State(state): State<AppState>, Path(cache_key): Path<String>) -> impl IntoResponse {
+    if !cache::is_valid_key(&cache_key) {
+        return invalid_cache_key();
+    }
+    let disposition = format!("inline; filename=\"{}.kcl\"", cache_key);
+    stream_model(&state, &cache_key, Artifact::Kcl, Artifact::Kcl.content_type(), disposition).await
+}
+
+/// Email a cached model
+///
+/// Sends the plate's STEP file to `email`: attached when it is at most
+/// EMAIL_MAX_ATTACHMENT_BYTES (7 MB by default), otherwise as a download link to
+/// `/api/models/{cache_key}/download`. Generate the plate first.
+#[utoipa::path(
+    post,
+    path = "/api/models/{cache_key}/email",
+    tag = "generation",
+    params(
+        ("cache_key" = String, Path, description = "Cache key of a generated plate")
+    ),
+    request_body = EmailModelRequest,
+    responses(
+        (status = 200, description = "Email sent", body = EmailModelResponse),
+        (status = 400, description = "Invalid email address, or not a cache key", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid, or revoked API key", body = ErrorResponse),
+        (status = 404, description = "No cached model for this key", body = ErrorResponse),
+        (status = 413, description = "Too large to attach, and the server has no PUBLIC_URL to link to", body = ErrorResponse),
+        (status = 429, description = "Too many requests from this client", body = ErrorResponse),
+        (status = 502, description = "The mail server refused the message or couldn't be reached", body = ErrorResponse)
+    )
+)]
+async fn email_model(
+    State(state): State<AppState>,
+    _auth: ApiKeyAuth,
+    Path(cache_key): Path<String>,
+    Json(payload): Json<EmailModelRequest>,
+) -> impl IntoResponse {
+    let error = |status: StatusCode, message: &str| {
+        let res = ErrorResponse {
+            success: false,
+            got_it: false,
+            errors: vec![message.to_string()],
+        };
+        (status, Json(res)).into_response()
+    };
+    if !cache::is_valid_key(&cache_key) {
+        return invalid_cache_key();
+    }
+    let to = payload.email.trim();
+    if !email::is_valid_address(to) || to.len() > MAX_CONTACT_FIELD_LEN {
+        return error(StatusCode::BAD_REQUEST, "A valid email address is required");
+    }
+
+    let step = match state.cache.get(&cache_key).await {
+        Ok(files) => match files.get(Artifact::Step) {
+            Some(step) => step.clone(),
+            None => return cache_lookup_error(&cache_key, CacheError::NotFound),
+        },
+        Err(e) => return cache_lookup_error(&cache_key, e),
+    };
+    let Some(message) = email::model_email(to, &cache_key, step, &state.model_email) else {
+        return error(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "This model is too large to email. Download it instead.",
+        );
+    };
+
+    let attached = !message.attachments.is_empty();
+    match state.mailer.send(&message).await {
+        Ok(()) => {
+            tracing::info!("Emailed {} ({})", cache_key, if attached { "attached" } else { "linked" });
+            (StatusCode::OK, Json(EmailModelResponse { success: true, attached })).into_response()
+        }
+        Err(MailError::InvalidMessage(e)) => {
+            tracing::info!("Not emailing {}: {}", cache_key, e);
+            error(StatusCode::BAD_REQUEST, "A valid email address is required")
+        }
+        Err(e) => {
+            tracing::warn!("Failed to email {}: {}", cache_key, e);
+            error(StatusCode::BAD_GATEWAY, "Could not send the email. Please retry.")
+        }
+    }
+}
+
+/// Stream one cached file as the response body, so a large STEP file passes
+/// through without being held in memory whole.
+async fn stream_model(
+    state: &AppStateInner,
+    cache_key: &str,
+    artifact: Artifact,
+    content_type: &str,
+    disposition: String,
+) -> axum::response::Response {
+    let stream = match state.cache.stream(cache_key, artifact).await {
+        Ok(stream) => stream,
+        Err(e) => return cache_lookup_error(cache_key, e),
+    };
+    let mut response = (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, content_type.to_string()), (header::CONTENT_DISPOSITION, disposition)],
+        Body::from_stream(stream.body),
+    )
+        .into_response();
+    if let Some(length) = stream.length {
+        response.headers_mut().insert(header::CONTENT_LENGTH, length.into());
+    }
+    response
+}
+
+/// Look up a model before generating it. Any failure means generating afresh; a
+/// corrupt entry is overwritten when the new files are cached.
+async fn cached_for_generation(state: &AppStateInner, cache_key: &str) -> Option<CachedFiles> {
+    match state.cache.get(cache_key).await {
+        Ok(files) => {
+            record_audit(state, AuditEvent::new(AuditKind::CacheHit, cache_key));
+            Some(files)
+        }
+        Err(e @ CacheError::Corrupt { .. }) => {
+            tracing::warn!("Regenerating {}: {}", cache_key, e);
+            None
+        }
+        Err(_) => None,
+    }
+}
+
+/// Turn a failed model lookup into a 404 for a miss and a 500 for other failures.
+/// A corrupt entry counts as a miss, so the client generates the model again.
+fn cache_lookup_error(cache_key: &str, e: CacheError) -> axum::response::Response {
+    match e {
+        CacheError::NotFound | CacheError::Corrupt { .. } => {
+            if matches!(e, CacheError::Corrupt { .. }) {
+                tracing::warn!("Not serving {}: {}", cache_key, e);
+            }
+            let res = ErrorResponse {
+                success: false,
+                got_it: false,
+                errors: vec!["Model not found. Please generate the model first.".to_string()],
+            };
+            (StatusCode::NOT_FOUND, Json(res)).into_response()
+        }
+        e => cache_error_response(e),
+    }
+}
+
+/// List plate presets
+///
+/// Returns the standard plates users can start from, smallest first.
+#[utoipa::path(
+    get,
+    path = "/api/presets",
+    tag = "configs",
+    responses(
+        (status = 200, description = "Standard plate presets", body = [domain::presets::Preset])
+    )
+)]
+async fn list_presets() -> impl IntoResponse {
+    (StatusCode::OK, Json(domain::presets::PRESETS))
+}
+
+/// Longest name a configuration can be saved under.
+const MAX_CONFIG_NAME_LEN: usize = 100;
+
+/// Save a plate configuration
+///
+/// Stores the plate under a name so it can be listed and reloaded later. The plate
+/// is saved as given, without validation, so a work in progress can be kept.
+#[utoipa::path(
+    post,
+    path = "/api/configs",
+    tag = "configs",
+    request_body = SaveConfigRequest,
+    responses(
+        (status = 201, description = "Configuration saved", body = SavedConfig),
+        (status = 400, description = "Missing or overlong name", body = ErrorResponse),
+        (status = 500, description = "Configuration storage failed", body = ErrorResponse)
+    )
+)]
+async fn create_config(
+    State(state): State<AppState>,
+    Json(payload): Json<SaveConfigRequest>,
+) -> impl IntoResponse {
+    let name = payload.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_CONFIG_NAME_LEN {
+        let res = ErrorResponse {
+            success: false,
+            got_it: false,
+            errors: vec![format!("Name must be 1 to {} characters", MAX_CONFIG_NAME_LEN)],
+        };
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let config = SavedConfig {
+        id: Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        plate: payload.plate,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    match state.configs.insert(&config).await {
+        Ok(()) => (StatusCode::CREATED, Json(config)).into_response(),
+        Err(e) => config_error_response(e),
+    }
+}
+
+/// List saved plate configurations
+///
+/// Returns every saved configuration, most recently saved first.
+#[utoipa::path(
+    get,
+    path = "/api/configs",
+    tag = "configs",
+    responses(
+        (status = 200, description = "Saved configurations", body = [SavedConfig]),
+        (status = 500, description = "Configuration storage failed", body = ErrorResponse)
+    )
+)]
+async fn list_configs(State(state): State<AppState>) -> impl IntoResponse {
+    match state.configs.list().await {
+        Ok(configs) => (StatusCode::OK, Json(configs)).into_response(),
+        Err(e) => config_error_response(e),
+    }
+}
+
+/// List this browser's recent plates
+///
+/// Returns the last plates generated in this browser, newest first, so they can be
+/// reopened after a reload. Browsers are told apart by the `platerator_session`
+/// cookie set by `/api/generate` and `/api/jobs`; without it the list is empty.
+#[utoipa::path(
+    get,
+    path = "/api/session/recent",
+    tag = "configs",
+    responses(
+        (status = 200, description = "Recent plates, newest first", body = [RecentPlate])
+    )
+)]
+async fn list_recent_plates(session: Session) -> impl IntoResponse {
+    Json(recent::list(&session).await)
+}
+
+/// Get a saved plate configuration
+#[utoipa::path(
+    get,
+    path = "/api/configs/{id}",
+    tag = "configs",
+    params(
+        ("id" = String, Path, description = "Configuration ID")
+    ),
+    responses(
+        (status = 200, description = "Saved configuration", body = SavedConfig),
+        (status = 404, description = "Configuration not found", body = ErrorResponse),
+        (status = 500, description = "Configuration storage failed", body = ErrorResponse)
+    )
+)]
+async fn get_config(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
+    match state.configs.get(&id).await {
+        Ok(config) => (StatusCode::OK, Json(config)).into_response(),
+        Err(e) => config_error_response(e),
+    }
+}
+
+fn config_error_response(e: ConfigError) -> axum::response::Response {
+    let status = match e {
+        ConfigError::NotFound => StatusCode::NOT_FOUND,
+        ConfigError::StorageError(_) => {
+            tracing::error!("Configuration storage failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
     };
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec![e.to_string()],
+    };
+    (status, Json(res)).into_response()
+}
+
+/// Compare two plates
+///
+/// Lists the fields that differ between `before` and `after`, with the change in
+/// millimeters for each length. Either plate can come from a saved configuration or
+/// be entered directly; neither is validated.
+#[utoipa::path(
+    post,
+    path = "/api/compare",
+    tag = "configs",
+    request_body = ComparePlatesRequest,
+    responses(
+        (status = 200, description = "Fields that differ, in plate field order", body = PlateDiff)
+    )
+)]
+async fn compare_plates(Json(payload): Json<ComparePlatesRequest>) -> impl IntoResponse {
+    (StatusCode::OK, Json(payload.before.diff(&payload.after)))
+}
+
+/// Request manufacturing quotes
+///
+/// Submits the generated STEP file for a plate configuration, together with its
+/// material and the requested quantity, to each configured third-party
+/// manufacturing service and returns their prices, along with our own price
+/// estimate, lead-time estimate, and ship date. The model must already have been generated (and cached)
+/// via the generate endpoint.
+#[utoipa::path(
+    post,
+    path = "/api/quote",
+    tag = "quote",
+    request_body = QuoteRequestBody,
+    responses(
+        (status = 200, description = "Quotes collected", body = QuoteResponse),
+        (status = 400, description = "Invalid quantity", body = ErrorResponse),
+        (status = 404, description = "Model has not been generated yet", body = ErrorResponse),
+        (status = 503, description = "Model storage is temporarily unavailable", body = ErrorResponse)
+    )
+)]
+async fn request_quote(
+    State(state): State<AppState>,
+    Json(payload): Json<QuoteRequestBody>,
+) -> impl IntoResponse {
+    let Ok(quantity) = Count::new(payload.quantity) else {
+        let res = ErrorResponse {
+            success: false,
+            got_it: false,
+            errors: vec!["Quantity must be at least 1".to_string()],
+        };
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    };
+
+    let cache_key = payload.plate.cache_key();
+    let cached = state.cache.get(&cache_key).await;
+    let step_data = match cached.and_then(|cached| cached.get(Artifact::Step).cloned().ok_or(CacheError::NotFound)) {
+        Ok(step_data) => step_data,
+        Err(e) if e.is_retryable() => {
+            tracing::warn!("Cache unavailable while quoting {}: {}", cache_key, e);
+            let res = ErrorResponse {
+                success: false,
+                got_it: false,
+                errors: vec!["Model storage is temporarily unavailable. Please retry.".to_string()],
+            };
+            return (StatusCode::SERVICE_UNAVAILABLE, Json(res)).into_response();
+        }
+        Err(e) => {
+            tracing::info!("Quote requested for uncached key {}: {}", cache_key, e);
+            let res = ErrorResponse {
+                success: false,
+                got_it: false,
+                errors: vec!["Model not found. Please generate the model first.".to_string()],
+            };
+            return (StatusCode::NOT_FOUND, Json(res)).into_response();
+        }
+    };
+
+    let request = QuoteRequest {
+        step_data,
+        material: payload.plate.material,
+        quantity,
+    };
+    let external_quotes = quote::collect_external_quotes(&state.quote_providers, &request).await;
+    let estimate = state.prices.estimate(&payload.plate, quantity);
+    let lead_time = state.lead_times.estimate(
+        &payload.plate,
+        quantity,
+        payload.finish,
+        chrono::Utc::now().date_naive(),
+    );
+
+    let res = QuoteResponse {
+        success: true,
+        quantity,
+        estimate,
+        external_quotes,
+        lead_time,
+        mass_properties: payload.plate.mass_properties(),
+    };
+    (StatusCode::OK, Json(res)).into_response()
+}
+
+/// Longest customer name, email, or phone number an order accepts.
+const MAX_CONTACT_FIELD_LEN: usize = 200;
+
+/// Place an order
+///
+/// Records an order for a plate configuration with the customer's contact details,
+/// priced with our own estimate, and emails the customer a confirmation with their
+/// order number. New orders are `received`; staff move them on to `quoted` and
+/// `confirmed`.
+#[utoipa::path(
+    post,
+    path = "/api/orders",
+    tag = "orders",
+    request_body = PlaceOrderRequest,
+    responses(
+        (status = 201, description = "Order received", body = Order),
+        (status = 400, description = "Invalid plate, quantity, or contact details", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid, or revoked API key", body = ErrorResponse),
+        (status = 429, description = "Too many requests from this client", body = ErrorResponse),
+        (status = 500, description = "Order storage failed", body = ErrorResponse)
+    )
+)]
+async fn create_order(
+    State(state): State<AppState>,
+    ApiKeyAuth(api_key): ApiKeyAuth,
+    Json(payload): Json<PlaceOrderRequest>,
+) -> impl IntoResponse {
+    let mut errors = Vec::new();
+    if let Err(errs) = validation::validate_with_limits(&payload.plate, &state.limits) {
+        errors.extend(errs.iter().map(|e| e.to_string()));
+    }
+    let quantity = Count::new(payload.quantity);
+    if quantity.is_err() {
+        errors.push("Quantity must be at least 1".to_string());
+    }
+    let name = payload.customer_name.trim();
+    if name.is_empty() || name.chars().count() > MAX_CONTACT_FIELD_LEN {
+        errors.push(format!("Name must be 1 to {} characters", MAX_CONTACT_FIELD_LEN));
+    }
+    let email = payload.customer_email.trim();
+    if !email::is_valid_address(email) || email.len() > MAX_CONTACT_FIELD_LEN {
+        errors.push("A valid email address is required".to_string());
+    }
+    let phone = payload.customer_phone.as_deref().map(str::trim).filter(|p| !p.is_empty());
+    if phone.is_some_and(|p| p.chars().count() > MAX_CONTACT_FIELD_LEN) {
+        errors.push(format!("Phone number must be at most {} characters", MAX_CONTACT_FIELD_LEN));
+    }
+    let (Ok(quantity), true) = (quantity, errors.is_empty()) else {
+        let res = ErrorResponse {
+            success: false,
+            got_it: false,
+            errors,
+        };
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    };
+
+    let estimate = state.prices.estimate(&payload.plate, quantity);
+    let order = Order {
+        id: Uuid::new_v4().to_string(),
+        order_number: String::new(),
+        status: OrderStatus::Received,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        part_number: payload.plate.cache_key(),
+        material: payload.plate.material,
+        plate: Some(payload.plate),
+        quantity,
+        unit_price_cents: estimate.unit_price_cents,
+        total_price_cents: estimate.total_price_cents,
+        customer_name: name.to_string(),
+        customer_email: email.to_string(),
+        customer_phone: phone.map(str::to_string),
+    };
+    let order = match state.orders.create(order).await {
+        Ok(order) => order,
+        Err(e) => return order_error_response(e),
+    };
+    record_audit(
+        &state,
+        AuditEvent::new(AuditKind::Order, &order.order_number).with_detail(format!(
+            "{} × {} for {}, ${}.{:02}",
+            order.quantity,
+            order.part_number,
+            order.customer_name,
+            order.total_price_cents / 100,
+            order.total_price_cents % 100
+        )),
+    );
+    match &api_key {
+        Some(key) => tracing::info!("Order {} received for {} from API key {}", order.order_number, order.part_number, key.name),
+        None => tracing::info!("Order {} received for {}", order.order_number, order.part_number),
+    }
+
+    let mailer = state.mailer.clone();
+    let confirmation = email::order_confirmation(&order);
+    state.supervisor.spawn_task("order-confirmation", async move {
+        if let Err(e) = mailer.send(&confirmation).await {
+            tracing::warn!("Failed to send confirmation to {}: {}", confirmation.to, e);
+        }
+    });
+    notify::notify_all(
+        &state.supervisor,
+        &state.notifiers,
+        Notification::OrderPlaced {
+            order_id: order.order_number.clone(),
+            quantity: order.quantity,
+            total_price_cents: order.total_price_cents,
+        },
+    );
+
+    (StatusCode::CREATED, Json(order)).into_response()
+}
+
+/// Advance an order's status
+///
+/// Moves an order one step along `received` → `quoted` → `confirmed`. Skipping a
+/// step or going backwards is a 409.
+#[utoipa::path(
+    post,
+    path = "/api/admin/orders/{order_number}/status",
+    tag = "admin",
+    params(
+        ("order_number" = String, Path, description = "Order number, e.g. PL-000042")
+    ),
+    request_body = OrderStatusRequest,
+    responses(
+        (status = 200, description = "Status updated", body = Order),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "Order not found", body = ErrorResponse),
+        (status = 409, description = "Not the next status for this order", body = ErrorResponse),
+        (status = 500, description = "Order storage failed", body = ErrorResponse)
+    )
+)]
+async fn update_order_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(order_number): Path<String>,
+    Json(payload): Json<OrderStatusRequest>,
+) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+
+    match state.orders.set_status(&order_number, payload.status).await {
+        Ok(order) => {
+            tracing::info!("Order {} is now {}", order.order_number, order.status.as_str());
+            if order.status == OrderStatus::Confirmed {
+                state.webhooks.dispatch(
+                    &state.supervisor,
+                    WebhookEvent::OrderPaid,
+                    None,
+                    serde_json::json!({ "order": order }),
+                );
+            }
+            (StatusCode::OK, Json(order)).into_response()
+        }
+        Err(e) => order_error_response(e),
+    }
+}
+
+fn order_error_response(e: OrderError) -> axum::response::Response {
+    let status = match e {
+        OrderError::NotFound => StatusCode::NOT_FOUND,
+        OrderError::InvalidTransition { .. } => StatusCode::CONFLICT,
+        OrderError::StorageError(_) => {
+            tracing::error!("Order storage failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec![e.to_string()],
+    };
+    (status, Json(res)).into_response()
+}
+
+/// Audit events shown on the dashboard.
+const DASHBOARD_EVENTS: usize = 200;
+
+/// The operator dashboard: recent generations, the cache hit rate, failed Zoo calls
+/// with their error text, and orders. Browsers sign in with basic auth, using the
+/// admin token as the password. Not part of the OpenAPI spec, since it serves HTML.
+async fn admin_dashboard(State(state): State<AppState>, headers: HeaderMap) -> axum::response::Response {
+    let Some(token) = state.admin_token.as_deref() else {
+        return (StatusCode::FORBIDDEN, "The admin dashboard is disabled; set ADMIN_TOKEN").into_response();
+    };
+    if !dashboard::has_password(&headers, token) {
+        let challenge = format!("Basic realm=\"{}\", charset=\"UTF-8\"", dashboard::REALM);
+        return (StatusCode::UNAUTHORIZED, [(header::WWW_AUTHENTICATE, challenge)], "Admin token required").into_response();
+    }
+
+    let events = match state.audit.recent(DASHBOARD_EVENTS).await {
+        Ok(events) => events,
+        Err(e) => {
+            tracing::error!("Failed to read the audit log: {}", e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
+        }
+    };
+    let html = dashboard::render(&state.cache_metrics.snapshot(), &events);
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/html; charset=utf-8"), (header::CACHE_CONTROL, "no-store")],
+        html,
+    )
+        .into_response()
+}
+
+/// Check the request's bearer token against the configured admin token.
+fn is_admin(state: &AppStateInner, headers: &HeaderMap) -> bool {
+    let Some(expected) = state.admin_token.as_deref() else {
+        return false;
+    };
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|token| auth::secret_matches(token, expected))
+}
+
+fn forbidden() -> axum::response::Response {
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec!["Admin token required".to_string()],
+    };
+    (StatusCode::FORBIDDEN, Json(res)).into_response()
+}
+
+/// Export orders for ERP import
+///
+/// Returns orders as CSV or JSON using the deployment's configured column mapping
+/// (`ORDER_EXPORT_COLUMNS`). Pass `since` to export only orders placed at or after
+/// a timestamp, so imports can be run in batches.
+#[utoipa::path(
+    get,
+    path = "/api/admin/orders/export",
+    tag = "admin",
+    params(OrderExportQuery),
+    responses(
+        (status = 200, description = "Orders exported as CSV, or a JSON array with `format=json`", content_type = "text/csv"),
+        (status = 400, description = "Invalid `since` timestamp", body = ErrorResponse),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse)
+    )
+)]
+async fn export_orders(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<OrderExportQuery>,
+) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+
+    let since = match query.since.as_deref().map(chrono::DateTime::parse_from_rfc3339) {
+        None => None,
+        Some(Ok(since)) => Some(since),
+        Some(Err(e)) => {
+            let res = ErrorResponse {
+                success: false,
+                got_it: false,
+                errors: vec![format!("Invalid `since` timestamp: {}", e)],
+            };
+            return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+        }
+    };
+
+    let orders = match state.orders.list().await {
+        Ok(orders) => orders,
+        Err(e) => {
+            tracing::error!("Failed to list orders: {}", e);
+            let res = ErrorResponse {
+                success: false,
+                got_it: false,
+                errors: vec!["Failed to load orders".to_string()],
+            };
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(res)).into_response();
+        }
+    };
+
+    let orders: Vec<Order> = orders
+        .into_iter()
+        .filter(|order| match since {
+            Some(since) => chrono::DateTime::parse_from_rfc3339(&order.created_at)
+                .is_ok_and(|created| created >= since),
+            None => true,
+        })
+        .collect();
+
+    match query.format {
+        ExportFormat::Csv => {
+            let headers = [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (header::CONTENT_DISPOSITION, "attachment; filename=\"orders.csv\""),
+            ];
+            (StatusCode::OK, headers, state.order_export_columns.to_csv(&orders)).into_response()
+        }
+        ExportFormat::Json => {
+            (StatusCode::OK, Json(state.order_export_columns.to_json(&orders))).into_response()
+        }
+    }
+}
+
+/// List background workers
+///
+/// Returns the state of each supervised background worker, including how often it
+/// has been restarted and why it last failed.
+#[utoipa::path(
+    get,
+    path = "/api/admin/workers",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Supervised workers", body = [WorkerHealth]),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse)
+    )
+)]
+async fn list_workers(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+    (StatusCode::OK, Json(state.supervisor.health())).into_response()
+}
+
+/// Clear the model cache
+///
+/// Removes every cached model. Models are regenerated on their next request.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/cache",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Cache cleared", body = ClearCacheResponse),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 500, description = "Failed to remove cache entries", body = ErrorResponse)
+    )
+)]
+async fn clear_cache(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+    match state.cache.clear().await {
+        Ok(removed) => {
+            tracing::info!("Cleared {} cache entries", removed);
+            (StatusCode::OK, Json(ClearCacheResponse { success: true, removed })).into_response()
+        }
+        Err(e) => cache_error_response(e),
+    }
+}
+
+/// Delete a cached model
+///
+/// Removes one cache entry so the model is regenerated on its next request.
+/// Deleting a key that isn't cached succeeds.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/cache/{cache_key}",
+    tag = "admin",
+    params(
+        ("cache_key" = String, Path, description = "Cache key, e.g. plate-1a2b3c4d5e6f7a8b")
+    ),
+    responses(
+        (status = 204, description = "Entry removed"),
+        (status = 400, description = "Not a cache key", body = ErrorResponse),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 500, description = "Failed to remove the entry", body = ErrorResponse)
+    )
+)]
+async fn delete_cache_entry(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(cache_key): Path<String>,
+) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+    if !cache::is_valid_key(&cache_key) {
+        return invalid_cache_key();
+    }
+    match state.cache.delete(&cache_key).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => cache_error_response(e),
+    }
+}
+
+/// 400 for a path segment that can't be a cache key, returned before the cache sees it.
+fn invalid_cache_key() -> axum::response::Response {
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec!["Invalid cache key".to_string()],
+    };
+    (StatusCode::BAD_REQUEST, Json(res)).into_response()
+}
+
+fn cache_error_response(e: CacheError) -> axum::response::Response {
+    tracing::error!("Cache operation failed: {}", e);
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec![e.to_string()],
+    };
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(res)).into_response()
+}
+
+/// List webhook endpoints
+///
+/// Returns every registered webhook endpoint. Secrets are never included.
+#[utoipa::path(
+    get,
+    path = "/api/admin/webhooks",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Registered endpoints", body = [WebhookEndpoint]),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse)
+    )
+)]
+async fn list_webhooks(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+    (StatusCode::OK, Json(state.webhooks.registry.list())).into_response()
+}
+
+/// Register a webhook endpoint
+///
+/// Subscribes a URL to one or more events. Deliveries are signed with HMAC-SHA256
+/// using the endpoint's secret (generated if not supplied); the secret is only
+/// returned in this response.
+#[utoipa::path(
+    post,
+    path = "/api/admin/webhooks",
+    tag = "admin",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Endpoint registered", body = WebhookEndpoint),
+        (status = 400, description = "Invalid URL or empty event list", body = ErrorResponse),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse)
+    )
+)]
+async fn create_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+    register_webhook(&state, payload, None).await
+}
+
+/// Validate and register an endpoint for the API key `owner`, or for the operator.
+/// API clients' endpoints must also be https URLs on public addresses.
+async fn register_webhook(state: &AppState, payload: CreateWebhookRequest, owner: Option<String>) -> axum::response::Response {
+    let mut errors = Vec::new();
+    if !(payload.url.starts_with("https://") || payload.url.starts_with("http://")) {
+        errors.push("Webhook URL must start with http:// or https://".to_string());
+    } else if let Err(e) = state.webhooks.check_target(&payload.url, owner.as_deref()).await {
+        errors.push(e.to_string());
+    }
+    if payload.events.is_empty() {
+        errors.push("At least one event is required".to_string());
+    }
+    if !errors.is_empty() {
+        let res = ErrorResponse { success: false, got_it: false, errors };
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    }
+
+    let endpoint = state
+        .webhooks
+        .registry
+        .register(payload.url, payload.events, payload.secret, owner);
+    (StatusCode::CREATED, Json(endpoint)).into_response()
+}
+
+fn webhook_not_found() -> axum::response::Response {
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec!["Webhook endpoint not found".to_string()],
+    };
+    (StatusCode::NOT_FOUND, Json(res)).into_response()
+}
+
+/// Delete a webhook endpoint
+#[utoipa::path(
+    delete,
+    path = "/api/admin/webhooks/{id}",
+    tag = "admin",
+    params(
+        ("id" = String, Path, description = "Webhook endpoint ID")
+    ),
+    responses(
+        (status = 204, description = "Endpoint removed"),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "No endpoint with that ID", body = ErrorResponse)
+    )
+)]
+async fn delete_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+    if state.webhooks.registry.remove(&id) {
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        webhook_not_found()
+    }
+}
+
+/// List a webhook endpoint's deliveries
+///
+/// Returns recent delivery attempts to any endpoint, newest first, one entry per
+/// attempt. The log keeps the last 1000 attempts across all endpoints.
+#[utoipa::path(
+    get,
+    path = "/api/admin/webhooks/{id}/deliveries",
+    tag = "admin",
+    params(
+        ("id" = String, Path, description = "Webhook endpoint ID")
+    ),
+    responses(
+        (status = 200, description = "Delivery attempts, newest first", body = [WebhookDelivery]),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "No endpoint with that ID", body = ErrorResponse)
+    )
+)]
+async fn webhook_deliveries(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+    if !state.webhooks.registry.contains(&id, None) {
+        return webhook_not_found();
+    }
+    (StatusCode::OK, Json(state.webhooks.deliveries(&id))).into_response()
+}
+
+/// Client webhook routes need an API key even when keys are optional elsewhere,
+/// since endpoints belong to a key.
+fn webhook_key_required() -> axum::response::Response {
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec!["An API key is required in the X-API-Key header".to_string()],
+    };
+    (StatusCode::UNAUTHORIZED, Json(res)).into_response()
+}
+
+/// List your webhook endpoints
+///
+/// Returns the endpoints registered with this API key. Secrets are never included.
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Endpoints registered with this key", body = [WebhookEndpoint]),
+        (status = 401, description = "Missing, invalid, or revoked API key", body = ErrorResponse)
+    )
+)]
+async fn list_client_webhooks(State(state): State<AppState>, ApiKeyAuth(api_key): ApiKeyAuth) -> impl IntoResponse {
+    match api_key {
+        Some(key) => (StatusCode::OK, Json(state.webhooks.registry.list_owned(&key.id))).into_response(),
+        None => webhook_key_required(),
+    }
+}
+
+/// Register a webhook endpoint for your jobs
+///
+/// The endpoint belongs to this API key and receives events for work started with
+/// it: `job.completed` when one of its generation jobs (from `/api/jobs`,
+/// `/api/plates/batch`, or `/api/sweep`) succeeds or fails. Deliveries are signed
+/// like operator webhooks; the secret is only returned in this response. The URL
+/// must be https, and its host must not resolve to a loopback, private, or
+/// link-local address.
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Endpoint registered", body = WebhookEndpoint),
+        (status = 400, description = "Invalid URL or empty event list", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid, or revoked API key", body = ErrorResponse)
+    )
+)]
+async fn create_client_webhook(
+    State(state): State<AppState>,
+    ApiKeyAuth(api_key): ApiKeyAuth,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> impl IntoResponse {
+    match api_key {
+        Some(key) => register_webhook(&state, payload, Some(key.id)).await,
+        None => webhook_key_required(),
+    }
+}
 
-    match contents {
-        Ok(contents) => {
-            let headers = [
-                (header::CONTENT_TYPE, "application/STEP"),
-                (
-                    header::CONTENT_DISPOSITION,
-                    "attachment; filename=\"actuator_plate.step\"",
-                ),
-            ];
-            (StatusCode::OK, headers, contents).into_response()
-        }
-        Err(e) => {
-            tracing::error!("Failed to read STEP file: {}", e);
-            let res = ErrorResponse {
-                success: false,
-                got_it: false,
-                errors: vec!["STEP file not found. Please generate the model first.".to_string()],
-            };
-            (StatusCode::NOT_FOUND, Json(res)).into_response()
-        }
+/// Delete one of your webhook endpoints
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        ("id" = String, Path, description = "Webhook endpoint ID")
+    ),
+    responses(
+        (status = 204, description = "Endpoint removed"),
+        (status = 401, description = "Missing, invalid, or revoked API key", body = ErrorResponse),
+        (status = 404, description = "This key has no endpoint with that ID", body = ErrorResponse)
+    )
+)]
+async fn delete_client_webhook(
+    State(state): State<AppState>,
+    ApiKeyAuth(api_key): ApiKeyAuth,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Some(owner) = api_key.map(|key| key.id) else {
+        return webhook_key_required();
+    };
+    if state.webhooks.registry.remove_owned(&id, &owner) {
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        webhook_not_found()
     }
 }
 
-/// Download glTF file
+/// List deliveries to one of your webhook endpoints
 ///
-/// Downloads the generated glTF model file for a given session ID.
-/// The session ID is obtained from the generate endpoint response.
+/// Returns recent delivery attempts, newest first, with the status code the
+/// endpoint answered or the reason it couldn't be reached.
 #[utoipa::path(
     get,
-    path = "/api/download/gltf/{session_id}",
-    tag = "generation",
+    path = "/api/webhooks/{id}/deliveries",
+    tag = "webhooks",
     params(
-        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
+        ("id" = String, Path, description = "Webhook endpoint ID")
     ),
     responses(
-        (status = 200, description = "glTF file downloaded successfully", content_type = "model/gltf+json"),
-        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+        (status = 200, description = "Delivery attempts, newest first", body = [WebhookDelivery]),
+        (status = 401, description = "Missing, invalid, or revoked API key", body = ErrorResponse),
+        (status = 404, description = "This key has no endpoint with that ID", body = ErrorResponse)
     )
 )]
-async fn download_gltf(
+async fn client_webhook_deliveries(
     State(state): State<AppState>,
-    Path(session_id): Path<String>,
+    ApiKeyAuth(api_key): ApiKeyAuth,
+    Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let sessions = state.sessions.read().await;
+    let Some(owner) = api_key.map(|key| key.id) else {
+        return webhook_key_required();
+    };
+    if !state.webhooks.registry.contains(&id, Some(&owner)) {
+        return webhook_not_found();
+    }
+    (StatusCode::OK, Json(state.webhooks.deliveries(&id))).into_response()
+}
+
+/// List API keys
+///
+/// Returns every issued API key, including revoked ones. Keys are stored hashed, so
+/// only their prefix is shown.
+#[utoipa::path(
+    get,
+    path = "/api/admin/api-keys",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Issued keys", body = [ApiKey]),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 500, description = "Key storage failed", body = ErrorResponse)
+    )
+)]
+async fn list_api_keys(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+    match state.api_keys.list().await {
+        Ok(keys) => (StatusCode::OK, Json(keys)).into_response(),
+        Err(e) => api_key_error_response(e),
+    }
+}
 
-    let Some(session_data) = sessions.get(&session_id) else {
+/// Issue an API key
+///
+/// Creates a key for a programmatic client. The key is only returned in this
+/// response; clients send it in the `X-API-Key` header.
+#[utoipa::path(
+    post,
+    path = "/api/admin/api-keys",
+    tag = "admin",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "Key issued", body = ApiKey),
+        (status = 400, description = "Missing name", body = ErrorResponse),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 500, description = "Key storage failed", body = ErrorResponse)
+    )
+)]
+async fn create_api_key(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+    let name = payload.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_CONTACT_FIELD_LEN {
         let res = ErrorResponse {
             success: false,
             got_it: false,
-            errors: vec!["Session not found. Please generate the model first.".to_string()],
+            errors: vec![format!("Name must be 1 to {} characters", MAX_CONTACT_FIELD_LEN)],
         };
-        return (StatusCode::NOT_FOUND, Json(res)).into_response();
-    };
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    }
 
-    let contents = match session_data {
-        SessionData::Cached(cached) => Ok(cached.gltf_data.clone()),
-        SessionData::Generated(result) => tokio::fs::read(&result.gltf_file).await,
+    let (key, hash) = auth::new_key(name);
+    if let Err(e) = state.api_keys.insert(&key, &hash).await {
+        return api_key_error_response(e);
+    }
+    tracing::info!("Issued API key {} ({}) to {}", key.id, key.prefix, key.name);
+    (StatusCode::CREATED, Json(key)).into_response()
+}
+
+/// Revoke an API key
+///
+/// Requests made with a revoked key are rejected with 401. Revoked keys stay in the
+/// list so their use can still be traced.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/api-keys/{id}",
+    tag = "admin",
+    params(
+        ("id" = String, Path, description = "API key ID")
+    ),
+    responses(
+        (status = 204, description = "Key revoked"),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "No key with that ID", body = ErrorResponse),
+        (status = 500, description = "Key storage failed", body = ErrorResponse)
+    )
+)]
+async fn revoke_api_key(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+    match state.api_keys.revoke(&id).await {
+        Ok(key) => {
+            tracing::info!("Revoked API key {} ({})", key.id, key.name);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => api_key_error_response(e),
+    }
+}
+
+fn api_key_error_response(e: ApiKeyError) -> axum::response::Response {
+    let status = match e {
+        ApiKeyError::NotFound => StatusCode::NOT_FOUND,
+        ApiKeyError::StorageError(_) => {
+            tracing::error!("API key storage failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    let res = ErrorResponse {
+        success: false,
+        got_it: false,
+        errors: vec![e.to_string()],
     };
+    (status, Json(res)).into_response()
+}
 
-    match contents {
-        Ok(contents) => {
+/// Download a backup archive
+///
+/// Returns a tar archive of every order and the list of cache keys. With
+/// `artifacts=true` every cached file is included too, so the archive can fully
+/// repopulate the cache of a fresh deployment. The archive is streamed as the cache
+/// is read. Saved configurations, API keys, the audit log, and the Zoo quota counts
+/// aren't included; back up their files directly.
+#[utoipa::path(
+    get,
+    path = "/api/admin/backup",
+    tag = "admin",
+    params(BackupQuery),
+    responses(
+        (status = 200, description = "Backup archive", content_type = "application/x-tar"),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 500, description = "Failed to read orders or cache", body = ErrorResponse)
+    )
+)]
+async fn backup(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<BackupQuery>,
+) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
+
+    match create_backup(state.cache.clone(), state.orders.as_ref(), query.artifacts).await {
+        Ok(archive) => {
+            let filename = format!(
+                "attachment; filename=\"platerator-backup-{}.tar\"",
+                chrono::Utc::now().format("%Y%m%dT%H%M%SZ")
+            );
             let headers = [
-                (header::CONTENT_TYPE, "model/gltf+json"),
-                (
-                    header::CONTENT_DISPOSITION,
-                    "inline; filename=\"actuator_plate.gltf\"",
-                ),
+                (header::CONTENT_TYPE, "application/x-tar".to_string()),
+                (header::CONTENT_DISPOSITION, filename),
             ];
-            (StatusCode::OK, headers, contents).into_response()
+            (StatusCode::OK, headers, Body::from_stream(archive)).into_response()
         }
         Err(e) => {
-            tracing::error!("Failed to read glTF file: {}", e);
+            tracing::error!("Failed to create backup: {}", e);
             let res = ErrorResponse {
                 success: false,
                 got_it: false,
-                errors: vec!["glTF file not found. Please generate the model first.".to_string()],
+                errors: vec![e.to_string()],
             };
-            (StatusCode::NOT_FOUND, Json(res)).into_response()
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(res)).into_response()
         }
     }
 }
 
-/// Download STL file
+/// Restore a backup archive
 ///
-/// Downloads the generated STL model file for a given session ID.
-/// The session ID is obtained from the generate endpoint response.
+/// Accepts an archive from `/api/admin/backup` as the raw request body, read as it
+/// arrives. Orders are inserted and included artifacts are written to the cache;
+/// cache keys without artifacts are regenerated on demand.
 #[utoipa::path(
-    get,
-    path = "/api/download/stl/{session_id}",
-    tag = "generation",
-    params(
-        ("session_id" = String, Path, description = "Session ID from the generate endpoint")
-    ),
+    post,
+    path = "/api/admin/restore",
+    tag = "admin",
+    request_body(content = Vec<u8>, content_type = "application/x-tar"),
     responses(
-        (status = 200, description = "STL file downloaded successfully", content_type = "model/stl"),
-        (status = 404, description = "Session not found or file not available", body = ErrorResponse)
+        (status = 200, description = "Backup restored", body = RestoreResponse),
+        (status = 400, description = "Archive is invalid or from an unsupported version", body = ErrorResponse),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 500, description = "Failed to write orders or cache", body = ErrorResponse)
     )
 )]
-async fn download_stl(
-    State(state): State<AppState>,
-    Path(session_id): Path<String>,
-) -> impl IntoResponse {
-    let sessions = state.sessions.read().await;
-
-    let Some(session_data) = sessions.get(&session_id) else {
-        let res = ErrorResponse {
-            success: false,
-            got_it: false,
-            errors: vec!["Session not found. Please generate the model first.".to_string()],
-        };
-        return (StatusCode::NOT_FOUND, Json(res)).into_response();
-    };
-
-    let contents = match session_data {
-        SessionData::Cached(cached) => Ok(cached.stl_data.clone()),
-        SessionData::Generated(result) => tokio::fs::read(&result.stl_file).await,
-    };
+async fn restore(State(state): State<AppState>, headers: HeaderMap, body: Body) -> impl IntoResponse {
+    if !is_admin(&state, &headers) {
+        return forbidden();
+    }
 
-    match contents {
-        Ok(contents) => {
-            let headers = [
-                (header::CONTENT_TYPE, "model/stl"),
-                (
-                    header::CONTENT_DISPOSITION,
-                    "attachment; filename=\"actuator_plate.stl\"",
-                ),
-            ];
-            (StatusCode::OK, headers, contents).into_response()
+    let archive = tokio_util::io::StreamReader::new(body.into_data_stream().map_err(std::io::Error::other));
+    match restore_backup(state.cache.as_ref(), state.orders.as_ref(), archive).await {
+        Ok(restored) => {
+            let res = RestoreResponse { success: true, restored };
+            (StatusCode::OK, Json(res)).into_response()
         }
         Err(e) => {
-            tracing::error!("Failed to read STL file: {}", e);
+            let status = match e {
+                BackupError::Archive(_) | BackupError::UnsupportedVersion(_) => StatusCode::BAD_REQUEST,
+                BackupError::Cache(_) | BackupError::Orders(_) => {
+                    tracing::error!("Failed to restore backup: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+            };
             let res = ErrorResponse {
                 success: false,
                 got_it: false,
-                errors: vec!["STL file not found. Please generate the model first.".to_string()],
+                errors: vec![e.to_string()],
             };
-            (StatusCode::NOT_FOUND, Json(res)).into_response()
+            (status, Json(res)).into_response()
         }
     }
 }
 
+/// Response from clearing the cache
+#[derive(Serialize, ToSchema)]
+struct ClearCacheResponse {
+    /// Always true when the cache was cleared
+    success: bool,
+    /// Number of cached models removed
+    removed: usize,
+}
+
 /// Health check response
 #[derive(Serialize, ToSchema)]
 struct OkResponse {
@@ -634,8 +3461,16 @@ struct VersionResponse {
     git_hash: String,
 }
 
-/// Successful model generation response
+/// Feature flags response
 #[derive(Serialize, ToSchema)]
+struct FlagsResponse {
+    /// Whether each flag is on, by name, e.g. `{"async_jobs": true}`
+    #[schema(value_type = HashMap<String, bool>)]
+    flags: std::collections::BTreeMap<&'static str, bool>,
+}
+
+/// Successful model generation response
+#[derive(Clone, Serialize, ToSchema)]
 struct GenerateSuccessResponse {
     /// Always true for successful generation
     success: bool,
@@ -647,19 +3482,88 @@ struct GenerateSuccessResponse {
     gltf_url: String,
     /// URL to download the STL file
     stl_url: String,
+    /// URL to download the mesh as OBJ
+    obj_url: String,
+    /// URL to download the mesh as 3MF, for slicers
+    three_mf_url: String,
+    /// URL to download the 2D drawing as SVG
+    svg_url: String,
+    /// URL to download the 2D drawing as PDF
+    pdf_url: String,
+    /// URL to download the flat cut profile as DXF
+    dxf_url: String,
+    /// URL to download the KCL source the model was built from
+    kcl_url: String,
     /// Session ID for retrieving the generated files
     session_id: String,
+    /// Cache key of the plate; download any format later from `/api/models/{cache_key}/download`
+    cache_key: String,
+    /// Volume, mass, and center of mass of the finished plate
+    mass_properties: MassProperties,
+    /// Problems that didn't stop generation, such as a thin plate
+    warnings: Vec<ErrorDetail>,
 }
 
 /// A single validation error with the message and the form fields it implicates.
-#[derive(Serialize, ToSchema)]
+#[derive(Clone, Serialize, ToSchema)]
 struct ErrorDetail {
+    /// Stable machine-readable error code (e.g. "BOLT_SPACING_TOO_SMALL")
+    code: String,
     /// Human-readable error message
     message: String,
     /// Form field name(s) implicated by this error (e.g. ["plateThickness", "expectedForce"])
     fields: Vec<String>,
 }
 
+impl ErrorDetail {
+    /// The zoo CLI failed; no field is to blame.
+    fn generation_failed(message: String) -> Self {
+        Self {
+            code: "GENERATION_FAILED".to_string(),
+            message,
+            fields: vec![],
+        }
+    }
+}
+
+impl ErrorDetail {
+    /// The Zoo quota is spent, so the engine wasn't called.
+    fn quota_exhausted(e: &QuotaExhausted) -> Self {
+        Self {
+            code: "QUOTA_EXHAUSTED".to_string(),
+            message: e.to_string(),
+            fields: vec![],
+        }
+    }
+
+    /// A validation error with its message in `locale`.
+    fn localized(e: &PlateValidationError, locale: Locale) -> Self {
+        Self {
+            code: e.code().to_string(),
+            message: e.message(locale).to_string(),
+            fields: e.related_fields().iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// A warning, in `locale`.
+    fn warning(w: &PlateValidationWarning, locale: Locale) -> Self {
+        Self {
+            code: w.code().to_string(),
+            message: w.message(locale).to_string(),
+            fields: w.related_fields().iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+/// The locale validation messages are written in, from the `Accept-Language`
+/// header. English when it's missing or names no supported language.
+fn locale(headers: &HeaderMap) -> Locale {
+    headers
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .map_or(Locale::En, Locale::from_accept_language)
+}
+
 /// Error response for generation failures
 #[derive(Serialize, ToSchema)]
 struct GenerateErrorResponse {
@@ -691,6 +3595,40 @@ struct ValidationSuccessResponse {
     message: String,
     /// Stress analysis summary (included when plate passes validation)
     stress_summary: StressSummary,
+    /// Result of the structural check against `load_n`, when one was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    load_check: Option<LoadCheckSummary>,
+    /// Problems that don't make the plate invalid, such as a thin plate or failing the load check
+    warnings: Vec<ErrorDetail>,
+}
+
+/// Optional load to check the plate against
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct LoadQuery {
+    /// Total load the plate must carry, in Newtons
+    load_n: Option<u32>,
+    /// Safety factor applied to material strengths (default 2.0, minimum 1.0)
+    safety_factor: Option<f32>,
+}
+
+/// Bending and shear check of the plate against a customer-supplied load.
+#[derive(Serialize, ToSchema)]
+struct LoadCheckSummary {
+    /// Load checked, in Newtons
+    load_n: u32,
+    /// Safety factor applied
+    safety_factor: f32,
+    /// Bending stress / allowable (> 1.0 fails)
+    bending_utilization: f32,
+    /// Shear stress / allowable (> 1.0 fails)
+    shear_utilization: f32,
+    /// Thinnest plate (mm) that passes for this load and material
+    required_thickness_mm: u16,
+    /// Whether the plate passes both checks
+    passes: bool,
+    /// One-line summary for design reports
+    report_line: String,
 }
 
 /// Stress analysis summary showing utilization ratios and recommended minimums.
@@ -708,6 +3646,48 @@ struct StressSummary {
     minimum_thickness_mm: u16,
 }
 
+/// Bolt analysis request body
+#[derive(Deserialize, ToSchema)]
+struct AnalysisRequest {
+    /// Plate to check
+    plate: ActuatorPlate,
+    /// Total load on the plate, in Newtons
+    #[schema(example = 3000)]
+    load_n: u32,
+}
+
+/// Stresses at the mounting bolts under the requested load.
+#[derive(Serialize, ToSchema)]
+struct AnalysisResponse {
+    /// Load checked, in Newtons
+    load_n: u32,
+    /// Bolts sharing the load
+    bolt_count: u32,
+    /// Load on each bolt, in Newtons
+    force_per_bolt_n: f32,
+    /// Shear in each bolt against its shear yield strength
+    bolt_shear: StressCheck,
+    /// Bearing of each bolt on the plate against the plate's yield strength
+    plate_bearing: StressCheck,
+    /// The smaller of the two safety factors
+    safety_factor: f32,
+    /// One-line summary for design reports
+    report_line: String,
+    /// Safety factors below 2
+    warnings: Vec<ErrorDetail>,
+}
+
+/// One stress compared against the strength it must stay under.
+#[derive(Serialize, ToSchema)]
+struct StressCheck {
+    /// Stress under the load, in MPa
+    stress_mpa: f32,
+    /// Yield strength, in MPa
+    yield_mpa: f32,
+    /// `yield_mpa / stress_mpa`
+    safety_factor: f32,
+}
+
 /// Validation error response
 #[derive(Serialize, ToSchema)]
 struct ValidationErrorResponse {
@@ -717,4 +3697,312 @@ struct ValidationErrorResponse {
     errors: Vec<ErrorDetail>,
     /// Minimum plate thickness (mm) that would satisfy stress constraints, if applicable
     minimum_thickness_mm: Option<u16>,
+    /// Problems that wouldn't make the plate invalid on their own, such as a thin plate
+    warnings: Vec<ErrorDetail>,
+}
+
+/// Quote request body
+#[derive(Deserialize, ToSchema)]
+struct QuoteRequestBody {
+    /// Plate configuration to quote (must already be generated)
+    plate: ActuatorPlate,
+    /// Number of plates to manufacture
+    #[schema(example = 25)]
+    quantity: u32,
+    /// Surface finish (default `as_machined`); affects lead time
+    #[serde(default)]
+    finish: Finish,
+}
+
+/// Order submission body
+#[derive(Deserialize, ToSchema)]
+struct PlaceOrderRequest {
+    /// Plate configuration to manufacture
+    plate: ActuatorPlate,
+    /// Number of plates to manufacture
+    #[schema(example = 25)]
+    quantity: u32,
+    /// Customer name
+    #[schema(example = "Ada Lovelace")]
+    customer_name: String,
+    /// Where the confirmation is sent
+    #[schema(example = "ada@example.com")]
+    customer_email: String,
+    /// Optional phone number
+    #[serde(default)]
+    customer_phone: Option<String>,
+}
+
+/// Request to email a model
+#[derive(Deserialize, ToSchema)]
+struct EmailModelRequest {
+    /// Where to send the STEP file
+    #[schema(example = "ada@example.com")]
+    email: String,
+}
+
+/// Response after emailing a model
+#[derive(Serialize, ToSchema)]
+struct EmailModelResponse {
+    /// Always true when the email was sent
+    success: bool,
+    /// Whether the STEP file was attached; otherwise the email has a download link
+    attached: bool,
+}
+
+/// Order status change body
+#[derive(Deserialize, ToSchema)]
+struct OrderStatusRequest {
+    /// The status to move to; must be the next one after the current status
+    status: OrderStatus,
+}
+
+/// Quote response
+#[derive(Serialize, ToSchema)]
+struct QuoteResponse {
+    /// Always true for successful quote requests
+    success: bool,
+    /// Number of plates quoted
+    quantity: Count,
+    /// Our estimated price from material, machining time, and quantity
+    estimate: PriceEstimate,
+    /// Prices from third-party manufacturing services; providers that fail are omitted
+    external_quotes: Vec<ExternalQuote>,
+    /// Our estimated lead time and ship date for the order
+    lead_time: LeadTimeEstimate,
+    /// Volume, mass, and center of mass of one plate
+    mass_properties: MassProperties,
+}
+
+/// Output format for the order export
+#[derive(Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// Output format for cache statistics
+#[derive(Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+enum StatsFormat {
+    #[default]
+    Json,
+    Prometheus,
+}
+
+/// Query parameters for cache statistics
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct StatsQuery {
+    /// `json` (default) or `prometheus`
+    #[serde(default)]
+    format: StatsFormat,
+}
+
+/// File format for model, mesh, drawing, cut profile, and source downloads
+#[derive(Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+enum ModelFormat {
+    #[default]
+    Step,
+    Gltf,
+    Stl,
+    /// Mesh as Wavefront OBJ, in millimeters
+    Obj,
+    /// Mesh as a 3MF package, for slicers
+    #[serde(rename = "3mf")]
+    ThreeMf,
+    /// 2D drawing as SVG
+    Svg,
+    /// 2D drawing as PDF
+    Pdf,
+    /// Flat cut profile as DXF R12
+    Dxf,
+    /// KCL source the model was built from
+    Kcl,
+}
+
+impl ModelFormat {
+    fn extension(self) -> &'static str {
+        self.artifact().extension()
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ModelFormat::Step => "STEP",
+            ModelFormat::Gltf => "glTF",
+            ModelFormat::Stl => "STL",
+            ModelFormat::Obj => "OBJ",
+            ModelFormat::ThreeMf => "3MF",
+            ModelFormat::Svg => "SVG",
+            ModelFormat::Pdf => "PDF",
+            ModelFormat::Dxf => "DXF",
+            ModelFormat::Kcl => "KCL",
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        self.artifact().content_type()
+    }
+
+    fn artifact(self) -> Artifact {
+        match self {
+            ModelFormat::Step => Artifact::Step,
+            ModelFormat::Gltf => Artifact::Gltf,
+            ModelFormat::Stl => Artifact::Stl,
+            ModelFormat::Obj => Artifact::Obj,
+            ModelFormat::ThreeMf => Artifact::ThreeMf,
+            ModelFormat::Svg => Artifact::Svg,
+            ModelFormat::Pdf => Artifact::Pdf,
+            ModelFormat::Dxf => Artifact::Dxf,
+            ModelFormat::Kcl => Artifact::Kcl,
+        }
+    }
+
+    fn path(self, result: &GenerationResult) -> &std::path::Path {
+        match self {
+            ModelFormat::Step => &result.step_file,
+            ModelFormat::Gltf => &result.gltf_file,
+            ModelFormat::Stl => &result.stl_file,
+            ModelFormat::Obj => &result.obj_file,
+            ModelFormat::ThreeMf => &result.three_mf_file,
+            ModelFormat::Svg => &result.svg_file,
+            ModelFormat::Pdf => &result.pdf_file,
+            ModelFormat::Dxf => &result.dxf_file,
+            ModelFormat::Kcl => &result.kcl_file,
+        }
+    }
+}
+
+/// Query parameters for model downloads
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ModelDownloadQuery {
+    /// File format: `step` (default), `gltf`, `stl`, the drawing as `svg` or `pdf`,
+    /// the cut profile as `dxf`, or the source as `kcl`
+    #[serde(default)]
+    format: ModelFormat,
+}
+
+/// Query parameters for batch generation
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct BatchQuery {
+    /// How many of the batch's jobs may generate at once (default 2)
+    parallelism: Option<usize>,
+}
+
+/// Outcome for one plate of a batch
+#[derive(Serialize, ToSchema)]
+struct BatchItem {
+    /// Position of the plate in the submitted array
+    index: usize,
+    /// Cache key of the plate
+    cache_key: String,
+    /// The job started for this plate; absent when the plate is invalid
+    job: Option<Job>,
+    /// Why the plate was rejected; empty when a job was started
+    errors: Vec<ErrorDetail>,
+}
+
+/// Batch generation response
+#[derive(Serialize, ToSchema)]
+struct BatchResponse {
+    /// Number of plates a job was started for
+    accepted: usize,
+    /// Number of plates rejected as invalid
+    rejected: usize,
+    /// One entry per submitted plate, in order
+    items: Vec<BatchItem>,
+}
+
+/// One valid combination of a sweep
+#[derive(Serialize, ToSchema)]
+struct SweepItem {
+    /// Position in the sweep's cartesian product, counting skipped combinations
+    index: usize,
+    /// Cache key of the plate
+    cache_key: String,
+    plate: ActuatorPlate,
+    /// The job generating this plate
+    job: Job,
+}
+
+/// Sweep manifest: the valid plates and their jobs
+#[derive(Serialize, ToSchema)]
+struct SweepResponse {
+    sweep: Sweep,
+    /// Combinations in the sweep, valid or not
+    total: usize,
+    /// Combinations skipped because they failed validation
+    skipped: usize,
+    /// Every valid combination, in order
+    plates: Vec<SweepItem>,
+}
+
+/// Request body for saving a plate configuration
+#[derive(Deserialize, ToSchema)]
+struct SaveConfigRequest {
+    /// Name to save the configuration under
+    name: String,
+    /// Plate parameters to save
+    plate: ActuatorPlate,
+}
+
+/// Request body for comparing two plates
+#[derive(Deserialize, ToSchema)]
+struct ComparePlatesRequest {
+    /// The plate compared from
+    before: ActuatorPlate,
+    /// The plate compared to
+    after: ActuatorPlate,
+}
+
+/// Query parameters for the order export
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct OrderExportQuery {
+    /// Output format: `csv` (default) or `json`
+    #[serde(default)]
+    format: ExportFormat,
+    /// Only export orders placed at or after this RFC 3339 timestamp
+    since: Option<String>,
+}
+
+/// Request body for registering a webhook endpoint
+#[derive(Deserialize, ToSchema)]
+struct CreateWebhookRequest {
+    /// URL that receives POSTed events
+    url: String,
+    /// Events to deliver to this endpoint
+    events: Vec<WebhookEvent>,
+    /// Signing secret; generated when omitted
+    secret: Option<String>,
+}
+
+/// Request body for issuing an API key
+#[derive(Deserialize, ToSchema)]
+struct CreateApiKeyRequest {
+    /// Who the key is for, e.g. the client or integration name
+    name: String,
+}
+
+/// Query parameters for the backup download
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct BackupQuery {
+    /// Include cached model files, not just cache keys (default false)
+    #[serde(default)]
+    artifacts: bool,
+}
+
+/// Restore result
+#[derive(Serialize, ToSchema)]
+struct RestoreResponse {
+    /// Always true for successful restores
+    success: bool,
+    /// What was restored
+    restored: RestoreSummary,
 }
@@ -0,0 +1,76 @@
+//! Deployment overrides for the plate parameter limits.
+//!
+//! Each limit in [`ValidationLimits`] can be overridden with an environment variable,
+//! so a shop can match the allowed range to its machine envelope without a rebuild.
+
+use validation::ValidationLimits;
+
+/// Read limits from MAX_BOLT_SPACING_MM, MAX_BRACKET_HEIGHT_MM, MAX_BRACKET_WIDTH_MM,
+/// MAX_PIN_DIAMETER_MM, MAX_PLATE_THICKNESS_MM, MAX_PIN_COUNT, and MAX_FORCE_PER_PIN_N.
+/// Unset or invalid variables keep the default for that limit.
+pub fn validation_limits_from_env() -> ValidationLimits {
+    limits_from_lookup(|key| std::env::var(key).ok())
+}
+
+fn limits_from_lookup(lookup: impl Fn(&str) -> Option<String>) -> ValidationLimits {
+    fn set<T: std::str::FromStr + PartialOrd + Default>(
+        lookup: &impl Fn(&str) -> Option<String>,
+        key: &str,
+        limit: &mut T,
+    ) {
+        let Some(value) = lookup(key).filter(|v| !v.is_empty()) else {
+            return;
+        };
+        match value.parse::<T>() {
+            Ok(parsed) if parsed > T::default() => *limit = parsed,
+            _ => tracing::warn!("{} must be a positive whole number, got {:?}; using the default", key, value),
+        }
+    }
+
+    let mut limits = ValidationLimits::DEFAULT;
+    set(&lookup, "MAX_BOLT_SPACING_MM", &mut limits.max_bolt_spacing_mm);
+    set(&lookup, "MAX_BRACKET_HEIGHT_MM", &mut limits.max_bracket_height_mm);
+    set(&lookup, "MAX_BRACKET_WIDTH_MM", &mut limits.max_bracket_width_mm);
+    set(&lookup, "MAX_PIN_DIAMETER_MM", &mut limits.max_pin_diameter_mm);
+    set(&lookup, "MAX_PLATE_THICKNESS_MM", &mut limits.max_plate_thickness_mm);
+    set(&lookup, "MAX_PIN_COUNT", &mut limits.max_pin_count);
+    set(&lookup, "MAX_FORCE_PER_PIN_N", &mut limits.max_force_per_pin_n);
+    if limits.max_force_per_pin_n > validation::MAX_FORCE_PER_PIN {
+        tracing::warn!(
+            "MAX_FORCE_PER_PIN_N is capped at {} N; larger forces would overflow the stress checks",
+            validation::MAX_FORCE_PER_PIN
+        );
+    }
+    limits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn test_limits_from_env() {
+        assert_eq!(limits_from_lookup(lookup(&[])), ValidationLimits::DEFAULT);
+
+        let limits = limits_from_lookup(lookup(&[
+            ("MAX_BRACKET_HEIGHT_MM", "1500"),
+            ("MAX_PIN_COUNT", "24"),
+            ("MAX_PLATE_THICKNESS_MM", "0"),
+            ("MAX_BOLT_SPACING_MM", "1m"),
+        ]));
+        assert_eq!(limits.max_bracket_height_mm, 1500);
+        assert_eq!(limits.max_pin_count, 24);
+        // Zero and unparsable values keep the default
+        assert_eq!(limits.max_plate_thickness_mm, ValidationLimits::DEFAULT.max_plate_thickness_mm);
+        assert_eq!(limits.max_bolt_spacing_mm, ValidationLimits::DEFAULT.max_bolt_spacing_mm);
+    }
+}
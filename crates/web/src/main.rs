@@ -1,4 +1,8 @@
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    web::run().await
+async fn main() {
+    // Display rather than Debug, so a configuration error reads as its list of problems
+    if let Err(e) = web::run().await {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
 }
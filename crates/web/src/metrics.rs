@@ -0,0 +1,163 @@
+//! Prometheus metrics for the web server, served at `/metrics`.
+//!
+//! Request counts and latency are labeled with the matched route (`/api/jobs/{job_id}`,
+//! not the job ID) so the number of series stays fixed. Generation duration, engine
+//! failures, and the cache counters from [`CacheMetrics`] are included in the same
+//! scrape. Set METRICS_TOKEN to require `Authorization: Bearer <token>`.
+
+use axum::extract::{MatchedPath, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::cache_metrics::CacheMetrics;
+
+/// Server metrics registered in their own registry, so tests don't share counters.
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_duration: HistogramVec,
+    generation_duration: HistogramVec,
+    engine_failures: IntCounterVec,
+}
+
+/// How a generation ended, as the `outcome` label.
+#[derive(Clone, Copy, Debug)]
+pub enum GenerationOutcome {
+    Succeeded,
+    /// The plate failed validation before the engine ran.
+    Invalid,
+    /// The engine or file export failed.
+    Failed,
+}
+
+impl GenerationOutcome {
+    /// The outcome of a call to the parametric generator.
+    pub fn of<T>(result: &Result<T, parametric::AllErrors>) -> Self {
+        match result {
+            Ok(_) => GenerationOutcome::Succeeded,
+            Err(parametric::AllErrors::ValidationErrors(_)) => GenerationOutcome::Invalid,
+            Err(parametric::AllErrors::GeneratorError(_)) => GenerationOutcome::Failed,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            GenerationOutcome::Succeeded => "succeeded",
+            GenerationOutcome::Invalid => "invalid",
+            GenerationOutcome::Failed => "failed",
+        }
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let http_requests = IntCounterVec::new(
+            Opts::new("platerator_http_requests_total", "HTTP requests by route and status"),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+        let http_duration = HistogramVec::new(
+            HistogramOpts::new("platerator_http_request_duration_seconds", "HTTP request latency by route"),
+            &["method", "route"],
+        )
+        .expect("valid metric");
+        let generation_duration = HistogramVec::new(
+            HistogramOpts::new("platerator_generation_duration_seconds", "Model generation time by outcome")
+                .buckets(vec![0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0]),
+            &["outcome"],
+        )
+        .expect("valid metric");
+        let engine_failures = IntCounterVec::new(
+            Opts::new("platerator_engine_failures_total", "Zoo engine calls that failed"),
+            &["kind"],
+        )
+        .expect("valid metric");
+
+        let registry = Registry::new();
+        registry.register(Box::new(http_requests.clone())).expect("registered once");
+        registry.register(Box::new(http_duration.clone())).expect("registered once");
+        registry.register(Box::new(generation_duration.clone())).expect("registered once");
+        registry.register(Box::new(engine_failures.clone())).expect("registered once");
+        Self {
+            registry,
+            http_requests,
+            http_duration,
+            generation_duration,
+            engine_failures,
+        }
+    }
+
+    /// Record how long a generation took and how it ended.
+    pub fn observe_generation(&self, outcome: GenerationOutcome, elapsed: Duration) {
+        self.generation_duration
+            .with_label_values(&[outcome.label()])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Count a failed engine call. `kind` says which, e.g. `generation`.
+    pub fn record_engine_failure(&self, kind: &str) {
+        self.engine_failures.with_label_values(&[kind]).inc();
+    }
+
+    /// Every metric, including the cache counters, in the Prometheus text format.
+    pub fn render(&self, cache: &CacheMetrics) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::warn!("Could not encode metrics: {}", e);
+        }
+        let mut text = String::from_utf8(buffer).unwrap_or_default();
+        cache.render_prometheus(&mut text);
+        text
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Middleware counting requests and their latency per matched route.
+///
+/// Requests that match no API route (static files, 404s) share the `unmatched`
+/// route, so scanners can't add series.
+pub async fn track(State(metrics): State<Arc<Metrics>>, request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().as_str().to_string();
+    let start = Instant::now();
+    let response = next.run(request).await;
+
+    metrics
+        .http_duration
+        .with_label_values(&[method.as_str(), route.as_str()])
+        .observe(start.elapsed().as_secs_f64());
+    metrics
+        .http_requests
+        .with_label_values(&[method.as_str(), route.as_str(), response.status().as_str()])
+        .inc();
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_server_and_cache_metrics() {
+        let metrics = Metrics::new();
+        metrics.observe_generation(GenerationOutcome::Succeeded, Duration::from_secs(3));
+        metrics.record_engine_failure("generation");
+
+        let text = metrics.render(&CacheMetrics::new());
+        assert!(text.contains("platerator_generation_duration_seconds_bucket{outcome=\"succeeded\",le=\"5\"} 1"));
+        assert!(text.contains("platerator_engine_failures_total{kind=\"generation\"} 1"));
+        assert!(text.contains("platerator_cache_hits_total 0"));
+    }
+}
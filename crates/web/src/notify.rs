@@ -0,0 +1,244 @@
+//! Chat notifications for operational events.
+//!
+//! A `Notifier` posts a short message to a team chat channel when something
+//! worth a human's attention happens: a new order, a failed generation, or the
+//! Zoo API quota running out. Slack and Discord incoming webhooks are supported.
+
+use crate::supervisor::Supervisor;
+use async_trait::async_trait;
+use domain::Count;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a chat webhook gets to answer. Well under the shutdown grace period,
+/// so a hung webhook doesn't hold up shutdown.
+pub const NOTIFY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// An event worth telling the team about.
+#[derive(Clone, Debug)]
+pub enum Notification {
+    /// A customer submitted an order.
+    OrderPlaced {
+        order_id: String,
+        quantity: Count,
+        total_price_cents: u64,
+    },
+    /// Model generation failed for a reason other than invalid input.
+    GenerationFailed { cache_key: String, message: String },
+    /// The Zoo API quota has been used up; generation will fail until it resets.
+    QuotaExhausted { used: u64, limit: u64 },
+}
+
+impl Notification {
+    /// Short machine-friendly event name, used to filter which events a deployment sends.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Notification::OrderPlaced { .. } => "order",
+            Notification::GenerationFailed { .. } => "generation_failed",
+            Notification::QuotaExhausted { .. } => "quota_exhausted",
+        }
+    }
+}
+
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Notification::OrderPlaced {
+                order_id,
+                quantity,
+                total_price_cents,
+            } => write!(
+                f,
+                "New order {}: {} plate(s), ${}.{:02}",
+                order_id,
+                quantity,
+                total_price_cents / 100,
+                total_price_cents % 100
+            ),
+            Notification::GenerationFailed { cache_key, message } => {
+                write!(f, "Generation failed for {}: {}", cache_key, message)
+            }
+            Notification::QuotaExhausted { used, limit } => {
+                write!(f, "Zoo API quota exhausted: {} of {} used", used, limit)
+            }
+        }
+    }
+}
+
+/// Errors that can occur while delivering a notification.
+#[derive(Debug)]
+pub enum NotifyError {
+    /// The webhook request failed or returned a non-success status.
+    Http(String),
+}
+
+impl fmt::Display for NotifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotifyError::Http(msg) => write!(f, "Notification delivery failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for NotifyError {}
+
+/// Trait for delivering notifications to a chat channel.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Deliver a single notification.
+    async fn notify(&self, notification: &Notification) -> Result<(), NotifyError>;
+}
+
+/// Which chat service a webhook URL belongs to; they expect different JSON bodies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebhookFlavor {
+    /// Slack incoming webhook: `{"text": "..."}`.
+    Slack,
+    /// Discord webhook: `{"content": "..."}`.
+    Discord,
+}
+
+/// Posts notifications to a Slack or Discord incoming webhook.
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+    flavor: WebhookFlavor,
+    /// Event kinds to send; `None` sends everything.
+    events: Option<Vec<String>>,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String, flavor: WebhookFlavor, events: Option<Vec<String>>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(NOTIFY_TIMEOUT)
+                .build()
+                .expect("notifier HTTP client"),
+            url,
+            flavor,
+            events,
+        }
+    }
+
+    fn wants(&self, notification: &Notification) -> bool {
+        match &self.events {
+            Some(events) => events.iter().any(|e| e == notification.kind()),
+            None => true,
+        }
+    }
+
+    fn payload(&self, notification: &Notification) -> serde_json::Value {
+        let text = notification.to_string();
+        match self.flavor {
+            WebhookFlavor::Slack => serde_json::json!({ "text": text }),
+            WebhookFlavor::Discord => serde_json::json!({ "content": text }),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotifyError> {
+        if !self.wants(notification) {
+            return Ok(());
+        }
+
+        self.client
+            .post(&self.url)
+            .json(&self.payload(notification))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| NotifyError::Http(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Build notifiers from environment variables.
+///
+/// SLACK_WEBHOOK_URL and DISCORD_WEBHOOK_URL each enable a notifier. NOTIFY_EVENTS
+/// optionally restricts which events are sent, as a comma-separated list of
+/// `order`, `generation_failed`, and `quota_exhausted`.
+pub fn notifiers_from_env() -> Vec<Arc<dyn Notifier>> {
+    let events = std::env::var("NOTIFY_EVENTS").ok().map(|list| {
+        list.split(',')
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .collect::<Vec<_>>()
+    });
+
+    let mut notifiers: Vec<Arc<dyn Notifier>> = Vec::new();
+    if let Ok(url) = std::env::var("SLACK_WEBHOOK_URL") {
+        tracing::info!("Sending notifications to Slack");
+        notifiers.push(Arc::new(WebhookNotifier::new(url, WebhookFlavor::Slack, events.clone())));
+    }
+    if let Ok(url) = std::env::var("DISCORD_WEBHOOK_URL") {
+        tracing::info!("Sending notifications to Discord");
+        notifiers.push(Arc::new(WebhookNotifier::new(url, WebhookFlavor::Discord, events)));
+    }
+    notifiers
+}
+
+/// Deliver a notification to every notifier in the background, logging failures.
+pub fn notify_all(
+    supervisor: &Supervisor,
+    notifiers: &[Arc<dyn Notifier>],
+    notification: Notification,
+) {
+    for notifier in notifiers {
+        let notifier = notifier.clone();
+        let notification = notification.clone();
+        supervisor.spawn_task("notification", async move {
+            if let Err(e) = notifier.notify(&notification).await {
+                tracing::warn!("Failed to send {} notification: {}", notification.kind(), e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_payload_matches_flavor() {
+        let notification = Notification::QuotaExhausted { used: 100, limit: 100 };
+
+        let slack = WebhookNotifier::new(String::new(), WebhookFlavor::Slack, None);
+        let discord = WebhookNotifier::new(String::new(), WebhookFlavor::Discord, None);
+
+        assert_eq!(
+            slack.payload(&notification)["text"],
+            "Zoo API quota exhausted: 100 of 100 used"
+        );
+        assert_eq!(
+            discord.payload(&notification)["content"],
+            "Zoo API quota exhausted: 100 of 100 used"
+        );
+    }
+
+    #[test]
+    fn test_webhook_event_filter() {
+        let notifier = WebhookNotifier::new(
+            String::new(),
+            WebhookFlavor::Slack,
+            Some(vec!["order".to_string()]),
+        );
+
+        let order = Notification::OrderPlaced {
+            order_id: "ord-1".to_string(),
+            quantity: Count::of(25),
+            total_price_cents: 105_050,
+        };
+        let failure = Notification::GenerationFailed {
+            cache_key: "plate-abc".to_string(),
+            message: "engine closed early".to_string(),
+        };
+
+        assert!(notifier.wants(&order));
+        assert!(!notifier.wants(&failure));
+        assert_eq!(order.to_string(), "New order ord-1: 25 plate(s), $1050.50");
+    }
+}
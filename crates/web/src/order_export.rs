@@ -0,0 +1,225 @@
+//! ERP-friendly order export with a configurable column mapping.
+//!
+//! Our ERP imports orders from CSV or JSON with its own column names, so the
+//! export maps each order field to a configurable header and emits prices as
+//! plain decimal strings.
+
+use crate::orders::Order;
+
+/// An order field that can appear in an export.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportColumn {
+    OrderId,
+    CreatedAt,
+    PartNumber,
+    Material,
+    Quantity,
+    UnitPrice,
+    TotalPrice,
+    CustomerName,
+    CustomerEmail,
+    OrderNumber,
+    Status,
+}
+
+impl ExportColumn {
+    const ALL: [ExportColumn; 11] = [
+        ExportColumn::OrderId,
+        ExportColumn::CreatedAt,
+        ExportColumn::PartNumber,
+        ExportColumn::Material,
+        ExportColumn::Quantity,
+        ExportColumn::UnitPrice,
+        ExportColumn::TotalPrice,
+        ExportColumn::CustomerName,
+        ExportColumn::CustomerEmail,
+        ExportColumn::OrderNumber,
+        ExportColumn::Status,
+    ];
+
+    /// The field name used in mapping configuration and as the default header.
+    pub fn key(self) -> &'static str {
+        match self {
+            ExportColumn::OrderId => "order_id",
+            ExportColumn::CreatedAt => "created_at",
+            ExportColumn::PartNumber => "part_number",
+            ExportColumn::Material => "material",
+            ExportColumn::Quantity => "quantity",
+            ExportColumn::UnitPrice => "unit_price",
+            ExportColumn::TotalPrice => "total_price",
+            ExportColumn::CustomerName => "customer_name",
+            ExportColumn::CustomerEmail => "customer_email",
+            ExportColumn::OrderNumber => "order_number",
+            ExportColumn::Status => "status",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.key() == key)
+    }
+
+    fn value(self, order: &Order) -> String {
+        match self {
+            ExportColumn::OrderId => order.id.clone(),
+            ExportColumn::CreatedAt => order.created_at.clone(),
+            ExportColumn::PartNumber => order.part_number.clone(),
+            ExportColumn::Material => serde_json::to_value(order.material)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            ExportColumn::Quantity => order.quantity.to_string(),
+            ExportColumn::UnitPrice => format_cents(order.unit_price_cents),
+            ExportColumn::TotalPrice => format_cents(order.total_price_cents),
+            ExportColumn::CustomerName => order.customer_name.clone(),
+            ExportColumn::CustomerEmail => order.customer_email.clone(),
+            ExportColumn::OrderNumber => order.order_number.clone(),
+            ExportColumn::Status => order.status.as_str().to_string(),
+        }
+    }
+}
+
+fn format_cents(cents: u64) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+/// Ordered list of exported columns and the header each is written under.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnMapping {
+    columns: Vec<(ExportColumn, String)>,
+}
+
+impl Default for ColumnMapping {
+    /// Every column, headed by its field name.
+    fn default() -> Self {
+        Self {
+            columns: ExportColumn::ALL
+                .into_iter()
+                .map(|c| (c, c.key().to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl ColumnMapping {
+    /// Parse a mapping like `part_number:PartNo,material:Material,quantity`.
+    ///
+    /// Each entry is a field name optionally followed by `:` and the header to use.
+    /// Returns the unknown field name on error.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut columns = Vec::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (key, header) = match entry.split_once(':') {
+                Some((key, header)) => (key.trim(), header.trim()),
+                None => (entry, entry),
+            };
+            let column = ExportColumn::from_key(key).ok_or_else(|| key.to_string())?;
+            columns.push((column, header.to_string()));
+        }
+        Ok(Self { columns })
+    }
+
+    /// Read the mapping from ORDER_EXPORT_COLUMNS, falling back to every column.
+    pub fn from_env() -> Self {
+        match std::env::var("ORDER_EXPORT_COLUMNS") {
+            Ok(spec) => Self::parse(&spec).unwrap_or_else(|key| {
+                tracing::warn!("Unknown column {:?} in ORDER_EXPORT_COLUMNS, using defaults", key);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Render orders as CSV with a header row.
+    pub fn to_csv(&self, orders: &[Order]) -> String {
+        let mut out = String::new();
+        let headers: Vec<String> = self.columns.iter().map(|(_, h)| csv_field(h)).collect();
+        out.push_str(&headers.join(","));
+        out.push_str("\r\n");
+        for order in orders {
+            let row: Vec<String> = self
+                .columns
+                .iter()
+                .map(|(c, _)| csv_field(&c.value(order)))
+                .collect();
+            out.push_str(&row.join(","));
+            out.push_str("\r\n");
+        }
+        out
+    }
+
+    /// Render orders as a JSON array of objects keyed by the mapped headers.
+    pub fn to_json(&self, orders: &[Order]) -> serde_json::Value {
+        orders
+            .iter()
+            .map(|order| {
+                let row: serde_json::Map<String, serde_json::Value> = self
+                    .columns
+                    .iter()
+                    .map(|(c, h)| (h.clone(), serde_json::Value::String(c.value(order))))
+                    .collect();
+                serde_json::Value::Object(row)
+            })
+            .collect()
+    }
+}
+
+/// Quote a CSV field when it contains a delimiter, quote, or newline (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::OrderStatus;
+    use domain::{Count, Material};
+
+    fn order() -> Order {
+        Order {
+            id: "ord-1".to_string(),
+            order_number: String::new(),
+            status: OrderStatus::Received,
+            created_at: "2026-01-02T03:04:05+00:00".to_string(),
+            part_number: "plate-0123456789abcdef".to_string(),
+            material: Material::StainlessSteel,
+            plate: None,
+            quantity: Count::of(25),
+            unit_price_cents: 4205,
+            total_price_cents: 105_125,
+            customer_name: "Acme, Inc.".to_string(),
+            customer_email: "buyer@acme.test".to_string(),
+            customer_phone: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_mapping_with_renamed_headers() {
+        let mapping = ColumnMapping::parse("part_number:PartNo, quantity, total_price:Amount").unwrap();
+        let csv = mapping.to_csv(&[order()]);
+        assert_eq!(csv, "PartNo,quantity,Amount\r\nplate-0123456789abcdef,25,1051.25\r\n");
+    }
+
+    #[test]
+    fn test_parse_mapping_rejects_unknown_column() {
+        assert_eq!(ColumnMapping::parse("part_number,colour"), Err("colour".to_string()));
+    }
+
+    #[test]
+    fn test_csv_quotes_fields_with_commas() {
+        let mapping = ColumnMapping::parse("customer_name:Customer,material").unwrap();
+        let csv = mapping.to_csv(&[order()]);
+        assert_eq!(csv, "Customer,material\r\n\"Acme, Inc.\",stainless_steel\r\n");
+    }
+
+    #[test]
+    fn test_json_uses_mapped_headers() {
+        let mapping = ColumnMapping::parse("order_id:OrderNo,unit_price:Price").unwrap();
+        let json = mapping.to_json(&[order()]);
+        assert_eq!(json[0]["OrderNo"], "ord-1");
+        assert_eq!(json[0]["Price"], "42.05");
+    }
+}
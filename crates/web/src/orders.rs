@@ -0,0 +1,428 @@
+//! Order records and storage.
+//!
+//! A new order is `received` and gets the next order number (`PL-000001`, ...).
+//! Staff then move it to `quoted` once a final price has been sent, and to
+//! `confirmed` when the customer accepts; statuses never skip or go backwards.
+
+use async_trait::async_trait;
+use domain::{ActuatorPlate, Count, Material};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+use utoipa::ToSchema;
+
+/// Where an order is in its lifecycle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    /// Submitted by the customer; nothing has been done yet
+    #[default]
+    Received,
+    /// A final price has been sent to the customer
+    Quoted,
+    /// The customer accepted the quote
+    Confirmed,
+}
+
+impl OrderStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OrderStatus::Received => "received",
+            OrderStatus::Quoted => "quoted",
+            OrderStatus::Confirmed => "confirmed",
+        }
+    }
+
+    /// The only status an order in this status may move to.
+    pub fn next(self) -> Option<Self> {
+        match self {
+            OrderStatus::Received => Some(OrderStatus::Quoted),
+            OrderStatus::Quoted => Some(OrderStatus::Confirmed),
+            OrderStatus::Confirmed => None,
+        }
+    }
+}
+
+impl FromStr for OrderStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "received" => Ok(OrderStatus::Received),
+            "quoted" => Ok(OrderStatus::Quoted),
+            "confirmed" => Ok(OrderStatus::Confirmed),
+            other => Err(other.to_string()),
+        }
+    }
+}
+
+const ORDER_NUMBER_PREFIX: &str = "PL-";
+
+fn format_order_number(n: u64) -> String {
+    format!("{}{:06}", ORDER_NUMBER_PREFIX, n)
+}
+
+fn parse_order_number(order_number: &str) -> Option<u64> {
+    order_number.strip_prefix(ORDER_NUMBER_PREFIX)?.parse().ok()
+}
+
+/// A customer order for one plate configuration.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Order {
+    /// Unique order identifier
+    pub id: String,
+    /// Order number given to the customer, e.g. `PL-000042`; empty on orders
+    /// placed before numbers were assigned
+    #[serde(default)]
+    pub order_number: String,
+    /// Where the order is in its lifecycle
+    #[serde(default)]
+    pub status: OrderStatus,
+    /// When the order was placed (RFC 3339)
+    pub created_at: String,
+    /// Part number; the plate's cache key, so it maps 1:1 to a generated model
+    pub part_number: String,
+    /// Plate material
+    pub material: Material,
+    /// The plate configuration ordered; missing on orders from older backups
+    #[serde(default)]
+    pub plate: Option<ActuatorPlate>,
+    /// Number of plates ordered
+    pub quantity: Count,
+    /// Price per plate in cents
+    pub unit_price_cents: u64,
+    /// Price for the whole order in cents
+    pub total_price_cents: u64,
+    /// Customer name
+    pub customer_name: String,
+    /// Customer email address
+    pub customer_email: String,
+    /// Customer phone number
+    #[serde(default)]
+    pub customer_phone: Option<String>,
+}
+
+/// Errors that can occur during order storage operations.
+#[derive(Debug)]
+pub enum OrderError {
+    /// The requested order was not found.
+    NotFound,
+    /// The order can't move from its current status to the requested one.
+    InvalidTransition { from: OrderStatus, to: OrderStatus },
+    /// The backing store failed.
+    StorageError(String),
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::NotFound => write!(f, "Order not found"),
+            OrderError::InvalidTransition { from, to } => write!(
+                f,
+                "An order that is {} can't be marked {}",
+                from.as_str(),
+                to.as_str()
+            ),
+            OrderError::StorageError(msg) => write!(f, "Order storage error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for OrderError {}
+
+impl From<sqlx::Error> for OrderError {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            sqlx::Error::RowNotFound => OrderError::NotFound,
+            e => OrderError::StorageError(e.to_string()),
+        }
+    }
+}
+
+/// Check that `order` may move to `status`.
+fn check_transition(order: &Order, status: OrderStatus) -> Result<(), OrderError> {
+    if order.status.next() == Some(status) {
+        Ok(())
+    } else {
+        Err(OrderError::InvalidTransition {
+            from: order.status,
+            to: status,
+        })
+    }
+}
+
+/// Trait for persisting orders.
+#[async_trait]
+pub trait OrderStore: Send + Sync {
+    /// Record an order exactly as given, e.g. when restoring a backup.
+    async fn insert(&self, order: &Order) -> Result<(), OrderError>;
+
+    /// Record a new order under the next free order number, and return it as stored.
+    async fn create(&self, order: Order) -> Result<Order, OrderError>;
+
+    /// List all orders, oldest first.
+    async fn list(&self) -> Result<Vec<Order>, OrderError>;
+
+    /// Fetch one order by order number.
+    async fn get(&self, order_number: &str) -> Result<Order, OrderError>;
+
+    /// Move an order to `status`, which must be the next step after its current one.
+    async fn set_status(&self, order_number: &str, status: OrderStatus) -> Result<Order, OrderError>;
+
+    /// Check that the store is reachable, for the readiness probe.
+    async fn check(&self) -> Result<(), OrderError> {
+        Ok(())
+    }
+}
+
+/// In-memory order store for testing and development.
+pub struct MemoryOrderStore {
+    orders: RwLock<Vec<Order>>,
+}
+
+impl MemoryOrderStore {
+    pub fn new() -> Self {
+        Self {
+            orders: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl Default for MemoryOrderStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl OrderStore for MemoryOrderStore {
+    async fn insert(&self, order: &Order) -> Result<(), OrderError> {
+        let mut orders = self.orders.write().unwrap();
+        orders.push(order.clone());
+        Ok(())
+    }
+
+    async fn create(&self, mut order: Order) -> Result<Order, OrderError> {
+        let mut orders = self.orders.write().unwrap();
+        let last = orders
+            .iter()
+            .filter_map(|o| parse_order_number(&o.order_number))
+            .max()
+            .unwrap_or(0);
+        order.order_number = format_order_number(last + 1);
+        orders.push(order.clone());
+        Ok(order)
+    }
+
+    async fn list(&self) -> Result<Vec<Order>, OrderError> {
+        let orders = self.orders.read().unwrap();
+        Ok(orders.clone())
+    }
+
+    async fn get(&self, order_number: &str) -> Result<Order, OrderError> {
+        let orders = self.orders.read().unwrap();
+        orders
+            .iter()
+            .find(|o| o.order_number == order_number)
+            .cloned()
+            .ok_or(OrderError::NotFound)
+    }
+
+    async fn set_status(&self, order_number: &str, status: OrderStatus) -> Result<Order, OrderError> {
+        let mut orders = self.orders.write().unwrap();
+        let order = orders
+            .iter_mut()
+            .find(|o| o.order_number == order_number)
+            .ok_or(OrderError::NotFound)?;
+        check_transition(order, status)?;
+        order.status = status;
+        Ok(order.clone())
+    }
+}
+
+/// SQLite order store. The order is stored as JSON so new order fields don't need
+/// a schema migration; the number and status are also columns so they can be
+/// assigned and updated in SQL.
+pub struct SqliteOrderStore {
+    pool: SqlitePool,
+}
+
+impl SqliteOrderStore {
+    /// Open (creating if needed) the database at `url`, e.g. `sqlite:///var/lib/platerator/orders.db`.
+    pub async fn connect(url: &str) -> Result<Self, OrderError> {
+        let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+        let pool = SqlitePoolOptions::new().connect_with(options).await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS orders (
+                id TEXT PRIMARY KEY,
+                order_number TEXT NOT NULL,
+                status TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                data TEXT NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        Ok(Self { pool })
+    }
+
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<Order, OrderError> {
+        let data: String = row.try_get("data")?;
+        let mut order: Order = serde_json::from_str(&data)
+            .map_err(|e| OrderError::StorageError(format!("Unreadable order: {}", e)))?;
+        order.order_number = row.try_get("order_number")?;
+        let status: String = row.try_get("status")?;
+        order.status = status
+            .parse()
+            .map_err(|s| OrderError::StorageError(format!("Unknown order status {:?}", s)))?;
+        Ok(order)
+    }
+
+    fn to_json(order: &Order) -> Result<String, OrderError> {
+        serde_json::to_string(order).map_err(|e| OrderError::StorageError(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl OrderStore for SqliteOrderStore {
+    async fn check(&self) -> Result<(), OrderError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn insert(&self, order: &Order) -> Result<(), OrderError> {
+        sqlx::query("INSERT INTO orders (id, order_number, status, created_at, data) VALUES (?, ?, ?, ?, ?)")
+            .bind(&order.id)
+            .bind(&order.order_number)
+            .bind(order.status.as_str())
+            .bind(&order.created_at)
+            .bind(Self::to_json(order)?)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn create(&self, order: Order) -> Result<Order, OrderError> {
+        // One statement, so two orders placed at once can't get the same number
+        let row = sqlx::query(
+            "INSERT INTO orders (id, order_number, status, created_at, data)
+             SELECT ?, printf('PL-%06d', COALESCE(MAX(CAST(SUBSTR(order_number, 4) AS INTEGER)), 0) + 1), ?, ?, ?
+             FROM orders WHERE order_number LIKE 'PL-%'
+             RETURNING order_number",
+        )
+        .bind(&order.id)
+        .bind(order.status.as_str())
+        .bind(&order.created_at)
+        .bind(Self::to_json(&order)?)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(Order {
+            order_number: row.try_get("order_number")?,
+            ..order
+        })
+    }
+
+    async fn list(&self) -> Result<Vec<Order>, OrderError> {
+        let rows = sqlx::query("SELECT * FROM orders ORDER BY created_at, order_number")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(Self::from_row).collect()
+    }
+
+    async fn get(&self, order_number: &str) -> Result<Order, OrderError> {
+        let row = sqlx::query("SELECT * FROM orders WHERE order_number = ?")
+            .bind(order_number)
+            .fetch_one(&self.pool)
+            .await?;
+        Self::from_row(&row)
+    }
+
+    async fn set_status(&self, order_number: &str, status: OrderStatus) -> Result<Order, OrderError> {
+        let mut order = self.get(order_number).await?;
+        check_transition(&order, status)?;
+        // Only advance from the status we checked, in case someone else got there first
+        let updated = sqlx::query("UPDATE orders SET status = ? WHERE order_number = ? AND status = ?")
+            .bind(status.as_str())
+            .bind(order_number)
+            .bind(order.status.as_str())
+            .execute(&self.pool)
+            .await?;
+        if updated.rows_affected() == 0 {
+            return Err(OrderError::InvalidTransition {
+                from: self.get(order_number).await?.status,
+                to: status,
+            });
+        }
+        order.status = status;
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(id: &str) -> Order {
+        Order {
+            id: id.to_string(),
+            order_number: String::new(),
+            status: OrderStatus::Received,
+            created_at: format!("2026-01-01T00:00:0{}+00:00", id.len()),
+            part_number: ActuatorPlate::default().cache_key(),
+            material: Material::Aluminum,
+            plate: Some(ActuatorPlate::default()),
+            quantity: Count::of(5),
+            unit_price_cents: 1000,
+            total_price_cents: 5000,
+            customer_name: "Acme".to_string(),
+            customer_email: "buyer@acme.test".to_string(),
+            customer_phone: None,
+        }
+    }
+
+    async fn exercise(store: &dyn OrderStore) {
+        // A restored order keeps its number, and new numbers continue after it
+        let restored = Order {
+            order_number: "PL-000007".to_string(),
+            ..order("a")
+        };
+        store.insert(&restored).await.unwrap();
+        let created = store.create(order("bb")).await.unwrap();
+        assert_eq!(created.order_number, "PL-000008");
+        assert_eq!(store.get("PL-000008").await.unwrap(), created);
+
+        let quoted = store.set_status("PL-000008", OrderStatus::Quoted).await.unwrap();
+        assert_eq!(quoted.status, OrderStatus::Quoted);
+        assert!(matches!(
+            store.set_status("PL-000008", OrderStatus::Quoted).await,
+            Err(OrderError::InvalidTransition { .. })
+        ));
+        assert!(matches!(
+            store.set_status("PL-000007", OrderStatus::Confirmed).await,
+            Err(OrderError::InvalidTransition { .. })
+        ));
+        assert!(matches!(
+            store.set_status("PL-999999", OrderStatus::Quoted).await,
+            Err(OrderError::NotFound)
+        ));
+
+        let listed = store.list().await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[1].status, OrderStatus::Quoted);
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_numbers_and_transitions() {
+        exercise(&MemoryOrderStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store_numbers_and_transitions() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}", dir.path().join("orders.db").display());
+        exercise(&SqliteOrderStore::connect(&url).await.unwrap()).await;
+    }
+}
@@ -0,0 +1,299 @@
+//! Third-party manufacturing quote integration.
+//!
+//! A `QuoteProvider` submits a generated STEP file plus material and quantity to
+//! an external manufacturing service and returns that service's price, so the
+//! quote endpoint can show it alongside ours. Providers are asked at the same time,
+//! and one that hasn't answered by [`QUOTE_DEADLINE`] is left out.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use domain::{Count, Material};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+use utoipa::ToSchema;
+
+/// How long to wait for a provider to accept a connection.
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long one request to a provider may take, upload and answer included.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+/// How long a quote waits for every provider to answer.
+pub const QUOTE_DEADLINE: Duration = Duration::from_secs(25);
+
+/// What we send to a manufacturing service when asking for a quote.
+pub struct QuoteRequest {
+    pub step_data: Bytes,
+    pub material: Material,
+    pub quantity: Count,
+}
+
+/// A price returned by a third-party manufacturing service.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ExternalQuote {
+    /// Name of the manufacturing service that produced this quote
+    pub provider: String,
+    /// Price per plate in cents
+    pub unit_price_cents: u64,
+    /// Price for the whole order in cents
+    pub total_price_cents: u64,
+    /// ISO 4217 currency code (e.g. "USD")
+    pub currency: String,
+    /// Quoted lead time in business days, if the provider reports one
+    pub lead_time_days: Option<u16>,
+    /// Link to the quote on the provider's site, if the provider reports one
+    pub quote_url: Option<String>,
+}
+
+/// Errors that can occur while requesting a third-party quote.
+#[derive(Debug)]
+pub enum QuoteError {
+    /// The request to the provider failed or returned a non-success status.
+    Http(String),
+    /// The provider responded with a body we could not interpret.
+    InvalidResponse(String),
+}
+
+impl fmt::Display for QuoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuoteError::Http(msg) => write!(f, "Quote provider request failed: {}", msg),
+            QuoteError::InvalidResponse(msg) => {
+                write!(f, "Quote provider returned an invalid response: {}", msg)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QuoteError {}
+
+/// Trait for requesting prices from external manufacturing services.
+#[async_trait]
+pub trait QuoteProvider: Send + Sync {
+    /// Human-readable provider name, reported in the quote response.
+    fn name(&self) -> &str;
+
+    /// Request a quote for the given STEP file, material, and quantity.
+    async fn quote(&self, request: &QuoteRequest) -> Result<ExternalQuote, QuoteError>;
+}
+
+/// Instant-quote adapter for Xometry-style HTTP APIs.
+///
+/// Posts a multipart form with the STEP file (`file`), `material`, and `quantity`
+/// fields and expects a JSON body with decimal `unit_price` and `total_price`.
+pub struct HttpQuoteProvider {
+    client: reqwest::Client,
+    name: String,
+    endpoint: String,
+    api_key: Option<String>,
+}
+
+/// Response body expected from an instant-quote endpoint.
+#[derive(Deserialize)]
+struct InstantQuoteResponse {
+    unit_price: f64,
+    total_price: f64,
+    #[serde(default = "default_currency")]
+    currency: String,
+    lead_time_days: Option<u16>,
+    quote_url: Option<String>,
+}
+
+fn default_currency() -> String {
+    "USD".to_string()
+}
+
+impl HttpQuoteProvider {
+    pub fn new(name: String, endpoint: String, api_key: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .connect_timeout(CONNECT_TIMEOUT)
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("quote HTTP client"),
+            name,
+            endpoint,
+            api_key,
+        }
+    }
+
+    /// Create an HttpQuoteProvider from environment variables.
+    /// Returns `None` unless QUOTE_PROVIDER_URL is set; QUOTE_PROVIDER_NAME and
+    /// QUOTE_PROVIDER_API_KEY are optional.
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var("QUOTE_PROVIDER_URL").ok()?;
+        let name = std::env::var("QUOTE_PROVIDER_NAME").unwrap_or_else(|_| "external".to_string());
+        let api_key = std::env::var("QUOTE_PROVIDER_API_KEY").ok();
+        Some(Self::new(name, endpoint, api_key))
+    }
+}
+
+#[async_trait]
+impl QuoteProvider for HttpQuoteProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn quote(&self, request: &QuoteRequest) -> Result<ExternalQuote, QuoteError> {
+        let file = reqwest::multipart::Part::stream_with_length(
+            request.step_data.clone(),
+            request.step_data.len() as u64,
+        )
+            .file_name("actuator_plate.step")
+            .mime_str("application/STEP")
+            .map_err(|e| QuoteError::Http(e.to_string()))?;
+        let material = serde_json::to_value(request.material)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let form = reqwest::multipart::Form::new()
+            .part("file", file)
+            .text("material", material)
+            .text("quantity", request.quantity.to_string());
+
+        let mut builder = self.client.post(&self.endpoint).multipart(form);
+        if let Some(api_key) = &self.api_key {
+            builder = builder.bearer_auth(api_key);
+        }
+
+        let response = builder
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| QuoteError::Http(e.to_string()))?;
+
+        let body: InstantQuoteResponse = response
+            .json()
+            .await
+            .map_err(|e| QuoteError::InvalidResponse(e.to_string()))?;
+
+        if body.unit_price < 0.0 || body.total_price < 0.0 {
+            return Err(QuoteError::InvalidResponse("negative price".to_string()));
+        }
+
+        Ok(ExternalQuote {
+            provider: self.name.clone(),
+            unit_price_cents: (body.unit_price * 100.0).round() as u64,
+            total_price_cents: (body.total_price * 100.0).round() as u64,
+            currency: body.currency,
+            lead_time_days: body.lead_time_days,
+            quote_url: body.quote_url,
+        })
+    }
+}
+
+/// Ask every configured provider for a quote at once, skipping (and logging)
+/// failures and providers that don't answer within [`QUOTE_DEADLINE`].
+pub async fn collect_external_quotes(
+    providers: &[std::sync::Arc<dyn QuoteProvider>],
+    request: &QuoteRequest,
+) -> Vec<ExternalQuote> {
+    collect_within(providers, request, QUOTE_DEADLINE).await
+}
+
+async fn collect_within(
+    providers: &[std::sync::Arc<dyn QuoteProvider>],
+    request: &QuoteRequest,
+    deadline: Duration,
+) -> Vec<ExternalQuote> {
+    let asks = providers.iter().map(|provider| async move {
+        match tokio::time::timeout(deadline, provider.quote(request)).await {
+            Ok(Ok(quote)) => Some(quote),
+            Ok(Err(e)) => {
+                tracing::warn!("Quote provider {} failed: {}", provider.name(), e);
+                None
+            }
+            Err(_) => {
+                tracing::warn!("Quote provider {} didn't answer within {:?}", provider.name(), deadline);
+                None
+            }
+        }
+    });
+    futures_util::future::join_all(asks).await.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    struct FixedQuote(u64);
+
+    #[async_trait]
+    impl QuoteProvider for FixedQuote {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn quote(&self, request: &QuoteRequest) -> Result<ExternalQuote, QuoteError> {
+            Ok(ExternalQuote {
+                provider: "fixed".to_string(),
+                unit_price_cents: self.0,
+                total_price_cents: self.0 * u64::from(request.quantity),
+                currency: "USD".to_string(),
+                lead_time_days: None,
+                quote_url: None,
+            })
+        }
+    }
+
+    struct FailingQuote;
+
+    #[async_trait]
+    impl QuoteProvider for FailingQuote {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn quote(&self, _request: &QuoteRequest) -> Result<ExternalQuote, QuoteError> {
+            Err(QuoteError::Http("connection refused".to_string()))
+        }
+    }
+
+    struct SlowQuote;
+
+    #[async_trait]
+    impl QuoteProvider for SlowQuote {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        async fn quote(&self, request: &QuoteRequest) -> Result<ExternalQuote, QuoteError> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            FixedQuote(1).quote(request).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collect_external_quotes_skips_failures() {
+        let providers: Vec<Arc<dyn QuoteProvider>> =
+            vec![Arc::new(FailingQuote), Arc::new(FixedQuote(1250))];
+        let request = QuoteRequest {
+            step_data: Bytes::from_static(b"step"),
+            material: Material::Aluminum,
+            quantity: Count::of(4),
+        };
+
+        let quotes = collect_external_quotes(&providers, &request).await;
+
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0].provider, "fixed");
+        assert_eq!(quotes[0].total_price_cents, 5000);
+    }
+
+    #[tokio::test]
+    async fn test_slow_providers_are_left_out_at_the_deadline() {
+        let providers: Vec<Arc<dyn QuoteProvider>> = vec![Arc::new(SlowQuote), Arc::new(FixedQuote(1250))];
+        let request = QuoteRequest {
+            step_data: Bytes::from_static(b"step"),
+            material: Material::Aluminum,
+            quantity: Count::of(1),
+        };
+
+        let started = std::time::Instant::now();
+        let quotes = collect_within(&providers, &request, Duration::from_millis(50)).await;
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0].provider, "fixed");
+    }
+}
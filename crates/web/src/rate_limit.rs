@@ -0,0 +1,243 @@
+//! Per-client rate limiting for the routes that spend Zoo credits or send mail, and
+//! the JSON body size cap.
+//!
+//! Each client IP gets a token bucket holding RATE_LIMIT_PER_MINUTE requests (default
+//! 30, `0` turns limiting off) that refills continuously, so a burst can use the whole
+//! minute's allowance but a steady client can't exceed it. Requests over the limit get
+//! a 429 with `Retry-After`. At most `MAX_TRACKED_CLIENTS` buckets are kept; past
+//! that, a new client displaces the bucket closest to full, whose client loses the
+//! least by starting over.
+//!
+//! The client IP is the TCP peer. Behind a reverse proxy such as Caddy every request
+//! comes from the proxy, so set RATE_LIMIT_TRUST_PROXY=true to use the last address in
+//! `X-Forwarded-For` instead: the one the proxy added. Don't set it without a proxy in
+//! front, or clients can pick their own address.
+
+use axum::extract::{ConnectInfo, FromRequestParts, Request, State};
+use axum::http::request::Parts;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{AppState, ErrorResponse};
+
+/// Requests per minute per client when RATE_LIMIT_PER_MINUTE is unset.
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 30;
+
+/// Largest request body accepted when MAX_BODY_BYTES is unset. A full batch of
+/// plates is well under this.
+pub const DEFAULT_MAX_BODY_BYTES: usize = 256 * 1024;
+
+/// Buckets kept before idle ones are dropped.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token buckets per client IP.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Bucket size, and how many tokens refill per minute; `0` disables limiting
+    per_minute: u32,
+    trust_proxy: bool,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32, trust_proxy: bool) -> Self {
+        Self {
+            per_minute,
+            trust_proxy,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// A limiter that lets every request through.
+    pub fn disabled() -> Self {
+        Self::new(0, false)
+    }
+
+    /// Read RATE_LIMIT_PER_MINUTE and RATE_LIMIT_TRUST_PROXY.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let per_minute = match lookup("RATE_LIMIT_PER_MINUTE").filter(|v| !v.is_empty()) {
+            Some(value) => value.parse().unwrap_or_else(|_| {
+                tracing::warn!("RATE_LIMIT_PER_MINUTE must be a whole number, got {:?}; using the default", value);
+                DEFAULT_REQUESTS_PER_MINUTE
+            }),
+            None => DEFAULT_REQUESTS_PER_MINUTE,
+        };
+        let trust_proxy = lookup("RATE_LIMIT_TRUST_PROXY").is_some_and(|v| v == "true" || v == "1");
+        if per_minute == 0 {
+            tracing::info!("Rate limiting is off");
+        }
+        Self::new(per_minute, trust_proxy)
+    }
+
+    /// Take a token for `client`, or say how long until one is available.
+    fn acquire(&self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        if self.per_minute == 0 {
+            return Ok(());
+        }
+        let capacity = self.per_minute as f64;
+        let per_second = capacity / 60.0;
+
+        let refilled = |bucket: &Bucket| bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * per_second;
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(&client) {
+            // A bucket that would have refilled is the same as no bucket
+            buckets.retain(|_, bucket| refilled(bucket) < capacity);
+            // Still full: the fullest bucket is the one least changed by a reset
+            if buckets.len() >= MAX_TRACKED_CLIENTS {
+                let fullest = buckets
+                    .iter()
+                    .max_by(|(_, a), (_, b)| refilled(a).total_cmp(&refilled(b)))
+                    .map(|(ip, _)| *ip);
+                if let Some(ip) = fullest {
+                    buckets.remove(&ip);
+                }
+            }
+        }
+        let bucket = buckets.entry(client).or_insert(Bucket { tokens: capacity, updated: now });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+
+    /// The address requests are counted against, if there is one.
+    fn client(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> Option<IpAddr> {
+        if self.trust_proxy {
+            let forwarded = headers
+                .get_all("x-forwarded-for")
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|value| value.split(','))
+                .next_back()
+                .and_then(|addr| addr.trim().parse().ok());
+            if forwarded.is_some() {
+                return forwarded;
+            }
+        }
+        peer
+    }
+}
+
+/// The client's address as the rate limiter sees it, honoring
+/// RATE_LIMIT_TRUST_PROXY; `None` without a real connection, e.g. in tests.
+pub struct ClientAddr(pub Option<IpAddr>);
+
+impl FromRequestParts<AppState> for ClientAddr {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        Ok(ClientAddr(state.rate_limiter.client(&parts.headers, peer)))
+    }
+}
+
+/// Middleware rejecting clients over their limit with 429 Too Many Requests.
+///
+/// Requests with no known client address, e.g. in tests without a real connection,
+/// are let through.
+pub async fn limit(State(limiter): State<Arc<RateLimiter>>, request: Request, next: Next) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let Some(client) = limiter.client(request.headers(), peer) else {
+        return next.run(request).await;
+    };
+    match limiter.acquire(client, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            tracing::info!("Rate limited {} on {}", client, request.uri().path());
+            let seconds = wait.as_secs_f64().ceil().max(1.0) as u64;
+            let res = ErrorResponse {
+                success: false,
+                got_it: false,
+                errors: vec![format!("Too many requests. Try again in {} seconds.", seconds)],
+            };
+            let mut response = (StatusCode::TOO_MANY_REQUESTS, Json(res)).into_response();
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_a_burst_then_refills() {
+        let limiter = RateLimiter::new(2, false);
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        let start = Instant::now();
+
+        assert!(limiter.acquire(client, start).is_ok());
+        assert!(limiter.acquire(client, start).is_ok());
+        let wait = limiter.acquire(client, start).unwrap_err();
+        assert_eq!(wait.as_secs(), 30);
+        // Other clients have their own bucket
+        assert!(limiter.acquire("203.0.113.8".parse().unwrap(), start).is_ok());
+
+        assert!(limiter.acquire(client, start + Duration::from_secs(30)).is_ok());
+        assert!(limiter.acquire(client, start + Duration::from_secs(30)).is_err());
+
+        assert!(RateLimiter::disabled().acquire(client, start).is_ok());
+    }
+
+    #[test]
+    fn test_tracked_clients_are_capped() {
+        let limiter = RateLimiter::new(2, false);
+        let start = Instant::now();
+        // Every bucket is partly spent, so none can be dropped as idle
+        for i in 0..MAX_TRACKED_CLIENTS as u32 {
+            assert!(limiter.acquire(IpAddr::from(i.to_be_bytes()), start).is_ok());
+        }
+        let busy: IpAddr = "203.0.113.7".parse().unwrap();
+        assert!(limiter.acquire(busy, start).is_ok());
+        assert!(limiter.acquire(busy, start).is_ok());
+
+        assert!(limiter.acquire("203.0.113.8".parse().unwrap(), start).is_ok());
+        assert_eq!(limiter.buckets.lock().unwrap().len(), MAX_TRACKED_CLIENTS);
+        // The emptied bucket isn't the one displaced
+        assert!(limiter.acquire(busy, start).is_err());
+    }
+
+    #[test]
+    fn test_client_uses_forwarded_address_only_when_trusted() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_static("10.0.0.1, 198.51.100.4"));
+        let peer = Some("127.0.0.1".parse().unwrap());
+
+        let direct = RateLimiter::new(30, false);
+        assert_eq!(direct.client(&headers, peer), peer);
+
+        let proxied = RateLimiter::from_lookup(|key| (key == "RATE_LIMIT_TRUST_PROXY").then(|| "true".to_string()));
+        assert_eq!(proxied.per_minute, DEFAULT_REQUESTS_PER_MINUTE);
+        assert_eq!(proxied.client(&headers, peer), Some("198.51.100.4".parse().unwrap()));
+        assert_eq!(proxied.client(&HeaderMap::new(), peer), peer);
+    }
+}
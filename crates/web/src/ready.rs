@@ -0,0 +1,121 @@
+//! Readiness probe: whether this instance can serve requests right now.
+//!
+//! `/api/health` only says the process is up. `/api/ready` checks each dependency:
+//! the cache backend (S3 and DynamoDB, Redis, or the cache directory), the
+//! geometry engine's credentials, and the configuration, order, and API key
+//! stores. It answers 503 if any is unreachable, so a load balancer stops routing
+//! to the instance until it recovers.
+
+use serde::Serialize;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
+
+use crate::AppStateInner;
+
+/// Longest a single dependency check may take before it counts as failed.
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long an engine check result is reused. Checking starts the zoo CLI and
+/// calls the Zoo API, which is too slow and costly to do on every probe.
+const ENGINE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// State of one dependency.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DependencyStatus {
+    /// Dependency name: `cache`, `engine`, `configs`, `orders`, or `api_keys`
+    pub name: &'static str,
+    /// Whether the check passed
+    pub ok: bool,
+    /// Why the check failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// How long the check took, in milliseconds (0 for a remembered engine check)
+    pub latency_ms: u64,
+}
+
+/// Readiness of the instance and each dependency.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    /// True when every dependency is ok
+    pub ready: bool,
+    pub dependencies: Vec<DependencyStatus>,
+}
+
+/// Remembers the last engine check between probes.
+#[derive(Default)]
+pub struct ReadinessProbe {
+    engine: Mutex<Option<(Instant, Result<(), String>)>>,
+}
+
+/// Run `check` with [`CHECK_TIMEOUT`], timing it.
+async fn probe<E: std::fmt::Display>(
+    name: &'static str,
+    check: impl Future<Output = Result<(), E>>,
+) -> DependencyStatus {
+    let start = Instant::now();
+    let result = match tokio::time::timeout(CHECK_TIMEOUT, check).await {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(_) => Err(format!("no answer within {} seconds", CHECK_TIMEOUT.as_secs())),
+    };
+    DependencyStatus {
+        name,
+        ok: result.is_ok(),
+        error: result.err(),
+        latency_ms: start.elapsed().as_millis() as u64,
+    }
+}
+
+impl ReadinessProbe {
+    async fn check_engine(&self, state: &AppStateInner) -> DependencyStatus {
+        if let Some((checked_at, result)) = self.engine.lock().unwrap().as_ref() {
+            if checked_at.elapsed() < ENGINE_CHECK_INTERVAL {
+                return DependencyStatus {
+                    name: "engine",
+                    ok: result.is_ok(),
+                    error: result.clone().err(),
+                    latency_ms: 0,
+                };
+            }
+        }
+
+        let engine = state.engine.clone();
+        let status = probe("engine", async move {
+            tokio::task::spawn_blocking(move || engine.check())
+                .await
+                .unwrap_or_else(|e| Err(format!("engine check crashed: {}", e)))
+        })
+        .await;
+        let result = match &status.error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        };
+        *self.engine.lock().unwrap() = Some((Instant::now(), result));
+        status
+    }
+}
+
+/// Check every dependency at once.
+pub async fn check(state: &Arc<AppStateInner>) -> ReadinessResponse {
+    let (cache, engine, configs, orders, api_keys, audit) = tokio::join!(
+        probe("cache", state.cache.check()),
+        state.readiness.check_engine(state),
+        probe("configs", state.configs.check()),
+        probe("orders", state.orders.check()),
+        probe("api_keys", state.api_keys.check()),
+        probe("audit", state.audit.check()),
+    );
+    let dependencies = vec![cache, engine, configs, orders, api_keys, audit];
+    for failed in dependencies.iter().filter(|d| !d.ok) {
+        tracing::warn!(
+            "Readiness check {} failed: {}",
+            failed.name,
+            failed.error.as_deref().unwrap_or("unknown error")
+        );
+    }
+    ReadinessResponse {
+        ready: dependencies.iter().all(|d| d.ok),
+        dependencies,
+    }
+}
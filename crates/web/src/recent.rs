@@ -0,0 +1,174 @@
+//! Plates each browser recently generated, so the configurator can offer them
+//! again after a reload.
+//!
+//! A browser's list lives in a cookie session (`platerator_session`, HttpOnly,
+//! SameSite=Lax) that is only created once it generates something, and expires
+//! after 30 days without one. Sessions are kept in memory, so a restart forgets
+//! them, and at most [`MAX_SESSIONS`] are kept: when full, expired sessions are
+//! dropped first, then the ones idle longest.
+
+use async_trait::async_trait;
+use domain::ActuatorPlate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tower_sessions::cookie::time::{Duration, OffsetDateTime};
+use tower_sessions::cookie::SameSite;
+use tower_sessions::session::{Id, Record};
+use tower_sessions::session_store::{self, SessionStore};
+use tower_sessions::{Expiry, Session, SessionManagerLayer};
+use utoipa::ToSchema;
+
+/// Plates remembered per browser.
+pub const MAX_RECENT: usize = 10;
+
+/// Sessions kept before the idlest are dropped.
+pub const MAX_SESSIONS: usize = 10_000;
+
+const COOKIE_NAME: &str = "platerator_session";
+const RECENT_KEY: &str = "recent_plates";
+
+/// A plate this browser generated.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RecentPlate {
+    pub plate: ActuatorPlate,
+    pub cache_key: String,
+    /// RFC 3339 time it was last submitted
+    pub submitted_at: String,
+}
+
+/// The session layer for the routes that read or record recent plates.
+pub fn layer() -> SessionManagerLayer<BoundedMemoryStore> {
+    SessionManagerLayer::new(BoundedMemoryStore::new(MAX_SESSIONS))
+        .with_name(COOKIE_NAME)
+        .with_same_site(SameSite::Lax)
+        .with_expiry(Expiry::OnInactivity(Duration::days(30)))
+}
+
+/// This browser's recent plates, newest first. A session that can't be read is
+/// treated as empty.
+pub async fn list(session: &Session) -> Vec<RecentPlate> {
+    match session.get::<Vec<RecentPlate>>(RECENT_KEY).await {
+        Ok(recent) => recent.unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!("Failed to read recent plates: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Put `plate` at the top of this browser's recent plates, moving it there if it
+/// was already listed. A failed write is only logged.
+pub async fn remember(session: &Session, plate: &ActuatorPlate) {
+    let cache_key = plate.cache_key();
+    let mut recent = list(session).await;
+    recent.retain(|entry| entry.cache_key != cache_key);
+    recent.insert(
+        0,
+        RecentPlate {
+            plate: *plate,
+            cache_key,
+            submitted_at: chrono::Utc::now().to_rfc3339(),
+        },
+    );
+    recent.truncate(MAX_RECENT);
+    if let Err(e) = session.insert(RECENT_KEY, recent).await {
+        tracing::warn!("Failed to record a recent plate: {}", e);
+    }
+}
+
+/// Sessions in memory, at most `capacity` of them.
+#[derive(Clone, Debug)]
+pub struct BoundedMemoryStore {
+    records: Arc<Mutex<HashMap<Id, Record>>>,
+    capacity: usize,
+}
+
+impl BoundedMemoryStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(HashMap::new())),
+            capacity,
+        }
+    }
+
+    /// Store `record`, making room if it's new and the store is full.
+    fn insert(&self, record: Record) {
+        let mut records = self.records.lock().unwrap();
+        if !records.contains_key(&record.id) && records.len() >= self.capacity {
+            let now = OffsetDateTime::now_utc();
+            records.retain(|_, record| record.expiry_date > now);
+            // Expiry is measured from last use, so the soonest to expire is the idlest
+            if records.len() >= self.capacity {
+                let idlest = records.values().min_by_key(|record| record.expiry_date).map(|record| record.id);
+                if let Some(id) = idlest {
+                    records.remove(&id);
+                }
+            }
+        }
+        records.insert(record.id, record);
+    }
+}
+
+#[async_trait]
+impl SessionStore for BoundedMemoryStore {
+    async fn create(&self, record: &mut Record) -> session_store::Result<()> {
+        while self.records.lock().unwrap().contains_key(&record.id) {
+            record.id = Id::default();
+        }
+        self.insert(record.clone());
+        Ok(())
+    }
+
+    async fn save(&self, record: &Record) -> session_store::Result<()> {
+        self.insert(record.clone());
+        Ok(())
+    }
+
+    async fn load(&self, session_id: &Id) -> session_store::Result<Option<Record>> {
+        let records = self.records.lock().unwrap();
+        let now = OffsetDateTime::now_utc();
+        Ok(records.get(session_id).filter(|record| record.expiry_date > now).cloned())
+    }
+
+    async fn delete(&self, session_id: &Id) -> session_store::Result<()> {
+        self.records.lock().unwrap().remove(session_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(expires_in: Duration) -> Record {
+        Record {
+            id: Id::default(),
+            data: HashMap::new(),
+            expiry_date: OffsetDateTime::now_utc() + expires_in,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_full_store_drops_expired_then_idlest() {
+        let store = BoundedMemoryStore::new(2);
+        let mut expired = record(Duration::seconds(-1));
+        let mut idle = record(Duration::days(1));
+        let mut active = record(Duration::days(30));
+        store.create(&mut expired).await.unwrap();
+        store.create(&mut idle).await.unwrap();
+        store.create(&mut active).await.unwrap();
+        assert_eq!(store.load(&idle.id).await.unwrap(), Some(idle.clone()));
+        assert_eq!(store.records.lock().unwrap().len(), 2);
+
+        let mut newest = record(Duration::days(30));
+        store.create(&mut newest).await.unwrap();
+        assert_eq!(store.load(&idle.id).await.unwrap(), None);
+        assert!(store.load(&active.id).await.unwrap().is_some());
+        assert!(store.load(&newest.id).await.unwrap().is_some());
+
+        // Saving a session already stored doesn't evict anything
+        store.save(&active).await.unwrap();
+        assert!(store.load(&newest.id).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,243 @@
+//! Supervisor for background work.
+//!
+//! Long-lived workers (job runners, cache GC, cache warming) are started with
+//! [`Supervisor::spawn_worker`]: if one panics or returns, it is restarted with
+//! exponential backoff and its state is visible through [`Supervisor::health`].
+//! Short fire-and-forget jobs (cache writes, webhook and chat deliveries) go through
+//! [`Supervisor::spawn_task`] so that shutdown can wait for them to finish.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::Instrument;
+use utoipa::ToSchema;
+
+/// How long to wait before restarting a failed worker.
+#[derive(Clone, Copy, Debug)]
+pub struct RestartPolicy {
+    /// Delay before the first restart; doubled after each consecutive failure.
+    pub base_delay: Duration,
+    /// Upper bound on the delay. A worker that stayed up this long starts over at
+    /// `base_delay` the next time it fails.
+    pub max_delay: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Lifecycle state of a supervised worker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkerState {
+    Running,
+    /// Failed and waiting out its backoff before the next start.
+    Restarting,
+    /// Stopped by shutdown.
+    Stopped,
+}
+
+/// Health of one supervised worker.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub struct WorkerHealth {
+    pub name: String,
+    pub state: WorkerState,
+    /// Times the worker has been restarted since the process started
+    pub restarts: u32,
+    /// Why the worker last failed, if it ever has
+    pub last_error: Option<String>,
+}
+
+/// Owns the server's background tasks.
+pub struct Supervisor {
+    shutdown: CancellationToken,
+    tracker: TaskTracker,
+    workers: Arc<Mutex<BTreeMap<&'static str, WorkerHealth>>>,
+    restart: RestartPolicy,
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self::new(RestartPolicy::default())
+    }
+}
+
+impl Supervisor {
+    pub fn new(restart: RestartPolicy) -> Self {
+        Self {
+            shutdown: CancellationToken::new(),
+            tracker: TaskTracker::new(),
+            workers: Arc::new(Mutex::new(BTreeMap::new())),
+            restart,
+        }
+    }
+
+    /// Run a long-lived worker until shutdown, restarting it if it panics or returns.
+    ///
+    /// `worker` is called for each start and given a token that is cancelled on
+    /// shutdown; the worker should return promptly once it is.
+    pub fn spawn_worker<F, Fut>(&self, name: &'static str, worker: F)
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let workers = self.workers.clone();
+        let shutdown = self.shutdown.clone();
+        let policy = self.restart;
+        let set_state = move |state: WorkerState, error: Option<String>| {
+            let mut workers = workers.lock().unwrap();
+            let health = workers.entry(name).or_insert_with(|| WorkerHealth {
+                name: name.to_string(),
+                state,
+                restarts: 0,
+                last_error: None,
+            });
+            health.state = state;
+            if let Some(error) = error {
+                health.restarts += 1;
+                health.last_error = Some(error);
+            }
+        };
+
+        set_state(WorkerState::Running, None);
+        self.tracker.spawn(async move {
+            let mut delay = policy.base_delay;
+            loop {
+                let started = Instant::now();
+                let result = tokio::spawn(worker(shutdown.child_token())).await;
+                if shutdown.is_cancelled() {
+                    set_state(WorkerState::Stopped, None);
+                    return;
+                }
+
+                let error = match result {
+                    Ok(()) => "exited unexpectedly".to_string(),
+                    Err(e) if e.is_panic() => format!("panicked: {}", panic_message(e.into_panic())),
+                    Err(e) => e.to_string(),
+                };
+                if started.elapsed() >= policy.max_delay {
+                    delay = policy.base_delay;
+                }
+                tracing::error!("Worker {} {}; restarting in {:?}", name, error, delay);
+                set_state(WorkerState::Restarting, Some(error));
+
+                tokio::select! {
+                    _ = shutdown.cancelled() => {
+                        set_state(WorkerState::Stopped, None);
+                        return;
+                    }
+                    _ = tokio::time::sleep(delay) => {}
+                }
+                delay = (delay * 2).min(policy.max_delay);
+                set_state(WorkerState::Running, None);
+            }
+        });
+    }
+
+    /// Run a one-off background job. It is not restarted, but shutdown waits for it.
+    pub fn spawn_task<Fut>(&self, name: &'static str, task: Fut)
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        // Keep the caller's span, so the task's logs carry its request ID
+        let task = task.instrument(tracing::Span::current());
+        self.tracker.spawn(async move {
+            if let Err(e) = tokio::spawn(task).await {
+                tracing::error!("Background task {} failed: {}", name, e);
+            }
+        });
+    }
+
+    /// Health of every worker started with [`Supervisor::spawn_worker`], by name.
+    pub fn health(&self) -> Vec<WorkerHealth> {
+        self.workers.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Signal every worker to stop and wait up to `grace` for all tasks to finish.
+    /// Returns false if some were still running when the grace period ran out.
+    pub async fn shutdown(&self, grace: Duration) -> bool {
+        self.shutdown.cancel();
+        self.tracker.close();
+        if tokio::time::timeout(grace, self.tracker.wait()).await.is_err() {
+            tracing::warn!(
+                "{} background tasks still running after {:?}; abandoning them",
+                self.tracker.len(),
+                grace
+            );
+            return false;
+        }
+        true
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string payload".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_restarts() -> Supervisor {
+        Supervisor::new(RestartPolicy {
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_worker_is_restarted_after_panic() {
+        let supervisor = fast_restarts();
+        let starts = Arc::new(AtomicU32::new(0));
+        let counter = starts.clone();
+        supervisor.spawn_worker("flaky", move |shutdown| {
+            let start = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if start < 2 {
+                    panic!("boom {}", start);
+                }
+                shutdown.cancelled().await;
+            }
+        });
+
+        while starts.load(Ordering::SeqCst) < 3 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        let health = &supervisor.health()[0];
+        assert_eq!(health.name, "flaky");
+        assert_eq!(health.state, WorkerState::Running);
+        assert_eq!(health.restarts, 2);
+        assert_eq!(health.last_error.as_deref(), Some("panicked: boom 1"));
+
+        assert!(supervisor.shutdown(Duration::from_secs(1)).await);
+        assert_eq!(supervisor.health()[0].state, WorkerState::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_tasks() {
+        let supervisor = fast_restarts();
+        let done = Arc::new(AtomicU32::new(0));
+        let flag = done.clone();
+        supervisor.spawn_task("slow", async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            flag.store(1, Ordering::SeqCst);
+        });
+
+        assert!(supervisor.shutdown(Duration::from_secs(1)).await);
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+    }
+}
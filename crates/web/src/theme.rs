@@ -0,0 +1,139 @@
+//! The configurator page, served with the browser's theme already applied.
+//!
+//! The frontend keeps the theme in localStorage and mirrors it into two cookies,
+//! `theme-color-scheme` and `theme-mode`. When serving `index.html` the server
+//! reads them back and writes `data-theme` and the `dark` class onto `<html>`, so
+//! the page arrives in the right colors instead of waiting for a script. For the
+//! `system` mode it asks for the `Sec-CH-Prefers-Color-Scheme` client hint;
+//! browsers that don't send it get the light theme until the page's own script
+//! checks the preference.
+
+use axum::http::{header, HeaderMap, HeaderName, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+
+/// The built frontend's entry page.
+const INDEX_PATH: &str = "dist/index.html";
+
+/// Cookie names, the same as the frontend's localStorage keys.
+const COLOR_SCHEME_COOKIE: &str = "theme-color-scheme";
+const MODE_COOKIE: &str = "theme-mode";
+
+/// The frontend's color schemes; anything else in the cookie is ignored. A
+/// `custom` palette lives only in localStorage, so the server draws it on the
+/// neutral scheme and the page's script adds its colors.
+const COLOR_SCHEMES: [&str; 6] = ["neutral", "blue", "green", "rose", "cyber", "custom"];
+const DEFAULT_COLOR_SCHEME: &str = "neutral";
+
+const PREFERS_COLOR_SCHEME: HeaderName = HeaderName::from_static("sec-ch-prefers-color-scheme");
+
+/// How the page should be drawn before any script runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    pub color_scheme: &'static str,
+    pub dark: bool,
+}
+
+impl Theme {
+    /// The theme in the request's cookies, with the default neutral scheme and
+    /// system mode for whatever is missing or unknown.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let cookie = |name: &str| {
+            headers
+                .get_all(header::COOKIE)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|value| value.split(';'))
+                .filter_map(|pair| pair.trim().split_once('='))
+                .find_map(|(key, value)| (key == name).then_some(value))
+        };
+        let color_scheme = cookie(COLOR_SCHEME_COOKIE)
+            .and_then(|stored| COLOR_SCHEMES.into_iter().find(|scheme| *scheme == stored))
+            .unwrap_or(DEFAULT_COLOR_SCHEME);
+        let prefers_dark = || {
+            headers
+                .get(PREFERS_COLOR_SCHEME)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.trim_matches('"') == "dark")
+        };
+        // Cyber is always dark, as in the frontend's ThemeProvider
+        let dark = color_scheme == "cyber"
+            || match cookie(MODE_COOKIE) {
+                Some("dark") => true,
+                Some("light") => false,
+                _ => prefers_dark(),
+            };
+        Theme { color_scheme, dark }
+    }
+
+    /// `html` with the theme's attributes on its `<html>` tag.
+    pub fn apply(self, html: &str) -> String {
+        let Some(at) = html.find("<html").map(|at| at + "<html".len()) else {
+            return html.to_string();
+        };
+        let class = if self.dark { " class=\"dark\"" } else { "" };
+        format!("{} data-theme=\"{}\"{}{}", &html[..at], self.color_scheme, class, &html[at..])
+    }
+}
+
+/// Serve `index.html` in the browser's theme. Used for `/` and as the SPA fallback.
+pub async fn index(headers: HeaderMap) -> Response {
+    let html = match tokio::fs::read_to_string(INDEX_PATH).await {
+        Ok(html) => html,
+        Err(_) => return StatusCode::NOT_FOUND.into_response(),
+    };
+    (
+        [
+            // The page differs per browser, so it mustn't be cached as one
+            (header::CACHE_CONTROL, "no-cache"),
+            (header::VARY, "Cookie, Sec-CH-Prefers-Color-Scheme"),
+            (HeaderName::from_static("accept-ch"), "Sec-CH-Prefers-Color-Scheme"),
+        ],
+        Html(Theme::from_headers(&headers).apply(&html)),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_theme_comes_from_cookies() {
+        let theme = Theme::from_headers(&headers(&[("cookie", "platerator_session=abc; theme-color-scheme=rose; theme-mode=dark")]));
+        assert_eq!(theme, Theme { color_scheme: "rose", dark: true });
+
+        // Unknown values fall back rather than reaching the page
+        let theme = Theme::from_headers(&headers(&[("cookie", "theme-color-scheme=\"><script>; theme-mode=light")]));
+        assert_eq!(theme, Theme { color_scheme: "neutral", dark: false });
+
+        assert!(Theme::from_headers(&headers(&[("cookie", "theme-color-scheme=cyber; theme-mode=light")])).dark);
+    }
+
+    #[test]
+    fn test_system_mode_follows_the_client_hint() {
+        let system = [("cookie", "theme-mode=system"), ("sec-ch-prefers-color-scheme", "\"dark\"")];
+        assert!(Theme::from_headers(&headers(&system)).dark);
+        assert!(Theme::from_headers(&headers(&system[1..])).dark);
+        assert!(!Theme::from_headers(&headers(&system[..1])).dark);
+    }
+
+    #[test]
+    fn test_apply_marks_the_html_tag() {
+        let html = "<!doctype html>\n<html lang=\"en\">\n<head></head></html>";
+        assert_eq!(
+            Theme { color_scheme: "blue", dark: true }.apply(html),
+            "<!doctype html>\n<html data-theme=\"blue\" class=\"dark\" lang=\"en\">\n<head></head></html>"
+        );
+        assert!(Theme { color_scheme: "neutral", dark: false }.apply(html).contains("<html data-theme=\"neutral\" lang"));
+        assert_eq!(Theme { color_scheme: "neutral", dark: true }.apply("no tag"), "no tag");
+    }
+}
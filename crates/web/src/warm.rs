@@ -0,0 +1,158 @@
+//! Cache warming for the standard presets, so the first visitor to pick one sees
+//! it at once instead of waiting on the engine.
+//!
+//! With `cache.warm_presets` (CACHE_WARM_PRESETS) on, every preset that isn't
+//! cached is generated one at a time after startup, and again every
+//! `cache.warm_interval_secs` (CACHE_WARM_INTERVAL_SECS) if set, which re-creates
+//! presets a cache TTL expired. Warming goes through the same in-flight map as
+//! requests, so a visitor asking for a preset while it's warming shares that
+//! generation. Each generation spends engine credits, and counts against the Zoo
+//! quota, like any other; warming stops when the quota runs out.
+
+use std::time::Duration;
+
+use domain::presets::PRESETS;
+use parametric::generate_model_with_limits;
+use tokio_util::sync::CancellationToken;
+
+use crate::inflight::Claim;
+use crate::AppState;
+
+/// What one pass over the presets did.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WarmSummary {
+    pub generated: usize,
+    /// Cached already, or generated by a request meanwhile
+    pub cached: usize,
+    pub failed: usize,
+}
+
+/// Warm the presets in the background now, then every `interval` if given.
+pub fn spawn(state: &AppState, interval: Option<Duration>) {
+    let worker_state = state.clone();
+    state.supervisor.spawn_worker("preset-warming", move |shutdown| {
+        let state = worker_state.clone();
+        async move {
+            loop {
+                let summary = warm_presets(&state, &shutdown).await;
+                tracing::info!(
+                    "Warmed presets: {} generated, {} already cached, {} failed",
+                    summary.generated,
+                    summary.cached,
+                    summary.failed
+                );
+                // Returning would restart the worker, so wait for shutdown instead
+                let Some(interval) = interval else {
+                    return shutdown.cancelled().await;
+                };
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+            }
+        }
+    });
+}
+
+/// Generate and cache each preset that isn't cached, stopping early on shutdown.
+pub async fn warm_presets(state: &AppState, shutdown: &CancellationToken) -> WarmSummary {
+    let mut summary = WarmSummary::default();
+    for preset in PRESETS {
+        if shutdown.is_cancelled() {
+            break;
+        }
+        let cache_key = preset.plate.cache_key();
+        if state.cache.exists(&cache_key).await {
+            summary.cached += 1;
+            continue;
+        }
+        let lead = match state.in_flight.claim(&cache_key).await {
+            Claim::Lead(lead) => lead,
+            Claim::Shared(Ok(_)) => {
+                summary.cached += 1;
+                continue;
+            }
+            Claim::Shared(Err(_)) => {
+                summary.failed += 1;
+                continue;
+            }
+        };
+
+        // Later presets would be refused too
+        if let Err(e) = crate::spend_zoo_quota(state) {
+            tracing::warn!("Stopped warming presets at {}: {}", preset.id, e);
+            summary.failed += 1;
+            break;
+        }
+
+        let started = std::time::Instant::now();
+        let generated = {
+            let state = state.clone();
+            let plate = preset.plate;
+            tokio::task::spawn_blocking(move || generate_model_with_limits(&plate, state.engine.as_ref(), &state.limits)).await
+        };
+        let Ok(generated) = generated else {
+            tracing::error!("Warming preset {} panicked", preset.id);
+            summary.failed += 1;
+            continue;
+        };
+        crate::observe_generation(state, &cache_key, &generated, started.elapsed());
+        match generated {
+            Ok(result) => {
+                let files = crate::read_generated(&result).await;
+                lead.finish(Ok(files.clone()));
+                match state.cache.put(&cache_key, &files).await {
+                    Ok(()) => summary.generated += 1,
+                    Err(e) => {
+                        tracing::warn!("Failed to cache preset {}: {}", preset.id, e);
+                        summary.failed += 1;
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to warm preset {}: {:?}", preset.id, e);
+                if let parametric::AllErrors::GeneratorError(msg) = e {
+                    lead.finish(Err(msg));
+                }
+                summary.failed += 1;
+            }
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AppStateInner, Artifact, CachedFiles, MemoryCache, ModelCache};
+    use parametric::mock::MockBackend;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_uncached_presets_are_generated_once() {
+        let cache = Arc::new(MemoryCache::new());
+        let backend = Arc::new(MockBackend::new());
+        let mut inner = AppStateInner::new(cache.clone());
+        inner.engine = backend.clone();
+        let state: AppState = Arc::new(inner);
+        let files = CachedFiles::new().with(Artifact::Step, "step");
+        cache.put(&PRESETS[0].plate.cache_key(), &files).await.unwrap();
+
+        let summary = warm_presets(&state, &CancellationToken::new()).await;
+        let expected = WarmSummary { generated: PRESETS.len() - 1, cached: 1, failed: 0 };
+        assert_eq!(summary, expected);
+        assert_eq!(backend.generated(), PRESETS.len() - 1);
+        for preset in PRESETS {
+            assert!(cache.exists(&preset.plate.cache_key()).await, "{} wasn't cached", preset.id);
+        }
+
+        let again = warm_presets(&state, &CancellationToken::new()).await;
+        assert_eq!(again, WarmSummary { cached: PRESETS.len(), ..WarmSummary::default() });
+        assert_eq!(backend.generated(), PRESETS.len() - 1);
+
+        // Nothing is looked at after shutdown
+        let stopped = CancellationToken::new();
+        stopped.cancel();
+        assert_eq!(warm_presets(&state, &stopped).await, WarmSummary::default());
+    }
+}
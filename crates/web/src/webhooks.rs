@@ -0,0 +1,674 @@
+//! Generic outgoing webhooks.
+//!
+//! Operators register endpoints through the admin API, each subscribed to a set of
+//! events and holding its own signing secret. Deliveries are JSON bodies signed with
+//! HMAC-SHA256 and retried with exponential backoff, so integrations can consume
+//! events without bespoke code in this crate.
+//!
+//! API clients can register endpoints of their own through `/api/webhooks`. Those
+//! belong to the client's API key and only receive events for work started with
+//! that key, such as its generation jobs finishing. Every delivery attempt is kept
+//! in a bounded log, so a client can see why its endpoint isn't receiving events.
+//! Because that log shows what the endpoint answered, client endpoints must be
+//! https URLs on public addresses (see [`check_client_target`]), checked when
+//! they're registered and before each delivery. Deliveries to them also resolve the
+//! host through [`PublicResolver`], which never hands the connection a private
+//! address, so a host can't pass the check and then rebind somewhere internal.
+
+use crate::supervisor::Supervisor;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::VecDeque;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Header carrying the delivery signature: `t=<unix seconds>,v1=<hex hmac>`.
+pub const SIGNATURE_HEADER: &str = "x-platerator-signature";
+
+/// Events an endpoint can subscribe to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum WebhookEvent {
+    /// A new plate design was generated.
+    #[serde(rename = "design.created")]
+    DesignCreated,
+    /// A background generation job finished.
+    #[serde(rename = "job.completed")]
+    JobCompleted,
+    /// An order was paid for, which staff record by moving it to `confirmed`.
+    #[serde(rename = "order.paid")]
+    OrderPaid,
+}
+
+impl WebhookEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookEvent::DesignCreated => "design.created",
+            WebhookEvent::JobCompleted => "job.completed",
+            WebhookEvent::OrderPaid => "order.paid",
+        }
+    }
+}
+
+/// A registered webhook endpoint.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct WebhookEndpoint {
+    /// Endpoint identifier, used to delete it
+    pub id: String,
+    /// URL that receives POSTed events
+    pub url: String,
+    /// Events delivered to this endpoint
+    pub events: Vec<WebhookEvent>,
+    /// Shared secret used to sign deliveries (only returned when the endpoint is created)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// ID of the API key that registered the endpoint; `None` for operator endpoints,
+    /// which receive every event they subscribe to
+    #[serde(skip)]
+    pub owner: Option<String>,
+}
+
+/// Thread-safe set of registered webhook endpoints.
+#[derive(Default)]
+pub struct WebhookRegistry {
+    endpoints: RwLock<Vec<WebhookEndpoint>>,
+}
+
+impl WebhookRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an endpoint, generating a secret if none is given.
+    /// Returns the endpoint including its secret.
+    pub fn register(
+        &self,
+        url: String,
+        events: Vec<WebhookEvent>,
+        secret: Option<String>,
+        owner: Option<String>,
+    ) -> WebhookEndpoint {
+        let endpoint = WebhookEndpoint {
+            id: Uuid::new_v4().to_string(),
+            url,
+            events,
+            secret: Some(secret.unwrap_or_else(|| Uuid::new_v4().simple().to_string())),
+            owner,
+        };
+        self.endpoints.write().unwrap().push(endpoint.clone());
+        endpoint
+    }
+
+    /// List endpoints with their secrets redacted.
+    pub fn list(&self) -> Vec<WebhookEndpoint> {
+        self.endpoints
+            .read()
+            .unwrap()
+            .iter()
+            .map(|e| WebhookEndpoint {
+                secret: None,
+                ..e.clone()
+            })
+            .collect()
+    }
+
+    /// List the endpoints registered by the API key `owner`, secrets redacted.
+    pub fn list_owned(&self, owner: &str) -> Vec<WebhookEndpoint> {
+        self.list()
+            .into_iter()
+            .filter(|e| e.owner.as_deref() == Some(owner))
+            .collect()
+    }
+
+    /// Whether an endpoint with this id exists and, given an `owner`, belongs to it.
+    pub fn contains(&self, id: &str, owner: Option<&str>) -> bool {
+        self.endpoints
+            .read()
+            .unwrap()
+            .iter()
+            .any(|e| e.id == id && owner.is_none_or(|owner| e.owner.as_deref() == Some(owner)))
+    }
+
+    /// Remove an endpoint. Returns false if no endpoint had that id.
+    pub fn remove(&self, id: &str) -> bool {
+        let mut endpoints = self.endpoints.write().unwrap();
+        let before = endpoints.len();
+        endpoints.retain(|e| e.id != id);
+        endpoints.len() != before
+    }
+
+    /// Remove an endpoint registered by the API key `owner`. Returns false if it
+    /// has no endpoint with that id.
+    pub fn remove_owned(&self, id: &str, owner: &str) -> bool {
+        let mut endpoints = self.endpoints.write().unwrap();
+        let before = endpoints.len();
+        endpoints.retain(|e| e.id != id || e.owner.as_deref() != Some(owner));
+        endpoints.len() != before
+    }
+
+    /// Endpoints subscribed to `event`: every operator endpoint, plus those of the
+    /// API key `owner` the event concerns.
+    fn subscribers(&self, event: WebhookEvent, owner: Option<&str>) -> Vec<WebhookEndpoint> {
+        self.endpoints
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|e| e.events.contains(&event))
+            .filter(|e| e.owner.is_none() || e.owner.as_deref() == owner)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Compute the signature header value for a delivery body.
+///
+/// The signed message is `<timestamp>.<body>`, so receivers can reject replays by
+/// checking the timestamp before comparing signatures.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("t={},v1={}", timestamp, hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Delivery retry policy.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles after each failure.
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Why an API client's endpoint URL was refused.
+#[derive(Debug, thiserror::Error)]
+pub enum TargetError {
+    #[error("Webhook URL must be an https:// URL")]
+    NotHttps,
+    #[error("Webhook host {0} could not be resolved")]
+    Unresolved(String),
+    #[error("Webhook URL must not point at a loopback, private, link-local, or reserved address")]
+    NotPublic,
+}
+
+/// Check that an API client may send deliveries to `url`: it must be https and
+/// every address its host resolves to must be public, so a client can't use the
+/// server to reach (and, through the delivery log, read) internal services such as
+/// the cloud metadata endpoint at 169.254.169.254.
+pub async fn check_client_target(url: &str) -> Result<(), TargetError> {
+    let url = reqwest::Url::parse(url).map_err(|_| TargetError::NotHttps)?;
+    if url.scheme() != "https" {
+        return Err(TargetError::NotHttps);
+    }
+    let host = url.host_str().ok_or(TargetError::NotHttps)?;
+    // IPv6 literals keep their brackets in the URL
+    let addrs: Vec<IpAddr> = match host.trim_start_matches('[').trim_end_matches(']').parse() {
+        Ok(ip) => vec![ip],
+        Err(_) => tokio::net::lookup_host((host, url.port_or_known_default().unwrap_or(443)))
+            .await
+            .map_err(|_| TargetError::Unresolved(host.to_string()))?
+            .map(|addr| addr.ip())
+            .collect(),
+    };
+    if addrs.is_empty() {
+        return Err(TargetError::Unresolved(host.to_string()));
+    }
+    if addrs.into_iter().all(is_public) {
+        Ok(())
+    } else {
+        Err(TargetError::NotPublic)
+    }
+}
+
+/// Not loopback, private, link-local, unspecified, or any other range that isn't
+/// routed on the public internet. IPv4 addresses mapped into IPv6 are judged as
+/// IPv4; NAT64 and Teredo addresses, which reach IPv4 hosts through a translator,
+/// are refused outright.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4() {
+            // Mapped (::ffff:a.b.c.d) and the deprecated compatible (::a.b.c.d) forms
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // "This network", 0.0.0.0/8
+        || a == 0
+        // Carrier-grade NAT, 100.64.0.0/10
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments, 192.0.0.0/24
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking, 198.18.0.0/15
+        || (a == 198 && (18..20).contains(&b))
+        // Reserved, 240.0.0.0/4
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let [a, b, ..] = ip.segments();
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local()
+        // NAT64, 64:ff9b::/96 and the local-use 64:ff9b:1::/48
+        || (a == 0x64 && b == 0xff9b)
+        // Teredo, 2001::/32, and documentation, 2001:db8::/32
+        || (a == 0x2001 && (b == 0 || b == 0xdb8))
+        // Discard-only, 100::/64
+        || (a == 0x100 && ip.segments()[1..4] == [0, 0, 0]))
+}
+
+/// Resolves the hosts of API clients' endpoints for delivery, dropping every
+/// address that isn't public. A host with none left fails to connect with
+/// [`TargetError::NotPublic`]. [`check_client_target`] runs before each delivery
+/// too, but resolving again here means the address connected to is the one checked.
+struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(TargetError::NotPublic.into());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// How long an endpoint gets to answer one delivery attempt.
+pub const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delivery attempts kept in the log, across all endpoints.
+pub const DELIVERY_LOG_LIMIT: usize = 1000;
+
+/// One attempt to deliver an event to an endpoint.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct WebhookDelivery {
+    /// Endpoint the event was sent to
+    pub endpoint_id: String,
+    /// Event ID, the same across retries of one event
+    pub event_id: String,
+    pub event: WebhookEvent,
+    /// Attempt number, starting at 1
+    pub attempt: u32,
+    /// When the attempt finished (RFC 3339)
+    pub at: String,
+    /// HTTP status the endpoint answered with, if it answered
+    pub status_code: Option<u16>,
+    /// Whether the endpoint accepted the event with a 2xx status
+    pub succeeded: bool,
+    /// Why the attempt failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Sends events to every subscribed endpoint in the registry.
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+    /// For API clients' endpoints: connects only to public addresses
+    public_client: reqwest::Client,
+    pub registry: WebhookRegistry,
+    pub retry: RetryPolicy,
+    allow_private_targets: bool,
+    log: Mutex<VecDeque<WebhookDelivery>>,
+}
+
+impl Default for WebhookDispatcher {
+    fn default() -> Self {
+        Self {
+            // A redirect could lead a checked endpoint somewhere private
+            client: reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .timeout(DELIVERY_TIMEOUT)
+                .build()
+                .expect("webhook HTTP client"),
+            public_client: reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .timeout(DELIVERY_TIMEOUT)
+                .dns_resolver(Arc::new(PublicResolver))
+                .build()
+                .expect("webhook HTTP client"),
+            registry: WebhookRegistry::new(),
+            retry: RetryPolicy::default(),
+            allow_private_targets: false,
+            log: Mutex::new(VecDeque::new()),
+        }
+    }
+}
+
+impl WebhookDispatcher {
+    /// Let API clients' endpoints use http and private addresses, skipping
+    /// [`check_client_target`]. For tests and closed networks only.
+    pub fn with_private_targets(mut self, allow: bool) -> Self {
+        self.allow_private_targets = allow;
+        self
+    }
+
+    /// Recent delivery attempts to the endpoint `endpoint_id`, newest first.
+    pub fn deliveries(&self, endpoint_id: &str) -> Vec<WebhookDelivery> {
+        self.log
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|d| d.endpoint_id == endpoint_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Check the URL of an endpoint registered by the API key `owner` with
+    /// [`check_client_target`]. Operator endpoints (no owner) aren't checked.
+    pub async fn check_target(&self, url: &str, owner: Option<&str>) -> Result<(), TargetError> {
+        if owner.is_none() || self.allow_private_targets {
+            return Ok(());
+        }
+        check_client_target(url).await
+    }
+
+    fn record(&self, delivery: WebhookDelivery) {
+        let mut log = self.log.lock().unwrap();
+        if log.len() == DELIVERY_LOG_LIMIT {
+            log.pop_front();
+        }
+        log.push_back(delivery);
+    }
+
+    /// Deliver an event to all subscribers in the background. `owner` is the ID of
+    /// the API key the event concerns, whose own endpoints also receive it.
+    pub fn dispatch(
+        self: &Arc<Self>,
+        supervisor: &Supervisor,
+        event: WebhookEvent,
+        owner: Option<&str>,
+        data: serde_json::Value,
+    ) {
+        let subscribers = self.registry.subscribers(event, owner);
+        if subscribers.is_empty() {
+            return;
+        }
+
+        let event_id = Uuid::new_v4().to_string();
+        let body = serde_json::json!({
+            "id": event_id,
+            "event": event.as_str(),
+            "created_at": chrono::Utc::now().to_rfc3339(),
+            "data": data,
+        })
+        .to_string();
+
+        for endpoint in subscribers {
+            let dispatcher = self.clone();
+            let body = body.clone();
+            let event_id = event_id.clone();
+            supervisor.spawn_task("webhook-delivery", async move {
+                dispatcher.deliver(&endpoint, event, &event_id, body).await;
+            });
+        }
+    }
+
+    async fn deliver(&self, endpoint: &WebhookEndpoint, event: WebhookEvent, event_id: &str, body: String) {
+        let secret = endpoint.secret.as_deref().unwrap_or_default();
+        let mut delay = self.retry.base_delay;
+
+        for attempt in 1..=self.retry.max_attempts {
+            let (status_code, result) = self.attempt(endpoint, secret, &body).await;
+            self.record(WebhookDelivery {
+                endpoint_id: endpoint.id.clone(),
+                event_id: event_id.to_string(),
+                event,
+                attempt,
+                at: chrono::Utc::now().to_rfc3339(),
+                status_code,
+                succeeded: result.is_ok(),
+                error: result.as_ref().err().cloned(),
+            });
+
+            match result {
+                Ok(()) => return,
+                Err(e) if attempt < self.retry.max_attempts => {
+                    tracing::info!(
+                        "Webhook {} delivery to {} failed (attempt {}): {}; retrying in {:?}",
+                        event.as_str(),
+                        endpoint.url,
+                        attempt,
+                        e,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Webhook {} delivery to {} failed after {} attempts: {}",
+                        event.as_str(),
+                        endpoint.url,
+                        attempt,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Send one delivery, returning the status the endpoint answered with, if it
+    /// answered, and why the attempt failed. The target is checked again each time,
+    /// since the host may resolve somewhere else than when it was registered.
+    async fn attempt(&self, endpoint: &WebhookEndpoint, secret: &str, body: &str) -> (Option<u16>, Result<(), String>) {
+        if let Err(e) = self.check_target(&endpoint.url, endpoint.owner.as_deref()).await {
+            return (None, Err(e.to_string()));
+        }
+        let client = if endpoint.owner.is_some() && !self.allow_private_targets {
+            &self.public_client
+        } else {
+            &self.client
+        };
+        let signature = sign(secret, chrono::Utc::now().timestamp(), body.as_bytes());
+        let result = client
+            .post(&endpoint.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .body(body.to_string())
+            .send()
+            .await;
+        match result {
+            Ok(response) if response.status().is_success() => (Some(response.status().as_u16()), Ok(())),
+            Ok(response) => (Some(response.status().as_u16()), Err(format!("Endpoint answered {}", response.status()))),
+            Err(e) => (None, Err(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_is_hmac_sha256_of_timestamp_and_body() {
+        // HMAC-SHA256(key = "secret", message = "1700000000.{}")
+        assert_eq!(
+            sign("secret", 1_700_000_000, b"{}"),
+            "t=1700000000,v1=b8569b78799ff9e3cbff0fc2d63a33a2b57f3282abd07c37ae5e8e7d79a5f163"
+        );
+        assert_ne!(sign("secret", 1_700_000_000, b"{}"), sign("other", 1_700_000_000, b"{}"));
+    }
+
+    #[test]
+    fn test_registry_redacts_secrets_and_filters_subscribers() {
+        let registry = WebhookRegistry::new();
+        let created = registry.register(
+            "https://erp.example/hooks".to_string(),
+            vec![WebhookEvent::OrderPaid],
+            None,
+            None,
+        );
+        assert!(created.secret.is_some());
+
+        let listed = registry.list();
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].secret.is_none());
+
+        assert_eq!(registry.subscribers(WebhookEvent::OrderPaid, None).len(), 1);
+        assert!(registry.subscribers(WebhookEvent::DesignCreated, None).is_empty());
+
+        assert!(registry.remove(&created.id));
+        assert!(!registry.remove(&created.id));
+    }
+
+    #[test]
+    fn test_client_endpoints_only_hear_about_their_own_work() {
+        let registry = WebhookRegistry::new();
+        let operator = registry.register("https://ops.example/hooks".to_string(), vec![WebhookEvent::JobCompleted], None, None);
+        let client = registry.register(
+            "https://ci.example/hooks".to_string(),
+            vec![WebhookEvent::JobCompleted],
+            None,
+            Some("key-1".to_string()),
+        );
+
+        let ids = |owner| -> Vec<String> {
+            registry.subscribers(WebhookEvent::JobCompleted, owner).into_iter().map(|e| e.id).collect()
+        };
+        assert_eq!(ids(Some("key-1")), [operator.id.as_str(), client.id.as_str()]);
+        assert_eq!(ids(Some("key-2")), [operator.id.as_str()]);
+        assert_eq!(ids(None), [operator.id.as_str()]);
+
+        assert_eq!(registry.list_owned("key-1").len(), 1);
+        assert!(registry.contains(&client.id, Some("key-1")));
+        assert!(!registry.contains(&client.id, Some("key-2")));
+        assert!(registry.contains(&client.id, None));
+        assert!(!registry.remove_owned(&client.id, "key-2"));
+        assert!(!registry.remove_owned(&operator.id, "key-1"));
+        assert!(registry.remove_owned(&client.id, "key-1"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_attempts_are_logged_per_endpoint() {
+        let dispatcher = Arc::new(WebhookDispatcher {
+            retry: RetryPolicy { max_attempts: 2, base_delay: Duration::from_millis(1) },
+            ..WebhookDispatcher::default()
+        });
+        // Nothing listens on port 9 (discard), so every attempt fails to connect
+        let endpoint = dispatcher.registry.register(
+            "http://127.0.0.1:9/hooks".to_string(),
+            vec![WebhookEvent::JobCompleted],
+            None,
+            None,
+        );
+        dispatcher.deliver(&endpoint, WebhookEvent::JobCompleted, "evt-1", "{}".to_string()).await;
+
+        let log = dispatcher.deliveries(&endpoint.id);
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].attempt, 2);
+        assert_eq!(log[0].event_id, "evt-1");
+        assert!(!log[0].succeeded);
+        assert!(log[0].status_code.is_none());
+        assert!(log[0].error.is_some());
+        assert!(dispatcher.deliveries("other").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_client_targets_must_be_public_https() {
+        assert!(check_client_target("https://93.184.216.34/hooks").await.is_ok());
+        assert!(matches!(check_client_target("http://93.184.216.34/hooks").await, Err(TargetError::NotHttps)));
+        assert!(matches!(check_client_target("not a url").await, Err(TargetError::NotHttps)));
+        for url in [
+            "https://127.0.0.1/hooks",
+            "https://localhost:8443/hooks",
+            "https://10.1.2.3/hooks",
+            "https://192.168.0.10/hooks",
+            "https://169.254.169.254/latest/meta-data/",
+            "https://0.0.0.0/hooks",
+            "https://0.1.2.3/hooks",
+            "https://100.64.0.1/hooks",
+            "https://100.127.255.254/hooks",
+            "https://192.0.0.8/hooks",
+            "https://198.18.0.1/hooks",
+            "https://203.0.113.5/hooks",
+            "https://224.0.0.1/hooks",
+            "https://240.0.0.1/hooks",
+            "https://255.255.255.255/hooks",
+            "https://[::1]/hooks",
+            "https://[fd00::1]/hooks",
+            "https://[fe80::1]/hooks",
+            "https://[ff02::1]/hooks",
+            "https://[::ffff:127.0.0.1]/hooks",
+            "https://[::ffff:100.64.0.1]/hooks",
+            "https://[64:ff9b::a9fe:a9fe]/hooks",
+            "https://[64:ff9b:1::1]/hooks",
+            "https://[2001::1]/hooks",
+            "https://[2001:db8::1]/hooks",
+        ] {
+            assert!(matches!(check_client_target(url).await, Err(TargetError::NotPublic)), "{}", url);
+        }
+        assert!(check_client_target("https://100.128.0.1/hooks").await.is_ok());
+        assert!(check_client_target("https://[2606:4700::1111]/hooks").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_public_resolver_drops_private_addresses() {
+        use reqwest::dns::Resolve;
+
+        let resolved = PublicResolver.resolve("localhost".parse().unwrap()).await;
+        let err = resolved.err().expect("localhost only resolves to loopback");
+        assert!(err.downcast_ref::<TargetError>().is_some_and(|e| matches!(e, TargetError::NotPublic)));
+    }
+
+    #[tokio::test]
+    async fn test_private_client_targets_are_refused_at_delivery() {
+        let dispatcher = Arc::new(WebhookDispatcher {
+            retry: RetryPolicy { max_attempts: 1, base_delay: Duration::from_millis(1) },
+            ..WebhookDispatcher::default()
+        });
+        // Registered directly, as if the host had resolved publicly when it was checked
+        let endpoint = dispatcher.registry.register(
+            "https://169.254.169.254/latest/meta-data/".to_string(),
+            vec![WebhookEvent::JobCompleted],
+            None,
+            Some("key-1".to_string()),
+        );
+        dispatcher.deliver(&endpoint, WebhookEvent::JobCompleted, "evt-1", "{}".to_string()).await;
+
+        let log = dispatcher.deliveries(&endpoint.id);
+        assert_eq!(log.len(), 1);
+        assert!(log[0].status_code.is_none());
+        assert_eq!(log[0].error.as_deref(), Some(TargetError::NotPublic.to_string().as_str()));
+    }
+
+    #[test]
+    fn test_event_names_round_trip() {
+        let json = serde_json::to_string(&WebhookEvent::JobCompleted).unwrap();
+        assert_eq!(json, "\"job.completed\"");
+        let parsed: WebhookEvent = serde_json::from_str("\"design.created\"").unwrap();
+        assert_eq!(parsed, WebhookEvent::DesignCreated);
+    }
+}
@@ -0,0 +1,290 @@
+//! Budget for calls to the Zoo engine, per UTC day and month.
+//!
+//! Every generation that reaches the engine counts one call, whether it succeeds
+//! or not, since the engine bills it either way; invalid plates and cache hits
+//! don't count. The unit is a generation, not a Zoo CLI invocation: a generation
+//! runs the CLI once to export and again for each conversion, and each run may
+//! be retried up to ZOO_RETRY_ATTEMPTS times, all under the one call. Size the
+//! caps in generations. With a daily (ZOO_DAILY_QUOTA) or monthly (ZOO_MONTHLY_QUOTA) cap
+//! set, generation is refused once either is spent: requests get a
+//! `QUOTA_EXHAUSTED` error, nothing is generated or cached, and plates already
+//! cached are still served. The call that spends the last of a budget tells the
+//! chat notifiers.
+//!
+//! The counts are kept in ZOO_QUOTA_FILE when it's set, so a restart doesn't hand
+//! out the budget again. `GET /api/quota` reports what's left.
+
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use utoipa::ToSchema;
+
+/// A window calls are counted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaPeriod {
+    Daily,
+    Monthly,
+}
+
+impl std::fmt::Display for QuotaPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            QuotaPeriod::Daily => "daily",
+            QuotaPeriod::Monthly => "monthly",
+        })
+    }
+}
+
+/// Engine calls in the current window and what's left of its budget.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub struct QuotaWindow {
+    /// Calls (generations) made so far in this window
+    pub used: u64,
+    /// Calls allowed per window; null when there's no cap
+    pub limit: Option<u64>,
+    /// Calls left in this window; null when there's no cap
+    pub remaining: Option<u64>,
+    /// When the window starts over (RFC 3339, UTC)
+    pub resets_at: String,
+}
+
+/// Engine calls used and left, as served by `/api/quota`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub struct QuotaStatus {
+    pub daily: QuotaWindow,
+    pub monthly: QuotaWindow,
+    /// Generation is refused until the spent window resets
+    pub exhausted: bool,
+}
+
+/// A generation refused because a budget is spent.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "Zoo API {period} quota exhausted ({used} of {limit} calls used), so the model was not generated or cached; \
+     try again after {resets_at}"
+)]
+pub struct QuotaExhausted {
+    pub period: QuotaPeriod,
+    pub used: u64,
+    pub limit: u64,
+    pub resets_at: String,
+}
+
+/// The counts as kept in memory and in the quota file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Counts {
+    /// UTC day counted by `daily`, as YYYY-MM-DD
+    day: String,
+    daily: u64,
+    /// UTC month counted by `monthly`, as YYYY-MM
+    month: String,
+    monthly: u64,
+}
+
+impl Counts {
+    /// Start the windows `today` has moved past over at zero.
+    fn roll(&mut self, today: NaiveDate) {
+        let day = today.format("%Y-%m-%d").to_string();
+        if self.day != day {
+            self.day = day;
+            self.daily = 0;
+        }
+        let month = today.format("%Y-%m").to_string();
+        if self.month != month {
+            self.month = month;
+            self.monthly = 0;
+        }
+    }
+}
+
+/// Engine calls counted against optional daily and monthly caps.
+#[derive(Debug, Default)]
+pub struct ZooQuota {
+    daily_limit: Option<u64>,
+    monthly_limit: Option<u64>,
+    /// Where the counts are saved after every call
+    file: Option<PathBuf>,
+    counts: Mutex<Counts>,
+}
+
+impl ZooQuota {
+    /// Count calls in memory, refusing them past `daily` or `monthly` calls when
+    /// given. A cap of 0 is no cap.
+    pub fn new(daily: Option<u64>, monthly: Option<u64>) -> Self {
+        Self {
+            daily_limit: daily.filter(|limit| *limit > 0),
+            monthly_limit: monthly.filter(|limit| *limit > 0),
+            file: None,
+            counts: Mutex::new(Counts::default()),
+        }
+    }
+
+    /// Like [`ZooQuota::new`], keeping the counts in `file` and carrying on from the
+    /// ones already there.
+    pub fn open(daily: Option<u64>, monthly: Option<u64>, file: &Path) -> std::io::Result<Self> {
+        let counts = match std::fs::read(file) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Zoo quota file {} is invalid: {}", file.display(), e),
+                )
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Counts::default(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            file: Some(file.to_path_buf()),
+            counts: Mutex::new(counts),
+            ..Self::new(daily, monthly)
+        })
+    }
+
+    /// Count one engine call, or refuse it if a budget is spent. On success, also
+    /// returns the budget this call spent the last of, if any.
+    pub fn acquire(&self) -> Result<Option<QuotaExhausted>, QuotaExhausted> {
+        self.acquire_on(Utc::now().date_naive())
+    }
+
+    fn acquire_on(&self, today: NaiveDate) -> Result<Option<QuotaExhausted>, QuotaExhausted> {
+        let mut counts = self.counts.lock().unwrap();
+        counts.roll(today);
+        if let Some(exhausted) = self.exhausted(&counts, today) {
+            return Err(exhausted);
+        }
+        counts.daily += 1;
+        counts.monthly += 1;
+        self.save(&counts);
+        Ok(self.exhausted(&counts, today))
+    }
+
+    /// Calls used and left in the current day and month.
+    pub fn status(&self) -> QuotaStatus {
+        self.status_on(Utc::now().date_naive())
+    }
+
+    fn status_on(&self, today: NaiveDate) -> QuotaStatus {
+        let mut counts = self.counts.lock().unwrap();
+        counts.roll(today);
+        let window = |used: u64, limit: Option<u64>, resets_at: String| QuotaWindow {
+            used,
+            limit,
+            remaining: limit.map(|limit| limit.saturating_sub(used)),
+            resets_at,
+        };
+        QuotaStatus {
+            daily: window(counts.daily, self.daily_limit, day_reset(today)),
+            monthly: window(counts.monthly, self.monthly_limit, month_reset(today)),
+            exhausted: self.exhausted(&counts, today).is_some(),
+        }
+    }
+
+    /// The spent budget that resets last, if any.
+    fn exhausted(&self, counts: &Counts, today: NaiveDate) -> Option<QuotaExhausted> {
+        let windows = [
+            (QuotaPeriod::Monthly, counts.monthly, self.monthly_limit, month_reset(today)),
+            (QuotaPeriod::Daily, counts.daily, self.daily_limit, day_reset(today)),
+        ];
+        windows.into_iter().find_map(|(period, used, limit, resets_at)| {
+            let limit = limit.filter(|limit| used >= *limit)?;
+            Some(QuotaExhausted { period, used, limit, resets_at })
+        })
+    }
+
+    /// Write the counts to the quota file, if there is one. A failed write is only
+    /// logged; the counts in memory still hold.
+    fn save(&self, counts: &Counts) {
+        let Some(file) = &self.file else {
+            return;
+        };
+        // Write a sibling and rename it over the file, so a crash can't leave half of it
+        let partial = file.with_extension("partial");
+        let written = serde_json::to_vec(counts)
+            .map_err(std::io::Error::other)
+            .and_then(|bytes| std::fs::write(&partial, bytes))
+            .and_then(|()| std::fs::rename(&partial, file));
+        if let Err(e) = written {
+            tracing::warn!("Failed to save the Zoo quota to {}: {}", file.display(), e);
+        }
+    }
+}
+
+/// Midnight UTC after `today`.
+fn day_reset(today: NaiveDate) -> String {
+    let tomorrow = today.succ_opt().unwrap_or(today);
+    tomorrow.format("%Y-%m-%dT00:00:00Z").to_string()
+}
+
+/// Midnight UTC on the first of the month after `today`'s.
+fn month_reset(today: NaiveDate) -> String {
+    let first = today.with_day(1).unwrap_or(today);
+    let next = first.checked_add_months(Months::new(1)).unwrap_or(first);
+    next.format("%Y-%m-%dT00:00:00Z").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_daily_cap_refuses_until_the_next_day() {
+        let quota = ZooQuota::new(Some(2), None);
+        let today = date(2026, 10, 17);
+        assert_eq!(quota.acquire_on(today), Ok(None));
+        let last = quota.acquire_on(today).unwrap().unwrap();
+        assert_eq!((last.period, last.used, last.limit), (QuotaPeriod::Daily, 2, 2));
+
+        let refused = quota.acquire_on(today).unwrap_err();
+        assert_eq!(refused.resets_at, "2026-10-18T00:00:00Z");
+        assert!(refused.to_string().contains("not generated or cached"), "{}", refused);
+        let status = quota.status_on(today);
+        assert!(status.exhausted);
+        assert_eq!(status.daily.remaining, Some(0));
+        assert_eq!(status.monthly, QuotaWindow {
+            used: 2,
+            limit: None,
+            remaining: None,
+            resets_at: "2026-11-01T00:00:00Z".to_string(),
+        });
+
+        assert_eq!(quota.acquire_on(date(2026, 10, 18)), Ok(None));
+        assert_eq!(quota.status_on(date(2026, 10, 18)).monthly.used, 3);
+    }
+
+    #[test]
+    fn test_monthly_cap_outlasts_the_daily_one() {
+        let quota = ZooQuota::new(Some(0), Some(1));
+        let day = date(2026, 12, 31);
+        assert!(quota.acquire_on(day).unwrap().is_some());
+        let refused = quota.acquire_on(date(2026, 12, 31)).unwrap_err();
+        assert_eq!((refused.period, refused.resets_at.as_str()), (QuotaPeriod::Monthly, "2027-01-01T00:00:00Z"));
+        assert_eq!(quota.acquire_on(date(2027, 1, 1)), Ok(Some(QuotaExhausted {
+            period: QuotaPeriod::Monthly,
+            used: 1,
+            limit: 1,
+            resets_at: "2027-02-01T00:00:00Z".to_string(),
+        })));
+    }
+
+    #[test]
+    fn test_counts_survive_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("quota.json");
+        let today = date(2026, 10, 17);
+        let quota = ZooQuota::open(None, Some(10), &file).unwrap();
+        for _ in 0..3 {
+            quota.acquire_on(today).unwrap();
+        }
+
+        let reopened = ZooQuota::open(None, Some(10), &file).unwrap();
+        assert_eq!(reopened.status_on(today).monthly.remaining, Some(7));
+
+        std::fs::write(&file, "not json").unwrap();
+        assert!(ZooQuota::open(None, None, &file).is_err());
+    }
+}
@@ -2,19 +2,18 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
-use domain::{ActuatorPlate, BoltSize, Material, Millimeters, Newtons};
+use domain::{ActuatorPlate, BoltSize, Count, HoleSpec, Length, Material, Newtons, PlateFamily, Tolerances};
 use http_body_util::BodyExt;
-use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 use tower::ServiceExt;
-use web::{AppStateInner, MemoryCache};
+use web::{
+    AppConfig, AppStateInner, Artifact, AuditEvent, AuditKind, CacheMetrics, CachedFiles, Email, ExternalQuote, FeatureFlags, Flag, MailError, Mailer,
+    MemoryCache, ModelCache, Order, OrderStatus, QuoteError, QuoteProvider, QuoteRequest,
+    RateLimiter, RecentPlate, ZooQuota,
+};
 
 fn create_test_router() -> axum::Router {
-    let state = Arc::new(AppStateInner {
-        sessions: RwLock::new(HashMap::new()),
-        cache: Arc::new(MemoryCache::new()),
-    });
+    let state = Arc::new(AppStateInner::new(Arc::new(MemoryCache::new())));
     web::create_router(state)
 }
 
@@ -40,15 +39,19 @@ async fn test_generate_endpoint_invalid_plate() {
     let app = create_test_router();
 
     let plate = ActuatorPlate {
-        bolt_spacing: Millimeters(0), // Invalid!
+        bolt_spacing: Length::from_mm(0), // Invalid!
         bolt_size: BoltSize::M10,
-        bracket_height: Millimeters(40),
-        bracket_width: Millimeters(30),
+        bracket_height: Length::from_mm(40),
+        bracket_width: Length::from_mm(30),
         material: Material::Aluminum,
-        pin_diameter: Millimeters(10),
-        pin_count: 6,
-        plate_thickness: Millimeters(8),
+        pin_diameter: Length::from_mm(10),
+        pin_count: Count::of(6),
+        plate_thickness: Length::from_mm(8),
         expected_force_per_pin: Newtons(500),
+        bolt_holes: HoleSpec::Through,
+        tolerances: Tolerances::NONE,
+        family: PlateFamily::Standard,
+        pin_fit: None,
     };
 
     let response = app
@@ -71,6 +74,44 @@ async fn test_generate_endpoint_invalid_plate() {
     assert!(!json["errors"].as_array().unwrap().is_empty());
 }
 
+#[tokio::test]
+async fn test_job_endpoints_reject_invalid_plate_and_unknown_job() {
+    let app = create_test_router();
+
+    let plate = ActuatorPlate {
+        bolt_spacing: Length::from_mm(0),
+        ..ActuatorPlate::default()
+    };
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/jobs")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&plate).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["success"], false);
+    assert!(!json["errors"].as_array().unwrap().is_empty());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/jobs/no-such-job")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
 // This test validates that the endpoint is properly wired, but will fail
 // to generate files if zoo CLI is not installed. We test that it returns
 // the expected error in that case.
@@ -110,6 +151,9 @@ async fn test_generate_endpoint_valid_plate() {
         assert!(json.get("download_url").is_some());
         assert!(json.get("gltf_url").is_some());
         assert!(json.get("stl_url").is_some());
+        assert!(json.get("svg_url").is_some());
+        assert!(json.get("pdf_url").is_some());
+        assert!(json.get("dxf_url").is_some());
     } else {
         // If zoo is not available
         assert_eq!(status, StatusCode::BAD_REQUEST);
@@ -120,6 +164,176 @@ async fn test_generate_endpoint_valid_plate() {
     // No cleanup needed - temp files are automatically cleaned up
 }
 
+#[tokio::test]
+async fn test_recent_plates_are_kept_per_browser() {
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.engine = Arc::new(parametric::mock::MockBackend::new());
+    let app = web::create_router(Arc::new(inner));
+
+    let generate = |plate: &ActuatorPlate, cookie: Option<&str>| {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/api/generate")
+            .header("content-type", "application/json");
+        if let Some(cookie) = cookie {
+            request = request.header("cookie", cookie);
+        }
+        request.body(Body::from(serde_json::to_string(plate).unwrap())).unwrap()
+    };
+    let recent = |cookie: Option<&str>| {
+        let mut request = Request::builder().uri("/api/session/recent");
+        if let Some(cookie) = cookie {
+            request = request.header("cookie", cookie);
+        }
+        request.body(Body::empty()).unwrap()
+    };
+
+    let first = ActuatorPlate::default();
+    let response = app.clone().oneshot(generate(&first, None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let set_cookie = response.headers()["set-cookie"].to_str().unwrap().to_string();
+    assert!(set_cookie.starts_with("platerator_session="), "{}", set_cookie);
+    assert!(set_cookie.contains("HttpOnly") && set_cookie.contains("SameSite=Lax"), "{}", set_cookie);
+    let cookie = set_cookie.split(';').next().unwrap();
+
+    let second = ActuatorPlate {
+        plate_thickness: Length::from_mm(12),
+        ..Default::default()
+    };
+    for plate in [&second, &first] {
+        let response = app.clone().oneshot(generate(plate, Some(cookie))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // Newest first, and generating a plate again moves it to the top
+    let response = app.clone().oneshot(recent(Some(cookie))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let plates: Vec<RecentPlate> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(plates.iter().map(|p| p.plate).collect::<Vec<_>>(), vec![first, second]);
+    assert_eq!(plates[0].cache_key, first.cache_key());
+
+    // Another browser has none
+    let response = app.oneshot(recent(None)).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(serde_json::from_slice::<serde_json::Value>(&body).unwrap(), serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_spent_quota_stops_generation_but_not_cache_hits() {
+    let backend = Arc::new(parametric::mock::MockBackend::new());
+    let cache = Arc::new(MemoryCache::new());
+    let mut inner = AppStateInner::new(cache.clone());
+    inner.engine = backend.clone();
+    inner.zoo_quota = Arc::new(ZooQuota::new(Some(1), None));
+    let app = web::create_router(Arc::new(inner));
+
+    let generate = |plate: &ActuatorPlate| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/generate")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(plate).unwrap()))
+            .unwrap()
+    };
+    let first = ActuatorPlate::default();
+    assert_eq!(app.clone().oneshot(generate(&first)).await.unwrap().status(), StatusCode::OK);
+    // The files are cached in the background
+    for _ in 0..50 {
+        if cache.exists(&first.cache_key()).await {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    let second = ActuatorPlate {
+        plate_thickness: Length::from_mm(12),
+        ..Default::default()
+    };
+    let response = app.clone().oneshot(generate(&second)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["errors"][0]["code"], "QUOTA_EXHAUSTED");
+    assert!(json["errors"][0]["message"].as_str().unwrap().contains("not generated or cached"), "{}", json);
+
+    // Invalid plates still get their validation errors, and cached plates are served
+    let invalid = ActuatorPlate {
+        bolt_spacing: Length::from_mm(0),
+        ..Default::default()
+    };
+    assert_eq!(app.clone().oneshot(generate(&invalid)).await.unwrap().status(), StatusCode::BAD_REQUEST);
+    let response = app.clone().oneshot(generate(&first)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-cache"], "HIT");
+    assert_eq!(backend.generated(), 1);
+
+    let response = app
+        .oneshot(Request::builder().uri("/api/quota").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let status: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(status["exhausted"], true);
+    assert_eq!(status["daily"]["used"], 1);
+    assert_eq!(status["daily"]["remaining"], 0);
+    assert_eq!(status["monthly"]["limit"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn test_generate_and_download_with_in_process_backend() {
+    let backend = Arc::new(parametric::mock::MockBackend::new());
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.engine = backend.clone();
+    let app = web::create_router(Arc::new(inner));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/generate")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&ActuatorPlate::default()).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    for (url, expected) in [("download_url", parametric::mock::MOCK_STEP), ("stl_url", parametric::mock::MOCK_STL)] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(json[url].as_str().unwrap()).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        // The STEP header also gets the plate's metadata, so only its data is compared
+        match expected.split_once("ENDSEC;") {
+            Some((_, data)) => assert!(body.ends_with(data.as_bytes()), "{}", String::from_utf8_lossy(&body)),
+            None => assert_eq!(body, expected.as_bytes()),
+        }
+    }
+
+    // The printing formats are converted from the engine's STL
+    for (url, content_type, magic) in [("obj_url", "model/obj", &b"# "[..]), ("three_mf_url", "model/3mf", b"PK")] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(json[url].as_str().unwrap()).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], content_type);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(body.starts_with(magic), "{}: {:?}", url, body);
+    }
+    assert_eq!(backend.generated(), 1);
+}
+
 #[tokio::test]
 async fn test_validate_endpoint_valid_plate() {
     let app = create_test_router();
@@ -155,20 +369,82 @@ async fn test_validate_endpoint_valid_plate() {
     assert!(summary["minimum_thickness_mm"].as_u64().unwrap() >= 1);
 }
 
+#[tokio::test]
+async fn test_validate_endpoint_accepts_imperial_lengths() {
+    let app = create_test_router();
+
+    let mut plate = serde_json::to_value(ActuatorPlate::default()).unwrap();
+    plate["plate_thickness"] = "0.375in".into();
+    plate["bracket_width"] = "11.75\"".into();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/validate")
+                .header("content-type", "application/json")
+                .body(Body::from(plate.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["valid"], true);
+}
+
+#[tokio::test]
+async fn test_validate_endpoint_warns_when_load_check_fails() {
+    let app = create_test_router();
+
+    let plate = ActuatorPlate::default();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/validate?load_n=200000&safety_factor=2")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&plate).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Failing the load check is a warning, not a validation error
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["valid"], true);
+    assert_eq!(json["load_check"]["passes"], false);
+    assert_eq!(json["load_check"]["required_thickness_mm"], 21);
+    let warnings = json["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0]["message"].as_str().unwrap().contains("at least 21 mm"));
+}
+
 #[tokio::test]
 async fn test_validate_endpoint_invalid_bolt_spacing() {
     let app = create_test_router();
 
     let plate = ActuatorPlate {
-        bolt_spacing: Millimeters(0), // Invalid!
+        bolt_spacing: Length::from_mm(0), // Invalid!
         bolt_size: BoltSize::M10,
-        bracket_height: Millimeters(40),
-        bracket_width: Millimeters(30),
+        bracket_height: Length::from_mm(40),
+        bracket_width: Length::from_mm(30),
         material: Material::Aluminum,
-        pin_diameter: Millimeters(10),
-        pin_count: 6,
-        plate_thickness: Millimeters(8),
+        pin_diameter: Length::from_mm(10),
+        pin_count: Count::of(6),
+        plate_thickness: Length::from_mm(8),
         expected_force_per_pin: Newtons(500),
+        bolt_holes: HoleSpec::Through,
+        tolerances: Tolerances::NONE,
+        family: PlateFamily::Standard,
+        pin_fit: None,
     };
 
     let response = app
@@ -193,6 +469,207 @@ async fn test_validate_endpoint_invalid_bolt_spacing() {
         .as_str()
         .unwrap()
         .contains("Bolt spacing"));
+    assert_eq!(json["errors"][0]["code"], "BOLT_SPACING_TOO_SMALL");
+    assert_eq!(json["errors"][0]["fields"][0], "boltSpacing");
+}
+
+#[tokio::test]
+async fn test_validate_endpoint_localizes_messages() {
+    let app = create_test_router();
+
+    let plate = ActuatorPlate {
+        bolt_spacing: Length::from_mm(0),
+        bolt_size: BoltSize::M10,
+        bracket_height: Length::from_mm(40),
+        bracket_width: Length::from_mm(30),
+        material: Material::Aluminum,
+        pin_diameter: Length::from_mm(10),
+        pin_count: Count::of(6),
+        plate_thickness: Length::from_mm(8),
+        expected_force_per_pin: Newtons(500),
+        bolt_holes: HoleSpec::Through,
+        tolerances: Tolerances::NONE,
+        family: PlateFamily::Standard,
+        pin_fit: None,
+    };
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/validate")
+                .header("content-type", "application/json")
+                .header("accept-language", "fr-FR, es;q=0.8, en;q=0.5")
+                .body(Body::from(serde_json::to_string(&plate).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["errors"][0]["code"], "BOLT_SPACING_TOO_SMALL");
+    assert_eq!(
+        json["errors"][0]["message"],
+        "La separación entre pernos debe ser mayor que 0"
+    );
+}
+
+#[tokio::test]
+async fn test_validate_endpoint_checks_family_rules() {
+    let app = create_test_router();
+
+    let body = serde_json::json!({
+        "bolt_spacing": 60, "bolt_size": "M10", "bracket_height": 400, "bracket_width": 300,
+        "material": "aluminum", "pin_diameter": 10, "pin_count": 6, "plate_thickness": 8,
+        "expected_force_per_pin": 500,
+        "family": { "type": "dual_pin", "pin_spacing": 80 }
+    });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/validate")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["errors"][0]["code"], "DUAL_PIN_COUNT_INVALID");
+    assert_eq!(json["errors"][0]["fields"], serde_json::json!(["family", "pinCount"]));
+}
+
+#[tokio::test]
+async fn test_validate_endpoint_uses_configured_limits() {
+    let plate = ActuatorPlate {
+        bracket_height: Length::from_mm(1500),
+        ..ActuatorPlate::default()
+    };
+    let validate = |app: axum::Router| async move {
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/validate")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_string(&plate).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+    };
+
+    let (status, json) = validate(create_test_router()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["errors"][0]["code"], "BRACKET_HEIGHT_TOO_LARGE");
+
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.limits.max_bracket_height_mm = 2000;
+    let (status, _) = validate(web::create_router(Arc::new(inner))).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_validate_warns_about_a_thin_plate() {
+    let plate = ActuatorPlate {
+        bolt_spacing: Length::from_mm(60),
+        bolt_size: BoltSize::M5,
+        bracket_height: Length::from_mm(200),
+        bracket_width: Length::from_mm(100),
+        material: Material::Aluminum,
+        pin_diameter: Length::from_mm(10),
+        pin_count: Count::of(4),
+        plate_thickness: Length::from_mm(2),
+        expected_force_per_pin: Newtons(50),
+        ..ActuatorPlate::default()
+    };
+    let response = create_test_router()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/validate")
+                .header("content-type", "application/json")
+                .header("accept-language", "es")
+                .body(Body::from(serde_json::to_string(&plate).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["valid"], true);
+    assert_eq!(json["warnings"][0]["code"], "THIN_PLATE");
+    assert_eq!(json["warnings"][0]["fields"], serde_json::json!(["plateThickness", "boltSize"]));
+    assert!(json["warnings"][0]["message"].as_str().unwrap().starts_with("Placa delgada"));
+}
+
+#[tokio::test]
+async fn test_generate_warnings_follow_accept_language() {
+    let plate = ActuatorPlate {
+        bolt_spacing: Length::from_mm(60),
+        bolt_size: BoltSize::M5,
+        bracket_height: Length::from_mm(200),
+        bracket_width: Length::from_mm(100),
+        material: Material::Aluminum,
+        pin_diameter: Length::from_mm(10),
+        pin_count: Count::of(4),
+        plate_thickness: Length::from_mm(2),
+        expected_force_per_pin: Newtons(50),
+        ..ActuatorPlate::default()
+    };
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.engine = Arc::new(parametric::mock::MockBackend::new());
+    let response = web::create_router(Arc::new(inner))
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/generate")
+                .header("content-type", "application/json")
+                .header("accept-language", "es")
+                .body(Body::from(serde_json::to_string(&plate).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["warnings"][0]["code"], "THIN_PLATE");
+    assert!(json["warnings"][0]["message"].as_str().unwrap().starts_with("Placa delgada"));
+}
+
+#[tokio::test]
+async fn test_validation_rules_use_configured_limits() {
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.limits.max_bracket_height_mm = 2000;
+    let response = web::create_router(Arc::new(inner))
+        .oneshot(Request::builder().uri("/api/validation-rules").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let rules: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+    let rule = |field: &str| rules.iter().find(|rule| rule["field"] == field).unwrap().clone();
+
+    assert_eq!(
+        rule("bracketHeight"),
+        serde_json::json!({"field": "bracketHeight", "key": "bracket_height", "kind": "number", "min": 1.0, "max": 2000.0, "step": 0.001, "unit": "mm"})
+    );
+    assert_eq!(rule("pinCount")["max"], 12.0);
+    assert_eq!(rule("material")["kind"], "choice");
+    assert_eq!(rule("material")["allowed"], serde_json::json!(["aluminum", "stainless_steel", "carbon_steel", "brass"]));
+    assert_eq!(rule("boltSize")["allowed"][6], "M12");
 }
 
 #[tokio::test]
@@ -217,20 +694,113 @@ async fn test_version_endpoint() {
     assert!(!hash.is_empty());
 }
 
+#[tokio::test]
+async fn test_openapi_spec_documents_every_route() {
+    let app = create_test_router();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/openapi.json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let spec: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    // Every HTTP route in create_router; the WebSocket route can't be described
+    let routes = [
+        ("get", "/api/health"),
+        ("get", "/api/ready"),
+        ("get", "/api/version"),
+        ("get", "/api/flags"),
+        ("get", "/api/cache/stats"),
+        ("get", "/api/quota"),
+        ("get", "/metrics"),
+        ("post", "/api/validate"),
+        ("get", "/api/validation-rules"),
+        ("post", "/api/suggestions"),
+        ("post", "/api/generate"),
+        ("post", "/api/jobs"),
+        ("get", "/api/jobs/{job_id}"),
+        ("post", "/api/plates/batch"),
+        ("post", "/api/sweep"),
+        ("get", "/api/download/step/{session_id}"),
+        ("get", "/api/download/gltf/{session_id}"),
+        ("get", "/api/download/stl/{session_id}"),
+        ("get", "/api/download/svg/{session_id}"),
+        ("get", "/api/download/pdf/{session_id}"),
+        ("get", "/api/download/dxf/{session_id}"),
+        ("get", "/api/download/obj/{session_id}"),
+        ("get", "/api/download/3mf/{session_id}"),
+        ("get", "/api/download/kcl/{session_id}"),
+        ("get", "/api/models/{cache_key}/download"),
+        ("get", "/api/models/{cache_key}/gltf"),
+        ("get", "/api/models/{cache_key}/kcl"),
+        ("post", "/api/models/{cache_key}/email"),
+        ("post", "/api/quote"),
+        ("get", "/api/presets"),
+        ("get", "/api/configs"),
+        ("post", "/api/configs"),
+        ("get", "/api/configs/{id}"),
+        ("get", "/api/session/recent"),
+        ("post", "/api/compare"),
+        ("post", "/api/analysis"),
+        ("post", "/api/orders"),
+        ("get", "/api/admin/orders/export"),
+        ("post", "/api/admin/orders/{order_number}/status"),
+        ("get", "/api/admin/webhooks"),
+        ("post", "/api/admin/webhooks"),
+        ("delete", "/api/admin/webhooks/{id}"),
+        ("get", "/api/admin/webhooks/{id}/deliveries"),
+        ("get", "/api/webhooks"),
+        ("post", "/api/webhooks"),
+        ("delete", "/api/webhooks/{id}"),
+        ("get", "/api/webhooks/{id}/deliveries"),
+        ("get", "/api/admin/api-keys"),
+        ("post", "/api/admin/api-keys"),
+        ("delete", "/api/admin/api-keys/{id}"),
+        ("get", "/api/admin/workers"),
+        ("delete", "/api/admin/cache"),
+        ("delete", "/api/admin/cache/{cache_key}"),
+        ("get", "/api/admin/backup"),
+        ("post", "/api/admin/restore"),
+    ];
+    for (method, path) in routes {
+        assert!(spec["paths"][path][method].is_object(), "{} {} is undocumented", method, path);
+    }
+    // ...and nothing the router doesn't serve
+    let paths: std::collections::BTreeSet<_> = routes.iter().map(|(_, path)| *path).collect();
+    assert_eq!(spec["paths"].as_object().unwrap().len(), paths.len());
+
+    // Clients generated from the spec need every type a plate is built from
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+    for schema in ["ActuatorPlate", "Length", "Count", "HoleSpec", "Tolerances", "ErrorResponse"] {
+        assert!(schemas.contains_key(schema), "{} is missing from the spec", schema);
+    }
+}
+
 #[tokio::test]
 async fn test_validate_endpoint_invalid_pin_count() {
     let app = create_test_router();
 
     let plate = ActuatorPlate {
-        bolt_spacing: Millimeters(60),
+        bolt_spacing: Length::from_mm(60),
         bolt_size: BoltSize::M10,
-        bracket_height: Millimeters(40),
-        bracket_width: Millimeters(30),
+        bracket_height: Length::from_mm(40),
+        bracket_width: Length::from_mm(30),
         material: Material::Aluminum,
-        pin_diameter: Millimeters(10),
-        pin_count: 13, // Invalid! Max is 12
-        plate_thickness: Millimeters(8),
+        pin_diameter: Length::from_mm(10),
+        pin_count: Count::of(13), // Invalid! Max is 12
+        plate_thickness: Length::from_mm(8),
         expected_force_per_pin: Newtons(500),
+        bolt_holes: HoleSpec::Through,
+        tolerances: Tolerances::NONE,
+        family: PlateFamily::Standard,
+        pin_fit: None,
     };
 
     let response = app
@@ -253,3 +823,1539 @@ async fn test_validate_endpoint_invalid_pin_count() {
     assert!(!json["errors"].as_array().unwrap().is_empty());
     assert!(json["errors"][0]["message"].as_str().unwrap().contains("Pin count"));
 }
+
+#[tokio::test]
+async fn test_quote_endpoint_requires_generated_model() {
+    let app = create_test_router();
+
+    let body = serde_json::json!({ "plate": ActuatorPlate::default(), "quantity": 25 });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/quote")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+struct FlatRateQuote;
+
+#[async_trait::async_trait]
+impl QuoteProvider for FlatRateQuote {
+    fn name(&self) -> &str {
+        "flat-rate"
+    }
+
+    async fn quote(&self, request: &QuoteRequest) -> Result<ExternalQuote, QuoteError> {
+        Ok(ExternalQuote {
+            provider: "flat-rate".to_string(),
+            unit_price_cents: 4200,
+            total_price_cents: 4200 * u64::from(request.quantity),
+            currency: "USD".to_string(),
+            lead_time_days: Some(10),
+            quote_url: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_quote_endpoint_returns_external_quotes() {
+    let plate = ActuatorPlate::default();
+    let cache = Arc::new(MemoryCache::new());
+    let files = CachedFiles::new()
+        .with(Artifact::Step, "step")
+        .with(Artifact::Gltf, "gltf")
+        .with(Artifact::Stl, "stl")
+        .with(Artifact::Obj, "obj")
+        .with(Artifact::ThreeMf, "3mf")
+        .with(Artifact::Svg, "svg")
+        .with(Artifact::Pdf, "pdf")
+        .with(Artifact::Dxf, "dxf")
+        .with(Artifact::Kcl, "kcl");
+    cache.put(&plate.cache_key(), &files).await.unwrap();
+
+    let mut inner = AppStateInner::new(cache);
+    inner.quote_providers.push(Arc::new(FlatRateQuote));
+    let app = web::create_router(Arc::new(inner));
+
+    let body = serde_json::json!({ "plate": plate, "quantity": 25, "finish": "anodized" });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/quote")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["quantity"], 25);
+    assert_eq!(json["external_quotes"][0]["provider"], "flat-rate");
+    assert_eq!(json["external_quotes"][0]["total_price_cents"], 105_000);
+    assert_eq!(json["lead_time"]["finish_days"], 3);
+    assert_eq!(json["estimate"]["discount_percent"], 5);
+    assert_eq!(
+        json["estimate"]["total_price_cents"].as_u64().unwrap(),
+        json["estimate"]["unit_price_cents"].as_u64().unwrap() * 25
+    );
+    assert!(json["lead_time"]["ship_date"].as_str().is_some());
+    assert_eq!(json["mass_properties"]["mass_grams"], json["estimate"]["mass_grams"]);
+    assert!(json["mass_properties"]["center_of_mass"]["z_um"].as_i64().is_some());
+}
+
+#[tokio::test]
+async fn test_order_export_requires_admin_token() {
+    let app = create_test_router();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/admin/orders/export")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_order_export_csv_since() {
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.admin_token = Some("secret".to_string());
+    for (id, created_at) in [("ord-1", "2026-01-01T00:00:00Z"), ("ord-2", "2026-02-01T00:00:00Z")] {
+        let order = Order {
+            id: id.to_string(),
+            order_number: String::new(),
+            status: OrderStatus::Received,
+            created_at: created_at.to_string(),
+            part_number: ActuatorPlate::default().cache_key(),
+            material: Material::Aluminum,
+            plate: None,
+            quantity: Count::of(25),
+            unit_price_cents: 4200,
+            total_price_cents: 105_000,
+            customer_name: "Acme".to_string(),
+            customer_email: "buyer@acme.test".to_string(),
+            customer_phone: None,
+        };
+        inner.orders.insert(&order).await.unwrap();
+    }
+    inner.order_export_columns = web::ColumnMapping::parse("order_id:OrderNo,total_price:Amount").unwrap();
+    let app = web::create_router(Arc::new(inner));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/admin/orders/export?format=csv&since=2026-01-15T00:00:00Z")
+                .header("authorization", "Bearer secret")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"OrderNo,Amount\r\nord-2,1050.00\r\n");
+}
+
+#[tokio::test]
+async fn test_admin_dashboard_requires_basic_auth() {
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.admin_token = Some("secret".to_string());
+    inner
+        .audit
+        .record(&AuditEvent::new(AuditKind::EngineFailure, "plate-abc").with_detail("KCL error: <unexpected token>"))
+        .await
+        .unwrap();
+    let app = web::create_router(Arc::new(inner));
+    let dashboard = |authorization: Option<&str>| {
+        let mut request = Request::builder().uri("/admin");
+        if let Some(value) = authorization {
+            request = request.header("authorization", value);
+        }
+        request.body(Body::empty()).unwrap()
+    };
+
+    let response = app.clone().oneshot(dashboard(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(response.headers()["www-authenticate"].to_str().unwrap().starts_with("Basic "));
+
+    // The bearer token the API uses isn't accepted by the browser page
+    let response = app.clone().oneshot(dashboard(Some("Bearer secret"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // ops:secret
+    let response = app.oneshot(dashboard(Some("Basic b3BzOnNlY3JldA=="))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/html; charset=utf-8");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+    assert!(html.contains("plate-abc"));
+    assert!(html.contains("KCL error: &lt;unexpected token&gt;"));
+
+    // Without an admin token the dashboard is off
+    let response = create_test_router().oneshot(dashboard(Some("Basic b3BzOg=="))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[derive(Default)]
+struct RecordingMailer {
+    sent: std::sync::Mutex<Vec<Email>>,
+}
+
+#[async_trait::async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, email: &Email) -> Result<(), MailError> {
+        self.sent.lock().unwrap().push(email.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_place_order_and_advance_status() {
+    // A receiver for the operator's order.paid webhook
+    let (sender, mut received) = tokio::sync::mpsc::unbounded_channel::<serde_json::Value>();
+    let receiver = axum::Router::new().route(
+        "/hooks",
+        axum::routing::post(move |body: String| {
+            let sender = sender.clone();
+            async move {
+                sender.send(serde_json::from_str(&body).unwrap()).unwrap();
+                StatusCode::OK
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let hook_url = format!("http://{}/hooks", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, receiver).await.unwrap() });
+
+    let mailer = Arc::new(RecordingMailer::default());
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.admin_token = Some("secret".to_string());
+    inner.mailer = mailer.clone();
+    inner.webhooks.registry.register(hook_url, vec![web::WebhookEvent::OrderPaid], None, None);
+    let app = web::create_router(Arc::new(inner));
+
+    let post = |uri: &str, body: serde_json::Value| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("authorization", "Bearer secret")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let order = serde_json::json!({
+        "plate": ActuatorPlate::default(),
+        "quantity": 0,
+        "customer_name": "Ada",
+        "customer_email": "not-an-email",
+    });
+    let response = app.clone().oneshot(post("/api/orders", order.clone())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["errors"].as_array().unwrap().len(), 2);
+
+    let order = serde_json::json!({
+        "plate": ActuatorPlate::default(),
+        "quantity": 25,
+        "customer_name": "Ada",
+        "customer_email": "ada@example.com",
+    });
+    let response = app.clone().oneshot(post("/api/orders", order)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let placed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(placed["order_number"], "PL-000001");
+    assert_eq!(placed["status"], "received");
+    assert_eq!(placed["part_number"], ActuatorPlate::default().cache_key());
+    assert!(placed["total_price_cents"].as_u64().unwrap() > 0);
+
+    // The confirmation goes out in the background
+    for _ in 0..50 {
+        if !mailer.sent.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let sent = mailer.sent.lock().unwrap().clone();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "ada@example.com");
+    assert!(sent[0].body.contains("PL-000001"));
+
+    let uri = "/api/admin/orders/PL-000001/status";
+    let response = app.clone().oneshot(post(uri, serde_json::json!({ "status": "confirmed" }))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let response = app.clone().oneshot(post(uri, serde_json::json!({ "status": "quoted" }))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app.clone().oneshot(post(uri, serde_json::json!({ "status": "confirmed" }))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let confirmed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(confirmed["status"], "confirmed");
+
+    // Only confirming the order counts as payment
+    let delivery = tokio::time::timeout(std::time::Duration::from_secs(10), received.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(delivery["event"], "order.paid");
+    assert_eq!(delivery["data"]["order"]["order_number"], "PL-000001");
+    assert_eq!(delivery["data"]["order"]["status"], "confirmed");
+    assert!(received.try_recv().is_err());
+
+    let response = app
+        .oneshot(post("/api/admin/orders/PL-000999/status", serde_json::json!({ "status": "quoted" })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_email_cached_model() {
+    let cache = Arc::new(MemoryCache::new());
+    cache
+        .put("plate-abc", &CachedFiles::new().with(Artifact::Step, "step"))
+        .await
+        .unwrap();
+    let mailer = Arc::new(RecordingMailer::default());
+    let mut inner = AppStateInner::new(cache);
+    inner.mailer = mailer.clone();
+    let app = web::create_router(Arc::new(inner));
+
+    let post = |uri: &str, email: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::json!({ "email": email }).to_string()))
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(post("/api/models/plate-abc/email", "ada@example.com")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["attached"], true);
+    let sent = mailer.sent.lock().unwrap().clone();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "ada@example.com");
+    assert_eq!(sent[0].attachments[0].file_name, "plate-abc.step");
+    assert_eq!(sent[0].attachments[0].data, "step");
+
+    let response = app.clone().oneshot(post("/api/models/plate-abc/email", "not-an-email")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app.clone().oneshot(post("/api/models/plate-0000/email", "ada@example.com")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app.oneshot(post("/api/models/..%2Fplate-abc/email", "ada@example.com")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(mailer.sent.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_webhook_admin_lifecycle() {
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.admin_token = Some("secret".to_string());
+    let app = web::create_router(Arc::new(inner));
+
+    let body = serde_json::json!({
+        "url": "https://erp.example/hooks",
+        "events": ["order.paid", "job.completed"]
+    });
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/admin/webhooks")
+                .header("authorization", "Bearer secret")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(created["secret"].as_str().is_some());
+    let id = created["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/admin/webhooks")
+                .header("authorization", "Bearer secret")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let listed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert!(listed[0].get("secret").is_none());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/api/admin/webhooks/{}", id))
+                .header("authorization", "Bearer secret")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_client_webhook_hears_when_its_job_finishes() {
+    // A receiver that records each delivery's signature and body
+    let (sender, mut received) = tokio::sync::mpsc::unbounded_channel::<(String, serde_json::Value)>();
+    let receiver = axum::Router::new().route(
+        "/hooks",
+        axum::routing::post(move |headers: axum::http::HeaderMap, body: String| {
+            let sender = sender.clone();
+            async move {
+                let signature = headers[web::SIGNATURE_HEADER].to_str().unwrap().to_string();
+                sender.send((signature, serde_json::from_str(&body).unwrap())).unwrap();
+                StatusCode::OK
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let hook_url = format!("http://{}/hooks", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, receiver).await.unwrap() });
+
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.engine = Arc::new(parametric::mock::MockBackend::new());
+    // The receiver is plain http on loopback
+    inner.webhooks = Arc::new(web::WebhookDispatcher::default().with_private_targets(true));
+    let key = web::ApiKey {
+        id: "key-1".to_string(),
+        name: "CI pipeline".to_string(),
+        prefix: "pk_test".to_string(),
+        created_at: "2026-01-01T00:00:00+00:00".to_string(),
+        revoked_at: None,
+        key: None,
+    };
+    inner.api_keys.insert(&key, &web::hash_key("pk_test_secret")).await.unwrap();
+    let app = web::create_router(Arc::new(inner));
+
+    let request = |method: &str, uri: &str, api_key: Option<&str>, body: Body| {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json");
+        if let Some(api_key) = api_key {
+            builder = builder.header("x-api-key", api_key);
+        }
+        builder.body(body).unwrap()
+    };
+    let hook = serde_json::json!({ "url": hook_url, "events": ["job.completed"] }).to_string();
+
+    // Endpoints belong to a key, so registering one needs a key
+    let response = app.clone().oneshot(request("POST", "/api/webhooks", None, Body::from(hook.clone()))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app
+        .clone()
+        .oneshot(request("POST", "/api/webhooks", Some("pk_test_secret"), Body::from(hook)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let endpoint: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let id = endpoint["id"].as_str().unwrap().to_string();
+    assert!(endpoint["secret"].is_string());
+
+    let plate = serde_json::to_string(&ActuatorPlate::default()).unwrap();
+    let response = app
+        .clone()
+        .oneshot(request("POST", "/api/jobs", Some("pk_test_secret"), Body::from(plate)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    let (signature, delivery) = tokio::time::timeout(std::time::Duration::from_secs(10), received.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(signature.starts_with("t=") && signature.contains(",v1="));
+    assert_eq!(delivery["event"], "job.completed");
+    assert_eq!(delivery["data"]["job_id"], job["id"]);
+    assert_eq!(delivery["data"]["status"], "succeeded");
+    assert_eq!(delivery["data"]["cache_key"], ActuatorPlate::default().cache_key());
+    assert!(delivery["data"]["result"]["download_url"].is_string());
+
+    // The attempt is in the endpoint's delivery log, which other keys can't read
+    let uri = format!("/api/webhooks/{}/deliveries", id);
+    let mut log = serde_json::Value::Null;
+    for _ in 0..50 {
+        let response = app
+            .clone()
+            .oneshot(request("GET", &uri, Some("pk_test_secret"), Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        log = serde_json::from_slice(&body).unwrap();
+        if !log.as_array().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(log[0]["event_id"], delivery["id"]);
+    assert_eq!(log[0]["status_code"], 200);
+    assert_eq!(log[0]["succeeded"], true);
+    let response = app.clone().oneshot(request("GET", &uri, None, Body::empty())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app
+        .oneshot(request("DELETE", &format!("/api/webhooks/{}", id), Some("pk_test_secret"), Body::empty()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_client_webhooks_must_be_public_https() {
+    let inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    let key = web::ApiKey {
+        id: "key-1".to_string(),
+        name: "CI pipeline".to_string(),
+        prefix: "pk_test".to_string(),
+        created_at: "2026-01-01T00:00:00+00:00".to_string(),
+        revoked_at: None,
+        key: None,
+    };
+    inner.api_keys.insert(&key, &web::hash_key("pk_test_secret")).await.unwrap();
+    let app = web::create_router(Arc::new(inner));
+
+    for url in ["https://169.254.169.254/latest/meta-data/", "https://127.0.0.1:3030/api/health", "http://93.184.216.34/hooks"] {
+        let hook = serde_json::json!({ "url": url, "events": ["job.completed"] }).to_string();
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/webhooks")
+                    .header("content-type", "application/json")
+                    .header("x-api-key", "pk_test_secret")
+                    .body(Body::from(hook))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", url);
+    }
+}
+
+#[tokio::test]
+async fn test_request_id_is_generated_and_propagated() {
+    let app = create_test_router();
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/api/health").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert!(response.headers().contains_key("x-request-id"));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/health")
+                .header("x-request-id", "req-123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["x-request-id"], "req-123");
+}
+
+#[tokio::test]
+async fn test_error_bodies_carry_the_request_id() {
+    let app = create_test_router();
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/download/step/no-such-session")
+                .header("x-request-id", "req-456")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["request_id"], "req-456");
+    assert_eq!(json["success"], false);
+
+    // Successful responses are left alone
+    let response = app.oneshot(Request::builder().uri("/api/health").body(Body::empty()).unwrap()).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(json.get("request_id").is_none());
+}
+
+#[tokio::test]
+async fn test_backup_restores_into_fresh_deployment() {
+    let cache = Arc::new(MemoryCache::new());
+    let files = CachedFiles::new()
+        .with(Artifact::Step, "step")
+        .with(Artifact::Gltf, "gltf")
+        .with(Artifact::Stl, "stl")
+        .with(Artifact::Obj, "obj")
+        .with(Artifact::ThreeMf, "3mf")
+        .with(Artifact::Svg, "svg")
+        .with(Artifact::Pdf, "pdf")
+        .with(Artifact::Dxf, "dxf")
+        .with(Artifact::Kcl, "kcl");
+    cache.put("plate-abc", &files).await.unwrap();
+    let mut inner = AppStateInner::new(cache);
+    inner.admin_token = Some("secret".to_string());
+    let app = web::create_router(Arc::new(inner));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/admin/backup?artifacts=true")
+                .header("authorization", "Bearer secret")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/x-tar");
+    let archive = response.into_body().collect().await.unwrap().to_bytes();
+
+    let fresh_cache = Arc::new(MemoryCache::new());
+    let mut inner = AppStateInner::new(fresh_cache.clone());
+    inner.admin_token = Some("secret".to_string());
+    let app = web::create_router(Arc::new(inner));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/admin/restore")
+                .header("authorization", "Bearer secret")
+                .header("content-type", "application/x-tar")
+                .body(Body::from(archive))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["restored"]["artifacts"], 1);
+    assert!(fresh_cache.exists("plate-abc").await);
+}
+
+#[tokio::test]
+async fn test_admin_cache_delete_and_clear() {
+    let cache = Arc::new(MemoryCache::new());
+    let files = CachedFiles::new()
+        .with(Artifact::Step, "step")
+        .with(Artifact::Gltf, "gltf")
+        .with(Artifact::Stl, "stl")
+        .with(Artifact::Obj, "obj")
+        .with(Artifact::ThreeMf, "3mf")
+        .with(Artifact::Svg, "svg")
+        .with(Artifact::Pdf, "pdf")
+        .with(Artifact::Dxf, "dxf")
+        .with(Artifact::Kcl, "kcl");
+    for key in ["plate-a", "plate-b", "plate-c"] {
+        cache.put(key, &files).await.unwrap();
+    }
+    let mut inner = AppStateInner::new(cache.clone());
+    inner.admin_token = Some("secret".to_string());
+    let app = web::create_router(Arc::new(inner));
+
+    let delete = |uri: &str, token: &str| {
+        Request::builder()
+            .method("DELETE")
+            .uri(uri)
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(delete("/api/admin/cache", "wrong")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(delete("/api/admin/cache/plate-a", "secret"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(!cache.exists("plate-a").await);
+
+    let response = app
+        .clone()
+        .oneshot(delete("/api/admin/cache/..%2Fplate-b", "secret"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app.oneshot(delete("/api/admin/cache", "secret")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["removed"], 2);
+    assert!(cache.keys().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_download_cached_model_by_key() {
+    let cache = Arc::new(MemoryCache::new());
+    let files = CachedFiles::new()
+        .with(Artifact::Step, "step")
+        .with(Artifact::Gltf, "gltf")
+        .with(Artifact::Stl, "stl")
+        .with(Artifact::Obj, "obj")
+        .with(Artifact::ThreeMf, "3mf")
+        .with(Artifact::Svg, "svg")
+        .with(Artifact::Pdf, "pdf")
+        .with(Artifact::Dxf, "dxf")
+        .with(Artifact::Kcl, "kcl");
+    cache.put("plate-abc", &files).await.unwrap();
+    let app = web::create_router(Arc::new(AppStateInner::new(cache)));
+
+    let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+    let response = app.clone().oneshot(get("/api/models/plate-abc/download")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/STEP");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "step");
+
+    let response = app
+        .clone()
+        .oneshot(get("/api/models/plate-abc/download?format=stl"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "model/stl");
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"plate-abc.stl\""
+    );
+
+    let response = app
+        .clone()
+        .oneshot(get("/api/models/plate-abc/download?format=pdf"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/pdf");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "pdf");
+
+    let response = app
+        .clone()
+        .oneshot(get("/api/models/plate-abc/download?format=dxf"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/vnd.dxf");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "dxf");
+
+    let response = app
+        .clone()
+        .oneshot(get("/api/models/plate-abc/download?format=kcl"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/plain; charset=utf-8");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "kcl");
+
+    let response = app
+        .clone()
+        .oneshot(get("/api/models/plate-abc/download?format=3mf"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "model/3mf");
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"plate-abc.3mf\""
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "3mf");
+
+    let response = app
+        .clone()
+        .oneshot(get("/api/models/plate-abc/download?format=fbx"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app.clone().oneshot(get("/api/models/plate-0000/download?format=gltf")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app.clone().oneshot(get("/api/models/plate-abc%22x/download")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app.clone().oneshot(get("/api/models/plate-abc/gltf")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "model/gltf+json");
+    assert!(response.headers()["content-disposition"].to_str().unwrap().starts_with("inline"));
+
+    let response = app.clone().oneshot(get("/api/models/..%2F..%2Fetc/gltf")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app.clone().oneshot(get("/api/models/plate-abc/kcl")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/plain; charset=utf-8");
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "inline; filename=\"plate-abc.kcl\""
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "kcl");
+
+    let response = app.clone().oneshot(get("/api/models/plate-0000/kcl")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app.oneshot(get("/api/models/plate-abc%22;x/kcl")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_save_and_load_config() {
+    let app = create_test_router();
+
+    let plate = ActuatorPlate {
+        pin_count: Count::of(4),
+        ..ActuatorPlate::default()
+    };
+    let body = serde_json::json!({ "name": "  Press line A  ", "plate": plate });
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/configs")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let saved: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(saved["name"], "Press line A");
+    let id = saved["id"].as_str().unwrap().to_string();
+
+    let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+    let response = app.clone().oneshot(get("/api/configs")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let listed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+
+    let response = app.clone().oneshot(get(&format!("/api/configs/{}", id))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let loaded: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let loaded: ActuatorPlate = serde_json::from_value(loaded["plate"].clone()).unwrap();
+    assert_eq!(loaded, plate);
+
+    let response = app.clone().oneshot(get("/api/configs/no-such-config")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let body = serde_json::json!({ "name": " ", "plate": plate });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/configs")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_compare_plates() {
+    let app = create_test_router();
+
+    let before = ActuatorPlate::default();
+    let after = ActuatorPlate {
+        plate_thickness: domain::Length::from_mm(12),
+        pin_count: Count::of(4),
+        ..before
+    };
+    let body = serde_json::json!({ "before": before, "after": after });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/compare")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let diff: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        diff["changes"],
+        serde_json::json!([
+            { "field": "pin_count", "before": "6", "after": "4" },
+            { "field": "plate_thickness", "before": "8", "after": "12", "delta_mm": 4.0 },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_bolt_analysis() {
+    let analyze = |load_n: u32| async move {
+        let body = serde_json::json!({ "plate": ActuatorPlate::default(), "load_n": load_n });
+        let response = create_test_router()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/analysis")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+    };
+
+    let (status, json) = analyze(3000).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["bolt_count"], 4);
+    assert_eq!(json["force_per_bolt_n"], 750.0);
+    assert_eq!(json["plate_bearing"]["stress_mpa"], 9.375);
+    assert!(json["safety_factor"].as_f64().unwrap() > 2.0);
+    assert_eq!(json["warnings"], serde_json::json!([]));
+
+    // 15 kN per M10 bolt overloads both the bolts and the 8 mm aluminum around them
+    let (status, json) = analyze(60_000).await;
+    assert_eq!(status, StatusCode::OK);
+    let codes: Vec<_> = json["warnings"].as_array().unwrap().iter().map(|w| w["code"].clone()).collect();
+    assert_eq!(codes, ["BOLT_SHEAR_SAFETY_FACTOR_LOW", "PLATE_BEARING_SAFETY_FACTOR_LOW"]);
+
+    let (status, json) = analyze(0).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["errors"][0]["code"], "LOAD_INVALID");
+}
+
+#[tokio::test]
+async fn test_suggestions_endpoint() {
+    let plate = ActuatorPlate { expected_force_per_pin: Newtons(3000), ..ActuatorPlate::default() };
+    let response = create_test_router()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/suggestions")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&plate).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!([
+            { "field": "bracketHeight", "key": "bracket_height", "value": 180, "unit": "mm", "reason": "Three pin diameters of room for each pin" },
+            { "field": "plateThickness", "key": "plate_thickness", "value": 16, "unit": "mm", "reason": "Very heavy load class (up to 5000 N per pin)" },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_presets_endpoint() {
+    let app = create_test_router();
+
+    let response = app
+        .oneshot(Request::builder().uri("/api/presets").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let presets = json.as_array().unwrap();
+    assert_eq!(presets.len(), domain::presets::PRESETS.len());
+    assert_eq!(presets[0]["id"], "compact-40");
+    // Each preset's plate is a valid request body as-is
+    let plate: ActuatorPlate = serde_json::from_value(presets[0]["plate"].clone()).unwrap();
+    assert_eq!(plate, domain::presets::PRESETS[0].plate);
+}
+
+#[tokio::test]
+async fn test_flags_endpoint_and_disabled_jobs() {
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.flags = Arc::new(FeatureFlags::with(&[(Flag::AsyncJobs, false)]));
+    let app = web::create_router(Arc::new(inner));
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/api/flags").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["flags"]["async_jobs"], false);
+    assert_eq!(json["flags"]["step_fallback"], true);
+
+    let plate = serde_json::to_string(&ActuatorPlate::default()).unwrap();
+    for uri in ["/api/jobs", "/api/plates/batch"] {
+        let body = if uri == "/api/jobs" { plate.clone() } else { format!("[{}]", plate) };
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", uri);
+    }
+}
+
+#[tokio::test]
+async fn test_batch_starts_jobs_for_valid_plates() {
+    let backend = Arc::new(parametric::mock::MockBackend::new());
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.engine = backend.clone();
+    let app = web::create_router(Arc::new(inner));
+
+    let plates = vec![
+        ActuatorPlate::default(),
+        ActuatorPlate {
+            bolt_spacing: Length::from_mm(0),
+            ..ActuatorPlate::default()
+        },
+        ActuatorPlate {
+            pin_count: Count::of(4),
+            ..ActuatorPlate::default()
+        },
+    ];
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/plates/batch?parallelism=1")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&plates).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["accepted"], 2);
+    assert_eq!(json["rejected"], 1);
+    let items = json["items"].as_array().unwrap();
+    assert!(items[1]["job"].is_null());
+    assert_eq!(items[1]["errors"][0]["code"], "BOLT_SPACING_TOO_SMALL");
+
+    for item in [&items[0], &items[2]] {
+        let uri = format!("/api/jobs/{}", item["job"]["id"].as_str().unwrap());
+        let mut status = serde_json::Value::Null;
+        for _ in 0..500 {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(&uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            status = job["status"].clone();
+            if status == "succeeded" || status == "failed" {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(status, "succeeded");
+    }
+    assert_eq!(backend.generated(), 2);
+
+    // An empty batch is rejected outright
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/plates/batch")
+                .header("content-type", "application/json")
+                .body(Body::from("[]"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_sweep_starts_jobs_for_valid_combinations() {
+    let backend = Arc::new(parametric::mock::MockBackend::new());
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.engine = backend.clone();
+    let app = web::create_router(Arc::new(inner));
+
+    let sweep = |ranges: serde_json::Value| {
+        let body = serde_json::json!({ "base": ActuatorPlate::default(), "ranges": ranges });
+        Request::builder()
+            .method("POST")
+            .uri("/api/sweep")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    // 13 pins is over the limit, so two of the six combinations are skipped
+    let response = app
+        .clone()
+        .oneshot(sweep(serde_json::json!([
+            { "field": "bolt_spacing", "start": 50, "end": 60, "step": 10 },
+            { "field": "pin_count", "start": 11, "end": 13, "step": 1 },
+        ])))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!((json["total"].as_u64(), json["skipped"].as_u64()), (Some(6), Some(2)));
+    let plates = json["plates"].as_array().unwrap();
+    let indices: Vec<u64> = plates.iter().map(|p| p["index"].as_u64().unwrap()).collect();
+    assert_eq!(indices, [0, 1, 3, 4]);
+    assert_eq!(plates[2]["plate"]["bolt_spacing"], 60);
+    assert_eq!(plates[2]["plate"]["pin_count"], 11);
+    assert!(plates[0]["cache_key"].as_str().unwrap().starts_with("plate-"));
+    assert!(plates[0]["job"]["id"].is_string());
+
+    // Ranges that don't parse or leave nothing valid are rejected
+    for ranges in [
+        serde_json::json!([{ "field": "pin_count", "start": 3, "end": 1, "step": 1 }]),
+        serde_json::json!([{ "field": "pin_count", "start": 13, "end": 14, "step": 1 }]),
+    ] {
+        let response = app.clone().oneshot(sweep(ranges)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn test_generation_progress_streams_over_websocket() {
+    use futures_util::StreamExt;
+    use tokio_tungstenite::tungstenite::Message;
+
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.engine = Arc::new(parametric::mock::MockBackend::new());
+    let app = web::create_router(Arc::new(inner));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = app.clone();
+    tokio::spawn(async move { axum::serve(listener, server).await.unwrap() });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/jobs")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&ActuatorPlate::default()).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    let url = format!("ws://{}/ws/generation/{}", addr, job["id"].as_str().unwrap());
+    let (mut socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+    let mut events = Vec::new();
+    while let Some(message) = socket.next().await {
+        match message.unwrap() {
+            Message::Text(text) => events.push(serde_json::from_str::<serde_json::Value>(&text).unwrap()),
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+
+    // Progress only moves forward, and the stream ends with the finished job
+    let progress: Vec<u64> = events.iter().map(|e| e["progress"].as_u64().unwrap()).collect();
+    assert!(progress.windows(2).all(|w| w[0] <= w[1]), "{:?}", progress);
+    let last = events.last().unwrap();
+    assert_eq!(last["status"], "succeeded");
+    assert!(last["result"]["download_url"].is_string());
+
+    let missing = tokio_tungstenite::connect_async(format!("ws://{}/ws/generation/no-such-job", addr)).await;
+    match missing {
+        Err(tokio_tungstenite::tungstenite::Error::Http(response)) => assert_eq!(response.status(), 404),
+        other => panic!("expected a 404, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn test_generation_is_rate_limited_per_client() {
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.rate_limiter = Arc::new(RateLimiter::new(2, true));
+    inner.max_body_bytes = 4096;
+    let app = web::create_router(Arc::new(inner));
+
+    let post = |uri: &str, client: &str, body: String| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("x-forwarded-for", client)
+            .body(Body::from(body))
+            .unwrap()
+    };
+    let invalid = serde_json::json!(ActuatorPlate {
+        bolt_spacing: Length::from_mm(0),
+        ..Default::default()
+    })
+    .to_string();
+
+    for _ in 0..2 {
+        let response = app.clone().oneshot(post("/api/generate", "198.51.100.1", invalid.clone())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+    // Jobs share the generate allowance
+    let response = app.clone().oneshot(post("/api/jobs", "198.51.100.1", invalid.clone())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["retry-after"], "30");
+
+    let response = app.clone().oneshot(post("/api/generate", "198.51.100.2", invalid.clone())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    // Validation is free
+    let response = app.clone().oneshot(post("/api/validate", "198.51.100.1", invalid)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let oversized = format!("{{\"padding\": \"{}\"}}", "x".repeat(8192));
+    let response = app.oneshot(post("/api/validate", "198.51.100.3", oversized)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_state_from_config_applies_its_limits() {
+    let mut config = AppConfig::default();
+    config.rate_limit.per_minute = 1;
+    config.rate_limit.trust_proxy = true;
+    config.rate_limit.max_body_bytes = 4096;
+    let cache: Arc<dyn ModelCache> = Arc::new(MemoryCache::new());
+    let inner = AppStateInner::from_config(
+        config.clone(),
+        cache,
+        Arc::new(CacheMetrics::new()),
+        Arc::new(FeatureFlags::default()),
+    );
+    assert_eq!(inner.config, config);
+    let app = web::create_router(Arc::new(inner));
+
+    let post = |uri: &str, body: String| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("x-forwarded-for", "198.51.100.4")
+            .body(Body::from(body))
+            .unwrap()
+    };
+    let invalid = serde_json::json!(ActuatorPlate {
+        bolt_spacing: Length::from_mm(0),
+        ..Default::default()
+    })
+    .to_string();
+    let response = app.clone().oneshot(post("/api/generate", invalid.clone())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = app.clone().oneshot(post("/api/generate", invalid)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let oversized = format!("{{\"padding\": \"{}\"}}", "x".repeat(8192));
+    let response = app.oneshot(post("/api/validate", oversized)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_cross_site_posts_are_rejected() {
+    let mut config = AppConfig::default();
+    config.csrf.trusted_origins = vec!["https://portal.example.com".to_string()];
+    let cache: Arc<dyn ModelCache> = Arc::new(MemoryCache::new());
+    let state = AppStateInner::from_config(config, cache, Arc::new(CacheMetrics::new()), Arc::new(FeatureFlags::default()));
+    let app = web::create_router(Arc::new(state));
+
+    let request = |method: &str, site: &str, origin: &str| {
+        Request::builder()
+            .method(method)
+            .uri("/api/configs")
+            .header("content-type", "application/json")
+            .header("sec-fetch-site", site)
+            .header("origin", origin)
+            .body(Body::from(r#"{"name": "Forged", "plate": {}}"#))
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(request("POST", "cross-site", "https://evil.example")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    // Reading is still allowed
+    let response = app.clone().oneshot(request("GET", "cross-site", "https://evil.example")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    // Our own pages and trusted portals get as far as validation
+    for (site, origin) in [("same-origin", "http://localhost:3030"), ("cross-site", "https://portal.example.com")] {
+        let response = app.clone().oneshot(request("POST", site, origin)).await.unwrap();
+        assert_ne!(response.status(), StatusCode::FORBIDDEN, "{} {}", site, origin);
+    }
+}
+
+#[tokio::test]
+async fn test_api_keys_gate_generation_when_required() {
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.admin_token = Some("secret".to_string());
+    inner.require_api_key = true;
+    let app = web::create_router(Arc::new(inner));
+
+    let request = |method: &str, uri: &str, key: Option<&str>, body: String| {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json");
+        builder = match key {
+            Some(key) => builder.header("x-api-key", key),
+            None => builder.header("authorization", "Bearer secret"),
+        };
+        builder.body(Body::from(body)).unwrap()
+    };
+    let invalid = serde_json::json!(ActuatorPlate {
+        bolt_spacing: Length::from_mm(0),
+        ..Default::default()
+    })
+    .to_string();
+
+    let response = app.clone().oneshot(request("POST", "/api/generate", None, invalid.clone())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = app.clone().oneshot(request("POST", "/api/generate", Some("pk_guess"), invalid.clone())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let body = serde_json::json!({ "name": "ERP importer" }).to_string();
+    let response = app.clone().oneshot(request("POST", "/api/admin/api-keys", None, body)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let key = created["key"].as_str().unwrap().to_string();
+    let id = created["id"].as_str().unwrap().to_string();
+
+    // A valid key gets as far as plate validation
+    let response = app.clone().oneshot(request("POST", "/api/generate", Some(&key), invalid.clone())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    // Validation doesn't need a key
+    let response = app.clone().oneshot(request("POST", "/api/validate", None, invalid.clone())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app.clone().oneshot(request("GET", "/api/admin/api-keys", None, String::new())).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let listed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert!(listed[0].get("key").is_none());
+
+    let uri = format!("/api/admin/api-keys/{}", id);
+    let response = app.clone().oneshot(request("DELETE", &uri, None, String::new())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = app.clone().oneshot(request("POST", "/api/orders", Some(&key), invalid)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = app.oneshot(request("DELETE", "/api/admin/api-keys/missing", None, String::new())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_cache_stats_count_generate_lookups() {
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.engine = Arc::new(parametric::mock::MockBackend::new());
+    let app = web::create_router(Arc::new(inner));
+    let generate = || {
+        Request::builder()
+            .method("POST")
+            .uri("/api/generate")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&ActuatorPlate::default()).unwrap()))
+            .unwrap()
+    };
+    let stats = |app: axum::Router| async move {
+        let response = app.oneshot(Request::builder().uri("/api/cache/stats").body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+    };
+
+    let response = app.clone().oneshot(generate()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    // The files are cached in the background
+    let mut json = stats(app.clone()).await;
+    for _ in 0..50 {
+        if json["puts"] == 1 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        json = stats(app.clone()).await;
+    }
+    assert_eq!(json["puts"], 1);
+
+    let response = app.clone().oneshot(generate()).await.unwrap();
+    assert_eq!(response.headers()["x-cache"], "HIT");
+    let json = stats(app.clone()).await;
+    assert_eq!((json["hits"].as_u64(), json["misses"].as_u64()), (Some(1), Some(1)));
+    assert_eq!(json["hit_ratio"], 0.5);
+    assert_eq!(json["get_latency"]["count"], 2);
+
+    let response = app
+        .oneshot(Request::builder().uri("/api/cache/stats?format=prometheus").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/plain"));
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    assert!(text.contains("platerator_cache_misses_total 1\n"));
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint_counts_requests_per_route() {
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.metrics_token = Some("scrape".to_string());
+    let app = web::create_router(Arc::new(inner));
+
+    for _ in 0..2 {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/jobs/no-such-job").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    let metrics = |token: Option<&str>| {
+        let mut builder = Request::builder().uri("/metrics");
+        if let Some(token) = token {
+            builder = builder.header("authorization", format!("Bearer {}", token));
+        }
+        builder.body(Body::empty()).unwrap()
+    };
+    let response = app.clone().oneshot(metrics(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = app.clone().oneshot(metrics(Some("wrong"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app.oneshot(metrics(Some("scrape"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    // Labeled by route pattern, not the job ID in the URL
+    assert!(text.contains(
+        "platerator_http_requests_total{method=\"GET\",route=\"/api/jobs/{job_id}\",status=\"404\"} 2"
+    ));
+    assert!(text.contains("platerator_cache_hits_total 0"));
+}
+
+#[tokio::test]
+async fn test_ready_reports_each_dependency() {
+    struct Unreachable;
+
+    #[async_trait::async_trait]
+    impl ModelCache for Unreachable {
+        async fn exists(&self, _: &str) -> bool {
+            false
+        }
+        async fn get(&self, _: &str) -> Result<CachedFiles, web::CacheError> {
+            Err(web::CacheError::Config("bucket missing".to_string()))
+        }
+        async fn put(&self, _: &str, _: &CachedFiles) -> Result<(), web::CacheError> {
+            Err(web::CacheError::Config("bucket missing".to_string()))
+        }
+        async fn keys(&self) -> Result<Vec<String>, web::CacheError> {
+            Ok(Vec::new())
+        }
+        async fn delete(&self, _: &str) -> Result<(), web::CacheError> {
+            Ok(())
+        }
+        async fn check(&self) -> Result<(), web::CacheError> {
+            Err(web::CacheError::Config("bucket missing".to_string()))
+        }
+    }
+
+    let ready = |cache: Arc<dyn ModelCache>| async move {
+        let mut inner = AppStateInner::new(cache);
+        inner.engine = Arc::new(parametric::mock::MockBackend::new());
+        let response = web::create_router(Arc::new(inner))
+            .oneshot(Request::builder().uri("/api/ready").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+    };
+
+    let (status, json) = ready(Arc::new(MemoryCache::new())).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["ready"], true);
+    let names: Vec<_> = json["dependencies"].as_array().unwrap().iter().map(|d| d["name"].clone()).collect();
+    assert_eq!(names, ["cache", "engine", "configs", "orders", "api_keys", "audit"]);
+
+    let (status, json) = ready(Arc::new(Unreachable)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(json["ready"], false);
+    assert_eq!(json["dependencies"][0]["ok"], false);
+    assert!(json["dependencies"][0]["error"].as_str().unwrap().contains("bucket missing"));
+    assert_eq!(json["dependencies"][1]["ok"], true);
+}
@@ -0,0 +1,217 @@
+//! `AwsCache` against a real S3 and DynamoDB API, such as LocalStack.
+//!
+//! Skipped unless AWS_CACHE_TEST_ENDPOINT is set. `just test-aws` starts LocalStack
+//! in Docker and runs these with it; to use your own, e.g.
+//! `AWS_CACHE_TEST_ENDPOINT=http://localhost:4566 cargo test -p web --test aws_cache`.
+//! Each test creates its own bucket and table and deletes them afterwards.
+
+use aws_sdk_dynamodb::types::{AttributeDefinition, BillingMode, KeySchemaElement, KeyType, ScalarAttributeType};
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+use futures_util::StreamExt;
+use web::{Artifact, AwsCache, CacheError, CachedFiles, ModelCache, S3Options};
+
+const ENDPOINT_VAR: &str = "AWS_CACHE_TEST_ENDPOINT";
+
+/// A bucket and table of their own for one test.
+struct Stores {
+    s3: aws_sdk_s3::Client,
+    dynamo: aws_sdk_dynamodb::Client,
+    bucket: String,
+    table: String,
+}
+
+impl Stores {
+    /// `None`, after saying so, when no endpoint is configured.
+    async fn create() -> Option<Self> {
+        let Ok(endpoint) = std::env::var(ENDPOINT_VAR) else {
+            eprintln!("{} not set; skipping AwsCache integration test", ENDPOINT_VAR);
+            return None;
+        };
+        let config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .endpoint_url(&endpoint)
+            .region(aws_config::Region::new("us-east-1"))
+            .credentials_provider(aws_sdk_s3::config::Credentials::new("test", "test", None, None, "aws-cache-tests"))
+            .load()
+            .await;
+        let s3 = S3Options {
+            endpoint_url: Some(endpoint),
+            force_path_style: true,
+            ..Default::default()
+        }
+        .build_client(&config);
+        let dynamo = aws_sdk_dynamodb::Client::new(&config);
+
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let bucket = format!("platerator-test-{}", id);
+        let table = format!("platerator-test-{}", id);
+        s3.create_bucket().bucket(&bucket).send().await.expect("create bucket");
+        dynamo
+            .create_table()
+            .table_name(&table)
+            .attribute_definitions(
+                AttributeDefinition::builder()
+                    .attribute_name("plate_hash")
+                    .attribute_type(ScalarAttributeType::S)
+                    .build()
+                    .unwrap(),
+            )
+            .key_schema(
+                KeySchemaElement::builder()
+                    .attribute_name("plate_hash")
+                    .key_type(KeyType::Hash)
+                    .build()
+                    .unwrap(),
+            )
+            .billing_mode(BillingMode::PayPerRequest)
+            .send()
+            .await
+            .expect("create table");
+        Some(Self { s3, dynamo, bucket, table })
+    }
+
+    fn cache(&self) -> AwsCache {
+        AwsCache::new(self.s3.clone(), self.dynamo.clone(), self.bucket.clone(), self.table.clone())
+    }
+
+    fn s3_only_cache(&self) -> AwsCache {
+        AwsCache::s3_only(self.s3.clone(), self.bucket.clone())
+    }
+
+    async fn put_object(&self, key: &str, data: &'static [u8]) {
+        self.s3
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from_static(data))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    async fn delete_object(&self, key: &str) {
+        self.s3.delete_object().bucket(&self.bucket).key(key).send().await.unwrap();
+    }
+
+    async fn destroy(self) {
+        let listed = self.s3.list_objects_v2().bucket(&self.bucket).send().await.unwrap();
+        for object in listed.contents() {
+            self.delete_object(object.key().unwrap()).await;
+        }
+        self.s3.delete_bucket().bucket(&self.bucket).send().await.unwrap();
+        self.dynamo.delete_table().table_name(&self.table).send().await.unwrap();
+    }
+}
+
+/// A complete entry whose files are named after their artifacts, with the STEP
+/// file `step_size` bytes long.
+fn entry(step_size: usize) -> CachedFiles {
+    let step: Vec<u8> = (0..step_size).map(|i| (i % 251) as u8).collect();
+    Artifact::ALL
+        .into_iter()
+        .map(|artifact| match artifact {
+            Artifact::Step => (artifact, Bytes::from(step.clone())),
+            _ => (artifact, Bytes::from(format!("{} content", artifact.extension()))),
+        })
+        .collect()
+}
+
+async fn read_stream(cache: &AwsCache, key: &str, artifact: Artifact) -> (usize, Result<Vec<u8>, CacheError>) {
+    let stream = match cache.stream(key, artifact).await {
+        Ok(stream) => stream,
+        Err(e) => return (0, Err(e)),
+    };
+    let mut body = stream.body;
+    let mut chunks = 0;
+    let mut data = Vec::new();
+    while let Some(chunk) = body.next().await {
+        match chunk {
+            Ok(chunk) => {
+                chunks += 1;
+                data.extend_from_slice(&chunk);
+            }
+            Err(e) => return (chunks, Err(e)),
+        }
+    }
+    (chunks, Ok(data))
+}
+
+#[tokio::test]
+async fn test_put_get_exists_and_delete() {
+    let Some(stores) = Stores::create().await else { return };
+    for cache in [stores.cache(), stores.s3_only_cache()] {
+        cache.check().await.unwrap();
+        assert!(!cache.exists("plate-1").await);
+        assert!(matches!(cache.get("plate-1").await, Err(CacheError::NotFound)));
+
+        let files = entry(1024);
+        cache.put("plate-1", &files).await.unwrap();
+        assert!(cache.exists("plate-1").await);
+        assert_eq!(cache.get("plate-1").await.unwrap(), files);
+        assert_eq!(cache.keys().await.unwrap(), vec!["plate-1".to_string()]);
+        let (_, step) = read_stream(&cache, "plate-1", Artifact::Step).await;
+        assert_eq!(step.unwrap(), files.get(Artifact::Step).unwrap().to_vec());
+
+        cache.delete("plate-1").await.unwrap();
+        assert!(!cache.exists("plate-1").await);
+        assert!(matches!(cache.get("plate-1").await, Err(CacheError::NotFound)));
+        assert!(cache.keys().await.unwrap().is_empty());
+    }
+    stores.destroy().await;
+}
+
+#[tokio::test]
+async fn test_missing_s3_object_with_dynamodb_row_is_a_miss() {
+    let Some(stores) = Stores::create().await else { return };
+    let cache = stores.cache();
+    cache.put("plate-2", &entry(1024)).await.unwrap();
+
+    // The row still says the entry is there, but a file is gone, e.g. expired by
+    // a lifecycle rule before DynamoDB TTL caught up
+    stores.delete_object("plate-2/drawing.pdf").await;
+    assert!(cache.exists("plate-2").await);
+    let err = cache.get("plate-2").await.unwrap_err();
+    assert!(matches!(err, CacheError::NotFound), "{}", err);
+    assert!(!err.is_retryable());
+    let (_, pdf) = read_stream(&cache, "plate-2", Artifact::Pdf).await;
+    assert!(matches!(pdf, Err(CacheError::NotFound)));
+
+    // Files still present stream as usual
+    let (_, stl) = read_stream(&cache, "plate-2", Artifact::Stl).await;
+    assert_eq!(stl.unwrap(), b"stl content");
+
+    // Regenerating the model repairs the entry
+    cache.put("plate-2", &entry(1024)).await.unwrap();
+    assert_eq!(cache.get("plate-2").await.unwrap(), entry(1024));
+    stores.destroy().await;
+}
+
+#[tokio::test]
+async fn test_replaced_s3_object_fails_its_checksum() {
+    let Some(stores) = Stores::create().await else { return };
+    let cache = stores.cache();
+    cache.put("plate-3", &entry(1024)).await.unwrap();
+
+    stores.put_object("plate-3/model.stl", b"not the stl").await;
+    assert!(matches!(cache.get("plate-3").await, Err(CacheError::Corrupt { file: "model.stl" })));
+    let (_, stl) = read_stream(&cache, "plate-3", Artifact::Stl).await;
+    assert!(matches!(stl, Err(CacheError::Corrupt { file: "model.stl" })));
+    stores.destroy().await;
+}
+
+#[tokio::test]
+async fn test_oversized_upload_round_trips_and_streams_in_chunks() {
+    let Some(stores) = Stores::create().await else { return };
+    let cache = stores.cache();
+    // Larger than S3's 5 MiB minimum multipart part, as a single PutObject
+    let files = entry(24 * 1024 * 1024);
+    cache.put("plate-4", &files).await.unwrap();
+
+    assert_eq!(cache.get("plate-4").await.unwrap(), files);
+    let stream = cache.stream("plate-4", Artifact::Step).await.unwrap();
+    assert_eq!(stream.length, Some(24 * 1024 * 1024));
+    let (chunks, step) = read_stream(&cache, "plate-4", Artifact::Step).await;
+    assert!(chunks > 1, "streamed in {} chunk(s)", chunks);
+    assert_eq!(step.unwrap(), files.get(Artifact::Step).unwrap().to_vec());
+    stores.destroy().await;
+}
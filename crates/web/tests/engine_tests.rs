@@ -0,0 +1,315 @@
+//! End-to-end generation tests against the mock zoo CLI.
+#![cfg(unix)]
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use domain::ActuatorPlate;
+use http_body_util::BodyExt;
+use parametric::mock::{MockBehavior, MockEngine};
+use std::sync::Arc;
+use tower::ServiceExt;
+use web::{ApiKey, AppStateInner, AuditKind, AuditStore, MemoryAuditStore, MemoryCache, ModelCache};
+
+fn router_with(engine: &MockEngine, cache: Arc<MemoryCache>) -> axum::Router {
+    let mut inner = AppStateInner::new(cache);
+    inner.engine = Arc::new(engine.cli());
+    web::create_router(Arc::new(inner))
+}
+
+async fn generate(app: axum::Router) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/generate")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&ActuatorPlate::default()).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+/// The mock engine's STEP file, with the plate's metadata written into its header.
+fn assert_is_mock_step(body: &[u8]) {
+    let step = std::str::from_utf8(body).unwrap();
+    assert!(step.contains("FILE_NAME('mock.step','"), "{}", step);
+    let key = format!("'CACHE_KEY {}'", ActuatorPlate::default().cache_key());
+    assert!(step.contains(&key), "{}", step);
+    assert!(step.ends_with("DATA;\nENDSEC;\nEND-ISO-10303-21;\n"), "{}", step);
+}
+
+#[tokio::test]
+async fn test_generate_download_and_cache_with_mock_engine() {
+    let engine = MockEngine::new(MockBehavior::Succeed).unwrap();
+    let cache = Arc::new(MemoryCache::new());
+    let app = router_with(&engine, cache.clone());
+
+    let (status, json) = generate(app.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(json["download_url"].as_str().unwrap())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_is_mock_step(&body);
+
+    // The drawing is rendered alongside the model
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(json["pdf_url"].as_str().unwrap())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/pdf");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(body.starts_with(b"%PDF-"));
+
+    // So is the cut profile
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(json["dxf_url"].as_str().unwrap())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/vnd.dxf");
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"actuator_plate.dxf\""
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(body.ends_with(b"EOF\n"));
+
+    // And the KCL source, for editing the plate further
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(json["kcl_url"].as_str().unwrap())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, parametric::kcl::emit(&ActuatorPlate::default()));
+
+    // Caching happens in the background after the response
+    let key = ActuatorPlate::default().cache_key();
+    for _ in 0..50 {
+        if cache.exists(&key).await {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert!(cache.exists(&key).await);
+
+    // A second request is served from the cache without touching the engine
+    let (status, _) = generate(app).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(engine.exports(), 1);
+}
+
+#[tokio::test]
+async fn test_engine_closed_early_returns_error() {
+    let engine = MockEngine::new(MockBehavior::CloseEarly).unwrap();
+    let (status, json) = generate(router_with(&engine, Arc::new(MemoryCache::new()))).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["success"], false);
+    assert!(json["errors"][0]["message"].as_str().unwrap().contains("closed early"));
+}
+
+#[tokio::test]
+async fn test_generation_requests_are_audited_with_who_and_what() {
+    let engine = MockEngine::new(MockBehavior::Succeed).unwrap();
+    let audit = Arc::new(MemoryAuditStore::new());
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.engine = Arc::new(engine.cli());
+    inner.audit = audit.clone();
+    let key = ApiKey {
+        id: "key-1".to_string(),
+        name: "CI pipeline".to_string(),
+        prefix: "pk_test".to_string(),
+        created_at: "2026-01-01T00:00:00+00:00".to_string(),
+        revoked_at: None,
+        key: None,
+    };
+    inner.api_keys.insert(&key, &web::hash_key("pk_test_secret")).await.unwrap();
+    let app = web::create_router(Arc::new(inner));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/generate")
+                .header("content-type", "application/json")
+                .header("x-api-key", "pk_test_secret")
+                .body(Body::from(serde_json::to_string(&ActuatorPlate::default()).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The audit log is written in the background
+    let mut events = Vec::new();
+    for _ in 0..50 {
+        events = audit.recent(10).await.unwrap();
+        if events.len() == 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let request = events.iter().find(|e| e.kind == AuditKind::Request).unwrap();
+    assert_eq!(request.actor, "key:CI pipeline");
+    assert_eq!(request.detail, "/api/generate");
+    assert_eq!(request.subject, ActuatorPlate::default().cache_key());
+    assert_eq!(request.plate, Some(ActuatorPlate::default()));
+    assert!(events.iter().any(|e| e.kind == AuditKind::Generated));
+}
+
+#[tokio::test]
+async fn test_engine_failure_is_audited_with_its_error_text() {
+    let engine = MockEngine::new(MockBehavior::CloseEarly).unwrap();
+    let audit = Arc::new(MemoryAuditStore::new());
+    let mut inner = AppStateInner::new(Arc::new(MemoryCache::new()));
+    inner.engine = Arc::new(engine.cli());
+    inner.audit = audit.clone();
+    let (status, _) = generate(web::create_router(Arc::new(inner))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // The audit log is written in the background
+    let mut events = Vec::new();
+    for _ in 0..50 {
+        events = audit.recent(10).await.unwrap();
+        if events.iter().any(|e| e.kind == AuditKind::EngineFailure) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let failure = events.iter().find(|e| e.kind == AuditKind::EngineFailure).unwrap();
+    assert_eq!(failure.subject, ActuatorPlate::default().cache_key());
+    assert!(failure.detail.contains("closed early"));
+    assert!(failure.duration_ms.is_some());
+}
+
+#[tokio::test]
+async fn test_engine_recovers_after_transient_failures() {
+    let engine = MockEngine::new(MockBehavior::CloseEarlyTimes(1)).unwrap();
+    let app = router_with(&engine, Arc::new(MemoryCache::new()));
+
+    // The dropped export is retried within the same request
+    let (status, _) = generate(app).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(engine.exports(), 2);
+}
+
+/// Poll a job until it leaves `queued`/`running`.
+async fn wait_for_job(app: axum::Router, location: &str) -> serde_json::Value {
+    for _ in 0..200 {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(location).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        if job["status"] == "succeeded" || job["status"] == "failed" {
+            return job;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("job at {} did not finish", location);
+}
+
+async fn start_job(app: axum::Router) -> String {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/jobs")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&ActuatorPlate::default()).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    response.headers()["location"].to_str().unwrap().to_string()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_concurrent_identical_requests_share_one_generation() {
+    let engine = MockEngine::with_latency(MockBehavior::Succeed, std::time::Duration::from_millis(500)).unwrap();
+    let app = router_with(&engine, Arc::new(MemoryCache::new()));
+
+    let first = tokio::spawn(generate(app.clone()));
+    let second = tokio::spawn(generate(app.clone()));
+    let (first, second) = (first.await.unwrap(), second.await.unwrap());
+
+    assert_eq!(first.0, StatusCode::OK, "{}", first.1);
+    assert_eq!(second.0, StatusCode::OK, "{}", second.1);
+    assert_ne!(first.1["session_id"], second.1["session_id"]);
+    assert_eq!(engine.exports(), 1);
+}
+
+#[tokio::test]
+async fn test_generation_job_succeeds_with_mock_engine() {
+    let engine = MockEngine::new(MockBehavior::Succeed).unwrap();
+    let app = router_with(&engine, Arc::new(MemoryCache::new()));
+
+    let location = start_job(app.clone()).await;
+    let job = wait_for_job(app.clone(), &location).await;
+    assert_eq!(job["status"], "succeeded");
+    assert_eq!(job["progress"], 100);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(job["result"]["download_url"].as_str().unwrap())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_is_mock_step(&body);
+}
+
+#[tokio::test]
+async fn test_generation_job_reports_engine_failure() {
+    let engine = MockEngine::new(MockBehavior::CloseEarly).unwrap();
+    let app = router_with(&engine, Arc::new(MemoryCache::new()));
+
+    let location = start_job(app.clone()).await;
+    let job = wait_for_job(app, &location).await;
+    assert_eq!(job["status"], "failed");
+    assert!(job["result"].is_null());
+    assert!(job["errors"][0]["message"].as_str().unwrap().contains("closed early"));
+}
@@ -0,0 +1,267 @@
+//! Malformed bodies against the plate routes: every one must get a structured 4xx,
+//! never a panic, a 5xx, or a plain-text rejection.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use domain::ActuatorPlate;
+use http_body_util::BodyExt;
+use proptest::prelude::*;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+use web::{AppStateInner, MemoryCache};
+
+/// Routes taking a plate body. None of them generate for a malformed body, so the
+/// default `zoo` engine is never run.
+const PLATE_ROUTES: [&str; 4] = ["/api/validate", "/api/suggestions", "/api/generate", "/api/jobs"];
+
+fn create_test_router() -> axum::Router {
+    let state = Arc::new(AppStateInner::new(Arc::new(MemoryCache::new())));
+    web::create_router(state)
+}
+
+async fn post(uri: &str, content_type: Option<&str>, body: impl Into<Body>) -> (StatusCode, Value) {
+    let mut request = Request::builder().method("POST").uri(uri);
+    if let Some(content_type) = content_type {
+        request = request.header("content-type", content_type);
+    }
+    let response = create_test_router().oneshot(request.body(body.into()).unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes)
+        .unwrap_or_else(|_| panic!("{} {} answered with a non-JSON body: {:?}", uri, status, bytes));
+    (status, json)
+}
+
+/// Asserts `uri` rejects `body` with `status` and a structured error carrying `code`.
+async fn assert_rejected(uri: &str, body: &str, status: StatusCode, code: &str) -> Value {
+    let (got, json) = post(uri, Some("application/json"), body.to_string()).await;
+    assert_eq!(got, status, "{} accepted or misjudged a body: {}", uri, json);
+    assert_eq!(json["success"], false);
+    assert_eq!(json["errors"][0]["code"], code, "{}", json);
+    assert!(json["errors"][0]["message"].as_str().is_some_and(|m| !m.is_empty()));
+    json
+}
+
+/// A valid plate with `field` set to `value`.
+fn plate_with(field: &str, value: Value) -> String {
+    let mut plate = serde_json::to_value(ActuatorPlate::default()).unwrap();
+    plate[field] = value;
+    plate.to_string()
+}
+
+/// A valid plate without `field`.
+fn plate_without(field: &str) -> String {
+    let mut plate = serde_json::to_value(ActuatorPlate::default()).unwrap();
+    plate.as_object_mut().unwrap().remove(field);
+    plate.to_string()
+}
+
+#[tokio::test]
+async fn test_huge_and_negative_numbers_are_rejected() {
+    let values = [
+        json!(-1),
+        json!(-0.5),
+        json!(i64::MIN),
+        json!(u64::MAX),
+        json!(1e300),
+        json!(4_294_967_296u64),
+    ];
+    for uri in PLATE_ROUTES {
+        for value in &values {
+            for field in ["bolt_spacing", "plate_thickness", "pin_count", "expected_force_per_pin"] {
+                let json = assert_rejected(
+                    uri,
+                    &plate_with(field, value.clone()),
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "INVALID_FIELD",
+                )
+                .await;
+                assert_eq!(json["errors"][0]["fields"], json!([field]));
+            }
+        }
+        // Too big even for an f64
+        let body = plate_with("bolt_spacing", json!(0)).replace("\"bolt_spacing\":0", "\"bolt_spacing\":1e400");
+        let json = assert_rejected(uri, &body, StatusCode::UNPROCESSABLE_ENTITY, "INVALID_FIELD").await;
+        assert_eq!(json["errors"][0]["fields"], json!(["bolt_spacing"]));
+    }
+}
+
+#[tokio::test]
+async fn test_missing_fields_are_rejected() {
+    for uri in PLATE_ROUTES {
+        for field in ["bolt_spacing", "bolt_size", "plate_thickness", "expected_force_per_pin"] {
+            assert_rejected(uri, &plate_without(field), StatusCode::UNPROCESSABLE_ENTITY, "INVALID_FIELD").await;
+        }
+        assert_rejected(uri, "{}", StatusCode::UNPROCESSABLE_ENTITY, "INVALID_FIELD").await;
+    }
+}
+
+#[tokio::test]
+async fn test_wrong_types_are_rejected() {
+    let cases = [
+        ("bolt_spacing", json!(true)),
+        ("bolt_spacing", json!([60])),
+        ("bolt_size", json!(10)),
+        ("bolt_size", json!("M14")),
+        ("material", json!({"name": "aluminum"})),
+        ("pin_count", json!("six")),
+        ("bolt_holes", json!(null)),
+        ("tolerances", json!("tight")),
+    ];
+    for uri in PLATE_ROUTES {
+        for (field, value) in &cases {
+            let json = assert_rejected(
+                uri,
+                &plate_with(field, value.clone()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "INVALID_FIELD",
+            )
+            .await;
+            assert!(json["errors"][0]["fields"][0].as_str().unwrap().starts_with(field), "{}", json);
+        }
+        for body in ["null", "[]", "42", "\"plate\""] {
+            assert_rejected(uri, body, StatusCode::UNPROCESSABLE_ENTITY, "INVALID_FIELD").await;
+        }
+    }
+
+    // The analysis body wraps the plate, so the path names both
+    let body = format!("{{\"plate\": {}, \"load_n\": 3000}}", plate_with("bolt_size", json!("M99")));
+    let json = assert_rejected("/api/analysis", &body, StatusCode::UNPROCESSABLE_ENTITY, "INVALID_FIELD").await;
+    assert_eq!(json["errors"][0]["fields"], json!(["plate.bolt_size"]));
+}
+
+#[tokio::test]
+async fn test_deeply_nested_junk_is_rejected() {
+    let deep_array = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+    let deep_object = format!("{}1{}", "{\"a\":".repeat(10_000), "}".repeat(10_000));
+    let bodies = [
+        deep_array.clone(),
+        deep_object.clone(),
+        plate_with("bolt_spacing", json!(0)).replace("\"bolt_spacing\":0", &format!("\"bolt_spacing\":{}", deep_array)),
+        plate_with("bolt_size", json!(0)).replace("\"bolt_size\":0", &format!("\"bolt_size\":{}", deep_object)),
+    ];
+    for uri in PLATE_ROUTES {
+        for body in &bodies {
+            let (status, json) = post(uri, Some("application/json"), body.clone()).await;
+            assert!(status.is_client_error(), "{} answered {}", uri, status);
+            assert_eq!(json["success"], false);
+            assert!(json["errors"][0]["code"].is_string(), "{}", json);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_unparseable_bodies_are_rejected() {
+    let valid = plate_with("bolt_spacing", json!(60));
+    let bodies = [
+        String::new(),
+        "{".to_string(),
+        "{\"bolt_spacing\": }".to_string(),
+        format!("{} trailing", valid),
+        valid[..valid.len() / 2].to_string(),
+    ];
+    for uri in PLATE_ROUTES {
+        for body in &bodies {
+            assert_rejected(uri, body, StatusCode::BAD_REQUEST, "INVALID_JSON").await;
+        }
+        let (status, json) = post(uri, Some("application/json"), vec![0xff, 0xfe, b'{']).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["errors"][0]["code"], "INVALID_JSON");
+    }
+}
+
+#[tokio::test]
+async fn test_non_json_content_types_are_rejected() {
+    let body = plate_with("bolt_spacing", json!(60));
+    for uri in PLATE_ROUTES {
+        for content_type in [None, Some("text/plain"), Some("application/x-www-form-urlencoded")] {
+            let (status, json) = post(uri, content_type, body.clone()).await;
+            assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE, "{} {:?}", uri, content_type);
+            assert_eq!(json["errors"][0]["code"], "UNSUPPORTED_MEDIA_TYPE");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_oversized_bodies_are_rejected() {
+    let padding = "x".repeat(web::DEFAULT_MAX_BODY_BYTES + 1);
+    let body = format!("{{\"pad\": \"{}\"}}", padding);
+    for uri in PLATE_ROUTES {
+        let (status, json) = post(uri, Some("application/json"), body.clone()).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(json["errors"][0]["code"], "BODY_TOO_LARGE");
+    }
+}
+
+/// Arbitrary JSON, nested a few levels deep.
+fn junk() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<u64>().prop_map(Value::from),
+        any::<f64>().prop_map(Value::from),
+        ".{0,12}".prop_map(Value::from),
+    ];
+    leaf.prop_recursive(4, 32, 6, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..6).prop_map(Value::from),
+            prop::collection::btree_map(".{0,8}", inner, 0..6)
+                .prop_map(|map| Value::Object(map.into_iter().collect())),
+        ]
+    })
+}
+
+const PLATE_FIELDS: [&str; 12] = [
+    "bolt_spacing",
+    "bolt_size",
+    "bracket_height",
+    "bracket_width",
+    "material",
+    "pin_diameter",
+    "pin_count",
+    "plate_thickness",
+    "expected_force_per_pin",
+    "bolt_holes",
+    "tolerances",
+    "family",
+];
+
+proptest! {
+    #![proptest_config(ProptestConfig { cases: 256, failure_persistence: None, ..ProptestConfig::default() })]
+
+    /// Junk where a plate belongs is a 4xx from every plate route. It can never
+    /// hold every required field, so `/api/generate` never gets as far as the engine.
+    #[test]
+    fn junk_bodies_get_structured_client_errors(body in junk()) {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        for uri in PLATE_ROUTES {
+            let (status, json) = runtime.block_on(post(uri, Some("application/json"), body.to_string()));
+            prop_assert!(status.is_client_error(), "{} answered {} to {}", uri, status, body);
+            prop_assert_eq!(&json["success"], &json!(false));
+            prop_assert!(json["errors"].as_array().is_some_and(|errors| !errors.is_empty()));
+        }
+    }
+
+    /// One field of a valid plate replaced with junk either still validates or gets
+    /// a structured 4xx, never a panic or a 5xx.
+    #[test]
+    fn plates_with_a_junk_field_never_fail_the_server(
+        field in prop::sample::select(PLATE_FIELDS.to_vec()),
+        value in junk(),
+    ) {
+        let body = plate_with(field, value);
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let (status, json) = runtime.block_on(post("/api/validate", Some("application/json"), body.clone()));
+        prop_assert!(
+            status == StatusCode::OK || status.is_client_error(),
+            "/api/validate answered {} to {}: {}", status, body, json
+        );
+        if status.is_client_error() {
+            prop_assert!(json["errors"].as_array().is_some_and(|errors| !errors.is_empty()));
+        }
+    }
+}
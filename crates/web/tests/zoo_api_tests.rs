@@ -0,0 +1,137 @@
+//! End-to-end tests with the engine across a real socket: requests go through the
+//! router to the mock Zoo API, and the files come back through the cache.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use domain::ActuatorPlate;
+use http_body_util::BodyExt;
+use std::sync::Arc;
+use tower::ServiceExt;
+use web::{AppStateInner, MemoryCache, ModelCache};
+use zoo_mock::{MockZoo, MOCK_GLTF, MOCK_STL};
+
+fn router_with(zoo: &MockZoo, cache: Arc<MemoryCache>) -> axum::Router {
+    let mut inner = AppStateInner::new(cache);
+    inner.engine = Arc::new(zoo.backend());
+    web::create_router(Arc::new(inner))
+}
+
+async fn send(app: &axum::Router, request: Request<Body>) -> (StatusCode, axum::http::HeaderMap, Vec<u8>) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let (parts, body) = response.into_parts();
+    (parts.status, parts.headers, body.collect().await.unwrap().to_bytes().to_vec())
+}
+
+fn post(uri: &str, plate: &ActuatorPlate) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_string(plate).unwrap()))
+        .unwrap()
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::builder().uri(uri).body(Body::empty()).unwrap()
+}
+
+/// Wait for the background cache write of `plate`.
+async fn wait_until_cached(cache: &MemoryCache, plate: &ActuatorPlate) {
+    for _ in 0..50 {
+        if cache.exists(&plate.cache_key()).await {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("{} was never cached", plate.cache_key());
+}
+
+#[tokio::test]
+async fn test_generate_cache_and_download_through_the_api() {
+    let zoo = MockZoo::start().unwrap();
+    let cache = Arc::new(MemoryCache::new());
+    let app = router_with(&zoo, cache.clone());
+    let plate = ActuatorPlate::default();
+
+    let (status, headers, body) = send(&app, post("/api/generate", &plate)).await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&body));
+    assert_eq!(headers["x-cache"], "MISS");
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!((zoo.exports(), zoo.conversions()), (1, 2));
+
+    // The STEP file gets the plate's metadata in its header on the way out
+    let (status, _, step) = send(&app, get(json["download_url"].as_str().unwrap())).await;
+    assert_eq!(status, StatusCode::OK);
+    let step = String::from_utf8(step).unwrap();
+    assert!(step.contains(&format!("'CACHE_KEY {}'", plate.cache_key())), "{}", step);
+    let (status, _, stl) = send(&app, get(json["stl_url"].as_str().unwrap())).await;
+    assert_eq!((status, stl.as_slice()), (StatusCode::OK, MOCK_STL.as_bytes()));
+
+    // Once cached, the model is served by cache key and generating again skips the engine
+    wait_until_cached(&cache, &plate).await;
+    let uri = format!("/api/models/{}/download?format=stl", plate.cache_key());
+    let (status, _, stl) = send(&app, get(&uri)).await;
+    assert_eq!((status, stl.as_slice()), (StatusCode::OK, MOCK_STL.as_bytes()));
+    let (status, headers, _) = send(&app, post("/api/generate", &plate)).await;
+    assert_eq!((status, &headers["x-cache"]), (StatusCode::OK, &"HIT".parse().unwrap()));
+    assert_eq!(zoo.exports(), 1);
+
+    // The readiness check logs in to the mock too
+    let (status, _, body) = send(&app, get("/api/ready")).await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&body));
+}
+
+#[tokio::test]
+async fn test_job_generates_through_the_api() {
+    let zoo = MockZoo::start().unwrap();
+    let cache = Arc::new(MemoryCache::new());
+    let app = router_with(&zoo, cache.clone());
+    let plate = ActuatorPlate {
+        plate_thickness: domain::Length::from_mm(12),
+        ..Default::default()
+    };
+
+    let (status, _, body) = send(&app, post("/api/jobs", &plate)).await;
+    assert_eq!(status, StatusCode::ACCEPTED, "{}", String::from_utf8_lossy(&body));
+    let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let uri = format!("/api/jobs/{}", job["id"].as_str().unwrap());
+
+    let mut job = job;
+    for _ in 0..100 {
+        if job["status"] == "succeeded" || job["status"] == "failed" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        let (_, _, body) = send(&app, get(&uri)).await;
+        job = serde_json::from_slice(&body).unwrap();
+    }
+    assert_eq!(job["status"], "succeeded", "{}", job);
+
+    let (status, _, gltf) = send(&app, get(job["result"]["gltf_url"].as_str().unwrap())).await;
+    assert_eq!((status, gltf.as_slice()), (StatusCode::OK, MOCK_GLTF.as_bytes()));
+    wait_until_cached(&cache, &plate).await;
+    assert_eq!(zoo.exports(), 1);
+}
+
+#[tokio::test]
+async fn test_dropped_export_is_reported_and_not_cached() {
+    let zoo = MockZoo::start().unwrap();
+    zoo.close_early(1);
+    let cache = Arc::new(MemoryCache::new());
+    let app = router_with(&zoo, cache.clone());
+    let plate = ActuatorPlate::default();
+
+    let (status, _, body) = send(&app, post("/api/generate", &plate)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["errors"][0]["code"], "GENERATION_FAILED");
+    assert!(json["errors"][0]["message"].as_str().unwrap().contains("closed early"), "{}", json);
+    assert!(!cache.exists(&plate.cache_key()).await);
+
+    // The engine recovers, and so does the next request
+    let (status, _, _) = send(&app, post("/api/generate", &plate)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(zoo.exports(), 1);
+}
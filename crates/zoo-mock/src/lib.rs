@@ -0,0 +1,423 @@
+//! An in-process mock of the Zoo export API, for end-to-end tests that need the
+//! engine on the other end of a real socket.
+//!
+//! [`MockZoo`] serves a small slice of the API on a loopback port, from its own
+//! thread, so it keeps answering while the code under test blocks on it:
+//!
+//! - `GET /user` answers for a valid token, which is how a client checks its login.
+//! - `GET /ws/modeling/commands` is a WebSocket that takes one export request for a
+//!   KCL program and returns the STEP file, like the engine session `zoo kcl export`
+//!   holds open. It can drop the connection before answering, as the real engine
+//!   does now and then.
+//! - `POST /file/conversion/{src}/{output}` converts a STEP file to glTF or STL and
+//!   returns the output base64-encoded, like `zoo file convert`.
+//!
+//! The files are the mock engine's canned [`MOCK_STEP`], [`MOCK_GLTF`], and
+//! [`MOCK_STL`]. Every request needs `Authorization: Bearer <token>`.
+//!
+//! The server does its work through the `zoo` CLI, which can't be pointed at this
+//! mock in CI, so [`ZooApiBackend`] stands in for it: a [`GeometryBackend`] that
+//! makes the same three calls over HTTP and WebSocket. With it, `crates/web` tests
+//! cover a request all the way from submission through the network to the cache
+//! and back out as a download.
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use axum::body::Bytes;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path as UrlPath, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::Engine as _;
+use domain::ActuatorPlate;
+use parametric::{GeometryBackend, Stage};
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+
+pub use parametric::mock::{MOCK_GLTF, MOCK_STEP, MOCK_STL};
+
+/// Token [`MockZoo`] accepts.
+pub const MOCK_TOKEN: &str = "mock-zoo-token";
+
+/// Error [`ZooApiBackend`] reports when the export WebSocket drops before answering,
+/// worded like the CLI's.
+pub const CLOSED_EARLY: &str = "engine: websocket closed early";
+
+/// Counters and scripted failures shared with the server thread.
+#[derive(Debug, Default)]
+struct Shared {
+    exports: AtomicUsize,
+    conversions: AtomicUsize,
+    /// Exports left to drop before answering
+    close_early: AtomicU32,
+}
+
+/// The mock API, listening on a loopback port until dropped.
+#[derive(Debug)]
+pub struct MockZoo {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockZoo {
+    /// Start serving on a free loopback port.
+    pub fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        let shared = Arc::new(Shared::default());
+        let app = router(shared.clone());
+        let (shutdown, stopped) = oneshot::channel::<()>();
+        let thread = std::thread::spawn(move || {
+            runtime.block_on(async move {
+                let listener = match tokio::net::TcpListener::from_std(listener) {
+                    Ok(listener) => listener,
+                    Err(e) => return eprintln!("mock zoo could not listen: {}", e),
+                };
+                let stopped = async {
+                    stopped.await.ok();
+                };
+                if let Err(e) = axum::serve(listener, app).with_graceful_shutdown(stopped).await {
+                    eprintln!("mock zoo stopped: {}", e);
+                }
+            })
+        });
+        Ok(Self {
+            addr,
+            shared,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        })
+    }
+
+    /// Base URL of the API, e.g. `http://127.0.0.1:40123`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// A backend that generates through this mock with [`MOCK_TOKEN`].
+    pub fn backend(&self) -> ZooApiBackend {
+        ZooApiBackend::new(self.addr, MOCK_TOKEN)
+    }
+
+    /// Drop the next `n` export connections before answering.
+    pub fn close_early(&self, n: u32) {
+        self.shared.close_early.store(n, Ordering::SeqCst);
+    }
+
+    /// How many STEP files the mock has exported.
+    pub fn exports(&self) -> usize {
+        self.shared.exports.load(Ordering::SeqCst)
+    }
+
+    /// How many files the mock has converted.
+    pub fn conversions(&self) -> usize {
+        self.shared.conversions.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for MockZoo {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+fn router(shared: Arc<Shared>) -> Router {
+    Router::new()
+        .route("/user", get(user))
+        .route("/ws/modeling/commands", get(modeling_commands))
+        .route("/file/conversion/{src}/{output}", post(convert))
+        .with_state(shared)
+}
+
+/// An export request sent over the modeling WebSocket.
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportRequest {
+    /// The KCL program to run
+    program: String,
+    /// Format to export; only `step`
+    format: String,
+}
+
+/// A file in an API response, base64-encoded.
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportFile {
+    name: String,
+    contents: String,
+}
+
+/// The API's error body.
+fn api_error(status: StatusCode, error_code: &str, message: &str) -> Response {
+    let body = serde_json::json!({ "error_code": error_code, "message": message });
+    (status, Json(body)).into_response()
+}
+
+fn authorized(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        == Some(MOCK_TOKEN)
+}
+
+fn unauthorized() -> Response {
+    api_error(StatusCode::UNAUTHORIZED, "unauthorized", "Your API token is invalid")
+}
+
+async fn user(headers: HeaderMap) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    Json(serde_json::json!({ "id": "mock-user", "email": "mock@zoo.dev" })).into_response()
+}
+
+async fn modeling_commands(State(shared): State<Arc<Shared>>, headers: HeaderMap, upgrade: WebSocketUpgrade) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    upgrade.on_upgrade(move |socket| export(shared, socket))
+}
+
+/// Answer one export request on `socket`.
+async fn export(shared: Arc<Shared>, mut socket: WebSocket) {
+    let Some(Ok(Message::Text(text))) = socket.recv().await else {
+        return;
+    };
+    // Dropping the socket without a close frame is what the engine does
+    let dropped = shared
+        .close_early
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+        .is_ok();
+    if dropped {
+        return;
+    }
+    let reply = match serde_json::from_str::<ExportRequest>(&text) {
+        Ok(request) if request.format == "step" && !request.program.trim().is_empty() => {
+            shared.exports.fetch_add(1, Ordering::SeqCst);
+            let file = ExportFile {
+                name: "output.step".to_string(),
+                contents: base64::engine::general_purpose::STANDARD.encode(MOCK_STEP),
+            };
+            serde_json::json!({ "success": true, "resp": { "type": "export", "data": { "files": [file] } } })
+        }
+        Ok(_) => serde_json::json!({
+            "success": false,
+            "errors": [{ "error_code": "bad_request", "message": "Only non-empty programs export, and only to step" }],
+        }),
+        Err(e) => serde_json::json!({
+            "success": false,
+            "errors": [{ "error_code": "bad_request", "message": e.to_string() }],
+        }),
+    };
+    socket.send(Message::Text(reply.to_string().into())).await.ok();
+    socket.send(Message::Close(None)).await.ok();
+}
+
+async fn convert(
+    State(shared): State<Arc<Shared>>,
+    UrlPath((src, output)): UrlPath<(String, String)>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    let contents = match (src.as_str(), output.as_str()) {
+        ("step", "gltf") => MOCK_GLTF,
+        ("step", "stl") => MOCK_STL,
+        _ => return api_error(StatusCode::BAD_REQUEST, "bad_request", "Only step to gltf or stl is supported"),
+    };
+    if body.is_empty() {
+        return api_error(StatusCode::BAD_REQUEST, "bad_request", "The source file is empty");
+    }
+    shared.conversions.fetch_add(1, Ordering::SeqCst);
+    let name = format!("output.{}", output);
+    Json(serde_json::json!({
+        "status": "completed",
+        "src_format": src,
+        "output_format": output,
+        "outputs": { name: base64::engine::general_purpose::STANDARD.encode(contents) },
+    }))
+    .into_response()
+}
+
+/// Generates through the API with blocking calls, the way [`parametric::ZooCli`]
+/// does through the CLI: export STEP over the modeling WebSocket, then convert it to
+/// glTF and STL.
+#[derive(Clone, Debug)]
+pub struct ZooApiBackend {
+    addr: SocketAddr,
+    token: String,
+}
+
+impl ZooApiBackend {
+    pub fn new(addr: SocketAddr, token: &str) -> Self {
+        Self {
+            addr,
+            token: token.to_string(),
+        }
+    }
+
+    /// Send one request and read the whole response: its status and body. The
+    /// mock answers JSON with a `Content-Length` and closes the connection.
+    fn request(&self, method: &str, path: &str, body: &[u8]) -> Result<(u16, Vec<u8>), String> {
+        let failed = |e: std::io::Error| format!("engine: {} {} failed: {}", method, path, e);
+        let mut stream = TcpStream::connect(self.addr).map_err(failed)?;
+        let head = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nAuthorization: Bearer {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            method,
+            path,
+            self.addr,
+            self.token,
+            body.len()
+        );
+        stream.write_all(head.as_bytes()).map_err(failed)?;
+        stream.write_all(body).map_err(failed)?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).map_err(failed)?;
+
+        let split = response
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .ok_or_else(|| format!("engine: {} {} answered without headers", method, path))?;
+        let status = std::str::from_utf8(&response[..split])
+            .ok()
+            .and_then(|head| head.split(' ').nth(1))
+            .and_then(|code| code.parse().ok())
+            .ok_or_else(|| format!("engine: {} {} answered without a status", method, path))?;
+        Ok((status, response[split + 4..].to_vec()))
+    }
+
+    /// Export `program` to STEP over the modeling WebSocket.
+    fn export_step(&self, program: &str) -> Result<Vec<u8>, String> {
+        use tungstenite::client::IntoClientRequest;
+
+        let url = format!("ws://{}/ws/modeling/commands", self.addr);
+        let mut request = url.into_client_request().map_err(|e| format!("engine: {}", e))?;
+        let auth = format!("Bearer {}", self.token).parse().map_err(|_| "engine: invalid token".to_string())?;
+        request.headers_mut().insert(header::AUTHORIZATION, auth);
+        let (mut socket, _) = tungstenite::connect(request).map_err(|e| format!("engine: {}", e))?;
+
+        let export = ExportRequest {
+            program: program.to_string(),
+            format: "step".to_string(),
+        };
+        let text = serde_json::to_string(&export).map_err(|e| format!("engine: {}", e))?;
+        socket
+            .send(tungstenite::Message::text(text))
+            .map_err(|_| CLOSED_EARLY.to_string())?;
+        let reply = match socket.read() {
+            Ok(tungstenite::Message::Text(reply)) => reply,
+            _ => return Err(CLOSED_EARLY.to_string()),
+        };
+        socket.close(None).ok();
+
+        let reply: serde_json::Value = serde_json::from_str(&reply).map_err(|e| format!("engine: {}", e))?;
+        if reply["success"] != true {
+            return Err(format!("engine: export failed: {}", reply["errors"]));
+        }
+        let files: Vec<ExportFile> =
+            serde_json::from_value(reply["resp"]["data"]["files"].clone()).map_err(|e| format!("engine: {}", e))?;
+        let step = files.first().ok_or("engine: the export has no files")?;
+        base64::engine::general_purpose::STANDARD
+            .decode(&step.contents)
+            .map_err(|e| format!("engine: {}", e))
+    }
+
+    /// Convert a STEP file to `output` (`gltf` or `stl`).
+    fn convert(&self, step: &[u8], output: &str) -> Result<Vec<u8>, String> {
+        let path = format!("/file/conversion/step/{}", output);
+        let (status, body) = self.request("POST", &path, step)?;
+        if status != 200 {
+            return Err(format!("engine: converting to {} failed ({}): {}", output, status, String::from_utf8_lossy(&body)));
+        }
+        let reply: serde_json::Value = serde_json::from_slice(&body).map_err(|e| format!("engine: {}", e))?;
+        let contents = reply["outputs"][format!("output.{}", output)]
+            .as_str()
+            .ok_or_else(|| format!("engine: the {} conversion has no output", output))?;
+        base64::engine::general_purpose::STANDARD
+            .decode(contents)
+            .map_err(|e| format!("engine: {}", e))
+    }
+}
+
+impl GeometryBackend for ZooApiBackend {
+    fn name(&self) -> &str {
+        "zoo-api"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        match self.request("GET", "/user", &[])? {
+            (200, _) => Ok(()),
+            (status, body) => Err(format!("engine: login check failed ({}): {}", status, String::from_utf8_lossy(&body))),
+        }
+    }
+
+    fn generate(&self, plate: &ActuatorPlate, dir: &Path) -> Result<(), String> {
+        self.generate_with_progress(plate, dir, &|_| {})
+    }
+
+    fn generate_with_progress(&self, plate: &ActuatorPlate, dir: &Path, progress: &dyn Fn(Stage)) -> Result<(), String> {
+        let write = |file: &str, contents: &[u8]| {
+            std::fs::write(dir.join(file), contents).map_err(|e| format!("Failed to write {}: {}", file, e))
+        };
+        progress(Stage::GeneratingKcl);
+        let program = parametric::kcl::emit(plate);
+        progress(Stage::UploadingToZoo);
+        let step = self.export_step(&program)?;
+        write("output.step", &step)?;
+        progress(Stage::ExportingGltf);
+        write("source.gltf", &self.convert(&step, "gltf")?)?;
+        progress(Stage::ExportingStl);
+        write("source.stl", &self.convert(&step, "stl")?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_generates_the_canned_files() {
+        let zoo = MockZoo::start().unwrap();
+        let backend = zoo.backend();
+        backend.check().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        backend.generate(&ActuatorPlate::default(), dir.path()).unwrap();
+        for (file, expected) in [("output.step", MOCK_STEP), ("source.gltf", MOCK_GLTF), ("source.stl", MOCK_STL)] {
+            assert_eq!(std::fs::read_to_string(dir.path().join(file)).unwrap(), expected, "{}", file);
+        }
+        assert_eq!((zoo.exports(), zoo.conversions()), (1, 2));
+    }
+
+    #[test]
+    fn test_dropped_exports_and_bad_tokens_fail() {
+        let zoo = MockZoo::start().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        zoo.close_early(1);
+        assert_eq!(zoo.backend().generate(&ActuatorPlate::default(), dir.path()), Err(CLOSED_EARLY.to_string()));
+        assert!(zoo.backend().generate(&ActuatorPlate::default(), dir.path()).is_ok());
+
+        let stranger = ZooApiBackend::new(zoo.addr, "not-the-token");
+        assert!(stranger.check().unwrap_err().contains("401"));
+        assert!(stranger.generate(&ActuatorPlate::default(), dir.path()).is_err());
+        assert_eq!(zoo.exports(), 1);
+    }
+}
@@ -8,6 +8,7 @@ import {
 import { Input } from "./components/ui/input";
 import { Label } from "./components/ui/label";
 import { Button } from "./components/ui/button";
+import { ValidatedNumberInput, ValidatedSelect } from "./components/validated-field";
 import {
   DropdownMenu,
   DropdownMenuContent,
@@ -15,14 +16,8 @@ import {
   DropdownMenuSeparator,
   DropdownMenuTrigger,
 } from "./components/ui/dropdown-menu";
-import {
-  Select,
-  SelectContent,
-  SelectItem,
-  SelectTrigger,
-  SelectValue,
-} from "./components/ui/select";
 import { ThemePicker } from "./components/ui/theme-picker";
+import { Redo2, Undo2 } from "lucide-react";
 import { AboutButton } from "./components/about-section";
 
 import "./index.css";
@@ -30,10 +25,25 @@ import {
   useState,
   useEffect,
   useCallback,
+  useRef,
   type FormEvent,
   type ChangeEvent,
 } from "react";
 import { ModelViewer } from "./components/model-viewer";
+import {
+  BOLT_SIZES,
+  DEFAULT_PLATE,
+  MATERIALS,
+  plateSummary,
+  type Plate,
+  type RecentPlate,
+  type SavedConfig,
+  type Suggestion,
+} from "./lib/plate";
+import { useFormHistory } from "./lib/form-history";
+import { plateFromQuery, shareUrl } from "./lib/share";
+import { embedOptions, keepLocked, postGenerated } from "./lib/embed";
+import { t } from "./lib/i18n";
 import {
   validateBoltSpacing,
   validateBoltSize,
@@ -44,275 +54,182 @@ import {
   validatePinCount,
   validatePlateThickness,
   validateExpectedForce,
-  type ValidationResult,
 } from "./lib/validation";
 
-function Combined({
-  forProp,
-  name,
-  defaultValue = "10",
-  validator,
-  onValidationChange,
-  unit,
-  serverError,
-}: {
-  forProp: string;
-  name: string;
-  defaultValue?: string;
-  validator: (value: number) => Promise<ValidationResult>;
-  onValidationChange?: (fieldName: string, isValid: boolean) => void;
-  unit?: string;
-  serverError?: boolean;
-}) {
-  const [value, setValue] = useState(defaultValue);
-  const [validationResult, setValidationResult] = useState<ValidationResult>({
-    valid: true,
-  });
-  const [touched, setTouched] = useState(false);
-
-  useEffect(() => {
-    const timeoutId = setTimeout(async () => {
-      if (value && touched) {
-        const result = await validator(Number(value));
-        setValidationResult(result);
-        onValidationChange?.(forProp, result.valid);
-      }
-    }, 300);
-
-    return () => clearTimeout(timeoutId);
-  }, [value, validator, touched, forProp, onValidationChange]);
+const BOLT_SIZE_OPTIONS = BOLT_SIZES.map((size) => ({ value: size, label: size }));
 
-  const handleChange = (e: ChangeEvent<HTMLInputElement>) => {
-    setValue(e.target.value);
-    if (!touched) {
-      setTouched(true);
-    }
-  };
-
-  const handleBlur = async () => {
-    setTouched(true);
-    if (value) {
-      const result = await validator(Number(value));
-      setValidationResult(result);
-      onValidationChange?.(forProp, result.valid);
-    }
-  };
-
-  const isInvalid = (touched && !validationResult.valid) || !!serverError;
+const JOB_POLL_INTERVAL_MS = 1000;
+// Wait for typing to pause before re-quoting a new quantity
+const QUOTE_DEBOUNCE_MS = 300;
 
+function FieldGroup({
+  title,
+  children,
+}: {
+  title: string;
+  children: React.ReactNode;
+}) {
   return (
-    <div className="space-y-1.5">
-      <Label
-        htmlFor={forProp}
-        className="text-xs font-medium uppercase tracking-wider text-muted-foreground"
-      >
-        {name}
-        {unit && <span className="ml-1 text-[10px] opacity-60">({unit})</span>}
-      </Label>
-      <Input
-        id={forProp}
-        type="number"
-        name={forProp}
-        value={value}
-        onChange={handleChange}
-        onBlur={handleBlur}
-        placeholder={defaultValue}
-        className={
-          isInvalid ? "border-destructive focus-visible:ring-destructive" : ""
-        }
-      />
-      {touched && !validationResult.valid && (
-        <p className="text-[10px] text-destructive font-medium">
-          {validationResult.error}
-        </p>
-      )}
+    <div className="space-y-3">
+      <h3 className="text-[10px] font-bold uppercase tracking-[0.2em] text-primary/80 border-b border-primary/20 pb-1">
+        {title}
+      </h3>
+      <div className="grid grid-cols-2 gap-3">{children}</div>
     </div>
   );
 }
 
-const BOLT_SIZES = ["M3", "M4", "M5", "M6", "M8", "M10", "M12"] as const;
-
-const MATERIALS = [
-  { value: "aluminum", label: "Aluminum 6061-T6" },
-  { value: "stainless_steel", label: "Stainless Steel 304" },
-  { value: "carbon_steel", label: "Carbon Steel" },
-  { value: "brass", label: "Brass" },
-] as const;
-
-function BoltSizeSelect({
-  forProp,
-  name,
-  defaultValue = "M10",
-  onValidationChange,
-  serverError,
-}: {
-  forProp: string;
+type ErrorDetail = { code: string; message: string; fields: string[] };
+
+type Preset = {
+  id: string;
   name: string;
-  defaultValue?: string;
-  onValidationChange?: (fieldName: string, isValid: boolean) => void;
-  serverError?: boolean;
-}) {
-  const [value, setValue] = useState(defaultValue);
-  const [validationResult, setValidationResult] = useState<ValidationResult>({
-    valid: true,
-  });
-  const [touched, setTouched] = useState(false);
+  description: string;
+  plate: Plate;
+};
 
-  useEffect(() => {
-    const validateValue = async () => {
-      if (value && touched) {
-        const result = await validateBoltSize(value);
-        setValidationResult(result);
-        onValidationChange?.(forProp, result.valid);
-      }
-    };
-    validateValue();
-  }, [value, touched, forProp, onValidationChange]);
+// Bolt shear and plate bearing under an applied load, from POST /api/analysis
+type StressCheck = { stress_mpa: number; yield_mpa: number; safety_factor: number };
 
-  const handleChange = (newValue: string) => {
-    setValue(newValue);
-    if (!touched) {
-      setTouched(true);
-    }
+type BoltAnalysis = {
+  bolt_shear: StressCheck;
+  plate_bearing: StressCheck;
+  warnings: ErrorDetail[];
+};
+
+type Quote = {
+  estimate: {
+    unit_price_cents: number;
+    total_price_cents: number;
+    discount_percent: number;
+    mass_grams: number;
+    currency: string;
   };
+  lead_time: { ship_date: string };
+};
 
-  const isInvalid = (touched && !validationResult.valid) || !!serverError;
+// Volume, mass, and center of mass of the generated plate; positions in micrometers
+type MassProperties = {
+  volume_mm3: number;
+  mass_grams: number;
+  center_of_mass: { x_um: number; y_um: number; z_um: number };
+};
 
-  return (
-    <div className="space-y-1.5">
-      <Label
-        htmlFor={forProp}
-        className="text-xs font-medium uppercase tracking-wider text-muted-foreground"
-      >
-        {name}
-      </Label>
-      <Select name={forProp} value={value} onValueChange={handleChange}>
-        <SelectTrigger
-          id={forProp}
-          className={
-            isInvalid ? "border-destructive focus-visible:ring-destructive" : ""
-          }
-        >
-          <SelectValue placeholder="Select bolt size" />
-        </SelectTrigger>
-        <SelectContent>
-          {BOLT_SIZES.map((size) => (
-            <SelectItem key={size} value={size}>
-              {size}
-            </SelectItem>
-          ))}
-        </SelectContent>
-      </Select>
-      {touched && !validationResult.valid && (
-        <p className="text-[10px] text-destructive font-medium">
-          {validationResult.error}
-        </p>
-      )}
-    </div>
-  );
-}
+const formatMass = (grams: number) =>
+  grams < 1000 ? `${grams} g` : `${(grams / 1000).toFixed(2)} kg`;
 
-function MaterialSelect({
-  forProp,
-  name,
-  defaultValue = "aluminum",
-  onValidationChange,
-  serverError,
-}: {
-  forProp: string;
-  name: string;
-  defaultValue?: string;
-  onValidationChange?: (fieldName: string, isValid: boolean) => void;
-  serverError?: boolean;
-}) {
-  const [value, setValue] = useState(defaultValue);
-  const [validationResult, setValidationResult] = useState<ValidationResult>({
-    valid: true,
-  });
-  const [touched, setTouched] = useState(false);
+const formatPrice = (cents: number, currency: string) =>
+  (cents / 100).toLocaleString(undefined, { style: "currency", currency });
 
-  useEffect(() => {
-    const validateValue = async () => {
-      if (value && touched) {
-        const result = await validateMaterial(value);
-        setValidationResult(result);
-        onValidationChange?.(forProp, result.valid);
-      }
-    };
-    validateValue();
-  }, [value, touched, forProp, onValidationChange]);
+type Job = {
+  id: string;
+  status: "queued" | "running" | "succeeded" | "failed";
+  progress: number;
+  stage: string;
+  result: Record<string, unknown> | null;
+  errors: ErrorDetail[];
+};
 
-  const handleChange = (newValue: string) => {
-    setValue(newValue);
-    if (!touched) {
-      setTouched(true);
-    }
-  };
+// Server-side feature flags from /api/flags, by name
+type Flags = Record<string, boolean>;
 
-  const isInvalid = (touched && !validationResult.valid) || !!serverError;
+const isFinished = (job: Job) => job.status === "succeeded" || job.status === "failed";
 
-  return (
-    <div className="space-y-1.5">
-      <Label
-        htmlFor={forProp}
-        className="text-xs font-medium uppercase tracking-wider text-muted-foreground"
-      >
-        {name}
-      </Label>
-      <Select name={forProp} value={value} onValueChange={handleChange}>
-        <SelectTrigger
-          id={forProp}
-          className={
-            isInvalid ? "border-destructive focus-visible:ring-destructive" : ""
-          }
-        >
-          <SelectValue placeholder="Select material" />
-        </SelectTrigger>
-        <SelectContent>
-          {MATERIALS.map((mat) => (
-            <SelectItem key={mat.value} value={mat.value}>
-              {mat.label}
-            </SelectItem>
-          ))}
-        </SelectContent>
-      </Select>
-      {touched && !validationResult.valid && (
-        <p className="text-[10px] text-destructive font-medium">
-          {validationResult.error}
-        </p>
-      )}
-    </div>
-  );
-}
+// Follow a job to completion over its progress WebSocket, falling back to polling
+// when the socket can't be opened or drops early.
+async function waitForJob(job: Job, onUpdate: (job: Job) => void): Promise<Job> {
+  const streamed = await new Promise<Job>((resolve) => {
+    const scheme = window.location.protocol === "https:" ? "wss" : "ws";
+    const socket = new WebSocket(`${scheme}://${window.location.host}/ws/generation/${job.id}`);
+    let latest = job;
+    socket.onmessage = (event) => {
+      latest = JSON.parse(event.data);
+      onUpdate(latest);
+    };
+    socket.onerror = () => socket.close();
+    socket.onclose = () => resolve(latest);
+  });
 
-function FieldGroup({
-  title,
-  children,
-}: {
-  title: string;
-  children: React.ReactNode;
-}) {
-  return (
-    <div className="space-y-3">
-      <h3 className="text-[10px] font-bold uppercase tracking-[0.2em] text-primary/80 border-b border-primary/20 pb-1">
-        {title}
-      </h3>
-      <div className="grid grid-cols-2 gap-3">{children}</div>
-    </div>
-  );
+  let current = streamed;
+  while (!isFinished(current)) {
+    await new Promise((resolve) => setTimeout(resolve, JOB_POLL_INTERVAL_MS));
+    const poll = await fetch(`/api/jobs/${current.id}`);
+    current = await poll.json();
+    onUpdate(current);
+  }
+  return current;
 }
 
-type ErrorDetail = { message: string; fields: string[] };
+function readPlate(form: HTMLFormElement): Plate {
+  const formData = new FormData(form);
+  return {
+    bolt_spacing: Number(formData.get("boltSpacing")),
+    bolt_size: String(formData.get("boltSize")),
+    bracket_height: Number(formData.get("bracketHeight")),
+    bracket_width: Number(formData.get("bracketWidth")),
+    material: String(formData.get("material")),
+    pin_diameter: Number(formData.get("pinDiameter")),
+    pin_count: Number(formData.get("pinCount")),
+    plate_thickness: Number(formData.get("plateThickness")),
+    expected_force_per_pin: Number(formData.get("expectedForce")),
+  };
+}
 
-export function App() {
+// `embed` drops the header and footer for an iframe on another site; see lib/embed.ts
+export function App({ embed = false }: { embed?: boolean }) {
   const [downloadUrl, setDownloadUrl] = useState<string | null>(null);
   const [stlUrl, setStlUrl] = useState<string | null>(null);
+  const [gltfUrl, setGltfUrl] = useState<string | null>(null);
+  const [pdfUrl, setPdfUrl] = useState<string | null>(null);
+  const [svgUrl, setSvgUrl] = useState<string | null>(null);
+  const [dxfUrl, setDxfUrl] = useState<string | null>(null);
+  const [objUrl, setObjUrl] = useState<string | null>(null);
+  const [threeMfUrl, setThreeMfUrl] = useState<string | null>(null);
+  const [kclUrl, setKclUrl] = useState<string | null>(null);
+  const [massProperties, setMassProperties] = useState<MassProperties | null>(null);
   const [validationErrors, setValidationErrors] = useState<ErrorDetail[]>([]);
+  const [generationWarnings, setGenerationWarnings] = useState<ErrorDetail[]>([]);
   const [minimumThicknessMm, setMinimumThicknessMm] = useState<number | null>(null);
   const [networkError, setNetworkError] = useState<string | null>(null);
   const [isLoading, setIsLoading] = useState(false);
+  const [jobProgress, setJobProgress] = useState<{ progress: number; stage: string } | null>(null);
   const [modelSrc, setModelSrc] = useState<string | null>(null);
+  const [generatedPlate, setGeneratedPlate] = useState<Plate | null>(null);
+  const [quantity, setQuantity] = useState(1);
+  const [quote, setQuote] = useState<Quote | null>(null);
+  const [customerName, setCustomerName] = useState("");
+  const [customerEmail, setCustomerEmail] = useState("");
+  const [isOrdering, setIsOrdering] = useState(false);
+  const [placedOrder, setPlacedOrder] = useState<string | null>(null);
+  const [cacheKey, setCacheKey] = useState<string | null>(null);
+  const [stepEmail, setStepEmail] = useState("");
+  const [isEmailing, setIsEmailing] = useState(false);
+  const [emailedTo, setEmailedTo] = useState<string | null>(null);
   const [isPanelExpanded, setIsPanelExpanded] = useState(true);
+  const [savedConfigs, setSavedConfigs] = useState<SavedConfig[]>([]);
+  const [recentPlates, setRecentPlates] = useState<RecentPlate[]>([]);
+  const [presets, setPresets] = useState<Preset[]>([]);
+  const [flags, setFlags] = useState<Flags>({});
+  // The fields are uncontrolled, so loading a config remounts the form with new defaults
+  // A shared link (`/?bs=60&bd=10&...`) opens with its plate in the form
+  const [initialPlate] = useState<Plate>(
+    () => plateFromQuery(window.location.search) ?? DEFAULT_PLATE,
+  );
+  const [{ locked, targetOrigin }] = useState(() =>
+    embed ? embedOptions(window.location.search) : { locked: new Set<string>(), targetOrigin: null },
+  );
+  const [formPlate, setFormPlate] = useState<Plate>(initialPlate);
+  const [formKey, setFormKey] = useState(0);
+  const formRef = useRef<HTMLFormElement>(null);
+  const formHistory = useFormHistory<Plate>(initialPlate);
+  // Edited since the last commit, so there is something to undo even with no history
+  const [formEdited, setFormEdited] = useState(false);
+  // Optional total load to check the bolts against; not part of the plate
+  const [loadN, setLoadN] = useState("");
+  const [analysis, setAnalysis] = useState<BoltAnalysis | null>(null);
+  const [suggestions, setSuggestions] = useState<Suggestion[]>([]);
+  // Read out by screen readers when a submit finishes
+  const [announcement, setAnnouncement] = useState("");
   const [fieldValidationState, setFieldValidationState] = useState<
     Record<string, boolean>
   >({
@@ -341,92 +258,409 @@ export function App() {
     (isValid) => isValid,
   );
 
-  const serverErrorFields = new Set(validationErrors.flatMap((e) => e.fields));
+  // The server's messages for one field, shown under it
+  const serverErrorsFor = (field: string) =>
+    validationErrors.filter((e) => e.fields.includes(field)).map((e) => e.message);
+
+  // Plates generated in this browser, remembered by the server's session cookie
+  const loadRecentPlates = useCallback(() => {
+    fetch("/api/session/recent")
+      .then((res) => (res.ok ? res.json() : []))
+      .then(setRecentPlates)
+      .catch(() => setRecentPlates([]));
+  }, []);
+
+  useEffect(() => {
+    loadRecentPlates();
+    fetch("/api/configs")
+      .then((res) => (res.ok ? res.json() : []))
+      .then(setSavedConfigs)
+      .catch(() => setSavedConfigs([]));
+    fetch("/api/presets")
+      .then((res) => (res.ok ? res.json() : []))
+      .then(setPresets)
+      .catch(() => setPresets([]));
+    fetch("/api/flags")
+      .then((res) => (res.ok ? res.json() : { flags: {} }))
+      .then((data) => setFlags(data.flags))
+      .catch(() => setFlags({}));
+  }, [loadRecentPlates]);
+
+  // Re-price the generated plate whenever the quantity changes
+  useEffect(() => {
+    if (!generatedPlate || quantity < 1) {
+      setQuote(null);
+      return;
+    }
+    let cancelled = false;
+    const timer = setTimeout(() => {
+      fetch("/api/quote", {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ plate: generatedPlate, quantity }),
+      })
+        .then((res) => (res.ok ? res.json() : null))
+        .then((data) => !cancelled && setQuote(data))
+        .catch(() => !cancelled && setQuote(null));
+    }, QUOTE_DEBOUNCE_MS);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [generatedPlate, quantity]);
+
+  // Copy a link to the form's current plate and show it in the address bar
+  const handleShare = async () => {
+    if (!formRef.current) return;
+    const url = shareUrl(readPlate(formRef.current));
+    window.history.replaceState(null, "", url);
+    try {
+      await navigator.clipboard.writeText(url);
+    } catch {
+      window.prompt("Copy this link:", url);
+    }
+  };
+
+  const handleSaveConfig = async () => {
+    if (!formRef.current) return;
+    const name = window.prompt("Save configuration as:");
+    if (!name?.trim()) return;
+
+    try {
+      const res = await fetch("/api/configs", {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ name, plate: readPlate(formRef.current) }),
+      });
+      const data = await res.json();
+      if (!res.ok) {
+        setNetworkError(data.errors?.[0] ?? "Could not save the configuration");
+        return;
+      }
+      setSavedConfigs((prev) => [data, ...prev]);
+    } catch (error) {
+      setNetworkError(String(error));
+    }
+  };
+
+  const handlePlaceOrder = async () => {
+    if (!generatedPlate) return;
+    setIsOrdering(true);
+    setNetworkError(null);
+
+    try {
+      const res = await fetch("/api/orders", {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({
+          plate: generatedPlate,
+          quantity,
+          customer_name: customerName,
+          customer_email: customerEmail,
+        }),
+      });
+      const data = await res.json();
+      if (!res.ok) {
+        setNetworkError(data.errors?.join(" ") ?? "Could not place the order");
+        return;
+      }
+      setPlacedOrder(data.order_number);
+    } catch (error) {
+      setNetworkError(String(error));
+    } finally {
+      setIsOrdering(false);
+    }
+  };
+
+  const handleEmailStep = async () => {
+    if (!cacheKey) return;
+    setIsEmailing(true);
+    setNetworkError(null);
+
+    try {
+      const res = await fetch(`/api/models/${cacheKey}/email`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ email: stepEmail }),
+      });
+      const data = await res.json();
+      if (!res.ok) {
+        setNetworkError(data.errors?.join(" ") ?? "Could not send the email");
+        return;
+      }
+      setEmailedTo(stepEmail);
+    } catch (error) {
+      setNetworkError(String(error));
+    } finally {
+      setIsEmailing(false);
+    }
+  };
+
+  // Record the form's values as one undo step, e.g. when a field loses focus
+  const commitForm = () => {
+    if (formRef.current) formHistory.commit(readPlate(formRef.current));
+    setFormEdited(false);
+    analyzeLoad(loadN);
+    loadSuggestions();
+  };
+
+  // Recommended values for the form's current plate; any field can show one
+  const loadSuggestions = async () => {
+    if (!formRef.current) return;
+    try {
+      const res = await fetch("/api/suggestions", {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(readPlate(formRef.current)),
+      });
+      setSuggestions(res.ok ? await res.json() : []);
+    } catch {
+      setSuggestions([]);
+    }
+  };
+
+  useEffect(() => {
+    loadSuggestions();
+  }, [formKey]);
+
+  const suggestionFor = (field: string) => suggestions.find((s) => s.field === field);
+
+  const applySuggestion = (suggestion: Suggestion) => {
+    if (!formRef.current) return;
+    loadPlate({ ...readPlate(formRef.current), [suggestion.key]: suggestion.value });
+  };
+
+  // Check the bolts against the entered load; clears the result when there is none
+  const analyzeLoad = async (load: string) => {
+    const loadValue = Math.floor(Number(load));
+    if (!formRef.current || !(loadValue > 0)) {
+      setAnalysis(null);
+      return;
+    }
+    try {
+      const res = await fetch("/api/analysis", {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ plate: readPlate(formRef.current), load_n: loadValue }),
+      });
+      setAnalysis(res.ok ? await res.json() : null);
+    } catch {
+      setAnalysis(null);
+    }
+  };
+
+  // Re-check as the load is typed, and when the form is remounted with another plate
+  useEffect(() => {
+    const timer = setTimeout(() => analyzeLoad(loadN), 300);
+    return () => clearTimeout(timer);
+  }, [loadN, formKey]);
+
+  const handleUndo = () => {
+    if (!formRef.current) return;
+    setFormEdited(false);
+    const plate = formHistory.undo(readPlate(formRef.current));
+    if (plate) showPlate(plate);
+  };
+
+  const handleRedo = () => {
+    if (!formRef.current) return;
+    setFormEdited(false);
+    const plate = formHistory.redo(readPlate(formRef.current));
+    if (plate) showPlate(plate);
+  };
+
+  // Ctrl/Cmd+Z to undo, Ctrl/Cmd+Shift+Z or Ctrl+Y to redo. Inside a text field the
+  // browser's own undo applies until the field is left.
+  useEffect(() => {
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (!(e.ctrlKey || e.metaKey) || e.altKey) return;
+      const key = e.key.toLowerCase();
+      if (key !== "z" && key !== "y") return;
+      if (e.target instanceof HTMLInputElement || e.target instanceof HTMLTextAreaElement) return;
+      e.preventDefault();
+      if (key === "y" || e.shiftKey) handleRedo();
+      else handleUndo();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  });
+
+  const loadPlate = (plate: Plate) => {
+    commitForm();
+    formHistory.commit(plate);
+    showPlate(plate);
+  };
+
+  // Remount the form with `plate` as its values, clearing stale errors. Fields the
+  // embedding page locked keep the values it gave.
+  const showPlate = (plate: Plate) => {
+    setFormPlate(keepLocked(plate, initialPlate, locked));
+    setFormKey((key) => key + 1);
+    setFieldValidationState((prev) =>
+      Object.fromEntries(Object.keys(prev).map((field) => [field, true])),
+    );
+    setValidationErrors([]);
+    setGenerationWarnings([]);
+    setMinimumThicknessMm(null);
+    setNetworkError(null);
+  };
+
+  // Move focus to the first field the server rejected, once the errors are shown
+  const focusFirstInvalid = (errors: ErrorDetail[]) => {
+    setTimeout(() => {
+      const field = errors.flatMap((e) => e.fields).find((f) => document.getElementById(f));
+      if (field) document.getElementById(field)!.focus();
+    });
+  };
 
   const handleSubmit = async (e: FormEvent<HTMLFormElement>) => {
     e.preventDefault();
 
     setIsLoading(true);
+    setAnnouncement("Generating model");
     setDownloadUrl(null);
     setStlUrl(null);
+    setGltfUrl(null);
+    setPdfUrl(null);
+    setSvgUrl(null);
+    setDxfUrl(null);
+    setObjUrl(null);
+    setThreeMfUrl(null);
+    setKclUrl(null);
+    setMassProperties(null);
+    setGeneratedPlate(null);
+    setPlacedOrder(null);
+    setCacheKey(null);
+    setEmailedTo(null);
     setValidationErrors([]);
+    setGenerationWarnings([]);
     setMinimumThicknessMm(null);
     setNetworkError(null);
 
     try {
-      const form = e.currentTarget;
-      const formData = new FormData(form);
-
-      const body = JSON.stringify({
-        bolt_spacing: Number(formData.get("boltSpacing")),
-        bolt_size: String(formData.get("boltSize")),
-        bracket_height: Number(formData.get("bracketHeight")),
-        bracket_width: Number(formData.get("bracketWidth")),
-        material: String(formData.get("material")),
-        pin_diameter: Number(formData.get("pinDiameter")),
-        pin_count: Number(formData.get("pinCount")),
-        plate_thickness: Number(formData.get("plateThickness")),
-        expected_force_per_pin: Number(formData.get("expectedForce")),
-      });
+      const plate = readPlate(e.currentTarget);
+      formHistory.commit(plate);
+      const body = JSON.stringify(plate);
 
-      const res = await fetch("/api/generate", {
+      // Without background jobs, generate in the request itself
+      const useJobs = flags.async_jobs !== false;
+      const res = await fetch(useJobs ? "/api/jobs" : "/api/generate", {
         method: "POST",
         headers: { "Content-Type": "application/json" },
         body,
       });
 
-      const data = await res.json();
+      let job = await res.json();
+
+      // Generation runs in the background; follow it until the job finishes
+      if (useJobs && res.status === 202 && !isFinished(job)) {
+        setJobProgress({ progress: job.progress, stage: job.stage });
+        job = await waitForJob(job, (update) =>
+          setJobProgress({ progress: update.progress, stage: update.stage }),
+        );
+      }
+
+      const data = useJobs && job.status === "succeeded" ? job.result : job;
 
       if (data.success && data.download_url) {
         setDownloadUrl(data.download_url);
         setStlUrl(data.stl_url ?? null);
+        setGltfUrl(data.gltf_url ?? null);
+        setPdfUrl(data.pdf_url ?? null);
+        setSvgUrl(data.svg_url ?? null);
+        setDxfUrl(data.dxf_url ?? null);
+        setObjUrl(data.obj_url ?? null);
+        setThreeMfUrl(data.three_mf_url ?? null);
+        setKclUrl(data.kcl_url ?? null);
+        setCacheKey(data.cache_key ?? null);
+        setMassProperties(data.mass_properties ?? null);
+        setGenerationWarnings(data.warnings ?? []);
         setModelSrc(`${data.gltf_url}?t=${Date.now()}`);
+        setGeneratedPlate(plate);
+        loadRecentPlates();
+        if (embed) {
+          postGenerated(
+            plate,
+            {
+              step: data.download_url,
+              stl: data.stl_url,
+              gltf: data.gltf_url,
+              pdf: data.pdf_url,
+              svg: data.svg_url,
+              dxf: data.dxf_url,
+              obj: data.obj_url,
+              three_mf: data.three_mf_url,
+              kcl: data.kcl_url,
+            },
+            targetOrigin,
+          );
+        }
+        setAnnouncement("Model generated; downloads are ready");
       } else if (data.errors && data.errors.length > 0) {
         setValidationErrors(data.errors);
         setMinimumThicknessMm(data.minimum_thickness_mm ?? null);
+        setAnnouncement(
+          `Model not generated: ${data.errors.map((err: ErrorDetail) => err.message).join(" ")}`,
+        );
+        focusFirstInvalid(data.errors);
       } else {
         setNetworkError("An unknown error occurred");
+        setAnnouncement("Model not generated: an unknown error occurred");
       }
     } catch (error) {
       setNetworkError(String(error));
+      setAnnouncement(`Model not generated: ${error}`);
     } finally {
       setIsLoading(false);
+      setJobProgress(null);
     }
   };
 
   return (
     <div className="h-screen w-full flex flex-col relative overflow-hidden">
       {/* Top navigation bar */}
-      <header className="fixed top-0 left-0 right-0 z-50 px-4 py-3 lg:px-6 lg:py-4">
-        <div className="flex items-center justify-between max-w-7xl mx-auto">
-          <div className="flex items-center gap-3">
-            <div className="w-10 h-10 rounded-xl bg-primary/10 border border-primary/20 flex items-center justify-center backdrop-blur-sm">
-              <svg
-                className="w-6 h-6 text-primary"
-                viewBox="0 0 24 24"
-                fill="none"
-                stroke="currentColor"
-                strokeWidth="1.5"
-              >
-                <path d="M12 3L3 8v8l9 5 9-5V8l-9-5z" />
-                <path d="M12 12l9-5M12 12v9M12 12L3 8" />
-              </svg>
+      {!embed && (
+        <header className="fixed top-0 left-0 right-0 z-50 px-4 py-3 lg:px-6 lg:py-4">
+          <div className="flex items-center justify-between max-w-7xl mx-auto">
+            <div className="flex items-center gap-3">
+              <div className="w-10 h-10 rounded-xl bg-primary/10 border border-primary/20 flex items-center justify-center backdrop-blur-sm">
+                <svg
+                  className="w-6 h-6 text-primary"
+                  viewBox="0 0 24 24"
+                  fill="none"
+                  stroke="currentColor"
+                  strokeWidth="1.5"
+                >
+                  <path d="M12 3L3 8v8l9 5 9-5V8l-9-5z" />
+                  <path d="M12 12l9-5M12 12v9M12 12L3 8" />
+                </svg>
+              </div>
+              <div>
+                <h1 className="text-lg lg:text-xl font-bold tracking-tight">
+                  Platerator
+                </h1>
+                <p className="text-[10px] lg:text-xs text-muted-foreground uppercase tracking-wider">
+                  Actuator Mounting Plate Configurator
+                </p>
+              </div>
             </div>
-            <div>
-              <h1 className="text-lg lg:text-xl font-bold tracking-tight">
-                Platerator
-              </h1>
-              <p className="text-[10px] lg:text-xs text-muted-foreground uppercase tracking-wider">
-                Actuator Mounting Plate Configurator
-              </p>
+            <div className="flex items-center gap-4">
+              <a
+                href="/compare"
+                className="text-[10px] uppercase tracking-wider text-muted-foreground hover:text-primary transition-colors"
+              >
+                Compare
+              </a>
+              <ThemePicker />
             </div>
           </div>
-          <ThemePicker />
-        </div>
-      </header>
+        </header>
+      )}
 
       {/* Main content area */}
-      <main className="flex-1 flex flex-col lg:flex-row pt-20 lg:pt-24 overflow-hidden">
+      <main
+        className={`flex-1 flex flex-col lg:flex-row overflow-hidden ${embed ? "" : "pt-20 lg:pt-24"}`}
+      >
         {/* 3D Viewer - Hero section */}
         <div className="flex-1 relative min-h-[40vh] lg:min-h-0">
           <div className="absolute inset-4 lg:inset-8 rounded-2xl overflow-hidden border border-border/50 backdrop-blur-sm">
@@ -457,7 +691,10 @@ export function App() {
 
           {/* Mobile panel toggle */}
           <button
+            type="button"
             onClick={() => setIsPanelExpanded(!isPanelExpanded)}
+            aria-expanded={isPanelExpanded}
+            aria-controls="configuration-panel"
             className="lg:hidden absolute bottom-0 left-1/2 -translate-x-1/2 translate-y-1/2 z-20 px-6 py-2 rounded-full bg-primary text-primary-foreground text-sm font-medium shadow-lg flex items-center gap-2"
           >
             <span>{isPanelExpanded ? "Hide" : "Configure"}</span>
@@ -473,12 +710,13 @@ export function App() {
           </button>
         </div>
 
-        {/* Configuration panel */}
+        {/* Configuration panel; hidden from the tab order while collapsed on small screens */}
         <div
+          id="configuration-panel"
           className={`
             lg:w-[420px] xl:w-[480px] shrink-0
             transition-all duration-300 ease-out
-            ${isPanelExpanded ? "max-h-[70vh] lg:max-h-none" : "max-h-0 lg:max-h-none"}
+            ${isPanelExpanded ? "max-h-[70vh] lg:max-h-none" : "max-h-0 lg:max-h-none invisible lg:visible"}
             overflow-hidden lg:h-full flex flex-col
           `}
         >
@@ -502,108 +740,273 @@ export function App() {
                     </svg>
                     Configuration
                   </CardTitle>
-                  <AboutButton />
+                  <div className="flex items-center gap-1">
+                    {presets.length > 0 && (
+                      <DropdownMenu>
+                        <DropdownMenuTrigger asChild>
+                          <Button variant="ghost" size="sm" className="text-xs">
+                            Presets
+                          </Button>
+                        </DropdownMenuTrigger>
+                        <DropdownMenuContent align="end">
+                          {presets.map((preset) => (
+                            <DropdownMenuItem
+                              key={preset.id}
+                              onSelect={() => loadPlate(preset.plate)}
+                              className="flex flex-col items-start gap-0"
+                            >
+                              <span>{preset.name}</span>
+                              <span className="text-[10px] text-muted-foreground">
+                                {preset.description}
+                              </span>
+                            </DropdownMenuItem>
+                          ))}
+                        </DropdownMenuContent>
+                      </DropdownMenu>
+                    )}
+                    {recentPlates.length > 0 && (
+                      <DropdownMenu>
+                        <DropdownMenuTrigger asChild>
+                          <Button variant="ghost" size="sm" className="text-xs">
+                            Recent
+                          </Button>
+                        </DropdownMenuTrigger>
+                        <DropdownMenuContent align="end">
+                          {recentPlates.map((recent) => (
+                            <DropdownMenuItem
+                              key={recent.cache_key}
+                              onSelect={() => loadPlate(recent.plate)}
+                              className="flex flex-col items-start gap-0"
+                            >
+                              <span>{plateSummary(recent.plate)}</span>
+                              <span className="text-[10px] text-muted-foreground">
+                                {new Date(recent.submitted_at).toLocaleString()}
+                              </span>
+                            </DropdownMenuItem>
+                          ))}
+                        </DropdownMenuContent>
+                      </DropdownMenu>
+                    )}
+                    <DropdownMenu>
+                      <DropdownMenuTrigger asChild>
+                        <Button variant="ghost" size="sm" className="text-xs">
+                          Saved
+                        </Button>
+                      </DropdownMenuTrigger>
+                      <DropdownMenuContent align="end">
+                        <DropdownMenuItem onSelect={handleSaveConfig}>
+                          Save current…
+                        </DropdownMenuItem>
+                        <DropdownMenuItem onSelect={handleShare}>
+                          Copy share link
+                        </DropdownMenuItem>
+                        {savedConfigs.length > 0 && <DropdownMenuSeparator />}
+                        {savedConfigs.map((config) => (
+                          <DropdownMenuItem
+                            key={config.id}
+                            onSelect={() => loadPlate(config.plate)}
+                          >
+                            {config.name}
+                          </DropdownMenuItem>
+                        ))}
+                      </DropdownMenuContent>
+                    </DropdownMenu>
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      className="text-xs"
+                      onClick={handleUndo}
+                      disabled={!formHistory.canUndo && !formEdited}
+                      aria-label="Undo"
+                      title="Undo (Ctrl+Z)"
+                    >
+                      <Undo2 className="w-4 h-4" />
+                    </Button>
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      className="text-xs"
+                      onClick={handleRedo}
+                      disabled={!formHistory.canRedo}
+                      aria-label="Redo"
+                      title="Redo (Ctrl+Shift+Z)"
+                    >
+                      <Redo2 className="w-4 h-4" />
+                    </Button>
+                    <AboutButton />
+                  </div>
                 </div>
               </CardHeader>
-              <form onSubmit={handleSubmit} className="flex flex-col flex-1 overflow-hidden">
+              <form
+                key={formKey}
+                ref={formRef}
+                onSubmit={handleSubmit}
+                onBlur={commitForm}
+                onChange={() => setFormEdited(true)}
+                className="flex flex-col flex-1 overflow-hidden"
+              >
               <CardContent className="flex-1 overflow-y-auto">
                 <div className="space-y-6">
-                  <FieldGroup title="Dimensions">
-                    <Combined
+                  <FieldGroup title={t("dimensions")}>
+                    <ValidatedNumberInput
                       forProp="bracketHeight"
-                      name="Height"
-                      defaultValue="400"
+                      name={t("height")}
+                      defaultValue={String(formPlate.bracket_height)}
                       validator={validateBracketHeight}
                       onValidationChange={handleValidationChange}
                       unit="mm"
-                      serverError={serverErrorFields.has("bracketHeight")}
+                      serverErrors={serverErrorsFor("bracketHeight")}
+                      locked={locked.has("bracketHeight")}
+                      suggestion={suggestionFor("bracketHeight")}
+                      onApplySuggestion={applySuggestion}
                     />
-                    <Combined
+                    <ValidatedNumberInput
                       forProp="bracketWidth"
-                      name="Width"
-                      defaultValue="300"
+                      name={t("width")}
+                      defaultValue={String(formPlate.bracket_width)}
                       validator={validateBracketWidth}
                       onValidationChange={handleValidationChange}
                       unit="mm"
-                      serverError={serverErrorFields.has("bracketWidth")}
+                      serverErrors={serverErrorsFor("bracketWidth")}
+                      locked={locked.has("bracketWidth")}
+                      suggestion={suggestionFor("bracketWidth")}
+                      onApplySuggestion={applySuggestion}
                     />
-                    <Combined
+                    <ValidatedNumberInput
                       forProp="plateThickness"
-                      name="Thickness"
-                      defaultValue="8"
+                      name={t("thickness")}
+                      defaultValue={String(formPlate.plate_thickness)}
                       validator={validatePlateThickness}
                       onValidationChange={handleValidationChange}
                       unit="mm"
-                      serverError={serverErrorFields.has("plateThickness")}
+                      serverErrors={serverErrorsFor("plateThickness")}
+                      locked={locked.has("plateThickness")}
+                      suggestion={suggestionFor("plateThickness")}
+                      onApplySuggestion={applySuggestion}
                     />
-                    <MaterialSelect
+                    <ValidatedSelect
                       forProp="material"
-                      name="Material"
-                      defaultValue="aluminum"
+                      name={t("material")}
+                      defaultValue={formPlate.material}
+                      validator={validateMaterial}
+                      options={MATERIALS}
+                      placeholder={t("selectMaterial")}
                       onValidationChange={handleValidationChange}
-                      serverError={serverErrorFields.has("material")}
+                      serverErrors={serverErrorsFor("material")}
+                      locked={locked.has("material")}
                     />
                   </FieldGroup>
 
-                  <FieldGroup title="Fasteners">
-                    <Combined
+                  <FieldGroup title={t("fasteners")}>
+                    <ValidatedNumberInput
                       forProp="boltSpacing"
-                      name="Bolt Spacing"
-                      defaultValue="60"
+                      name={t("boltSpacing")}
+                      defaultValue={String(formPlate.bolt_spacing)}
                       validator={validateBoltSpacing}
                       onValidationChange={handleValidationChange}
                       unit="mm"
-                      serverError={serverErrorFields.has("boltSpacing")}
+                      serverErrors={serverErrorsFor("boltSpacing")}
+                      locked={locked.has("boltSpacing")}
+                      suggestion={suggestionFor("boltSpacing")}
+                      onApplySuggestion={applySuggestion}
                     />
-                    <BoltSizeSelect
+                    <ValidatedSelect
                       forProp="boltSize"
-                      name="Bolt Size"
-                      defaultValue="M10"
+                      name={t("boltSize")}
+                      defaultValue={formPlate.bolt_size}
+                      validator={validateBoltSize}
+                      options={BOLT_SIZE_OPTIONS}
+                      placeholder={t("selectBoltSize")}
                       onValidationChange={handleValidationChange}
-                      serverError={serverErrorFields.has("boltSize")}
+                      serverErrors={serverErrorsFor("boltSize")}
+                      locked={locked.has("boltSize")}
                     />
                   </FieldGroup>
 
-                  <FieldGroup title="Pins">
-                    <Combined
+                  <FieldGroup title={t("pins")}>
+                    <ValidatedNumberInput
                       forProp="pinDiameter"
-                      name="Diameter"
-                      defaultValue="10"
+                      name={t("diameter")}
+                      defaultValue={String(formPlate.pin_diameter)}
                       validator={validatePinDiameter}
                       onValidationChange={handleValidationChange}
                       unit="mm"
-                      serverError={serverErrorFields.has("pinDiameter")}
+                      serverErrors={serverErrorsFor("pinDiameter")}
+                      locked={locked.has("pinDiameter")}
+                      suggestion={suggestionFor("pinDiameter")}
+                      onApplySuggestion={applySuggestion}
                     />
-                    <Combined
+                    <ValidatedNumberInput
                       forProp="pinCount"
-                      name="Count"
-                      defaultValue="6"
+                      name={t("count")}
+                      defaultValue={String(formPlate.pin_count)}
                       validator={validatePinCount}
                       onValidationChange={handleValidationChange}
-                      serverError={serverErrorFields.has("pinCount")}
+                      serverErrors={serverErrorsFor("pinCount")}
+                      locked={locked.has("pinCount")}
+                      suggestion={suggestionFor("pinCount")}
+                      onApplySuggestion={applySuggestion}
                     />
                   </FieldGroup>
 
-                  <FieldGroup title="Loading">
-                    <Combined
+                  <FieldGroup title={t("loading")}>
+                    <ValidatedNumberInput
                       forProp="expectedForce"
-                      name="Force per Pin"
-                      defaultValue="500"
-                      validator={(v) => validateExpectedForce(v)}
+                      name={t("forcePerPin")}
+                      defaultValue={String(formPlate.expected_force_per_pin)}
+                      validator={validateExpectedForce}
                       onValidationChange={handleValidationChange}
                       unit="N"
-                      serverError={serverErrorFields.has("expectedForce")}
+                      serverErrors={serverErrorsFor("expectedForce")}
+                      locked={locked.has("expectedForce")}
+                      suggestion={suggestionFor("expectedForce")}
+                      onApplySuggestion={applySuggestion}
                     />
-                    <div className="flex items-end">
-                      <p className="text-[10px] text-muted-foreground pb-2">
-                        2x safety factor applied
-                      </p>
+                    <div className="space-y-1.5">
+                      <Label
+                        htmlFor="load"
+                        className="text-xs font-medium uppercase tracking-wider text-muted-foreground"
+                      >
+                        {t("load")}
+                        <span className="ml-1 text-[10px] opacity-60">(N, {t("optional")})</span>
+                      </Label>
+                      <Input
+                        id="load"
+                        type="number"
+                        min={1}
+                        placeholder={t("checkBolts")}
+                        value={loadN}
+                        onChange={(e: ChangeEvent<HTMLInputElement>) => setLoadN(e.target.value)}
+                        aria-describedby="load-analysis"
+                        aria-invalid={(analysis?.warnings.length ?? 0) > 0 || undefined}
+                      />
                     </div>
                   </FieldGroup>
+                  <div
+                    id="load-analysis"
+                    aria-live="polite"
+                    className="text-[10px] text-muted-foreground -mt-3 space-y-0.5"
+                  >
+                    <p>{t("safetyFactorNote")}</p>
+                    {analysis && (
+                      <p>
+                        Bolt shear SF {analysis.bolt_shear.safety_factor.toFixed(1)} · plate bearing SF{" "}
+                        {analysis.plate_bearing.safety_factor.toFixed(1)}
+                      </p>
+                    )}
+                    {analysis?.warnings.map((warning) => (
+                      <p key={warning.code} className="text-destructive">
+                        {warning.message}
+                      </p>
+                    ))}
+                  </div>
 
                 </div>
               </CardContent>
               <div className="px-6 pb-6 pt-2 space-y-3 shrink-0">
+                    <p role="status" className="sr-only">
+                      {announcement}
+                    </p>
                     <Button
                       type="submit"
                       className="w-full h-11 text-sm font-semibold uppercase tracking-wider transition-all"
@@ -644,12 +1047,32 @@ export function App() {
                       )}
                     </Button>
 
+                    {jobProgress && (
+                      <div className="space-y-1">
+                        <div className="h-1.5 w-full rounded-full bg-muted overflow-hidden">
+                          <div
+                            className="h-full bg-primary transition-all duration-300"
+                            style={{ width: `${jobProgress.progress}%` }}
+                          />
+                        </div>
+                        <p className="text-[10px] text-muted-foreground text-center">
+                          {jobProgress.stage}
+                        </p>
+                      </div>
+                    )}
+
                     {!isFormValid && (
                       <p className="text-[10px] text-muted-foreground text-center">
                         Fix validation errors to continue
                       </p>
                     )}
 
+                    {generationWarnings.map((warning) => (
+                      <p key={warning.code} className="text-[10px] text-muted-foreground text-center">
+                        {warning.message}
+                      </p>
+                    ))}
+
                     {validationErrors.length > 0 && (
                       <div className="p-3 rounded-lg bg-destructive/10 border border-destructive/20 space-y-1.5">
                         <ul className="space-y-1">
@@ -712,6 +1135,20 @@ export function App() {
                           </svg>
                           Model generated successfully
                         </p>
+                        {massProperties && (
+                          <p className="text-xs text-muted-foreground mb-2">
+                            {formatMass(massProperties.mass_grams)} ·{" "}
+                            {(massProperties.volume_mm3 / 1000).toFixed(1)} cm³ · center of mass{" "}
+                            {[
+                              massProperties.center_of_mass.x_um,
+                              massProperties.center_of_mass.y_um,
+                              massProperties.center_of_mass.z_um,
+                            ]
+                              .map((um) => (um / 1000).toFixed(2))
+                              .join(", ")}{" "}
+                            mm
+                          </p>
+                        )}
                         <div className="flex w-full">
                           <Button
                             asChild
@@ -777,6 +1214,87 @@ export function App() {
                                   </a>
                                 </DropdownMenuItem>
                               )}
+                              {gltfUrl && (
+                                <DropdownMenuItem asChild>
+                                  <a
+                                    href={gltfUrl}
+                                    download="actuator_plate.gltf"
+                                    className="flex items-center gap-2 cursor-pointer"
+                                  >
+                                    Download glTF (.gltf)
+                                  </a>
+                                </DropdownMenuItem>
+                              )}
+                              {threeMfUrl && (
+                                <DropdownMenuItem asChild>
+                                  <a
+                                    href={threeMfUrl}
+                                    download="actuator_plate.3mf"
+                                    className="flex items-center gap-2 cursor-pointer"
+                                  >
+                                    For 3D printing (.3mf)
+                                  </a>
+                                </DropdownMenuItem>
+                              )}
+                              {objUrl && (
+                                <DropdownMenuItem asChild>
+                                  <a
+                                    href={objUrl}
+                                    download="actuator_plate.obj"
+                                    className="flex items-center gap-2 cursor-pointer"
+                                  >
+                                    Mesh (.obj)
+                                  </a>
+                                </DropdownMenuItem>
+                              )}
+                              {(pdfUrl || svgUrl || dxfUrl) && <DropdownMenuSeparator />}
+                              {pdfUrl && (
+                                <DropdownMenuItem asChild>
+                                  <a
+                                    href={pdfUrl}
+                                    download="actuator_plate.pdf"
+                                    className="flex items-center gap-2 cursor-pointer"
+                                  >
+                                    Drawing (.pdf)
+                                  </a>
+                                </DropdownMenuItem>
+                              )}
+                              {svgUrl && (
+                                <DropdownMenuItem asChild>
+                                  <a
+                                    href={svgUrl}
+                                    download="actuator_plate.svg"
+                                    className="flex items-center gap-2 cursor-pointer"
+                                  >
+                                    Drawing (.svg)
+                                  </a>
+                                </DropdownMenuItem>
+                              )}
+                              {dxfUrl && (
+                                <DropdownMenuItem asChild>
+                                  <a
+                                    href={dxfUrl}
+                                    download="actuator_plate.dxf"
+                                    className="flex items-center gap-2 cursor-pointer"
+                                  >
+                                    Cut profile (.dxf)
+                                  </a>
+                                </DropdownMenuItem>
+                              )}
+                              {kclUrl && (
+                                <>
+                                  <DropdownMenuSeparator />
+                                  <DropdownMenuItem asChild>
+                                    <a
+                                      href={kclUrl}
+                                      download="actuator_plate.kcl"
+                                      className="flex items-center gap-2 cursor-pointer"
+                                    >
+                                      KCL source (.kcl)
+                                    </a>
+                                  </DropdownMenuItem>
+                                </>
+                              )}
                               {stlUrl && (
                                 <>
                                   <DropdownMenuSeparator />
@@ -801,6 +1319,100 @@ export function App() {
                             </DropdownMenuContent>
                           </DropdownMenu>
                         </div>
+                        {cacheKey &&
+                          (emailedTo ? (
+                            <p className="mt-3 text-xs text-primary font-medium">
+                              The STEP file is on its way to {emailedTo}.
+                            </p>
+                          ) : (
+                            <div className="mt-3 flex gap-2">
+                              <Input
+                                type="email"
+                                placeholder="Email me the STEP file"
+                                value={stepEmail}
+                                onChange={(e: ChangeEvent<HTMLInputElement>) => setStepEmail(e.target.value)}
+                                className="h-8"
+                              />
+                              <Button
+                                type="button"
+                                size="sm"
+                                variant="outline"
+                                disabled={isEmailing || !stepEmail.trim()}
+                                onClick={handleEmailStep}
+                              >
+                                {isEmailing ? "Sending..." : "Send"}
+                              </Button>
+                            </div>
+                          ))}
+                        <div className="mt-3 flex items-end gap-3">
+                          <div className="space-y-1 w-20">
+                            <Label
+                              htmlFor="quoteQuantity"
+                              className="text-xs font-medium uppercase tracking-wider text-muted-foreground"
+                            >
+                              Quantity
+                            </Label>
+                            <Input
+                              id="quoteQuantity"
+                              type="number"
+                              min={1}
+                              value={quantity}
+                              onChange={(e: ChangeEvent<HTMLInputElement>) =>
+                                setQuantity(Math.floor(Number(e.target.value)))
+                              }
+                              className="h-8"
+                            />
+                          </div>
+                          {quote && (
+                            <div className="text-xs text-muted-foreground space-y-0.5">
+                              <p>
+                                <span className="font-medium text-foreground">
+                                  {formatPrice(quote.estimate.total_price_cents, quote.estimate.currency)}
+                                </span>{" "}
+                                ({formatPrice(quote.estimate.unit_price_cents, quote.estimate.currency)} each
+                                {quote.estimate.discount_percent > 0 &&
+                                  `, ${quote.estimate.discount_percent}% off`}
+                                )
+                              </p>
+                              <p>
+                                {formatMass(quote.estimate.mass_grams)} each · estimated ship date{" "}
+                                {quote.lead_time.ship_date}
+                              </p>
+                            </div>
+                          )}
+                        </div>
+                        {placedOrder ? (
+                          <p className="mt-3 text-xs text-primary font-medium">
+                            Order {placedOrder} received. A confirmation is on its way to {customerEmail}.
+                          </p>
+                        ) : (
+                          <div className="mt-3 space-y-2">
+                            <Input
+                              placeholder="Your name"
+                              value={customerName}
+                              onChange={(e: ChangeEvent<HTMLInputElement>) => setCustomerName(e.target.value)}
+                              className="h-8"
+                            />
+                            <Input
+                              type="email"
+                              placeholder="Email for the confirmation"
+                              value={customerEmail}
+                              onChange={(e: ChangeEvent<HTMLInputElement>) => setCustomerEmail(e.target.value)}
+                              className="h-8"
+                            />
+                            <Button
+                              type="button"
+                              size="sm"
+                              className="w-full"
+                              disabled={isOrdering || quantity < 1 || !customerName.trim() || !customerEmail.trim()}
+                              onClick={handlePlaceOrder}
+                            >
+                              {isOrdering
+                                ? "Placing order..."
+                                : `Order ${quantity} Plate${quantity === 1 ? "" : "s"}`}
+                            </Button>
+                          </div>
+                        )}
                       </div>
                     )}
 
@@ -812,11 +1424,13 @@ export function App() {
       </main>
 
       {/* Footer */}
-      <footer className="py-4 px-6 text-center">
-        <p className="text-[10px] text-muted-foreground/60 uppercase tracking-widest">
-          Made in PGH / {new Date().getFullYear()} / AMDG
-        </p>
-      </footer>
+      {!embed && (
+        <footer className="py-4 px-6 text-center">
+          <p className="text-[10px] text-muted-foreground/60 uppercase tracking-widest">
+            Made in PGH / {new Date().getFullYear()} / AMDG
+          </p>
+        </footer>
+      )}
     </div>
   );
 }
@@ -0,0 +1,291 @@
+import { useEffect, useState } from "react";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "./ui/card";
+import { Input } from "./ui/input";
+import { Label } from "./ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "./ui/select";
+import { ThemePicker } from "./ui/theme-picker";
+import {
+  BOLT_SIZES,
+  DEFAULT_PLATE,
+  MATERIALS,
+  type Plate,
+  type SavedConfig,
+} from "../lib/plate";
+
+// One row of the diff returned by POST /api/compare
+type FieldChange = {
+  field: string;
+  before: string;
+  after: string;
+  delta_mm?: number;
+};
+
+type PlateDiff = { changes: FieldChange[] };
+
+// Select value for "type the parameters in" rather than a saved config
+const CUSTOM = "custom";
+
+const NUMBER_FIELDS: { key: keyof Plate; label: string; unit: string }[] = [
+  { key: "bolt_spacing", label: "Bolt spacing", unit: "mm" },
+  { key: "bracket_height", label: "Bracket height", unit: "mm" },
+  { key: "bracket_width", label: "Bracket width", unit: "mm" },
+  { key: "pin_diameter", label: "Pin diameter", unit: "mm" },
+  { key: "pin_count", label: "Pin count", unit: "" },
+  { key: "plate_thickness", label: "Plate thickness", unit: "mm" },
+  { key: "expected_force_per_pin", label: "Force per pin", unit: "N" },
+];
+
+const LENGTH_FIELDS = NUMBER_FIELDS.filter((f) => f.unit === "mm");
+
+const formatDelta = (delta: number) => `${delta > 0 ? "+" : ""}${delta} mm`;
+
+function PlateSource({
+  title,
+  configs,
+  plate,
+  onChange,
+}: {
+  title: string;
+  configs: SavedConfig[];
+  plate: Plate;
+  onChange: (plate: Plate) => void;
+}) {
+  const [source, setSource] = useState(CUSTOM);
+
+  const choose = (value: string) => {
+    setSource(value);
+    const config = configs.find((c) => c.id === value);
+    if (config) onChange({ ...DEFAULT_PLATE, ...config.plate });
+  };
+
+  const set = (key: keyof Plate, value: string | number) => {
+    setSource(CUSTOM);
+    onChange({ ...plate, [key]: value });
+  };
+
+  return (
+    <Card className="flex-1">
+      <CardHeader>
+        <CardTitle className="text-sm">{title}</CardTitle>
+        <Select value={source} onValueChange={choose}>
+          <SelectTrigger className="w-full">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            <SelectItem value={CUSTOM}>Custom parameters</SelectItem>
+            {configs.map((config) => (
+              <SelectItem key={config.id} value={config.id}>
+                {config.name}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      </CardHeader>
+      <CardContent className="grid grid-cols-2 gap-3">
+        {NUMBER_FIELDS.map(({ key, label, unit }) => (
+          <div key={key} className="space-y-1.5">
+            <Label className="text-xs font-medium uppercase tracking-wider text-muted-foreground">
+              {label}
+              {unit && <span className="ml-1 text-[10px] opacity-60">({unit})</span>}
+            </Label>
+            <Input
+              type="number"
+              value={plate[key]}
+              onChange={(e) => set(key, Number(e.target.value))}
+            />
+          </div>
+        ))}
+        <div className="space-y-1.5">
+          <Label className="text-xs font-medium uppercase tracking-wider text-muted-foreground">
+            Bolt size
+          </Label>
+          <Select value={plate.bolt_size} onValueChange={(v) => set("bolt_size", v)}>
+            <SelectTrigger className="w-full">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {BOLT_SIZES.map((size) => (
+                <SelectItem key={size} value={size}>
+                  {size}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+        <div className="space-y-1.5">
+          <Label className="text-xs font-medium uppercase tracking-wider text-muted-foreground">
+            Material
+          </Label>
+          <Select value={plate.material} onValueChange={(v) => set("material", v)}>
+            <SelectTrigger className="w-full">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {MATERIALS.map((mat) => (
+                <SelectItem key={mat.value} value={mat.value}>
+                  {mat.label}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
+
+// Both plates' lengths as overlaid bars on a shared scale, so the size of each
+// change is visible at a glance
+function DimensionDeltas({ a, b }: { a: Plate; b: Plate }) {
+  return (
+    <div className="space-y-3">
+      {LENGTH_FIELDS.map(({ key, label }) => {
+        const before = Number(a[key]);
+        const after = Number(b[key]);
+        const scale = Math.max(before, after, 1);
+        const delta = Math.round((after - before) * 1000) / 1000;
+        return (
+          <div key={key} className="space-y-1">
+            <div className="flex justify-between text-xs">
+              <span className="text-muted-foreground">{label}</span>
+              <span className={delta === 0 ? "text-muted-foreground" : "font-medium text-primary"}>
+                {delta === 0 ? "same" : formatDelta(delta)}
+              </span>
+            </div>
+            <div className="relative h-3 rounded bg-muted/40">
+              <div
+                className="absolute inset-y-0 left-0 rounded bg-muted-foreground/40"
+                style={{ width: `${(before / scale) * 100}%` }}
+              />
+              <div
+                className="absolute inset-y-1 left-0 rounded bg-primary"
+                style={{ width: `${(after / scale) * 100}%` }}
+              />
+            </div>
+          </div>
+        );
+      })}
+    </div>
+  );
+}
+
+export function CompareView() {
+  const [configs, setConfigs] = useState<SavedConfig[]>([]);
+  const [a, setA] = useState<Plate>(DEFAULT_PLATE);
+  const [b, setB] = useState<Plate>(DEFAULT_PLATE);
+  const [diff, setDiff] = useState<PlateDiff | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    fetch("/api/configs")
+      .then((res) => (res.ok ? res.json() : []))
+      .then(setConfigs)
+      .catch(() => setConfigs([]));
+  }, []);
+
+  useEffect(() => {
+    const controller = new AbortController();
+    fetch("/api/compare", {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({ before: a, after: b }),
+      signal: controller.signal,
+    })
+      .then(async (res) => {
+        if (!res.ok) throw new Error(await res.text());
+        setDiff(await res.json());
+        setError(null);
+      })
+      .catch((e) => {
+        if (!controller.signal.aborted) setError(String(e.message ?? e));
+      });
+    return () => controller.abort();
+  }, [a, b]);
+
+  return (
+    <div className="min-h-screen w-full">
+      <header className="px-4 py-3 lg:px-6 lg:py-4">
+        <div className="flex items-center justify-between max-w-7xl mx-auto">
+          <div>
+            <h1 className="text-lg lg:text-xl font-bold tracking-tight">Compare plates</h1>
+            <a
+              href="/"
+              className="text-[10px] lg:text-xs text-muted-foreground uppercase tracking-wider hover:text-primary"
+            >
+              Back to the configurator
+            </a>
+          </div>
+          <ThemePicker />
+        </div>
+      </header>
+
+      <main className="max-w-7xl mx-auto px-4 lg:px-6 pb-8 space-y-6">
+        <div className="flex flex-col lg:flex-row gap-4">
+          <PlateSource title="Plate A" configs={configs} plate={a} onChange={setA} />
+          <PlateSource title="Plate B" configs={configs} plate={b} onChange={setB} />
+        </div>
+
+        <div className="flex flex-col lg:flex-row gap-4">
+          <Card className="flex-1">
+            <CardHeader>
+              <CardTitle className="text-sm">Differences</CardTitle>
+              <CardDescription>Fields that change going from A to B</CardDescription>
+            </CardHeader>
+            <CardContent>
+              {error ? (
+                <p className="text-xs text-destructive">{error}</p>
+              ) : !diff || diff.changes.length === 0 ? (
+                <p className="text-xs text-muted-foreground">The plates are identical.</p>
+              ) : (
+                <table className="w-full text-xs">
+                  <thead className="text-muted-foreground uppercase tracking-wider">
+                    <tr>
+                      <th className="text-left py-1">Field</th>
+                      <th className="text-right py-1">A</th>
+                      <th className="text-right py-1">B</th>
+                      <th className="text-right py-1">Change</th>
+                    </tr>
+                  </thead>
+                  <tbody>
+                    {diff.changes.map((change) => (
+                      <tr key={change.field} className="border-t border-border/50">
+                        <td className="py-1 font-mono">{change.field}</td>
+                        <td className="py-1 text-right">{change.before}</td>
+                        <td className="py-1 text-right">{change.after}</td>
+                        <td className="py-1 text-right font-medium">
+                          {change.delta_mm !== undefined && formatDelta(change.delta_mm)}
+                        </td>
+                      </tr>
+                    ))}
+                  </tbody>
+                </table>
+              )}
+            </CardContent>
+          </Card>
+
+          <Card className="flex-1">
+            <CardHeader>
+              <CardTitle className="text-sm">Dimensions</CardTitle>
+              <CardDescription>A in grey, B in color</CardDescription>
+            </CardHeader>
+            <CardContent>
+              <DimensionDeltas a={a} b={b} />
+            </CardContent>
+          </Card>
+        </div>
+      </main>
+    </div>
+  );
+}
@@ -0,0 +1,30 @@
+import { Button } from "./button";
+import { Label } from "./label";
+import { DEFAULT_PALETTE, PALETTE_FIELDS, useTheme } from "@/lib/theme";
+
+export function PaletteEditor() {
+  const { palette, setPalette } = useTheme();
+
+  return (
+    <div className="grid gap-3">
+      {PALETTE_FIELDS.map(({ key, label }) => (
+        <div key={key} className="flex items-center justify-between gap-4">
+          <Label htmlFor={`palette-${key}`}>{label}</Label>
+          <div className="flex items-center gap-2">
+            <span className="font-mono text-xs text-muted-foreground">{palette[key]}</span>
+            <input
+              id={`palette-${key}`}
+              type="color"
+              value={palette[key]}
+              onChange={(e) => setPalette({ ...palette, [key]: e.target.value })}
+              className="h-8 w-12 cursor-pointer rounded-md border border-border bg-transparent"
+            />
+          </div>
+        </div>
+      ))}
+      <Button variant="outline" size="sm" onClick={() => setPalette(DEFAULT_PALETTE)}>
+        Reset to defaults
+      </Button>
+    </div>
+  );
+}
@@ -1,5 +1,14 @@
-import { Moon, Sun, Monitor, Palette } from "lucide-react";
+import { Moon, Sun, Monitor, Palette, Pencil } from "lucide-react";
 import { Button } from "./button";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogHeader,
+  DialogTitle,
+  DialogTrigger,
+} from "./dialog";
+import { PaletteEditor } from "./palette-editor";
 import {
   Select,
   SelectContent,
@@ -21,7 +30,7 @@ const MODE_OPTIONS: { value: ThemeMode; label: string; icon: React.ReactNode }[]
 ];
 
 export function ThemePicker() {
-  const { colorScheme, mode, setColorScheme, setMode } = useTheme();
+  const { colorScheme, mode, palette, setColorScheme, setMode } = useTheme();
 
   return (
     <div className="flex items-center gap-1.5">
@@ -42,6 +51,8 @@ export function ThemePicker() {
                   style={{
                     background: scheme.name === "cyber"
                       ? "linear-gradient(135deg, oklch(0.75 0.18 195), oklch(0.65 0.2 330))"
+                      : scheme.name === "custom"
+                      ? `linear-gradient(135deg, ${palette.primary}, ${palette.accent})`
                       : scheme.name === "neutral"
                       ? "oklch(0.5 0 0)"
                       : scheme.name === "blue"
@@ -58,6 +69,23 @@ export function ThemePicker() {
         </SelectContent>
       </Select>
 
+      {colorScheme === "custom" && (
+        <Dialog>
+          <DialogTrigger asChild>
+            <Button variant="ghost" size="icon-sm" title="Edit colors" className="size-8 rounded-md">
+              <Pencil className="size-4" />
+            </Button>
+          </DialogTrigger>
+          <DialogContent className="max-w-xs">
+            <DialogHeader>
+              <DialogTitle>Custom colors</DialogTitle>
+              <DialogDescription>Saved in this browser.</DialogDescription>
+            </DialogHeader>
+            <PaletteEditor />
+          </DialogContent>
+        </Dialog>
+      )}
+
       <div className="flex items-center rounded-lg border border-border/40 bg-background/60 backdrop-blur-sm p-0.5">
         {MODE_OPTIONS.map((option) => (
           <Button
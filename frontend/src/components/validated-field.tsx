@@ -0,0 +1,226 @@
+/**
+ * Form fields that check their value as it's typed and explain what's wrong.
+ *
+ * Each field's messages (its own validation, errors the server returned for it,
+ * and any suggested value) sit in an element the input names in
+ * `aria-describedby`, and an invalid field sets `aria-invalid`, so a screen
+ * reader reads the problem along with the label.
+ */
+
+import { useCallback, useEffect, useState, type ChangeEvent, type ReactNode } from "react";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { t } from "@/lib/i18n";
+import type { Suggestion } from "@/lib/plate";
+import type { ValidationResult } from "@/lib/validation";
+
+type FieldProps = {
+  // Form field name, also the input's id
+  forProp: string;
+  name: string;
+  defaultValue: string;
+  onValidationChange?: (fieldName: string, isValid: boolean) => void;
+  // Messages from the server's last response that name this field
+  serverErrors?: string[];
+  // Fixed by the page embedding the form; still submitted with it
+  locked?: boolean;
+};
+
+function FieldLabel({ forProp, children }: { forProp: string; children: ReactNode }) {
+  return (
+    <Label
+      htmlFor={forProp}
+      className="text-xs font-medium uppercase tracking-wider text-muted-foreground"
+    >
+      {children}
+    </Label>
+  );
+}
+
+// Ids of the elements describing a field, for `aria-describedby`
+const errorId = (forProp: string) => `${forProp}-error`;
+const suggestionId = (forProp: string) => `${forProp}-suggestion`;
+
+function describedBy(forProp: string, hasErrors: boolean, hasSuggestion: boolean) {
+  const ids = [hasErrors && errorId(forProp), hasSuggestion && suggestionId(forProp)].filter(Boolean);
+  return ids.length > 0 ? ids.join(" ") : undefined;
+}
+
+function FieldErrors({ forProp, errors }: { forProp: string; errors: string[] }) {
+  if (errors.length === 0) return null;
+  return (
+    <div id={errorId(forProp)} className="space-y-0.5">
+      {errors.map((error) => (
+        <p key={error} className="text-[10px] text-destructive font-medium">
+          {error}
+        </p>
+      ))}
+    </div>
+  );
+}
+
+// Validate `value` once it has been touched, reporting each result to the form
+function useFieldValidation(
+  forProp: string,
+  value: string,
+  validate: (value: string) => Promise<ValidationResult>,
+  onValidationChange: FieldProps["onValidationChange"],
+  debounceMs: number,
+) {
+  const [result, setResult] = useState<ValidationResult>({ valid: true });
+  const [touched, setTouched] = useState(false);
+
+  const check = async () => {
+    const checked = await validate(value);
+    setResult(checked);
+    onValidationChange?.(forProp, checked.valid);
+  };
+
+  useEffect(() => {
+    if (!value || !touched) return;
+    const timeoutId = setTimeout(check, debounceMs);
+    return () => clearTimeout(timeoutId);
+  }, [value, validate, touched, forProp, onValidationChange]);
+
+  const errors = touched && !result.valid && result.error ? [result.error] : [];
+  return { errors, touched, setTouched, check };
+}
+
+export function ValidatedNumberInput({
+  forProp,
+  name,
+  defaultValue,
+  validator,
+  onValidationChange,
+  unit,
+  serverErrors = [],
+  locked,
+  suggestion,
+  onApplySuggestion,
+}: FieldProps & {
+  validator: (value: number) => Promise<ValidationResult>;
+  unit?: string;
+  suggestion?: Suggestion;
+  onApplySuggestion?: (suggestion: Suggestion) => void;
+}) {
+  const [value, setValue] = useState(defaultValue);
+  const validate = useCallback((v: string) => validator(Number(v)), [validator]);
+  const { errors, touched, setTouched, check } = useFieldValidation(
+    forProp,
+    value,
+    validate,
+    onValidationChange,
+    300,
+  );
+
+  const handleChange = (e: ChangeEvent<HTMLInputElement>) => {
+    setValue(e.target.value);
+    if (!touched) setTouched(true);
+  };
+
+  const handleBlur = () => {
+    setTouched(true);
+    if (value) check();
+  };
+
+  const allErrors = [...errors, ...serverErrors];
+  const showSuggestion = !!suggestion && !locked;
+
+  return (
+    <div className="space-y-1.5">
+      <FieldLabel forProp={forProp}>
+        {name}
+        {unit && <span className="ml-1 text-[10px] opacity-60">({unit})</span>}
+      </FieldLabel>
+      <Input
+        id={forProp}
+        type="number"
+        name={forProp}
+        value={value}
+        onChange={handleChange}
+        onBlur={handleBlur}
+        placeholder={defaultValue}
+        readOnly={locked}
+        aria-invalid={allErrors.length > 0 || undefined}
+        aria-describedby={describedBy(forProp, allErrors.length > 0, showSuggestion)}
+        className={locked ? "opacity-60 cursor-not-allowed" : ""}
+      />
+      <FieldErrors forProp={forProp} errors={allErrors} />
+      {showSuggestion && (
+        <button
+          id={suggestionId(forProp)}
+          type="button"
+          onClick={() => onApplySuggestion?.(suggestion)}
+          title={suggestion.reason}
+          className="text-[10px] text-muted-foreground hover:text-primary transition-colors"
+        >
+          {t("suggested")}: {suggestion.value}
+          {suggestion.unit && ` ${suggestion.unit}`}
+        </button>
+      )}
+    </div>
+  );
+}
+
+export function ValidatedSelect({
+  forProp,
+  name,
+  defaultValue,
+  validator,
+  onValidationChange,
+  serverErrors = [],
+  locked,
+  options,
+  placeholder,
+}: FieldProps & {
+  validator: (value: string) => Promise<ValidationResult>;
+  options: readonly { value: string; label: string }[];
+  placeholder: string;
+}) {
+  const [value, setValue] = useState(defaultValue);
+  const { errors, touched, setTouched } = useFieldValidation(forProp, value, validator, onValidationChange, 0);
+
+  const handleChange = (newValue: string) => {
+    setValue(newValue);
+    if (!touched) setTouched(true);
+  };
+
+  const allErrors = [...errors, ...serverErrors];
+
+  return (
+    <div className="space-y-1.5">
+      <FieldLabel forProp={forProp}>{name}</FieldLabel>
+      {/* A disabled select isn't submitted, so a locked one is carried by a hidden input */}
+      {locked && <input type="hidden" name={forProp} value={value} />}
+      <Select
+        name={locked ? undefined : forProp}
+        value={value}
+        onValueChange={handleChange}
+        disabled={locked}
+      >
+        <SelectTrigger
+          id={forProp}
+          aria-invalid={allErrors.length > 0 || undefined}
+          aria-describedby={describedBy(forProp, allErrors.length > 0, false)}
+        >
+          <SelectValue placeholder={placeholder} />
+        </SelectTrigger>
+        <SelectContent>
+          {options.map((option) => (
+            <SelectItem key={option.value} value={option.value}>
+              {option.label}
+            </SelectItem>
+          ))}
+        </SelectContent>
+      </Select>
+      <FieldErrors forProp={forProp} errors={allErrors} />
+    </div>
+  );
+}
@@ -9,12 +9,14 @@ import { StrictMode } from "react";
 import { createRoot } from "react-dom/client";
 import { ThemeProvider } from "@/lib/theme";
 import { App } from "./App";
+import { CompareView } from "./components/compare-view";
+import { isEmbedded } from "./lib/embed";
 
 const elem = document.getElementById("root")!;
 const app = (
   <StrictMode>
     <ThemeProvider>
-      <App />
+      {window.location.pathname === "/compare" ? <CompareView /> : <App embed={isEmbedded()} />}
     </ThemeProvider>
   </StrictMode>
 );
@@ -5,7 +5,8 @@ const API_URL = process.env.API_URL || "http://localhost:3030";
 
 const server = serve({
   routes: {
-    // Proxy all /api/* requests to Rust backend
+    // Proxy all /api/* requests to Rust backend. Progress WebSockets (/ws/*) aren't
+    // proxied; in development the app falls back to polling /api/jobs instead.
     "/api/*": async (req) => {
       const url = new URL(req.url);
       return fetch(`${API_URL}${url.pathname}`, {
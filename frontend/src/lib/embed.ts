@@ -0,0 +1,109 @@
+/**
+ * The configurator embedded in another page: `/embed` shows only the form and
+ * viewer, for an iframe on a host page.
+ *
+ * The query string takes a plate the same way a share link does, plus:
+ * - `lock=bd,m` to fix those fields at the given values, using the share link's
+ *   keys (see `share.ts`)
+ * - `origin=https://portal.example.com` to post messages only to that host, or
+ *   `origin=*` to post them to whichever page embeds the iframe
+ *
+ * Without a usable `origin` nothing is posted. Otherwise, after each successful
+ * generation the host window receives
+ * `{ type: "platerator:generated", plate, urls }`, where `urls` holds the
+ * absolute download URL of each format the server produced.
+ */
+
+import type { Plate } from "./plate";
+
+export const EMBED_PATH = "/embed";
+
+// Share link keys to the form fields they fill
+const FORM_FIELDS: Record<string, string> = {
+  bs: "boltSpacing",
+  bd: "boltSize",
+  bh: "bracketHeight",
+  bw: "bracketWidth",
+  m: "material",
+  pd: "pinDiameter",
+  pc: "pinCount",
+  pt: "plateThickness",
+  f: "expectedForce",
+};
+
+// Form fields to the plate fields they hold
+const PLATE_FIELDS: Record<string, keyof Plate> = {
+  boltSpacing: "bolt_spacing",
+  boltSize: "bolt_size",
+  bracketHeight: "bracket_height",
+  bracketWidth: "bracket_width",
+  material: "material",
+  pinDiameter: "pin_diameter",
+  pinCount: "pin_count",
+  plateThickness: "plate_thickness",
+  expectedForce: "expected_force_per_pin",
+};
+
+export type EmbedOptions = {
+  // Form fields the host fixed, by form field name
+  locked: Set<string>;
+  // Where messages go: a host's origin, or `*` when the host asked for any; `null`
+  // posts nothing
+  targetOrigin: string | null;
+};
+
+export const isEmbedded = () => window.location.pathname === EMBED_PATH;
+
+// Unknown keys in `lock` are ignored. An origin that doesn't parse posts nothing,
+// as does leaving it out: the plate and its download links only go to a host that
+// named itself, or that opted in to any host with `*`
+export function embedOptions(query: string): EmbedOptions {
+  const params = new URLSearchParams(query);
+  const locked = new Set(
+    (params.get("lock") ?? "")
+      .split(",")
+      .map((key) => FORM_FIELDS[key.trim()])
+      .filter((field): field is string => !!field),
+  );
+  return { locked, targetOrigin: parseOrigin(params.get("origin")) };
+}
+
+function parseOrigin(origin: string | null): string | null {
+  if (origin === "*") return "*";
+  if (!origin) return null;
+  try {
+    const parsed = new URL(origin).origin;
+    // Opaque origins such as data: URLs serialize as "null" and match no host
+    return parsed === "null" ? null : parsed;
+  } catch {
+    return null;
+  }
+}
+
+/** `plate` with its locked fields put back to their values in `fixed`. */
+export function keepLocked(plate: Plate, fixed: Plate, locked: Set<string>): Plate {
+  const kept = { ...plate };
+  for (const field of locked) {
+    const key = PLATE_FIELDS[field];
+    (kept as Record<keyof Plate, unknown>)[key] = fixed[key];
+  }
+  return kept;
+}
+
+/**
+ * Tell the host page about a generated plate. A no-op outside an iframe or
+ * without a target origin.
+ */
+export function postGenerated(
+  plate: Plate,
+  urls: Record<string, string | null | undefined>,
+  targetOrigin: string | null,
+) {
+  if (window.parent === window || targetOrigin === null) return;
+  const absolute = Object.fromEntries(
+    Object.entries(urls)
+      .filter((entry): entry is [string, string] => !!entry[1])
+      .map(([format, url]) => [format, new URL(url, window.location.origin).href]),
+  );
+  window.parent.postMessage({ type: "platerator:generated", plate, urls: absolute }, targetOrigin);
+}
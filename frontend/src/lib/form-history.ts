@@ -0,0 +1,87 @@
+/**
+ * Undo/redo history for a form's committed values.
+ *
+ * A value is committed when the user finishes editing a field (on blur), submits,
+ * or loads a preset or saved configuration. Undo and redo step between committed
+ * values; committing after an undo drops the values that could have been redone.
+ */
+
+import { useRef, useState } from "react";
+
+// Oldest values are dropped past this many undo steps
+const MAX_UNDO_STEPS = 100;
+
+export type FormHistory<T> = {
+  past: T[];
+  present: T;
+  future: T[];
+};
+
+const same = <T>(a: T, b: T) => JSON.stringify(a) === JSON.stringify(b);
+
+export function startHistory<T>(present: T): FormHistory<T> {
+  return { past: [], present, future: [] };
+}
+
+export function commitValue<T>(history: FormHistory<T>, value: T): FormHistory<T> {
+  if (same(history.present, value)) return history;
+  return {
+    past: [...history.past, history.present].slice(-MAX_UNDO_STEPS),
+    present: value,
+    future: [],
+  };
+}
+
+export function undoValue<T>(history: FormHistory<T>): FormHistory<T> {
+  const previous = history.past.at(-1);
+  if (previous === undefined) return history;
+  return {
+    past: history.past.slice(0, -1),
+    present: previous,
+    future: [history.present, ...history.future],
+  };
+}
+
+export function redoValue<T>(history: FormHistory<T>): FormHistory<T> {
+  const [next, ...future] = history.future;
+  if (next === undefined) return history;
+  return {
+    past: [...history.past, history.present],
+    present: next,
+    future,
+  };
+}
+
+/**
+ * Keep a {@link FormHistory} in React state.
+ *
+ * `undo` and `redo` take the form's current value, committing it first so an edit
+ * that hasn't been blurred yet isn't lost, and return the value to show, or `null`
+ * when there is nothing to step to.
+ */
+export function useFormHistory<T>(initial: T) {
+  const [history, setHistory] = useState(() => startHistory(initial));
+  // Read by undo/redo so several calls within one render see each other's updates
+  const latest = useRef(history);
+
+  const update = (next: FormHistory<T>) => {
+    latest.current = next;
+    setHistory(next);
+    return next;
+  };
+
+  const step = (current: T, move: (h: FormHistory<T>) => FormHistory<T>) => {
+    const committed = commitValue(latest.current, current);
+    const moved = move(committed);
+    update(moved);
+    return moved === committed ? null : moved.present;
+  };
+
+  return {
+    canUndo: history.past.length > 0,
+    canRedo: history.future.length > 0,
+    commit: (value: T) => update(commitValue(latest.current, value)),
+    undo: (current: T) => step(current, undoValue),
+    redo: (current: T) => step(current, redoValue),
+  };
+}
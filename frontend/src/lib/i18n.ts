@@ -0,0 +1,109 @@
+/**
+ * Form labels in English, Spanish, and German.
+ *
+ * The language comes from the browser. Validation messages are translated by the
+ * validation crate: the WASM module is told the same locale, and the API picks it
+ * up from the Accept-Language header the browser sends.
+ */
+
+export const LOCALES = ["en", "es", "de"] as const;
+
+export type Locale = (typeof LOCALES)[number];
+
+const LABELS = {
+  en: {
+    dimensions: "Dimensions",
+    fasteners: "Fasteners",
+    pins: "Pins",
+    loading: "Loading",
+    height: "Height",
+    width: "Width",
+    thickness: "Thickness",
+    material: "Material",
+    boltSpacing: "Bolt Spacing",
+    boltSize: "Bolt Size",
+    diameter: "Diameter",
+    count: "Count",
+    forcePerPin: "Force per Pin",
+    load: "Load",
+    optional: "optional",
+    suggested: "suggested",
+    checkBolts: "Check bolts",
+    selectBoltSize: "Select bolt size",
+    selectMaterial: "Select material",
+    safetyFactorNote: "2x safety factor applied to force per pin",
+  },
+  es: {
+    dimensions: "Dimensiones",
+    fasteners: "Fijaciones",
+    pins: "Pasadores",
+    loading: "Carga",
+    height: "Altura",
+    width: "Ancho",
+    thickness: "Espesor",
+    material: "Material",
+    boltSpacing: "Separación de pernos",
+    boltSize: "Tamaño de perno",
+    diameter: "Diámetro",
+    count: "Cantidad",
+    forcePerPin: "Fuerza por pasador",
+    load: "Carga",
+    optional: "opcional",
+    suggested: "sugerido",
+    checkBolts: "Comprobar pernos",
+    selectBoltSize: "Elija el tamaño de perno",
+    selectMaterial: "Elija el material",
+    safetyFactorNote: "Factor de seguridad de 2x aplicado a la fuerza por pasador",
+  },
+  de: {
+    dimensions: "Abmessungen",
+    fasteners: "Befestigung",
+    pins: "Stifte",
+    loading: "Belastung",
+    height: "Höhe",
+    width: "Breite",
+    thickness: "Dicke",
+    material: "Werkstoff",
+    boltSpacing: "Schraubenabstand",
+    boltSize: "Schraubengröße",
+    diameter: "Durchmesser",
+    count: "Anzahl",
+    forcePerPin: "Kraft pro Stift",
+    load: "Last",
+    optional: "optional",
+    suggested: "empfohlen",
+    checkBolts: "Schrauben prüfen",
+    selectBoltSize: "Schraubengröße wählen",
+    selectMaterial: "Werkstoff wählen",
+    safetyFactorNote: "Sicherheitsfaktor 2 auf die Kraft pro Stift angewendet",
+  },
+} satisfies Record<Locale, Record<string, string>>;
+
+export type LabelKey = keyof (typeof LABELS)["en"];
+
+/**
+ * The first supported language among `languages` (tags such as "es-MX"),
+ * or English when none is supported.
+ */
+export function detectLocale(languages: readonly string[]): Locale {
+  for (const tag of languages) {
+    const language = tag.split(/[-_]/)[0].toLowerCase();
+    const match = LOCALES.find((locale) => locale === language);
+    if (match) return match;
+  }
+  return "en";
+}
+
+/** The browser's preferred supported locale. */
+export const locale: Locale = detectLocale(
+  typeof navigator === "undefined"
+    ? []
+    : navigator.languages?.length
+      ? navigator.languages
+      : [navigator.language],
+);
+
+/** A form label in the browser's locale. */
+export function t(key: LabelKey): string {
+  return LABELS[locale][key];
+}
@@ -0,0 +1,67 @@
+/**
+ * Plate parameters as the API sends and accepts them.
+ */
+
+export const BOLT_SIZES = ["M3", "M4", "M5", "M6", "M8", "M10", "M12"] as const;
+
+export const MATERIALS = [
+  { value: "aluminum", label: "Aluminum 6061-T6" },
+  { value: "stainless_steel", label: "Stainless Steel 304" },
+  { value: "carbon_steel", label: "Carbon Steel" },
+  { value: "brass", label: "Brass" },
+] as const;
+
+export type Plate = {
+  bolt_spacing: number;
+  bolt_size: string;
+  bracket_height: number;
+  bracket_width: number;
+  material: string;
+  pin_diameter: number;
+  pin_count: number;
+  plate_thickness: number;
+  expected_force_per_pin: number;
+};
+
+export type SavedConfig = {
+  id: string;
+  name: string;
+  plate: Plate;
+  created_at: string;
+  updated_at: string;
+};
+
+// A recommended value for one field, from POST /api/suggestions; `key` is the plate property
+export type Suggestion = {
+  field: string;
+  key: keyof Plate;
+  value: number;
+  unit: string;
+  reason: string;
+};
+
+// A plate this browser generated, from /api/session/recent
+export type RecentPlate = {
+  plate: Plate;
+  cache_key: string;
+  submitted_at: string;
+};
+
+// One-line description of a plate for menus
+export function plateSummary(plate: Plate): string {
+  const material =
+    MATERIALS.find((m) => m.value === plate.material)?.label ?? plate.material;
+  return `${plate.bolt_spacing} mm ${plate.bolt_size}, ${plate.plate_thickness} mm ${material}`;
+}
+
+export const DEFAULT_PLATE: Plate = {
+  bolt_spacing: 60,
+  bolt_size: "M10",
+  bracket_height: 400,
+  bracket_width: 300,
+  material: "aluminum",
+  pin_diameter: 10,
+  pin_count: 6,
+  plate_thickness: 8,
+  expected_force_per_pin: 500,
+};
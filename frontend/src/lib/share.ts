@@ -0,0 +1,68 @@
+/**
+ * Shareable links: a plate's parameters as URL query parameters.
+ *
+ * Mirrors `crates/domain/src/share.rs`, which documents the format, so links made
+ * here restore the same plate through `ActuatorPlate::from_query` and back.
+ */
+
+import { BOLT_SIZES, DEFAULT_PLATE, MATERIALS, type Plate } from "./plate";
+
+type NumberField = Exclude<keyof Plate, "bolt_size" | "material">;
+
+const NUMBER_KEYS: [string, NumberField][] = [
+  ["bs", "bolt_spacing"],
+  ["bh", "bracket_height"],
+  ["bw", "bracket_width"],
+  ["pd", "pin_diameter"],
+  ["pc", "pin_count"],
+  ["pt", "plate_thickness"],
+  ["f", "expected_force_per_pin"],
+];
+
+export function plateToQuery(plate: Plate): string {
+  const params = new URLSearchParams();
+  params.set("bs", String(plate.bolt_spacing));
+  params.set("bd", plate.bolt_size.replace(/^M/, ""));
+  params.set("bh", String(plate.bracket_height));
+  params.set("bw", String(plate.bracket_width));
+  params.set("m", plate.material);
+  params.set("pd", String(plate.pin_diameter));
+  params.set("pc", String(plate.pin_count));
+  params.set("pt", String(plate.plate_thickness));
+  params.set("f", String(plate.expected_force_per_pin));
+  return params.toString();
+}
+
+/**
+ * Read a plate from a query string, or `null` when it has none of the plate's keys.
+ * Missing keys take their default and malformed values are ignored; the server
+ * validates the plate when it is generated.
+ */
+export function plateFromQuery(query: string): Plate | null {
+  const params = new URLSearchParams(query);
+  const plate = { ...DEFAULT_PLATE };
+  let found = false;
+
+  for (const [key, field] of NUMBER_KEYS) {
+    const value = Number(params.get(key) || NaN);
+    if (Number.isFinite(value)) {
+      plate[field] = value;
+      found = true;
+    }
+  }
+  const boltSize = `M${params.get("bd")}`;
+  if ((BOLT_SIZES as readonly string[]).includes(boltSize)) {
+    plate.bolt_size = boltSize;
+    found = true;
+  }
+  const material = params.get("m");
+  if (MATERIALS.some((m) => m.value === material)) {
+    plate.material = material!;
+    found = true;
+  }
+  return found ? plate : null;
+}
+
+export function shareUrl(plate: Plate): string {
+  return `${window.location.origin}/?${plateToQuery(plate)}`;
+}
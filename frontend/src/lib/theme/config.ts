@@ -11,12 +11,21 @@ export const COLOR_SCHEMES: ColorSchemeInfo[] = [
   { name: "green", displayName: "Green" },
   { name: "rose", displayName: "Rose" },
   { name: "cyber", displayName: "Cyber" },
+  { name: "custom", displayName: "Custom" },
 ];
 
 export const DEFAULT_COLOR_SCHEME: ColorScheme = "neutral";
 export const DEFAULT_MODE: ThemeMode = "system";
 
+// Also the names of the cookies the server reads to serve the page in the right theme
 export const STORAGE_KEYS = {
   colorScheme: "theme-color-scheme",
   mode: "theme-mode",
+  // localStorage only: the palette, and the properties it sets for the script in
+  // index.html to apply before the app loads
+  palette: "theme-palette",
+  paletteProperties: "theme-palette-properties",
 } as const;
+
+// A year, renewed whenever the theme changes
+export const COOKIE_MAX_AGE_SECONDS = 60 * 60 * 24 * 365;
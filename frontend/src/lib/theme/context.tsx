@@ -7,11 +7,19 @@ import {
   useState,
   useSyncExternalStore,
 } from "react";
-import type { ColorScheme, ThemeContextValue, ThemeMode } from "./types";
-import { DEFAULT_COLOR_SCHEME, DEFAULT_MODE, STORAGE_KEYS } from "./config";
+import type { ColorScheme, ThemeContextValue, ThemeMode, ThemePalette } from "./types";
+import { COOKIE_MAX_AGE_SECONDS, DEFAULT_COLOR_SCHEME, DEFAULT_MODE, STORAGE_KEYS } from "./config";
+import { DEFAULT_PALETTE, PALETTE_PROPERTIES, paletteProperties, parsePalette } from "./palette";
 
 const ThemeContext = createContext<ThemeContextValue | null>(null);
 
+// Stored in localStorage for the app and in a cookie for the server, which
+// renders the page in the same theme
+function persist(key: string, value: string) {
+  localStorage.setItem(key, value);
+  document.cookie = `${key}=${value}; path=/; max-age=${COOKIE_MAX_AGE_SECONDS}; samesite=lax`;
+}
+
 function useSystemPreference(): "light" | "dark" {
   const subscribe = useCallback((callback: () => void) => {
     const mediaQuery = window.matchMedia("(prefers-color-scheme: dark)");
@@ -53,6 +61,11 @@ export function ThemeProvider({
     return (stored as ThemeMode) || defaultMode;
   });
 
+  const [palette, setPaletteState] = useState<ThemePalette>(() => {
+    if (typeof window === "undefined") return DEFAULT_PALETTE;
+    return parsePalette(localStorage.getItem(STORAGE_KEYS.palette));
+  });
+
   const systemPreference = useSystemPreference();
   // Cyber theme forces dark mode
   const resolvedMode = colorScheme === "cyber" ? "dark" : (mode === "system" ? systemPreference : mode);
@@ -62,21 +75,39 @@ export function ThemeProvider({
 
     root.dataset.theme = colorScheme;
 
+    // The custom scheme is the neutral one with the palette's colors on top
+    if (colorScheme === "custom") {
+      const properties = paletteProperties(palette);
+      for (const [property, value] of Object.entries(properties)) {
+        root.style.setProperty(property, value);
+      }
+      localStorage.setItem(STORAGE_KEYS.paletteProperties, JSON.stringify(properties));
+    } else {
+      for (const property of PALETTE_PROPERTIES) {
+        root.style.removeProperty(property);
+      }
+    }
+
     if (resolvedMode === "dark") {
       root.classList.add("dark");
     } else {
       root.classList.remove("dark");
     }
-  }, [colorScheme, resolvedMode]);
+  }, [colorScheme, resolvedMode, palette]);
 
   const setColorScheme = useCallback((scheme: ColorScheme) => {
     setColorSchemeState(scheme);
-    localStorage.setItem(STORAGE_KEYS.colorScheme, scheme);
+    persist(STORAGE_KEYS.colorScheme, scheme);
   }, []);
 
   const setMode = useCallback((newMode: ThemeMode) => {
     setModeState(newMode);
-    localStorage.setItem(STORAGE_KEYS.mode, newMode);
+    persist(STORAGE_KEYS.mode, newMode);
+  }, []);
+
+  const setPalette = useCallback((newPalette: ThemePalette) => {
+    setPaletteState(newPalette);
+    localStorage.setItem(STORAGE_KEYS.palette, JSON.stringify(newPalette));
   }, []);
 
   const value = useMemo<ThemeContextValue>(
@@ -84,10 +115,12 @@ export function ThemeProvider({
       colorScheme,
       mode,
       resolvedMode,
+      palette,
       setColorScheme,
       setMode,
+      setPalette,
     }),
-    [colorScheme, mode, resolvedMode, setColorScheme, setMode]
+    [colorScheme, mode, resolvedMode, palette, setColorScheme, setMode, setPalette]
   );
 
   return (
@@ -1,3 +1,4 @@
 export { ThemeProvider, useTheme } from "./context";
 export { COLOR_SCHEMES, DEFAULT_COLOR_SCHEME, DEFAULT_MODE } from "./config";
-export type { ColorScheme, ThemeMode, ThemeContextValue } from "./types";
+export { DEFAULT_PALETTE, PALETTE_FIELDS } from "./palette";
+export type { ColorScheme, ThemeMode, ThemeContextValue, ThemePalette } from "./types";
@@ -0,0 +1,66 @@
+import type { ThemePalette } from "./types";
+
+export const DEFAULT_PALETTE: ThemePalette = {
+  primary: "#2563eb",
+  secondary: "#e2e8f0",
+  accent: "#f59e0b",
+};
+
+export const PALETTE_FIELDS: { key: keyof ThemePalette; label: string }[] = [
+  { key: "primary", label: "Primary" },
+  { key: "secondary", label: "Secondary" },
+  { key: "accent", label: "Accent" },
+];
+
+function isHexColor(value: unknown): value is string {
+  return typeof value === "string" && /^#[0-9a-f]{6}$/i.test(value);
+}
+
+// A stored palette, falling back to the default for anything missing or malformed
+export function parsePalette(stored: string | null): ThemePalette {
+  let parsed: Partial<Record<keyof ThemePalette, unknown>> = {};
+  try {
+    parsed = stored ? JSON.parse(stored) : {};
+  } catch {
+    // Keep the defaults
+  }
+  const palette = { ...DEFAULT_PALETTE };
+  for (const { key } of PALETTE_FIELDS) {
+    const value = parsed?.[key];
+    if (isHexColor(value)) palette[key] = value;
+  }
+  return palette;
+}
+
+// Near-black or near-white, whichever contrasts more with `hex` (WCAG relative luminance)
+function foregroundFor(hex: string): string {
+  const [r, g, b] = [1, 3, 5].map((i) => {
+    const channel = parseInt(hex.slice(i, i + 2), 16) / 255;
+    return channel <= 0.03928 ? channel / 12.92 : ((channel + 0.055) / 1.055) ** 2.4;
+  }) as [number, number, number];
+  const luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+  return luminance > 0.179 ? "oklch(0.145 0 0)" : "oklch(0.985 0 0)";
+}
+
+// CSS custom properties the palette sets over the neutral theme, light or dark
+export function paletteProperties(palette: ThemePalette): Record<string, string> {
+  const { primary, secondary, accent } = palette;
+  return {
+    "--primary": primary,
+    "--primary-foreground": foregroundFor(primary),
+    "--ring": primary,
+    "--chart-1": primary,
+    "--sidebar-primary": primary,
+    "--sidebar-primary-foreground": foregroundFor(primary),
+    "--sidebar-ring": primary,
+    "--secondary": secondary,
+    "--secondary-foreground": foregroundFor(secondary),
+    "--accent": accent,
+    "--accent-foreground": foregroundFor(accent),
+    "--chart-2": accent,
+    "--sidebar-accent": accent,
+    "--sidebar-accent-foreground": foregroundFor(accent),
+  };
+}
+
+export const PALETTE_PROPERTIES = Object.keys(paletteProperties(DEFAULT_PALETTE));
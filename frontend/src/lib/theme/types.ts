@@ -1,10 +1,19 @@
-export type ColorScheme = "neutral" | "blue" | "green" | "rose" | "cyber";
+export type ColorScheme = "neutral" | "blue" | "green" | "rose" | "cyber" | "custom";
 export type ThemeMode = "light" | "dark" | "system";
 
+// Brand colors for the custom scheme, as #rrggbb
+export interface ThemePalette {
+  primary: string;
+  secondary: string;
+  accent: string;
+}
+
 export interface ThemeContextValue {
   colorScheme: ColorScheme;
   mode: ThemeMode;
   resolvedMode: "light" | "dark";
+  palette: ThemePalette;
   setColorScheme: (scheme: ColorScheme) => void;
   setMode: (mode: ThemeMode) => void;
+  setPalette: (palette: ThemePalette) => void;
 }
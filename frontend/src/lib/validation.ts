@@ -16,7 +16,9 @@ import init, {
   wasm_validate_expected_force,
   wasm_validate_stress,
   wasm_minimum_thickness,
+  wasm_set_locale,
 } from '../wasm-validation/validation.js';
+import { locale } from './i18n';
 
 // Initialize WASM module on first import
 let wasmInitialized = false;
@@ -42,6 +44,7 @@ export async function initValidation(): Promise<void> {
       // In production: /wasm-validation/validation_bg.wasm
       const wasmUrl = '/wasm-validation/validation_bg.wasm';
       await init(wasmUrl);
+      wasm_set_locale(locale);
       wasmInitialized = true;
     } catch (error) {
       console.error('Failed to initialize WASM validation module:', error);
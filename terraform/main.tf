@@ -35,6 +35,7 @@ module "s3" {
     transition_to_glacier_days = 90
     expiration_days            = 365
   }
+  cache_ttl_days = var.cache_ttl_days
 }
 
 # DynamoDB table for caching plate configurations
@@ -87,7 +88,7 @@ module "lightsail" {
   instance_name     = local.project_slug
   availability_zone = "${var.aws_region}a"
   blueprint_id      = "ubuntu_22_04"
-  bundle_id         = "nano_3_0"  # $3.50/month
+  bundle_id         = "nano_3_0" # $3.50/month
 
   s3_bucket_name = module.s3.bucket_name
   dynamodb_table = module.dynamodb.table_name
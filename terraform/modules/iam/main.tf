@@ -19,10 +19,16 @@ resource "aws_iam_user_policy" "s3_access" {
       Effect = "Allow"
       Action = [
         "s3:PutObject",
+        "s3:PutObjectTagging",
         "s3:GetObject",
-        "s3:HeadObject"
+        "s3:HeadObject",
+        "s3:DeleteObject"
       ]
       Resource = ["${var.s3_bucket_arn}/*"]
+      }, {
+      Effect   = "Allow"
+      Action   = ["s3:ListBucket"]
+      Resource = [var.s3_bucket_arn]
     }]
   })
 }
@@ -38,7 +44,8 @@ resource "aws_iam_user_policy" "dynamodb_access" {
       Effect = "Allow"
       Action = [
         "dynamodb:GetItem",
-        "dynamodb:PutItem"
+        "dynamodb:PutItem",
+        "dynamodb:DeleteItem"
       ]
       Resource = [var.dynamodb_table_arn]
     }]
@@ -47,6 +47,8 @@ Environment="KCL_SRC_DIR=/opt/platerator/kcl"
 Environment="AWS_REGION=${aws_region}"
 Environment="S3_BUCKET_NAME=${s3_bucket_name}"
 Environment="DYNAMODB_TABLE=${dynamodb_table}"
+# Caddy proxies every request, so rate limit on the client address it forwards
+Environment="RATE_LIMIT_TRUST_PROXY=true"
 EnvironmentFile=-/opt/platerator/.env
 ExecStart=/opt/platerator/web
 Restart=always
@@ -39,6 +39,25 @@ resource "aws_s3_bucket_lifecycle_configuration" "generated_files" {
       days = var.lifecycle_rules.expiration_days
     }
   }
+
+  dynamic "rule" {
+    for_each = var.cache_ttl_days == null ? [] : [var.cache_ttl_days]
+    content {
+      id     = "expire-cache-entries"
+      status = "Enabled"
+
+      filter {
+        tag {
+          key   = "cache-ttl-days"
+          value = tostring(rule.value)
+        }
+      }
+
+      expiration {
+        days = rule.value
+      }
+    }
+  }
 }
 
 resource "aws_s3_bucket_public_access_block" "generated_files" {
@@ -20,3 +20,9 @@ variable "lifecycle_rules" {
     expiration_days            = 365
   }
 }
+
+variable "cache_ttl_days" {
+  description = "Expire cache objects tagged cache-ttl-days=N after N days; match CACHE_TTL_SECS. Null disables."
+  type        = number
+  default     = null
+}
@@ -63,3 +63,9 @@ variable "ssh_public_key_path" {
   type        = string
   default     = "~/.ssh/id_ed25519.pub"
 }
+
+variable "cache_ttl_days" {
+  description = "Days before cached models expire from S3 (set CACHE_TTL_SECS to match). Null keeps them until the bucket's general expiration."
+  type        = number
+  default     = null
+}